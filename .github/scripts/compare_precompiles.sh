@@ -0,0 +1,6 @@
+#!/bin/bash
+
+# This script should be run on the target branch, after saving the precompile benchmarks of the main branch as the `main` baseline.
+
+# If any precompile is more than 10% slower than on the main branch, exits in error.
+cargo bench --package reth-executor --bench precompiles -- --baseline main --noise-threshold 0.10 | tee /dev/tty | awk '/Performance has regressed/{f=1} END{exit f}'
@@ -1,10 +1,11 @@
 //! clap [Args](clap::Args) for network related arguments.
 
-use crate::dirs::{KnownPeersPath, PlatformPath};
+use crate::dirs::{BannedPeersPath, KnownPeersPath, PlatformPath};
 use clap::Args;
 use reth_discv4::bootnodes::mainnet_nodes;
+use reth_dns_discovery::{tree::LinkEntry, DnsDiscoveryConfig};
 use reth_net_nat::NatResolver;
-use reth_network::NetworkConfigBuilder;
+use reth_network::{NetworkConfigBuilder, SyncBandwidthConfig};
 use reth_primitives::{ChainSpec, NodeRecord};
 use reth_staged_sync::Config;
 use std::{path::PathBuf, sync::Arc};
@@ -39,12 +40,26 @@ pub struct NetworkArgs {
     pub peers_file: PlatformPath<KnownPeersPath>,
 
     /// Do not persist peers. Cannot be used with --peers-file
-    #[arg(long, verbatim_doc_comment, conflicts_with = "peers_file")]
+    /// or --ban-list-file
+    #[arg(long, verbatim_doc_comment, conflicts_with_all = ["peers_file", "ban_list_file"])]
     pub no_persist_peers: bool,
 
+    /// The path to the banned peers file. Temporary bans of
+    /// misbehaving peers are dumped to this file on node shutdown,
+    /// and restored on startup.
+    /// Cannot be used with --no-persist-peers
+    #[arg(long, value_name = "FILE", verbatim_doc_comment, default_value_t)]
+    pub ban_list_file: PlatformPath<BannedPeersPath>,
+
     /// NAT resolution method.
     #[arg(long, default_value = "any")]
     pub nat: NatResolver,
+
+    /// The max bandwidth of outgoing sync requests in megabits per second.
+    ///
+    /// Throttles how fast header and body requests are dispatched to peers. Unlimited if not set.
+    #[arg(long = "net.max-sync-bandwidth-mbps", value_name = "MBPS")]
+    pub max_sync_bandwidth_mbps: Option<u64>,
 }
 
 impl NetworkArgs {
@@ -55,11 +70,16 @@ impl NetworkArgs {
         config: &Config,
         chain_spec: Arc<ChainSpec>,
     ) -> NetworkConfigBuilder {
-        let network_config_builder = config
-            .network_config(self.nat, self.persistent_peers_file())
+        let mut network_config_builder = config
+            .network_config(self.nat, self.persistent_peers_file(), self.persistent_ban_list_file())
             .boot_nodes(self.bootnodes.clone().unwrap_or_else(mainnet_nodes))
             .chain_spec(chain_spec);
 
+        if let Some(mbps) = self.max_sync_bandwidth_mbps {
+            network_config_builder =
+                network_config_builder.sync_bandwidth_config(SyncBandwidthConfig::from_mbps(mbps));
+        }
+
         self.discovery.apply_to_builder(network_config_builder)
     }
 }
@@ -74,6 +94,14 @@ impl NetworkArgs {
         }
         Some(self.peers_file.clone().into())
     }
+
+    /// If `no_persist_peers` is true then this returns the path to the persistent ban list file
+    pub fn persistent_ban_list_file(&self) -> Option<PathBuf> {
+        if self.no_persist_peers {
+            return None
+        }
+        Some(self.ban_list_file.clone().into())
+    }
 }
 
 /// Arguments to setup discovery
@@ -91,6 +119,19 @@ pub struct DiscoveryArgs {
     #[arg(long, conflicts_with = "disable_discovery")]
     disable_discv4_discovery: bool,
 
+    /// EIP-1459 DNS trees to discover peers from.
+    /// --dns-discovery-urls
+    /// enrtree://AKA3AM6LPBYEUDMVNU3BSVQJ5AD45Y7YPOHJLEF6W26QOE4VTUDPE@all.mainnet.ethdisco.net
+    ///
+    /// Will fall back to a network-specific default if not specified.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        verbatim_doc_comment,
+        conflicts_with_all = ["disable_discovery", "disable_dns_discovery"]
+    )]
+    dns_discovery_urls: Option<Vec<LinkEntry>>,
+
     /// The UDP port to use for P2P discovery/networking.
     #[arg(long = "discovery.port")]
     pub port: Option<u16>,
@@ -104,6 +145,11 @@ impl DiscoveryArgs {
     ) -> NetworkConfigBuilder {
         if self.disable_discovery || self.disable_dns_discovery {
             network_config_builder = network_config_builder.disable_dns_discovery();
+        } else if let Some(urls) = &self.dns_discovery_urls {
+            network_config_builder = network_config_builder.dns_discovery(DnsDiscoveryConfig {
+                bootstrap_dns_networks: Some(urls.iter().cloned().collect()),
+                ..Default::default()
+            });
         }
 
         if self.disable_discovery || self.disable_discv4_discovery {
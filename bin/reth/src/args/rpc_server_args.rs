@@ -5,10 +5,10 @@ use clap::Args;
 use jsonrpsee::{core::Error as RpcError, server::ServerHandle};
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{BlockProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory};
-use reth_rpc::{JwtError, JwtSecret};
+use reth_rpc::{AtomicJwtSecretProvider, JwtError, JwtSecret};
 use reth_rpc_builder::{
-    constants, IpcServerBuilder, RethRpcModule, RpcModuleSelection, RpcServerConfig,
-    RpcServerHandle, ServerBuilder, TransportRpcModuleConfig,
+    constants, IpcServerBuilder, RethRpcModule, RpcModuleConfig, RpcModuleSelection,
+    RpcServerConfig, RpcServerHandle, ServerBuilder, TransportRpcModuleConfig,
 };
 use reth_rpc_engine_api::EngineApiHandle;
 use reth_tasks::TaskSpawner;
@@ -77,6 +77,11 @@ pub struct RpcServerArgs {
     /// Path to a JWT secret to use for authenticated RPC endpoints
     #[arg(long = "authrpc.jwtsecret", value_name = "PATH", global = true, required = false)]
     auth_jwtsecret: Option<PlatformPath<JwtSecretPath>>,
+
+    /// Serve non-standard methods that are still experimental, like
+    /// `reth_net_peerCountByProtocol` and `reth_dryRunTransaction`
+    #[arg(long = "rpc.experimental-methods")]
+    pub experimental_methods: bool,
 }
 
 impl RpcServerArgs {
@@ -142,6 +147,7 @@ impl RpcServerArgs {
         network: Network,
         executor: Tasks,
         handle: EngineApiHandle,
+        jwt_secret: AtomicJwtSecretProvider,
     ) -> Result<ServerHandle, RpcError>
     where
         Client: BlockProvider
@@ -159,7 +165,6 @@ impl RpcServerArgs {
             self.auth_addr.unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)),
             self.auth_port.unwrap_or(constants::DEFAULT_AUTH_PORT),
         );
-        let secret = self.jwt_secret().map_err(|err| RpcError::Custom(err.to_string()))?;
         reth_rpc_builder::auth::launch(
             client,
             pool,
@@ -167,14 +172,16 @@ impl RpcServerArgs {
             executor,
             handle,
             socket_address,
-            secret,
+            jwt_secret,
         )
         .await
     }
 
     /// Creates the [TransportRpcModuleConfig] from cli args.
     fn transport_rpc_module_config(&self) -> TransportRpcModuleConfig {
-        let mut config = TransportRpcModuleConfig::default();
+        let mut config = TransportRpcModuleConfig::default().with_config(
+            RpcModuleConfig::builder().experimental_methods(self.experimental_methods).build(),
+        );
         let rpc_modules =
             RpcModuleSelection::Selection(vec![RethRpcModule::Admin, RethRpcModule::Eth]);
         if self.http {
@@ -0,0 +1,248 @@
+//! Writer for the [ERA1](https://github.com/ethereum/go-ethereum/blob/master/internal/era/era.go)
+//! block archive format.
+//!
+//! An ERA1 file is an [e2store](https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md)
+//! file that contains up to [`MAX_ERA1_BLOCKS`] consecutive blocks:
+//!
+//! ```text
+//! era1 := Version | block-tuple* | Accumulator | BlockIndex
+//! block-tuple := CompressedHeader | CompressedBody | CompressedReceipts | TotalDifficulty
+//! ```
+
+use reth_primitives::{Block, BlockNumber, Receipt, H256, U256};
+use reth_rlp::{Encodable, Header as RlpHeader};
+use sha2::{Digest, Sha256};
+use std::io::{self, Write};
+
+/// The maximum number of blocks in an ERA1 file.
+pub(crate) const MAX_ERA1_BLOCKS: usize = 8192;
+
+/// The entry types of an ERA1 file.
+const VERSION: u16 = 0x3265;
+const COMPRESSED_HEADER: u16 = 0x03;
+const COMPRESSED_BODY: u16 = 0x04;
+const COMPRESSED_RECEIPTS: u16 = 0x05;
+const TOTAL_DIFFICULTY: u16 = 0x06;
+const ACCUMULATOR: u16 = 0x07;
+const BLOCK_INDEX: u16 = 0x3266;
+
+/// Writes blocks to an ERA1 file.
+///
+/// The file is only valid after [`Era1Writer::finish`] wrote the accumulator and the block index.
+#[derive(Debug)]
+pub(crate) struct Era1Writer<W> {
+    /// The underlying writer.
+    writer: W,
+    /// The number of bytes written so far.
+    written: u64,
+    /// The number of the first block.
+    starting_number: Option<BlockNumber>,
+    /// The offsets of the block tuples in the file.
+    offsets: Vec<u64>,
+    /// The SSZ roots of the header records of the written blocks.
+    header_records: Vec<H256>,
+}
+
+impl<W: Write> Era1Writer<W> {
+    /// Creates a new writer and writes the version entry.
+    pub(crate) fn new(writer: W) -> io::Result<Self> {
+        let mut this = Self {
+            writer,
+            written: 0,
+            starting_number: None,
+            offsets: Vec::new(),
+            header_records: Vec::new(),
+        };
+        this.write_entry(VERSION, &[])?;
+        Ok(this)
+    }
+
+    /// Returns the number of blocks written.
+    pub(crate) fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Writes the next block with its receipts and the total difficulty up to and including the
+    /// block.
+    pub(crate) fn write_block(
+        &mut self,
+        block: &Block,
+        receipts: &[Receipt],
+        total_difficulty: U256,
+    ) -> io::Result<()> {
+        if self.len() >= MAX_ERA1_BLOCKS {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("an ERA1 file holds at most {MAX_ERA1_BLOCKS} blocks"),
+            ))
+        }
+        self.starting_number.get_or_insert(block.header.number);
+        self.offsets.push(self.written);
+
+        let mut header = Vec::new();
+        block.header.encode(&mut header);
+        self.write_entry(COMPRESSED_HEADER, &compress(&header)?)?;
+
+        let mut body = Vec::new();
+        encode_body(block, &mut body);
+        self.write_entry(COMPRESSED_BODY, &compress(&body)?)?;
+
+        let mut encoded_receipts = Vec::new();
+        reth_rlp::encode_list::<Receipt, _>(receipts, &mut encoded_receipts);
+        self.write_entry(COMPRESSED_RECEIPTS, &compress(&encoded_receipts)?)?;
+
+        let total_difficulty = total_difficulty.to_le_bytes::<32>();
+        self.write_entry(TOTAL_DIFFICULTY, &total_difficulty)?;
+
+        let block_hash = block.header.hash_slow();
+        self.header_records.push(sha256(block_hash.as_bytes(), &total_difficulty));
+        Ok(())
+    }
+
+    /// Writes the accumulator and the block index, and flushes the writer.
+    pub(crate) fn finish(mut self) -> io::Result<W> {
+        let root = accumulator_root(&self.header_records);
+        self.write_entry(ACCUMULATOR, root.as_bytes())?;
+
+        // the offsets are relative to the start of the block index entry
+        let index_offset = self.written as i64;
+        let mut index = Vec::with_capacity(8 * (self.offsets.len() + 2));
+        index.extend_from_slice(&self.starting_number.unwrap_or_default().to_le_bytes());
+        for offset in &self.offsets {
+            index.extend_from_slice(&(*offset as i64 - index_offset).to_le_bytes());
+        }
+        index.extend_from_slice(&(self.offsets.len() as u64).to_le_bytes());
+        self.write_entry(BLOCK_INDEX, &index)?;
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Writes an e2store entry: `type (2 bytes) | length (4 bytes) | reserved (2 bytes) | data`.
+    fn write_entry(&mut self, ty: u16, data: &[u8]) -> io::Result<()> {
+        let len = u32::try_from(data.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "e2store entry too large"))?;
+        self.writer.write_all(&ty.to_le_bytes())?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(&[0, 0])?;
+        self.writer.write_all(data)?;
+        self.written += 8 + data.len() as u64;
+        Ok(())
+    }
+}
+
+/// Encodes the body of the block as `rlp([transactions, ommers, withdrawals?])`.
+fn encode_body(block: &Block, out: &mut Vec<u8>) {
+    let payload_length = block.body.length() +
+        block.ommers.length() +
+        block.withdrawals.as_ref().map_or(0, |withdrawals| withdrawals.length());
+    RlpHeader { list: true, payload_length }.encode(out);
+    block.body.encode(out);
+    block.ommers.encode(out);
+    if let Some(withdrawals) = &block.withdrawals {
+        withdrawals.encode(out);
+    }
+}
+
+/// Compresses the data with the snappy framing format.
+fn compress(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut compressed = Vec::new();
+    let mut encoder = snap::write::FrameEncoder::new(&mut compressed);
+    encoder.write_all(data)?;
+    encoder.flush()?;
+    drop(encoder);
+    Ok(compressed)
+}
+
+/// Computes the SSZ `hash_tree_root` of the `List[HeaderRecord, MAX_ERA1_BLOCKS]` with the given
+/// header record roots.
+fn accumulator_root(header_records: &[H256]) -> H256 {
+    let mut layer = header_records.to_vec();
+    let mut zero_hash = H256::zero();
+    // merkleize the records, padded with zero hashes to the list limit
+    for _ in 0..MAX_ERA1_BLOCKS.trailing_zeros() {
+        if layer.len() % 2 == 1 {
+            layer.push(zero_hash);
+        }
+        layer =
+            layer.chunks(2).map(|pair| sha256(pair[0].as_bytes(), pair[1].as_bytes())).collect();
+        zero_hash = sha256(zero_hash.as_bytes(), zero_hash.as_bytes());
+    }
+    let root = layer.first().copied().unwrap_or(zero_hash);
+
+    // mix in the length of the list
+    let mut length = [0u8; 32];
+    length[..8].copy_from_slice(&(header_records.len() as u64).to_le_bytes());
+    sha256(root.as_bytes(), &length)
+}
+
+/// Returns the sha256 hash of the concatenation of the two chunks.
+fn sha256(left: &[u8], right: &[u8]) -> H256 {
+    H256::from_slice(&Sha256::new().chain_update(left).chain_update(right).finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Header;
+    use std::io::Read;
+
+    /// Reads the e2store entry at the given offset.
+    fn read_entry(file: &[u8], offset: usize) -> (u16, &[u8]) {
+        let ty = u16::from_le_bytes(file[offset..offset + 2].try_into().unwrap());
+        let len = u32::from_le_bytes(file[offset + 2..offset + 6].try_into().unwrap()) as usize;
+        (ty, &file[offset + 8..offset + 8 + len])
+    }
+
+    #[test]
+    fn write_era1_file() {
+        let blocks = (100..103)
+            .map(|number| Block {
+                header: Header { number, ..Default::default() },
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in &blocks {
+            writer.write_block(block, &[], U256::from(block.header.number)).unwrap();
+        }
+        let file = writer.finish().unwrap();
+
+        assert_eq!(read_entry(&file, 0), (VERSION, &[][..]));
+
+        // the block index is the last entry
+        let index_len = 8 * (blocks.len() + 2);
+        let index_offset = file.len() - 8 - index_len;
+        let (ty, index) = read_entry(&file, index_offset);
+        assert_eq!(ty, BLOCK_INDEX);
+        assert_eq!(u64::from_le_bytes(index[..8].try_into().unwrap()), 100);
+        assert_eq!(u64::from_le_bytes(index[index_len - 8..].try_into().unwrap()), 3);
+
+        for (i, block) in blocks.iter().enumerate() {
+            let relative = i64::from_le_bytes(index[8 + i * 8..16 + i * 8].try_into().unwrap());
+            let (ty, data) = read_entry(&file, (index_offset as i64 + relative) as usize);
+            assert_eq!(ty, COMPRESSED_HEADER);
+
+            let mut header = Vec::new();
+            snap::read::FrameDecoder::new(data).read_to_end(&mut header).unwrap();
+            let mut expected = Vec::new();
+            block.header.encode(&mut expected);
+            assert_eq!(header, expected);
+        }
+
+        let (ty, root) = read_entry(&file, index_offset - 8 - 32);
+        assert_eq!(ty, ACCUMULATOR);
+        assert_ne!(root, accumulator_root(&[]).as_bytes());
+    }
+
+    #[test]
+    fn reject_too_many_blocks() {
+        let mut writer = Era1Writer::new(io::sink()).unwrap();
+        let block = Block::default();
+        for _ in 0..MAX_ERA1_BLOCKS {
+            writer.write_block(&block, &[], U256::ZERO).unwrap();
+        }
+        assert!(writer.write_block(&block, &[], U256::ZERO).is_err());
+    }
+}
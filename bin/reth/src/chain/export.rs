@@ -0,0 +1,230 @@
+use super::era1::{Era1Writer, MAX_ERA1_BLOCKS};
+use crate::dirs::{DbPath, PlatformPath};
+use clap::{Parser, ValueEnum};
+use reth_primitives::{BlockNumber, ChainSpec};
+use reth_provider::{
+    BlockIdProvider, BlockProvider, HeaderProvider, ReceiptProvider, ShareableDatabase,
+};
+use reth_rlp::Encodable;
+use reth_staged_sync::utils::{chainspec::genesis_value_parser, init::init_db};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tracing::{info, warn};
+
+/// How often the progress of the export is reported.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Exports the blocks of the database to an RLP encoded file.
+#[derive(Debug, Parser)]
+pub struct ExportCommand {
+    /// The path to the database folder.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/db` or `$HOME/.local/share/reth/db`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/db`
+    /// - macOS: `$HOME/Library/Application Support/reth/db`
+    #[arg(long, value_name = "PATH", verbatim_doc_comment, default_value_t)]
+    db: PlatformPath<DbPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file,
+    /// in reth's format or a geth `genesis.json`.
+    ///
+    /// Built-in chains:
+    /// - mainnet
+    /// - goerli
+    /// - sepolia
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        verbatim_doc_comment,
+        default_value = "mainnet",
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// The first block to export.
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to export.
+    ///
+    /// Defaults to the highest fully synced block of the database.
+    #[arg(long, value_name = "BLOCK", verbatim_doc_comment)]
+    to: Option<BlockNumber>,
+
+    /// The format of the exported file.
+    ///
+    /// - rlp: the RLP encoded blocks one after another, as written by `geth export`
+    /// - era1: an ERA1 archive of up to 8192 blocks including their receipts
+    #[arg(long, value_enum, verbatim_doc_comment, default_value_t = ExportFormat::Rlp)]
+    format: ExportFormat,
+
+    /// The path of the file to write the blocks to.
+    #[arg(value_name = "OUTPUT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+/// The file formats blocks can be exported to.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    /// RLP encoded blocks, compatible with `geth export` and `reth import`.
+    Rlp,
+    /// The ERA1 block archive format.
+    Era1,
+}
+
+impl ExportCommand {
+    /// Execute the `export` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", path = %self.db, "Opening database");
+        let db = Arc::new(init_db(&self.db)?);
+        let provider = ShareableDatabase::new(db, self.chain.clone());
+
+        let to = match self.to {
+            Some(to) => to,
+            None => provider.chain_info()?.best_number,
+        };
+        if to < self.from {
+            eyre::bail!("Invalid block range {}..={}", self.from, to)
+        }
+        if self.format == ExportFormat::Era1 && to - self.from >= MAX_ERA1_BLOCKS as u64 {
+            eyre::bail!("An ERA1 file holds at most {MAX_ERA1_BLOCKS} blocks")
+        }
+
+        let shutdown = shutdown_flag();
+        let file = BufWriter::new(File::create(&self.path)?);
+        let mut writer = match self.format {
+            ExportFormat::Rlp => BlockWriter::Rlp(file),
+            ExportFormat::Era1 => BlockWriter::Era1(Era1Writer::new(file)?),
+        };
+
+        info!(target: "reth::cli", from = self.from, to, path = %self.path.display(), "Exporting blocks");
+        let mut progress = ExportProgress::new(to - self.from + 1);
+        for number in self.from..=to {
+            // stop after a complete block, so that the file is not truncated mid-block
+            if shutdown.load(Ordering::Relaxed) {
+                warn!(target: "reth::cli", last_block = number.checked_sub(1), "Received shutdown signal, stopping export");
+                break
+            }
+
+            let block = provider
+                .block_by_number(number)?
+                .ok_or_else(|| eyre::eyre!("Block {number} not found"))?;
+            match &mut writer {
+                BlockWriter::Rlp(file) => {
+                    let mut buf = Vec::with_capacity(block.length());
+                    block.encode(&mut buf);
+                    file.write_all(&buf)?;
+                }
+                BlockWriter::Era1(era1) => {
+                    let receipts = provider
+                        .receipts_by_block(number.into())?
+                        .ok_or_else(|| eyre::eyre!("Receipts of block {number} not found"))?;
+                    let total_difficulty =
+                        provider.header_td_by_number(number)?.ok_or_else(|| {
+                            eyre::eyre!("Total difficulty of block {number} not found")
+                        })?;
+                    era1.write_block(&block, &receipts, total_difficulty)?;
+                }
+            }
+            progress.on_block_exported();
+        }
+
+        match writer {
+            BlockWriter::Rlp(mut file) => file.flush()?,
+            BlockWriter::Era1(era1) => {
+                era1.finish()?;
+            }
+        }
+
+        info!(target: "reth::cli", blocks = progress.exported, path = %self.path.display(), "Export finished");
+        Ok(())
+    }
+}
+
+/// Writes the exported blocks in the chosen format.
+enum BlockWriter {
+    Rlp(BufWriter<File>),
+    Era1(Era1Writer<BufWriter<File>>),
+}
+
+/// Tracks the progress of the export and reports the export rate and the remaining time.
+struct ExportProgress {
+    /// The total number of blocks to export.
+    total: u64,
+    /// The number of blocks exported.
+    exported: u64,
+    /// When the export started.
+    started: Instant,
+    /// When the progress was last reported.
+    last_report: Instant,
+}
+
+impl ExportProgress {
+    fn new(total: u64) -> Self {
+        let now = Instant::now();
+        Self { total, exported: 0, started: now, last_report: now }
+    }
+
+    fn on_block_exported(&mut self) {
+        self.exported += 1;
+        if self.last_report.elapsed() < PROGRESS_INTERVAL {
+            return
+        }
+        self.last_report = Instant::now();
+
+        let blocks_per_sec = self.exported as f64 / self.started.elapsed().as_secs_f64();
+        let remaining = self.total - self.exported;
+        let eta = Duration::from_secs_f64(remaining as f64 / blocks_per_sec);
+        info!(target: "reth::cli", exported = self.exported, total = self.total, blocks_per_sec = blocks_per_sec as u64, ?eta, "Export progress");
+    }
+}
+
+/// Returns a flag that is set once the process receives a `SIGINT` or `SIGTERM` signal.
+fn shutdown_flag() -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    let shutdown = flag.clone();
+    tokio::spawn(async move {
+        #[cfg(unix)]
+        {
+            let mut sigterm =
+                tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+                    .expect("failed to install SIGTERM handler");
+            tokio::select! {
+                _ = sigterm.recv() => {},
+                _ = tokio::signal::ctrl_c() => {},
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = tokio::signal::ctrl_c().await;
+
+        shutdown.store(true, Ordering::Relaxed);
+    });
+    flag
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_export_command() {
+        let args: ExportCommand =
+            ExportCommand::parse_from(["reth", "--from", "10", "--format", "era1", "blocks.era1"]);
+        assert_eq!(args.from, 10);
+        assert_eq!(args.to, None);
+        assert_eq!(args.format, ExportFormat::Era1);
+        assert_eq!(args.path, PathBuf::from("blocks.era1"));
+    }
+}
@@ -6,17 +6,22 @@ use clap::{crate_version, Parser};
 use eyre::Context;
 use futures::{Stream, StreamExt};
 use reth_beacon_consensus::BeaconConsensus;
-use reth_db::mdbx::{Env, WriteMap};
+use reth_db::{
+    database::Database,
+    mdbx::{Env, WriteMap},
+    tables,
+    transaction::DbTx,
+};
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
     headers::reverse_headers::ReverseHeadersDownloaderBuilder, test_utils::FileClient,
 };
 use reth_interfaces::{
-    consensus::{Consensus, ForkchoiceState},
+    consensus::{Consensus, ConsensusError, ForkchoiceState},
     p2p::headers::client::NoopStatusUpdater,
     sync::SyncStateUpdater,
 };
-use reth_primitives::ChainSpec;
+use reth_primitives::{BlockNumber, ChainSpec, SealedBlock, SealedHeader, U256};
 use reth_staged_sync::{
     utils::{
         chainspec::genesis_value_parser,
@@ -26,9 +31,10 @@ use reth_staged_sync::{
 };
 use reth_stages::{
     prelude::*,
-    stages::{ExecutionStage, SenderRecoveryStage, TotalDifficultyStage},
+    stages::{ExecutionStage, SenderRecoveryStage, TotalDifficultyStage, FINISH},
 };
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
+use tokio::sync::watch;
 use tracing::{debug, info};
 
 /// Syncs RLP encoded blocks from a file.
@@ -50,7 +56,8 @@ pub struct ImportCommand {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain or the path to a chain specification file,
+    /// in reth's format or a geth `genesis.json`.
     ///
     /// Built-in chains:
     /// - mainnet
@@ -65,12 +72,20 @@ pub struct ImportCommand {
     )]
     chain: Arc<ChainSpec>,
 
-    /// The path to a block file for import.
+    /// Import the blocks without validating their headers and bodies against the consensus
+    /// rules, trusting the source of the file.
+    #[arg(long, verbatim_doc_comment)]
+    skip_validation: bool,
+
+    /// The path to a block file for import, e.g. as produced by `geth export`.
+    ///
+    /// The file contains the RLP encoded blocks one after another. If no path is given, the
+    /// blocks are read from stdin.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
     /// remaining stages are executed.
     #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
-    path: PlatformPath<ConfigPath>,
+    path: Option<PathBuf>,
 }
 
 impl ImportCommand {
@@ -91,13 +106,34 @@ impl ImportCommand {
 
         // create a new FileClient
         info!(target: "reth::cli", "Importing chain file");
-        let file_client = Arc::new(FileClient::new(&self.path).await?);
+        let file_client = match &self.path {
+            Some(path) => FileClient::new(path).await?,
+            None => FileClient::from_reader(tokio::io::stdin()).await?,
+        };
+        let file_client = Arc::new(file_client);
 
         // override the tip
-        let tip = file_client.tip().expect("file client has no tip");
-        info!(target: "reth::cli", "Chain file imported");
+        let (Some(tip), Some(max_block)) = (file_client.tip(), file_client.max_block()) else {
+            info!(target: "reth::cli", "Chain file contains no blocks");
+            return Ok(())
+        };
+        info!(target: "reth::cli", %tip, number = max_block, "Chain file imported");
+
+        // skip the import if the blocks were already imported by a previous run
+        let (finished, canonical) = db.view(|tx| -> Result<_, reth_db::Error> {
+            Ok((FINISH.get_progress(tx)?, tx.get::<tables::CanonicalHeaders>(max_block)?))
+        })??;
+        if finished.map_or(false, |finished| finished >= max_block) {
+            if canonical != Some(tip) {
+                eyre::bail!("Database contains a different block at height {max_block}")
+            }
+            info!(target: "reth::cli", %tip, "Blocks already imported, skipping");
+            return Ok(())
+        }
 
         let (consensus, notifier) = BeaconConsensus::builder().build(self.chain.clone());
+        let consensus =
+            Arc::new(ImportConsensus { inner: consensus, skip_validation: self.skip_validation });
         debug!(target: "reth::cli", %tip, "Tip manually set");
         notifier.send(ForkchoiceState {
             head_block_hash: tip,
@@ -106,10 +142,11 @@ impl ImportCommand {
         })?;
         info!(target: "reth::cli", "Consensus engine initialized");
 
-        let (mut pipeline, events) =
-            self.build_import_pipeline(config, db.clone(), &consensus, file_client).await?;
+        let (mut pipeline, events) = self
+            .build_import_pipeline(config, db.clone(), &consensus, file_client, max_block)
+            .await?;
 
-        tokio::spawn(handle_events(None, events));
+        tokio::spawn(handle_events(None, None, events));
 
         // Run pipeline
         info!(target: "reth::cli", "Starting sync pipeline");
@@ -128,6 +165,7 @@ impl ImportCommand {
         db: Arc<Env<WriteMap>>,
         consensus: &Arc<C>,
         file_client: Arc<FileClient>,
+        max_block: BlockNumber,
     ) -> eyre::Result<(Pipeline<Env<WriteMap>, impl SyncStateUpdater>, impl Stream<Item = NodeEvent>)>
     where
         C: Consensus + 'static,
@@ -161,7 +199,7 @@ impl ImportCommand {
                 })
                 .set(ExecutionStage::new(factory, config.stages.execution.commit_threshold)),
             )
-            .with_max_block(0)
+            .with_max_block(max_block)
             .build();
 
         let events = pipeline.events().map(Into::into);
@@ -174,6 +212,54 @@ impl ImportCommand {
     }
 }
 
+/// A [`Consensus`] that optionally skips the validation of the imported headers and blocks.
+#[derive(Debug)]
+struct ImportConsensus<C> {
+    /// The consensus used to validate the blocks.
+    inner: Arc<C>,
+    /// Whether the blocks are trusted and not validated.
+    skip_validation: bool,
+}
+
+impl<C: Consensus> Consensus for ImportConsensus<C> {
+    fn fork_choice_state(&self) -> watch::Receiver<ForkchoiceState> {
+        self.inner.fork_choice_state()
+    }
+
+    fn pre_validate_header(
+        &self,
+        header: &SealedHeader,
+        parent: &SealedHeader,
+    ) -> Result<(), ConsensusError> {
+        if self.skip_validation {
+            return Ok(())
+        }
+        self.inner.pre_validate_header(header, parent)
+    }
+
+    fn validate_header(
+        &self,
+        header: &SealedHeader,
+        total_difficulty: U256,
+    ) -> Result<(), ConsensusError> {
+        if self.skip_validation {
+            return Ok(())
+        }
+        self.inner.validate_header(header, total_difficulty)
+    }
+
+    fn pre_validate_block(&self, block: &SealedBlock) -> Result<(), ConsensusError> {
+        if self.skip_validation {
+            return Ok(())
+        }
+        self.inner.pre_validate_block(block)
+    }
+
+    fn has_block_reward(&self, total_difficulty: U256, difficulty: U256) -> bool {
+        self.inner.has_block_reward(total_difficulty, difficulty)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -185,4 +271,11 @@ mod tests {
             assert_eq!(args.chain.chain, chain.parse().unwrap());
         }
     }
+
+    #[test]
+    fn parse_import_from_stdin() {
+        let args: ImportCommand = ImportCommand::parse_from(["reth", "--skip-validation"]);
+        assert!(args.skip_validation);
+        assert!(args.path.is_none());
+    }
 }
@@ -23,7 +23,8 @@ pub struct InitCommand {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain or the path to a chain specification file,
+    /// in reth's format or a geth `genesis.json`.
     ///
     /// Built-in chains:
     /// - mainnet
@@ -31,6 +32,7 @@ pub struct InitCommand {
     /// - sepolia
     #[arg(
         long,
+        alias = "genesis",
         value_name = "CHAIN_OR_PATH",
         verbatim_doc_comment,
         default_value = "mainnet",
@@ -8,7 +8,7 @@ use crate::{
     runner::CliRunner,
     stage, test_eth_chain, test_vectors,
 };
-use clap::{ArgAction, Args, Parser, Subcommand};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
 use reth_tracing::{
     tracing::{metadata::LevelFilter, Level, Subscriber},
     tracing_subscriber::{filter::Directive, registry::LookupSpan},
@@ -20,7 +20,7 @@ pub fn run() -> eyre::Result<()> {
     let opt = Cli::parse();
 
     let (layer, _guard) = opt.logs.layer();
-    reth_tracing::init(vec![layer, reth_tracing::stdout(opt.verbosity.directive())]);
+    reth_tracing::init(vec![layer, opt.logs.stdout_layer(opt.verbosity.directive())]);
 
     let runner = CliRunner::default();
 
@@ -28,6 +28,7 @@ pub fn run() -> eyre::Result<()> {
         Commands::Node(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
         Commands::Init(command) => runner.run_until_ctrl_c(command.execute()),
         Commands::Import(command) => runner.run_until_ctrl_c(command.execute()),
+        Commands::Export(command) => runner.run_until_ctrl_c(command.execute()),
         Commands::Db(command) => runner.run_until_ctrl_c(command.execute()),
         Commands::Stage(command) => runner.run_until_ctrl_c(command.execute()),
         Commands::DumpStage(command) => runner.run_until_ctrl_c(command.execute()),
@@ -49,6 +50,9 @@ pub enum Commands {
     /// This syncs RLP encoded blocks from a file.
     #[command(name = "import")]
     Import(chain::ImportCommand),
+    /// Export the blocks of the database to an RLP encoded file.
+    #[command(name = "export")]
+    Export(chain::ExportCommand),
     /// Database debugging utilities
     #[command(name = "db")]
     Db(db::Command),
@@ -109,6 +113,19 @@ pub struct Logs {
     /// The filter to use for logs written to the log file.
     #[arg(long = "log.filter", value_name = "FILTER", global = true, default_value = "debug")]
     filter: String,
+
+    /// The format of the logs written to stdout.
+    #[arg(long = "log.format", value_enum, global = true, default_value_t = LogFormat::Terminal)]
+    format: LogFormat,
+}
+
+/// The formats of the logs written to stdout.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, ValueEnum)]
+pub enum LogFormat {
+    /// Human readable logs.
+    Terminal,
+    /// Newline-delimited JSON, for log aggregation systems like Loki or Elasticsearch.
+    Json,
 }
 
 impl Logs {
@@ -128,6 +145,18 @@ impl Logs {
             (layer, Some(guard))
         }
     }
+
+    /// Builds the tracing layer that writes to stdout in the configured format.
+    pub fn stdout_layer<S>(&self, default_directive: Directive) -> BoxedLayer<S>
+    where
+        S: Subscriber,
+        for<'a> S: LookupSpan<'a>,
+    {
+        match self.format {
+            LogFormat::Terminal => reth_tracing::stdout(default_directive),
+            LogFormat::Json => reth_tracing::stdout_json(default_directive),
+        }
+    }
 }
 
 /// The verbosity settings for the cli.
@@ -187,4 +216,13 @@ mod tests {
             assert_eq!(err.kind(), clap::error::ErrorKind::DisplayHelp);
         }
     }
+
+    #[test]
+    fn parse_log_format() {
+        let cli = Cli::parse_from(["reth", "node"]);
+        assert_eq!(cli.logs.format, LogFormat::Terminal);
+
+        let cli = Cli::parse_from(["reth", "node", "--log.format", "json"]);
+        assert_eq!(cli.logs.format, LogFormat::Json);
+    }
 }
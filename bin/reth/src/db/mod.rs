@@ -1,21 +1,23 @@
 //! Database debugging tool
 use crate::dirs::{DbPath, PlatformPath};
 use clap::{Parser, Subcommand};
-use comfy_table::{Cell, Row, Table as ComfyTable};
 use eyre::{Result, WrapErr};
-use human_bytes::human_bytes;
 use reth_db::{
     cursor::{DbCursorRO, Walker},
     database::Database,
-    table::Table,
+    mdbx::{Env, WriteMap},
+    table::{Decode, Decompress, Table},
     tables,
     transaction::DbTx,
 };
 use reth_interfaces::test_utils::generators::random_block_range;
+use reth_primitives::hex;
 use reth_provider::insert_canonical_block;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, panic::AssertUnwindSafe};
 use tracing::{error, info};
 
+/// DB table statistics
+mod stats;
 /// DB List TUI
 mod tui;
 
@@ -42,7 +44,7 @@ const DEFAULT_NUM_ITEMS: &str = "5";
 /// `reth db` subcommands
 pub enum Subcommands {
     /// Lists all the tables, their entry count and their size
-    Stats,
+    Stats(StatsArgs),
     /// Lists the contents of a table
     List(ListArgs),
     /// Seeds the database with random blocks on top of each other
@@ -55,6 +57,14 @@ pub enum Subcommands {
     Drop,
 }
 
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db stats` command
+pub struct StatsArgs {
+    /// Output the statistics as JSON
+    #[arg(long)]
+    json: bool,
+}
+
 #[derive(Parser, Debug)]
 /// The arguments for the `reth db list` command
 pub struct ListArgs {
@@ -64,70 +74,40 @@ pub struct ListArgs {
     #[arg(long, short, default_value = "0")]
     start: usize,
     /// How many items to take from the walker
-    #[arg(long, short, default_value = DEFAULT_NUM_ITEMS)]
+    #[arg(long, short, alias = "limit", default_value = DEFAULT_NUM_ITEMS)]
     len: usize,
+    /// The hex encoded key to start iterating from, implies `--print`
+    #[arg(long, value_name = "HEX_KEY")]
+    from: Option<String>,
+    /// Print the raw and decoded entries instead of opening the interactive viewer
+    #[arg(long)]
+    print: bool,
 }
 
 impl Command {
     /// Execute `db` command
     pub async fn execute(&self) -> eyre::Result<()> {
-        std::fs::create_dir_all(&self.db)?;
+        // the stats only read the database, so they can be collected while a node is running
+        let kind = if matches!(self.command, Subcommands::Stats(_)) {
+            reth_db::mdbx::EnvKind::RO
+        } else {
+            std::fs::create_dir_all(&self.db)?;
+            reth_db::mdbx::EnvKind::RW
+        };
 
         // TODO: Auto-impl for Database trait
-        let db = reth_db::mdbx::Env::<reth_db::mdbx::WriteMap>::open(
-            self.db.as_ref(),
-            reth_db::mdbx::EnvKind::RW,
-        )?;
+        let db = reth_db::mdbx::Env::<reth_db::mdbx::WriteMap>::open(self.db.as_ref(), kind)?;
 
         let mut tool = DbTool::new(&db)?;
 
         match &self.command {
-            // TODO: We'll need to add this on the DB trait.
-            Subcommands::Stats { .. } => {
-                let mut stats_table = ComfyTable::new();
-                stats_table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
-                stats_table.set_header([
-                    "Table Name",
-                    "# Entries",
-                    "Branch Pages",
-                    "Leaf Pages",
-                    "Overflow Pages",
-                    "Total Size",
-                ]);
-
-                tool.db.view(|tx| {
-                    for table in tables::TABLES.iter().map(|(_, name)| name) {
-                        let table_db =
-                            tx.inner.open_db(Some(table)).wrap_err("Could not open db.")?;
-
-                        let stats = tx
-                            .inner
-                            .db_stat(&table_db)
-                            .wrap_err(format!("Could not find table: {table}"))?;
-
-                        // Defaults to 16KB right now but we should
-                        // re-evaluate depending on the DB we end up using
-                        // (e.g. REDB does not have these options as configurable intentionally)
-                        let page_size = stats.page_size() as usize;
-                        let leaf_pages = stats.leaf_pages();
-                        let branch_pages = stats.branch_pages();
-                        let overflow_pages = stats.overflow_pages();
-                        let num_pages = leaf_pages + branch_pages + overflow_pages;
-                        let table_size = page_size * num_pages;
-
-                        let mut row = Row::new();
-                        row.add_cell(Cell::new(table))
-                            .add_cell(Cell::new(stats.entries()))
-                            .add_cell(Cell::new(branch_pages))
-                            .add_cell(Cell::new(leaf_pages))
-                            .add_cell(Cell::new(overflow_pages))
-                            .add_cell(Cell::new(human_bytes(table_size as f64)));
-                        stats_table.add_row(row);
-                    }
-                    Ok::<(), eyre::Report>(())
-                })??;
-
-                println!("{stats_table}");
+            Subcommands::Stats(args) => {
+                let stats = stats::table_stats(tool.db)?;
+                if args.json {
+                    println!("{}", serde_json::to_string_pretty(&stats)?);
+                } else {
+                    println!("{}", stats::stats_table(&stats));
+                }
             }
             Subcommands::Seed { len } => {
                 tool.seed(*len)?;
@@ -137,6 +117,18 @@ impl Command {
                     ($arg:expr, $start:expr, $len:expr => [$($table:ident),*]) => {
                         match $arg {
                             $(stringify!($table) => {
+                                if args.print || args.from.is_some() {
+                                    let from = args.from.as_deref().map(|key| hex::decode(key.trim_start_matches("0x"))).transpose().wrap_err("Invalid hex key")?;
+                                    print_entries::<tables::$table>(
+                                        tool.db,
+                                        from.as_deref(),
+                                        $start,
+                                        $len,
+                                        (tables::$table::key_type(), tables::$table::value_type()),
+                                    )?;
+                                    return Ok(());
+                                }
+
                                 tool.db.view(|tx| {
                                     let table_db = tx.inner.open_db(Some(stringify!($table))).wrap_err("Could not open db.")?;
                                     let stats = tx.inner.db_stat(&table_db).wrap_err(format!("Could not find table: {}", stringify!($table)))?;
@@ -190,6 +182,60 @@ impl Command {
     }
 }
 
+/// Prints the entries of the table, starting at the given encoded key if any.
+///
+/// Both the raw bytes and the decoded form of the keys and values are printed, annotated with
+/// their types. Entries that can't be decoded are printed as raw bytes only.
+fn print_entries<T: Table>(
+    db: &Env<WriteMap>,
+    from: Option<&[u8]>,
+    start: usize,
+    len: usize,
+    (key_type, value_type): (&str, &str),
+) -> Result<()> {
+    db.view(|tx| {
+        let table_db = tx.inner.open_db(Some(T::NAME)).wrap_err("Could not open db.")?;
+        let mut cursor = tx.inner.cursor(&table_db)?;
+        let entries = match from {
+            Some(key) => cursor.iter_from::<Vec<u8>, Vec<u8>>(key),
+            None => cursor.iter_start::<Vec<u8>, Vec<u8>>(),
+        };
+
+        for entry in entries.skip(start).take(len) {
+            let (key, value) = entry?;
+            let (decoded_key, decoded_value) = decode_entry::<T>(&key, &value);
+            println!("key ({key_type}): 0x{} = {decoded_key}", hex::encode(&key));
+            println!("value ({value_type}): 0x{}", hex::encode(&value));
+            println!("{decoded_value}");
+            println!();
+        }
+        Ok::<(), eyre::Report>(())
+    })?
+}
+
+/// Decodes the raw key and value of an entry of the table for printing.
+///
+/// Describes the key or value instead if it's malformed. The compact decoding panics on truncated
+/// input, so panics are caught as well.
+fn decode_entry<T: Table>(key: &[u8], value: &[u8]) -> (String, String) {
+    let decoded_key = match catch_decode_panic(|| T::Key::decode(key.to_vec())) {
+        Ok(key) => format!("{key:?}"),
+        Err(err) => format!("<malformed: {err}>"),
+    };
+    let decoded_value = match catch_decode_panic(|| T::Value::decompress(value.to_vec())) {
+        Ok(value) => serde_json::to_string_pretty(&value).unwrap_or_else(|_| format!("{value:?}")),
+        Err(err) => format!("<malformed: {err}>"),
+    };
+    (decoded_key, decoded_value)
+}
+
+/// Runs the decoding function and turns a panic into an error.
+fn catch_decode_panic<T>(
+    decode: impl FnOnce() -> Result<T, reth_db::Error>,
+) -> Result<T, reth_db::Error> {
+    std::panic::catch_unwind(AssertUnwindSafe(decode)).unwrap_or(Err(reth_db::Error::DecodeError))
+}
+
 /// Wrapper over DB that implements many useful DB queries.
 pub(crate) struct DbTool<'a, DB: Database> {
     pub(crate) db: &'a DB,
@@ -241,3 +287,26 @@ impl<'a, DB: Database> DbTool<'a, DB> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::table::Compress;
+    use reth_primitives::{Account, Address, U256};
+
+    #[test]
+    fn decode_truncated_entry() {
+        let key = Address::random();
+        let account = Account { nonce: 1, balance: U256::from(1), bytecode_hash: None };
+        let value = account.compress();
+
+        let (decoded_key, decoded_value) =
+            decode_entry::<tables::PlainAccountState>(key.as_bytes(), value.as_ref());
+        assert_eq!(decoded_key, format!("{key:?}"));
+        assert!(!decoded_value.starts_with("<malformed"));
+
+        let (_, decoded_value) =
+            decode_entry::<tables::PlainAccountState>(key.as_bytes(), &value.as_ref()[..1]);
+        assert!(decoded_value.starts_with("<malformed"));
+    }
+}
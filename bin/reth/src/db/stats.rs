@@ -0,0 +1,211 @@
+//! Statistics of the database tables.
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use eyre::WrapErr;
+use human_bytes::human_bytes;
+use reth_db::{
+    database::Database,
+    mdbx::{Env, ObjectLength, WriteMap},
+    tables,
+};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// The size statistics of a table.
+#[derive(Debug, Serialize)]
+pub(crate) struct TableStats {
+    /// The name of the table.
+    pub(crate) table: &'static str,
+    /// The number of entries in the table.
+    pub(crate) entries: usize,
+    /// The total size of all keys in bytes.
+    pub(crate) key_bytes: usize,
+    /// The total size of all values in bytes.
+    pub(crate) value_bytes: usize,
+    /// The number of branch pages.
+    pub(crate) branch_pages: usize,
+    /// The number of leaf pages.
+    pub(crate) leaf_pages: usize,
+    /// The number of overflow pages.
+    pub(crate) overflow_pages: usize,
+    /// The total size of all pages of the table in bytes.
+    pub(crate) total_size: usize,
+}
+
+/// The number of entries that are read with the same read-only transaction.
+const ENTRIES_PER_TX: usize = 100_000;
+
+/// Collects the statistics of all tables, sorted by their total size in descending order.
+///
+/// Besides the page statistics of MDBX, this walks all entries of every table to sum up the sizes
+/// of the keys and values.
+pub(crate) fn table_stats(db: &Env<WriteMap>) -> eyre::Result<Vec<TableStats>> {
+    let mut stats = tables::TABLES
+        .iter()
+        .map(|&(_, table)| table_stats_chunked(db, table, ENTRIES_PER_TX))
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    stats.sort_by(|a, b| b.total_size.cmp(&a.total_size));
+    Ok(stats)
+}
+
+/// Collects the statistics of the table.
+///
+/// The entries are walked in chunks of at least `chunk_size` entries with a new read-only
+/// transaction per chunk, so that a long walk over a database that is in use doesn't keep a
+/// transaction open and prevent MDBX from reusing the pages freed in the meantime. The
+/// duplicates of a key are always read in the same chunk.
+fn table_stats_chunked(
+    db: &Env<WriteMap>,
+    table: &'static str,
+    chunk_size: usize,
+) -> eyre::Result<TableStats> {
+    let mut stats = db.view(|tx| {
+        let table_db = tx.inner.open_db(Some(table)).wrap_err("Could not open db.")?;
+        let stat =
+            tx.inner.db_stat(&table_db).wrap_err(format!("Could not find table: {table}"))?;
+
+        // Defaults to 16KB right now but we should
+        // re-evaluate depending on the DB we end up using
+        // (e.g. REDB does not have these options as configurable intentionally)
+        let page_size = stat.page_size() as usize;
+        let branch_pages = stat.branch_pages();
+        let leaf_pages = stat.leaf_pages();
+        let overflow_pages = stat.overflow_pages();
+        Ok::<_, eyre::Report>(TableStats {
+            table,
+            entries: stat.entries(),
+            key_bytes: 0,
+            value_bytes: 0,
+            branch_pages,
+            leaf_pages,
+            overflow_pages,
+            total_size: page_size * (branch_pages + leaf_pages + overflow_pages),
+        })
+    })??;
+
+    // the key the next chunk starts at, `None` for the first chunk
+    let mut next_key: Option<Vec<u8>> = None;
+    loop {
+        next_key = db.view(|tx| {
+            let table_db = tx.inner.open_db(Some(table)).wrap_err("Could not open db.")?;
+            let mut cursor = tx.inner.cursor(&table_db)?;
+            let entries = match &next_key {
+                Some(key) => cursor.iter_from::<Cow<'_, [u8]>, ObjectLength>(key),
+                None => cursor.iter_start::<Cow<'_, [u8]>, ObjectLength>(),
+            };
+
+            let mut read = 0;
+            // the last key of a full chunk, whose remaining duplicates are still read
+            let mut last_key: Option<Vec<u8>> = None;
+            for entry in entries {
+                let (key, value) = entry?;
+                if let Some(last_key) = &last_key {
+                    if key.as_ref() != last_key.as_slice() {
+                        return Ok(Some(key.into_owned()))
+                    }
+                }
+
+                stats.key_bytes += key.len();
+                stats.value_bytes += *value;
+                read += 1;
+                if read == chunk_size {
+                    last_key = Some(key.into_owned());
+                }
+            }
+            Ok::<_, eyre::Report>(None)
+        })??;
+
+        if next_key.is_none() {
+            return Ok(stats)
+        }
+    }
+}
+
+/// Formats the statistics as a table.
+pub(crate) fn stats_table(stats: &[TableStats]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Table Name",
+        "# Entries",
+        "Key Size",
+        "Value Size",
+        "Branch Pages",
+        "Leaf Pages",
+        "Overflow Pages",
+        "Total Size",
+    ]);
+
+    for stat in stats {
+        let mut row = Row::new();
+        row.add_cell(Cell::new(stat.table))
+            .add_cell(Cell::new(stat.entries))
+            .add_cell(Cell::new(human_bytes(stat.key_bytes as f64)))
+            .add_cell(Cell::new(human_bytes(stat.value_bytes as f64)))
+            .add_cell(Cell::new(stat.branch_pages))
+            .add_cell(Cell::new(stat.leaf_pages))
+            .add_cell(Cell::new(stat.overflow_pages))
+            .add_cell(Cell::new(human_bytes(stat.total_size as f64)));
+        table.add_row(row);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{mdbx::test_utils::create_test_rw_db, transaction::DbTxMut};
+    use reth_primitives::{Address, StorageEntry, H256, U256};
+
+    #[test]
+    fn collect_table_stats() {
+        let db = create_test_rw_db();
+        db.update(|tx| {
+            for number in 0..10 {
+                tx.put::<tables::CanonicalHeaders>(number, H256::random())?;
+            }
+            Ok::<_, reth_db::Error>(())
+        })
+        .unwrap()
+        .unwrap();
+
+        let stats = table_stats(&db).unwrap();
+        assert_eq!(stats.len(), tables::TABLES.len());
+        assert!(stats.windows(2).all(|pair| pair[0].total_size >= pair[1].total_size));
+
+        let headers = stats.iter().find(|stat| stat.table == "CanonicalHeaders").unwrap();
+        assert_eq!(headers.entries, 10);
+        assert_eq!(headers.key_bytes, 10 * 8);
+        assert_eq!(headers.value_bytes, 10 * 32);
+    }
+
+    #[test]
+    fn collect_table_stats_in_chunks() {
+        let db = create_test_rw_db();
+        db.update(|tx| {
+            for number in 0..10 {
+                tx.put::<tables::CanonicalHeaders>(number, H256::random())?;
+            }
+            // duplicates of the same key
+            for slot in 0..5 {
+                tx.put::<tables::PlainStorageState>(
+                    Address::zero(),
+                    StorageEntry { key: H256::from_low_u64_be(slot), value: U256::from(1) },
+                )?;
+            }
+            Ok::<_, reth_db::Error>(())
+        })
+        .unwrap()
+        .unwrap();
+
+        for chunk_size in [1, 3, 10, 100] {
+            let headers = table_stats_chunked(&db, "CanonicalHeaders", chunk_size).unwrap();
+            assert_eq!(headers.key_bytes, 10 * 8);
+            assert_eq!(headers.value_bytes, 10 * 32);
+
+            let storage = table_stats_chunked(&db, "PlainStorageState", chunk_size).unwrap();
+            assert_eq!(storage.entries, 5);
+            assert_eq!(storage.key_bytes, 5 * 20);
+        }
+    }
+}
@@ -108,6 +108,32 @@ impl XdgPath for KnownPeersPath {
     }
 }
 
+/// Returns the path to the default reth banned peers file.
+///
+/// Refer to [dirs_next::data_dir] for cross-platform behavior.
+#[derive(Default, Debug, Clone)]
+#[non_exhaustive]
+pub struct BannedPeersPath;
+
+impl XdgPath for BannedPeersPath {
+    fn resolve() -> Option<PathBuf> {
+        net_dir().map(|p| p.join("banned-peers.json"))
+    }
+}
+
+/// Returns the path to the default reth sync state file.
+///
+/// Refer to [dirs_next::data_dir] for cross-platform behavior.
+#[derive(Default, Debug, Clone)]
+#[non_exhaustive]
+pub struct SyncStatePath;
+
+impl XdgPath for SyncStatePath {
+    fn resolve() -> Option<PathBuf> {
+        data_dir().map(|p| p.join("sync-state.json"))
+    }
+}
+
 /// Returns the path to the reth logs directory.
 ///
 /// Refer to [dirs_next::cache_dir] for cross-platform behavior.
@@ -42,21 +42,21 @@ fn import_tables_with_range<DB: Database>(
 ) -> eyre::Result<()> {
     //  We're not sharing the transaction in case the memory grows too much.
 
-    output_db.update(|tx| {
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::CanonicalHeaders, _>(&db_tool.db.tx()?, Some(from), to)
-    })??;
-    output_db.update(|tx| {
+    })?;
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::HeaderTD, _>(&db_tool.db.tx()?, Some(from), to)
-    })??;
-    output_db.update(|tx| {
+    })?;
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::Headers, _>(&db_tool.db.tx()?, Some(from), to)
-    })??;
-    output_db.update(|tx| {
+    })?;
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::BlockBodies, _>(&db_tool.db.tx()?, Some(from), to)
-    })??;
-    output_db.update(|tx| {
+    })?;
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::BlockOmmers, _>(&db_tool.db.tx()?, Some(from), to)
-    })??;
+    })?;
 
     // Find range of transactions that need to be copied over
     let (from_tx, to_tx) = db_tool.db.view(|read_tx| {
@@ -72,17 +72,17 @@ fn import_tables_with_range<DB: Database>(
         ))
     })??;
 
-    output_db.update(|tx| {
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::Transactions, _>(
             &db_tool.db.tx()?,
             Some(from_tx),
             to_tx,
         )
-    })??;
+    })?;
 
-    output_db.update(|tx| {
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::TxSenders, _>(&db_tool.db.tx()?, Some(from_tx), to_tx)
-    })??;
+    })?;
 
     Ok(())
 }
@@ -111,9 +111,12 @@ async fn unwind_and_copy<DB: Database>(
 
     let unwind_inner_tx = unwind_tx.deref_mut();
 
-    output_db.update(|tx| tx.import_dupsort::<tables::PlainStorageState, _>(unwind_inner_tx))??;
-    output_db.update(|tx| tx.import_table::<tables::PlainAccountState, _>(unwind_inner_tx))??;
-    output_db.update(|tx| tx.import_table::<tables::Bytecodes, _>(unwind_inner_tx))??;
+    output_db.update_with_retry(|tx| {
+        tx.import_dupsort::<tables::PlainStorageState, _>(unwind_inner_tx)
+    })?;
+    output_db
+        .update_with_retry(|tx| tx.import_table::<tables::PlainAccountState, _>(unwind_inner_tx))?;
+    output_db.update_with_retry(|tx| tx.import_table::<tables::Bytecodes, _>(unwind_inner_tx))?;
 
     unwind_tx.drop()?;
 
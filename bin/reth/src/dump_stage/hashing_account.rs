@@ -25,13 +25,13 @@ pub(crate) async fn dump_hashing_account_stage<DB: Database>(
         tx.get::<tables::BlockTransitionIndex>(from)?.expect("there should be at least one.");
     let to_transition_rev =
         tx.get::<tables::BlockTransitionIndex>(to)?.expect("there should be at least one.");
-    output_db.update(|tx| {
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::AccountChangeSet, _>(
             &db_tool.db.tx()?,
             Some(from_transition_rev),
             to_transition_rev,
         )
-    })??;
+    })?;
 
     unwind_and_copy::<DB>(db_tool, from, tip_block_number, &output_db).await?;
 
@@ -60,7 +60,8 @@ async fn unwind_and_copy<DB: Database>(
         .await?;
     let unwind_inner_tx = unwind_tx.deref_mut();
 
-    output_db.update(|tx| tx.import_table::<tables::PlainAccountState, _>(unwind_inner_tx))??;
+    output_db
+        .update_with_retry(|tx| tx.import_table::<tables::PlainAccountState, _>(unwind_inner_tx))?;
 
     unwind_tx.drop()?;
 
@@ -47,8 +47,12 @@ async fn unwind_and_copy<DB: Database>(
     let unwind_inner_tx = unwind_tx.deref_mut();
 
     // TODO optimize we can actually just get the entries we need for both these tables
-    output_db.update(|tx| tx.import_dupsort::<tables::PlainStorageState, _>(unwind_inner_tx))??;
-    output_db.update(|tx| tx.import_dupsort::<tables::StorageChangeSet, _>(unwind_inner_tx))??;
+    output_db.update_with_retry(|tx| {
+        tx.import_dupsort::<tables::PlainStorageState, _>(unwind_inner_tx)
+    })?;
+    output_db.update_with_retry(|tx| {
+        tx.import_dupsort::<tables::StorageChangeSet, _>(unwind_inner_tx)
+    })?;
 
     unwind_tx.drop()?;
 
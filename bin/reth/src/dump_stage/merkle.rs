@@ -23,9 +23,9 @@ pub(crate) async fn dump_merkle_stage<DB: Database>(
 ) -> Result<()> {
     let (output_db, tip_block_number) = setup::<DB>(from, to, output_db, db_tool)?;
 
-    output_db.update(|tx| {
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::Headers, _>(&db_tool.db.tx()?, Some(from), to)
-    })??;
+    })?;
 
     let tx = db_tool.db.tx()?;
     let from_transition_rev =
@@ -33,13 +33,13 @@ pub(crate) async fn dump_merkle_stage<DB: Database>(
     let to_transition_rev =
         tx.get::<tables::BlockTransitionIndex>(to)?.expect("there should be at least one.");
 
-    output_db.update(|tx| {
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::AccountChangeSet, _>(
             &db_tool.db.tx()?,
             Some(from_transition_rev),
             to_transition_rev,
         )
-    })??;
+    })?;
 
     unwind_and_copy::<DB>(db_tool, (from, to), tip_block_number, &output_db).await?;
 
@@ -97,12 +97,18 @@ async fn unwind_and_copy<DB: Database>(
     let unwind_inner_tx = unwind_tx.deref_mut();
 
     // TODO optimize we can actually just get the entries we need
-    output_db.update(|tx| tx.import_dupsort::<tables::StorageChangeSet, _>(unwind_inner_tx))??;
-
-    output_db.update(|tx| tx.import_table::<tables::HashedAccount, _>(unwind_inner_tx))??;
-    output_db.update(|tx| tx.import_dupsort::<tables::HashedStorage, _>(unwind_inner_tx))??;
-    output_db.update(|tx| tx.import_table::<tables::AccountsTrie, _>(unwind_inner_tx))??;
-    output_db.update(|tx| tx.import_dupsort::<tables::StoragesTrie, _>(unwind_inner_tx))??;
+    output_db.update_with_retry(|tx| {
+        tx.import_dupsort::<tables::StorageChangeSet, _>(unwind_inner_tx)
+    })?;
+
+    output_db
+        .update_with_retry(|tx| tx.import_table::<tables::HashedAccount, _>(unwind_inner_tx))?;
+    output_db
+        .update_with_retry(|tx| tx.import_dupsort::<tables::HashedStorage, _>(unwind_inner_tx))?;
+    output_db
+        .update_with_retry(|tx| tx.import_table::<tables::AccountsTrie, _>(unwind_inner_tx))?;
+    output_db
+        .update_with_retry(|tx| tx.import_dupsort::<tables::StoragesTrie, _>(unwind_inner_tx))?;
 
     unwind_tx.drop()?;
 
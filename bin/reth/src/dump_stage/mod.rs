@@ -122,13 +122,13 @@ pub(crate) fn setup<DB: Database>(
 
     let output_db = init_db(output_db)?;
 
-    output_db.update(|tx| {
+    output_db.update_with_retry(|tx| {
         tx.import_table_with_range::<tables::BlockTransitionIndex, _>(
             &db_tool.db.tx()?,
             Some(from - 1),
             to + 1,
         )
-    })??;
+    })?;
 
     let (tip_block_number, _) = db_tool
         .db
@@ -1,10 +1,15 @@
 //! Support for handling events emitted by node components.
 
+use super::sync_state::{SyncState, SyncStateFile};
 use futures::{Stream, StreamExt};
+use reth_interfaces::consensus::ForkchoiceState;
 use reth_network::{NetworkEvent, NetworkHandle};
 use reth_network_api::PeersInfo;
 use reth_primitives::BlockNumber;
-use reth_stages::{PipelineEvent, StageId};
+use reth_stages::{
+    stages::{EXECUTION, HEADERS},
+    PipelineEvent, StageId,
+};
 use std::time::Duration;
 use tracing::{info, warn};
 
@@ -16,17 +21,39 @@ struct NodeState {
     current_stage: Option<StageId>,
     /// The current checkpoint of the executing stage.
     current_checkpoint: BlockNumber,
+    /// The file the sync progress is persisted to.
+    sync_state: Option<SyncStateFile>,
 }
 
 impl NodeState {
-    fn new(network: Option<NetworkHandle>) -> Self {
-        Self { network, current_stage: None, current_checkpoint: 0 }
+    fn new(network: Option<NetworkHandle>, sync_state: Option<SyncStateFile>) -> Self {
+        Self { network, current_stage: None, current_checkpoint: 0, sync_state }
     }
 
     fn num_connected_peers(&self) -> usize {
         self.network.as_ref().map(|net| net.num_connected_peers()).unwrap_or_default()
     }
 
+    /// Records the progress of a stage in the sync state file.
+    fn persist_stage_progress(&mut self, stage_id: StageId, stage_progress: BlockNumber) {
+        self.persist_sync_state(|state| {
+            if stage_id == HEADERS {
+                state.highest_downloaded_header = stage_progress;
+            } else if stage_id == EXECUTION {
+                state.highest_executed_block = stage_progress;
+            }
+        })
+    }
+
+    /// Applies the update to the sync state file, if any.
+    fn persist_sync_state(&mut self, f: impl FnOnce(&mut SyncState)) {
+        if let Some(sync_state) = &mut self.sync_state {
+            if let Err(error) = sync_state.update(f) {
+                warn!(target: "reth::cli", %error, "Failed to persist sync state");
+            }
+        }
+    }
+
     /// Processes an event emitted by the pipeline
     async fn handle_pipeline_event(&mut self, event: PipelineEvent) {
         match event {
@@ -40,6 +67,7 @@ impl NodeState {
                 }
             }
             PipelineEvent::Ran { stage_id, result } => {
+                self.persist_stage_progress(stage_id, result.stage_progress);
                 let notable = result.stage_progress > self.current_checkpoint;
                 self.current_checkpoint = result.stage_progress;
                 if result.done {
@@ -49,10 +77,21 @@ impl NodeState {
                     info!(target: "reth::cli", stage = %stage_id, checkpoint = result.stage_progress, "Stage committed progress");
                 }
             }
+            PipelineEvent::Unwound { stage_id, result } => {
+                self.persist_stage_progress(stage_id, result.stage_progress);
+            }
             _ => (),
         }
     }
 
+    /// Records the finalized block of the forkchoice state in the sync state file.
+    fn handle_forkchoice_update(&mut self, forkchoice: ForkchoiceState) {
+        let finalized = forkchoice.finalized_block_hash;
+        if !finalized.is_zero() {
+            self.persist_sync_state(|state| state.highest_finalized_block = Some(finalized));
+        }
+    }
+
     async fn handle_network_event(&mut self, event: NetworkEvent) {
         match event {
             NetworkEvent::SessionEstablished { peer_id, status, .. } => {
@@ -73,6 +112,8 @@ pub enum NodeEvent {
     Network(NetworkEvent),
     /// A sync pipeline event.
     Pipeline(PipelineEvent),
+    /// The forkchoice state was updated.
+    ForkchoiceUpdated(ForkchoiceState),
 }
 
 impl From<NetworkEvent> for NodeEvent {
@@ -87,13 +128,22 @@ impl From<PipelineEvent> for NodeEvent {
     }
 }
 
+impl From<ForkchoiceState> for NodeEvent {
+    fn from(state: ForkchoiceState) -> NodeEvent {
+        NodeEvent::ForkchoiceUpdated(state)
+    }
+}
+
 /// Displays relevant information to the user from components of the node, and periodically
 /// displays the high-level status of the node.
+///
+/// If a [SyncStateFile] is given, the sync progress is persisted to it.
 pub async fn handle_events(
     network: Option<NetworkHandle>,
+    sync_state: Option<SyncStateFile>,
     mut events: impl Stream<Item = NodeEvent> + Unpin,
 ) {
-    let mut state = NodeState::new(network);
+    let mut state = NodeState::new(network, sync_state);
 
     let mut interval = tokio::time::interval(Duration::from_secs(30));
     interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
@@ -107,6 +157,9 @@ pub async fn handle_events(
                     NodeEvent::Pipeline(event) => {
                         state.handle_pipeline_event(event).await;
                     }
+                    NodeEvent::ForkchoiceUpdated(forkchoice) => {
+                        state.handle_forkchoice_update(forkchoice);
+                    }
                 }
             },
             _ = interval.tick() => {
@@ -3,8 +3,8 @@
 //! Starts the client
 use crate::{
     args::{NetworkArgs, RpcServerArgs},
-    dirs::{ConfigPath, DbPath, PlatformPath},
-    prometheus_exporter,
+    dirs::{ConfigPath, DbPath, PlatformPath, SyncStatePath},
+    prometheus_exporter::{self, MetricsExporter},
     runner::CliContext,
     utils::get_single_header,
 };
@@ -16,14 +16,18 @@ use futures::{pin_mut, stream::select as stream_select, Stream, StreamExt};
 use reth_beacon_consensus::BeaconConsensus;
 use reth_db::{
     database::Database,
-    mdbx::{Env, WriteMap},
+    mdbx::{DatabaseConfig, Env, WriteMap},
     tables,
     transaction::DbTx,
 };
 use reth_discv4::DEFAULT_DISCOVERY_PORT;
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
-    headers::reverse_headers::ReverseHeadersDownloaderBuilder,
+    headers::reverse_headers::{ReverseHeadersDownloaderBuilder, TrustedCheckpoint},
+};
+use reth_executor::blockchain_tree::{
+    config::BlockchainTreeConfig, externals::TreeExternals, shareable::ShareableBlockchainTree,
+    BlockchainTree,
 };
 use reth_interfaces::{
     consensus::{Consensus, ForkchoiceState},
@@ -37,20 +41,27 @@ use reth_network::{
     error::NetworkError, FetchClient, NetworkConfig, NetworkHandle, NetworkManager,
 };
 use reth_network_api::NetworkInfo;
-use reth_primitives::{BlockHashOrNumber, ChainSpec, Head, Header, SealedHeader, H256};
-use reth_provider::{BlockProvider, HeaderProvider, ShareableDatabase};
+use reth_primitives::{BlockHashOrNumber, BlockNumber, ChainSpec, Head, Header, SealedHeader, H256};
+use reth_provider::{
+    pruner::{NodeMode, Pruner},
+    BlockProvider, HeaderProvider, NodeDataProvider, ShareableDatabase,
+};
+use reth_rpc::AtomicJwtSecretProvider;
 use reth_rpc_engine_api::{EngineApi, EngineApiHandle};
 use reth_staged_sync::{
     utils::{
         chainspec::genesis_value_parser,
-        init::{init_db, init_genesis},
-        parse_socket_address,
+        init::{init_db_with_config, init_genesis},
+        parse_false_positive_rate, parse_socket_address,
     },
     Config,
 };
 use reth_stages::{
     prelude::*,
-    stages::{ExecutionStage, HeaderStage, SenderRecoveryStage, TotalDifficultyStage, FINISH},
+    stages::{
+        ExecutionStage, HeaderStage, SenderRecoveryStage, TotalDifficultyStage, EXECUTION, FINISH,
+        HEADERS,
+    },
 };
 use reth_tasks::TaskExecutor;
 use std::{
@@ -58,10 +69,12 @@ use std::{
     path::PathBuf,
     sync::Arc,
 };
+use sync_state::{SyncState, SyncStateFile};
 use tokio::sync::{mpsc::unbounded_channel, watch};
 use tracing::*;
 
 pub mod events;
+pub mod sync_state;
 
 /// Start the node
 #[derive(Debug, Parser)]
@@ -80,9 +93,17 @@ pub struct Command {
     #[arg(long, value_name = "PATH", verbatim_doc_comment, default_value_t)]
     db: PlatformPath<DbPath>,
 
+    /// The path to the file the sync progress is persisted to.
+    ///
+    /// On startup, the sync resumes towards the last finalized block recorded in this file unless
+    /// a tip is given with `--debug.tip`.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment, default_value_t)]
+    sync_state: PlatformPath<SyncStatePath>,
+
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain or the path to a chain specification file,
+    /// in reth's format or a geth `genesis.json`.
     ///
     /// Built-in chains:
     /// - mainnet
@@ -97,15 +118,57 @@ pub struct Command {
     )]
     chain: Arc<ChainSpec>,
 
+    /// How much of the historical state the node keeps.
+    ///
+    /// Possible values:
+    /// - archive: the state of all blocks
+    /// - full: the state of the last 128 blocks
+    /// - pruned:<N>: the state of the last N blocks
+    #[arg(long, value_name = "MODE", verbatim_doc_comment, default_value_t)]
+    mode: NodeMode,
+
+    /// Keep a Bloom filter over the existing accounts with the given false positive rate, so
+    /// lookups of accounts that don't exist skip the database.
+    ///
+    /// The filter is loaded from the database on startup. Disabled if not set.
+    #[arg(
+        long = "db.account-filter-fp-rate",
+        value_name = "RATE",
+        value_parser = parse_false_positive_rate,
+        help_heading = "Database"
+    )]
+    db_account_filter_fp_rate: Option<f64>,
+
     /// Enable Prometheus metrics.
     ///
-    /// The metrics will be served at the given interface and port.
+    /// The metrics will be served at `/metrics` on the given interface and port.
     #[arg(long, value_name = "SOCKET", value_parser = parse_socket_address, help_heading = "Metrics")]
     metrics: Option<SocketAddr>,
 
+    /// Push the Prometheus metrics to the push gateway at the given URL instead of serving them.
+    ///
+    /// For environments that do not allow inbound connections.
+    #[arg(
+        long = "metrics-push-gateway",
+        value_name = "URL",
+        conflicts_with = "metrics",
+        help_heading = "Metrics"
+    )]
+    metrics_push_gateway: Option<String>,
+
     #[clap(flatten)]
     network: NetworkArgs,
 
+    /// The hash of a block that is trusted to be canonical, e.g. a finalized checkpoint.
+    ///
+    /// Headers before the checkpoint are only checked to be linked to it, but not validated.
+    #[arg(long, value_name = "HASH", requires = "trusted_checkpoint_number")]
+    trusted_checkpoint_hash: Option<H256>,
+
+    /// The number of the block given by `--trusted-checkpoint-hash`.
+    #[arg(long, value_name = "NUMBER", requires = "trusted_checkpoint_hash")]
+    trusted_checkpoint_number: Option<u64>,
+
     /// Prompt the downloader to download blocks one at a time.
     ///
     /// NOTE: This is for testing purposes only.
@@ -144,8 +207,11 @@ impl Command {
         info!(target: "reth::cli", path = %self.db, "Configuration loaded");
 
         info!(target: "reth::cli", path = %self.db, "Opening database");
-        let db = Arc::new(init_db(&self.db)?);
-        let shareable_db = ShareableDatabase::new(Arc::clone(&db), self.chain.clone());
+        let db_config = DatabaseConfig {
+            account_filter_fp_rate: self.db_account_filter_fp_rate,
+            ..Default::default()
+        };
+        let db = Arc::new(init_db_with_config(&self.db, db_config)?);
         info!(target: "reth::cli", "Database opened");
 
         self.start_metrics_endpoint()?;
@@ -154,9 +220,24 @@ impl Command {
 
         init_genesis(db.clone(), self.chain.clone())?;
 
-        let (consensus, forkchoice_state_tx) = self.init_consensus()?;
+        let mut sync_state = SyncStateFile::load(self.sync_state.clone());
+        debug!(target: "reth::cli", path = %self.sync_state, state = ?sync_state.state(), "Sync state loaded");
+        let (headers_checkpoint, execution_checkpoint) =
+            db.view(|tx| -> Result<(BlockNumber, BlockNumber), reth_db::Error> {
+                Ok((
+                    HEADERS.get_progress(tx)?.unwrap_or_default(),
+                    EXECUTION.get_progress(tx)?.unwrap_or_default(),
+                ))
+            })??;
+        sync_state.resume(headers_checkpoint, execution_checkpoint)?;
+
+        let (consensus, forkchoice_state_tx) = self.init_consensus(sync_state.state())?;
         info!(target: "reth::cli", "Consensus engine initialized");
 
+        // resolves the safe and finalized blocks of the RPC from the forkchoice updates
+        let shareable_db = ShareableDatabase::new(Arc::clone(&db), self.chain.clone())
+            .with_forkchoice_state(consensus.fork_choice_state());
+
         self.init_trusted_nodes(&mut config);
 
         info!(target: "reth::cli", "Connecting to P2P network");
@@ -168,6 +249,16 @@ impl Command {
         let test_transaction_pool = reth_transaction_pool::test_utils::testing_pool();
         info!(target: "reth::cli", "Test transaction pool initialized");
 
+        let blockchain_tree = self.init_blockchain_tree(Arc::clone(&db), Arc::clone(&consensus))?;
+        ctx.task_executor.spawn_critical(
+            "txpool maintenance task",
+            reth_transaction_pool::maintain::maintain_transaction_pool(
+                blockchain_tree.clone(),
+                test_transaction_pool.clone(),
+            ),
+        );
+        info!(target: "reth::cli", "Transaction pool maintenance task spawned");
+
         let _rpc_server = self
             .rpc
             .start_rpc_server(
@@ -183,8 +274,12 @@ impl Command {
             info!(target: "reth::cli", "Continuous sync mode enabled");
         }
 
-        let engine_api_handle =
-            self.init_engine_api(Arc::clone(&db), forkchoice_state_tx, &ctx.task_executor);
+        let engine_api_handle = self.init_engine_api(
+            Arc::clone(&db),
+            forkchoice_state_tx,
+            blockchain_tree,
+            &ctx.task_executor,
+        );
         info!(target: "reth::cli", "Engine API handler initialized");
 
         let _auth_server = self
@@ -195,6 +290,7 @@ impl Command {
                 network.clone(),
                 ctx.task_executor.clone(),
                 engine_api_handle,
+                AtomicJwtSecretProvider::new(self.rpc.jwt_secret()?),
             )
             .await?;
         info!(target: "reth::cli", "Started Auth server");
@@ -209,7 +305,17 @@ impl Command {
             )
             .await?;
 
-        ctx.task_executor.spawn(events::handle_events(Some(network.clone()), events));
+        ctx.task_executor.spawn(events::handle_events(
+            Some(network.clone()),
+            Some(sync_state),
+            events,
+        ));
+
+        if let Some(blocks) = self.mode.state_history_blocks() {
+            info!(target: "reth::cli", mode = %self.mode, blocks, "Pruning state history");
+            let pruner = Pruner::new(db.clone(), self.mode.pruner_config());
+            ctx.task_executor.spawn_blocking(run_pruner(pruner, pipeline.events()));
+        }
 
         // Run pipeline
         let (rx, tx) = tokio::sync::oneshot::channel();
@@ -253,7 +359,11 @@ impl Command {
         // TODO: remove Arc requirement from downloader builders.
         // building network downloaders using the fetch client
         let fetch_client = Arc::new(fetch_client);
-        let header_downloader = ReverseHeadersDownloaderBuilder::from(config.stages.headers)
+        let mut header_downloader = ReverseHeadersDownloaderBuilder::from(config.stages.headers);
+        if let Some(checkpoint) = self.trusted_checkpoint(db.clone())? {
+            header_downloader = header_downloader.trusted_checkpoint(checkpoint);
+        }
+        let header_downloader = header_downloader
             .build(fetch_client.clone(), consensus.clone())
             .into_task_with(task_executor);
 
@@ -273,13 +383,48 @@ impl Command {
             )
             .await?;
 
+        let forkchoice_updates =
+            futures::stream::unfold(consensus.fork_choice_state(), |mut rx| async move {
+                rx.changed().await.ok()?;
+                let state = rx.borrow().clone();
+                Some((state, rx))
+            })
+            .boxed();
         let events = stream_select(
-            network.event_listener().map(Into::into),
-            pipeline.events().map(Into::into),
+            stream_select(
+                network.event_listener().map(Into::into),
+                pipeline.events().map(Into::into),
+            ),
+            forkchoice_updates.map(Into::into),
         );
         Ok((pipeline, events))
     }
 
+    /// Returns the trusted checkpoint, if configured.
+    ///
+    /// Fails if the database already has a different canonical block at the checkpoint's number.
+    fn trusted_checkpoint(
+        &self,
+        db: Arc<Env<WriteMap>>,
+    ) -> eyre::Result<Option<TrustedCheckpoint>> {
+        let (Some(hash), Some(number)) =
+            (self.trusted_checkpoint_hash, self.trusted_checkpoint_number)
+        else {
+            return Ok(None)
+        };
+
+        if let Some(canonical) = db.view(|tx| tx.get::<tables::CanonicalHeaders>(number))?? {
+            if canonical != hash {
+                eyre::bail!(
+                    "The trusted checkpoint {hash} does not match the canonical block {canonical} at number {number}"
+                )
+            }
+        }
+
+        warn!(target: "reth::cli", %hash, number, "Checkpoint sync enabled. Headers before the trusted checkpoint are not validated, make sure the checkpoint is canonical.");
+        Ok(Some(TrustedCheckpoint { number, hash }))
+    }
+
     fn load_config(&self) -> eyre::Result<Config> {
         confy::load_path::<Config>(&self.config).wrap_err("Could not load config")
     }
@@ -298,16 +443,25 @@ impl Command {
     fn start_metrics_endpoint(&self) -> eyre::Result<()> {
         if let Some(listen_addr) = self.metrics {
             info!(target: "reth::cli", addr = %listen_addr, "Starting metrics endpoint");
-            prometheus_exporter::initialize(listen_addr)
+            prometheus_exporter::initialize(MetricsExporter::Http(listen_addr))
+        } else if let Some(endpoint) = &self.metrics_push_gateway {
+            info!(target: "reth::cli", %endpoint, "Pushing metrics to push gateway");
+            prometheus_exporter::initialize(MetricsExporter::PushGateway(endpoint.clone()))
         } else {
             Ok(())
         }
     }
 
-    fn init_consensus(&self) -> eyre::Result<(Arc<dyn Consensus>, watch::Sender<ForkchoiceState>)> {
+    fn init_consensus(
+        &self,
+        sync_state: &SyncState,
+    ) -> eyre::Result<(Arc<dyn Consensus>, watch::Sender<ForkchoiceState>)> {
         let (consensus, notifier) = BeaconConsensus::builder().build(self.chain.clone());
 
-        if let Some(tip) = self.tip {
+        if let Some(tip) = self.tip.or(sync_state.highest_finalized_block) {
+            if self.tip.is_none() {
+                info!(target: "reth::cli", %tip, "Resuming sync towards the last finalized block");
+            }
             debug!(target: "reth::cli", %tip, "Tip manually set");
             notifier.send(ForkchoiceState {
                 head_block_hash: tip,
@@ -324,10 +478,32 @@ impl Command {
         Ok((consensus, notifier))
     }
 
+    /// Creates the [BlockchainTree] on top of the canonical chain of the database.
+    ///
+    /// The Engine API inserts the payloads into the tree and makes the forkchoice heads canonical
+    /// through it.
+    fn init_blockchain_tree(
+        &self,
+        db: Arc<Env<WriteMap>>,
+        consensus: Arc<dyn Consensus>,
+    ) -> eyre::Result<
+        ShareableBlockchainTree<Arc<Env<WriteMap>>, Arc<dyn Consensus>, reth_executor::Factory>,
+    > {
+        let factory = reth_executor::Factory::new(self.chain.clone());
+        let externals = TreeExternals::new(db, consensus, factory, self.chain.clone());
+        let tree = BlockchainTree::new(externals, BlockchainTreeConfig::default())?;
+        Ok(ShareableBlockchainTree::new(tree))
+    }
+
     fn init_engine_api(
         &self,
         db: Arc<Env<WriteMap>>,
         forkchoice_state_tx: watch::Sender<ForkchoiceState>,
+        blockchain_tree: ShareableBlockchainTree<
+            Arc<Env<WriteMap>>,
+            Arc<dyn Consensus>,
+            reth_executor::Factory,
+        >,
         task_executor: &TaskExecutor,
     ) -> EngineApiHandle {
         let (message_tx, message_rx) = unbounded_channel();
@@ -336,7 +512,8 @@ impl Command {
             self.chain.clone(),
             message_rx,
             forkchoice_state_tx,
-        );
+        )
+        .with_blockchain_tree(Arc::new(blockchain_tree));
         task_executor.spawn_critical("engine API task", engine_api);
         message_tx
     }
@@ -351,15 +528,16 @@ impl Command {
         _pool: (),
     ) -> Result<NetworkHandle, NetworkError>
     where
-        C: BlockProvider + HeaderProvider + Clone + Unpin + 'static,
+        C: BlockProvider + HeaderProvider + NodeDataProvider + Clone + Unpin + 'static,
     {
         let client = config.client.clone();
         let (handle, network, _txpool, eth) =
             NetworkManager::builder(config).await?.request_handler(client).split_with_handle();
 
         let known_peers_file = self.network.persistent_peers_file();
+        let ban_list_file = self.network.persistent_ban_list_file();
         task_executor.spawn_critical_with_signal("p2p network task", |shutdown| {
-            run_network_until_shutdown(shutdown, network, known_peers_file)
+            run_network_until_shutdown(shutdown, network, known_peers_file, ban_list_file)
         });
 
         task_executor.spawn_critical("p2p eth request handler", eth);
@@ -523,7 +701,10 @@ impl Command {
                     .set(SenderRecoveryStage {
                         commit_threshold: stage_conf.sender_recovery.commit_threshold,
                     })
-                    .set(ExecutionStage::new(factory, stage_conf.execution.commit_threshold)),
+                    .set(
+                        ExecutionStage::new(factory, stage_conf.execution.commit_threshold)
+                            .with_state_history_blocks(self.mode.state_history_blocks()),
+                    ),
             )
             .build();
 
@@ -531,12 +712,28 @@ impl Command {
     }
 }
 
+/// Prunes the state history every time the pipeline finished syncing to a new tip.
+async fn run_pruner<DB: Database>(
+    pruner: Pruner<DB>,
+    mut events: impl Stream<Item = PipelineEvent> + Unpin,
+) {
+    while let Some(event) = events.next().await {
+        if let PipelineEvent::Ran { stage_id: FINISH, result } = event {
+            if let Err(error) = pruner.run(result.stage_progress) {
+                warn!(target: "reth::cli", %error, tip = result.stage_progress, "Failed to prune state history");
+            }
+        }
+    }
+}
+
 /// Drives the [NetworkManager] future until a [Shutdown](reth_tasks::shutdown::Shutdown) signal is
-/// received. If configured, this writes known peers to `persistent_peers_file` afterwards.
+/// received. If configured, this writes known peers to `persistent_peers_file` and the temporary
+/// bans to `persistent_ban_list_file` afterwards.
 async fn run_network_until_shutdown<C>(
     shutdown: reth_tasks::shutdown::Shutdown,
     network: NetworkManager<C>,
     persistent_peers_file: Option<PathBuf>,
+    persistent_ban_list_file: Option<PathBuf>,
 ) where
     C: BlockProvider + HeaderProvider + Clone + Unpin + 'static,
 {
@@ -562,6 +759,19 @@ async fn run_network_until_shutdown<C>(
             }
         }
     }
+
+    if let Some(file_path) = persistent_ban_list_file {
+        let bans = network.persisted_bans();
+        trace!(target : "reth::cli", ban_list_file =?file_path, num_peers=%bans.peers.len(), num_ips=%bans.ips.len(), "Saving banned peers");
+        match bans.save(&file_path) {
+            Ok(_) => {
+                info!(target: "reth::cli", ban_list_file=?file_path, "Wrote banned peers to file");
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", ?err, ban_list_file=?file_path, "Failed to write banned peers to file");
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -581,4 +791,26 @@ mod tests {
             assert_eq!(args.chain.chain, chain.parse().unwrap());
         }
     }
+
+    #[test]
+    fn parse_metrics_args() {
+        let args = Command::parse_from(["reth", "--metrics", "127.0.0.1:9001"]);
+        assert_eq!(args.metrics, Some(SocketAddr::from(([127, 0, 0, 1], 9001))));
+
+        let args = Command::parse_from([
+            "reth",
+            "--metrics-push-gateway",
+            "http://localhost:9091/metrics",
+        ]);
+        assert_eq!(args.metrics_push_gateway.as_deref(), Some("http://localhost:9091/metrics"));
+
+        assert!(Command::try_parse_from([
+            "reth",
+            "--metrics",
+            "127.0.0.1:9001",
+            "--metrics-push-gateway",
+            "http://localhost:9091/metrics"
+        ])
+        .is_err());
+    }
 }
@@ -0,0 +1,181 @@
+//! Persistence of the sync progress across node restarts.
+
+use reth_primitives::{keccak256, BlockNumber, H256};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use tracing::{info, trace, warn};
+
+/// The sync progress of the node.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncState {
+    /// The highest header committed by the headers stage.
+    pub highest_downloaded_header: BlockNumber,
+    /// The highest block committed by the execution stage.
+    pub highest_executed_block: BlockNumber,
+    /// The hash of the highest block the consensus layer marked as finalized.
+    pub highest_finalized_block: Option<H256>,
+}
+
+impl SyncState {
+    /// Returns the checksum of the state.
+    fn checksum(&self) -> H256 {
+        keccak256(serde_json::to_vec(self).expect("serialization does not fail"))
+    }
+}
+
+/// The contents of the sync state file.
+#[derive(Debug, Serialize, Deserialize)]
+struct SyncStateFileContents {
+    /// The sync state.
+    state: SyncState,
+    /// The [SyncState::checksum] of the state.
+    checksum: H256,
+}
+
+/// A [SyncState] that is persisted to a file.
+#[derive(Debug)]
+pub struct SyncStateFile {
+    /// The path of the file.
+    path: PathBuf,
+    /// The last persisted state.
+    state: SyncState,
+}
+
+impl SyncStateFile {
+    /// Loads the sync state from the file at the given path.
+    ///
+    /// A missing, unreadable or corrupted file is treated as empty, in which case the stages resume
+    /// from the progress stored in the database.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let state = match Self::read(&path) {
+            Ok(Some(state)) => state,
+            Ok(None) => SyncState::default(),
+            Err(error) => {
+                warn!(target: "reth::cli", path = %path.display(), %error, "Ignoring invalid sync state file");
+                SyncState::default()
+            }
+        };
+        Self { path, state }
+    }
+
+    /// Returns the last persisted state.
+    pub fn state(&self) -> &SyncState {
+        &self.state
+    }
+
+    /// Reconciles the persisted progress with the checkpoints of the headers and execution stages
+    /// in the database, which the stages resume from.
+    ///
+    /// The checkpoints of the database are authoritative. If the persisted progress is ahead of
+    /// them, e.g. because the database was replaced by an older copy, it's reset to the
+    /// checkpoints and the stages re-scan from there.
+    pub fn resume(
+        &mut self,
+        headers_checkpoint: BlockNumber,
+        execution_checkpoint: BlockNumber,
+    ) -> io::Result<()> {
+        let SyncState { highest_downloaded_header, highest_executed_block, .. } = self.state;
+        if highest_downloaded_header > headers_checkpoint ||
+            highest_executed_block > execution_checkpoint
+        {
+            warn!(target: "reth::cli", highest_downloaded_header, highest_executed_block, headers_checkpoint, execution_checkpoint, "Sync state is ahead of the database, re-scanning from the database checkpoints");
+        } else if highest_downloaded_header > 0 || highest_executed_block > 0 {
+            info!(target: "reth::cli", highest_downloaded_header, highest_executed_block, "Resuming sync from the persisted progress");
+        }
+        self.update(|state| {
+            state.highest_downloaded_header = headers_checkpoint;
+            state.highest_executed_block = execution_checkpoint;
+        })
+    }
+
+    /// Applies the given update to the state and persists it if it changed.
+    pub fn update(&mut self, f: impl FnOnce(&mut SyncState)) -> io::Result<()> {
+        let mut state = self.state.clone();
+        f(&mut state);
+        if state == self.state {
+            return Ok(())
+        }
+        self.write(&state)?;
+        self.state = state;
+        Ok(())
+    }
+
+    fn read(path: &Path) -> io::Result<Option<SyncState>> {
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        let SyncStateFileContents { state, checksum } = serde_json::from_slice(&data)?;
+        if state.checksum() != checksum {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "checksum mismatch"))
+        }
+        Ok(Some(state))
+    }
+
+    /// Writes the state to a temporary file which then replaces the file, so the file is never
+    /// partially written.
+    fn write(&self, state: &SyncState) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = SyncStateFileContents { state: state.clone(), checksum: state.checksum() };
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&contents)?)?;
+        fs::rename(&tmp, &self.path)?;
+        trace!(target: "reth::cli", path = %self.path.display(), ?state, "Persisted sync state");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persists_sync_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sync-state.json");
+
+        let mut file = SyncStateFile::load(&path);
+        assert_eq!(file.state(), &SyncState::default());
+        file.update(|state| {
+            state.highest_downloaded_header = 10;
+            state.highest_finalized_block = Some(H256::random());
+        })
+        .unwrap();
+        assert_eq!(SyncStateFile::load(&path).state(), file.state());
+
+        // a corrupted file is ignored
+        let mut contents: serde_json::Value =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        contents["state"]["highestDownloadedHeader"] = 11.into();
+        fs::write(&path, serde_json::to_vec(&contents).unwrap()).unwrap();
+        assert_eq!(SyncStateFile::load(&path).state(), &SyncState::default());
+    }
+
+    #[test]
+    fn resumes_from_database_checkpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sync-state.json");
+
+        let mut file = SyncStateFile::load(&path);
+        file.update(|state| {
+            state.highest_downloaded_header = 100;
+            state.highest_executed_block = 50;
+        })
+        .unwrap();
+
+        // the database is behind the persisted progress
+        let mut file = SyncStateFile::load(&path);
+        assert_eq!(file.state().highest_downloaded_header, 100);
+        file.resume(80, 50).unwrap();
+        let state = SyncStateFile::load(&path).state().clone();
+        assert_eq!((state.highest_downloaded_header, state.highest_executed_block), (80, 50));
+    }
+}
@@ -12,11 +12,13 @@ use reth_interfaces::p2p::bodies::client::BodiesClient;
 use reth_primitives::{BlockHashOrNumber, ChainSpec, NodeRecord};
 use reth_provider::ShareableDatabase;
 use reth_staged_sync::{
-    utils::{chainspec::chain_spec_value_parser, hash_or_num_value_parser},
+    utils::{chainspec::genesis_value_parser, hash_or_num_value_parser},
     Config,
 };
 use std::sync::Arc;
 
+mod ping;
+
 /// `reth p2p` command
 #[derive(Debug, Parser)]
 pub struct Command {
@@ -26,7 +28,8 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain or the path to a chain specification file,
+    /// in reth's format or a geth `genesis.json`.
     ///
     /// Built-in chains:
     /// - mainnet
@@ -37,7 +40,7 @@ pub struct Command {
         value_name = "CHAIN_OR_PATH",
         verbatim_doc_comment,
         default_value = "mainnet",
-        value_parser = chain_spec_value_parser
+        value_parser = genesis_value_parser
     )]
     chain: Arc<ChainSpec>,
 
@@ -79,10 +82,24 @@ pub enum Subcommands {
         #[arg(value_parser = hash_or_num_value_parser)]
         id: BlockHashOrNumber,
     },
+    /// Check that a peer is reachable.
+    ///
+    /// Connects to the peer, completes the RLPx handshake, prints the capabilities of the peer and
+    /// the round-trip time of a `Ping`.
+    Ping {
+        /// The enode URL of the peer, e.g. `enode://<pubkey>@<ip>:<port>`
+        #[arg(value_name = "ENODE_URL")]
+        peer: NodeRecord,
+    },
 }
 impl Command {
     /// Execute `p2p` command
     pub async fn execute(&self) -> eyre::Result<()> {
+        // the ping connects to the peer directly, without starting the network
+        if let Subcommands::Ping { peer } = self.command {
+            return ping::ping(peer).await
+        }
+
         let tempdir = tempfile::TempDir::new()?;
         let noop_db = Arc::new(Env::<WriteMap>::open(&tempdir.into_path(), EnvKind::RW)?);
 
@@ -99,7 +116,7 @@ impl Command {
         config.peers.connect_trusted_nodes_only = self.trusted_only;
 
         let mut network_config_builder =
-            config.network_config(self.nat, None).chain_spec(self.chain.clone());
+            config.network_config(self.nat, None, None).chain_spec(self.chain.clone());
 
         network_config_builder = self.discovery.apply_to_builder(network_config_builder);
 
@@ -152,8 +169,23 @@ impl Command {
                 let body = result.into_iter().next().unwrap();
                 println!("Successfully downloaded body: {body:?}")
             }
+            Subcommands::Ping { .. } => unreachable!("handled before starting the network"),
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ping_command() {
+        let enode = "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303";
+        let args = Command::parse_from(["reth", "ping", enode]);
+        let Subcommands::Ping { peer } = args.command else { panic!("expected ping command") };
+        assert_eq!(peer, enode.parse().unwrap());
+        assert_eq!(peer.tcp_port, 30303);
+    }
+}
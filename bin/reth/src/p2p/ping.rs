@@ -0,0 +1,107 @@
+//! Connectivity check of a single peer.
+use eyre::WrapErr;
+use futures::{SinkExt, StreamExt};
+use reth_ecies::{stream::ECIESStream, util::pk2id};
+use reth_eth_wire::{DisconnectReason, HelloMessage, P2PMessage, P2PMessageID, UnauthedP2PStream};
+use reth_network::config::rng_secret_key;
+use reth_primitives::{
+    bytes::{Bytes, BytesMut},
+    NodeRecord,
+};
+use reth_rlp::{Decodable, Encodable};
+use secp256k1::SECP256K1;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+/// How long the peer has to complete the handshakes and answer the ping.
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connects to the peer, completes the RLPx and `Hello` handshakes and measures the round-trip
+/// time of a `Ping`.
+///
+/// Prints the `Hello` message of the peer and the round-trip time.
+pub(crate) async fn ping(peer: NodeRecord) -> eyre::Result<()> {
+    tokio::time::timeout(PING_TIMEOUT, ping_peer(peer)).await.map_err(|_| {
+        eyre::eyre!("Timed out after {PING_TIMEOUT:?} waiting for {}", peer.tcp_addr())
+    })?
+}
+
+async fn ping_peer(peer: NodeRecord) -> eyre::Result<()> {
+    let addr = peer.tcp_addr();
+    println!("Connecting to {} at {addr}...", peer.id);
+    let tcp = TcpStream::connect(addr)
+        .await
+        .wrap_err_with(|| format!("TCP connection to {addr} failed"))?;
+
+    let secret_key = rng_secret_key();
+    let ecies =
+        ECIESStream::connect(tcp, secret_key, peer.id).await.wrap_err("RLPx handshake failed")?;
+
+    let hello = HelloMessage::builder(pk2id(&secret_key.public_key(SECP256K1))).port(0).build();
+    let (stream, their_hello) =
+        UnauthedP2PStream::new(ecies).handshake(hello).await.wrap_err("Hello handshake failed")?;
+
+    println!("Client: {}", their_hello.client_version);
+    println!("Protocol version: {:?}", their_hello.protocol_version);
+    let capabilities = their_hello
+        .capabilities
+        .iter()
+        .map(|cap| format!("{}/{}", cap.name, cap.version))
+        .collect::<Vec<_>>();
+    println!("Capabilities: {}", capabilities.join(", "));
+    let shared = stream.shared_capability();
+    println!("Negotiated: {}/{}", shared.name(), shared.version());
+
+    // the ping is sent on the raw stream, since the p2p stream consumes pongs internally
+    let mut stream = stream.into_inner();
+    stream.send(encode(P2PMessage::Ping)).await?;
+    let sent = Instant::now();
+    while let Some(msg) = stream.next().await {
+        let msg = msg?;
+        match msg.first().copied() {
+            Some(id) if id == P2PMessageID::Pong as u8 => {
+                println!("Pong received, round-trip time: {:?}", sent.elapsed());
+                return Ok(())
+            }
+            Some(id) if id == P2PMessageID::Ping as u8 => {
+                stream.send(encode(P2PMessage::Pong)).await?;
+            }
+            Some(id) if id == P2PMessageID::Disconnect as u8 => {
+                eyre::bail!(
+                    "Peer disconnected before answering the ping: {}",
+                    disconnect_reason(&msg)
+                )
+            }
+            // subprotocol messages, e.g. the `Status` of the peer
+            _ => {}
+        }
+    }
+
+    eyre::bail!("Connection closed before the peer answered the ping")
+}
+
+/// Encodes a `p2p` message as it is sent after the `Hello` handshake.
+fn encode(msg: P2PMessage) -> Bytes {
+    let mut buf = BytesMut::with_capacity(msg.length());
+    msg.encode(&mut buf);
+    buf.freeze()
+}
+
+/// Decodes the reason of a snappy compressed `Disconnect` message.
+fn disconnect_reason(msg: &[u8]) -> String {
+    snap::raw::Decoder::new()
+        .decompress_vec(&msg[1..])
+        .ok()
+        .and_then(|rlp| DisconnectReason::decode(&mut &rlp[..]).ok())
+        .map_or_else(|| "unknown reason".to_string(), |reason| reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_ping() {
+        assert_eq!(&encode(P2PMessage::Ping)[..], &[0x02, 0x01, 0x00, 0xc0]);
+    }
+}
@@ -3,14 +3,53 @@
 use eyre::WrapErr;
 use metrics_exporter_prometheus::PrometheusBuilder;
 use metrics_util::layers::{PrefixLayer, Stack};
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
+use tracing::error;
 
-pub(crate) fn initialize(listen_addr: SocketAddr) -> eyre::Result<()> {
-    let (recorder, exporter) = PrometheusBuilder::new()
-        .with_http_listener(listen_addr)
+/// How often the metrics are pushed to a push gateway.
+const PUSH_GATEWAY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How the metrics are exposed.
+#[derive(Debug, Clone)]
+pub(crate) enum MetricsExporter {
+    /// Serve the metrics at `/metrics` on the given address.
+    Http(SocketAddr),
+    /// Periodically push the metrics to the Prometheus push gateway at the given URL.
+    PushGateway(String),
+}
+
+/// Installs the Prometheus recorder and starts the exporter.
+///
+/// The exporter runs on a dedicated thread with its own Tokio runtime, so that the metrics remain
+/// available when the runtime of the node is under high load.
+pub(crate) fn initialize(exporter: MetricsExporter) -> eyre::Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
         .build()
-        .wrap_err("Could not build Prometheus endpoint.")?;
-    tokio::task::spawn(exporter);
+        .wrap_err("Could not build the metrics runtime.")?;
+
+    let builder = match exporter {
+        MetricsExporter::Http(listen_addr) => {
+            PrometheusBuilder::new().with_http_listener(listen_addr)
+        }
+        MetricsExporter::PushGateway(endpoint) => PrometheusBuilder::new()
+            .with_push_gateway(endpoint, PUSH_GATEWAY_INTERVAL)
+            .wrap_err("Invalid push gateway URL.")?,
+    };
+    // the HTTP listener is bound on build, which must happen within the runtime it is served on
+    let (recorder, exporter) = {
+        let _guard = runtime.enter();
+        builder.build().wrap_err("Could not build Prometheus endpoint.")?
+    };
+    std::thread::Builder::new()
+        .name("reth-metrics".to_string())
+        .spawn(move || {
+            if let Err(err) = runtime.block_on(exporter) {
+                error!(target: "reth::cli", ?err, "Metrics exporter failed");
+            }
+        })
+        .wrap_err("Could not spawn the metrics thread.")?;
+
     Stack::new(recorder)
         .push(PrefixLayer::new("reth"))
         .install()
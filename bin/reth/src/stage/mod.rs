@@ -4,7 +4,7 @@
 use crate::{
     args::NetworkArgs,
     dirs::{ConfigPath, DbPath, PlatformPath},
-    prometheus_exporter,
+    prometheus_exporter::{self, MetricsExporter},
 };
 use clap::{Parser, ValueEnum};
 use reth_beacon_consensus::BeaconConsensus;
@@ -12,7 +12,7 @@ use reth_downloaders::bodies::bodies::BodiesDownloaderBuilder;
 use reth_primitives::ChainSpec;
 use reth_provider::{ShareableDatabase, Transaction};
 use reth_staged_sync::{
-    utils::{chainspec::chain_spec_value_parser, init::init_db},
+    utils::{chainspec::genesis_value_parser, init::init_db},
     Config,
 };
 use reth_stages::{
@@ -41,7 +41,8 @@ pub struct Command {
 
     /// The chain this node is running.
     ///
-    /// Possible values are either a built-in chain or the path to a chain specification file.
+    /// Possible values are either a built-in chain or the path to a chain specification file,
+    /// in reth's format or a geth `genesis.json`.
     ///
     /// Built-in chains:
     /// - mainnet
@@ -52,7 +53,7 @@ pub struct Command {
         value_name = "CHAIN_OR_PATH",
         verbatim_doc_comment,
         default_value = "mainnet",
-        value_parser = chain_spec_value_parser
+        value_parser = genesis_value_parser
     )]
     chain: Arc<ChainSpec>,
 
@@ -82,6 +83,12 @@ pub struct Command {
     #[arg(long, short)]
     skip_unwind: bool,
 
+    /// Execute the transactions of each block speculatively in parallel.
+    ///
+    /// Only used by the execution stage.
+    #[arg(long)]
+    parallel: bool,
+
     #[clap(flatten)]
     network: NetworkArgs,
 }
@@ -103,7 +110,7 @@ impl Command {
 
         if let Some(listen_addr) = self.metrics {
             info!(target: "reth::cli", "Starting metrics endpoint at {}", listen_addr);
-            prometheus_exporter::initialize(listen_addr)?;
+            prometheus_exporter::initialize(MetricsExporter::Http(listen_addr))?;
         }
 
         let config: Config = confy::load_path(&self.config).unwrap_or_default();
@@ -170,6 +177,15 @@ impl Command {
                 }
                 stage.execute(&mut tx, input).await?;
             }
+            StageEnum::Execution if self.parallel => {
+                let factory = reth_executor::ParallelFactory::new(self.chain.clone());
+                let mut stage = ExecutionStage::new(factory, 10_000);
+                stage.commit_threshold = num_blocks;
+                if !self.skip_unwind {
+                    stage.unwind(&mut tx, unwind).await?;
+                }
+                stage.execute(&mut tx, input).await?;
+            }
             StageEnum::Execution => {
                 let factory = reth_executor::Factory::new(self.chain.clone());
                 let mut stage = ExecutionStage::new(factory, 10_000);
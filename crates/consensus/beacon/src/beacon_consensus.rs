@@ -1,7 +1,9 @@
 //! Consensus for ethereum network
 use reth_consensus_common::validation;
 use reth_interfaces::consensus::{Consensus, ConsensusError, ForkchoiceState};
-use reth_primitives::{ChainSpec, Hardfork, SealedBlock, SealedHeader, EMPTY_OMMER_ROOT, U256};
+use reth_primitives::{
+    ChainSpec, ForkCondition, Hardfork, SealedBlock, SealedHeader, EMPTY_OMMER_ROOT, U256,
+};
 use std::sync::Arc;
 use tokio::sync::watch;
 
@@ -55,8 +57,8 @@ impl Consensus for BeaconConsensus {
         header: &SealedHeader,
         total_difficulty: U256,
     ) -> Result<(), ConsensusError> {
-        if self.chain_spec.fork(Hardfork::Paris).active_at_ttd(total_difficulty, header.difficulty)
-        {
+        let paris = self.chain_spec.fork(Hardfork::Paris);
+        if paris.active_at_ttd(total_difficulty, header.difficulty) {
             // EIP-3675: Upgrade consensus to Proof-of-Stake:
             // https://eips.ethereum.org/EIPS/eip-3675#replacing-difficulty-with-0
             if header.difficulty != U256::ZERO {
@@ -74,6 +76,12 @@ impl Consensus for BeaconConsensus {
             // mixHash is used instead of difficulty inside EVM
             // https://eips.ethereum.org/EIPS/eip-4399#using-mixhash-field-instead-of-difficulty
         } else {
+            // Blocks before the merge are mined, so they have a difficulty. Chains that don't merge
+            // at a terminal total difficulty are not checked.
+            if matches!(paris, ForkCondition::TTD { .. }) && header.difficulty == U256::ZERO {
+                return Err(ConsensusError::PreMergeDifficultyIsZero)
+            }
+
             // TODO Consensus checks for old blocks:
             //  * difficulty, mix_hash & nonce aka PoW stuff
             // low priority as syncing is done in reverse order
@@ -94,8 +102,8 @@ impl Consensus for BeaconConsensus {
 #[cfg(test)]
 mod test {
     use super::BeaconConsensus;
-    use reth_interfaces::consensus::Consensus;
-    use reth_primitives::{ChainSpecBuilder, U256};
+    use reth_interfaces::consensus::{Consensus, ConsensusError};
+    use reth_primitives::{ChainSpecBuilder, ForkCondition, Hardfork, Header, U256};
     use std::sync::Arc;
 
     #[test]
@@ -104,4 +112,34 @@ mod test {
         let (consensus, _) = BeaconConsensus::builder().build(chain_spec);
         assert!(consensus.has_block_reward(U256::ZERO, U256::ZERO));
     }
+
+    #[test]
+    fn test_difficulty_regarding_merge() {
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().build());
+        let ttd = chain_spec.fork(Hardfork::Paris).ttd().unwrap();
+        let (consensus, _) = BeaconConsensus::builder().build(chain_spec);
+
+        let mined = Header { difficulty: U256::from(1), ..Default::default() }.seal_slow();
+        let proof_of_stake = Header::default().seal_slow();
+        assert_eq!(consensus.validate_header(&mined, ttd), Ok(()));
+        assert_eq!(
+            consensus.validate_header(&proof_of_stake, U256::ZERO),
+            Err(ConsensusError::PreMergeDifficultyIsZero)
+        );
+        assert_eq!(
+            consensus.validate_header(&mined, ttd + U256::from(1)),
+            Err(ConsensusError::TheMergeDifficultyIsNotZero)
+        );
+    }
+
+    #[test]
+    fn test_zero_difficulty_without_ttd() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::mainnet().with_fork(Hardfork::Paris, ForkCondition::Never).build(),
+        );
+        let (consensus, _) = BeaconConsensus::builder().build(chain_spec);
+
+        let header = Header::default().seal_slow();
+        assert_eq!(consensus.validate_header(&header, U256::ZERO), Ok(()));
+    }
 }
@@ -7,5 +7,8 @@
 
 //! Commonly used consensus methods.
 
+/// Validation of the transition to proof-of-stake.
+pub mod merge;
+
 /// Collection of consensus validation methods.
 pub mod validation;
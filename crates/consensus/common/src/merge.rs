@@ -0,0 +1,98 @@
+use reth_interfaces::consensus::ConsensusError;
+use reth_primitives::{ChainSpec, Hardfork, Header, U256};
+use std::sync::Arc;
+
+/// Tracks the total difficulty of a chain to validate the difficulty of its headers in regards to
+/// the merge.
+///
+/// A block is after the merge if the total difficulty of its parent reached the terminal total
+/// difficulty of the chain, see [EIP-3675](https://eips.ethereum.org/EIPS/eip-3675). Blocks before
+/// the merge must have a non-zero difficulty, blocks after it must have a difficulty of zero. The
+/// difficulty of chains without a terminal total difficulty is not checked.
+#[derive(Debug, Clone)]
+pub struct MergeTransitionValidator {
+    /// The chain spec with the terminal total difficulty.
+    chain_spec: Arc<ChainSpec>,
+    /// The total difficulty of all validated headers.
+    total_difficulty: U256,
+}
+
+impl MergeTransitionValidator {
+    /// Create a validator for a chain whose first validated header is the genesis header.
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        Self::with_total_difficulty(chain_spec, U256::ZERO)
+    }
+
+    /// Create a validator that continues after a block with the given total difficulty.
+    pub fn with_total_difficulty(chain_spec: Arc<ChainSpec>, total_difficulty: U256) -> Self {
+        Self { chain_spec, total_difficulty }
+    }
+
+    /// The total difficulty of all validated headers.
+    pub fn total_difficulty(&self) -> U256 {
+        self.total_difficulty
+    }
+
+    /// Returns `true` if a block whose parent has the given total difficulty is after the merge.
+    ///
+    /// Chains without a terminal total difficulty never merge.
+    pub fn is_post_merge(&self, total_difficulty: U256) -> bool {
+        self.chain_spec.fork(Hardfork::Paris).ttd().map_or(false, |ttd| total_difficulty >= ttd)
+    }
+
+    /// Validate the difficulty of the next header of the chain and add it to the total difficulty.
+    pub fn validate_header(&mut self, header: &Header) -> Result<(), ConsensusError> {
+        if self.is_post_merge(self.total_difficulty) {
+            if header.difficulty != U256::ZERO {
+                return Err(ConsensusError::TheMergeDifficultyIsNotZero)
+            }
+        } else if self.chain_spec.fork(Hardfork::Paris).ttd().is_some() &&
+            header.difficulty == U256::ZERO
+        {
+            return Err(ConsensusError::PreMergeDifficultyIsZero)
+        }
+
+        self.total_difficulty = self.total_difficulty.saturating_add(header.difficulty);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{constants::MAINNET_TERMINAL_TOTAL_DIFFICULTY, MAINNET};
+
+    #[test]
+    fn classify_blocks_around_ttd() {
+        let ttd = U256::from(MAINNET_TERMINAL_TOTAL_DIFFICULTY);
+        assert_eq!(MAINNET.fork(Hardfork::Paris).ttd(), Some(ttd));
+
+        let difficulty = U256::from(10);
+        let mut validator = MergeTransitionValidator::with_total_difficulty(
+            MAINNET.clone().into(),
+            ttd - difficulty * U256::from(2),
+        );
+        assert!(!validator.is_post_merge(validator.total_difficulty()));
+
+        let header = Header { difficulty, ..Default::default() };
+        assert_eq!(
+            validator.validate_header(&Header::default()),
+            Err(ConsensusError::PreMergeDifficultyIsZero)
+        );
+        assert_eq!(validator.validate_header(&header), Ok(()));
+
+        // the terminal block reaches the ttd, but is still mined
+        assert!(!validator.is_post_merge(validator.total_difficulty()));
+        assert_eq!(validator.validate_header(&header), Ok(()));
+        assert_eq!(validator.total_difficulty(), ttd);
+
+        // the first block after the terminal block is after the merge
+        assert!(validator.is_post_merge(validator.total_difficulty()));
+        assert_eq!(
+            validator.validate_header(&header),
+            Err(ConsensusError::TheMergeDifficultyIsNotZero)
+        );
+        assert_eq!(validator.validate_header(&Header::default()), Ok(()));
+        assert_eq!(validator.total_difficulty(), ttd);
+    }
+}
@@ -1,15 +1,18 @@
 //! Collection of methods for block validation.
 use reth_interfaces::{consensus::ConsensusError, Result as RethResult};
 use reth_primitives::{
-    constants, BlockNumber, ChainSpec, Hardfork, Header, InvalidTransactionError, SealedBlock,
-    SealedHeader, Transaction, TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxLegacy,
+    constants, BlobTransaction, BlockNumber, ChainSpec, Hardfork, Header, HeaderValidator,
+    InvalidTransactionError, SealedBlock, SealedHeader, Transaction, TransactionSignedEcRecovered,
+    TxEip1559, TxEip2930, TxLegacy, H256, U256,
 };
 use reth_provider::{AccountProvider, HeaderProvider, WithdrawalsProvider};
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     time::SystemTime,
 };
 
+pub use reth_primitives::calculate_next_block_base_fee;
+
 /// Validate header standalone
 pub fn validate_header_standalone(
     header: &SealedHeader,
@@ -62,11 +65,13 @@ pub fn validate_header_standalone(
 
 /// Validate a transaction in regards to a block header.
 ///
-/// The only parameter from the header that affects the transaction is `base_fee`.
+/// The only parameter from the header that affects the transaction is `base_fee`, the block number
+/// and timestamp decide which transaction types are enabled.
 pub fn validate_transaction_regarding_header(
     transaction: &Transaction,
     chain_spec: &ChainSpec,
     at_block_number: BlockNumber,
+    at_timestamp: u64,
     base_fee: Option<u64>,
 ) -> Result<(), ConsensusError> {
     let chain_id = match transaction {
@@ -103,6 +108,29 @@ pub fn validate_transaction_regarding_header(
                 return Err(InvalidTransactionError::PriorityFeeMoreThenMaxFee.into())
             }
 
+            Some(*chain_id)
+        }
+        Transaction::Blob(BlobTransaction {
+            chain_id,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            blob_versioned_hashes,
+            ..
+        }) => {
+            // EIP-4844: Shard Blob Transactions https://eips.ethereum.org/EIPS/eip-4844
+            if !chain_spec.fork(Hardfork::Cancun).active_at_timestamp(at_timestamp) {
+                return Err(InvalidTransactionError::Eip4844Disabled.into())
+            }
+
+            // a blob transaction has to carry at least one blob
+            if blob_versioned_hashes.is_empty() {
+                return Err(InvalidTransactionError::BlobVersionedHashesEmpty.into())
+            }
+
+            if max_priority_fee_per_gas > max_fee_per_gas {
+                return Err(InvalidTransactionError::PriorityFeeMoreThenMaxFee.into())
+            }
+
             Some(*chain_id)
         }
     };
@@ -140,6 +168,7 @@ pub fn validate_all_transaction_regarding_block_and_nonces<
             transaction,
             chain_spec,
             header.number,
+            header.timestamp,
             header.base_fee_per_gas,
         )?;
 
@@ -241,30 +270,44 @@ pub fn validate_block_standalone(
     Ok(())
 }
 
-/// Calculate base fee for next block. EIP-1559 spec
-pub fn calculate_next_block_base_fee(gas_used: u64, gas_limit: u64, base_fee: u64) -> u64 {
-    let gas_target = gas_limit / constants::EIP1559_ELASTICITY_MULTIPLIER;
+/// Calculate the fee for the blob gas used by a block with the given excess blob gas. EIP-4844 spec
+pub fn blob_fee(blob_gas_used: u64, excess_blob_gas: u64) -> U256 {
+    U256::from(blob_gas_used).saturating_mul(blob_gasprice(excess_blob_gas))
+}
 
-    if gas_used == gas_target {
-        return base_fee
-    }
-    if gas_used > gas_target {
-        let gas_used_delta = gas_used - gas_target;
-        let base_fee_delta = std::cmp::max(
-            1,
-            base_fee as u128 * gas_used_delta as u128 /
-                gas_target as u128 /
-                constants::EIP1559_BASE_FEE_MAX_CHANGE_DENOMINATOR as u128,
-        );
-        base_fee + (base_fee_delta as u64)
-    } else {
-        let gas_used_delta = gas_target - gas_used;
-        let base_fee_per_gas_delta = base_fee as u128 * gas_used_delta as u128 /
-            gas_target as u128 /
-            constants::EIP1559_BASE_FEE_MAX_CHANGE_DENOMINATOR as u128;
+/// Calculate the price of blob gas for the given excess blob gas. EIP-4844 spec
+pub fn blob_gasprice(excess_blob_gas: u64) -> U256 {
+    fake_exponential(
+        constants::EIP4844_MIN_BLOB_GASPRICE,
+        excess_blob_gas,
+        constants::EIP4844_BLOB_GASPRICE_UPDATE_FRACTION,
+    )
+}
+
+/// Calculate the excess blob gas of a block from its parent. EIP-4844 spec
+///
+/// The excess grows with the blob gas a block uses above the target and is used up by blocks
+/// below the target.
+pub fn calculate_excess_blob_gas(parent_excess_blob_gas: u64, parent_blob_gas_used: u64) -> u64 {
+    (parent_excess_blob_gas.saturating_add(parent_blob_gas_used))
+        .saturating_sub(constants::EIP4844_TARGET_DATA_GAS_PER_BLOCK)
+}
 
-        base_fee.saturating_sub(base_fee_per_gas_delta as u64)
+/// Approximates `factor * e ** (numerator / denominator)` using a Taylor expansion, as defined in
+/// EIP-4844.
+fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> U256 {
+    let numerator = U256::from(numerator);
+    let denominator = U256::from(denominator);
+
+    let mut i = U256::from(1);
+    let mut output = U256::ZERO;
+    let mut numerator_accum = U256::from(factor) * denominator;
+    while numerator_accum > U256::ZERO {
+        output = output.saturating_add(numerator_accum);
+        numerator_accum = numerator_accum.saturating_mul(numerator) / (denominator * i);
+        i += U256::from(1);
     }
+    output / denominator
 }
 
 /// Validate block in regards to parent
@@ -318,24 +361,77 @@ pub fn validate_header_regarding_parent(
         })
     }
 
-    // EIP-1559 check base fee
-    if chain_spec.fork(Hardfork::London).active_at_block(child.number) {
-        let base_fee = child.base_fee_per_gas.ok_or(ConsensusError::BaseFeeMissing)?;
+    HeaderValidator::new(chain_spec).validate_base_fee(child, parent)?;
+
+    // EIP-4844: the excess blob gas is derived from the blob gas of the parent. The fields are
+    // zero for a parent before Cancun.
+    if chain_spec.fork(Hardfork::Cancun).active_at_timestamp(child.timestamp) {
+        let excess_blob_gas = child.excess_blob_gas.ok_or(ConsensusError::ExcessBlobGasMissing)?;
+        let expected_excess_blob_gas = calculate_excess_blob_gas(
+            parent.excess_blob_gas.unwrap_or_default(),
+            parent.blob_gas_used.unwrap_or_default(),
+        );
+        if excess_blob_gas != expected_excess_blob_gas {
+            return Err(ConsensusError::ExcessBlobGasDiff {
+                expected: expected_excess_blob_gas,
+                got: excess_blob_gas,
+            })
+        }
+    }
+
+    Ok(())
+}
+
+/// The maximum number of ommers a block can include.
+pub const MAX_OMMERS: usize = 2;
+
+/// The number of ancestors of a block that an ommer can be a child of.
+pub const MAX_OMMER_ANCESTORS: usize = 7;
+
+/// Validate the ommers of a pre-merge block.
+///
+/// `ancestors` are the most recent ancestors of the block, starting with its parent. Only the
+/// first [MAX_OMMER_ANCESTORS] are considered. `seen_ommers` are the hashes of the ommers that were
+/// already included by these ancestors.
+///
+/// Checks:
+///  Block has at most [MAX_OMMERS] ommers.
+///  Ommers are not included twice and are not the block itself or one of its ancestors.
+///  Ommers are children of an ancestor other than the parent of the block, so they are
+///  siblings of an ancestor.
+///  Ommer headers are valid, standalone and in regards to their parent.
+pub fn validate_ommers(
+    block: &SealedBlock,
+    ancestors: &[SealedHeader],
+    seen_ommers: &HashSet<H256>,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    if block.ommers.len() > MAX_OMMERS {
+        return Err(ConsensusError::TooManyOmmers { count: block.ommers.len(), max: MAX_OMMERS })
+    }
 
-        let expected_base_fee =
-            if chain_spec.fork(Hardfork::London).transitions_at_block(child.number) {
-                constants::EIP1559_INITIAL_BASE_FEE
-            } else {
-                // This BaseFeeMissing will not happen as previous blocks are checked to have them.
-                calculate_next_block_base_fee(
-                    parent.gas_used,
-                    parent.gas_limit,
-                    parent.base_fee_per_gas.ok_or(ConsensusError::BaseFeeMissing)?,
-                )
-            };
-        if expected_base_fee != base_fee {
-            return Err(ConsensusError::BaseFeeDiff { expected: expected_base_fee, got: base_fee })
+    let ancestors = &ancestors[..ancestors.len().min(MAX_OMMER_ANCESTORS)];
+    let mut included = HashSet::with_capacity(block.ommers.len());
+    for ommer in &block.ommers {
+        let hash = ommer.hash();
+        if !included.insert(hash) {
+            return Err(ConsensusError::OmmerDuplicated { hash })
+        }
+        if seen_ommers.contains(&hash) {
+            return Err(ConsensusError::OmmerAlreadyIncluded { hash })
         }
+        if hash == block.hash() || ancestors.iter().any(|ancestor| ancestor.hash() == hash) {
+            return Err(ConsensusError::OmmerIsAncestor { hash })
+        }
+
+        let parent = ancestors
+            .iter()
+            .find(|ancestor| ancestor.hash() == ommer.parent_hash)
+            .filter(|parent| parent.hash() != block.parent_hash)
+            .ok_or(ConsensusError::OmmerParentInvalid { hash, parent_hash: ommer.parent_hash })?;
+
+        validate_header_standalone(ommer, chain_spec)?;
+        validate_header_regarding_parent(parent, ommer, chain_spec)?;
     }
 
     Ok(())
@@ -462,6 +558,166 @@ mod tests {
         }
     }
 
+    #[test]
+    fn calculate_blob_fee() {
+        // (excess_blob_gas, blob_gasprice)
+        let prices = [(0, 1), (2314057, 1), (2314058, 2), (10 * 1024 * 1024, 23)];
+        for (excess_blob_gas, price) in prices {
+            assert_eq!(blob_gasprice(excess_blob_gas), U256::from(price), "{excess_blob_gas}");
+        }
+
+        assert_eq!(blob_fee(131072, 10 * 1024 * 1024), U256::from(131072 * 23));
+    }
+
+    #[test]
+    fn calculate_excess_blob_gas_success() {
+        let target = constants::EIP4844_TARGET_DATA_GAS_PER_BLOCK;
+        let per_blob = constants::EIP4844_DATA_GAS_PER_BLOB;
+
+        assert_eq!(calculate_excess_blob_gas(0, 0), 0);
+        assert_eq!(calculate_excess_blob_gas(0, target), 0);
+        assert_eq!(calculate_excess_blob_gas(0, target + per_blob), per_blob);
+        assert_eq!(calculate_excess_blob_gas(per_blob, target - per_blob), 0);
+        assert_eq!(calculate_excess_blob_gas(2 * per_blob, target - per_blob), per_blob);
+        assert_eq!(calculate_excess_blob_gas(u64::MAX, u64::MAX), u64::MAX - target);
+    }
+
+    #[test]
+    fn validate_excess_blob_gas_regarding_parent() {
+        let chain_spec = ChainSpecBuilder::mainnet().cancun_activated().build();
+        let per_blob = constants::EIP4844_DATA_GAS_PER_BLOB;
+        let parent = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            gas_used: 15_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            timestamp: 100,
+            blob_gas_used: Some(constants::EIP4844_TARGET_DATA_GAS_PER_BLOCK + per_blob),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        }
+        .seal_slow();
+        let child = |excess_blob_gas| {
+            Header {
+                parent_hash: parent.hash(),
+                number: 2,
+                gas_limit: 30_000_000,
+                base_fee_per_gas: Some(1_000_000_000),
+                timestamp: 112,
+                blob_gas_used: Some(0),
+                excess_blob_gas,
+                ..Default::default()
+            }
+            .seal_slow()
+        };
+
+        assert_eq!(
+            validate_header_regarding_parent(&parent, &child(Some(per_blob)), &chain_spec),
+            Ok(())
+        );
+        assert_eq!(
+            validate_header_regarding_parent(&parent, &child(Some(0)), &chain_spec),
+            Err(ConsensusError::ExcessBlobGasDiff { expected: per_blob, got: 0 })
+        );
+        assert_eq!(
+            validate_header_regarding_parent(&parent, &child(None), &chain_spec),
+            Err(ConsensusError::ExcessBlobGasMissing)
+        );
+    }
+
+    /// A pre-London chain of headers, oldest first.
+    fn mock_chain(len: u64) -> Vec<SealedHeader> {
+        let genesis = Header {
+            number: 1_000_000,
+            gas_limit: 5_000_000,
+            timestamp: 1_600_000_000,
+            ..Default::default()
+        };
+        let mut chain = vec![genesis.seal_slow()];
+        for _ in 1..len {
+            chain.push(mock_child(chain.last().unwrap(), 0));
+        }
+        chain
+    }
+
+    fn mock_child(parent: &SealedHeader, extra_data: u8) -> SealedHeader {
+        Header {
+            parent_hash: parent.hash(),
+            number: parent.number + 1,
+            gas_limit: parent.gas_limit,
+            timestamp: parent.timestamp + 12 + extra_data as u64,
+            extra_data: vec![extra_data].into(),
+            ..Default::default()
+        }
+        .seal_slow()
+    }
+
+    #[test]
+    fn validate_ommers_regarding_ancestors() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let chain = mock_chain(9);
+        let parent = &chain[8];
+        let ancestors = chain.iter().rev().cloned().collect::<Vec<_>>();
+        let block = |ommers| SealedBlock {
+            header: mock_child(parent, 0),
+            body: vec![],
+            ommers,
+            withdrawals: None,
+        };
+        let validate = |ommers, seen_ommers: &HashSet<H256>| {
+            validate_ommers(&block(ommers), &ancestors, seen_ommers, &chain_spec)
+        };
+        let no_ommers = HashSet::new();
+
+        // siblings of the grandparent up to the seventh ancestor
+        let ommer = mock_child(&chain[7], 1);
+        let oldest_ommer = mock_child(&chain[2], 1);
+        assert_eq!(validate(vec![], &no_ommers), Ok(()));
+        assert_eq!(validate(vec![ommer.clone(), oldest_ommer.clone()], &no_ommers), Ok(()));
+
+        assert_eq!(
+            validate(
+                vec![ommer.clone(), oldest_ommer.clone(), mock_child(&chain[6], 1)],
+                &no_ommers
+            ),
+            Err(ConsensusError::TooManyOmmers { count: 3, max: MAX_OMMERS })
+        );
+        assert_eq!(
+            validate(vec![ommer.clone(), ommer.clone()], &no_ommers),
+            Err(ConsensusError::OmmerDuplicated { hash: ommer.hash() })
+        );
+        assert_eq!(
+            validate(vec![ommer.clone()], &HashSet::from([ommer.hash()])),
+            Err(ConsensusError::OmmerAlreadyIncluded { hash: ommer.hash() })
+        );
+        assert_eq!(
+            validate(vec![chain[7].clone()], &no_ommers),
+            Err(ConsensusError::OmmerIsAncestor { hash: chain[7].hash() })
+        );
+
+        // siblings of the block and of the eighth ancestor are not valid ommers
+        for ommer in [mock_child(parent, 1), mock_child(&chain[1], 1)] {
+            assert_eq!(
+                validate(vec![ommer.clone()], &no_ommers),
+                Err(ConsensusError::OmmerParentInvalid {
+                    hash: ommer.hash(),
+                    parent_hash: ommer.parent_hash
+                })
+            );
+        }
+
+        let invalid_ommer =
+            Header { gas_limit: 2 * parent.gas_limit, ..mock_child(&chain[7], 1).unseal() }
+                .seal_slow();
+        assert_eq!(
+            validate(vec![invalid_ommer], &no_ommers),
+            Err(ConsensusError::GasLimitInvalidIncrease {
+                parent_gas_limit: parent.gas_limit,
+                child_gas_limit: 2 * parent.gas_limit
+            })
+        );
+    }
+
     mock! {
         WithdrawalsProvider {}
 
@@ -592,6 +848,9 @@ mod tests {
             nonce: 0x0000000000000000,
             base_fee_per_gas: 0x28f0001df.into(),
             withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
         };
         // size: 0x9b5
 
@@ -681,6 +940,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn blob_transaction_without_blobs() {
+        let chain_spec = ChainSpecBuilder::mainnet().cancun_activated().build();
+        let blob_tx = |blob_versioned_hashes| {
+            Transaction::Blob(BlobTransaction {
+                chain_id: chain_spec.chain().id(),
+                max_fee_per_gas: 0x28f000fff,
+                max_priority_fee_per_gas: 0x28f000fff,
+                to: Address::random(),
+                blob_versioned_hashes,
+                ..Default::default()
+            })
+        };
+        let validate = |transaction: &Transaction| {
+            validate_transaction_regarding_header(transaction, &chain_spec, 0, 0, None)
+        };
+
+        assert_eq!(validate(&blob_tx(vec![H256::random()])), Ok(()));
+        assert_eq!(
+            validate(&blob_tx(vec![])),
+            Err(InvalidTransactionError::BlobVersionedHashesEmpty.into())
+        );
+    }
+
     #[test]
     fn valid_withdrawal_index() {
         let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
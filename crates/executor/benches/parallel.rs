@@ -0,0 +1,90 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use pprof::criterion::{Output, PProfProfiler};
+use reth_executor::{executor::Executor, parallel::ParallelExecutor};
+use reth_primitives::{
+    Address, Block, ChainSpecBuilder, Header, Signature, Transaction, TransactionKind,
+    TransactionSigned, TxLegacy, U256,
+};
+use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+use reth_revm::database::{State, SubState};
+use std::sync::Arc;
+
+/// Returns a block of transfers from distinct senders to distinct recipients, and its senders.
+///
+/// The transfers only conflict through the block beneficiary, which the parallel executor
+/// handles without re-execution.
+fn independent_transfers(count: u64) -> (MockEthProvider, Block, Vec<Address>) {
+    let provider = MockEthProvider::default();
+    let senders = (1..=count).map(Address::from_low_u64_be).collect::<Vec<_>>();
+    for sender in &senders {
+        provider.add_account(*sender, ExtendedAccount::new(0, U256::from(10u64.pow(18))));
+    }
+
+    let body = (1..=count)
+        .map(|i| {
+            TransactionSigned::from_transaction_and_signature(
+                Transaction::Legacy(TxLegacy {
+                    gas_price: 1,
+                    gas_limit: 21_000,
+                    to: TransactionKind::Call(Address::from_low_u64_be(count + i)),
+                    value: 1,
+                    ..Default::default()
+                }),
+                Signature::default(),
+            )
+        })
+        .collect();
+    let block = Block {
+        header: Header {
+            beneficiary: Address::from_low_u64_be(u64::MAX),
+            gas_limit: 21_000 * count,
+            ..Default::default()
+        },
+        body,
+        ommers: vec![],
+        withdrawals: None,
+    };
+    (provider, block, senders)
+}
+
+/// Benchmarks the serial and the parallel execution of blocks with independent transactions.
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let chain_spec = Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build());
+
+    let mut group = c.benchmark_group("block execution");
+    for count in [16, 128, 512] {
+        let (provider, block, senders) = independent_transfers(count);
+
+        group.bench_with_input(BenchmarkId::new("serial", count), &block, |b, block| {
+            b.iter_batched(
+                || Executor::new(chain_spec.clone(), SubState::new(State::new(provider.clone()))),
+                |mut executor| {
+                    executor.execute_transactions(block, U256::ZERO, Some(senders.clone())).unwrap()
+                },
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", count), &block, |b, block| {
+            b.iter_batched(
+                || {
+                    ParallelExecutor::new(
+                        chain_spec.clone(),
+                        SubState::new(State::new(provider.clone())),
+                    )
+                },
+                |mut executor| {
+                    executor.execute_transactions(block, U256::ZERO, Some(senders.clone())).unwrap()
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
+    targets = criterion_benchmark
+}
+criterion_main!(benches);
@@ -0,0 +1,76 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use hex_literal::hex;
+use pprof::criterion::{Output, PProfProfiler};
+use revm::{
+    precompile::{Precompile, Precompiles, SpecId},
+    primitives::{Env, B160},
+};
+
+/// A valid signature of a message hash: `hash || v || r || s`.
+const ECRECOVER_INPUT: [u8; 128] = hex!("18c547e4f7b0f325ad1e56f57e26c745b09a3e503d86e00e5255ff7f715d3d1c000000000000000000000000000000000000000000000000000000000000001c73b1693892219d736caba55bdb67216e485557ea6b6af75f37096c9aa6a5a75feeb940b1d03b21e36b0e47e79769f095fe2ab855bd91e3a38756b7d75a9c4549");
+
+/// The generator of G1 twice: `x1 || y1 || x2 || y2`.
+const BN256_ADD_INPUT: [u8; 128] = hex!("0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002");
+
+/// The generator of G1 and a 256 bit scalar: `x || y || scalar`.
+const BN256_MUL_INPUT: [u8; 96] = hex!("00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+
+/// A single pair of the generators of G1 and G2.
+const BN256_PAIRING_INPUT: [u8; 192] = hex!("00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c21800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa");
+
+/// The 12 round compression of "abc" from EIP-152: `rounds || h || m || t || f`.
+const BLAKE2F_INPUT: [u8; 213] = hex!("0000000c48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b61626300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000300000000000000000000000000000001");
+
+/// Returns a modexp input with 32 byte base, exponent and modulus.
+fn modexp_input() -> Vec<u8> {
+    let mut input = Vec::with_capacity(3 * 32 + 3 * 32);
+    for _ in 0..3 {
+        input.extend_from_slice(&[0u8; 31]);
+        input.push(32);
+    }
+    input.extend_from_slice(&[0x03; 32]);
+    input.extend_from_slice(&[0xff; 32]);
+    input.extend_from_slice(&[0xfd; 32]);
+    input
+}
+
+/// Runs the precompile with unlimited gas and returns its output.
+fn run(precompile: &Precompile, input: &[u8], env: &Env) -> Vec<u8> {
+    let result = match precompile {
+        Precompile::Standard(precompile) => precompile(input, u64::MAX),
+        Precompile::Env(precompile) => precompile(input, u64::MAX, env),
+    };
+    result.expect("valid input").1
+}
+
+/// Benchmarks each precompile of the latest hardfork with a representative input.
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let precompiles = Precompiles::new(SpecId::LATEST);
+    let env = Env::default();
+    let inputs: [(&str, u64, Vec<u8>); 8] = [
+        ("ecrecover", 1, ECRECOVER_INPUT.to_vec()),
+        ("sha256", 2, vec![0xab; 64]),
+        ("ripemd160", 3, vec![0xab; 64]),
+        ("modexp", 5, modexp_input()),
+        ("bn256add", 6, BN256_ADD_INPUT.to_vec()),
+        ("bn256mul", 7, BN256_MUL_INPUT.to_vec()),
+        ("bn256pairing", 8, BN256_PAIRING_INPUT.to_vec()),
+        ("blake2f", 9, BLAKE2F_INPUT.to_vec()),
+    ];
+
+    let mut group = c.benchmark_group("precompiles");
+    for (name, address, input) in inputs {
+        let precompile = precompiles
+            .get(&B160::from_low_u64_be(address))
+            .unwrap_or_else(|| panic!("{name} precompile exists"));
+        group.bench_function(name, |b| b.iter(|| run(&precompile, &input, &env)));
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
+    targets = criterion_benchmark
+}
+criterion_main!(benches);
@@ -0,0 +1,192 @@
+//! Implementation of [`BufferedBlocks`] related to [`super::BlockchainTree`]
+
+use reth_primitives::{BlockHash, SealedBlockWithSenders};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// Blocks whose parent is not known to the tree yet.
+///
+/// The blocks are indexed by their parent hash, so they can be connected as soon as the parent is
+/// inserted into the tree. If the buffer is full the oldest block is evicted, and blocks that were
+/// buffered for longer than the TTL are discarded.
+#[derive(Debug)]
+pub struct BufferedBlocks {
+    /// The buffered blocks and the time they were buffered at.
+    blocks: HashMap<BlockHash, (SealedBlockWithSenders, Instant)>,
+    /// Parent hash to the hashes of the buffered blocks that extend it.
+    children: HashMap<BlockHash, HashSet<BlockHash>>,
+    /// Buffered blocks in insertion order.
+    ///
+    /// NOTE: Blocks that were taken out of the buffer are only removed from the queue once they
+    /// reach the front.
+    insertion_order: VecDeque<(Instant, BlockHash)>,
+    /// Max number of buffered blocks.
+    max_blocks: usize,
+    /// Time after which a buffered block is discarded.
+    ttl: Duration,
+}
+
+impl BufferedBlocks {
+    /// Create a new buffer that holds at most `max_blocks` blocks for up to `ttl`.
+    pub fn new(max_blocks: usize, ttl: Duration) -> Self {
+        Self {
+            blocks: Default::default(),
+            children: Default::default(),
+            insertion_order: Default::default(),
+            max_blocks,
+            ttl,
+        }
+    }
+
+    /// Number of buffered blocks.
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Return `true` if no blocks are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Check if the block with the given hash is buffered.
+    pub fn contains(&self, block_hash: &BlockHash) -> bool {
+        self.blocks.contains_key(block_hash)
+    }
+
+    /// Buffer a block, evicting the oldest block if the buffer is full.
+    pub fn insert(&mut self, block: SealedBlockWithSenders, now: Instant) {
+        self.remove_expired(now);
+        if self.max_blocks == 0 || self.contains(&block.hash()) {
+            return
+        }
+        while self.blocks.len() >= self.max_blocks {
+            self.remove_oldest();
+        }
+
+        let block_hash = block.hash();
+        self.children.entry(block.parent_hash).or_default().insert(block_hash);
+        self.insertion_order.push_back((now, block_hash));
+        self.blocks.insert(block_hash, (block, now));
+    }
+
+    /// Remove and return the buffered blocks whose parent is the given block.
+    pub fn take_children(
+        &mut self,
+        parent_hash: &BlockHash,
+        now: Instant,
+    ) -> Vec<SealedBlockWithSenders> {
+        self.remove_expired(now);
+        self.children
+            .remove(parent_hash)
+            .into_iter()
+            .flatten()
+            .filter_map(|block_hash| self.blocks.remove(&block_hash))
+            .map(|(block, _)| block)
+            .collect()
+    }
+
+    /// Discard all blocks that were buffered for longer than the TTL.
+    pub fn remove_expired(&mut self, now: Instant) {
+        while let Some((inserted_at, _)) = self.insertion_order.front() {
+            if now.duration_since(*inserted_at) < self.ttl {
+                break
+            }
+            self.remove_oldest();
+        }
+    }
+
+    /// Remove the front of the insertion queue, and the block it refers to if it's still buffered.
+    fn remove_oldest(&mut self) {
+        let Some((inserted_at, block_hash)) = self.insertion_order.pop_front() else { return };
+        // the block may have been taken and buffered again since
+        if !matches!(self.blocks.get(&block_hash), Some((_, at)) if *at == inserted_at) {
+            return
+        }
+        let (block, _) = self.blocks.remove(&block_hash).expect("block is buffered");
+        if let Some(children) = self.children.get_mut(&block.parent_hash) {
+            children.remove(&block_hash);
+            if children.is_empty() {
+                self.children.remove(&block.parent_hash);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, SealedBlock};
+
+    fn block(number: u64, parent_hash: BlockHash) -> SealedBlockWithSenders {
+        let header = Header { number, parent_hash, ..Default::default() }.seal_slow();
+        SealedBlockWithSenders {
+            block: SealedBlock { header, ..Default::default() },
+            senders: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn take_children() {
+        let now = Instant::now();
+        let mut buffer = BufferedBlocks::new(10, Duration::from_secs(60));
+
+        let parent = BlockHash::random();
+        let block1 = block(1, parent);
+        let block1a = block(2, parent);
+        let block2 = block(3, block1.hash());
+        buffer.insert(block1.clone(), now);
+        buffer.insert(block1a.clone(), now);
+        buffer.insert(block2.clone(), now);
+        assert_eq!(buffer.len(), 3);
+
+        let mut children = buffer.take_children(&parent, now);
+        children.sort_by_key(|block| block.number);
+        assert_eq!(children, vec![block1.clone(), block1a]);
+        assert!(buffer.take_children(&parent, now).is_empty());
+        assert_eq!(buffer.take_children(&block1.hash(), now), vec![block2]);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn evicts_oldest_block() {
+        let now = Instant::now();
+        let mut buffer = BufferedBlocks::new(2, Duration::from_secs(60));
+
+        let parent = BlockHash::random();
+        let block1 = block(1, parent);
+        let block2 = block(2, parent);
+        let block3 = block(3, parent);
+        buffer.insert(block1.clone(), now);
+        buffer.insert(block2.clone(), now);
+
+        // buffering a block again after it was taken doesn't evict it early
+        assert_eq!(buffer.take_children(&parent, now).len(), 2);
+        buffer.insert(block2.clone(), now + Duration::from_secs(1));
+        buffer.insert(block1.clone(), now + Duration::from_secs(2));
+        buffer.insert(block3.clone(), now + Duration::from_secs(3));
+
+        assert_eq!(buffer.len(), 2);
+        assert!(!buffer.contains(&block2.hash()));
+        assert!(buffer.contains(&block1.hash()));
+        assert!(buffer.contains(&block3.hash()));
+    }
+
+    #[test]
+    fn discards_expired_blocks() {
+        let now = Instant::now();
+        let ttl = Duration::from_secs(60);
+        let mut buffer = BufferedBlocks::new(10, ttl);
+
+        let parent = BlockHash::random();
+        buffer.insert(block(1, parent), now);
+        buffer.insert(block(2, parent), now + Duration::from_secs(30));
+
+        assert_eq!(buffer.take_children(&parent, now + ttl).len(), 1);
+
+        buffer.insert(block(3, parent), now + ttl);
+        buffer.remove_expired(now + 2 * ttl);
+        assert!(buffer.is_empty());
+    }
+}
@@ -1,5 +1,18 @@
 //! Blockchain tree configuration
 
+use std::time::Duration;
+
+/// The default number of blocks with an unknown parent that the tree buffers.
+pub const DEFAULT_MAX_BUFFERED_BLOCKS: usize = 512;
+
+/// The default time after which a buffered block with an unknown parent is discarded.
+pub const DEFAULT_BUFFERED_BLOCK_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// The default number of blocks that can be re-orged.
+///
+/// Gasper allows reorgs of any length from 1 to 64.
+pub const DEFAULT_MAX_REORG_DEPTH: u64 = 64;
+
 /// The configuration for the blockchain tree.
 #[derive(Clone, Debug)]
 pub struct BlockchainTreeConfig {
@@ -13,18 +26,24 @@ pub struct BlockchainTreeConfig {
     /// at least `additional_canonical_block_hashes`+`max_reorg_depth`, for eth that would be
     /// 256+64.
     num_of_additional_canonical_block_hashes: u64,
+    /// The number of blocks with an unknown parent that are buffered until the parent is
+    /// inserted.
+    max_buffered_blocks: usize,
+    /// The time after which a buffered block is discarded.
+    buffered_block_ttl: Duration,
 }
 
 impl Default for BlockchainTreeConfig {
     fn default() -> Self {
         // The defaults for Ethereum mainnet
         Self {
-            // Gasper allows reorgs of any length from 1 to 64.
-            max_reorg_depth: 64,
+            max_reorg_depth: DEFAULT_MAX_REORG_DEPTH,
             // This default is just an assumption. Has to be greater than the `max_reorg_depth`.
             max_blocks_in_chain: 65,
             // EVM requires that last 256 block hashes are available.
             num_of_additional_canonical_block_hashes: 256,
+            max_buffered_blocks: DEFAULT_MAX_BUFFERED_BLOCKS,
+            buffered_block_ttl: DEFAULT_BUFFERED_BLOCK_TTL,
         }
     }
 }
@@ -39,7 +58,25 @@ impl BlockchainTreeConfig {
         if max_reorg_depth > max_blocks_in_chain {
             panic!("Side chain size should be more then finalization window");
         }
-        Self { max_blocks_in_chain, max_reorg_depth, num_of_additional_canonical_block_hashes }
+        Self {
+            max_blocks_in_chain,
+            max_reorg_depth,
+            num_of_additional_canonical_block_hashes,
+            max_buffered_blocks: DEFAULT_MAX_BUFFERED_BLOCKS,
+            buffered_block_ttl: DEFAULT_BUFFERED_BLOCK_TTL,
+        }
+    }
+
+    /// Set the number of blocks with an unknown parent that are buffered.
+    pub fn with_max_buffered_blocks(mut self, max_buffered_blocks: usize) -> Self {
+        self.max_buffered_blocks = max_buffered_blocks;
+        self
+    }
+
+    /// Set the time after which a buffered block is discarded.
+    pub fn with_buffered_block_ttl(mut self, buffered_block_ttl: Duration) -> Self {
+        self.buffered_block_ttl = buffered_block_ttl;
+        self
     }
 
     /// Return the maximum reorg depth.
@@ -57,4 +94,14 @@ impl BlockchainTreeConfig {
     pub fn num_of_additional_canonical_block_hashes(&self) -> u64 {
         self.num_of_additional_canonical_block_hashes
     }
+
+    /// Return the number of blocks with an unknown parent that are buffered.
+    pub fn max_buffered_blocks(&self) -> usize {
+        self.max_buffered_blocks
+    }
+
+    /// Return the time after which a buffered block is discarded.
+    pub fn buffered_block_ttl(&self) -> Duration {
+        self.buffered_block_ttl
+    }
 }
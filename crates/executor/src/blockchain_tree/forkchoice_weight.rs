@@ -0,0 +1,75 @@
+//! Implementation of [`ForkchoiceWeightTracker`] related to [`super::BlockchainTree`]
+
+use reth_primitives::BlockHash;
+use std::collections::HashMap;
+
+/// The weight of blocks, approximated from the forkchoice updates of the consensus layer.
+///
+/// The execution layer doesn't see attestations, so every forkchoice update counts as a vote for
+/// its head and the ancestors of the head that are not finalized. A tip that the consensus layer
+/// keeps choosing accumulates weight, which is used to break ties between competing tips.
+#[derive(Debug, Default)]
+pub struct ForkchoiceWeightTracker {
+    /// Number of forkchoice updates whose head was the block or one of its descendants.
+    weights: HashMap<BlockHash, u64>,
+}
+
+impl ForkchoiceWeightTracker {
+    /// Count a forkchoice update for the given blocks, i.e. the head and its ancestors.
+    pub fn add_vote(&mut self, blocks: impl IntoIterator<Item = BlockHash>) {
+        for block_hash in blocks {
+            *self.weights.entry(block_hash).or_default() += 1;
+        }
+    }
+
+    /// Return the weight of the block.
+    pub fn weight(&self, block_hash: &BlockHash) -> u64 {
+        self.weights.get(block_hash).copied().unwrap_or_default()
+    }
+
+    /// Return the heaviest of the given blocks.
+    ///
+    /// Ties are broken in favour of the block that comes first.
+    pub fn heaviest(&self, blocks: impl IntoIterator<Item = BlockHash>) -> Option<BlockHash> {
+        let mut heaviest: Option<(BlockHash, u64)> = None;
+        for block_hash in blocks {
+            let weight = self.weight(&block_hash);
+            if heaviest.map_or(true, |(_, max)| weight > max) {
+                heaviest = Some((block_hash, weight));
+            }
+        }
+        heaviest.map(|(block_hash, _)| block_hash)
+    }
+
+    /// Only keep the weights of the blocks for which the predicate returns `true`.
+    pub fn retain(&mut self, mut f: impl FnMut(&BlockHash) -> bool) {
+        self.weights.retain(|block_hash, _| f(block_hash));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heaviest_block() {
+        let mut tracker = ForkchoiceWeightTracker::default();
+        let (ancestor, tip1, tip2) =
+            (BlockHash::random(), BlockHash::random(), BlockHash::random());
+        assert_eq!(tracker.heaviest([tip1, tip2]), Some(tip1));
+
+        tracker.add_vote([ancestor, tip1]);
+        tracker.add_vote([ancestor, tip2]);
+        tracker.add_vote([ancestor, tip2]);
+        assert_eq!(tracker.weight(&ancestor), 3);
+        assert_eq!(tracker.heaviest([tip1, tip2]), Some(tip2));
+
+        tracker.add_vote([ancestor, tip1]);
+        assert_eq!(tracker.heaviest([tip1, tip2]), Some(tip1));
+        assert_eq!(tracker.heaviest([tip2, tip1]), Some(tip2));
+
+        tracker.retain(|block_hash| *block_hash != tip2);
+        assert_eq!(tracker.weight(&tip2), 0);
+        assert_eq!(tracker.heaviest([]), None);
+    }
+}
@@ -4,16 +4,23 @@ use reth_db::{cursor::DbCursorRO, database::Database, tables, transaction::DbTx}
 use reth_interfaces::{consensus::Consensus, executor::Error as ExecError, Error};
 use reth_primitives::{BlockHash, BlockNumber, SealedBlock, SealedBlockWithSenders};
 use reth_provider::{
-    providers::ChainState, ExecutorFactory, HeaderProvider, StateProviderFactory, Transaction,
+    providers::ChainState, CanonStateNotification, CanonStateNotificationSender,
+    CanonStateNotifications, CanonStateSubscriptions, ExecutorFactory, HeaderProvider,
+    StateProviderFactory, Transaction,
 };
 use std::{
     collections::{BTreeMap, HashMap},
     ops::DerefMut,
+    time::Instant,
 };
+use tracing::debug;
 
 pub mod block_indices;
 use block_indices::BlockIndices;
 
+pub mod buffered_blocks;
+use buffered_blocks::BufferedBlocks;
+
 pub mod chain;
 use chain::{ChainSplit, SplitAt};
 
@@ -23,6 +30,14 @@ use config::BlockchainTreeConfig;
 pub mod externals;
 use externals::TreeExternals;
 
+pub mod forkchoice_weight;
+use forkchoice_weight::ForkchoiceWeightTracker;
+
+pub mod shareable;
+
+/// The number of canonical state notifications that are kept for subscribers that fall behind.
+const CANON_STATE_NOTIFICATION_CHANNEL_SIZE: usize = 256;
+
 #[cfg_attr(doc, aquamarine::aquamarine)]
 /// Tree of chains and its identifications.
 ///
@@ -71,10 +86,20 @@ pub struct BlockchainTree<DB: Database, C: Consensus, EF: ExecutorFactory> {
     block_chain_id_generator: u64,
     /// Indices to block and their connection to the canonical chain.
     block_indices: BlockIndices,
+    /// Blocks whose parent is not known yet.
+    buffered_blocks: BufferedBlocks,
     /// External components (the database, consensus engine etc.)
     externals: TreeExternals<DB, C, EF>,
     /// Tree configuration
     config: BlockchainTreeConfig,
+    /// Broadcasts the changes of the canonical chain.
+    canon_state_notification_sender: CanonStateNotificationSender,
+    /// The safe block of the latest forkchoice update, zero if unknown.
+    safe_block_hash: BlockHash,
+    /// The finalized block of the latest forkchoice update, zero if unknown.
+    finalized_block_hash: BlockHash,
+    /// The weight of the blocks, from the forkchoice updates.
+    forkchoice_weights: ForkchoiceWeightTracker,
 }
 
 /// From Engine API spec, block inclusion can be valid, accepted or invalid.
@@ -138,10 +163,103 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
                 last_finalized_block_number,
                 BTreeMap::from_iter(last_canonical_hashes.into_iter()),
             ),
+            buffered_blocks: BufferedBlocks::new(
+                config.max_buffered_blocks(),
+                config.buffered_block_ttl(),
+            ),
             config,
+            canon_state_notification_sender: tokio::sync::broadcast::channel(
+                CANON_STATE_NOTIFICATION_CHANNEL_SIZE,
+            )
+            .0,
+            safe_block_hash: BlockHash::zero(),
+            finalized_block_hash: BlockHash::zero(),
+            forkchoice_weights: Default::default(),
         })
     }
 
+    /// Count a forkchoice update to the given head towards the weight of the head and its
+    /// ancestors in the tree that are not finalized, canonical or not.
+    pub fn on_forkchoice_head(&mut self, head: &BlockHash) {
+        let last_finalized_block = self.block_indices.last_finalized_block();
+        let (head_number, fork_number, mut hashes) =
+            if let Some(chain_id) = self.block_indices.get_blocks_chain_id(head) {
+                let hashes = self.all_chain_hashes(chain_id);
+                let head_number = hashes
+                    .iter()
+                    .find_map(|(number, hash)| (hash == head).then_some(*number))
+                    .expect("head is part of its chain");
+                let fork_number = *hashes.keys().next().expect("chain is not empty") - 1;
+                (head_number, fork_number, hashes)
+            } else if let Some(head_number) = self
+                .block_indices
+                .canonical_chain()
+                .iter()
+                .find_map(|(number, hash)| (hash == head).then_some(*number))
+            {
+                (head_number, head_number, BTreeMap::new())
+            } else {
+                // unknown head
+                self.forkchoice_weights.add_vote([*head]);
+                return
+            };
+        hashes.extend(
+            self.block_indices
+                .canonical_chain()
+                .range(..=fork_number)
+                .filter(|(number, _)| **number >= last_finalized_block)
+                .map(|(number, hash)| (*number, *hash)),
+        );
+        self.forkchoice_weights.add_vote(hashes.range(..=head_number).map(|(_, hash)| *hash));
+    }
+
+    /// Return the heavier of the forkchoice head in the given chain and the canonical tip, if the
+    /// two compete at the same height, by the weight of the forkchoice updates.
+    ///
+    /// The branches are compared where they fork from each other. Ties are broken in favour of
+    /// the head, the latest choice of the consensus layer.
+    pub fn heaviest_tip(&self, chain_id: BlockChainId, head: &BlockHash) -> BlockHash {
+        let canonical_tip = self.block_indices.canonical_tip();
+        let hashes = self.all_chain_hashes(chain_id);
+        let is_competing = hashes.get(&canonical_tip.number) == Some(head);
+        let Some(fork) = self.canonical_fork(chain_id).filter(|_| is_competing) else {
+            return *head
+        };
+        let branch_number = fork.number + 1;
+        let (Some(head_branch), Some(canonical_branch)) =
+            (hashes.get(&branch_number), self.block_indices.canonical_hash(&branch_number))
+        else {
+            return *head
+        };
+        if self.forkchoice_weights.heaviest([*head_branch, canonical_branch]) ==
+            Some(canonical_branch)
+        {
+            canonical_tip.hash
+        } else {
+            *head
+        }
+    }
+
+    /// Track the safe and finalized blocks of a forkchoice update.
+    pub fn set_safe_and_finalized(
+        &mut self,
+        safe_block_hash: BlockHash,
+        finalized_block_hash: BlockHash,
+    ) {
+        self.safe_block_hash = safe_block_hash;
+        self.finalized_block_hash = finalized_block_hash;
+    }
+
+    /// The safe block of the latest forkchoice update, zero if unknown.
+    pub fn safe_block_hash(&self) -> BlockHash {
+        self.safe_block_hash
+    }
+
+    /// The finalized block of the latest forkchoice update, zero if unknown.
+    pub fn finalized_block_hash(&self) -> BlockHash {
+        self.finalized_block_hash
+    }
+
     /// Create a new sidechain by forking the given chain, or append the block if the parent block
     /// is the top of the given chain.
     fn fork_side_chain(
@@ -333,7 +451,7 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
     /// the chain or any sidechains.
     ///
     /// This means that if the block becomes canonical, we need to fetch the missing blocks over
-    /// P2P.
+    /// P2P. Until then the block is buffered, and it's inserted as soon as its parent is.
     ///
     /// # Note
     ///
@@ -383,24 +501,41 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
 
         // check if block parent can be found in Tree
         if let Some(parent_chain) = self.block_indices.get_blocks_chain_id(&block.parent_hash) {
-            return self.fork_side_chain(block.clone(), parent_chain)
+            let status = self.fork_side_chain(block.clone(), parent_chain)?;
             // TODO save pending block to database
             // https://github.com/paradigmxyz/reth/issues/1713
+            self.connect_buffered_blocks(block.hash());
+            return Ok(status)
         }
 
         // if not found, check if the parent can be found inside canonical chain.
         if Some(block.parent_hash) == self.block_indices.canonical_hash(&(block.number - 1)) {
             // create new chain that points to that block
-            return self.fork_canonical_chain(block.clone())
+            let status = self.fork_canonical_chain(block.clone())?;
             // TODO save pending block to database
             // https://github.com/paradigmxyz/reth/issues/1713
+            self.connect_buffered_blocks(block.hash());
+            return Ok(status)
         }
         // NOTE: Block doesn't have a parent, and if we receive this block in `make_canonical`
         // function this could be a trigger to initiate p2p syncing, as we are missing the
         // parent.
+        self.buffered_blocks.insert(block.clone(), Instant::now());
         Ok(BlockStatus::Disconnected)
     }
 
+    /// Insert the buffered blocks that extend the given block, which was just inserted.
+    ///
+    /// Their buffered children are inserted in turn by
+    /// [`BlockchainTree::insert_block_with_senders`].
+    fn connect_buffered_blocks(&mut self, parent_hash: BlockHash) {
+        for block in self.buffered_blocks.take_children(&parent_hash, Instant::now()) {
+            if let Err(err) = self.insert_block_with_senders(&block) {
+                debug!(target: "blockchain_tree", ?err, hash = ?block.hash(), "Failed to insert buffered block");
+            }
+        }
+    }
+
     /// Finalize blocks up until and including `finalized_block`, and remove them from the tree.
     pub fn finalize_block(&mut self, finalized_block: BlockNumber) {
         let mut remove_chains = self.block_indices.finalize_canonical_blocks(
@@ -413,6 +548,13 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
                 remove_chains.extend(self.block_indices.remove_chain(&chain));
             }
         }
+
+        // finalized blocks don't compete anymore
+        let block_indices = &self.block_indices;
+        self.forkchoice_weights.retain(|block_hash| {
+            block_indices.get_blocks_chain_id(block_hash).is_some() ||
+                block_indices.is_block_hash_canonical(block_hash)
+        });
     }
 
     /// Reads the last `N` canonical hashes from the database and updates the block indices of the
@@ -480,10 +622,16 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
     /// This unwinds the database if necessary, i.e. if parts of the canonical chain have been
     /// re-orged.
     ///
+    /// The call counts as a forkchoice update to the block, see
+    /// [BlockchainTree::on_forkchoice_head]. A block that competes with a heavier canonical tip
+    /// at the same height is not made canonical, see [BlockchainTree::heaviest_tip].
+    ///
     /// # Returns
     ///
-    /// Returns `Ok` if the blocks were canonicalized, or if the blocks were already canonical.
+    /// Returns `Ok` if the blocks were canonicalized, if the blocks were already canonical, or if
+    /// the canonical tip is heavier.
     pub fn make_canonical(&mut self, block_hash: &BlockHash) -> Result<(), Error> {
+        self.on_forkchoice_head(block_hash);
         let chain_id = if let Some(chain_id) = self.block_indices.get_blocks_chain_id(block_hash) {
             chain_id
         } else {
@@ -493,6 +641,14 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
             }
             return Err(ExecError::BlockHashNotFoundInChain { block_hash: *block_hash }.into())
         };
+
+        // a competing tip that the consensus layer chose less often than the canonical tip
+        // doesn't replace it
+        let heaviest_tip = self.heaviest_tip(chain_id, block_hash);
+        if heaviest_tip != *block_hash {
+            debug!(target: "blockchain_tree", head = ?block_hash, canonical_tip = ?heaviest_tip, "Keeping the heavier canonical tip");
+            return Ok(())
+        }
         let chain = self.chains.remove(&chain_id).expect("To be present");
 
         // we are spliting chain as there is possibility that only part of chain get canonicalized.
@@ -521,10 +677,13 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
         // update canonical index
         self.block_indices.canonicalize_blocks(new_canon_chain.blocks());
 
+        let new_blocks = sealed_blocks(&new_canon_chain);
+
         // if joins to the tip
-        if new_canon_chain.fork_block_hash() == old_tip.hash {
+        let notification = if new_canon_chain.fork_block_hash() == old_tip.hash {
             // append to database
             self.commit_canonical(new_canon_chain)?;
+            CanonStateNotification::Committed { new_blocks }
         } else {
             // it forks to canonical block that is not the tip.
 
@@ -537,9 +696,14 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
             let old_canon_chain = self.revert_canonical(canon_fork.number)?;
             // commit new canonical chain.
             self.commit_canonical(new_canon_chain)?;
+            let old_blocks = sealed_blocks(&old_canon_chain);
             // insert old canon chain
             self.insert_chain(old_canon_chain);
-        }
+            CanonStateNotification::Reorganized { old_blocks, new_blocks }
+        };
+
+        // there may be no subscribers
+        let _ = self.canon_state_notification_sender.send(notification);
 
         Ok(())
     }
@@ -632,6 +796,21 @@ impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTree<DB, C, EF>
     }
 }
 
+/// Returns the blocks of the chain in ascending order.
+fn sealed_blocks(chain: &Chain) -> Vec<SealedBlock> {
+    chain.blocks().values().map(|block| block.block.clone()).collect()
+}
+
+impl<DB: Database, C: Consensus, EF: ExecutorFactory> CanonStateSubscriptions
+    for BlockchainTree<DB, C, EF>
+{
+    /// Returns a new receiver of the changes of the canonical chain made by
+    /// [BlockchainTree::make_canonical].
+    fn subscribe_canon_state(&self) -> CanonStateNotifications {
+        self.canon_state_notification_sender.subscribe()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -794,6 +973,7 @@ mod tests {
         // make tree
         let config = BlockchainTreeConfig::new(1, 2, 3);
         let mut tree = BlockchainTree::new(externals, config).expect("failed to create tree");
+        let mut canon_notifications = tree.subscribe_canon_state();
 
         // genesis block 10 is already canonical
         assert_eq!(tree.make_canonical(&H256::zero()), Ok(()));
@@ -812,11 +992,13 @@ mod tests {
         // make genesis block 10 as finalized
         tree.finalize_block(10);
 
-        // block 2 parent is not known.
+        // block 2 parent is not known, so it's buffered.
         assert_eq!(tree.insert_block_with_senders(&block2), Ok(BlockStatus::Disconnected));
+        assert!(tree.buffered_blocks.contains(&block2.hash()));
 
-        // insert block1
+        // insert block1, which also inserts the buffered block2
         assert_eq!(tree.insert_block_with_senders(&block1), Ok(BlockStatus::Valid));
+        assert!(tree.buffered_blocks.is_empty());
         // already inserted block will return true.
         assert_eq!(tree.insert_block_with_senders(&block1), Ok(BlockStatus::Valid));
 
@@ -840,8 +1022,16 @@ mod tests {
 
         // make block1 canonical
         assert_eq!(tree.make_canonical(&block1.hash()), Ok(()));
+        assert_eq!(
+            canon_notifications.try_recv(),
+            Ok(CanonStateNotification::Committed { new_blocks: vec![block1.block.clone()] })
+        );
         // make block2 canonical
         assert_eq!(tree.make_canonical(&block2.hash()), Ok(()));
+        assert_eq!(
+            canon_notifications.try_recv(),
+            Ok(CanonStateNotification::Committed { new_blocks: vec![block2.block.clone()] })
+        );
 
         // Trie state:
         // b2 (canonical block)
@@ -878,6 +1068,21 @@ mod tests {
             .assert(&tree);
 
         assert_eq!(tree.insert_block_with_senders(&block2a), Ok(BlockStatus::Accepted));
+
+        // forkchoice updates to the canonical tip count towards its canonical ancestors
+        assert_eq!(tree.forkchoice_weights.weight(&block1.hash), 2);
+        tree.on_forkchoice_head(&block2.hash);
+        assert_eq!(tree.forkchoice_weights.weight(&block2.hash), 2);
+        assert_eq!(tree.forkchoice_weights.weight(&block1.hash), 3);
+
+        // b2a competes with the heavier canonical tip b2 and doesn't replace it
+        assert_eq!(tree.heaviest_tip(2, &block2a_hash), block2.hash);
+        assert_eq!(tree.make_canonical(&block2a_hash), Ok(()));
+        assert!(canon_notifications.try_recv().is_err());
+        assert_eq!(tree.block_indices.canonical_tip().hash, block2.hash);
+        // the next forkchoice update to b2a ties with b2, ties go to the head
+        assert_eq!(tree.forkchoice_weights.weight(&block2a_hash), 1);
+        assert_eq!(tree.forkchoice_weights.weight(&block1.hash), 4);
         // Trie state:
         // b2   b2a (side chain)
         // |   /
@@ -898,6 +1103,14 @@ mod tests {
 
         // make b2a canonical
         assert_eq!(tree.make_canonical(&block2a_hash), Ok(()));
+        match canon_notifications.try_recv() {
+            Ok(CanonStateNotification::Reorganized { old_blocks, new_blocks }) => {
+                let old_hashes = old_blocks.iter().map(|block| block.hash()).collect::<Vec<_>>();
+                assert_eq!(old_hashes, vec![block2.hash()]);
+                assert_eq!(new_blocks, vec![block2a.block.clone()]);
+            }
+            other => panic!("expected a reorg notification, got {other:?}"),
+        }
         // Trie state:
         // b2a   b2 (side chain)
         // |   /
@@ -0,0 +1,81 @@
+//! Wrapper around the [BlockchainTree] that can be shared between tasks.
+
+use super::{BlockStatus, BlockchainTree};
+use parking_lot::RwLock;
+use reth_db::database::Database;
+use reth_interfaces::{consensus::Consensus, Error};
+use reth_primitives::{BlockHash, SealedBlock};
+use reth_provider::{CanonStateNotifications, CanonStateSubscriptions, ExecutorFactory};
+use std::sync::Arc;
+
+/// The operations of the blockchain tree that the Engine API drives.
+pub trait BlockchainTreeEngine: Send + Sync {
+    /// Insert a new payload block in the tree, see [BlockchainTree::insert_block].
+    fn insert_block(&self, block: SealedBlock) -> Result<BlockStatus, Error>;
+
+    /// Make the head of a forkchoice update and its ancestors canonical, see
+    /// [BlockchainTree::make_canonical].
+    fn make_canonical(&self, block_hash: &BlockHash) -> Result<(), Error>;
+
+    /// Track the safe and finalized blocks of a forkchoice update, see
+    /// [BlockchainTree::set_safe_and_finalized].
+    fn set_safe_and_finalized(&self, safe_block_hash: BlockHash, finalized_block_hash: BlockHash);
+}
+
+/// A [BlockchainTree] behind a lock, shared by the Engine API that inserts blocks and makes them
+/// canonical, and the subscribers of its canonical state notifications.
+#[derive(Debug)]
+pub struct ShareableBlockchainTree<DB: Database, C: Consensus, EF: ExecutorFactory> {
+    /// The shared tree.
+    tree: Arc<RwLock<BlockchainTree<DB, C, EF>>>,
+}
+
+impl<DB: Database, C: Consensus, EF: ExecutorFactory> ShareableBlockchainTree<DB, C, EF> {
+    /// Wraps the tree so it can be shared.
+    pub fn new(tree: BlockchainTree<DB, C, EF>) -> Self {
+        Self { tree: Arc::new(RwLock::new(tree)) }
+    }
+}
+
+impl<DB: Database, C: Consensus, EF: ExecutorFactory> Clone for ShareableBlockchainTree<DB, C, EF> {
+    fn clone(&self) -> Self {
+        Self { tree: Arc::clone(&self.tree) }
+    }
+}
+
+impl<DB: Database, C: Consensus, EF: ExecutorFactory> BlockchainTreeEngine
+    for ShareableBlockchainTree<DB, C, EF>
+{
+    /// Inserts the block into the tree.
+    ///
+    /// The pipeline extends the canonical chain in the database without the tree, so the
+    /// canonical hashes of the tree are restored from the database first if the parent of the
+    /// block is not known to the tree.
+    fn insert_block(&self, block: SealedBlock) -> Result<BlockStatus, Error> {
+        let mut tree = self.tree.write();
+        let is_parent_known =
+            tree.block_indices.get_blocks_chain_id(&block.parent_hash).is_some() ||
+                tree.block_indices.is_block_hash_canonical(&block.parent_hash);
+        if !is_parent_known {
+            let last_finalized_block = tree.block_indices.last_finalized_block();
+            tree.restore_canonical_hashes(last_finalized_block)?;
+        }
+        tree.insert_block(block)
+    }
+
+    fn make_canonical(&self, block_hash: &BlockHash) -> Result<(), Error> {
+        self.tree.write().make_canonical(block_hash)
+    }
+
+    fn set_safe_and_finalized(&self, safe_block_hash: BlockHash, finalized_block_hash: BlockHash) {
+        self.tree.write().set_safe_and_finalized(safe_block_hash, finalized_block_hash)
+    }
+}
+
+impl<DB: Database, C: Consensus, EF: ExecutorFactory> CanonStateSubscriptions
+    for ShareableBlockchainTree<DB, C, EF>
+{
+    fn subscribe_canon_state(&self) -> CanonStateNotifications {
+        self.tree.read().subscribe_canon_state()
+    }
+}
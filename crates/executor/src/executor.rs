@@ -1,8 +1,10 @@
-use crate::post_state::PostState;
+use crate::{post_state::PostState, selfdestruct::SelfDestructInspector};
 use reth_interfaces::executor::Error;
 use reth_primitives::{
-    bloom::logs_bloom, Account, Address, Block, Bloom, Bytecode, ChainSpec, Hardfork, Header, Log,
-    Receipt, TransactionSigned, H256, U256,
+    bloom::logs_bloom,
+    constants::{BEACON_ROOTS_ADDRESS, BEACON_ROOTS_HISTORY_BUFFER_LENGTH},
+    verify_signatures_batch, Account, Address, Block, Bloom, Bytecode, ChainSpec, Hardfork, Header,
+    Log, Receipt, TransactionSigned, H256, U256,
 };
 use reth_provider::{BlockExecutor, StateProvider};
 use reth_revm::{
@@ -16,9 +18,9 @@ use revm::{
     db::AccountState,
     primitives::{
         hash_map::{self, Entry},
-        Account as RevmAccount, AccountInfo, ResultAndState,
+        Account as RevmAccount, AccountInfo, Env, ResultAndState, KECCAK_EMPTY,
     },
-    EVM,
+    Database, EVM,
 };
 use std::{
     collections::{BTreeMap, HashMap},
@@ -71,7 +73,7 @@ where
         self.evm.db().expect("db to not be moved")
     }
 
-    fn recover_senders(
+    pub(crate) fn recover_senders(
         &self,
         body: &[TransactionSigned],
         senders: Option<Vec<Address>>,
@@ -83,12 +85,12 @@ where
                 Err(Error::SenderRecoveryError)
             }
         } else {
-            body.iter().map(|tx| tx.recover_signer().ok_or(Error::SenderRecoveryError)).collect()
+            verify_signatures_batch(body).ok_or(Error::SenderRecoveryError)
         }
     }
 
     /// Initializes the config and block env.
-    fn init_env(&mut self, header: &Header, total_difficulty: U256) {
+    pub(crate) fn init_env(&mut self, header: &Header, total_difficulty: U256) {
         fill_cfg_and_block_env(
             &mut self.evm.env.cfg,
             &mut self.evm.env.block,
@@ -98,6 +100,11 @@ where
         );
     }
 
+    /// Returns the environment the transactions are executed in.
+    pub(crate) fn env(&self) -> &Env {
+        &self.evm.env
+    }
+
     /// Commit change to the run-time database, and update the given [PostState] with the changes
     /// made in the transaction, which can be persisted to the database.
     fn commit_changes(
@@ -205,6 +212,75 @@ where
         }
     }
 
+    /// EIP-6780: `SELFDESTRUCT` only deletes an account that was created in the same transaction.
+    ///
+    /// Accounts that already had code before the transaction could not have been created by it,
+    /// so they are committed as regular changes instead. Their balance was already transferred to
+    /// the beneficiary, while their code, nonce and storage are preserved.
+    ///
+    /// Contracts that name themselves as the beneficiary are not destroyed in the first place, see
+    /// [SelfDestructInspector].
+    fn preserve_selfdestructed_accounts(
+        &mut self,
+        changes: &mut hash_map::HashMap<Address, RevmAccount>,
+    ) {
+        let db = self.db();
+        for (address, account) in changes.iter_mut().filter(|(_, account)| account.is_destroyed) {
+            // the cached account still reflects the state before the transaction
+            let is_pre_existing = db.accounts.get(address).map_or(false, |db_account| {
+                !matches!(db_account.account_state, AccountState::NotExisting) &&
+                    db_account.info.code_hash != KECCAK_EMPTY
+            });
+            if is_pre_existing {
+                account.is_destroyed = false;
+            }
+        }
+    }
+
+    /// EIP-4788: Stores the root of the parent beacon block in the beacon roots contract before
+    /// the transactions of the block are executed.
+    ///
+    /// This has the effect of the system call to the contract, which stores the timestamp of the
+    /// block and the root in the ring buffers of the contract storage. Nothing is stored if the
+    /// contract is not deployed.
+    ///
+    /// Returns the storage slots of the contract that were written.
+    pub(crate) fn apply_beacon_root_contract_call(
+        &mut self,
+        block: &Block,
+        post_state: &mut PostState,
+    ) -> Result<Vec<U256>, Error> {
+        let Some(parent_beacon_block_root) = block.parent_beacon_block_root else {
+            return Ok(Vec::new())
+        };
+        if !self.chain_spec.fork(Hardfork::Cancun).active_at_timestamp(block.timestamp) {
+            return Ok(Vec::new())
+        }
+
+        let db = self.db();
+        let contract = db.load_account(BEACON_ROOTS_ADDRESS).map_err(|_| Error::ProviderError)?;
+        if contract.info.code_hash == KECCAK_EMPTY {
+            return Ok(Vec::new())
+        }
+
+        let timestamp_index = U256::from(block.timestamp % BEACON_ROOTS_HISTORY_BUFFER_LENGTH);
+        let root_index = timestamp_index + U256::from(BEACON_ROOTS_HISTORY_BUFFER_LENGTH);
+        let mut storage_changeset = BTreeMap::new();
+        for (slot, value) in [
+            (timestamp_index, U256::from(block.timestamp)),
+            (root_index, U256::from_be_bytes(parent_beacon_block_root.0)),
+        ] {
+            let old = db.storage(BEACON_ROOTS_ADDRESS, slot).map_err(|_| Error::ProviderError)?;
+            db.insert_account_storage(BEACON_ROOTS_ADDRESS, slot, value)
+                .map_err(|_| Error::ProviderError)?;
+            storage_changeset.insert(slot, (old, value));
+        }
+        let slots = storage_changeset.keys().copied().collect();
+        post_state.change_storage(BEACON_ROOTS_ADDRESS, storage_changeset);
+
+        Ok(slots)
+    }
+
     /// Collect all balance changes at the end of the block.
     ///
     /// Balance changes might include the block reward, uncle rewards, withdrawals, or irregular
@@ -349,15 +425,25 @@ where
         fill_tx_env(&mut self.evm.env.tx, transaction, sender);
 
         let hash = transaction.hash();
+        let is_cancun = self
+            .chain_spec
+            .fork(Hardfork::Cancun)
+            .active_at_timestamp(self.evm.env.block.timestamp.to::<u64>());
         let out = if self.stack.should_inspect(&self.evm.env, hash) {
             // execution with inspector.
-            let output = self.evm.inspect(&mut self.stack);
+            let output = if is_cancun {
+                self.evm.inspect(SelfDestructInspector::new(Some(&mut self.stack)))
+            } else {
+                self.evm.inspect(&mut self.stack)
+            };
             tracing::trace!(
                 target: "evm",
                 ?hash, ?output, ?transaction, env = ?self.evm.env,
                 "Executed transaction"
             );
             output
+        } else if is_cancun {
+            self.evm.inspect(SelfDestructInspector::<InspectorStack>::new(None))
         } else {
             // main execution.
             self.evm.transact()
@@ -365,6 +451,46 @@ where
         out.map_err(|e| Error::EVM { hash, message: format!("{e:?}") })
     }
 
+    /// Commits the state of an executed transaction to the run-time database and adds its changes
+    /// and receipt to the [PostState].
+    pub(crate) fn commit_transaction(
+        &mut self,
+        block: &Block,
+        transaction: &TransactionSigned,
+        ResultAndState { result, mut state }: ResultAndState,
+        cumulative_gas_used: &mut u64,
+        post_state: &mut PostState,
+    ) {
+        if self.chain_spec.fork(Hardfork::Cancun).active_at_timestamp(block.timestamp) {
+            self.preserve_selfdestructed_accounts(&mut state);
+        }
+
+        // commit changes
+        self.commit_changes(
+            state,
+            self.chain_spec.fork(Hardfork::SpuriousDragon).active_at_block(block.number),
+            post_state,
+        );
+
+        // append gas used
+        *cumulative_gas_used += result.gas_used();
+
+        // cast revm logs to reth logs
+        let logs: Vec<Log> = result.logs().into_iter().map(into_reth_log).collect();
+
+        // Push transaction changeset and calculate header bloom filter for receipt.
+        post_state.add_receipt(Receipt {
+            tx_type: transaction.tx_type(),
+            // Success flag was added in `EIP-658: Embedding transaction status code in
+            // receipts`.
+            success: result.is_success(),
+            cumulative_gas_used: *cumulative_gas_used,
+            bloom: logs_bloom(logs.iter()),
+            logs,
+        });
+        post_state.finish_transition();
+    }
+
     /// Runs the provided transactions and commits their state to the run-time database.
     ///
     /// The returned [PostState] can be used to persist the changes to disk, and contains the
@@ -383,71 +509,60 @@ where
 
         self.init_env(&block.header, total_difficulty);
 
-        let mut cumulative_gas_used = 0;
         let mut post_state = PostState::with_tx_capacity(block.body.len());
+        // The changes are part of the transition of the first transaction, or the block transition
+        // if the block has no transactions.
+        self.apply_beacon_root_contract_call(block, &mut post_state)?;
+        let cumulative_gas_used =
+            self.execute_block_transactions(block, senders, &mut post_state)?;
+
+        Ok((post_state, cumulative_gas_used))
+    }
+
+    /// Executes the transactions of the block one after another and commits their changes on top
+    /// of the given [PostState].
+    ///
+    /// Assumes the environment has been initialized for the block. Returns the cumulative gas used
+    /// by the transactions.
+    pub(crate) fn execute_block_transactions(
+        &mut self,
+        block: &Block,
+        senders: Vec<Address>,
+        post_state: &mut PostState,
+    ) -> Result<u64, Error> {
+        let mut cumulative_gas_used = 0;
         for (transaction, sender) in block.body.iter().zip(senders.into_iter()) {
-            // The sum of the transaction’s gas limit, Tg, and the gas utilised in this block prior,
-            // must be no greater than the block’s gasLimit.
-            let block_available_gas = block.header.gas_limit - cumulative_gas_used;
-            if transaction.gas_limit() > block_available_gas {
-                return Err(Error::TransactionGasLimitMoreThenAvailableBlockGas {
-                    transaction_gas_limit: transaction.gas_limit(),
-                    block_available_gas,
-                })
-            }
+            verify_available_block_gas(block, transaction, cumulative_gas_used)?;
             // Execute transaction.
-            let ResultAndState { result, state } = self.transact(transaction, sender)?;
-
-            // commit changes
-            self.commit_changes(
-                state,
-                self.chain_spec.fork(Hardfork::SpuriousDragon).active_at_block(block.number),
-                &mut post_state,
+            let result_and_state = self.transact(transaction, sender)?;
+            self.commit_transaction(
+                block,
+                transaction,
+                result_and_state,
+                &mut cumulative_gas_used,
+                post_state,
             );
-
-            // append gas used
-            cumulative_gas_used += result.gas_used();
-
-            // cast revm logs to reth logs
-            let logs: Vec<Log> = result.logs().into_iter().map(into_reth_log).collect();
-
-            // Push transaction changeset and calculate header bloom filter for receipt.
-            post_state.add_receipt(Receipt {
-                tx_type: transaction.tx_type(),
-                // Success flag was added in `EIP-658: Embedding transaction status code in
-                // receipts`.
-                success: result.is_success(),
-                cumulative_gas_used,
-                bloom: logs_bloom(logs.iter()),
-                logs,
-            });
-            post_state.finish_transition();
         }
-
-        Ok((post_state, cumulative_gas_used))
+        Ok(cumulative_gas_used)
     }
-}
 
-impl<DB> BlockExecutor<DB> for Executor<DB>
-where
-    DB: StateProvider,
-{
-    fn execute(
+    /// Applies the changes at the end of the block to the [PostState] of its executed
+    /// transactions.
+    pub(crate) fn finish_block(
         &mut self,
         block: &Block,
         total_difficulty: U256,
-        senders: Option<Vec<Address>>,
+        cumulative_gas_used: u64,
+        mut post_state: PostState,
     ) -> Result<PostState, Error> {
-        let (mut post_state, cumulative_gas_used) =
-            self.execute_transactions(block, total_difficulty, senders)?;
-
         // Check if gas used matches the value set in header.
         if block.gas_used != cumulative_gas_used {
             return Err(Error::BlockGasUsed { got: cumulative_gas_used, expected: block.gas_used })
         }
 
         let balance_increments = self.post_block_balance_increments(block, total_difficulty)?;
-        let mut includes_block_transition = !balance_increments.is_empty();
+        let mut includes_block_transition =
+            !balance_increments.is_empty() || block.header.has_beacon_root_block_transition();
         for (address, increment) in balance_increments.into_iter() {
             self.increment_account_balance(address, increment, &mut post_state)?;
         }
@@ -463,6 +578,22 @@ where
 
         Ok(post_state)
     }
+}
+
+impl<DB> BlockExecutor<DB> for Executor<DB>
+where
+    DB: StateProvider,
+{
+    fn execute(
+        &mut self,
+        block: &Block,
+        total_difficulty: U256,
+        senders: Option<Vec<Address>>,
+    ) -> Result<PostState, Error> {
+        let (post_state, cumulative_gas_used) =
+            self.execute_transactions(block, total_difficulty, senders)?;
+        self.finish_block(block, total_difficulty, cumulative_gas_used, post_state)
+    }
 
     fn execute_and_verify_receipt(
         &mut self,
@@ -489,6 +620,36 @@ where
     }
 }
 
+/// Verify that the block has enough gas left for the transaction.
+pub(crate) fn verify_available_block_gas(
+    block: &Block,
+    transaction: &TransactionSigned,
+    cumulative_gas_used: u64,
+) -> Result<(), Error> {
+    // The sum of the transaction’s gas limit, Tg, and the gas utilised in this block prior,
+    // must be no greater than the block’s gasLimit.
+    let block_available_gas = block.header.gas_limit - cumulative_gas_used;
+    if transaction.gas_limit() > block_available_gas {
+        return Err(Error::TransactionGasLimitMoreThenAvailableBlockGas {
+            transaction_gas_limit: transaction.gas_limit(),
+            block_available_gas,
+        })
+    }
+    Ok(())
+}
+
+/// Returns the gas refunded to the sender of a transaction that used `gas_used` gas before the
+/// refund and accumulated `refund_counter` in refunds.
+///
+/// The refund is capped at a fifth of the gas used since London, see
+/// [EIP-3529](https://eips.ethereum.org/EIPS/eip-3529), and at half of it before.
+///
+/// revm applies the same cap when it computes the gas used by a transaction.
+pub fn apply_gas_refund(gas_used: u64, refund_counter: u64, is_london: bool) -> u64 {
+    let max_refund_quotient = if is_london { 5 } else { 2 };
+    refund_counter.min(gas_used / max_refund_quotient)
+}
+
 /// Verify receipts
 pub fn verify_receipt<'a>(
     expected_receipts_root: H256,
@@ -516,8 +677,9 @@ pub fn verify_receipt<'a>(
 mod tests {
     use super::*;
     use reth_primitives::{
-        hex_literal::hex, keccak256, Account, Address, BlockNumber, Bytecode, Bytes,
-        ChainSpecBuilder, ForkCondition, StorageKey, H256, MAINNET, U256,
+        hex_literal::hex, keccak256, AccessList, AccessListItem, Account, Address, BlockNumber,
+        Bytecode, Bytes, ChainSpec, ChainSpecBuilder, ForkCondition, Signature, StorageKey,
+        Transaction, TransactionKind, TxEip2930, TxLegacy, H256, MAINNET, U256,
     };
     use reth_provider::{
         post_state::{Change, Storage},
@@ -988,6 +1150,353 @@ mod tests {
         );
     }
 
+    #[test]
+    fn beacon_root_contract_call() {
+        let mut db = StateProviderTest::default();
+        db.insert_account(
+            BEACON_ROOTS_ADDRESS,
+            Account::default(),
+            Some(Bytes::from_str("0x00").unwrap()),
+            HashMap::new(),
+        );
+
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().cancun_activated().build());
+        let parent_beacon_block_root = H256::repeat_byte(0x55);
+        let timestamp = BEACON_ROOTS_HISTORY_BUFFER_LENGTH + 1;
+        let block = Block {
+            header: Header {
+                timestamp,
+                parent_beacon_block_root: Some(parent_beacon_block_root),
+                ..Default::default()
+            },
+            withdrawals: Some(vec![]),
+            ..Default::default()
+        };
+
+        let mut executor = Executor::new(chain_spec, SubState::new(State::new(db)));
+        let out = executor.execute(&block, U256::ZERO, None).unwrap();
+        assert_eq!(out.transitions_count(), 1, "Only the block transition");
+        assert_eq!(
+            out.account_storage(&BEACON_ROOTS_ADDRESS),
+            Some(&Storage {
+                wiped: false,
+                storage: BTreeMap::from([
+                    (U256::from(1), U256::from(timestamp)),
+                    (
+                        U256::from(BEACON_ROOTS_HISTORY_BUFFER_LENGTH + 1),
+                        U256::from_be_bytes(parent_beacon_block_root.0)
+                    ),
+                ]),
+            })
+        );
+
+        // Nothing is stored if the contract is not deployed
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().cancun_activated().build());
+        let db = SubState::new(State::new(StateProviderTest::default()));
+        let mut executor = Executor::new(chain_spec, db);
+        let out = executor.execute(&block, U256::ZERO, None).unwrap();
+        assert_eq!(out.account_storage(&BEACON_ROOTS_ADDRESS), None);
+    }
+
+    /// Executes the transaction of the given sender in an otherwise empty block.
+    fn execute_transaction(
+        chain_spec: ChainSpec,
+        db: StateProviderTest,
+        transaction: Transaction,
+        sender: Address,
+    ) -> Result<PostState, Error> {
+        let block = Block {
+            header: Header { gas_limit: 30_000_000, ..Default::default() },
+            body: vec![TransactionSigned::from_transaction_and_signature(
+                transaction,
+                Signature::default(),
+            )],
+            ommers: vec![],
+            withdrawals: None,
+        };
+        let mut executor = Executor::new(Arc::new(chain_spec), SubState::new(State::new(db)));
+        executor
+            .execute_transactions(&block, U256::ZERO, Some(vec![sender]))
+            .map(|(post_state, _)| post_state)
+    }
+
+    // EIP-2929: Gas cost increases for state access opcodes
+    #[test]
+    fn test_access_list_is_warm() {
+        let sender = Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
+        let contract = Address::from_str("1000000000000000000000000000000000000000").unwrap();
+        let other = Address::from_str("2000000000000000000000000000000000000000").unwrap();
+
+        let mut db = StateProviderTest::default();
+        // SLOAD(0) BALANCE(other)
+        db.insert_account(
+            contract,
+            Account::default(),
+            Some(hex!("60005473200000000000000000000000000000000000000031").into()),
+            HashMap::new(),
+        );
+        let transaction = |access_list| {
+            Transaction::Eip2930(TxEip2930 {
+                chain_id: 1,
+                gas_limit: 1_000_000,
+                to: TransactionKind::Call(contract),
+                access_list,
+                ..Default::default()
+            })
+        };
+        let chain_spec = ChainSpecBuilder::mainnet().berlin_activated().build();
+
+        // cold storage slot (2100) and cold account (2600)
+        let post_state = execute_transaction(
+            chain_spec.clone(),
+            db.clone(),
+            transaction(AccessList::default()),
+            sender,
+        )
+        .unwrap();
+        assert_eq!(post_state.receipts()[0].cumulative_gas_used, 21_000 + 3 + 2100 + 3 + 2600);
+
+        // both are warm (100 each) after paying for the access list (2400 per address and 1900
+        // per storage key)
+        let access_list = AccessList(vec![
+            AccessListItem { address: contract, storage_keys: vec![H256::zero()] },
+            AccessListItem { address: other, storage_keys: vec![] },
+        ]);
+        let post_state =
+            execute_transaction(chain_spec, db, transaction(access_list), sender).unwrap();
+        assert_eq!(
+            post_state.receipts()[0].cumulative_gas_used,
+            21_000 + 2 * 2400 + 1900 + 3 + 100 + 3 + 100
+        );
+    }
+
+    #[test]
+    fn test_apply_gas_refund() {
+        assert_eq!(apply_gas_refund(50_000, 4_800, true), 4_800);
+        assert_eq!(apply_gas_refund(50_000, 24_000, true), 10_000);
+        assert_eq!(apply_gas_refund(50_000, 24_000, false), 24_000);
+        assert_eq!(apply_gas_refund(50_000, 30_000, false), 25_000);
+    }
+
+    // EIP-3529: Reduction in refunds
+    #[test]
+    fn test_gas_refund_cap() {
+        let sender = Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
+        let contract = Address::from_str("1000000000000000000000000000000000000000").unwrap();
+
+        let mut db = StateProviderTest::default();
+        // SSTORE(1, 0) SSTORE(2, 0) SSTORE(3, 0)
+        db.insert_account(
+            contract,
+            Account::default(),
+            Some(hex!("600060015560006002556000600355").into()),
+            (1..=3).map(|slot| (H256::from_low_u64_be(slot), U256::from(1))).collect(),
+        );
+        let transaction = Transaction::Legacy(TxLegacy {
+            gas_limit: 1_000_000,
+            to: TransactionKind::Call(contract),
+            ..Default::default()
+        });
+        // each cleared slot costs 5000 gas as it's cold
+        let gas_used = 21_000 + 3 * (3 + 3 + 5000);
+
+        // 15000 refund per cleared slot before london
+        let chain_spec = ChainSpecBuilder::mainnet().berlin_activated().build();
+        let post_state =
+            execute_transaction(chain_spec, db.clone(), transaction.clone(), sender).unwrap();
+        assert_eq!(
+            post_state.receipts()[0].cumulative_gas_used,
+            gas_used - apply_gas_refund(gas_used, 3 * 15_000, false)
+        );
+
+        // 4800 refund per cleared slot since london, which exceeds a fifth of the gas used
+        let chain_spec = ChainSpecBuilder::mainnet().london_activated().build();
+        let post_state = execute_transaction(chain_spec, db, transaction, sender).unwrap();
+        assert!(3 * 4800 > gas_used / 5);
+        assert_eq!(
+            post_state.receipts()[0].cumulative_gas_used,
+            gas_used - apply_gas_refund(gas_used, 3 * 4800, true)
+        );
+    }
+
+    // EIP-3860: Limit and meter initcode
+    #[test]
+    fn test_create_transaction_initcode_size_limit() {
+        let sender = Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
+        // initcode that exceeds the limit of 2 * 24576 bytes by one byte
+        let transaction = Transaction::Legacy(TxLegacy {
+            gas_limit: 1_000_000,
+            to: TransactionKind::Create,
+            input: vec![0; 49153].into(),
+            ..Default::default()
+        });
+
+        // accepted before shanghai
+        let chain_spec = ChainSpecBuilder::mainnet().berlin_activated().build();
+        let post_state = execute_transaction(
+            chain_spec,
+            StateProviderTest::default(),
+            transaction.clone(),
+            sender,
+        )
+        .unwrap();
+        assert!(post_state.receipts()[0].success);
+
+        // rejected as invalid transaction since shanghai
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        let err =
+            execute_transaction(chain_spec, StateProviderTest::default(), transaction, sender)
+                .unwrap_err();
+        match err {
+            Error::EVM { message, .. } => {
+                assert!(message.contains("CreateInitcodeSizeLimit"), "{message}")
+            }
+            err => panic!("unexpected error: {err:?}"),
+        }
+    }
+
+    // EIP-3860: Limit and meter initcode
+    #[test]
+    fn test_create_opcode_initcode_size_limit() {
+        let sender = Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
+        let contract = Address::from_str("1000000000000000000000000000000000000000").unwrap();
+
+        let mut db = StateProviderTest::default();
+        // CREATE with 49153 bytes of (empty) memory as initcode
+        db.insert_account(
+            contract,
+            Account::default(),
+            Some(hex!("6200c00160006000f000").into()),
+            HashMap::new(),
+        );
+        let transaction = Transaction::Legacy(TxLegacy {
+            gas_limit: 1_000_000,
+            to: TransactionKind::Call(contract),
+            ..Default::default()
+        });
+
+        // succeeds before shanghai
+        let chain_spec = ChainSpecBuilder::mainnet().berlin_activated().build();
+        let post_state =
+            execute_transaction(chain_spec, db.clone(), transaction.clone(), sender).unwrap();
+        assert!(post_state.receipts()[0].success);
+
+        // the transaction fails since shanghai, but the block is still valid
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        let post_state = execute_transaction(chain_spec, db, transaction, sender).unwrap();
+        assert!(!post_state.receipts()[0].success);
+    }
+
+    // EIP-6780: SELFDESTRUCT only in same transaction
+    #[test]
+    fn test_selfdestruct_pre_existing_contract_cancun() {
+        let sender = Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
+        let contract = Address::from_str("1000000000000000000000000000000000000000").unwrap();
+
+        let mut db = StateProviderTest::default();
+        // CALLER SELFDESTRUCT
+        let code = Bytes::from(hex!("33ff"));
+        db.insert_account(
+            contract,
+            Account { balance: U256::from(1), nonce: 1, bytecode_hash: None },
+            Some(code.clone()),
+            HashMap::from([(H256::from_low_u64_be(1), U256::from(1))]),
+        );
+        let transaction = Transaction::Legacy(TxLegacy {
+            gas_limit: 1_000_000,
+            to: TransactionKind::Call(contract),
+            ..Default::default()
+        });
+
+        // the contract is deleted before cancun
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        let post_state =
+            execute_transaction(chain_spec, db.clone(), transaction.clone(), sender).unwrap();
+        assert_eq!(post_state.accounts().get(&contract), Some(&None));
+        assert!(post_state.storage().get(&contract).unwrap().wiped);
+
+        // only the balance is transferred since cancun
+        let chain_spec = ChainSpecBuilder::mainnet().cancun_activated().build();
+        let post_state = execute_transaction(chain_spec, db, transaction, sender).unwrap();
+        assert_eq!(
+            post_state.accounts().get(&contract),
+            Some(&Some(Account {
+                balance: U256::ZERO,
+                nonce: 1,
+                bytecode_hash: Some(keccak256(&code))
+            }))
+        );
+        assert!(post_state.storage().get(&contract).map_or(true, |storage| !storage.wiped));
+        assert_eq!(
+            post_state.accounts().get(&sender).unwrap().as_ref().unwrap().balance,
+            U256::from(1)
+        );
+    }
+
+    // EIP-6780: SELFDESTRUCT only in same transaction
+    #[test]
+    fn test_selfdestruct_to_self_pre_existing_contract_cancun() {
+        let sender = Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
+        let contract = Address::from_str("1000000000000000000000000000000000000000").unwrap();
+
+        let mut db = StateProviderTest::default();
+        // ADDRESS SELFDESTRUCT
+        let code = Bytes::from(hex!("30ff"));
+        db.insert_account(
+            contract,
+            Account { balance: U256::from(1), nonce: 1, bytecode_hash: None },
+            Some(code.clone()),
+            HashMap::default(),
+        );
+        let transaction = Transaction::Legacy(TxLegacy {
+            gas_limit: 1_000_000,
+            to: TransactionKind::Call(contract),
+            ..Default::default()
+        });
+
+        // the contract and its balance are deleted before cancun
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        let post_state =
+            execute_transaction(chain_spec, db.clone(), transaction.clone(), sender).unwrap();
+        assert_eq!(post_state.accounts().get(&contract), Some(&None));
+        let gas_used = post_state.receipts()[0].cumulative_gas_used;
+
+        // the balance is kept since cancun
+        let chain_spec = ChainSpecBuilder::mainnet().cancun_activated().build();
+        let post_state = execute_transaction(chain_spec, db, transaction, sender).unwrap();
+        assert!(post_state.receipts()[0].success);
+        assert_eq!(post_state.receipts()[0].cumulative_gas_used, gas_used);
+        let account =
+            Account { balance: U256::from(1), nonce: 1, bytecode_hash: Some(keccak256(&code)) };
+        assert!(post_state
+            .accounts()
+            .get(&contract)
+            .map_or(true, |changed| changed == &Some(account)));
+    }
+
+    // EIP-6780: SELFDESTRUCT only in same transaction
+    #[test]
+    fn test_selfdestruct_new_contract_cancun() {
+        let sender = Address::from_str("a94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
+        let contract = revm::primitives::create_address(sender, 0);
+
+        // initcode: SSTORE(1, 1) CALLER SELFDESTRUCT
+        let transaction = Transaction::Legacy(TxLegacy {
+            gas_limit: 1_000_000,
+            to: TransactionKind::Create,
+            input: hex!("600160015533ff").into(),
+            ..Default::default()
+        });
+
+        let chain_spec = ChainSpecBuilder::mainnet().cancun_activated().build();
+        let post_state =
+            execute_transaction(chain_spec, StateProviderTest::default(), transaction, sender)
+                .unwrap();
+        assert!(post_state.receipts()[0].success);
+        assert_eq!(post_state.accounts().get(&contract), Some(&None));
+        assert!(post_state.storage().get(&contract).unwrap().wiped);
+    }
+
     #[test]
     fn test_account_state_preserved() {
         let account = Address::from_str("c94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap();
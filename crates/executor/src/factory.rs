@@ -2,7 +2,7 @@ use reth_primitives::ChainSpec;
 use reth_provider::{ExecutorFactory, StateProvider};
 use reth_revm::database::{State, SubState};
 
-use crate::executor::Executor;
+use crate::{executor::Executor, parallel::ParallelExecutor};
 use std::sync::Arc;
 
 /// Factory that spawn Executor.
@@ -32,3 +32,31 @@ impl ExecutorFactory for Factory {
         self.chain_spec.as_ref()
     }
 }
+
+/// Factory that spawns [ParallelExecutor]s.
+#[derive(Clone, Debug)]
+pub struct ParallelFactory {
+    chain_spec: Arc<ChainSpec>,
+}
+
+impl ParallelFactory {
+    /// Create new factory
+    pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
+        Self { chain_spec }
+    }
+}
+
+impl ExecutorFactory for ParallelFactory {
+    type Executor<SP: StateProvider> = ParallelExecutor<SP>;
+
+    /// Executor with [`StateProvider`]
+    fn with_sp<SP: StateProvider>(&self, sp: SP) -> Self::Executor<SP> {
+        let substate = SubState::new(State::new(sp));
+        ParallelExecutor::new(self.chain_spec.clone(), substate)
+    }
+
+    /// Return internal chainspec
+    fn chain_spec(&self) -> &ChainSpec {
+        self.chain_spec.as_ref()
+    }
+}
@@ -20,4 +20,9 @@ pub mod executor;
 
 /// ExecutorFactory impl
 pub mod factory;
-pub use factory::Factory;
+pub use factory::{Factory, ParallelFactory};
+
+/// Speculative parallel executor
+pub mod parallel;
+
+mod selfdestruct;
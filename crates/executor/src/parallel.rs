@@ -0,0 +1,646 @@
+//! Speculative parallel execution of the transactions of a block.
+
+use crate::{
+    executor::{verify_available_block_gas, verify_receipt, Executor},
+    post_state::PostState,
+    selfdestruct::SelfDestructInspector,
+};
+use rayon::prelude::*;
+use reth_interfaces::executor::Error;
+use reth_primitives::{
+    constants::BEACON_ROOTS_ADDRESS, Address, Block, ChainSpec, Hardfork, TransactionKind,
+    TransactionSigned, H256, KECCAK_EMPTY, U256,
+};
+use reth_provider::{BlockExecutor, StateProvider};
+use reth_revm::{database::SubState, env::fill_tx_env, to_reth_acc};
+use revm::{
+    db::DatabaseRef,
+    interpreter::{opcode, InstructionResult, Interpreter},
+    primitives::{hash_map, Account as RevmAccount, AccountInfo, Bytecode, Env, ResultAndState},
+    Database, EVMData, Inspector, EVM,
+};
+use std::{collections::HashSet, sync::Arc};
+
+/// The default share of conflicting transactions above which a block is executed serially.
+pub const DEFAULT_MAX_CONFLICT_RATE: f64 = 0.8;
+
+/// An account (`None`) or one of its storage slots.
+pub type StateKey = (Address, Option<H256>);
+
+/// Block executor that speculatively executes the transactions of a block in parallel.
+///
+/// All transactions are first executed concurrently on the [rayon] thread pool against the state
+/// before the block. The results are then committed in order: the result of a transaction is
+/// only used if it touched no account or storage slot that was written by a preceding transaction
+/// of the block, otherwise the transaction is executed again on top of the preceding ones.
+///
+/// Every transaction pays its fees to the block beneficiary, so the beneficiary is not considered
+/// a conflict for transactions that don't access it otherwise. Instead, the fees of their
+/// speculative execution are added to the beneficiary's balance when they're committed.
+///
+/// If the share of conflicting transactions exceeds the
+/// [max conflict rate](ParallelExecutor::with_max_conflict_rate), the speculative results are
+/// discarded and the block is executed serially.
+pub struct ParallelExecutor<DB>
+where
+    DB: StateProvider,
+{
+    /// The executor that commits the results and executes conflicting transactions.
+    executor: Executor<DB>,
+    /// The share of conflicting transactions above which a block is executed serially.
+    max_conflict_rate: f64,
+}
+
+impl<DB> ParallelExecutor<DB>
+where
+    DB: StateProvider,
+{
+    /// Creates a new parallel executor from the given chain spec and database.
+    pub fn new(chain_spec: Arc<ChainSpec>, db: SubState<DB>) -> Self {
+        Self {
+            executor: Executor::new(chain_spec, db),
+            max_conflict_rate: DEFAULT_MAX_CONFLICT_RATE,
+        }
+    }
+
+    /// Sets the share of conflicting transactions above which a block is executed serially.
+    pub fn with_max_conflict_rate(mut self, max_conflict_rate: f64) -> Self {
+        self.max_conflict_rate = max_conflict_rate;
+        self
+    }
+
+    /// Gives a reference to the database
+    pub fn db(&mut self) -> &mut SubState<DB> {
+        self.executor.db()
+    }
+
+    /// Runs the provided transactions and commits their state to the run-time database.
+    ///
+    /// The returned [PostState] is the same as the one of [Executor::execute_transactions].
+    pub fn execute_transactions(
+        &mut self,
+        block: &Block,
+        total_difficulty: U256,
+        senders: Option<Vec<Address>>,
+    ) -> Result<(PostState, u64), Error> {
+        if block.body.len() < 2 {
+            return self.executor.execute_transactions(block, total_difficulty, senders)
+        }
+        let senders = self.executor.recover_senders(&block.body, senders)?;
+
+        self.executor.init_env(&block.header, total_difficulty);
+        let env = self.executor.env().clone();
+        let beneficiary = env.block.coinbase;
+
+        // The changes are part of the transition of the first transaction, like in the serial
+        // executor. They are applied before any transaction is executed, and transactions that
+        // touch the written slots are executed again on top of them.
+        let mut post_state = PostState::with_tx_capacity(block.body.len());
+        let mut detector = ConflictDetector::new();
+        let beacon_root_slots =
+            self.executor.apply_beacon_root_contract_call(block, &mut post_state)?;
+        detector.record(
+            beacon_root_slots
+                .into_iter()
+                .map(|slot| (BEACON_ROOTS_ADDRESS, Some(H256(slot.to_be_bytes())))),
+        );
+
+        // execute all transactions against the state before the block
+        let is_cancun =
+            self.executor.chain_spec.fork(Hardfork::Cancun).active_at_timestamp(block.timestamp);
+        let db = &*self.executor.db();
+        let beneficiary_balance =
+            db.basic(beneficiary).map_err(|_| Error::ProviderError)?.unwrap_or_default().balance;
+        let speculative = block
+            .body
+            .par_iter()
+            .zip(senders.par_iter())
+            .map(|(transaction, sender)| {
+                execute_speculatively(&env, db, transaction, *sender, is_cancun)
+            })
+            .collect::<Vec<_>>();
+
+        if self.exceeds_max_conflict_rate(detector.clone(), &speculative) {
+            tracing::trace!(
+                target: "executor::parallel",
+                block = block.number,
+                "Conflict rate exceeded, executing block serially"
+            );
+            let cumulative_gas_used =
+                self.executor.execute_block_transactions(block, senders, &mut post_state)?;
+            return Ok((post_state, cumulative_gas_used))
+        }
+
+        let mut cumulative_gas_used = 0;
+        for ((transaction, sender), speculative) in
+            block.body.iter().zip(senders.into_iter()).zip(speculative.into_iter())
+        {
+            verify_available_block_gas(block, transaction, cumulative_gas_used)?;
+
+            let result_and_state = match speculative
+                .filter(|result| !detector.conflicts(&result.access.touched))
+            {
+                Some(SpeculativeResult { mut result_and_state, access, accesses_beneficiary }) => {
+                    self.prepare_speculative_commit(
+                        &mut result_and_state.state,
+                        (!accesses_beneficiary).then_some((beneficiary, beneficiary_balance)),
+                    )?;
+                    detector.record(access.written);
+                    result_and_state
+                }
+                None => {
+                    let result_and_state = self.executor.transact(transaction, sender)?;
+                    let access = AccessSets::new(&*self.executor.db(), &result_and_state.state);
+                    detector.record(access.written);
+                    result_and_state
+                }
+            };
+
+            self.executor.commit_transaction(
+                block,
+                transaction,
+                result_and_state,
+                &mut cumulative_gas_used,
+                &mut post_state,
+            );
+        }
+
+        Ok((post_state, cumulative_gas_used))
+    }
+
+    /// Returns `true` if the share of speculative results that conflict with preceding
+    /// speculative results, or with the writes already recorded by the given detector, exceeds the
+    /// max conflict rate.
+    fn exceeds_max_conflict_rate(
+        &self,
+        mut detector: ConflictDetector,
+        speculative: &[Option<SpeculativeResult>],
+    ) -> bool {
+        let mut conflicts = 0;
+        for result in speculative {
+            let Some(result) = result else {
+                conflicts += 1;
+                continue
+            };
+            if detector.conflicts(&result.access.touched) {
+                conflicts += 1;
+            }
+            detector.record(result.access.written.iter().copied());
+        }
+        conflicts as f64 > self.max_conflict_rate * speculative.len() as f64
+    }
+
+    /// Prepares the state changes of a speculative execution to be committed on top of the
+    /// preceding transactions.
+    ///
+    /// Loads the changed accounts into the run-time database like the execution of the
+    /// transaction would.
+    ///
+    /// If the transaction didn't access the given beneficiary other than by paying fees, the
+    /// beneficiary might have been changed by the preceding transactions. The fees are then added
+    /// to its current balance instead of the given balance before the block.
+    fn prepare_speculative_commit(
+        &mut self,
+        state: &mut hash_map::HashMap<Address, RevmAccount>,
+        fee_recipient: Option<(Address, U256)>,
+    ) -> Result<(), Error> {
+        let db = self.executor.db();
+        for (address, account) in state.iter_mut() {
+            let info = db.basic(*address).map_err(|_| Error::ProviderError)?.unwrap_or_default();
+            if info.code_hash != KECCAK_EMPTY && info.code_hash == account.info.code_hash {
+                db.code_by_hash(info.code_hash).map_err(|_| Error::ProviderError)?;
+            }
+
+            if let Some((_, balance_before_block)) =
+                fee_recipient.filter(|(beneficiary, _)| beneficiary == address)
+            {
+                let fees = account.info.balance.saturating_sub(balance_before_block);
+                account.info = AccountInfo { balance: info.balance + fees, ..info };
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<DB> BlockExecutor<DB> for ParallelExecutor<DB>
+where
+    DB: StateProvider,
+{
+    fn execute(
+        &mut self,
+        block: &Block,
+        total_difficulty: U256,
+        senders: Option<Vec<Address>>,
+    ) -> Result<PostState, Error> {
+        let (post_state, cumulative_gas_used) =
+            self.execute_transactions(block, total_difficulty, senders)?;
+        self.executor.finish_block(block, total_difficulty, cumulative_gas_used, post_state)
+    }
+
+    fn execute_and_verify_receipt(
+        &mut self,
+        block: &Block,
+        total_difficulty: U256,
+        senders: Option<Vec<Address>>,
+    ) -> Result<PostState, Error> {
+        let post_state = self.execute(block, total_difficulty, senders)?;
+
+        if self.executor.chain_spec.fork(Hardfork::Byzantium).active_at_block(block.header.number) {
+            verify_receipt(
+                block.header.receipts_root,
+                block.header.logs_bloom,
+                post_state.receipts().iter(),
+            )?;
+        }
+
+        Ok(post_state)
+    }
+}
+
+/// Detects transactions that conflict with the preceding transactions of a block.
+#[derive(Debug, Clone, Default)]
+pub struct ConflictDetector {
+    /// The accounts and storage slots written by the preceding transactions.
+    written: HashSet<StateKey>,
+}
+
+impl ConflictDetector {
+    /// Creates a new detector without any preceding transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if any of the touched accounts or storage slots was written by a preceding
+    /// transaction.
+    pub fn conflicts(&self, touched: &HashSet<StateKey>) -> bool {
+        !self.written.is_disjoint(touched)
+    }
+
+    /// Records the accounts and storage slots written by a transaction.
+    pub fn record(&mut self, written: impl IntoIterator<Item = StateKey>) {
+        self.written.extend(written)
+    }
+}
+
+/// The accounts and storage slots accessed by a transaction.
+#[derive(Debug, Default)]
+struct AccessSets {
+    /// Accounts and storage slots that were read or written.
+    touched: HashSet<StateKey>,
+    /// Accounts and storage slots that were written.
+    written: HashSet<StateKey>,
+}
+
+impl AccessSets {
+    /// Collects the accessed accounts and storage slots from the state changes of a transaction
+    /// that was executed on top of the given database.
+    fn new<D: DatabaseRef>(db: &D, state: &hash_map::HashMap<Address, RevmAccount>) -> Self {
+        let mut access = Self::default();
+        for (address, account) in state {
+            access.touched.insert((*address, None));
+
+            let is_changed = match db.basic(*address) {
+                Ok(info) => {
+                    let info = info.unwrap_or_default();
+                    info.balance != account.info.balance ||
+                        info.nonce != account.info.nonce ||
+                        info.code_hash != account.info.code_hash
+                }
+                Err(_) => true,
+            };
+            // touched empty accounts are removed by the state clear EIP
+            let is_cleared = account.is_touched && to_reth_acc(&account.info).is_empty();
+            if is_changed || is_cleared || account.is_destroyed || account.storage_cleared {
+                access.written.insert((*address, None));
+            }
+
+            for (slot, value) in account.storage.iter() {
+                let key = (*address, Some(H256(slot.to_be_bytes())));
+                access.touched.insert(key);
+                if value.original_value() != value.present_value() {
+                    access.written.insert(key);
+                }
+            }
+        }
+        access
+    }
+}
+
+/// The result of a transaction that was executed against the state before the block.
+struct SpeculativeResult {
+    /// The result and state changes of the execution.
+    result_and_state: ResultAndState,
+    /// The accounts and storage slots accessed by the execution.
+    access: AccessSets,
+    /// Whether the execution accessed the beneficiary other than by paying fees to it.
+    accesses_beneficiary: bool,
+}
+
+/// Executes the transaction against the given state.
+///
+/// Returns `None` if the transaction is invalid on top of the given state, since it might still
+/// be valid on top of the preceding transactions.
+fn execute_speculatively<D>(
+    env: &Env,
+    db: &D,
+    transaction: &TransactionSigned,
+    sender: Address,
+    is_cancun: bool,
+) -> Option<SpeculativeResult>
+where
+    D: DatabaseRef,
+{
+    let beneficiary = env.block.coinbase;
+
+    let mut evm = EVM::new();
+    evm.env = env.clone();
+    evm.database(DatabaseSnapshot(db));
+    fill_tx_env(&mut evm.env.tx, transaction, sender);
+
+    let mut inspector = BeneficiaryAccessInspector::new(beneficiary);
+    let result_and_state = if is_cancun {
+        evm.inspect(SelfDestructInspector::new(Some(&mut inspector))).ok()?
+    } else {
+        evm.inspect(&mut inspector).ok()?
+    };
+
+    let mut access = AccessSets::new(db, &result_and_state.state);
+    let accesses_beneficiary = inspector.accessed ||
+        sender == beneficiary ||
+        matches!(transaction.kind(), TransactionKind::Call(to) if to == beneficiary);
+    if !accesses_beneficiary {
+        access.touched.remove(&(beneficiary, None));
+    }
+
+    Some(SpeculativeResult { result_and_state, access, accesses_beneficiary })
+}
+
+/// A read-only view of a database that is shared by the speculative executions.
+struct DatabaseSnapshot<'a, D>(&'a D);
+
+impl<'a, D> Database for DatabaseSnapshot<'a, D>
+where
+    D: DatabaseRef,
+{
+    type Error = D::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.0.basic(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: H256) -> Result<Bytecode, Self::Error> {
+        self.0.code_by_hash(code_hash)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.0.storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<H256, Self::Error> {
+        self.0.block_hash(number)
+    }
+}
+
+/// An [Inspector] that detects whether a transaction accesses the block beneficiary by any other
+/// means than paying fees to it.
+#[derive(Debug)]
+struct BeneficiaryAccessInspector {
+    /// The block beneficiary.
+    beneficiary: Address,
+    /// Whether the beneficiary was accessed.
+    accessed: bool,
+}
+
+impl BeneficiaryAccessInspector {
+    fn new(beneficiary: Address) -> Self {
+        Self { beneficiary, accessed: false }
+    }
+}
+
+impl<DB> Inspector<DB> for BeneficiaryAccessInspector
+where
+    DB: Database,
+{
+    fn step(
+        &mut self,
+        interpreter: &mut Interpreter,
+        _data: &mut EVMData<'_, DB>,
+        _is_static: bool,
+    ) -> InstructionResult {
+        let pc = interpreter.program_counter();
+        let op = interpreter.contract.bytecode.bytecode()[pc];
+
+        let address_position = match op {
+            opcode::EXTCODECOPY |
+            opcode::EXTCODEHASH |
+            opcode::EXTCODESIZE |
+            opcode::BALANCE |
+            opcode::SELFDESTRUCT => 0,
+            opcode::DELEGATECALL | opcode::CALL | opcode::STATICCALL | opcode::CALLCODE => 1,
+            _ => return InstructionResult::Continue,
+        };
+        if let Ok(slot) = interpreter.stack().peek(address_position) {
+            let addr: Address = H256::from(slot.to_be_bytes()).into();
+            if addr == self.beneficiary {
+                self.accessed = true;
+            }
+        }
+
+        InstructionResult::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        Account, Bytecode as RethBytecode, Bytes, ChainSpecBuilder, Header, Signature, StorageKey,
+        Transaction, TxLegacy,
+    };
+    use reth_provider::{AccountProvider, BlockHashProvider};
+    use reth_revm::database::State;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Default, Clone)]
+    struct StateProviderTest {
+        accounts: HashMap<Address, Account>,
+    }
+
+    impl AccountProvider for StateProviderTest {
+        fn basic_account(&self, address: Address) -> reth_interfaces::Result<Option<Account>> {
+            Ok(self.accounts.get(&address).copied())
+        }
+    }
+
+    impl BlockHashProvider for StateProviderTest {
+        fn block_hash(&self, _number: u64) -> reth_interfaces::Result<Option<H256>> {
+            Ok(None)
+        }
+
+        fn canonical_hashes_range(
+            &self,
+            _start: u64,
+            _end: u64,
+        ) -> reth_interfaces::Result<Vec<H256>> {
+            Ok(vec![])
+        }
+    }
+
+    impl StateProvider for StateProviderTest {
+        fn storage(
+            &self,
+            _account: Address,
+            _storage_key: StorageKey,
+        ) -> reth_interfaces::Result<Option<U256>> {
+            Ok(None)
+        }
+
+        fn bytecode_by_hash(
+            &self,
+            _code_hash: H256,
+        ) -> reth_interfaces::Result<Option<RethBytecode>> {
+            Ok(None)
+        }
+
+        fn proof(
+            &self,
+            _address: Address,
+            _keys: &[H256],
+        ) -> reth_interfaces::Result<(Vec<Bytes>, H256, Vec<Vec<Bytes>>)> {
+            unimplemented!()
+        }
+    }
+
+    fn transfer(nonce: u64, to: Address) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy {
+                nonce,
+                gas_price: 1,
+                gas_limit: 21_000,
+                to: TransactionKind::Call(to),
+                value: 1,
+                ..Default::default()
+            }),
+            Signature::default(),
+        )
+    }
+
+    /// Returns a state in which each of the given senders has enough balance for a few transfers.
+    fn funded_state(senders: &[Address]) -> StateProviderTest {
+        let mut db = StateProviderTest::default();
+        for sender in senders {
+            db.accounts.insert(
+                *sender,
+                Account { balance: U256::from(1_000_000), nonce: 0, bytecode_hash: None },
+            );
+        }
+        db
+    }
+
+    /// Executes the transactions of the block with the serial and the parallel executor.
+    fn execute_serial_and_parallel(
+        chain_spec: ChainSpec,
+        db: StateProviderTest,
+        block: &Block,
+        senders: Vec<Address>,
+    ) -> ((PostState, u64), (PostState, u64)) {
+        let chain_spec = Arc::new(chain_spec);
+        let serial = Executor::new(chain_spec.clone(), SubState::new(State::new(db.clone())))
+            .execute_transactions(block, U256::ZERO, Some(senders.clone()))
+            .unwrap();
+        let parallel = ParallelExecutor::new(chain_spec, SubState::new(State::new(db)))
+            .execute_transactions(block, U256::ZERO, Some(senders))
+            .unwrap();
+        (serial, parallel)
+    }
+
+    #[test]
+    fn test_parallel_execution_matches_serial() {
+        let alice = Address::from_low_u64_be(1);
+        let bob = Address::from_low_u64_be(2);
+        let beneficiary = Address::from_low_u64_be(100);
+
+        // the second transaction of alice conflicts with her first one
+        let block = Block {
+            header: Header { beneficiary, gas_limit: 1_000_000, ..Default::default() },
+            body: vec![
+                transfer(0, Address::from_low_u64_be(10)),
+                transfer(0, Address::from_low_u64_be(11)),
+                transfer(1, Address::from_low_u64_be(12)),
+            ],
+            ommers: vec![],
+            withdrawals: None,
+        };
+
+        let ((expected, expected_gas), (post_state, gas)) = execute_serial_and_parallel(
+            ChainSpecBuilder::mainnet().berlin_activated().build(),
+            funded_state(&[alice, bob]),
+            &block,
+            vec![alice, bob, alice],
+        );
+
+        assert_eq!(gas, expected_gas);
+        assert_eq!(post_state.receipts(), expected.receipts());
+        assert_eq!(post_state.accounts(), expected.accounts());
+        assert_eq!(
+            post_state.accounts().get(&beneficiary),
+            Some(&Some(Account { balance: U256::from(3 * 21_000), nonce: 0, bytecode_hash: None }))
+        );
+    }
+
+    #[test]
+    fn test_parallel_execution_applies_beacon_root() {
+        let alice = Address::from_low_u64_be(1);
+        let bob = Address::from_low_u64_be(2);
+
+        let mut db = funded_state(&[alice, bob]);
+        db.accounts.insert(
+            BEACON_ROOTS_ADDRESS,
+            Account { bytecode_hash: Some(H256::repeat_byte(0x01)), ..Default::default() },
+        );
+
+        let block = Block {
+            header: Header {
+                gas_limit: 1_000_000,
+                timestamp: 1,
+                parent_beacon_block_root: Some(H256::repeat_byte(0x55)),
+                ..Default::default()
+            },
+            body: vec![
+                transfer(0, Address::from_low_u64_be(10)),
+                transfer(0, Address::from_low_u64_be(11)),
+            ],
+            ommers: vec![],
+            withdrawals: Some(vec![]),
+        };
+
+        let ((expected, expected_gas), (post_state, gas)) = execute_serial_and_parallel(
+            ChainSpecBuilder::mainnet().cancun_activated().build(),
+            db,
+            &block,
+            vec![alice, bob],
+        );
+
+        assert!(expected.account_storage(&BEACON_ROOTS_ADDRESS).is_some());
+        assert_eq!(gas, expected_gas);
+        assert_eq!(post_state.receipts(), expected.receipts());
+        assert_eq!(post_state.accounts(), expected.accounts());
+        assert_eq!(
+            post_state.account_storage(&BEACON_ROOTS_ADDRESS),
+            expected.account_storage(&BEACON_ROOTS_ADDRESS)
+        );
+    }
+
+    #[test]
+    fn test_conflict_detector() {
+        let account = Address::from_low_u64_be(1);
+        let slot = (account, Some(H256::from_low_u64_be(1)));
+
+        let mut detector = ConflictDetector::new();
+        detector.record([slot]);
+
+        assert!(detector.conflicts(&HashSet::from([slot])));
+        assert!(!detector.conflicts(&HashSet::from([(account, None)])));
+        assert!(!detector.conflicts(&HashSet::from([(account, Some(H256::from_low_u64_be(2)))])));
+    }
+}
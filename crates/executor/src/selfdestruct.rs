@@ -0,0 +1,175 @@
+//! EIP-6780 handling of `SELFDESTRUCT` with the contract itself as the beneficiary.
+
+use reth_primitives::{bytes::Bytes, Address, H256, KECCAK_EMPTY};
+use revm::{
+    interpreter::{opcode, CallInputs, CreateInputs, Gas, InstructionResult, Interpreter},
+    Database, EVMData, Inspector,
+};
+
+/// The gas cost of a `SELFDESTRUCT` whose beneficiary is the warm, existing contract itself.
+const SELFDESTRUCT_TO_SELF_GAS: u64 = 5000;
+
+/// An [Inspector] that halts a `SELFDESTRUCT` of a pre-existing contract that names itself as the
+/// beneficiary, and forwards all hooks to an optional inner inspector.
+///
+/// revm burns the balance of a contract that self-destructs to itself. Since Cancun, such a
+/// contract is only deleted if it was created in the same transaction (EIP-6780), otherwise the
+/// `SELFDESTRUCT` has no effect on its balance. The instruction is therefore replaced by a `STOP`
+/// that charges the gas of the `SELFDESTRUCT`.
+///
+/// A contract is pre-existing if it had code before the transaction, see
+/// `Executor::preserve_selfdestructed_accounts`.
+pub(crate) struct SelfDestructInspector<'a, I> {
+    inner: Option<&'a mut I>,
+}
+
+impl<'a, I> SelfDestructInspector<'a, I> {
+    /// Creates a new inspector that forwards all hooks to the given inspector, if any.
+    pub(crate) fn new(inner: Option<&'a mut I>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, DB, I> Inspector<DB> for SelfDestructInspector<'a, I>
+where
+    DB: Database,
+    I: Inspector<DB>,
+{
+    fn initialize_interp(
+        &mut self,
+        interpreter: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        is_static: bool,
+    ) -> InstructionResult {
+        match &mut self.inner {
+            Some(inner) => inner.initialize_interp(interpreter, data, is_static),
+            None => InstructionResult::Continue,
+        }
+    }
+
+    fn step(
+        &mut self,
+        interpreter: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        is_static: bool,
+    ) -> InstructionResult {
+        if let Some(inner) = &mut self.inner {
+            let status = inner.step(interpreter, data, is_static);
+            if status != InstructionResult::Continue {
+                return status
+            }
+        }
+
+        // a `SELFDESTRUCT` in a static context fails as usual
+        let pc = interpreter.program_counter();
+        if is_static || interpreter.contract.bytecode.bytecode()[pc] != opcode::SELFDESTRUCT {
+            return InstructionResult::Continue
+        }
+
+        let address = interpreter.contract.address;
+        let Ok(target) = interpreter.stack().peek(0) else { return InstructionResult::Continue };
+        if Address::from(H256::from(target.to_be_bytes())) != address {
+            return InstructionResult::Continue
+        }
+
+        // the database still holds the state before the transaction
+        let is_pre_existing = data
+            .db
+            .basic(address)
+            .ok()
+            .flatten()
+            .map_or(false, |info| info.code_hash != KECCAK_EMPTY);
+        if !is_pre_existing {
+            return InstructionResult::Continue
+        }
+
+        if !interpreter.gas.record_cost(SELFDESTRUCT_TO_SELF_GAS) {
+            return InstructionResult::OutOfGas
+        }
+        InstructionResult::Stop
+    }
+
+    fn log(
+        &mut self,
+        evm_data: &mut EVMData<'_, DB>,
+        address: &Address,
+        topics: &[H256],
+        data: &Bytes,
+    ) {
+        if let Some(inner) = &mut self.inner {
+            inner.log(evm_data, address, topics, data);
+        }
+    }
+
+    fn step_end(
+        &mut self,
+        interpreter: &mut Interpreter,
+        data: &mut EVMData<'_, DB>,
+        is_static: bool,
+        eval: InstructionResult,
+    ) -> InstructionResult {
+        match &mut self.inner {
+            Some(inner) => inner.step_end(interpreter, data, is_static, eval),
+            None => InstructionResult::Continue,
+        }
+    }
+
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CallInputs,
+        is_static: bool,
+    ) -> (InstructionResult, Gas, Bytes) {
+        match &mut self.inner {
+            Some(inner) => inner.call(data, inputs, is_static),
+            None => (InstructionResult::Continue, Gas::new(inputs.gas_limit), Bytes::new()),
+        }
+    }
+
+    fn call_end(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &CallInputs,
+        remaining_gas: Gas,
+        ret: InstructionResult,
+        out: Bytes,
+        is_static: bool,
+    ) -> (InstructionResult, Gas, Bytes) {
+        match &mut self.inner {
+            Some(inner) => inner.call_end(data, inputs, remaining_gas, ret, out, is_static),
+            None => (ret, remaining_gas, out),
+        }
+    }
+
+    fn create(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &mut CreateInputs,
+    ) -> (InstructionResult, Option<Address>, Gas, Bytes) {
+        match &mut self.inner {
+            Some(inner) => inner.create(data, inputs),
+            None => (InstructionResult::Continue, None, Gas::new(inputs.gas_limit), Bytes::new()),
+        }
+    }
+
+    fn create_end(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        inputs: &CreateInputs,
+        ret: InstructionResult,
+        address: Option<Address>,
+        remaining_gas: Gas,
+        out: Bytes,
+    ) -> (InstructionResult, Option<Address>, Gas, Bytes) {
+        match &mut self.inner {
+            Some(inner) => inner.create_end(data, inputs, ret, address, remaining_gas, out),
+            None => (ret, address, remaining_gas, out),
+        }
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address) {
+        if let Some(inner) = &mut self.inner {
+            inner.selfdestruct(contract, target);
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use reth_primitives::{
-    BlockHash, BlockNumber, InvalidTransactionError, SealedBlock, SealedHeader, H256, U256,
+    BlockHash, BlockNumber, HeaderValidationError, InvalidTransactionError, SealedBlock,
+    SealedHeader, H256, U256,
 };
 use std::fmt::Debug;
 use tokio::sync::watch::Receiver;
@@ -90,6 +91,10 @@ pub enum ConsensusError {
     BaseFeeMissing,
     #[error("Block base fee ({got:?}) is different then expected: ({expected:?}).")]
     BaseFeeDiff { expected: u64, got: u64 },
+    #[error("Excess blob gas missing.")]
+    ExcessBlobGasMissing,
+    #[error("Block excess blob gas ({got:?}) is different then expected: ({expected:?}).")]
+    ExcessBlobGasDiff { expected: u64, got: u64 },
     #[error("Transaction signer recovery error.")]
     TransactionSignerRecoveryError,
     #[error(
@@ -102,6 +107,8 @@ pub enum ConsensusError {
     ExtraDataExceedsMax { len: usize },
     #[error("Difficulty after merge is not zero")]
     TheMergeDifficultyIsNotZero,
+    #[error("Difficulty before merge is zero")]
+    PreMergeDifficultyIsZero,
     #[error("Nonce after merge is not zero")]
     TheMergeNonceIsNotZero,
     #[error("Ommer root after merge is not empty")]
@@ -116,7 +123,45 @@ pub enum ConsensusError {
     WithdrawalIndexInvalid { got: u64, expected: u64 },
     #[error("Missing withdrawals")]
     BodyWithdrawalsMissing,
+    #[error("Block has {count} ommers, but at most {max} are allowed.")]
+    TooManyOmmers { count: usize, max: usize },
+    #[error("Ommer [hash:{hash:?}] is included more than once.")]
+    OmmerDuplicated { hash: BlockHash },
+    #[error("Ommer [hash:{hash:?}] was already included by an ancestor.")]
+    OmmerAlreadyIncluded { hash: BlockHash },
+    #[error("Ommer [hash:{hash:?}] is the block itself or one of its ancestors.")]
+    OmmerIsAncestor { hash: BlockHash },
+    #[error("Ommer [hash:{hash:?}] parent [hash:{parent_hash:?}] is not a valid ommer parent.")]
+    OmmerParentInvalid { hash: BlockHash, parent_hash: BlockHash },
     /// Error for a transaction that violates consensus.
     #[error(transparent)]
     InvalidTransaction(#[from] InvalidTransactionError),
 }
+
+impl From<HeaderValidationError> for ConsensusError {
+    fn from(err: HeaderValidationError) -> Self {
+        match err {
+            HeaderValidationError::GasLimitInvalidIncrease {
+                parent_gas_limit,
+                child_gas_limit,
+            } => ConsensusError::GasLimitInvalidIncrease { parent_gas_limit, child_gas_limit },
+            HeaderValidationError::GasLimitInvalidDecrease {
+                parent_gas_limit,
+                child_gas_limit,
+            } => ConsensusError::GasLimitInvalidDecrease { parent_gas_limit, child_gas_limit },
+            HeaderValidationError::TimestampNotAfterParent { parent_timestamp, timestamp } => {
+                ConsensusError::TimestampIsInPast { parent_timestamp, timestamp }
+            }
+            HeaderValidationError::ExtraDataExceedsMax { len } => {
+                ConsensusError::ExtraDataExceedsMax { len }
+            }
+            HeaderValidationError::TheMergeDifficultyIsNotZero { .. } => {
+                ConsensusError::TheMergeDifficultyIsNotZero
+            }
+            HeaderValidationError::BaseFeeMissing => ConsensusError::BaseFeeMissing,
+            HeaderValidationError::BaseFeeDiff { expected, got } => {
+                ConsensusError::BaseFeeDiff { expected, got }
+            }
+        }
+    }
+}
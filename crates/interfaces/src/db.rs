@@ -28,4 +28,7 @@ pub enum Error {
     /// Failed to decode a key from a table.
     #[error("Error decoding value.")]
     DecodeError,
+    /// The database reached the maximum size of its memory map.
+    #[error("Database map is full.")]
+    MapFull,
 }
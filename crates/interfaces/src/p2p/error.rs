@@ -156,6 +156,14 @@ pub enum DownloadError {
         /// The block number of the expected tip
         expected: u64,
     },
+    /// Received a header at the block number of the trusted checkpoint with a different hash
+    #[error("Received invalid checkpoint: {received:?}. Expected {expected:?}.")]
+    InvalidCheckpoint {
+        /// The hash of the received header
+        received: H256,
+        /// The hash of the trusted checkpoint
+        expected: H256,
+    },
     /// Received a response to a request with unexpected start block
     #[error("Headers response starts at unexpected block: {received:?}. Expected {expected:?}.")]
     HeadersResponseStartBlockMismatch {
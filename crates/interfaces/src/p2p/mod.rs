@@ -12,6 +12,9 @@ pub mod bodies;
 /// [`HeadersClient`]: crate::p2p::headers::client::HeadersClient
 pub mod headers;
 
+/// Traits for implementing P2P `snap` protocol clients.
+pub mod snap;
+
 /// Error types broadly used by p2p interfaces for any operation which may produce an error when
 /// interacting with the network implementation
 pub mod error;
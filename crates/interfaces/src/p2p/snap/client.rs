@@ -0,0 +1,30 @@
+use std::pin::Pin;
+
+use crate::p2p::{download::DownloadClient, error::PeerRequestResult, priority::Priority};
+use futures::Future;
+pub use reth_eth_wire::snap::{AccountRange, GetAccountRange};
+
+/// The account range future type
+pub type AccountRangeFut =
+    Pin<Box<dyn Future<Output = PeerRequestResult<AccountRange>> + Send + Sync>>;
+
+/// A client capable of downloading state ranges with the `snap` protocol.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait SnapClient: DownloadClient {
+    /// The account range type
+    type Output: Future<Output = PeerRequestResult<AccountRange>> + Sync + Send + Unpin;
+
+    /// Sends the account range request to the p2p network and returns the account range response
+    /// received from a peer.
+    fn get_account_range(&self, request: GetAccountRange) -> Self::Output {
+        self.get_account_range_with_priority(request, Priority::Normal)
+    }
+
+    /// Sends the account range request to the p2p network with priority set and returns the
+    /// account range response received from a peer.
+    fn get_account_range_with_priority(
+        &self,
+        request: GetAccountRange,
+        priority: Priority,
+    ) -> Self::Output;
+}
@@ -76,6 +76,12 @@ pub enum ProviderError {
     StateTrie,
     #[error("History state root, can't be calculated")]
     HistoryStateRoot,
+    /// The accounts can only be iterated in the latest state.
+    #[error("History accounts can't be iterated")]
+    HistoryAccountRange,
+    /// The state history of the block was pruned.
+    #[error("State history for block #{block_number} has been pruned")]
+    StateHistoryPruned { block_number: BlockNumber },
     /// Thrown when required header related data was not found but was required.
     #[error("requested data not found")]
     HeaderNotFound,
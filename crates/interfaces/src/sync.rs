@@ -2,6 +2,8 @@
 
 use reth_primitives::BlockNumber;
 
+pub use reth_network_api::SyncEvent;
+
 /// A type that provides information about whether the node is currently syncing and the network is
 /// currently serving syncing related requests.
 #[auto_impl::auto_impl(&, Arc, Box)]
@@ -26,6 +28,11 @@ pub trait SyncStateProvider: Send + Sync {
 pub trait SyncStateUpdater: SyncStateProvider {
     /// Notifies about an [SyncState] update.
     fn update_sync_state(&self, state: SyncState);
+
+    /// Notifies about a [SyncEvent].
+    ///
+    /// By default, the event is discarded.
+    fn notify_sync_event(&self, _event: SyncEvent) {}
 }
 
 /// The state the network is currently in when it comes to synchronization.
@@ -84,6 +84,16 @@ impl BanList {
         (ips, peers)
     }
 
+    /// Returns all banned peers and until when they are banned, `None` if indefinitely.
+    pub fn banned_peers(&self) -> impl Iterator<Item = (PeerId, Option<Instant>)> + '_ {
+        self.banned_peers.iter().map(|(peer, until)| (*peer, *until))
+    }
+
+    /// Returns all banned ip addresses and until when they are banned, `None` if indefinitely.
+    pub fn banned_ips(&self) -> impl Iterator<Item = (IpAddr, Option<Instant>)> + '_ {
+        self.banned_ips.iter().map(|(ip, until)| (*ip, *until))
+    }
+
     /// Returns true if either the given peer id _or_ ip address is banned.
     #[inline]
     pub fn is_banned(&self, peer_id: &PeerId, ip: &IpAddr) -> bool {
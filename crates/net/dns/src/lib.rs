@@ -519,6 +519,11 @@ mod tests {
         // await recheck timeout
         tokio::time::sleep(config.recheck_interval).await;
 
+        // publish a new version of the tree
+        root.sequence_number += 1;
+        root.sign(&secret_key).unwrap();
+        resolver.insert(link.domain.clone(), root.to_string());
+
         let enr = EnrBuilder::new("v4").build(&secret_key).unwrap();
         resolver.insert(format!("{}.{}", root.enr_root.clone(), link.domain), enr.to_base64());
 
@@ -100,31 +100,36 @@ impl<K: EnrKeyUnambiguous> SyncTree<K> {
         Some(SyncAction::Enr(enr))
     }
 
-    /// Updates the root and returns what changed
+    /// Updates the root and resyncs the parts of the tree that changed.
+    ///
+    /// The tree is only considered updated if the sequence number of the new root is higher than
+    /// the current one.
     pub(crate) fn update_root(&mut self, root: TreeRootEntry) {
-        let enr = root.enr_root == self.root.enr_root;
-        let link = root.link_root == self.root.link_root;
+        self.root_updated = Instant::now();
+
+        if root.sequence_number <= self.root.sequence_number {
+            // unchanged
+            self.sync_state = SyncState::Active;
+            return
+        }
 
+        let enr_changed = root.enr_root != self.root.enr_root;
+        let link_changed = root.link_root != self.root.link_root;
         self.root = root;
-        self.root_updated = Instant::now();
 
-        let state = match (enr, link) {
-            (true, true) => {
-                self.unresolved_nodes.clear();
-                self.unresolved_links.clear();
-                SyncState::Pending
-            }
-            (true, _) => {
+        let state = match (enr_changed, link_changed) {
+            (true, false) => {
                 self.unresolved_nodes.clear();
                 SyncState::Enr
             }
-            (_, true) => {
+            (false, true) => {
                 self.unresolved_links.clear();
                 SyncState::Link
             }
             _ => {
-                // unchanged
-                return
+                self.unresolved_nodes.clear();
+                self.unresolved_links.clear();
+                SyncState::Pending
             }
         };
         self.sync_state = state;
@@ -160,3 +165,44 @@ impl ResolveKind {
         matches!(self, ResolveKind::Link)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secp256k1::rand::thread_rng;
+
+    fn sync_all(tree: &mut SyncTree) {
+        let now = Instant::now();
+        while tree.poll(now, Duration::from_secs(60)).is_some() {}
+    }
+
+    #[test]
+    fn test_update_root_sequence_number() {
+        let secret_key = SecretKey::new(&mut thread_rng());
+        let s = "enrtree-root:v1 e=QFT4PBCRX4XQCV3VUYJ6BTCEPU l=JGUFMSAGI7KZYB3P7IZW4S5Y3A seq=3 sig=3FmXuVwpa8Y7OstZTx9PIb1mt8FrW7VpDOFv4AaGCsZ2EIHmhraWhe4NxYhQDlw5MjeFXYMbJjsPeKlHzmJREQE";
+        let root: TreeRootEntry = s.parse().unwrap();
+        let link =
+            LinkEntry { domain: "nodes.example.org".to_string(), pubkey: secret_key.public() };
+
+        let mut tree = SyncTree::new(root.clone(), link);
+        sync_all(&mut tree);
+
+        // same sequence number, nothing to resync
+        let mut unchanged = root.clone();
+        unchanged.enr_root = "AAAAAAAAAAAAAAAAAAAAAAAAAA".to_string();
+        tree.update_root(unchanged);
+        assert!(tree.poll(Instant::now(), Duration::from_secs(60)).is_none());
+        assert_eq!(tree.root(), &root);
+
+        // only the enr subtree changed
+        let mut updated = root;
+        updated.sequence_number += 1;
+        updated.enr_root = "AAAAAAAAAAAAAAAAAAAAAAAAAA".to_string();
+        tree.update_root(updated.clone());
+        match tree.poll(Instant::now(), Duration::from_secs(60)) {
+            Some(SyncAction::Enr(hash)) => assert_eq!(hash, updated.enr_root),
+            _ => panic!("expected enr root resync"),
+        }
+        assert!(tree.poll(Instant::now(), Duration::from_secs(60)).is_none());
+    }
+}
@@ -0,0 +1,143 @@
+use super::request::BodiesRequestStats;
+use reth_primitives::SealedHeader;
+use std::{ops::RangeInclusive, time::Duration};
+
+/// The soft limit for the size of a `BlockBodies` response of the eth protocol.
+pub(crate) const SOFT_RESPONSE_SIZE_LIMIT: u64 = 2 * 1024 * 1024;
+
+/// Requests that are answered faster than this on average increase the batch size.
+const FAST_RESPONSE_TIME: Duration = Duration::from_secs(1);
+
+/// The weight of a new sample in the moving averages.
+const MOVING_AVERAGE_WEIGHT: f64 = 0.2;
+
+/// The estimated number of body bytes per unit of gas used, until bodies were received.
+const DEFAULT_BYTES_PER_GAS: f64 = 0.01;
+
+/// Adapts the number of non-empty blocks per body request to the observed network conditions.
+///
+/// The batch size grows while peers respond quickly and is halved whenever a request times out,
+/// always staying within the configured range. Since large blocks can exceed the response size
+/// limit of a peer, the size of a body is additionally estimated from the gas used by its block.
+#[derive(Debug)]
+pub(crate) struct AdaptiveBatchSize {
+    /// The smallest and largest allowed batch size.
+    range: RangeInclusive<u64>,
+    /// The current batch size.
+    current: u64,
+    /// The moving average of the response time of peers.
+    response_time: Option<Duration>,
+    /// The moving average of encoded body bytes per unit of gas used.
+    bytes_per_gas: f64,
+}
+
+impl AdaptiveBatchSize {
+    /// Creates a new instance that starts at the given batch size.
+    pub(crate) fn new(initial: u64, range: RangeInclusive<u64>) -> Self {
+        let current = initial.clamp(*range.start(), *range.end());
+        Self { range, current, response_time: None, bytes_per_gas: DEFAULT_BYTES_PER_GAS }
+    }
+
+    /// Returns the current maximum number of non-empty blocks per request.
+    pub(crate) fn current(&self) -> u64 {
+        self.current
+    }
+
+    /// Returns the estimated encoded size of the body of the given header.
+    pub(crate) fn estimated_body_size(&self, header: &SealedHeader) -> u64 {
+        (header.gas_used as f64 * self.bytes_per_gas) as u64
+    }
+
+    /// Adapts the batch size to the stats of a finished request.
+    pub(crate) fn on_request_finished(&mut self, stats: &BodiesRequestStats) {
+        if stats.gas_used > 0 {
+            let bytes_per_gas = stats.body_size as f64 / stats.gas_used as f64;
+            self.bytes_per_gas = moving_average(self.bytes_per_gas, bytes_per_gas);
+        }
+
+        if stats.responses > 0 {
+            let response_time = stats.response_time / stats.responses;
+            self.response_time = Some(match self.response_time {
+                Some(avg) => Duration::from_secs_f64(moving_average(
+                    avg.as_secs_f64(),
+                    response_time.as_secs_f64(),
+                )),
+                None => response_time,
+            });
+        }
+
+        if stats.timeouts > 0 {
+            self.current /= 2;
+        } else if self.response_time.map_or(false, |avg| avg < FAST_RESPONSE_TIME) {
+            self.current += self.current / 4 + 1;
+        }
+        self.current = self.current.clamp(*self.range.start(), *self.range.end());
+    }
+}
+
+fn moving_average(average: f64, sample: f64) -> f64 {
+    average * (1. - MOVING_AVERAGE_WEIGHT) + sample * MOVING_AVERAGE_WEIGHT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fast_response() -> BodiesRequestStats {
+        BodiesRequestStats {
+            responses: 1,
+            response_time: Duration::from_millis(100),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn adapts_to_response_times_and_timeouts() {
+        let mut batch_size = AdaptiveBatchSize::new(100, 10..=200);
+
+        batch_size.on_request_finished(&fast_response());
+        assert_eq!(batch_size.current(), 126);
+
+        for _ in 0..10 {
+            batch_size.on_request_finished(&fast_response());
+        }
+        assert_eq!(batch_size.current(), 200);
+
+        let timeout = BodiesRequestStats { timeouts: 1, ..Default::default() };
+        batch_size.on_request_finished(&timeout);
+        assert_eq!(batch_size.current(), 100);
+
+        for _ in 0..10 {
+            batch_size.on_request_finished(&timeout);
+        }
+        assert_eq!(batch_size.current(), 10);
+    }
+
+    #[test]
+    fn does_not_grow_on_slow_responses() {
+        let mut batch_size = AdaptiveBatchSize::new(100, 10..=200);
+        let slow = BodiesRequestStats {
+            responses: 2,
+            response_time: Duration::from_secs(10),
+            ..Default::default()
+        };
+        batch_size.on_request_finished(&slow);
+        assert_eq!(batch_size.current(), 100);
+    }
+
+    #[test]
+    fn estimates_body_size_from_gas() {
+        let mut batch_size = AdaptiveBatchSize::new(100, 10..=200);
+        let header =
+            reth_primitives::Header { gas_used: 1_000_000, ..Default::default() }.seal_slow();
+        assert_eq!(batch_size.estimated_body_size(&header), 10_000);
+
+        let stats =
+            BodiesRequestStats { body_size: 100_000, gas_used: 1_000_000, ..Default::default() };
+        for _ in 0..50 {
+            batch_size.on_request_finished(&stats);
+        }
+        // converges to the observed bytes per gas
+        assert!((99_000..=100_000).contains(&batch_size.estimated_body_size(&header)));
+    }
+}
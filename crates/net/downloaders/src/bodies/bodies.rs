@@ -1,4 +1,7 @@
-use super::queue::BodiesRequestQueue;
+use super::{
+    batch_size::{AdaptiveBatchSize, SOFT_RESPONSE_SIZE_LIMIT},
+    queue::BodiesRequestQueue,
+};
 use crate::{bodies::task::TaskDownloader, metrics::DownloaderMetrics};
 use futures::Stream;
 use futures_util::StreamExt;
@@ -51,8 +54,8 @@ pub struct BodiesDownloader<B: BodiesClient, DB> {
     // TODO: make this a [HeaderProvider]
     /// The database handle
     db: Arc<DB>,
-    /// The maximum number of non-empty blocks per one request
-    request_limit: u64,
+    /// The maximum number of non-empty blocks per one request, adapted to network conditions
+    batch_size: AdaptiveBatchSize,
     /// The maximum number of block bodies returned at once from the stream
     stream_batch_size: usize,
     /// The allowed range for number of concurrent requests.
@@ -85,7 +88,7 @@ where
             None => self.download_range.start,
         };
 
-        let limit = self.download_range.end.saturating_sub(start_at).min(self.request_limit);
+        let limit = self.download_range.end.saturating_sub(start_at).min(self.batch_size.current());
         self.query_headers(start_at..self.download_range.end, limit)
     }
 
@@ -94,9 +97,10 @@ where
     /// This method is going to return the batch as soon as one of the conditions below
     /// is fullfilled:
     ///     1. The number of non-empty headers in the batch equals requested.
-    ///     2. The total number of headers in the batch (both empty and non-empty)
-    ///        is greater than or equal to the stream batch size.
-    ///     3. Downloader reached the end of the range
+    ///     2. The total number of headers in the batch (both empty and non-empty) is greater than
+    ///        or equal to the stream batch size.
+    ///     3. The estimated size of the bodies reached the soft response size limit.
+    ///     4. Downloader reached the end of the range
     ///
     /// NOTE: The batches returned have a variable length.
     fn query_headers(
@@ -116,6 +120,9 @@ where
         // Non empty headers count
         let mut non_empty_headers = 0;
 
+        // Estimated size of the requested bodies
+        let mut estimated_size = 0;
+
         // Collection of results
         let mut headers = Vec::<SealedHeader>::default();
 
@@ -125,9 +132,11 @@ where
         //      1. Current block number is in range
         //      2. The number of non empty headers is less than maximum
         //      3. The total number of headers is less than the stream batch size
+        //      4. The estimated size of the bodies is less than the soft response size limit
         while range.contains(&current_block_num) &&
             non_empty_headers < max_non_empty &&
-            headers.len() < self.stream_batch_size
+            headers.len() < self.stream_batch_size &&
+            estimated_size < SOFT_RESPONSE_SIZE_LIMIT
         {
             // Find the block hash.
             let (number, hash) = canonical_cursor
@@ -138,13 +147,16 @@ where
                 .seek_exact(number)?
                 .ok_or(DownloadError::MissingHeader { block_number: number })?;
 
+            let header = header.seal(hash);
+
             // If the header is not empty, increment the counter
             if !header.is_empty() {
                 non_empty_headers += 1;
+                estimated_size += self.batch_size.estimated_body_size(&header);
             }
 
             // Add header to the result collection
-            headers.push(header.seal(hash));
+            headers.push(header);
 
             // Increment current block number
             current_block_num += 1;
@@ -322,8 +334,11 @@ where
         // Submit new requests and poll any in progress
         loop {
             // Poll requests
-            while let Poll::Ready(Some(response)) = this.in_progress_queue.poll_next_unpin(cx) {
+            while let Poll::Ready(Some((response, stats))) =
+                this.in_progress_queue.poll_next_unpin(cx)
+            {
                 this.metrics.in_flight_requests.decrement(1.);
+                this.batch_size.on_request_finished(&stats);
                 match response {
                     Ok(response) => {
                         let response = OrderedBodiesResponse(response);
@@ -440,8 +455,12 @@ impl Ord for OrderedBodiesResponse {
 
 /// Builder for [BodiesDownloader].
 pub struct BodiesDownloaderBuilder {
-    /// The batch size of non-empty blocks per one request
+    /// The initial batch size of non-empty blocks per one request
     request_limit: u64,
+    /// The minimum batch size of non-empty blocks per one request
+    min_batch_size: u64,
+    /// The maximum batch size of non-empty blocks per one request
+    max_batch_size: u64,
     /// The maximum number of block bodies returned at once from the stream
     stream_batch_size: usize,
     /// Maximum amount of received bodies to buffer internally.
@@ -454,6 +473,8 @@ impl Default for BodiesDownloaderBuilder {
     fn default() -> Self {
         Self {
             request_limit: 200,
+            min_batch_size: 10,
+            max_batch_size: 1000,
             stream_batch_size: 1000,
             max_buffered_responses: 30000,
             concurrent_requests_range: 5..=100,
@@ -462,12 +483,28 @@ impl Default for BodiesDownloaderBuilder {
 }
 
 impl BodiesDownloaderBuilder {
-    /// Set request batch size on the downloader.
+    /// Set the initial request batch size on the downloader.
+    ///
+    /// The batch size is adapted to the network conditions within the range of
+    /// [BodiesDownloaderBuilder::with_min_batch_size] and
+    /// [BodiesDownloaderBuilder::with_max_batch_size].
     pub fn with_request_limit(mut self, request_limit: u64) -> Self {
         self.request_limit = request_limit;
         self
     }
 
+    /// Set the minimum request batch size on the downloader.
+    pub fn with_min_batch_size(mut self, min_batch_size: u64) -> Self {
+        self.min_batch_size = min_batch_size;
+        self
+    }
+
+    /// Set the maximum request batch size on the downloader.
+    pub fn with_max_batch_size(mut self, max_batch_size: u64) -> Self {
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
     /// Set stream batch size on the downloader.
     pub fn with_stream_batch_size(mut self, stream_batch_size: usize) -> Self {
         self.stream_batch_size = stream_batch_size;
@@ -502,6 +539,8 @@ impl BodiesDownloaderBuilder {
     {
         let Self {
             request_limit,
+            min_batch_size,
+            max_batch_size,
             stream_batch_size,
             concurrent_requests_range,
             max_buffered_responses,
@@ -512,7 +551,10 @@ impl BodiesDownloaderBuilder {
             client,
             consensus,
             db,
-            request_limit,
+            batch_size: AdaptiveBatchSize::new(
+                request_limit,
+                min_batch_size..=max_batch_size.max(min_batch_size),
+            ),
             stream_batch_size,
             max_buffered_responses,
             concurrent_requests_range,
@@ -597,6 +639,8 @@ mod tests {
         let client = Arc::new(TestBodiesClient::default().with_bodies(bodies.clone()));
         let mut downloader = BodiesDownloaderBuilder::default()
             .with_request_limit(request_limit)
+            .with_min_batch_size(request_limit)
+            .with_max_batch_size(request_limit)
             .build(client.clone(), Arc::new(TestConsensus::default()), db);
         downloader.set_download_range(0..200).expect("failed to set download range");
 
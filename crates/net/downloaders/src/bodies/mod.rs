@@ -5,6 +5,7 @@ pub mod bodies;
 /// TODO:
 pub mod task;
 
+mod batch_size;
 mod queue;
 mod request;
 
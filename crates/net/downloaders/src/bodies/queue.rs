@@ -1,7 +1,7 @@
-use super::request::BodiesRequestFuture;
+use super::request::{BodiesRequestFuture, BodiesRequestStats};
 use crate::metrics::DownloaderMetrics;
-use futures::{stream::FuturesUnordered, Stream};
-use futures_util::StreamExt;
+use futures::{stream::FuturesUnordered, Future, Stream};
+use futures_util::{FutureExt, StreamExt};
 use reth_interfaces::{
     consensus::Consensus,
     p2p::{
@@ -14,7 +14,7 @@ use reth_primitives::{BlockNumber, SealedHeader};
 use std::{
     pin::Pin,
     sync::Arc,
-    task::{Context, Poll},
+    task::{ready, Context, Poll},
 };
 
 /// The wrapper around [FuturesUnordered] that keeps information
@@ -22,7 +22,7 @@ use std::{
 #[derive(Debug)]
 pub(crate) struct BodiesRequestQueue<B: BodiesClient> {
     /// Inner body request queue.
-    inner: FuturesUnordered<BodiesRequestFuture<B>>,
+    inner: FuturesUnordered<TrackedBodiesRequest<B>>,
     /// The downloader metrics.
     metrics: DownloaderMetrics,
     /// Last requested block number.
@@ -73,10 +73,10 @@ where
             .or(self.last_requested_block_number);
 
         // Create request and push into the queue.
-        self.inner.push(
+        self.inner.push(TrackedBodiesRequest(
             BodiesRequestFuture::new(client, consensus, priority, self.metrics.clone())
                 .with_headers(request),
-        )
+        ))
     }
 }
 
@@ -84,9 +84,25 @@ impl<B> Stream for BodiesRequestQueue<B>
 where
     B: BodiesClient + 'static,
 {
-    type Item = DownloadResult<Vec<BlockResponse>>;
+    type Item = (DownloadResult<Vec<BlockResponse>>, BodiesRequestStats);
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         self.get_mut().inner.poll_next_unpin(cx)
     }
 }
+
+/// A [BodiesRequestFuture] that also resolves to the statistics of its responses.
+struct TrackedBodiesRequest<B: BodiesClient>(BodiesRequestFuture<B>);
+
+impl<B> Future for TrackedBodiesRequest<B>
+where
+    B: BodiesClient + 'static,
+{
+    type Output = (DownloadResult<Vec<BlockResponse>>, BodiesRequestStats);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let result = ready!(this.0.poll_unpin(cx));
+        Poll::Ready((result, this.0.take_stats()))
+    }
+}
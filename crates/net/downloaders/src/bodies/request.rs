@@ -5,18 +5,35 @@ use reth_interfaces::{
     consensus::{Consensus as ConsensusTrait, Consensus},
     p2p::{
         bodies::{client::BodiesClient, response::BlockResponse},
-        error::{DownloadError, DownloadResult},
+        error::{DownloadError, DownloadResult, RequestError},
         priority::Priority,
     },
 };
 use reth_primitives::{PeerId, SealedBlock, SealedHeader, WithPeerId, H256};
+use reth_rlp::Encodable;
 use std::{
     collections::VecDeque,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
+    time::{Duration, Instant},
 };
 
+/// Statistics about the responses received by a [BodiesRequestFuture].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BodiesRequestStats {
+    /// The number of valid responses.
+    pub(crate) responses: u32,
+    /// The total time peers took to send the valid responses.
+    pub(crate) response_time: Duration,
+    /// The number of requests that timed out.
+    pub(crate) timeouts: u32,
+    /// The total encoded size of the valid bodies.
+    pub(crate) body_size: u64,
+    /// The total gas used by the blocks of the valid bodies.
+    pub(crate) gas_used: u64,
+}
+
 /// Body request implemented as a [Future].
 ///
 /// The future will poll the underlying request until fullfilled.
@@ -46,6 +63,9 @@ pub(crate) struct BodiesRequestFuture<B: BodiesClient> {
     buffer: Vec<BlockResponse>,
     fut: Option<B::Output>,
     last_request_len: Option<usize>,
+    /// When the pending request was submitted.
+    last_request_at: Option<Instant>,
+    stats: BodiesRequestStats,
 }
 
 impl<B> BodiesRequestFuture<B>
@@ -67,6 +87,8 @@ where
             headers: Default::default(),
             buffer: Default::default(),
             last_request_len: None,
+            last_request_at: None,
+            stats: Default::default(),
             fut: None,
         }
     }
@@ -82,8 +104,19 @@ where
         self
     }
 
+    /// Returns the statistics of the responses received so far and resets them.
+    pub(crate) fn take_stats(&mut self) -> BodiesRequestStats {
+        std::mem::take(&mut self.stats)
+    }
+
     fn on_error(&mut self, error: DownloadError, peer_id: Option<PeerId>) {
         self.metrics.increment_errors(&error);
+        if matches!(
+            error,
+            DownloadError::Timeout | DownloadError::RequestError(RequestError::Timeout)
+        ) {
+            self.stats.timeouts += 1;
+        }
         tracing::error!(target: "downloaders::bodies", ?peer_id, %error, "Error requesting bodies");
         if let Some(peer_id) = peer_id {
             self.client.report_bad_message(peer_id);
@@ -106,6 +139,7 @@ where
         tracing::trace!(target: "downloaders::bodies", request_len = req.len(), "Requesting bodies");
         let client = Arc::clone(&self.client);
         self.last_request_len = Some(req.len());
+        self.last_request_at = Some(Instant::now());
         self.fut = Some(client.get_block_bodies_with_priority(req, self.priority));
     }
 
@@ -115,6 +149,7 @@ where
         let (peer_id, bodies) = response.split();
         let request_len = self.last_request_len.unwrap_or_default();
         let response_len = bodies.len();
+        let response_time = self.last_request_at.map(|at| at.elapsed()).unwrap_or_default();
 
         tracing::trace!(target: "downloaders::bodies", request_len, response_len, ?peer_id, "Received bodies");
 
@@ -138,6 +173,8 @@ where
 
         // Buffer block responses
         self.try_buffer_blocks(bodies)?;
+        self.stats.responses += 1;
+        self.stats.response_time += response_time;
 
         // Submit next request if any
         if let Some(req) = self.next_request() {
@@ -167,6 +204,7 @@ where
                 self.buffer.push(BlockResponse::Empty(next_header));
             } else {
                 let next_body = bodies.next().unwrap();
+                let body_size = next_body.length() as u64;
                 let block = SealedBlock {
                     header: next_header,
                     body: next_body.transactions,
@@ -181,6 +219,8 @@ where
                     return Err(DownloadError::BodyValidation { hash, error })
                 }
 
+                self.stats.body_size += body_size;
+                self.stats.gas_used += block.header.gas_used;
                 self.buffer.push(BlockResponse::Full(block));
             }
         }
@@ -11,7 +11,7 @@ use reth_interfaces::{
         error::{DownloadError, DownloadResult, PeerRequestResult},
         headers::{
             client::{HeadersClient, HeadersRequest},
-            downloader::{validate_header_download, HeaderDownloader, SyncTarget},
+            downloader::{ensure_parent, validate_header_download, HeaderDownloader, SyncTarget},
         },
         priority::Priority,
     },
@@ -38,6 +38,18 @@ const REQUESTS_PER_PEER_MULTIPLIER: usize = 5;
 /// The scope for headers downloader metrics.
 pub const HEADERS_DOWNLOADER_SCOPE: &str = "downloaders.headers";
 
+/// A block that is trusted to be part of the canonical chain.
+///
+/// Once the downloader received the header of the checkpoint, the headers before it are only
+/// checked to be linked to the checkpoint, but not validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustedCheckpoint {
+    /// The number of the checkpoint block.
+    pub number: BlockNumber,
+    /// The hash of the checkpoint block.
+    pub hash: H256,
+}
+
 /// Downloads headers concurrently.
 ///
 /// This [HeaderDownloader] downloads headers using the configured [HeadersClient].
@@ -87,6 +99,10 @@ pub struct ReverseHeadersDownloader<H: HeadersClient> {
     ///
     /// Note: headers are sorted from high to low
     queued_validated_headers: Vec<SealedHeader>,
+    /// The trusted checkpoint, if any.
+    trusted_checkpoint: Option<TrustedCheckpoint>,
+    /// Whether the header of the trusted checkpoint was received in the current range.
+    checkpoint_reached: bool,
     /// Header downloader metrics.
     metrics: DownloaderMetrics,
 }
@@ -254,6 +270,12 @@ where
                 }
             }
 
+            if self.trusted_checkpoint.map_or(false, |checkpoint| checkpoint.hash == parent.hash())
+            {
+                trace!(target: "downloaders::headers", number = parent.number, "Reached trusted checkpoint");
+                self.checkpoint_reached = true;
+            }
+
             validated.push(parent);
         }
 
@@ -547,7 +569,20 @@ where
     }
 
     /// Validate whether the header is valid in relation to it's parent
+    ///
+    /// Headers before a reached [TrustedCheckpoint] are only checked to be linked to their child.
     fn validate(&self, header: &SealedHeader, parent: &SealedHeader) -> DownloadResult<()> {
+        if let Some(checkpoint) = self.trusted_checkpoint {
+            if parent.number == checkpoint.number && parent.hash() != checkpoint.hash {
+                return Err(DownloadError::InvalidCheckpoint {
+                    received: parent.hash(),
+                    expected: checkpoint.hash,
+                })
+            }
+            if self.checkpoint_reached && header.number <= checkpoint.number {
+                return ensure_parent(header, parent)
+            }
+        }
         validate_header_download(&self.consensus, header, parent)
     }
 
@@ -555,6 +590,7 @@ where
     fn clear(&mut self) {
         self.lowest_validated_header.take();
         self.queued_validated_headers.clear();
+        self.checkpoint_reached = false;
         self.buffered_responses.clear();
         self.in_progress_queue.clear();
 
@@ -993,6 +1029,8 @@ pub struct ReverseHeadersDownloaderBuilder {
     max_concurrent_requests: usize,
     /// How many responses to buffer
     max_buffered_responses: usize,
+    /// The trusted checkpoint
+    trusted_checkpoint: Option<TrustedCheckpoint>,
 }
 
 impl Default for ReverseHeadersDownloaderBuilder {
@@ -1003,6 +1041,7 @@ impl Default for ReverseHeadersDownloaderBuilder {
             max_concurrent_requests: 150,
             min_concurrent_requests: 5,
             max_buffered_responses: 750,
+            trusted_checkpoint: None,
         }
     }
 }
@@ -1055,6 +1094,15 @@ impl ReverseHeadersDownloaderBuilder {
         self
     }
 
+    /// Set the trusted checkpoint.
+    ///
+    /// Headers before the checkpoint are not validated once the checkpoint was downloaded, so the
+    /// checkpoint must be known to be canonical, e.g. a finalized block.
+    pub fn trusted_checkpoint(mut self, checkpoint: TrustedCheckpoint) -> Self {
+        self.trusted_checkpoint = Some(checkpoint);
+        self
+    }
+
     /// Build [ReverseHeadersDownloader] with provided consensus
     /// and header client implementations
     pub fn build<H>(
@@ -1071,6 +1119,7 @@ impl ReverseHeadersDownloaderBuilder {
             min_concurrent_requests,
             max_concurrent_requests,
             max_buffered_responses,
+            trusted_checkpoint,
         } = self;
         ReverseHeadersDownloader {
             consensus,
@@ -1091,6 +1140,8 @@ impl ReverseHeadersDownloaderBuilder {
             in_progress_queue: Default::default(),
             buffered_responses: Default::default(),
             queued_validated_headers: Default::default(),
+            trusted_checkpoint,
+            checkpoint_reached: false,
             metrics: DownloaderMetrics::new(HEADERS_DOWNLOADER_SCOPE),
         }
     }
@@ -1296,6 +1347,53 @@ mod tests {
         assert_eq!(heap.pop().unwrap().block_number(), lo);
     }
 
+    #[test]
+    fn skips_validation_before_trusted_checkpoint() {
+        let p3 = SealedHeader::default();
+        let p2 = child_header(&p3);
+        let p1 = child_header(&p2);
+        let p0 = child_header(&p1);
+
+        let consensus = Arc::new(TestConsensus::default());
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .trusted_checkpoint(TrustedCheckpoint { number: p2.number, hash: p2.hash() })
+            .build(Arc::new(TestHeadersClient::default()), consensus.clone());
+        downloader.update_local_head(p3.clone());
+        downloader.sync_target = Some(SyncTargetBlock::from_hash(p0.hash()));
+        downloader.lowest_validated_header = Some(p0);
+
+        let request = HeadersRequest {
+            start: p1.number.into(),
+            limit: 2,
+            direction: HeadersDirection::Falling,
+        };
+        let headers = vec![p1.as_ref().clone(), p2.as_ref().clone()];
+        downloader.process_next_headers(request, headers, Default::default()).unwrap();
+        assert!(downloader.checkpoint_reached);
+
+        // headers before the checkpoint are only checked to be linked to it
+        consensus.set_fail_validation(true);
+        assert_matches!(downloader.validate(&p2, &p3), Ok(()));
+        let mut forked = p3.as_ref().clone();
+        forked.gas_limit += 1;
+        assert_matches!(
+            downloader.validate(&p2, &forked.seal_slow()),
+            Err(DownloadError::MismatchedHeaders { .. })
+        );
+
+        // a different header at the number of the checkpoint is rejected
+        let mut other = p2.as_ref().clone();
+        other.gas_limit += 1;
+        assert_matches!(
+            downloader.validate(&p1, &other.seal_slow()),
+            Err(DownloadError::InvalidCheckpoint { .. })
+        );
+
+        // headers are validated again until the checkpoint is received in the new range
+        downloader.clear();
+        assert_matches!(downloader.validate(&p2, &p3), Err(DownloadError::HeaderValidation { .. }));
+    }
+
     #[tokio::test]
     async fn download_at_fork_head() {
         reth_tracing::init_test_tracing();
@@ -27,11 +27,14 @@ use std::{
 use thiserror::Error;
 use tokio::{
     fs::File,
-    io::{AsyncReadExt, BufReader},
+    io::{AsyncRead, AsyncReadExt, BufReader},
 };
 use tokio_stream::StreamExt;
 use tokio_util::codec::FramedRead;
-use tracing::{trace, warn};
+use tracing::{info, trace, warn};
+
+/// The number of blocks after which the progress of reading a file is reported.
+const PROGRESS_INTERVAL: usize = 10_000;
 
 /// Front-end API for fetching chain data from a file.
 ///
@@ -79,21 +82,23 @@ impl FileClient {
     }
 
     /// Initialize the [`FileClient`](FileClient) with a file directly.
-    pub(crate) async fn from_file(mut file: File) -> Result<Self, FileClientError> {
-        // get file len from metadata before reading
-        let metadata = file.metadata().await?;
-        let file_len = metadata.len();
+    pub(crate) async fn from_file(file: File) -> Result<Self, FileClientError> {
+        FileClient::from_reader(file).await
+    }
 
-        // read the entire file into memory
-        let mut reader = vec![];
-        file.read_to_end(&mut reader).await.unwrap();
+    /// Initialize the [`FileClient`](FileClient) with the blocks read from the given reader, e.g.
+    /// stdin.
+    pub async fn from_reader<R: AsyncRead + Unpin>(mut reader: R) -> Result<Self, FileClientError> {
+        // read the entire input into memory
+        let mut buf = vec![];
+        reader.read_to_end(&mut buf).await?;
 
         let mut headers = HashMap::new();
         let mut hash_to_number = HashMap::new();
         let mut bodies = HashMap::new();
 
-        // use with_capacity to make sure the internal buffer contains the entire file
-        let mut stream = FramedRead::with_capacity(&reader[..], BlockFileCodec, file_len as usize);
+        // use with_capacity to make sure the internal buffer contains the entire input
+        let mut stream = FramedRead::with_capacity(&buf[..], BlockFileCodec, buf.len());
 
         while let Some(block_res) = stream.next().await {
             let block = block_res?;
@@ -110,6 +115,10 @@ impl FileClient {
                     withdrawals: block.withdrawals,
                 },
             );
+
+            if headers.len() % PROGRESS_INTERVAL == 0 {
+                info!(target: "downloaders::file", blocks = headers.len(), "Read blocks");
+            }
         }
 
         trace!(blocks = headers.len(), "Initialized file client");
@@ -119,7 +128,12 @@ impl FileClient {
 
     /// Get the tip hash of the chain.
     pub fn tip(&self) -> Option<H256> {
-        self.headers.get(&(self.headers.len() as u64)).map(|h| h.hash_slow())
+        self.headers.get(&self.max_block()?).map(|h| h.hash_slow())
+    }
+
+    /// Returns the number of the highest block in the file.
+    pub fn max_block(&self) -> Option<BlockNumber> {
+        self.headers.keys().max().copied()
     }
 
     /// Use the provided bodies as the file client's block body buffer.
@@ -375,4 +389,15 @@ mod tests {
             Some(Ok(res)) => assert_eq!(res, zip_blocks(headers.iter(), &mut bodies))
         );
     }
+
+    #[tokio::test]
+    async fn test_tip_from_reader() {
+        let (file, headers, _) = generate_bodies_file(0..20).await;
+
+        let client = FileClient::from_reader(file).await.unwrap();
+
+        let tip = headers.last().unwrap();
+        assert_eq!(client.max_block(), Some(tip.number));
+        assert_eq!(client.tip(), Some(tip.hash()));
+    }
 }
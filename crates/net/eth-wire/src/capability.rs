@@ -1,6 +1,10 @@
 //! All capability related types
 
-use crate::{version::ParseVersionError, EthMessage, EthVersion};
+use crate::{
+    snap::{SnapMessage, SnapMessageId},
+    version::ParseVersionError,
+    EthMessage, EthVersion,
+};
 use reth_codecs::add_arbitrary_tests;
 use reth_primitives::bytes::{BufMut, Bytes};
 use reth_rlp::{Decodable, DecodeError, Encodable, RlpDecodable, RlpEncodable};
@@ -32,6 +36,8 @@ pub struct RawCapabilityMessage {
 pub enum CapabilityMessage {
     /// Eth sub-protocol message.
     Eth(EthMessage),
+    /// Snap sub-protocol message.
+    Snap(SnapMessage),
     /// Any other capability message.
     Other(RawCapabilityMessage),
 }
@@ -70,6 +76,12 @@ impl Capability {
     pub fn is_eth_v68(&self) -> bool {
         self.name == "eth" && self.version == 68
     }
+
+    /// Whether this is snap v1.
+    #[inline]
+    pub fn is_snap_v1(&self) -> bool {
+        self.name == "snap" && self.version == 1
+    }
 }
 
 #[cfg(any(test, feature = "arbitrary"))]
@@ -104,6 +116,7 @@ pub struct Capabilities {
     eth_66: bool,
     eth_67: bool,
     eth_68: bool,
+    snap_1: bool,
 }
 
 impl Capabilities {
@@ -142,6 +155,12 @@ impl Capabilities {
     pub fn supports_eth_v68(&self) -> bool {
         self.eth_68
     }
+
+    /// Whether this peer supports snap v1 protocol.
+    #[inline]
+    pub fn supports_snap(&self) -> bool {
+        self.snap_1
+    }
 }
 
 impl From<Vec<Capability>> for Capabilities {
@@ -150,6 +169,7 @@ impl From<Vec<Capability>> for Capabilities {
             eth_66: value.iter().any(Capability::is_eth_v66),
             eth_67: value.iter().any(Capability::is_eth_v67),
             eth_68: value.iter().any(Capability::is_eth_v68),
+            snap_1: value.iter().any(Capability::is_snap_v1),
             inner: value,
         }
     }
@@ -169,6 +189,7 @@ impl Decodable for Capabilities {
             eth_66: inner.iter().any(Capability::is_eth_v66),
             eth_67: inner.iter().any(Capability::is_eth_v67),
             eth_68: inner.iter().any(Capability::is_eth_v68),
+            snap_1: inner.iter().any(Capability::is_snap_v1),
             inner,
         })
     }
@@ -181,6 +202,9 @@ pub enum SharedCapability {
     /// The `eth` capability.
     Eth { version: EthVersion, offset: u8 },
 
+    /// The `snap` capability.
+    Snap { version: u8, offset: u8 },
+
     /// An unknown capability.
     UnknownCapability { name: SmolStr, version: u8, offset: u8 },
 }
@@ -190,6 +214,7 @@ impl SharedCapability {
     pub(crate) fn new(name: &str, version: u8, offset: u8) -> Result<Self, SharedCapabilityError> {
         match name {
             "eth" => Ok(Self::Eth { version: EthVersion::try_from(version)?, offset }),
+            "snap" if version == 1 => Ok(Self::Snap { version, offset }),
             _ => Ok(Self::UnknownCapability { name: name.into(), version, offset }),
         }
     }
@@ -198,6 +223,7 @@ impl SharedCapability {
     pub fn name(&self) -> &str {
         match self {
             SharedCapability::Eth { .. } => "eth",
+            SharedCapability::Snap { .. } => "snap",
             SharedCapability::UnknownCapability { name, .. } => name,
         }
     }
@@ -206,6 +232,7 @@ impl SharedCapability {
     pub fn version(&self) -> u8 {
         match self {
             SharedCapability::Eth { version, .. } => *version as u8,
+            SharedCapability::Snap { version, .. } => *version,
             SharedCapability::UnknownCapability { version, .. } => *version,
         }
     }
@@ -214,6 +241,7 @@ impl SharedCapability {
     pub fn offset(&self) -> u8 {
         match self {
             SharedCapability::Eth { offset, .. } => *offset,
+            SharedCapability::Snap { offset, .. } => *offset,
             SharedCapability::UnknownCapability { offset, .. } => *offset,
         }
     }
@@ -222,6 +250,7 @@ impl SharedCapability {
     pub fn num_messages(&self) -> Result<u8, SharedCapabilityError> {
         match self {
             SharedCapability::Eth { version, .. } => Ok(version.total_messages()),
+            SharedCapability::Snap { .. } => Ok(SnapMessageId::total_messages()),
             _ => Err(SharedCapabilityError::UnknownCapability),
         }
     }
@@ -282,5 +311,19 @@ mod tests {
         assert!(capabilities.supports_eth_v66());
         assert!(capabilities.supports_eth_v67());
         assert!(capabilities.supports_eth_v68());
+        assert!(!capabilities.supports_snap());
+    }
+
+    #[test]
+    fn from_snap_1() {
+        let capability = SharedCapability::new("snap", 1, 0x21).unwrap();
+
+        assert_eq!(capability.name(), "snap");
+        assert_eq!(capability.version(), 1);
+        assert_eq!(capability.num_messages().unwrap(), 8);
+        assert_eq!(capability, SharedCapability::Snap { version: 1, offset: 0x21 });
+
+        let capabilities: Capabilities = vec![Capability::new("snap".into(), 1)].into();
+        assert!(capabilities.supports_snap());
     }
 }
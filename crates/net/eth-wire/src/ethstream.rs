@@ -1,6 +1,8 @@
 use crate::{
+    capability::CapabilityMessage,
     errors::{EthHandshakeError, EthStreamError},
     message::{EthBroadcastMessage, ProtocolBroadcastMessage},
+    snap::SnapMessage,
     types::{EthMessage, ProtocolMessage, Status},
     CanDisconnect, DisconnectReason, EthVersion,
 };
@@ -10,7 +12,7 @@ use reth_primitives::{
     bytes::{Bytes, BytesMut},
     ForkFilter,
 };
-use reth_rlp::Encodable;
+use reth_rlp::{Decodable, Encodable};
 use std::{
     pin::Pin,
     task::{Context, Poll},
@@ -166,6 +168,8 @@ where
 #[derive(Debug)]
 pub struct EthStream<S> {
     version: EthVersion,
+    /// The id of the first `snap` message relative to the `eth` messages, if `snap` is shared.
+    snap_offset: Option<u8>,
     #[pin]
     inner: S,
 }
@@ -174,7 +178,13 @@ impl<S> EthStream<S> {
     /// Creates a new unauthed [`EthStream`] from a provided stream. You will need
     /// to manually handshake a peer.
     pub fn new(version: EthVersion, inner: S) -> Self {
-        Self { version, inner }
+        Self { version, snap_offset: None, inner }
+    }
+
+    /// Enables `snap` messages that follow the `eth` messages at the given relative message id.
+    pub fn with_snap_offset(mut self, snap_offset: u8) -> Self {
+        self.snap_offset = Some(snap_offset);
+        self
     }
 
     /// Returns the eth version.
@@ -182,6 +192,11 @@ impl<S> EthStream<S> {
         self.version
     }
 
+    /// Returns `true` if `snap` messages can be exchanged over this stream.
+    pub fn is_snap_shared(&self) -> bool {
+        self.snap_offset.is_some()
+    }
+
     /// Returns the underlying stream.
     pub fn inner(&self) -> &S {
         &self.inner
@@ -216,6 +231,77 @@ where
 
         Ok(())
     }
+
+    /// Same as [`Sink::start_send`] but accepts a [`SnapMessage`] instead.
+    ///
+    /// Returns an error if `snap` is not shared with the peer.
+    pub fn start_send_snap(&mut self, item: SnapMessage) -> Result<(), EthStreamError> {
+        let snap_offset = self.snap_offset.ok_or(EthStreamError::SnapNotShared)?;
+
+        let mut bytes = BytesMut::new();
+        item.encode(&mut bytes);
+        bytes[0] += snap_offset;
+
+        self.inner.start_send_unpin(bytes.freeze())?;
+
+        Ok(())
+    }
+}
+
+impl<S, E> EthStream<S>
+where
+    S: Stream<Item = Result<BytesMut, E>> + Unpin,
+    EthStreamError: From<E>,
+{
+    /// Polls the next message of the `eth` capability, or of the `snap` capability if it is
+    /// shared, see [`EthStream::with_snap_offset`].
+    pub fn poll_next_capability_message(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<CapabilityMessage, EthStreamError>>> {
+        let mut bytes = match ready!(self.inner.poll_next_unpin(cx)) {
+            Some(Ok(bytes)) => bytes,
+            Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+            None => return Poll::Ready(None),
+        };
+
+        match self.snap_offset {
+            Some(snap_offset) if bytes.first().map_or(false, |id| *id >= snap_offset) => {
+                if bytes.len() > MAX_MESSAGE_SIZE {
+                    return Poll::Ready(Some(Err(EthStreamError::MessageTooBig(bytes.len()))))
+                }
+                bytes[0] -= snap_offset;
+                let msg = SnapMessage::decode(&mut bytes.as_ref())
+                    .map(CapabilityMessage::Snap)
+                    .map_err(EthStreamError::from);
+                Poll::Ready(Some(msg))
+            }
+            _ => Poll::Ready(Some(
+                decode_eth_message(self.version, bytes).map(CapabilityMessage::Eth),
+            )),
+        }
+    }
+}
+
+/// Decodes an `eth` message that was received after the handshake.
+fn decode_eth_message(version: EthVersion, bytes: BytesMut) -> Result<EthMessage, EthStreamError> {
+    if bytes.len() > MAX_MESSAGE_SIZE {
+        return Err(EthStreamError::MessageTooBig(bytes.len()))
+    }
+
+    let msg = match ProtocolMessage::decode_message(version, &mut bytes.as_ref()) {
+        Ok(m) => m,
+        Err(err) => {
+            tracing::debug!("decode error: msg={bytes:x}");
+            return Err(err)
+        }
+    };
+
+    if matches!(msg.message, EthMessage::Status(_)) {
+        return Err(EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake))
+    }
+
+    Ok(msg.message)
 }
 
 impl<S, E> Stream for EthStream<S>
@@ -234,25 +320,7 @@ where
             None => return Poll::Ready(None),
         };
 
-        if bytes.len() > MAX_MESSAGE_SIZE {
-            return Poll::Ready(Some(Err(EthStreamError::MessageTooBig(bytes.len()))))
-        }
-
-        let msg = match ProtocolMessage::decode_message(*this.version, &mut bytes.as_ref()) {
-            Ok(m) => m,
-            Err(err) => {
-                tracing::debug!("decode error: msg={bytes:x}");
-                return Poll::Ready(Some(Err(err)))
-            }
-        };
-
-        if matches!(msg.message, EthMessage::Status(_)) {
-            return Poll::Ready(Some(Err(EthStreamError::EthHandshakeError(
-                EthHandshakeError::StatusNotInHandshake,
-            ))))
-        }
-
-        Poll::Ready(Some(Ok(msg.message)))
+        Poll::Ready(Some(decode_eth_message(*this.version, bytes)))
     }
 }
 
@@ -314,10 +382,11 @@ where
 mod tests {
     use super::UnauthedEthStream;
     use crate::{
-        capability::Capability,
+        capability::{Capability, CapabilityMessage},
         errors::{EthHandshakeError, EthStreamError},
         hello::HelloMessage,
         p2pstream::{ProtocolVersion, UnauthedP2PStream},
+        snap::{GetAccountRange, SnapMessage},
         types::{broadcast::BlockHashNumber, EthMessage, EthVersion, Status},
         EthStream, PassthroughCodec,
     };
@@ -326,6 +395,7 @@ mod tests {
     use reth_ecies::{stream::ECIESStream, util::pk2id};
     use reth_primitives::{ForkFilter, Head, H256, U256};
     use secp256k1::{SecretKey, SECP256K1};
+    use std::future::poll_fn;
     use tokio::net::{TcpListener, TcpStream};
     use tokio_util::codec::Decoder;
 
@@ -511,6 +581,54 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn can_write_and_read_snap_messages() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let snap_msg = SnapMessage::GetAccountRange(GetAccountRange {
+            request_id: 1,
+            root_hash: H256::random(),
+            limit_hash: H256::repeat_byte(0xff),
+            response_bytes: 1024,
+            ..Default::default()
+        });
+        let eth_msg = EthMessage::NewBlockHashes(
+            vec![BlockHashNumber { hash: H256::random(), number: 5 }].into(),
+        );
+
+        let snap_offset = EthVersion::Eth67.total_messages();
+        let snap_msg_clone = snap_msg.clone();
+        let eth_msg_clone = eth_msg.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let mut stream =
+                EthStream::new(EthVersion::Eth67, stream).with_snap_offset(snap_offset);
+
+            let message =
+                poll_fn(|cx| stream.poll_next_capability_message(cx)).await.unwrap().unwrap();
+            assert!(matches!(message, CapabilityMessage::Snap(msg) if msg == snap_msg_clone));
+            let message =
+                poll_fn(|cx| stream.poll_next_capability_message(cx)).await.unwrap().unwrap();
+            assert!(matches!(message, CapabilityMessage::Eth(msg) if msg == eth_msg_clone));
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+        let mut client_stream = EthStream::new(EthVersion::Eth67, sink);
+        assert!(matches!(
+            client_stream.start_send_snap(snap_msg.clone()),
+            Err(EthStreamError::SnapNotShared)
+        ));
+
+        let mut client_stream = client_stream.with_snap_offset(snap_offset);
+        client_stream.start_send_snap(snap_msg).unwrap();
+        client_stream.send(eth_msg).await.unwrap();
+
+        // make sure the server receives the messages and asserts before ending the test
+        handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn can_write_and_read_ecies() {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
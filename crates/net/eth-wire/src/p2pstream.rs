@@ -141,20 +141,21 @@ where
             })
         }
 
-        // determine shared capabilities (currently returns only one capability)
+        // determine shared capabilities and their offsets
         let capability_res =
-            set_capability_offsets(hello.capabilities, their_hello.capabilities.clone());
+            shared_capability_offsets(hello.capabilities, their_hello.capabilities.clone());
 
-        let shared_capability = match capability_res {
+        let shared_capabilities = match capability_res {
             Err(err) => {
                 // we don't share any capabilities, send a disconnect message
                 self.send_disconnect(DisconnectReason::UselessPeer).await?;
                 Err(err)
             }
-            Ok(cap) => Ok(cap),
+            Ok(caps) => Ok(caps),
         }?;
 
-        let stream = P2PStream::new(self.inner, shared_capability);
+        let mut stream = P2PStream::new(self.inner, shared_capabilities[0].clone());
+        stream.shared_capabilities = shared_capabilities;
 
         Ok((stream, their_hello))
     }
@@ -191,6 +192,9 @@ where
 
 /// A P2PStream wraps over any `Stream` that yields bytes and makes it compatible with `p2p`
 /// protocol messages.
+///
+/// All messages sent after the handshake, including large `BlockBodies` responses, are snappy
+/// compressed as required by `p2p` protocol version 5, so no further compression is negotiated.
 #[pin_project]
 #[derive(Debug)]
 pub struct P2PStream<S> {
@@ -207,8 +211,14 @@ pub struct P2PStream<S> {
     pinger: Pinger,
 
     /// The supported capability for this stream.
+    ///
+    /// This is the capability with the lowest offset. The message ids of all shared capabilities
+    /// are relative to its offset.
     shared_capability: SharedCapability,
 
+    /// All shared capabilities of this stream, ordered by their offsets.
+    shared_capabilities: Vec<SharedCapability>,
+
     /// Outgoing messages buffered for sending to the underlying stream.
     outgoing_messages: VecDeque<Bytes>,
 
@@ -227,6 +237,7 @@ impl<S> P2PStream<S> {
             encoder: snap::raw::Encoder::new(),
             decoder: snap::raw::Decoder::new(),
             pinger: Pinger::new(PING_INTERVAL, PING_TIMEOUT),
+            shared_capabilities: vec![capability.clone()],
             shared_capability: capability,
             outgoing_messages: VecDeque::new(),
             disconnecting: false,
@@ -238,11 +249,24 @@ impl<S> P2PStream<S> {
         &self.shared_capability
     }
 
+    /// Returns all shared capabilities of this stream, ordered by their offsets.
+    pub fn shared_capabilities(&self) -> &[SharedCapability] {
+        &self.shared_capabilities
+    }
+
     /// Returns `true` if the connection is about to disconnect.
     pub fn is_disconnecting(&self) -> bool {
         self.disconnecting
     }
 
+    /// Consumes the stream and returns the underlying stream.
+    ///
+    /// Outgoing messages that are still buffered are discarded, and `Ping`s of the peer are no
+    /// longer answered.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
     /// Queues in a _snappy_ encoded [`P2PMessage::Pong`] message.
     fn send_pong(&mut self) {
         let pong = P2PMessage::Pong;
@@ -533,16 +557,30 @@ where
     }
 }
 
+/// Determines the offsets for each shared capability between the input list of peer
+/// capabilities and the input list of locally supported capabilities, and returns the capability
+/// with the lowest offset.
+///
+/// See [shared_capability_offsets].
+pub fn set_capability_offsets(
+    local_capabilities: Vec<Capability>,
+    peer_capabilities: Vec<Capability>,
+) -> Result<SharedCapability, P2PStreamError> {
+    Ok(shared_capability_offsets(local_capabilities, peer_capabilities)?.swap_remove(0))
+}
+
 /// Determines the offsets for each shared capability between the input list of peer
 /// capabilities and the input list of locally supported capabilities.
 ///
-/// Currently only `eth` versions 66 and 67 are supported.
+/// Currently only `eth` versions 66, 67 and 68, and `snap` version 1 are supported.
 /// Additionally, the `p2p` capability version 5 is supported, but is
 /// expected _not_ to be in neither `local_capabilities` or `peer_capabilities`.
-pub fn set_capability_offsets(
+///
+/// The returned capabilities are ordered by their offsets and not empty.
+pub fn shared_capability_offsets(
     local_capabilities: Vec<Capability>,
     peer_capabilities: Vec<Capability>,
-) -> Result<SharedCapability, P2PStreamError> {
+) -> Result<Vec<SharedCapability>, P2PStreamError> {
     // find intersection of capabilities
     let our_capabilities = local_capabilities.into_iter().collect::<HashSet<_>>();
 
@@ -603,7 +641,7 @@ pub fn set_capability_offsets(
                 // Capabilities which are not shared are ignored
                 tracing::debug!("unknown capability: name={:?}, version={}", name, version,);
             }
-            SharedCapability::Eth { .. } => {
+            SharedCapability::Eth { .. } | SharedCapability::Snap { .. } => {
                 // increment the offset if the capability is known
                 offset += shared_capability.num_messages()?;
 
@@ -612,17 +650,12 @@ pub fn set_capability_offsets(
         }
     }
 
-    // TODO: support multiple capabilities - we would need a new Stream type to go on top of
-    // `P2PStream` containing its capability. `P2PStream` would still send pings and handle
-    // pongs, but instead contain a map of capabilities to their respective stream / channel.
-    // Each channel would be responsible for containing the offset for that stream and would
-    // only increment / decrement message IDs.
-    // NOTE: since the `P2PStream` currently only supports one capability, we set the
-    // capability with the lowest offset.
-    Ok(shared_with_offsets
-        .first()
-        .ok_or(P2PStreamError::HandshakeError(P2PHandshakeError::NoSharedCapabilities))?
-        .clone())
+    // NOTE: the `P2PStream` only offsets message IDs by the capability with the lowest offset,
+    // the message IDs of the following capabilities are relative to it.
+    if shared_with_offsets.is_empty() {
+        return Err(P2PStreamError::HandshakeError(P2PHandshakeError::NoSharedCapabilities))
+    }
+    Ok(shared_with_offsets)
 }
 
 /// This represents only the reserved `p2p` subprotocol messages.
@@ -984,6 +1017,47 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_shared_eth68_capability_version() {
+        let local_capabilities: Vec<Capability> =
+            vec![EthVersion::Eth66.into(), EthVersion::Eth67.into(), EthVersion::Eth68.into()];
+        let peer_capabilities: Vec<Capability> =
+            vec![EthVersion::Eth66.into(), EthVersion::Eth68.into()];
+
+        let shared_capability =
+            set_capability_offsets(local_capabilities, peer_capabilities).unwrap();
+
+        assert_eq!(
+            shared_capability,
+            SharedCapability::Eth {
+                version: EthVersion::Eth68,
+                offset: MAX_RESERVED_MESSAGE_ID + 1
+            }
+        )
+    }
+
+    #[test]
+    fn test_shared_snap_capability_offset() {
+        let snap = Capability::new("snap".into(), 1);
+        let local_capabilities: Vec<Capability> = vec![EthVersion::Eth68.into(), snap.clone()];
+        let peer_capabilities: Vec<Capability> = vec![snap, EthVersion::Eth68.into()];
+
+        let shared_capabilities =
+            shared_capability_offsets(local_capabilities, peer_capabilities).unwrap();
+
+        // snap follows the 17 message ids of eth
+        assert_eq!(
+            shared_capabilities,
+            vec![
+                SharedCapability::Eth {
+                    version: EthVersion::Eth68,
+                    offset: MAX_RESERVED_MESSAGE_ID + 1
+                },
+                SharedCapability::Snap { version: 1, offset: MAX_RESERVED_MESSAGE_ID + 1 + 17 },
+            ]
+        )
+    }
+
     #[test]
     fn test_peer_capability_version_too_low() {
         let local_capabilities: Vec<Capability> = vec![EthVersion::Eth67.into()];
@@ -1010,6 +1084,38 @@ mod tests {
         ))
     }
 
+    // block bodies are compressed like every other subprotocol message, as mandated by p2p v5
+    #[tokio::test]
+    async fn test_compress_block_bodies() {
+        use crate::{
+            types::{BlockBodies, BlockBody, EthMessage, ProtocolMessage, RequestPair},
+            PassthroughCodec,
+        };
+        use reth_primitives::TransactionSigned;
+
+        let raw = hex_literal::hex!("f88b8212b085028fa6ae00830f424094aad593da0c8116ef7d2d594dd6a63241bccfc26c80a48318b64b000000000000000000000000641c5d790f862a58ec7abcfd644c0442e9c201b32aa0a6ef9e170bca5ffb7ac05433b13b7043de667fbb0b4a5e45d3b54fb2d6efcc63a0037ec2c05c3d60c5f5f78244ce0a3859e3a18a36c61efb061b383507d3ce19d2");
+        let tx = TransactionSigned::decode(&mut raw.as_ref()).unwrap();
+        let body = BlockBody { transactions: vec![tx; 200], ..Default::default() };
+        let message = EthMessage::BlockBodies(RequestPair {
+            request_id: 1,
+            message: BlockBodies(vec![body; 4]),
+        });
+        let mut item = BytesMut::new();
+        ProtocolMessage::from(message).encode(&mut item);
+        let item = item.freeze();
+
+        let (io, _) = tokio::io::duplex(64);
+        let capability = SharedCapability::new("eth", 67, MAX_RESERVED_MESSAGE_ID + 1).unwrap();
+        let mut p2p_stream = P2PStream::new(PassthroughCodec::default().framed(io), capability);
+        Pin::new(&mut p2p_stream).start_send(item.clone()).unwrap();
+
+        let sent = p2p_stream.outgoing_messages.pop_front().unwrap();
+        assert!(sent.len() * 10 < item.len());
+        assert_eq!(sent[0], item[0] + MAX_RESERVED_MESSAGE_ID + 1);
+        let decompressed = snap::raw::Decoder::new().decompress_vec(&sent[1..]).unwrap();
+        assert_eq!(decompressed, &item[1..]);
+    }
+
     #[test]
     fn snappy_decode_encode_ping() {
         let snappy_ping = b"\x02\x01\0\xc0";
@@ -2,7 +2,7 @@
 use crate::{EthMessage, EthVersion};
 use bytes::Bytes;
 use reth_codecs::derive_arbitrary;
-use reth_primitives::{Block, TransactionSigned, H256, U128};
+use reth_primitives::{Block, TransactionSigned, TxHash, H256, U128};
 use reth_rlp::{
     Decodable, Encodable, RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper,
 };
@@ -258,6 +258,32 @@ pub struct NewPooledTransactionHashes68 {
     pub hashes: Vec<H256>,
 }
 
+impl NewPooledTransactionHashes68 {
+    /// Returns an iterator over the announced `(type, size, hash)` triples.
+    pub fn iter_announced(&self) -> impl Iterator<Item = AnnouncedTransaction> + '_ {
+        self.types.iter().zip(self.sizes.iter()).zip(self.hashes.iter()).map(
+            |((tx_type, size), hash)| AnnouncedTransaction {
+                tx_type: *tx_type,
+                // saturate sizes that don't fit, so they are still considered oversized
+                size: u32::try_from(*size).unwrap_or(u32::MAX),
+                hash: *hash,
+            },
+        )
+    }
+}
+
+/// A single transaction announced via an eth/68
+/// [`NewPooledTransactionHashes68`] message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnnouncedTransaction {
+    /// The [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718) type of the transaction.
+    pub tx_type: u8,
+    /// The encoded size of the transaction in bytes.
+    pub size: u32,
+    /// The hash of the transaction.
+    pub hash: TxHash,
+}
+
 impl Encodable for NewPooledTransactionHashes68 {
     fn encode(&self, out: &mut dyn bytes::BufMut) {
         #[derive(RlpEncodable)]
@@ -0,0 +1,536 @@
+//! Implements the message types of the `snap/1` protocol.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+use reth_codecs::derive_arbitrary;
+use reth_primitives::{
+    bytes::{Buf, BufMut},
+    proofs::EMPTY_ROOT,
+    Bytes, H256, KECCAK_EMPTY, U256,
+};
+use reth_rlp::{Decodable, DecodeError, Encodable, Header, RlpDecodable, RlpEncodable};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The name of the `snap` capability.
+pub const SNAP_PROTOCOL_NAME: &str = "snap";
+
+/// The version of the supported `snap` capability.
+pub const SNAP_PROTOCOL_VERSION: u8 = 1;
+
+/// Represents message IDs for `snap` protocol messages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(missing_docs)]
+pub enum SnapMessageId {
+    GetAccountRange = 0x00,
+    AccountRange = 0x01,
+    GetStorageRanges = 0x02,
+    StorageRanges = 0x03,
+    GetByteCodes = 0x04,
+    ByteCodes = 0x05,
+    GetTrieNodes = 0x06,
+    TrieNodes = 0x07,
+}
+
+impl SnapMessageId {
+    /// The number of message IDs reserved by the `snap/1` protocol.
+    pub const fn total_messages() -> u8 {
+        8
+    }
+}
+
+impl Encodable for SnapMessageId {
+    fn encode(&self, out: &mut dyn BufMut) {
+        out.put_u8(*self as u8);
+    }
+    fn length(&self) -> usize {
+        1
+    }
+}
+
+impl Decodable for SnapMessageId {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let id = buf.first().ok_or(DecodeError::InputTooShort)?;
+        let id = match id {
+            0x00 => SnapMessageId::GetAccountRange,
+            0x01 => SnapMessageId::AccountRange,
+            0x02 => SnapMessageId::GetStorageRanges,
+            0x03 => SnapMessageId::StorageRanges,
+            0x04 => SnapMessageId::GetByteCodes,
+            0x05 => SnapMessageId::ByteCodes,
+            0x06 => SnapMessageId::GetTrieNodes,
+            0x07 => SnapMessageId::TrieNodes,
+            _ => return Err(DecodeError::Custom("Invalid message ID")),
+        };
+        buf.advance(1);
+        Ok(id)
+    }
+}
+
+/// A `snap` protocol message, containing a message ID and payload.
+///
+/// Unlike `eth` requests, `snap` messages carry their request id as the first field of the
+/// message itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(missing_docs)]
+pub enum SnapMessage {
+    GetAccountRange(GetAccountRange),
+    AccountRange(AccountRange),
+    GetStorageRanges(GetStorageRanges),
+    StorageRanges(StorageRanges),
+    GetByteCodes(GetByteCodes),
+    ByteCodes(ByteCodes),
+    GetTrieNodes(GetTrieNodes),
+    TrieNodes(TrieNodes),
+}
+
+impl SnapMessage {
+    /// Returns the message's ID.
+    pub fn message_id(&self) -> SnapMessageId {
+        match self {
+            SnapMessage::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            SnapMessage::AccountRange(_) => SnapMessageId::AccountRange,
+            SnapMessage::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            SnapMessage::StorageRanges(_) => SnapMessageId::StorageRanges,
+            SnapMessage::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            SnapMessage::ByteCodes(_) => SnapMessageId::ByteCodes,
+            SnapMessage::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            SnapMessage::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns the request id of the message.
+    pub fn request_id(&self) -> u64 {
+        match self {
+            SnapMessage::GetAccountRange(msg) => msg.request_id,
+            SnapMessage::AccountRange(msg) => msg.request_id,
+            SnapMessage::GetStorageRanges(msg) => msg.request_id,
+            SnapMessage::StorageRanges(msg) => msg.request_id,
+            SnapMessage::GetByteCodes(msg) => msg.request_id,
+            SnapMessage::ByteCodes(msg) => msg.request_id,
+            SnapMessage::GetTrieNodes(msg) => msg.request_id,
+            SnapMessage::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    fn payload_length(&self) -> usize {
+        match self {
+            SnapMessage::GetAccountRange(msg) => msg.length(),
+            SnapMessage::AccountRange(msg) => msg.length(),
+            SnapMessage::GetStorageRanges(msg) => msg.length(),
+            SnapMessage::StorageRanges(msg) => msg.length(),
+            SnapMessage::GetByteCodes(msg) => msg.length(),
+            SnapMessage::ByteCodes(msg) => msg.length(),
+            SnapMessage::GetTrieNodes(msg) => msg.length(),
+            SnapMessage::TrieNodes(msg) => msg.length(),
+        }
+    }
+}
+
+/// Encodes the message into bytes.
+/// The message ID is encoded as a single byte and prepended to the message.
+impl Encodable for SnapMessage {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.message_id().encode(out);
+        match self {
+            SnapMessage::GetAccountRange(msg) => msg.encode(out),
+            SnapMessage::AccountRange(msg) => msg.encode(out),
+            SnapMessage::GetStorageRanges(msg) => msg.encode(out),
+            SnapMessage::StorageRanges(msg) => msg.encode(out),
+            SnapMessage::GetByteCodes(msg) => msg.encode(out),
+            SnapMessage::ByteCodes(msg) => msg.encode(out),
+            SnapMessage::GetTrieNodes(msg) => msg.encode(out),
+            SnapMessage::TrieNodes(msg) => msg.encode(out),
+        }
+    }
+    fn length(&self) -> usize {
+        self.message_id().length() + self.payload_length()
+    }
+}
+
+impl Decodable for SnapMessage {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let message = match SnapMessageId::decode(buf)? {
+            SnapMessageId::GetAccountRange => {
+                SnapMessage::GetAccountRange(GetAccountRange::decode(buf)?)
+            }
+            SnapMessageId::AccountRange => SnapMessage::AccountRange(AccountRange::decode(buf)?),
+            SnapMessageId::GetStorageRanges => {
+                SnapMessage::GetStorageRanges(GetStorageRanges::decode(buf)?)
+            }
+            SnapMessageId::StorageRanges => SnapMessage::StorageRanges(StorageRanges::decode(buf)?),
+            SnapMessageId::GetByteCodes => SnapMessage::GetByteCodes(GetByteCodes::decode(buf)?),
+            SnapMessageId::ByteCodes => SnapMessage::ByteCodes(ByteCodes::decode(buf)?),
+            SnapMessageId::GetTrieNodes => SnapMessage::GetTrieNodes(GetTrieNodes::decode(buf)?),
+            SnapMessageId::TrieNodes => SnapMessage::TrieNodes(TrieNodes::decode(buf)?),
+        };
+        Ok(message)
+    }
+}
+
+/// A request for a range of accounts of the state trie with the given root.
+///
+/// The peer returns the accounts with hashes in `[starting_hash, limit_hash]`, but may stop early
+/// once `response_bytes` is exceeded.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetAccountRange {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The root of the account trie to serve.
+    pub root_hash: H256,
+    /// The account hash of the first account to retrieve.
+    pub starting_hash: H256,
+    /// The account hash after which to stop serving accounts.
+    pub limit_hash: H256,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetAccountRange`], containing consecutive accounts and the merkle proofs
+/// for the boundaries of the range.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountRange {
+    /// The id of the request this is a response to.
+    pub request_id: u64,
+    /// The accounts, ordered by their hash.
+    pub accounts: Vec<AccountData>,
+    /// The trie nodes that prove the first and the last account of the range.
+    pub proof: Vec<Bytes>,
+}
+
+/// An account of an [`AccountRange`] response.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccountData {
+    /// The hash of the account address.
+    pub hash: H256,
+    /// The account itself.
+    pub body: SlimAccount,
+}
+
+/// An account in the "slim" encoding of the `snap` protocol.
+///
+/// This is the consensus encoding of an account, except that the empty storage root and the empty
+/// code hash are encoded as empty strings.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SlimAccount {
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The root of the account's storage trie.
+    pub storage_root: H256,
+    /// The hash of the account's bytecode.
+    pub code_hash: H256,
+}
+
+impl SlimAccount {
+    /// The encoded storage root, which is empty for the empty root.
+    fn slim_storage_root(&self) -> &[u8] {
+        if self.storage_root == EMPTY_ROOT {
+            &[]
+        } else {
+            self.storage_root.as_bytes()
+        }
+    }
+
+    /// The encoded code hash, which is empty for accounts without code.
+    fn slim_code_hash(&self) -> &[u8] {
+        if self.code_hash == KECCAK_EMPTY {
+            &[]
+        } else {
+            self.code_hash.as_bytes()
+        }
+    }
+
+    fn payload_length(&self) -> usize {
+        self.nonce.length() +
+            self.balance.length() +
+            self.slim_storage_root().length() +
+            self.slim_code_hash().length()
+    }
+}
+
+impl Default for SlimAccount {
+    fn default() -> Self {
+        Self { nonce: 0, balance: U256::ZERO, storage_root: EMPTY_ROOT, code_hash: KECCAK_EMPTY }
+    }
+}
+
+impl Encodable for SlimAccount {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.nonce.encode(out);
+        self.balance.encode(out);
+        self.slim_storage_root().encode(out);
+        self.slim_code_hash().encode(out);
+    }
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + reth_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for SlimAccount {
+    fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(DecodeError::UnexpectedString)
+        }
+        let started_len = buf.len();
+
+        let nonce = u64::decode(buf)?;
+        let balance = U256::decode(buf)?;
+        let storage_root = decode_slim_hash(buf, EMPTY_ROOT)?;
+        let code_hash = decode_slim_hash(buf, KECCAK_EMPTY)?;
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(DecodeError::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+        Ok(Self { nonce, balance, storage_root, code_hash })
+    }
+}
+
+/// Decodes a hash that is encoded as an empty string if it equals `empty`.
+fn decode_slim_hash(buf: &mut &[u8], empty: H256) -> Result<H256, DecodeError> {
+    let bytes = Bytes::decode(buf)?;
+    match bytes.len() {
+        0 => Ok(empty),
+        32 => Ok(H256::from_slice(&bytes)),
+        _ => Err(DecodeError::UnexpectedLength),
+    }
+}
+
+/// A request for the storage slots of multiple accounts of the state trie with the given root.
+///
+/// If `starting_hash` and `limit_hash` are not empty, they restrict the storage range of the
+/// first account, which is used to continue the download of a large storage trie.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetStorageRanges {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The root of the account trie to serve.
+    pub root_hash: H256,
+    /// The hashes of the accounts to serve the storage of.
+    pub account_hashes: Vec<H256>,
+    /// The storage slot hash of the first slot to retrieve.
+    pub starting_hash: Bytes,
+    /// The storage slot hash after which to stop serving slots.
+    pub limit_hash: Bytes,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetStorageRanges`], containing the storage slots of the requested accounts.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StorageRanges {
+    /// The id of the request this is a response to.
+    pub request_id: u64,
+    /// The storage slots of each account, ordered by their hash.
+    pub slots: Vec<Vec<StorageData>>,
+    /// The trie nodes that prove the last storage range if it's incomplete.
+    pub proof: Vec<Bytes>,
+}
+
+/// A storage slot of a [`StorageRanges`] response.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StorageData {
+    /// The hash of the storage slot key.
+    pub hash: H256,
+    /// The RLP encoded value of the storage slot.
+    pub data: Bytes,
+}
+
+/// A request for contract bytecodes by their hashes.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetByteCodes {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The code hashes to retrieve the bytecode of.
+    pub hashes: Vec<H256>,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetByteCodes`], containing the requested bytecodes in request order.
+///
+/// Bytecodes the peer doesn't have are omitted.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ByteCodes {
+    /// The id of the request this is a response to.
+    pub request_id: u64,
+    /// The requested bytecodes.
+    pub codes: Vec<Bytes>,
+}
+
+/// A request for trie nodes of the state trie with the given root.
+///
+/// Each path is a list of the path of an account trie node, followed by the paths of storage trie
+/// nodes of that account.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GetTrieNodes {
+    /// The id of the request.
+    pub request_id: u64,
+    /// The root of the account trie to serve.
+    pub root_hash: H256,
+    /// The compact encoded paths of the requested trie nodes.
+    pub paths: Vec<Vec<Bytes>>,
+    /// The soft limit of the response size in bytes.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetTrieNodes`], containing the requested trie nodes in request order.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrieNodes {
+    /// The id of the request this is a response to.
+    pub request_id: u64,
+    /// The requested trie nodes.
+    pub nodes: Vec<Bytes>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use hex_literal::hex;
+
+    fn roundtrip(message: SnapMessage) {
+        let mut buf = vec![];
+        message.encode(&mut buf);
+        assert_eq!(buf.len(), message.length());
+        let decoded = SnapMessage::decode(&mut &buf[..]).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn encode_get_account_range() {
+        let request = GetAccountRange {
+            request_id: 1111,
+            root_hash: H256::repeat_byte(0x01),
+            starting_hash: H256::zero(),
+            limit_hash: H256::repeat_byte(0xff),
+            response_bytes: 512 * 1024,
+        };
+        let mut buf = vec![];
+        SnapMessage::GetAccountRange(request.clone()).encode(&mut buf);
+        // message id, list header, request id
+        assert_eq!(&buf[..6], &hex!("00f86a820457"));
+        roundtrip(SnapMessage::GetAccountRange(request));
+    }
+
+    #[test]
+    fn roundtrip_account_range() {
+        let response = AccountRange {
+            request_id: 1111,
+            accounts: vec![
+                AccountData { hash: H256::repeat_byte(0x01), body: SlimAccount::default() },
+                AccountData {
+                    hash: H256::repeat_byte(0x02),
+                    body: SlimAccount {
+                        nonce: 3,
+                        balance: U256::from(1_000_000u64),
+                        storage_root: H256::repeat_byte(0xaa),
+                        code_hash: H256::repeat_byte(0xbb),
+                    },
+                },
+            ],
+            proof: vec![Bytes::from(hex!("deadbeef").as_slice())],
+        };
+        roundtrip(SnapMessage::AccountRange(response));
+    }
+
+    #[test]
+    fn slim_account_encoding() {
+        let mut buf = vec![];
+        SlimAccount::default().encode(&mut buf);
+        // nonce 0, balance 0, empty storage root and empty code hash
+        assert_eq!(buf, hex!("c480808080"));
+
+        let account = SlimAccount { storage_root: H256::repeat_byte(0xaa), ..Default::default() };
+        let mut buf = vec![];
+        account.encode(&mut buf);
+        assert_eq!(SlimAccount::decode(&mut &buf[..]).unwrap(), account);
+
+        // hashes must be empty or 32 bytes
+        let invalid = hex!("c5808081aa80");
+        assert!(SlimAccount::decode(&mut &invalid[..]).is_err());
+    }
+
+    #[test]
+    fn roundtrip_storage_ranges() {
+        roundtrip(SnapMessage::GetStorageRanges(GetStorageRanges {
+            request_id: 1,
+            root_hash: H256::repeat_byte(0x01),
+            account_hashes: vec![H256::repeat_byte(0x02), H256::repeat_byte(0x03)],
+            starting_hash: Bytes::default(),
+            limit_hash: Bytes::default(),
+            response_bytes: 1024,
+        }));
+        roundtrip(SnapMessage::StorageRanges(StorageRanges {
+            request_id: 1,
+            slots: vec![
+                vec![StorageData {
+                    hash: H256::repeat_byte(0x04),
+                    data: Bytes::from(hex!("2a").as_slice()),
+                }],
+                vec![],
+            ],
+            proof: vec![],
+        }));
+    }
+
+    #[test]
+    fn roundtrip_bytecodes_and_trie_nodes() {
+        roundtrip(SnapMessage::GetByteCodes(GetByteCodes {
+            request_id: 2,
+            hashes: vec![H256::repeat_byte(0x05)],
+            response_bytes: 1024,
+        }));
+        roundtrip(SnapMessage::ByteCodes(ByteCodes {
+            request_id: 2,
+            codes: vec![Bytes::from(hex!("6000").as_slice())],
+        }));
+        roundtrip(SnapMessage::GetTrieNodes(GetTrieNodes {
+            request_id: 3,
+            root_hash: H256::repeat_byte(0x06),
+            paths: vec![vec![Bytes::from(hex!("00").as_slice())]],
+            response_bytes: 1024,
+        }));
+        roundtrip(SnapMessage::TrieNodes(TrieNodes {
+            request_id: 3,
+            nodes: vec![Bytes::from(hex!("c0").as_slice())],
+        }));
+    }
+
+    #[test]
+    fn invalid_message_id() {
+        assert!(SnapMessage::decode(&mut &hex!("08c0")[..]).is_err());
+    }
+}
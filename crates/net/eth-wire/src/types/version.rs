@@ -27,14 +27,13 @@ impl EthVersion {
     /// The latest known eth version
     pub const LATEST: EthVersion = EthVersion::Eth68;
 
-    /// Returns the total number of messages the protocol version supports.
+    /// Returns the number of message ids reserved by the protocol version.
+    ///
+    /// This determines the message id offset of the capabilities that follow `eth`, e.g. `snap`.
     pub fn total_messages(&self) -> u8 {
         match self {
-            EthVersion::Eth66 => 15,
-            EthVersion::Eth67 | EthVersion::Eth68 => {
-                // eth/67,68 are eth/66 minus GetNodeData and NodeData messages
-                13
-            }
+            // eth/67,68 removed GetNodeData and NodeData, but still reserve their message ids
+            EthVersion::Eth66 | EthVersion::Eth67 | EthVersion::Eth68 => 17,
         }
     }
 }
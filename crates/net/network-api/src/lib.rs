@@ -10,9 +10,10 @@
 //! Provides abstractions for the reth-network crate.
 
 use async_trait::async_trait;
-use reth_eth_wire::DisconnectReason;
-use reth_primitives::{NodeRecord, PeerId, H256, U256};
-use std::net::SocketAddr;
+use reth_eth_wire::{capability::Capabilities, DisconnectReason, EthVersion, Status};
+use reth_primitives::{BlockNumber, NodeRecord, PeerId, H256, U256};
+use std::{net::SocketAddr, sync::Arc};
+use tokio::sync::broadcast;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -40,6 +41,9 @@ pub trait NetworkInfo: Send + Sync {
 
     /// Returns the chain id
     fn chain_id(&self) -> u64;
+
+    /// Returns a new receiver for the [`SyncEvent`]s of the node's sync.
+    fn subscribe_sync_events(&self) -> broadcast::Receiver<SyncEvent>;
 }
 
 /// Provides general purpose information about Peers in the network.
@@ -54,6 +58,7 @@ pub trait PeersInfo: Send + Sync {
 }
 
 /// Provides an API for managing the peers of the network.
+#[async_trait]
 pub trait Peers: PeersInfo {
     /// Adds a peer to the peer set.
     fn add_peer(&self, peer: PeerId, addr: SocketAddr) {
@@ -79,6 +84,51 @@ pub trait Peers: PeersInfo {
 
     /// Send a reputation change for the given peer.
     fn reputation_change(&self, peer_id: PeerId, kind: ReputationChangeKind);
+
+    /// Returns [`PeerInfo`] for all connected peers.
+    async fn get_peers(&self) -> Result<Vec<PeerInfo>, NetworkError>;
+
+    /// Returns [`PeerInfo`] for a given peer.
+    ///
+    /// Returns `None` if there's no active session to the peer.
+    async fn get_peer_by_id(&self, peer_id: PeerId) -> Result<Option<PeerInfo>, NetworkError>;
+}
+
+/// Info about an active peer session.
+#[derive(Debug, Clone)]
+pub struct PeerInfo {
+    /// Announced capabilities of the peer
+    pub capabilities: Arc<Capabilities>,
+    /// The identifier of the remote peer
+    pub remote_id: PeerId,
+    /// The client's name and version
+    pub client_version: String,
+    /// The address we're connected to
+    pub remote_addr: SocketAddr,
+    /// The local address of the connection, if known
+    pub local_addr: Option<SocketAddr>,
+    /// The direction of the session
+    pub direction: Direction,
+    /// The negotiated eth version of the session
+    pub eth_version: EthVersion,
+    /// The `Status` message the peer sent during the handshake
+    pub status: Status,
+}
+
+/// The direction of the connection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    /// Incoming connection.
+    Incoming,
+    /// Outgoing connection to a specific node.
+    Outgoing(PeerId),
+}
+
+impl Direction {
+    /// Returns `true` if this an incoming connection.
+    pub fn is_incoming(&self) -> bool {
+        matches!(self, Direction::Incoming)
+    }
 }
 
 /// Represents the kind of peer
@@ -91,6 +141,27 @@ pub enum PeerKind {
     Trusted,
 }
 
+/// An event emitted as the node's sync progresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncEvent {
+    /// The node started to sync.
+    SyncStarted {
+        /// The block the sync started at.
+        start_block: BlockNumber,
+        /// The block the node is syncing to.
+        target_block: BlockNumber,
+    },
+    /// The sync made progress.
+    SyncProgress {
+        /// The block the node has synced to.
+        current_block: BlockNumber,
+        /// The highest block known to the node.
+        highest_block: BlockNumber,
+    },
+    /// The node caught up with the chain.
+    SyncFinished,
+}
+
 /// The status of the network being ran by the local node.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
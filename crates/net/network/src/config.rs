@@ -2,20 +2,23 @@
 
 use crate::{
     error::NetworkError,
+    fetch::SyncBandwidthConfig,
     import::{BlockImport, ProofOfStakeBlockImport},
+    peer_activity::DEFAULT_PEER_INACTIVITY_TIMEOUT,
     peers::PeersConfig,
     session::SessionsConfig,
     NetworkHandle, NetworkManager,
 };
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, DEFAULT_DISCOVERY_PORT};
 use reth_primitives::{ChainSpec, ForkFilter, Head, NodeRecord, PeerId, MAINNET};
-use reth_provider::{BlockProvider, HeaderProvider};
+use reth_provider::{BlockProvider, HeaderProvider, NodeDataProvider};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use secp256k1::{SecretKey, SECP256K1};
 use std::{
     collections::HashSet,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     sync::Arc,
+    time::Duration,
 };
 
 /// reexports for convenience
@@ -54,6 +57,11 @@ pub struct NetworkConfig<C> {
     pub peers_config: PeersConfig,
     /// How to configure the [SessionManager](crate::session::SessionManager).
     pub sessions_config: SessionsConfig,
+    /// The bandwidth limit of outgoing sync requests, unlimited if `None`.
+    pub sync_bandwidth_config: Option<SyncBandwidthConfig>,
+    /// Time without any message after which a peer with an active request is considered stale
+    /// and replaced.
+    pub peer_inactivity_timeout: Duration,
     /// The chain spec
     pub chain_spec: Arc<ChainSpec>,
     /// The [`ForkFilter`] to use at launch for authenticating sessions.
@@ -103,7 +111,7 @@ impl<C> NetworkConfig<C> {
 
 impl<C> NetworkConfig<C>
 where
-    C: BlockProvider + HeaderProvider + Clone + Unpin + 'static,
+    C: BlockProvider + HeaderProvider + NodeDataProvider + Clone + Unpin + 'static,
 {
     /// Starts the networking stack given a [NetworkConfig] and returns a handle to the network.
     pub async fn start_network(self) -> Result<NetworkHandle, NetworkError> {
@@ -139,6 +147,10 @@ pub struct NetworkConfigBuilder {
     peers_config: Option<PeersConfig>,
     /// How to configure the sessions manager
     sessions_config: Option<SessionsConfig>,
+    /// The bandwidth limit of outgoing sync requests
+    sync_bandwidth_config: Option<SyncBandwidthConfig>,
+    /// Time without any message after which a peer with an active request is stale
+    peer_inactivity_timeout: Option<Duration>,
     /// The network's chain spec
     chain_spec: Arc<ChainSpec>,
     /// The default mode of the network.
@@ -166,6 +178,8 @@ impl NetworkConfigBuilder {
             listener_addr: None,
             peers_config: None,
             sessions_config: None,
+            sync_bandwidth_config: None,
+            peer_inactivity_timeout: None,
             chain_spec: Arc::new(MAINNET.clone()),
             network_mode: Default::default(),
             executor: None,
@@ -232,6 +246,21 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Limits the bandwidth of outgoing sync requests, like `GetBlockBodies`.
+    pub fn sync_bandwidth_config(mut self, config: SyncBandwidthConfig) -> Self {
+        self.sync_bandwidth_config = Some(config);
+        self
+    }
+
+    /// Sets the time without any message after which a peer with an active request is considered
+    /// stale.
+    ///
+    /// The request of a stale peer is sent to another peer, and the stale peer is disconnected.
+    pub fn peer_inactivity_timeout(mut self, timeout: Duration) -> Self {
+        self.peer_inactivity_timeout = Some(timeout);
+        self
+    }
+
     /// Sets the socket address the network will listen on
     pub fn listener_addr(mut self, listener_addr: SocketAddr) -> Self {
         self.listener_addr = Some(listener_addr);
@@ -299,6 +328,8 @@ impl NetworkConfigBuilder {
             listener_addr,
             peers_config,
             sessions_config,
+            sync_bandwidth_config,
+            peer_inactivity_timeout,
             chain_spec,
             network_mode,
             executor,
@@ -351,6 +382,9 @@ impl NetworkConfigBuilder {
             listener_addr,
             peers_config: peers_config.unwrap_or_default(),
             sessions_config: sessions_config.unwrap_or_default(),
+            sync_bandwidth_config,
+            peer_inactivity_timeout: peer_inactivity_timeout
+                .unwrap_or(DEFAULT_PEER_INACTIVITY_TIMEOUT),
             chain_spec,
             block_import: Box::<ProofOfStakeBlockImport>::default(),
             network_mode,
@@ -1,6 +1,6 @@
 //! Blocks/Headers management for the p2p network.
 
-use crate::peers::PeersHandle;
+use crate::{node_data::NodeDataHandler, peers::PeersHandle};
 use futures::StreamExt;
 use reth_eth_wire::{
     BlockBodies, BlockBody, BlockHeaders, GetBlockBodies, GetBlockHeaders, GetNodeData,
@@ -8,7 +8,7 @@ use reth_eth_wire::{
 };
 use reth_interfaces::p2p::error::RequestResult;
 use reth_primitives::{BlockHashOrNumber, Header, HeadersDirection, PeerId};
-use reth_provider::{BlockProvider, HeaderProvider};
+use reth_provider::{BlockProvider, HeaderProvider, NodeDataProvider};
 use std::{
     borrow::Borrow,
     future::Future,
@@ -55,6 +55,8 @@ pub struct EthRequestHandler<C> {
     peers: PeersHandle,
     /// Incoming request from the [NetworkManager](crate::NetworkManager).
     incoming_requests: UnboundedReceiverStream<IncomingEthRequest>,
+    /// Serves `GetNodeData` requests.
+    node_data: NodeDataHandler,
 }
 
 // === impl EthRequestHandler ===
@@ -65,13 +67,18 @@ impl<C> EthRequestHandler<C> {
         peers: PeersHandle,
         incoming: UnboundedReceiver<IncomingEthRequest>,
     ) -> Self {
-        Self { client, peers, incoming_requests: UnboundedReceiverStream::new(incoming) }
+        Self {
+            client,
+            peers,
+            incoming_requests: UnboundedReceiverStream::new(incoming),
+            node_data: Default::default(),
+        }
     }
 }
 
 impl<C> EthRequestHandler<C>
 where
-    C: BlockProvider + HeaderProvider,
+    C: BlockProvider + HeaderProvider + NodeDataProvider,
 {
     /// Returns the list of requested heders
     fn get_headers_response(&self, request: GetBlockHeaders) -> Vec<Header> {
@@ -185,6 +192,15 @@ where
 
         let _ = response.send(Ok(BlockBodies(bodies)));
     }
+
+    fn on_node_data_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetNodeData,
+        response: oneshot::Sender<RequestResult<NodeData>>,
+    ) {
+        self.node_data.on_request(&self.client, peer_id, request, response)
+    }
 }
 
 /// An endless future.
@@ -192,7 +208,7 @@ where
 /// This should be spawned or used as part of `tokio::select!`.
 impl<C> Future for EthRequestHandler<C>
 where
-    C: BlockProvider + HeaderProvider + Unpin,
+    C: BlockProvider + HeaderProvider + NodeDataProvider + Unpin,
 {
     type Output = ();
 
@@ -210,7 +226,9 @@ where
                     IncomingEthRequest::GetBlockBodies { peer_id, request, response } => {
                         this.on_bodies_request(peer_id, request, response)
                     }
-                    IncomingEthRequest::GetNodeData { .. } => {}
+                    IncomingEthRequest::GetNodeData { peer_id, request, response } => {
+                        this.on_node_data_request(peer_id, request, response)
+                    }
                     IncomingEthRequest::GetReceipts { .. } => {}
                 },
             }
@@ -0,0 +1,199 @@
+//! Bandwidth limiting of outgoing sync requests.
+
+use std::time::{Duration, Instant};
+
+/// Configures the bandwidth available to outgoing sync requests.
+///
+/// The bandwidth is shared between the kinds of [`SyncTraffic`] in proportion to their shares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SyncBandwidthConfig {
+    /// The max number of bytes per second of all outgoing sync requests.
+    pub max_bytes_per_sec: u64,
+    /// The share of the bandwidth for header downloads.
+    pub headers_share: u32,
+    /// The share of the bandwidth for body downloads.
+    pub bodies_share: u32,
+    /// The share of the bandwidth for state downloads.
+    pub state_share: u32,
+}
+
+// === impl SyncBandwidthConfig ===
+
+impl SyncBandwidthConfig {
+    /// Creates a config that limits outgoing sync requests to the given megabits per second.
+    pub fn from_mbps(mbps: u64) -> Self {
+        Self { max_bytes_per_sec: mbps.saturating_mul(1_000_000) / 8, ..Default::default() }
+    }
+
+    /// Returns the bytes per second available to the given kind of traffic.
+    fn bytes_per_sec(&self, traffic: SyncTraffic) -> f64 {
+        let share = match traffic {
+            SyncTraffic::Headers => self.headers_share,
+            SyncTraffic::Bodies => self.bodies_share,
+            SyncTraffic::State => self.state_share,
+        };
+        let total = self.headers_share as u64 + self.bodies_share as u64 + self.state_share as u64;
+        if total == 0 {
+            return 0.0
+        }
+        self.max_bytes_per_sec as f64 * share as f64 / total as f64
+    }
+}
+
+impl Default for SyncBandwidthConfig {
+    fn default() -> Self {
+        Self { max_bytes_per_sec: u64::MAX, headers_share: 1, bodies_share: 2, state_share: 1 }
+    }
+}
+
+/// The kinds of outgoing sync traffic that share the bandwidth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SyncTraffic {
+    /// `GetBlockHeaders` requests.
+    Headers,
+    /// `GetBlockBodies` requests.
+    Bodies,
+    /// State requests, e.g. `GetAccountRange`.
+    State,
+}
+
+/// A token-bucket rate limiter for the bytes of outgoing sync requests.
+///
+/// Every kind of [`SyncTraffic`] has its own bucket that refills at its share of the bandwidth
+/// and holds up to one second worth of bytes. A request is allowed if its bucket holds enough
+/// bytes, or if the bucket is full, so that requests larger than the bucket are not blocked
+/// forever. Those put the bucket into debt instead.
+#[derive(Debug)]
+pub(crate) struct BandwidthLimiter {
+    /// The buckets for headers, bodies and state traffic.
+    buckets: [Bucket; 3],
+}
+
+// === impl BandwidthLimiter ===
+
+impl BandwidthLimiter {
+    /// Creates a new limiter with full buckets.
+    pub(crate) fn new(config: SyncBandwidthConfig) -> Self {
+        let now = Instant::now();
+        let bucket = |traffic| Bucket::new(config.bytes_per_sec(traffic), now);
+        Self {
+            buckets: [
+                bucket(SyncTraffic::Headers),
+                bucket(SyncTraffic::Bodies),
+                bucket(SyncTraffic::State),
+            ],
+        }
+    }
+
+    /// Tries to consume the bytes of a request.
+    ///
+    /// Returns how long to wait until the request can be sent if the bandwidth is exhausted.
+    pub(crate) fn try_consume(
+        &mut self,
+        traffic: SyncTraffic,
+        bytes: usize,
+    ) -> Result<(), Duration> {
+        self.try_consume_at(traffic, bytes, Instant::now())
+    }
+
+    fn try_consume_at(
+        &mut self,
+        traffic: SyncTraffic,
+        bytes: usize,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let bucket = &mut self.buckets[traffic as usize];
+        bucket.refill(now);
+
+        let required = (bytes as f64).min(bucket.capacity());
+        if bucket.tokens < required {
+            if bucket.bytes_per_sec <= 0.0 {
+                // no bandwidth for this kind of traffic
+                return Err(Duration::MAX)
+            }
+            let wait = (required - bucket.tokens) / bucket.bytes_per_sec;
+            return Err(Duration::try_from_secs_f64(wait).unwrap_or(Duration::MAX))
+        }
+        bucket.tokens -= bytes as f64;
+        Ok(())
+    }
+}
+
+/// A bucket of bytes that refills at a fixed rate.
+#[derive(Debug)]
+struct Bucket {
+    /// The refill rate.
+    bytes_per_sec: f64,
+    /// The available bytes, negative if a request exceeded them.
+    tokens: f64,
+    /// When the bucket was last refilled.
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn new(bytes_per_sec: f64, now: Instant) -> Self {
+        Self { bytes_per_sec, tokens: bytes_per_sec, last_refill: now }
+    }
+
+    /// The max number of bytes the bucket holds, i.e. the allowed burst.
+    fn capacity(&self) -> f64 {
+        self.bytes_per_sec
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.bytes_per_sec).min(self.capacity());
+        self.last_refill = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_bandwidth() {
+        let config = SyncBandwidthConfig { max_bytes_per_sec: 4_000, ..Default::default() };
+        assert_eq!(config.bytes_per_sec(SyncTraffic::Headers), 1_000.0);
+        assert_eq!(config.bytes_per_sec(SyncTraffic::Bodies), 2_000.0);
+        assert_eq!(config.bytes_per_sec(SyncTraffic::State), 1_000.0);
+        assert_eq!(SyncBandwidthConfig::from_mbps(8).max_bytes_per_sec, 1_000_000);
+    }
+
+    #[test]
+    fn throttles_requests() {
+        let config = SyncBandwidthConfig { max_bytes_per_sec: 4_000, ..Default::default() };
+        let mut limiter = BandwidthLimiter::new(config);
+        let now = Instant::now();
+
+        assert!(limiter.try_consume_at(SyncTraffic::Bodies, 1_500, now).is_ok());
+        assert_eq!(
+            limiter.try_consume_at(SyncTraffic::Bodies, 1_000, now),
+            Err(Duration::from_millis(250))
+        );
+        // the headers have their own share
+        assert!(limiter.try_consume_at(SyncTraffic::Headers, 1_000, now).is_ok());
+
+        let later = now + Duration::from_millis(250);
+        assert!(limiter.try_consume_at(SyncTraffic::Bodies, 1_000, later).is_ok());
+        assert!(limiter.try_consume_at(SyncTraffic::Bodies, 1, later).is_err());
+    }
+
+    #[test]
+    fn allows_requests_larger_than_bucket() {
+        let config = SyncBandwidthConfig { max_bytes_per_sec: 4_000, ..Default::default() };
+        let mut limiter = BandwidthLimiter::new(config);
+        let now = Instant::now();
+
+        // a full bucket allows a request of any size, which puts it into debt
+        assert!(limiter.try_consume_at(SyncTraffic::Headers, 3_000, now).is_ok());
+        assert_eq!(
+            limiter.try_consume_at(SyncTraffic::Headers, 3_000, now),
+            Err(Duration::from_secs(3))
+        );
+        assert!(limiter
+            .try_consume_at(SyncTraffic::Headers, 3_000, now + Duration::from_secs(3))
+            .is_ok());
+    }
+}
@@ -9,6 +9,7 @@ use reth_interfaces::p2p::{
     error::{PeerRequestResult, RequestError},
     headers::client::{HeadersClient, HeadersRequest},
     priority::Priority,
+    snap::client::{AccountRangeFut, GetAccountRange, SnapClient},
 };
 use reth_network_api::ReputationChangeKind;
 use reth_primitives::{Header, PeerId, H256};
@@ -23,8 +24,8 @@ use tokio::sync::{
 
 /// Front-end API for fetching data from the network.
 ///
-/// Following diagram illustrates how a request, See [`HeadersClient::get_headers`] and
-/// [`BodiesClient::get_block_bodies`] is handled internally.
+/// Following diagram illustrates how a request, See [`HeadersClient::get_headers`],
+/// [`BodiesClient::get_block_bodies`] and [`SnapClient::get_account_range`] is handled internally.
 #[cfg_attr(doc, aquamarine::aquamarine)]
 /// ```mermaid
 /// sequenceDiagram
@@ -34,7 +35,7 @@ use tokio::sync::{
 //     participant Session as Active Peer Session
 //     participant Peers as PeerManager
 //     loop Send Request, retry if retriable and remaining retries
-//         Client->>Fetcher: DownloadRequest{GetHeaders, GetBodies}
+//         Client->>Fetcher: DownloadRequest{GetHeaders, GetBodies, GetAccountRange}
 //         Note over Client,Fetcher: Request and oneshot Sender sent via `request_tx` channel
 //         loop Process buffered requests
 //             State->>Fetcher: poll action
@@ -63,6 +64,8 @@ use tokio::sync::{
 pub struct FetchClient {
     /// Sender half of the request channel.
     pub(crate) request_tx: UnboundedSender<DownloadRequest>,
+    /// Sender half of the channel for peers that sent a bad response.
+    pub(crate) bad_response_tx: UnboundedSender<PeerId>,
     /// The handle to the peers
     pub(crate) peers_handle: PeersHandle,
     /// Number of active peer sessions the node's currently handling.
@@ -72,6 +75,8 @@ pub struct FetchClient {
 impl DownloadClient for FetchClient {
     fn report_bad_message(&self, peer_id: PeerId) {
         self.peers_handle.reputation_change(peer_id, ReputationChangeKind::BadMessage);
+        // avoid retrying the request with the same peer
+        let _ = self.bad_response_tx.send(peer_id);
     }
 
     fn num_connected_peers(&self) -> usize {
@@ -126,3 +131,27 @@ impl BodiesClient for FetchClient {
         }
     }
 }
+
+impl SnapClient for FetchClient {
+    type Output = AccountRangeFut;
+
+    /// Sends a `GetAccountRange` request to an available peer that shares the `snap` capability.
+    ///
+    /// The request is only served if `snap` is part of the capabilities of the local `Hello`.
+    fn get_account_range_with_priority(
+        &self,
+        request: GetAccountRange,
+        priority: Priority,
+    ) -> Self::Output {
+        let (response, rx) = oneshot::channel();
+        if self
+            .request_tx
+            .send(DownloadRequest::GetAccountRange { request, response, priority })
+            .is_ok()
+        {
+            Box::pin(FlattenedResponse::from(rx))
+        } else {
+            Box::pin(future::err(RequestError::ChannelClosed))
+        }
+    }
+}
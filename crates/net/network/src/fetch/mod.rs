@@ -2,7 +2,10 @@
 
 use crate::{message::BlockRequest, peers::PeersHandle};
 use futures::StreamExt;
-use reth_eth_wire::{BlockBody, GetBlockBodies, GetBlockHeaders};
+use reth_eth_wire::{
+    snap::{AccountRange, GetAccountRange},
+    BlockBody, GetBlockBodies, GetBlockHeaders,
+};
 use reth_interfaces::p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
     headers::client::HeadersRequest,
@@ -10,18 +13,27 @@ use reth_interfaces::p2p::{
 };
 use reth_network_api::ReputationChangeKind;
 use reth_primitives::{Header, PeerId, H256};
+use reth_rlp::Encodable;
 use std::{
     collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     task::{Context, Poll},
 };
-use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
+use tokio::{
+    sync::{mpsc, mpsc::UnboundedSender, oneshot},
+    time::Sleep,
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
+mod bandwidth;
 mod client;
+pub use bandwidth::SyncBandwidthConfig;
+use bandwidth::{BandwidthLimiter, SyncTraffic};
 pub use client::FetchClient;
 
 /// Manages data fetching operations.
@@ -37,6 +49,9 @@ pub struct StateFetcher {
     /// Currently active [`GetBlockBodies`] requests
     inflight_bodies_requests:
         HashMap<PeerId, Request<Vec<H256>, PeerRequestResult<Vec<BlockBody>>>>,
+    /// Currently active [`GetAccountRange`] requests
+    inflight_account_range_requests:
+        HashMap<PeerId, Request<GetAccountRange, PeerRequestResult<AccountRange>>>,
     /// The list of _available_ peers for requests.
     peers: HashMap<PeerId, Peer>,
     /// The handle to the peers manager
@@ -49,6 +64,14 @@ pub struct StateFetcher {
     download_requests_rx: UnboundedReceiverStream<DownloadRequest>,
     /// Sender for download requests, used to detach a [`FetchClient`]
     download_requests_tx: UnboundedSender<DownloadRequest>,
+    /// Receiver for peers that were reported for a bad response by a [`FetchClient`]
+    bad_responses_rx: UnboundedReceiverStream<PeerId>,
+    /// Sender for bad response reports, used to detach a [`FetchClient`]
+    bad_responses_tx: UnboundedSender<PeerId>,
+    /// Limits the bandwidth of outgoing requests, if configured
+    bandwidth_limiter: Option<BandwidthLimiter>,
+    /// Wakes up the fetcher once the bandwidth for the next queued request is available
+    throttle: Option<Pin<Box<Sleep>>>,
 }
 
 // === impl StateSyncer ===
@@ -56,18 +79,30 @@ pub struct StateFetcher {
 impl StateFetcher {
     pub(crate) fn new(peers_handle: PeersHandle, num_active_peers: Arc<AtomicUsize>) -> Self {
         let (download_requests_tx, download_requests_rx) = mpsc::unbounded_channel();
+        let (bad_responses_tx, bad_responses_rx) = mpsc::unbounded_channel();
         Self {
             inflight_headers_requests: Default::default(),
             inflight_bodies_requests: Default::default(),
+            inflight_account_range_requests: Default::default(),
             peers: Default::default(),
             peers_handle,
             num_active_peers,
             queued_requests: Default::default(),
             download_requests_rx: UnboundedReceiverStream::new(download_requests_rx),
             download_requests_tx,
+            bad_responses_rx: UnboundedReceiverStream::new(bad_responses_rx),
+            bad_responses_tx,
+            bandwidth_limiter: None,
+            throttle: None,
         }
     }
 
+    /// Limits the bandwidth of outgoing requests.
+    pub(crate) fn with_bandwidth_limit(mut self, config: Option<SyncBandwidthConfig>) -> Self {
+        self.bandwidth_limiter = config.map(BandwidthLimiter::new);
+        self
+    }
+
     /// Invoked when connected to a new peer.
     pub(crate) fn new_active_peer(
         &mut self,
@@ -76,8 +111,26 @@ impl StateFetcher {
         best_number: u64,
         timeout: Arc<AtomicU64>,
     ) {
-        self.peers
-            .insert(peer_id, Peer { state: PeerState::Idle, best_hash, best_number, timeout });
+        self.peers.insert(
+            peer_id,
+            Peer {
+                state: PeerState::Idle,
+                best_hash,
+                best_number,
+                timeout,
+                last_response_likely_bad: false,
+                supports_snap: false,
+            },
+        );
+    }
+
+    /// Marks the peer as available for `snap` requests.
+    ///
+    /// Invoked when the peer announced the `snap` capability.
+    pub(crate) fn set_snap_supported(&mut self, peer_id: &PeerId) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.supports_snap = true;
+        }
     }
 
     /// Removes the peer from the peer list, after which it is no longer available for future
@@ -94,6 +147,9 @@ impl StateFetcher {
         if let Some(req) = self.inflight_bodies_requests.remove(peer) {
             let _ = req.response.send(Err(RequestError::ConnectionDropped));
         }
+        if let Some(req) = self.inflight_account_range_requests.remove(peer) {
+            let _ = req.response.send(Err(RequestError::ConnectionDropped));
+        }
     }
 
     /// Updates the block information for the peer.
@@ -117,14 +173,62 @@ impl StateFetcher {
         }
     }
 
+    /// Invoked when the peer stopped responding to its active request and is about to be
+    /// disconnected.
+    ///
+    /// The active request is queued again with high priority, so it is sent to another peer.
+    pub(crate) fn on_stale_peer(&mut self, peer_id: &PeerId) {
+        self.on_pending_disconnect(peer_id);
+        if let Some(Request { request, response }) = self.inflight_headers_requests.remove(peer_id)
+        {
+            self.queued_requests.push_front(DownloadRequest::GetBlockHeaders {
+                request,
+                response,
+                priority: Priority::High,
+            });
+        }
+        if let Some(Request { request, response }) = self.inflight_bodies_requests.remove(peer_id) {
+            self.queued_requests.push_front(DownloadRequest::GetBlockBodies {
+                request,
+                response,
+                priority: Priority::High,
+            });
+        }
+        if let Some(Request { request, response }) =
+            self.inflight_account_range_requests.remove(peer_id)
+        {
+            self.queued_requests.push_front(DownloadRequest::GetAccountRange {
+                request,
+                response,
+                priority: Priority::High,
+            });
+        }
+    }
+
+    /// Invoked when the response of the peer was reported as bad, e.g. bodies that don't match
+    /// their headers.
+    fn on_bad_response(&mut self, peer_id: &PeerId) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.last_response_likely_bad = true;
+        }
+    }
+
     /// Returns the _next_ idle peer that's ready to accept a request,
     /// prioritizing those with the lowest timeout/latency.
+    ///
+    /// Peers whose last response was bad are only selected if no other peer is idle, so that a
+    /// failed request is retried with a different peer.
     /// Once a peer has been yielded, it will be moved to the end of the map
-    fn next_peer(&mut self) -> Option<PeerId> {
+    fn next_peer(&self) -> Option<PeerId> {
+        self.next_idle_peer(false)
+    }
+
+    /// Same as [`Self::next_peer`], but only selects peers that support `snap` if `snap` is set.
+    fn next_idle_peer(&self, snap: bool) -> Option<PeerId> {
         self.peers
             .iter()
-            .filter(|(_, peer)| peer.state.is_idle())
-            .min_by_key(|(_, peer)| peer.timeout())
+            .filter(|(_, peer)| peer.state.is_idle() && (!snap || peer.supports_snap))
+            .min_by_key(|(_, peer)| (peer.last_response_likely_bad, peer.timeout()))
             .map(|(id, _)| *id)
     }
 
@@ -135,26 +239,67 @@ impl StateFetcher {
             return PollAction::NoRequests
         }
 
-        let peer_id = if let Some(peer_id) = self.next_peer() {
-            peer_id
-        } else {
+        // `snap` requests are skipped while no idle peer supports `snap`, so they don't hold up
+        // the block requests behind them
+        let Some((idx, peer_id)) =
+            self.queued_requests.iter().enumerate().find_map(|(idx, request)| {
+                Some((idx, self.next_idle_peer(request.is_snap_request())?))
+            })
+        else {
             return PollAction::NoPeersAvailable
         };
 
-        let request = self.queued_requests.pop_front().expect("not empty; qed");
+        if !self.has_bandwidth_for_request(idx) {
+            return PollAction::Throttled
+        }
+
+        let request = self.queued_requests.remove(idx).expect("exists; qed");
         let request = self.prepare_block_request(peer_id, request);
 
         PollAction::Ready(FetchAction::BlockRequest { peer_id, request })
     }
 
+    /// Returns `true` if the queued request at the given index can be sent without exceeding the
+    /// bandwidth limit, and accounts for its bytes.
+    ///
+    /// Otherwise a timer is armed that wakes up the fetcher once the bandwidth is available.
+    fn has_bandwidth_for_request(&mut self, idx: usize) -> bool {
+        let (Some(limiter), Some(request)) =
+            (self.bandwidth_limiter.as_mut(), self.queued_requests.get(idx))
+        else {
+            return true
+        };
+        let (traffic, bytes) = request.encoded_size();
+        match limiter.try_consume(traffic, bytes) {
+            Ok(()) => true,
+            Err(wait) => {
+                self.throttle = Some(Box::pin(tokio::time::sleep(wait)));
+                false
+            }
+        }
+    }
+
     /// Advance the state the syncer
     pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<FetchAction> {
+        // apply reported bad responses before selecting peers for new requests
+        while let Poll::Ready(Some(peer_id)) = self.bad_responses_rx.poll_next_unpin(cx) {
+            self.on_bad_response(&peer_id);
+        }
+
         // drain buffered actions first
         loop {
+            if let Some(throttle) = self.throttle.as_mut() {
+                if throttle.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending
+                }
+                self.throttle = None;
+            }
+
             let no_peers_available = match self.poll_action() {
                 PollAction::Ready(action) => return Poll::Ready(action),
                 PollAction::NoRequests => false,
                 PollAction::NoPeersAvailable => true,
+                PollAction::Throttled => continue,
             };
 
             loop {
@@ -188,6 +333,8 @@ impl StateFetcher {
         // update the peer's state
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             peer.state = req.peer_state();
+            // the peer gets another chance with this request
+            peer.last_response_likely_bad = false;
         }
 
         match req {
@@ -207,6 +354,11 @@ impl StateFetcher {
                 self.inflight_bodies_requests.insert(peer_id, inflight);
                 BlockRequest::GetBlockBodies(GetBlockBodies(request))
             }
+            DownloadRequest::GetAccountRange { request, response, .. } => {
+                let inflight = Request { request: request.clone(), response };
+                self.inflight_account_range_requests.insert(peer_id, inflight);
+                BlockRequest::GetAccountRange(request)
+            }
         }
     }
 
@@ -214,7 +366,18 @@ impl StateFetcher {
     ///
     /// Caution: this expects that the peer is _not_ closed.
     fn followup_request(&mut self, peer_id: PeerId) -> Option<BlockResponseOutcome> {
-        let req = self.queued_requests.pop_front()?;
+        if self.throttle.is_some() {
+            return None
+        }
+        let supports_snap = self.peers.get(&peer_id)?.supports_snap;
+        let idx = self
+            .queued_requests
+            .iter()
+            .position(|request| supports_snap || !request.is_snap_request())?;
+        if !self.has_bandwidth_for_request(idx) {
+            return None
+        }
+        let req = self.queued_requests.remove(idx).expect("exists; qed");
         let req = self.prepare_block_request(peer_id, req);
         Some(BlockResponseOutcome::Request(peer_id, req))
     }
@@ -275,10 +438,37 @@ impl StateFetcher {
         None
     }
 
+    /// Called on a `GetAccountRange` response from a peer.
+    ///
+    /// A peer that doesn't share the `snap` capability is no longer selected for `snap` requests.
+    pub(crate) fn on_account_range_response(
+        &mut self,
+        peer_id: PeerId,
+        res: RequestResult<AccountRange>,
+    ) -> Option<BlockResponseOutcome> {
+        let is_error = res.is_err();
+        let is_timeout = matches!(res, Err(RequestError::Timeout));
+        let is_unsupported = matches!(res, Err(RequestError::UnsupportedCapability));
+        if let Some(resp) = self.inflight_account_range_requests.remove(&peer_id) {
+            let _ = resp.response.send(res.map(|r| (peer_id, r).into()));
+        }
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            if is_unsupported {
+                peer.supports_snap = false;
+            }
+            if peer.state.on_request_finished() && !is_error {
+                return self.followup_request(peer_id)
+            }
+        }
+        is_timeout
+            .then_some(BlockResponseOutcome::BadResponse(peer_id, ReputationChangeKind::Timeout))
+    }
+
     /// Returns a new [`FetchClient`] that can send requests to this type.
     pub(crate) fn client(&self) -> FetchClient {
         FetchClient {
             request_tx: self.download_requests_tx.clone(),
+            bad_response_tx: self.bad_responses_tx.clone(),
             peers_handle: self.peers_handle.clone(),
             num_active_peers: Arc::clone(&self.num_active_peers),
         }
@@ -290,6 +480,7 @@ enum PollAction {
     Ready(FetchAction),
     NoRequests,
     NoPeersAvailable,
+    Throttled,
 }
 
 /// Represents a connected peer
@@ -302,6 +493,10 @@ struct Peer {
     best_number: u64,
     /// Tracks the current timeout value we use for the peer.
     timeout: Arc<AtomicU64>,
+    /// Whether the last response of the peer was reported as bad.
+    last_response_likely_bad: bool,
+    /// Whether the peer supports `snap` requests.
+    supports_snap: bool,
 }
 
 impl Peer {
@@ -318,6 +513,8 @@ enum PeerState {
     GetBlockHeaders,
     /// Peer is handling a `GetBlockBodies` request.
     GetBlockBodies,
+    /// Peer is handling a `GetAccountRange` request.
+    GetAccountRange,
     /// Peer session is about to close
     Closing,
 }
@@ -368,6 +565,12 @@ pub(crate) enum DownloadRequest {
         response: oneshot::Sender<PeerRequestResult<Vec<BlockBody>>>,
         priority: Priority,
     },
+    /// Download the requested account range and send response through channel
+    GetAccountRange {
+        request: GetAccountRange,
+        response: oneshot::Sender<PeerRequestResult<AccountRange>>,
+        priority: Priority,
+    },
 }
 
 // === impl DownloadRequest ===
@@ -378,6 +581,7 @@ impl DownloadRequest {
         match self {
             DownloadRequest::GetBlockHeaders { .. } => PeerState::GetBlockHeaders,
             DownloadRequest::GetBlockBodies { .. } => PeerState::GetBlockBodies,
+            DownloadRequest::GetAccountRange { .. } => PeerState::GetAccountRange,
         }
     }
 
@@ -385,6 +589,30 @@ impl DownloadRequest {
         match self {
             DownloadRequest::GetBlockHeaders { priority, .. } => priority,
             DownloadRequest::GetBlockBodies { priority, .. } => priority,
+            DownloadRequest::GetAccountRange { priority, .. } => priority,
+        }
+    }
+
+    /// Returns `true` if the request can only be sent to peers that support `snap`.
+    fn is_snap_request(&self) -> bool {
+        matches!(self, DownloadRequest::GetAccountRange { .. })
+    }
+
+    /// Returns the kind of traffic and the RLP encoded size of the request that is sent to the
+    /// peer.
+    fn encoded_size(&self) -> (SyncTraffic, usize) {
+        match self {
+            DownloadRequest::GetBlockHeaders { request, .. } => {
+                let HeadersRequest { start, limit, direction } = request.clone();
+                let request = GetBlockHeaders { start_block: start, limit, skip: 0, direction };
+                (SyncTraffic::Headers, request.length())
+            }
+            DownloadRequest::GetBlockBodies { request, .. } => {
+                (SyncTraffic::Bodies, reth_rlp::list_length::<H256, _>(request))
+            }
+            DownloadRequest::GetAccountRange { request, .. } => {
+                (SyncTraffic::State, request.length())
+            }
         }
     }
 }
@@ -415,6 +643,7 @@ pub(crate) enum BlockResponseOutcome {
 mod tests {
     use super::*;
     use crate::{peers::PeersManager, PeersConfig};
+    use reth_interfaces::p2p::download::DownloadClient;
     use reth_primitives::{SealedHeader, H256, H512};
     use std::future::poll_fn;
 
@@ -438,6 +667,37 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_throttled_requests() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let config = SyncBandwidthConfig { max_bytes_per_sec: 1_000, ..Default::default() };
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default())
+            .with_bandwidth_limit(Some(config));
+        for _ in 0..2 {
+            fetcher.new_active_peer(H512::random(), H256::random(), 1, Arc::new(AtomicU64::new(1)));
+        }
+
+        // the bodies share of 500 bytes per second fits a single request for 10 bodies
+        for _ in 0..2 {
+            let (tx, _rx) = oneshot::channel();
+            fetcher.queued_requests.push_back(DownloadRequest::GetBlockBodies {
+                request: vec![H256::random(); 10],
+                response: tx,
+                priority: Priority::default(),
+            });
+        }
+
+        poll_fn(move |cx| {
+            assert!(fetcher.poll(cx).is_ready());
+            assert!(fetcher.poll(cx).is_pending());
+            assert!(fetcher.throttle.is_some());
+            assert_eq!(fetcher.queued_requests.len(), 1);
+
+            Poll::Ready(())
+        })
+        .await;
+    }
+
     #[tokio::test]
     async fn test_peer_rotation() {
         let manager = PeersManager::new(PeersConfig::default());
@@ -486,6 +746,83 @@ mod tests {
         assert_eq!(fetcher.next_peer(), Some(peer2));
     }
 
+    #[tokio::test]
+    async fn test_peer_rotation_after_bad_response() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer1 = H512::random();
+        let peer2 = H512::random();
+
+        fetcher.new_active_peer(peer1, H256::random(), 1, Arc::new(AtomicU64::new(10)));
+        fetcher.new_active_peer(peer2, H256::random(), 2, Arc::new(AtomicU64::new(20)));
+        assert_eq!(fetcher.next_peer(), Some(peer1));
+
+        // peer1 sent a bad response, the request is retried with peer2
+        fetcher.client().report_bad_message(peer1);
+        poll_fn(|cx| {
+            assert!(fetcher.poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        assert_eq!(fetcher.next_peer(), Some(peer2));
+
+        // peer1 is still used if it's the only idle peer
+        fetcher.on_pending_disconnect(&peer2);
+        assert_eq!(fetcher.next_peer(), Some(peer1));
+    }
+
+    #[tokio::test]
+    async fn test_account_range_requests_use_snap_peers() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let eth_peer = H512::random();
+        let snap_peer = H512::random();
+        fetcher.new_active_peer(eth_peer, H256::random(), 1, Arc::new(AtomicU64::new(10)));
+
+        let (tx, _rx) = oneshot::channel();
+        fetcher.queued_requests.push_back(DownloadRequest::GetAccountRange {
+            request: GetAccountRange::default(),
+            response: tx,
+            priority: Priority::default(),
+        });
+        let (tx, _rx) = oneshot::channel();
+        fetcher.queued_requests.push_back(DownloadRequest::GetBlockBodies {
+            request: vec![],
+            response: tx,
+            priority: Priority::default(),
+        });
+
+        poll_fn(|cx| {
+            // the bodies request is not held up while no peer supports snap
+            let Poll::Ready(FetchAction::BlockRequest { peer_id, request }) = fetcher.poll(cx)
+            else {
+                panic!("expected a request")
+            };
+            assert_eq!(peer_id, eth_peer);
+            assert!(matches!(request, BlockRequest::GetBlockBodies(_)));
+            assert!(fetcher.poll(cx).is_pending());
+
+            fetcher.new_active_peer(snap_peer, H256::random(), 1, Arc::new(AtomicU64::new(20)));
+            fetcher.set_snap_supported(&snap_peer);
+            let Poll::Ready(FetchAction::BlockRequest { peer_id, request }) = fetcher.poll(cx)
+            else {
+                panic!("expected a request")
+            };
+            assert_eq!(peer_id, snap_peer);
+            assert!(matches!(request, BlockRequest::GetAccountRange(_)));
+
+            Poll::Ready(())
+        })
+        .await;
+
+        // the peer is no longer used for snap requests if it doesn't share the capability
+        let outcome =
+            fetcher.on_account_range_response(snap_peer, Err(RequestError::UnsupportedCapability));
+        assert_eq!(outcome, None);
+        assert!(!fetcher.peers[&snap_peer].supports_snap);
+        assert!(fetcher.inflight_account_range_requests.is_empty());
+    }
+
     #[tokio::test]
     async fn test_on_block_headers_response() {
         let manager = PeersManager::new(PeersConfig::default());
@@ -129,19 +129,24 @@ mod manager;
 mod message;
 mod metrics;
 mod network;
+mod node_data;
+mod peer_activity;
 pub mod peers;
+mod seen_transactions;
 mod session;
+pub mod snap;
 mod state;
 mod swarm;
 pub mod transactions;
 
 pub use builder::NetworkBuilder;
 pub use config::{NetworkConfig, NetworkConfigBuilder};
-pub use fetch::FetchClient;
+pub use fetch::{FetchClient, SyncBandwidthConfig};
 pub use manager::{NetworkEvent, NetworkManager};
 pub use message::PeerRequest;
 pub use network::NetworkHandle;
+pub use peer_activity::DEFAULT_PEER_INACTIVITY_TIMEOUT;
 pub use peers::PeersConfig;
-pub use session::PeerInfo;
+pub use session::{ConnectionRateLimitConfig, PeerInfo, SessionsConfig};
 
 pub use reth_eth_wire::DisconnectReason;
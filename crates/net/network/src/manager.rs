@@ -25,7 +25,7 @@ use crate::{
     message::{NewBlockMessage, PeerMessage, PeerRequest, PeerRequestSender},
     metrics::NetworkMetrics,
     network::{NetworkHandle, NetworkHandleMessage},
-    peers::{PeersHandle, PeersManager},
+    peers::{PeersHandle, PeersManager, PersistedBans},
     session::SessionManager,
     state::NetworkState,
     swarm::{NetworkConnectionState, Swarm, SwarmEvent},
@@ -36,6 +36,7 @@ use futures::{Future, StreamExt};
 use parking_lot::Mutex;
 use reth_eth_wire::{
     capability::{Capabilities, CapabilityMessage},
+    snap::AccountRange,
     DisconnectReason, EthVersion, Status,
 };
 use reth_net_common::bandwidth_meter::BandwidthMeter;
@@ -154,6 +155,8 @@ where
             listener_addr,
             peers_config,
             sessions_config,
+            sync_bandwidth_config,
+            peer_inactivity_timeout,
             chain_spec,
             block_import,
             network_mode,
@@ -204,6 +207,8 @@ where
             peers_manager,
             chain_spec.genesis_hash(),
             Arc::clone(&num_active_peers),
+            sync_bandwidth_config,
+            peer_inactivity_timeout,
         );
 
         let swarm = Swarm::new(incoming, sessions, state, NetworkConnectionState::default());
@@ -299,6 +304,13 @@ where
         self.swarm.state().peers().iter_peers()
     }
 
+    /// Returns the temporary bans that are persisted across restarts.
+    ///
+    /// Peers that are only in time out, e.g. because they had too many peers, are not included.
+    pub fn persisted_bans(&self) -> PersistedBans {
+        self.swarm.state().peers().persisted_bans()
+    }
+
     /// Returns a new [`PeersHandle`] that can be cloned and shared.
     ///
     /// The [`PeersHandle`] can be used to interact with the network's peer set.
@@ -399,6 +411,11 @@ where
                     response,
                 });
             }
+            PeerRequest::GetAccountRange { response, .. } => {
+                // serving the state is not supported yet, an empty range tells the peer that the
+                // state of the requested root is not available
+                let _ = response.send(Ok(AccountRange::default()));
+            }
         }
     }
 
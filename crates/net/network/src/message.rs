@@ -5,10 +5,12 @@
 
 use futures::FutureExt;
 use reth_eth_wire::{
-    capability::RawCapabilityMessage, message::RequestPair, BlockBodies, BlockBody, BlockHeaders,
-    EthMessage, GetBlockBodies, GetBlockHeaders, GetNodeData, GetPooledTransactions, GetReceipts,
-    NewBlock, NewBlockHashes, NewPooledTransactionHashes, NodeData, PooledTransactions, Receipts,
-    SharedTransactions, Transactions,
+    capability::{CapabilityMessage, RawCapabilityMessage},
+    message::RequestPair,
+    snap::{AccountRange, GetAccountRange, SnapMessage},
+    BlockBodies, BlockBody, BlockHeaders, EthMessage, GetBlockBodies, GetBlockHeaders, GetNodeData,
+    GetPooledTransactions, GetReceipts, NewBlock, NewBlockHashes, NewPooledTransactionHashes,
+    NodeData, PooledTransactions, Receipts, SharedTransactions, Transactions,
 };
 use reth_interfaces::p2p::error::{RequestError, RequestResult};
 use reth_primitives::{Bytes, Header, PeerId, Receipt, TransactionSigned, H256};
@@ -51,20 +53,21 @@ pub enum PeerMessage {
     SendTransactions(SharedTransactions),
     /// Send new pooled transactions
     PooledTransactions(NewPooledTransactionHashes),
-    /// All `eth` request variants.
+    /// All `eth` and `snap` request variants.
     EthRequest(PeerRequest),
     /// Other than eth namespace message
     #[allow(unused)]
     Other(RawCapabilityMessage),
 }
 
-/// Request Variants that only target block related data.
+/// Request Variants that only target block related data, or the state for syncing.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[allow(missing_docs)]
 #[allow(clippy::enum_variant_names)]
 pub enum BlockRequest {
     GetBlockHeaders(GetBlockHeaders),
     GetBlockBodies(GetBlockBodies),
+    GetAccountRange(GetAccountRange),
 }
 
 /// Protocol related request messages that expect a response
@@ -100,6 +103,13 @@ pub enum PeerRequest {
     ///
     /// The response should be sent through the channel.
     GetReceipts { request: GetReceipts, response: oneshot::Sender<RequestResult<Receipts>> },
+    /// Request a range of accounts from the peer over the `snap` protocol.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        request: GetAccountRange,
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    },
 }
 
 // === impl PeerRequest ===
@@ -118,12 +128,18 @@ impl PeerRequest {
             PeerRequest::GetPooledTransactions { response, .. } => response.send(Err(err)).ok(),
             PeerRequest::GetNodeData { response, .. } => response.send(Err(err)).ok(),
             PeerRequest::GetReceipts { response, .. } => response.send(Err(err)).ok(),
+            PeerRequest::GetAccountRange { response, .. } => response.send(Err(err)).ok(),
         };
     }
 
-    /// Returns the [`EthMessage`] for this type
-    pub fn create_request_message(&self, request_id: u64) -> EthMessage {
-        match self {
+    /// Returns `true` if this is a `snap` request.
+    pub fn is_snap_request(&self) -> bool {
+        matches!(self, PeerRequest::GetAccountRange { .. })
+    }
+
+    /// Returns the [`CapabilityMessage`] for this type
+    pub fn create_request_message(&self, request_id: u64) -> CapabilityMessage {
+        let message = match self {
             PeerRequest::GetBlockHeaders { request, .. } => {
                 EthMessage::GetBlockHeaders(RequestPair { request_id, message: *request })
             }
@@ -142,7 +158,15 @@ impl PeerRequest {
             PeerRequest::GetReceipts { request, .. } => {
                 EthMessage::GetReceipts(RequestPair { request_id, message: request.clone() })
             }
-        }
+            PeerRequest::GetAccountRange { request, .. } => {
+                // snap messages carry the request id of the session in the message itself
+                return CapabilityMessage::Snap(SnapMessage::GetAccountRange(GetAccountRange {
+                    request_id,
+                    ..request.clone()
+                }))
+            }
+        };
+        CapabilityMessage::Eth(message)
     }
 }
 
@@ -154,6 +178,7 @@ pub enum PeerResponse {
     PooledTransactions { response: oneshot::Receiver<RequestResult<PooledTransactions>> },
     NodeData { response: oneshot::Receiver<RequestResult<NodeData>> },
     Receipts { response: oneshot::Receiver<RequestResult<Receipts>> },
+    AccountRange { response: oneshot::Receiver<RequestResult<AccountRange>> },
 }
 
 // === impl PeerResponse ===
@@ -186,6 +211,10 @@ impl PeerResponse {
             PeerResponse::Receipts { response } => {
                 poll_request!(response, Receipts, cx)
             }
+            PeerResponse::AccountRange { response } => match ready!(response.poll_unpin(cx)) {
+                Ok(res) => PeerResponseResult::AccountRange(res),
+                Err(err) => PeerResponseResult::AccountRange(Err(err.into())),
+            },
         };
         Poll::Ready(res)
     }
@@ -200,19 +229,20 @@ pub enum PeerResponseResult {
     PooledTransactions(RequestResult<Vec<TransactionSigned>>),
     NodeData(RequestResult<Vec<Bytes>>),
     Receipts(RequestResult<Vec<Vec<Receipt>>>),
+    AccountRange(RequestResult<AccountRange>),
 }
 
 // === impl PeerResponseResult ===
 
 impl PeerResponseResult {
-    /// Converts this response into an [`EthMessage`]
-    pub fn try_into_message(self, id: u64) -> RequestResult<EthMessage> {
+    /// Converts this response into a [`CapabilityMessage`]
+    pub fn try_into_message(self, id: u64) -> RequestResult<CapabilityMessage> {
         macro_rules! to_message {
             ($response:ident, $item:ident, $request_id:ident) => {
                 match $response {
                     Ok(res) => {
                         let request = RequestPair { request_id: $request_id, message: $item(res) };
-                        Ok(EthMessage::$item(request))
+                        Ok(CapabilityMessage::Eth(EthMessage::$item(request)))
                     }
                     Err(err) => Err(err),
                 }
@@ -234,6 +264,12 @@ impl PeerResponseResult {
             PeerResponseResult::Receipts(resp) => {
                 to_message!(resp, Receipts, id)
             }
+            PeerResponseResult::AccountRange(resp) => resp.map(|resp| {
+                CapabilityMessage::Snap(SnapMessage::AccountRange(AccountRange {
+                    request_id: id,
+                    ..resp
+                }))
+            }),
         }
     }
 
@@ -245,6 +281,7 @@ impl PeerResponseResult {
             PeerResponseResult::PooledTransactions(res) => res.as_ref().err(),
             PeerResponseResult::NodeData(res) => res.as_ref().err(),
             PeerResponseResult::Receipts(res) => res.as_ref().err(),
+            PeerResponseResult::AccountRange(res) => res.as_ref().err(),
         }
     }
 
@@ -257,6 +294,7 @@ impl PeerResponseResult {
             PeerResponseResult::PooledTransactions(res) => res.is_err(),
             PeerResponseResult::NodeData(res) => res.is_err(),
             PeerResponseResult::Receipts(res) => res.is_err(),
+            PeerResponseResult::AccountRange(res) => res.is_err(),
         }
     }
 }
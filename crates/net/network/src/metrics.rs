@@ -39,4 +39,6 @@ pub struct NetworkMetrics {
 pub struct TransactionsManagerMetrics {
     /// Total number of propagated transactions
     pub(crate) propagated_transactions: Counter,
+    /// Total number of announced transactions that were recently received and not fetched again
+    pub(crate) duplicate_announcements: Counter,
 }
@@ -0,0 +1,132 @@
+//! Serving of `GetNodeData` requests.
+
+use reth_eth_wire::{GetNodeData, NodeData};
+use reth_interfaces::p2p::error::RequestResult;
+use reth_primitives::{Bytes, PeerId};
+use reth_provider::NodeDataProvider;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
+use tracing::trace;
+
+/// Maximum number of trie nodes to serve per request.
+const MAX_NODE_DATA_SERVE: usize = 256;
+
+/// Maximum number of `GetNodeData` requests a single peer is served per second.
+const MAX_NODE_DATA_REQUESTS_PER_SEC: u32 = 10;
+
+/// Maximum size of a `NodeData` response.
+const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
+
+/// How long a rate limit window lasts.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(1);
+
+/// Serves the trie nodes and contract code requested by light clients via `GetNodeData`.
+///
+/// Hashes that are not found are omitted from the response. Every peer is only served
+/// [`MAX_NODE_DATA_REQUESTS_PER_SEC`] requests per second, so that light client traffic doesn't
+/// slow down the node, further requests are answered with an empty response.
+#[derive(Debug, Default)]
+pub(crate) struct NodeDataHandler {
+    /// The start of the current rate limit window and the number of requests served in it, per
+    /// peer.
+    windows: HashMap<PeerId, (Instant, u32)>,
+}
+
+// === impl NodeDataHandler ===
+
+impl NodeDataHandler {
+    /// Responds to a `GetNodeData` request of the peer.
+    pub(crate) fn on_request<C: NodeDataProvider>(
+        &mut self,
+        client: &C,
+        peer_id: PeerId,
+        request: GetNodeData,
+        response: oneshot::Sender<RequestResult<NodeData>>,
+    ) {
+        let nodes = if self.try_acquire(peer_id, Instant::now()) {
+            get_node_data(client, request)
+        } else {
+            trace!(target: "net::eth", ?peer_id, "Rate limited GetNodeData request");
+            Vec::new()
+        };
+        let _ = response.send(Ok(NodeData(nodes)));
+    }
+
+    /// Returns `true` if the peer may be served another request.
+    fn try_acquire(&mut self, peer_id: PeerId, now: Instant) -> bool {
+        // forget the peers whose window has passed
+        self.windows
+            .retain(|_, (start, _)| now.saturating_duration_since(*start) < RATE_LIMIT_WINDOW);
+
+        let (_, served) = self.windows.entry(peer_id).or_insert((now, 0));
+        if *served >= MAX_NODE_DATA_REQUESTS_PER_SEC {
+            return false
+        }
+        *served += 1;
+        true
+    }
+}
+
+/// Returns the raw bytes of the requested nodes that are found.
+fn get_node_data<C: NodeDataProvider>(client: &C, request: GetNodeData) -> Vec<Bytes> {
+    let mut nodes = Vec::new();
+    let mut total_bytes = 0;
+
+    for hash in request.0.into_iter().take(MAX_NODE_DATA_SERVE) {
+        let Some(node) = client.node_data(hash).unwrap_or_default() else { continue };
+        total_bytes += node.len();
+        nodes.push(node);
+
+        if total_bytes > SOFT_RESPONSE_LIMIT {
+            break
+        }
+    }
+
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{keccak256, H256};
+
+    #[derive(Default)]
+    struct TestNodes(HashMap<H256, Bytes>);
+
+    impl NodeDataProvider for TestNodes {
+        fn node_data(&self, hash: H256) -> reth_interfaces::Result<Option<Bytes>> {
+            Ok(self.0.get(&hash).cloned())
+        }
+    }
+
+    #[test]
+    fn omit_missing_nodes() {
+        let node = Bytes::from(vec![0xc0]);
+        let hash = keccak256(&node);
+        let client = TestNodes(HashMap::from([(hash, node.clone())]));
+
+        let request = GetNodeData(vec![H256::random(), hash, H256::random()]);
+        assert_eq!(get_node_data(&client, request), vec![node.clone()]);
+
+        let request = GetNodeData(vec![hash; MAX_NODE_DATA_SERVE + 1]);
+        assert_eq!(get_node_data(&client, request).len(), MAX_NODE_DATA_SERVE);
+    }
+
+    #[test]
+    fn rate_limit_requests() {
+        let mut handler = NodeDataHandler::default();
+        let (peer, other) = (PeerId::random(), PeerId::random());
+        let now = Instant::now();
+
+        for _ in 0..MAX_NODE_DATA_REQUESTS_PER_SEC {
+            assert!(handler.try_acquire(peer, now));
+        }
+        assert!(!handler.try_acquire(peer, now + Duration::from_millis(500)));
+        assert!(handler.try_acquire(other, now));
+
+        assert!(handler.try_acquire(peer, now + RATE_LIMIT_WINDOW));
+    }
+}
@@ -0,0 +1,154 @@
+//! Detection of stale peers that stopped responding to requests.
+
+use reth_primitives::PeerId;
+use std::{
+    collections::HashMap,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::time::Interval;
+
+/// Default time after which a peer with an active request that didn't send any message is
+/// considered stale.
+pub const DEFAULT_PEER_INACTIVITY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often peers are checked for inactivity, at most.
+const INACTIVITY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tracks when messages were last received from the connected peers.
+///
+/// A peer is stale if it has an active request, but neither responded nor sent any other message
+/// within the inactivity timeout since the request was sent. Such a peer keeps the connection
+/// open but blocks the request until it is timed out by the session, so it is replaced instead.
+#[derive(Debug)]
+pub(crate) struct PeerActivityMonitor {
+    /// The activity of all connected peers.
+    peers: HashMap<PeerId, PeerActivity>,
+    /// Time without any message after which a peer with an active request is stale.
+    inactivity_timeout: Duration,
+    /// Interval at which the peers are checked for inactivity.
+    check_interval: Interval,
+}
+
+// === impl PeerActivityMonitor ===
+
+impl PeerActivityMonitor {
+    /// Creates a new monitor that considers peers stale after the given timeout.
+    pub(crate) fn new(inactivity_timeout: Duration) -> Self {
+        let period =
+            INACTIVITY_CHECK_INTERVAL.min(inactivity_timeout).max(Duration::from_millis(1));
+        Self {
+            peers: Default::default(),
+            inactivity_timeout,
+            check_interval: tokio::time::interval(period),
+        }
+    }
+
+    /// Invoked when a session with the peer was established.
+    pub(crate) fn on_session_activated(&mut self, peer_id: PeerId, now: Instant) {
+        self.peers.insert(peer_id, PeerActivity { last_message: now, request_sent: None });
+    }
+
+    /// Invoked when the session with the peer was closed.
+    pub(crate) fn on_session_closed(&mut self, peer_id: &PeerId) {
+        self.peers.remove(peer_id);
+    }
+
+    /// Invoked when a message was received from the peer.
+    pub(crate) fn on_message(&mut self, peer_id: &PeerId, now: Instant) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.last_message = now;
+        }
+    }
+
+    /// Invoked when a request was sent to the peer.
+    pub(crate) fn on_request_sent(&mut self, peer_id: &PeerId, now: Instant) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.request_sent = Some(now);
+        }
+    }
+
+    /// Invoked when the response to the active request was received from the peer.
+    pub(crate) fn on_response(&mut self, peer_id: &PeerId, now: Instant) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.last_message = now;
+            peer.request_sent = None;
+        }
+    }
+
+    /// Returns the peers that became stale.
+    ///
+    /// The active requests of the returned peers are no longer tracked, so every stale request is
+    /// only reported once.
+    pub(crate) fn take_stale_peers(&mut self, now: Instant) -> Vec<PeerId> {
+        let mut stale = Vec::new();
+        for (peer_id, peer) in self.peers.iter_mut() {
+            let Some(request_sent) = peer.request_sent else { continue };
+            let last_activity = request_sent.max(peer.last_message);
+            if now.saturating_duration_since(last_activity) >= self.inactivity_timeout {
+                peer.request_sent = None;
+                stale.push(*peer_id);
+            }
+        }
+        stale
+    }
+
+    /// Returns the peers that became stale since the last check, once it's time for the next
+    /// check.
+    pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<Vec<PeerId>> {
+        if self.check_interval.poll_tick(cx).is_pending() {
+            return Poll::Pending
+        }
+        let stale = self.take_stale_peers(Instant::now());
+        if stale.is_empty() {
+            // register the waker for the next tick
+            let _ = self.check_interval.poll_tick(cx);
+            return Poll::Pending
+        }
+        Poll::Ready(stale)
+    }
+}
+
+/// The activity of a connected peer.
+#[derive(Debug)]
+struct PeerActivity {
+    /// When the last message was received from the peer.
+    last_message: Instant,
+    /// When the active request was sent to the peer, if any.
+    request_sent: Option<Instant>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn detect_stale_peers() {
+        let timeout = Duration::from_secs(30);
+        let mut monitor = PeerActivityMonitor::new(timeout);
+        let now = Instant::now();
+
+        let (stale, busy, idle) = (PeerId::random(), PeerId::random(), PeerId::random());
+        for peer_id in [stale, busy, idle] {
+            monitor.on_session_activated(peer_id, now);
+        }
+        monitor.on_request_sent(&stale, now);
+        monitor.on_request_sent(&busy, now);
+        monitor.on_message(&busy, now + Duration::from_secs(20));
+
+        assert!(monitor.take_stale_peers(now + Duration::from_secs(29)).is_empty());
+        assert_eq!(monitor.take_stale_peers(now + timeout), vec![stale]);
+        // reported only once
+        assert!(monitor.take_stale_peers(now + timeout).is_empty());
+
+        // the idle peer has no active request
+        let later = now + Duration::from_secs(60);
+        assert_eq!(monitor.take_stale_peers(later), vec![busy]);
+
+        // a request to a peer that was idle for a long time is tracked from when it was sent
+        monitor.on_request_sent(&idle, later);
+        assert!(monitor.take_stale_peers(later + Duration::from_secs(1)).is_empty());
+        monitor.on_response(&idle, later + Duration::from_secs(1));
+        assert!(monitor.take_stale_peers(later + timeout * 2).is_empty());
+    }
+}
@@ -1,8 +1,12 @@
 use crate::{
     error::{BackoffKind, SessionError},
     peers::{
-        reputation::{is_banned_reputation, BACKOFF_REPUTATION_CHANGE, DEFAULT_REPUTATION},
-        ReputationChangeWeights, DEFAULT_MAX_PEERS_INBOUND, DEFAULT_MAX_PEERS_OUTBOUND,
+        reputation::{
+            is_banned_reputation, BACKOFF_REPUTATION_CHANGE, DEFAULT_REPUTATION, MAX_REPUTATION,
+            MIN_REPUTATION,
+        },
+        PersistedBans, ReputationChangeWeights, ReputationDecay, DEFAULT_MAX_PEERS_INBOUND,
+        DEFAULT_MAX_PEERS_OUTBOUND,
     },
     session::{Direction, PendingSessionHandshakeError},
 };
@@ -93,10 +97,16 @@ pub(crate) struct PeersManager {
     refill_slots_interval: Interval,
     /// How to weigh reputation changes
     reputation_weights: ReputationChangeWeights,
+    /// How the reputation of peers is restored over time.
+    reputation_decay: ReputationDecay,
+    /// Interval at which the reputation of peers decays.
+    reputation_decay_interval: Interval,
     /// Tracks current slot stats.
     connection_info: ConnectionInfo,
     /// Tracks unwanted ips/peer ids,
     ban_list: BanList,
+    /// Peers of the ban list that are only in time out and not banned.
+    backed_off_peers: HashSet<PeerId>,
     /// Interval at which to check for peers to unban.
     unban_interval: Interval,
     /// How long to ban bad peers.
@@ -115,6 +125,7 @@ impl PeersManager {
             refill_slots_interval,
             connection_info,
             reputation_weights,
+            reputation_decay,
             ban_list,
             ban_duration,
             backoff_durations,
@@ -145,6 +156,11 @@ impl PeersManager {
             handle_rx: UnboundedReceiverStream::new(handle_rx),
             queued_actions: Default::default(),
             reputation_weights,
+            reputation_decay,
+            reputation_decay_interval: tokio::time::interval_at(
+                now + reputation_decay.interval,
+                reputation_decay.interval,
+            ),
             refill_slots_interval: tokio::time::interval_at(
                 now + refill_slots_interval,
                 refill_slots_interval,
@@ -152,6 +168,7 @@ impl PeersManager {
             unban_interval: tokio::time::interval_at(now + unban_interval, unban_interval),
             connection_info,
             ban_list,
+            backed_off_peers: Default::default(),
             ban_duration,
             backoff_durations,
             connect_trusted_nodes_only,
@@ -169,6 +186,14 @@ impl PeersManager {
         self.peers.len()
     }
 
+    /// Returns the temporary bans that should be persisted, without the peers that are only in
+    /// time out.
+    pub(crate) fn persisted_bans(&self) -> PersistedBans {
+        let mut bans = PersistedBans::from_ban_list(&self.ban_list);
+        bans.peers.retain(|peer_id, _| !self.backed_off_peers.contains(peer_id));
+        bans
+    }
+
     /// Returns an iterator over all peers
     pub(crate) fn iter_peers(&self) -> impl Iterator<Item = NodeRecord> + '_ {
         self.peers.iter().map(|(peer_id, v)| NodeRecord::new(v.addr, *peer_id))
@@ -267,6 +292,7 @@ impl PeersManager {
     /// Bans the peer temporarily with the configured ban timeout
     fn ban_peer(&mut self, peer_id: PeerId) {
         self.ban_list.ban_peer_until(peer_id, std::time::Instant::now() + self.ban_duration);
+        self.backed_off_peers.remove(&peer_id);
         self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
     }
 
@@ -280,11 +306,13 @@ impl PeersManager {
         trace!(target: "net::peers", ?peer_id, "backing off");
 
         self.ban_list.ban_peer_until(peer_id, until);
+        self.backed_off_peers.insert(peer_id);
     }
 
     /// Unbans the peer
     fn unban_peer(&mut self, peer_id: PeerId) {
         self.ban_list.unban_peer(&peer_id);
+        self.backed_off_peers.remove(&peer_id);
         self.queued_actions.push_back(PeerAction::UnBanPeer { peer_id });
     }
 
@@ -313,6 +341,17 @@ impl PeersManager {
         }
     }
 
+    /// Restores the reputation of all peers that are not banned towards the default reputation.
+    ///
+    /// Banned peers are unbanned by the [`BanList`], which also resets their reputation.
+    fn decay_reputations(&mut self) {
+        for peer in self.peers.values_mut() {
+            if !peer.is_banned() {
+                peer.reputation = self.reputation_decay.decay(peer.reputation);
+            }
+        }
+    }
+
     /// Gracefully disconnected a pending session
     pub(crate) fn on_pending_session_gracefully_closed(&mut self, peer_id: &PeerId) {
         if let Some(mut peer) = self.peers.get_mut(peer_id) {
@@ -432,7 +471,10 @@ impl PeersManager {
 
                 self.connection_info.decr_state(peer.state);
                 peer.state = PeerConnectionState::Idle;
-                peer.reputation = peer.reputation.saturating_add(reputation_change.as_i32());
+                peer.reputation = peer
+                    .reputation
+                    .saturating_add(reputation_change.as_i32())
+                    .clamp(MIN_REPUTATION, MAX_REPUTATION);
             }
             if let Some(backoff_until) = backoff_until {
                 self.backoff_peer_until(*peer_id, backoff_until);
@@ -660,6 +702,7 @@ impl PeersManager {
                 let (_, unbanned_peers) = self.ban_list.evict(std::time::Instant::now());
 
                 for peer_id in unbanned_peers {
+                    self.backed_off_peers.remove(&peer_id);
                     if let Some(peer) = self.peers.get_mut(&peer_id) {
                         peer.unban();
                     } else {
@@ -669,6 +712,10 @@ impl PeersManager {
                 }
             }
 
+            if self.reputation_decay_interval.poll_tick(cx).is_ready() {
+                self.decay_reputations();
+            }
+
             if self.refill_slots_interval.poll_tick(cx).is_ready() {
                 self.fill_outbound_slots();
             }
@@ -801,7 +848,7 @@ impl Peer {
     fn apply_reputation(&mut self, reputation: i32) -> ReputationChangeOutcome {
         let previous = self.reputation;
         // we add reputation since negative reputation change decrease total reputation
-        self.reputation = previous.saturating_add(reputation);
+        self.reputation = previous.saturating_add(reputation).clamp(MIN_REPUTATION, MAX_REPUTATION);
 
         trace!(target: "net::peers", repuation=%self.reputation, banned=%self.is_banned(), "applied reputation change");
 
@@ -966,6 +1013,9 @@ pub struct PeersConfig {
     pub connection_info: ConnectionInfo,
     /// How to weigh reputation changes.
     pub reputation_weights: ReputationChangeWeights,
+    /// How the reputation of peers is restored over time.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reputation_decay: ReputationDecay,
     /// How long to backoff peers that are we failed to connect to for non-fatal reasons, such as
     /// [`DisconnectReason::TooManyPeers`].
     pub backoff_durations: PeerBackoffDurations,
@@ -977,9 +1027,10 @@ impl Default for PeersConfig {
             refill_slots_interval: Duration::from_millis(1_000),
             connection_info: Default::default(),
             reputation_weights: Default::default(),
+            reputation_decay: Default::default(),
             ban_list: Default::default(),
-            // Ban peers for 12h
-            ban_duration: Duration::from_secs(60 * 60 * 12),
+            // Ban peers for 1h
+            ban_duration: Duration::from_secs(60 * 60),
             backoff_durations: Default::default(),
             trusted_nodes: Default::default(),
             connect_trusted_nodes_only: false,
@@ -995,6 +1046,24 @@ impl PeersConfig {
         self
     }
 
+    /// How long to ban bad peers.
+    pub fn with_ban_duration(mut self, ban_duration: Duration) -> Self {
+        self.ban_duration = ban_duration;
+        self
+    }
+
+    /// How the reputation of peers is restored over time.
+    pub fn with_reputation_decay(mut self, reputation_decay: ReputationDecay) -> Self {
+        self.reputation_decay = reputation_decay;
+        self
+    }
+
+    /// Ban peers after they sent the given number of bad messages, blocks or transactions.
+    pub fn with_max_bad_messages(mut self, max_bad_messages: u32) -> Self {
+        self.reputation_weights = self.reputation_weights.with_max_bad_messages(max_bad_messages);
+        self
+    }
+
     /// Maximum occupied slots for outbound connections.
     pub fn with_max_pending_outbound(mut self, num_outbound: usize) -> Self {
         self.connection_info.num_outbound = num_outbound;
@@ -1052,6 +1121,22 @@ impl PeersConfig {
         let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
         Ok(self.with_basic_nodes(nodes))
     }
+
+    /// Read from file the bans that were persisted on shutdown and add them to the ban list.
+    /// Ignored if None.
+    pub fn with_ban_list_from_file(
+        mut self,
+        optional_file: Option<impl AsRef<Path>>,
+    ) -> Result<Self, io::Error> {
+        let Some(file_path) = optional_file else {
+            return Ok(self)
+        };
+        if let Some(bans) = PersistedBans::load(file_path.as_ref())? {
+            info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading banned peers");
+            bans.apply_to(&mut self.ban_list);
+        }
+        Ok(self)
+    }
 }
 
 /// The durations to use when a backoff should be applied to a peer.
@@ -1122,7 +1207,8 @@ mod test {
         error::BackoffKind,
         peers::{
             manager::{ConnectionInfo, PeerBackoffDurations, PeerConnectionState},
-            PeerAction,
+            reputation::DEFAULT_REPUTATION,
+            PeerAction, ReputationDecay,
         },
         session::PendingSessionHandshakeError,
         PeersConfig,
@@ -1290,6 +1376,8 @@ mod test {
 
         assert!(peers.ban_list.is_banned_peer(&peer));
         assert!(peers.peers.get(&peer).is_some());
+        // the time out is not persisted
+        assert!(peers.persisted_bans().peers.is_empty());
 
         tokio::time::sleep(backoff_durations.low).await;
 
@@ -1457,6 +1545,7 @@ mod test {
         .await;
 
         assert!(peers.peers.get(&peer).is_none());
+        assert!(peers.persisted_bans().peers.contains_key(&peer));
     }
 
     #[tokio::test]
@@ -1615,6 +1704,34 @@ mod test {
         }
     }
 
+    #[tokio::test]
+    async fn test_reputation_decay() {
+        let penalized = PeerId::random();
+        let banned = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let reputation_decay =
+            ReputationDecay { interval: Duration::from_millis(100), factor: 1.0 };
+        let config = PeersConfig::default().with_reputation_decay(reputation_decay);
+        let mut peers = PeersManager::new(config);
+        peers.add_peer(penalized, socket_addr, None);
+        peers.add_peer(banned, socket_addr, None);
+
+        peers.apply_reputation_change(&penalized, ReputationChangeKind::Timeout);
+        peers.apply_reputation_change(&banned, ReputationChangeKind::BadProtocol);
+        assert!(peers.peers[&penalized].reputation < DEFAULT_REPUTATION);
+
+        tokio::time::sleep(reputation_decay.interval).await;
+        poll_fn(|cx| {
+            let _ = peers.poll(cx);
+            Poll::Ready(())
+        })
+        .await;
+
+        assert_eq!(peers.peers[&penalized].reputation, DEFAULT_REPUTATION);
+        // banned peers are only restored once the ban expires
+        assert!(peers.peers[&banned].is_banned());
+    }
+
     #[tokio::test]
     async fn test_remove_discovered_active() {
         let peer = PeerId::random();
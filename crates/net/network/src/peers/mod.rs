@@ -1,11 +1,13 @@
 //! Peer related implementations
 
 mod manager;
+mod persisted_bans;
 mod reputation;
 
 pub(crate) use manager::{InboundConnectionError, PeerAction, PeersManager};
 pub use manager::{Peer, PeersConfig, PeersHandle};
-pub use reputation::ReputationChangeWeights;
+pub use persisted_bans::PersistedBans;
+pub use reputation::{ReputationChangeWeights, ReputationDecay};
 pub use reth_network_api::PeerKind;
 
 /// Maximum number of available slots for outbound sessions.
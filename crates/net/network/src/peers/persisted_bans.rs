@@ -0,0 +1,137 @@
+//! Persistence of temporary bans across node restarts.
+
+use reth_net_common::ban_list::BanList;
+use reth_primitives::PeerId;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io::{self, ErrorKind},
+    net::IpAddr,
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// The temporary bans of a [`BanList`], with the unix timestamps in seconds at which they expire.
+///
+/// Indefinite bans are not persisted, because they are configured on startup.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersistedBans {
+    /// Banned peers.
+    pub peers: HashMap<PeerId, u64>,
+    /// Banned ip addresses.
+    pub ips: HashMap<IpAddr, u64>,
+}
+
+// === impl PersistedBans ===
+
+impl PersistedBans {
+    /// Returns the temporary bans of the ban list.
+    pub fn from_ban_list(ban_list: &BanList) -> Self {
+        Self::from_ban_list_at(ban_list, Instant::now(), unix_now())
+    }
+
+    /// Adds the bans that did not expire yet to the ban list.
+    pub fn apply_to(&self, ban_list: &mut BanList) {
+        self.apply_to_at(ban_list, Instant::now(), unix_now())
+    }
+
+    /// Reads the bans from the file, if it exists.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Option<Self>> {
+        let data = match std::fs::read(path.as_ref()) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        Ok(Some(serde_json::from_slice(&data)?))
+    }
+
+    /// Writes the bans to the file, creating the parent directory if necessary.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+    }
+
+    fn from_ban_list_at(ban_list: &BanList, now: Instant, unix_now: Duration) -> Self {
+        let expires_at =
+            |until: Instant| (unix_now + until.saturating_duration_since(now)).as_secs();
+        Self {
+            peers: ban_list
+                .banned_peers()
+                .filter_map(|(peer, until)| Some((peer, expires_at(until?))))
+                .collect(),
+            ips: ban_list
+                .banned_ips()
+                .filter_map(|(ip, until)| Some((ip, expires_at(until?))))
+                .collect(),
+        }
+    }
+
+    fn apply_to_at(&self, ban_list: &mut BanList, now: Instant, unix_now: Duration) {
+        let until = |expires_at: u64| {
+            let remaining = Duration::from_secs(expires_at).checked_sub(unix_now)?;
+            (!remaining.is_zero()).then(|| now + remaining)
+        };
+        for (peer, expires_at) in &self.peers {
+            if let Some(until) = until(*expires_at) {
+                ban_list.ban_peer_until(*peer, until);
+            }
+        }
+        for (ip, expires_at) in &self.ips {
+            if let Some(until) = until(*expires_at) {
+                ban_list.ban_ip_until(*ip, until);
+            }
+        }
+    }
+}
+
+/// Returns the time since the unix epoch.
+fn unix_now() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_temporary_bans() {
+        let now = Instant::now();
+        let unix_now = Duration::from_secs(1_000_000);
+
+        let (peer, expired_peer, banned_forever) =
+            (PeerId::random(), PeerId::random(), PeerId::random());
+        let ip = IpAddr::from([1, 1, 1, 1]);
+        let mut ban_list = BanList::default();
+        ban_list.ban_peer_until(peer, now + Duration::from_secs(60));
+        ban_list.ban_peer_until(expired_peer, now + Duration::from_secs(10));
+        ban_list.ban_peer(banned_forever);
+        ban_list.ban_ip_until(ip, now + Duration::from_secs(3600));
+
+        let bans = PersistedBans::from_ban_list_at(&ban_list, now, unix_now);
+        assert_eq!(bans.peers.len(), 2);
+        assert_eq!(bans.peers[&peer], 1_000_060);
+        assert_eq!(bans.ips[&ip], 1_003_600);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("banned-peers.json");
+        assert_eq!(PersistedBans::load(&path).unwrap(), None);
+        bans.save(&path).unwrap();
+        let bans = PersistedBans::load(&path).unwrap().unwrap();
+
+        // restored after a restart 30 seconds later
+        let restarted = now + Duration::from_secs(5);
+        let mut ban_list = BanList::default();
+        bans.apply_to_at(&mut ban_list, restarted, unix_now + Duration::from_secs(30));
+        assert!(ban_list.is_banned_peer(&peer));
+        assert!(!ban_list.is_banned_peer(&expired_peer));
+        assert!(!ban_list.is_banned_peer(&banned_forever));
+        assert!(ban_list.is_banned_ip(&ip));
+
+        ban_list.evict(restarted + Duration::from_secs(31));
+        assert!(!ban_list.is_banned_peer(&peer));
+        assert!(ban_list.is_banned_ip(&ip));
+    }
+}
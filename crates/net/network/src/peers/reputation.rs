@@ -1,6 +1,7 @@
 //! Peer reputation management
 
 use reth_network_api::{Reputation, ReputationChangeKind};
+use std::time::Duration;
 
 /// The default reputation of a peer
 pub(crate) const DEFAULT_REPUTATION: Reputation = 0;
@@ -8,6 +9,12 @@ pub(crate) const DEFAULT_REPUTATION: Reputation = 0;
 /// The minimal unit we're measuring reputation
 const REPUTATION_UNIT: i32 = -1024;
 
+/// The lowest reputation a peer can have, e.g. after violating protocol rules.
+pub(crate) const MIN_REPUTATION: Reputation = i32::MIN;
+
+/// The highest reputation a peer can have.
+pub(crate) const MAX_REPUTATION: Reputation = 50 * -REPUTATION_UNIT;
+
 /// The reputation value below which new connection from/to peers are rejected.
 pub(crate) const BANNED_REPUTATION: i32 = 50 * REPUTATION_UNIT;
 
@@ -20,8 +27,8 @@ const FAILED_TO_CONNECT_REPUTATION_CHANGE: i32 = 25 * REPUTATION_UNIT;
 /// The reputation change to apply to a peer that failed to respond in time.
 const TIMEOUT_REPUTATION_CHANGE: i32 = 4 * REPUTATION_UNIT;
 
-/// The reputation change to apply to a peer that sent a bad message.
-const BAD_MESSAGE_REPUTATION_CHANGE: i32 = 16 * REPUTATION_UNIT;
+/// The number of bad messages, blocks or transactions after which a peer is banned.
+const DEFAULT_MAX_BAD_MESSAGES: u32 = 3;
 
 /// The reputation change to apply to a peer which violates protocol rules: minimal reputation
 const BAD_PROTOCOL_REPUTATION_CHANGE: i32 = i32::MIN;
@@ -36,6 +43,14 @@ pub(crate) fn is_banned_reputation(reputation: i32) -> bool {
     reputation < BANNED_REPUTATION
 }
 
+/// Returns the reputation change to apply to a peer that sent a bad message, so that a peer with
+/// the [`DEFAULT_REPUTATION`] is banned after `max_bad_messages` of them.
+fn bad_message_reputation_change(max_bad_messages: u32) -> Reputation {
+    let max_bad_messages = max_bad_messages.clamp(1, BANNED_REPUTATION.unsigned_abs()) as i32;
+    // the last message must push the reputation below the threshold
+    BANNED_REPUTATION / max_bad_messages - 1
+}
+
 /// How the [`ReputationChangeKind`] are weighted.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -73,14 +88,25 @@ impl ReputationChangeWeights {
             ReputationChangeKind::Other(val) => val.into(),
         }
     }
+
+    /// Returns the weights with which a peer is banned after the given number of bad messages,
+    /// blocks or transactions.
+    pub fn with_max_bad_messages(mut self, max_bad_messages: u32) -> Self {
+        let change = bad_message_reputation_change(max_bad_messages);
+        self.bad_message = change;
+        self.bad_block = change;
+        self.bad_transactions = change;
+        self
+    }
 }
 
 impl Default for ReputationChangeWeights {
     fn default() -> Self {
+        let bad_message = bad_message_reputation_change(DEFAULT_MAX_BAD_MESSAGES);
         Self {
-            bad_block: BAD_MESSAGE_REPUTATION_CHANGE,
-            bad_transactions: BAD_MESSAGE_REPUTATION_CHANGE,
-            bad_message: BAD_MESSAGE_REPUTATION_CHANGE,
+            bad_block: bad_message,
+            bad_transactions: bad_message,
+            bad_message,
             timeout: TIMEOUT_REPUTATION_CHANGE,
             bad_protocol: BAD_PROTOCOL_REPUTATION_CHANGE,
             failed_to_connect: FAILED_TO_CONNECT_REPUTATION_CHANGE,
@@ -89,6 +115,43 @@ impl Default for ReputationChangeWeights {
     }
 }
 
+/// How the reputation of peers is restored towards the [`DEFAULT_REPUTATION`] over time, so that
+/// peers that were penalized for transient issues can eventually reconnect.
+///
+/// Each interval the reputation moves by `(DEFAULT_REPUTATION - reputation) * factor`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReputationDecay {
+    /// How often the reputation of the peers decays.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub interval: Duration,
+    /// The fraction of the distance to the default reputation that is restored each interval,
+    /// between 0 and 1.
+    pub factor: f64,
+}
+
+// === impl ReputationDecay ===
+
+impl ReputationDecay {
+    /// Returns the reputation after one interval of decay.
+    pub(crate) fn decay(&self, reputation: Reputation) -> Reputation {
+        let factor = self.factor.clamp(0.0, 1.0);
+        let distance = DEFAULT_REPUTATION as i64 - reputation as i64;
+        let mut step = (distance as f64 * factor) as i64;
+        if step == 0 && factor > 0.0 {
+            // the remaining distance is too small to be decayed
+            step = distance;
+        }
+        (reputation as i64 + step).clamp(MIN_REPUTATION as i64, MAX_REPUTATION as i64) as i32
+    }
+}
+
+impl Default for ReputationDecay {
+    fn default() -> Self {
+        Self { interval: Duration::from_secs(10 * 60), factor: 0.1 }
+    }
+}
+
 /// Represents a change in a peer's reputation.
 #[derive(Debug, Copy, Clone, Default)]
 pub(crate) struct ReputationChange(Reputation);
@@ -114,3 +177,48 @@ impl From<Reputation> for ReputationChange {
         ReputationChange(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn banned_after_max_bad_messages() {
+        for max_bad_messages in [1, 3, 10] {
+            let weights =
+                ReputationChangeWeights::default().with_max_bad_messages(max_bad_messages);
+            let change = weights.change(ReputationChangeKind::BadBlock).as_i32();
+            let mut reputation = DEFAULT_REPUTATION;
+            for _ in 1..max_bad_messages {
+                reputation += change;
+                assert!(!is_banned_reputation(reputation));
+            }
+            assert!(is_banned_reputation(reputation + change));
+        }
+
+        let weights = ReputationChangeWeights::default();
+        let bad_block = weights.change(ReputationChangeKind::BadBlock).as_i32();
+        assert!(!is_banned_reputation(DEFAULT_REPUTATION + 2 * bad_block));
+        assert!(is_banned_reputation(DEFAULT_REPUTATION + 3 * bad_block));
+    }
+
+    #[test]
+    fn reputation_decays_to_default() {
+        let decay = ReputationDecay { factor: 0.5, ..Default::default() };
+        assert_eq!(decay.decay(-1000), -500);
+        assert_eq!(decay.decay(1000), 500);
+        assert_eq!(decay.decay(-1), DEFAULT_REPUTATION);
+        assert_eq!(decay.decay(DEFAULT_REPUTATION), DEFAULT_REPUTATION);
+        assert_eq!(decay.decay(MIN_REPUTATION), MIN_REPUTATION / 2);
+
+        let mut reputation = BANNED_REPUTATION;
+        let decay = ReputationDecay::default();
+        for _ in 0..200 {
+            reputation = decay.decay(reputation);
+        }
+        assert_eq!(reputation, DEFAULT_REPUTATION);
+
+        let disabled = ReputationDecay { factor: 0.0, ..Default::default() };
+        assert_eq!(disabled.decay(-1000), -1000);
+    }
+}
@@ -0,0 +1,134 @@
+//! Approximate tracking of recently seen transactions.
+
+use bloomfilter::Bloom;
+use reth_primitives::TxHash;
+use std::fmt;
+
+/// The default number of transactions a single generation of [`SeenTransactions`] holds.
+pub const DEFAULT_SEEN_TRANSACTIONS_CAPACITY: usize = 10_000;
+
+/// The default false positive rate of a single generation of [`SeenTransactions`].
+pub const DEFAULT_SEEN_TRANSACTIONS_FALSE_POSITIVE_RATE: f64 = 0.001;
+
+/// A rolling bloom filter of recently seen transaction hashes.
+///
+/// The filter consists of two generations: new hashes are inserted into the current generation,
+/// and once it holds `capacity` hashes it replaces the previous generation and a fresh one is
+/// started. A hash is considered seen if it is in either generation, so every hash is remembered
+/// for at least `capacity` insertions while the memory usage remains bounded.
+///
+/// Being a bloom filter, [`SeenTransactions::contains`] can return false positives, but never
+/// false negatives.
+pub struct SeenTransactions {
+    /// The generation new hashes are inserted into.
+    current: Bloom<TxHash>,
+    /// The generation that was replaced last.
+    previous: Bloom<TxHash>,
+    /// Number of hashes inserted into the current generation.
+    len: usize,
+    /// Number of insertions after which the generations are swapped.
+    capacity: usize,
+}
+
+// === impl SeenTransactions ===
+
+impl SeenTransactions {
+    /// Creates a new filter for `capacity` hashes per generation with the given false positive
+    /// rate.
+    pub fn new(capacity: usize, false_positive_rate: f64) -> Self {
+        let capacity = capacity.max(1);
+        let current = Bloom::new_for_fp_rate(capacity, false_positive_rate);
+        let previous = Bloom::new_for_fp_rate(capacity, false_positive_rate);
+        Self { current, previous, len: 0, capacity }
+    }
+
+    /// Returns `true` if the hash was (probably) seen before.
+    pub fn contains(&self, hash: &TxHash) -> bool {
+        self.current.check(hash) || self.previous.check(hash)
+    }
+
+    /// Marks the given hash as seen.
+    ///
+    /// Returns `false` if the hash was (probably) seen before.
+    pub fn insert(&mut self, hash: TxHash) -> bool {
+        if self.contains(&hash) {
+            return false
+        }
+        if self.len >= self.capacity {
+            self.rotate();
+        }
+        self.current.set(&hash);
+        self.len += 1;
+        true
+    }
+
+    /// Replaces the previous generation with the current one and starts a new generation.
+    fn rotate(&mut self) {
+        self.previous.clear();
+        std::mem::swap(&mut self.current, &mut self.previous);
+        self.len = 0;
+    }
+}
+
+impl Default for SeenTransactions {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEEN_TRANSACTIONS_CAPACITY, DEFAULT_SEEN_TRANSACTIONS_FALSE_POSITIVE_RATE)
+    }
+}
+
+impl fmt::Debug for SeenTransactions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeenTransactions")
+            .field("len", &self.len)
+            .field("capacity", &self.capacity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_contains() {
+        let mut seen = SeenTransactions::default();
+        let hash = TxHash::random();
+        assert!(!seen.contains(&hash));
+        assert!(seen.insert(hash));
+        assert!(seen.contains(&hash));
+        assert!(!seen.insert(hash));
+    }
+
+    #[test]
+    fn test_rotate_generations() {
+        let mut seen = SeenTransactions::new(10, 0.001);
+        let first = TxHash::random();
+        seen.insert(first);
+
+        // fill the first generation, the hash moves to the previous generation
+        for _ in 0..10 {
+            seen.insert(TxHash::random());
+        }
+        assert!(seen.contains(&first));
+
+        // fill the second generation, the first generation is dropped
+        for _ in 0..10 {
+            seen.insert(TxHash::random());
+        }
+        assert!(!seen.contains(&first));
+    }
+
+    #[test]
+    fn test_false_positive_rate() {
+        let mut seen = SeenTransactions::default();
+        for _ in 0..DEFAULT_SEEN_TRANSACTIONS_CAPACITY {
+            seen.insert(TxHash::random());
+        }
+
+        let samples = 100_000;
+        let false_positives = (0..samples).filter(|_| seen.contains(&TxHash::random())).count();
+        let rate = false_positives as f64 / samples as f64;
+        // allow some slack for the randomness of the sample
+        assert!(rate < DEFAULT_SEEN_TRANSACTIONS_FALSE_POSITIVE_RATE * 3.0, "rate: {rate}");
+    }
+}
@@ -13,9 +13,10 @@ use fnv::FnvHashMap;
 use futures::{stream::Fuse, SinkExt, StreamExt};
 use reth_ecies::stream::ECIESStream;
 use reth_eth_wire::{
-    capability::Capabilities,
+    capability::{Capabilities, CapabilityMessage},
     errors::{EthHandshakeError, EthStreamError, P2PStreamError},
     message::{EthBroadcastMessage, RequestPair},
+    snap::{AccountRange, ByteCodes, SnapMessage, StorageRanges, TrieNodes},
     DisconnectReason, EthMessage, EthStream, P2PStream,
 };
 use reth_interfaces::p2p::error::RequestError;
@@ -227,11 +228,101 @@ impl ActiveSession {
         }
     }
 
+    /// Handle a `snap` message read from the connection.
+    ///
+    /// Account range requests are delegated like `eth` requests. The other state requests are not
+    /// served and answered with an empty response right away, which tells the peer that the
+    /// requested state is not available.
+    fn on_incoming_snap(&mut self, msg: SnapMessage) -> OnIncomingMessageOutcome {
+        match msg {
+            SnapMessage::GetAccountRange(request) => {
+                let (tx, response) = oneshot::channel();
+                let received = ReceivedRequest {
+                    request_id: request.request_id,
+                    rx: PeerResponse::AccountRange { response },
+                    received: Instant::now(),
+                };
+                self.received_requests_from_remote.push(received);
+                self.try_emit_request(PeerMessage::EthRequest(PeerRequest::GetAccountRange {
+                    request,
+                    response: tx,
+                }))
+                .into()
+            }
+            SnapMessage::AccountRange(message) => {
+                #[allow(clippy::collapsible_match)]
+                if let Some(req) = self.inflight_requests.remove(&message.request_id) {
+                    match req.request {
+                        RequestState::Waiting(PeerRequest::GetAccountRange {
+                            request,
+                            response,
+                        }) => {
+                            // restore the id of the original request
+                            let message =
+                                AccountRange { request_id: request.request_id, ..message };
+                            let _ = response.send(Ok(message));
+                            self.update_request_timeout(req.timestamp, Instant::now());
+                        }
+                        RequestState::Waiting(request) => {
+                            request.send_bad_response();
+                        }
+                        RequestState::TimedOut => {
+                            // request was already timed out internally
+                            self.update_request_timeout(req.timestamp, Instant::now());
+                        }
+                    };
+                } else {
+                    // we received a response to a request we never sent
+                    self.on_bad_message();
+                }
+                OnIncomingMessageOutcome::Ok
+            }
+            SnapMessage::GetStorageRanges(request) => {
+                let request_id = request.request_id;
+                let response = StorageRanges { request_id, ..Default::default() };
+                self.queued_outgoing.push_back(SnapMessage::StorageRanges(response).into());
+                OnIncomingMessageOutcome::Ok
+            }
+            SnapMessage::GetByteCodes(request) => {
+                let response = ByteCodes { request_id: request.request_id, ..Default::default() };
+                self.queued_outgoing.push_back(SnapMessage::ByteCodes(response).into());
+                OnIncomingMessageOutcome::Ok
+            }
+            SnapMessage::GetTrieNodes(request) => {
+                let response = TrieNodes { request_id: request.request_id, ..Default::default() };
+                self.queued_outgoing.push_back(SnapMessage::TrieNodes(response).into());
+                OnIncomingMessageOutcome::Ok
+            }
+            SnapMessage::StorageRanges(_) |
+            SnapMessage::ByteCodes(_) |
+            SnapMessage::TrieNodes(_) => {
+                // we never request these
+                self.on_bad_message();
+                OnIncomingMessageOutcome::Ok
+            }
+        }
+    }
+
+    /// Queues in a message that will be sent to the remote.
+    fn queue_outgoing_message(&mut self, msg: CapabilityMessage) {
+        match msg {
+            CapabilityMessage::Eth(msg) => self.queued_outgoing.push_back(msg.into()),
+            CapabilityMessage::Snap(msg) => self.queued_outgoing.push_back(msg.into()),
+            CapabilityMessage::Other(other) => {
+                error!(target : "net::session", message_id=%other.id, "Ignoring unsupported message");
+            }
+        }
+    }
+
     /// Handle an internal peer request that will be sent to the remote.
     fn on_internal_peer_request(&mut self, request: PeerRequest, deadline: Instant) {
+        if request.is_snap_request() && !self.conn.is_snap_shared() {
+            request.send_err_response(RequestError::UnsupportedCapability);
+            return
+        }
         let request_id = self.next_id();
         let msg = request.create_request_message(request_id);
-        self.queued_outgoing.push_back(msg.into());
+        self.queue_outgoing_message(msg);
         let req = InflightRequest {
             request: RequestState::Waiting(request),
             timestamp: Instant::now(),
@@ -280,7 +371,7 @@ impl ActiveSession {
     fn handle_outgoing_response(&mut self, id: u64, resp: PeerResponseResult) {
         match resp.try_into_message(id) {
             Ok(msg) => {
-                self.queued_outgoing.push_back(msg.into());
+                self.queue_outgoing_message(msg);
             }
             Err(err) => {
                 error!(target : "net", ?err, "Failed to respond to received request");
@@ -517,6 +608,7 @@ impl Future for ActiveSession {
                     let res = match msg {
                         OutgoingMessage::Eth(msg) => this.conn.start_send_unpin(msg),
                         OutgoingMessage::Broadcast(msg) => this.conn.start_send_broadcast(msg),
+                        OutgoingMessage::Snap(msg) => this.conn.start_send_snap(msg),
                     };
                     if let Err(err) = res {
                         error!(target: "net::session", ?err,  remote_peer_id=?this.remote_peer_id, "failed to send message");
@@ -551,7 +643,7 @@ impl Future for ActiveSession {
                     }
                 }
 
-                match this.conn.poll_next_unpin(cx) {
+                match this.conn.poll_next_capability_message(cx) {
                     Poll::Pending => break,
                     Poll::Ready(None) => {
                         if this.is_disconnecting() {
@@ -565,9 +657,22 @@ impl Future for ActiveSession {
                     Poll::Ready(Some(res)) => {
                         match res {
                             Ok(msg) => {
-                                trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
-                                // decode and handle message
-                                match this.on_incoming(msg) {
+                                let outcome = match msg {
+                                    CapabilityMessage::Eth(msg) => {
+                                        trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
+                                        this.on_incoming(msg)
+                                    }
+                                    CapabilityMessage::Snap(msg) => {
+                                        trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received snap message");
+                                        this.on_incoming_snap(msg)
+                                    }
+                                    CapabilityMessage::Other(other) => {
+                                        debug!(target: "net::session", message_id=%other.id, remote_peer_id=?this.remote_peer_id, "Ignoring unsupported message");
+                                        continue 'receive
+                                    }
+                                };
+                                // handle the decoded message
+                                match outcome {
                                     OnIncomingMessageOutcome::Ok => {
                                         // handled successfully
                                         progress = true;
@@ -688,6 +793,8 @@ pub(crate) enum OutgoingMessage {
     Eth(EthMessage),
     /// A message that may be shared by multiple sessions.
     Broadcast(EthBroadcastMessage),
+    /// A `snap` message.
+    Snap(SnapMessage),
 }
 
 impl From<EthMessage> for OutgoingMessage {
@@ -702,6 +809,12 @@ impl From<EthBroadcastMessage> for OutgoingMessage {
     }
 }
 
+impl From<SnapMessage> for OutgoingMessage {
+    fn from(value: SnapMessage) -> Self {
+        OutgoingMessage::Snap(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(dead_code)]
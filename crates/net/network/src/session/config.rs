@@ -4,6 +4,7 @@ use crate::{
     peers::{DEFAULT_MAX_PEERS_INBOUND, DEFAULT_MAX_PEERS_OUTBOUND},
     session::{Direction, ExceedsSessionLimit},
 };
+use ipnet::IpNet;
 use std::time::Duration;
 
 /// Default request timeout for a single request.
@@ -37,6 +38,8 @@ pub struct SessionsConfig {
     /// `PROTOCOL_BREACH_REQUEST_TIMEOUT`) this is considered a protocol violation and results in a
     /// dropped session.
     pub protocol_breach_request_timeout: Duration,
+    /// Limits for incoming connections per IP address.
+    pub connection_rate_limit: ConnectionRateLimitConfig,
 }
 
 impl Default for SessionsConfig {
@@ -55,6 +58,7 @@ impl Default for SessionsConfig {
             limits: Default::default(),
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
+            connection_rate_limit: Default::default(),
         }
     }
 }
@@ -69,6 +73,43 @@ impl SessionsConfig {
         self.session_event_buffer = n;
         self
     }
+
+    /// Sets the limits for incoming connections per IP address.
+    pub fn with_connection_rate_limit(mut self, config: ConnectionRateLimitConfig) -> Self {
+        self.connection_rate_limit = config;
+        self
+    }
+}
+
+/// Limits for incoming connections per IP address.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectionRateLimitConfig {
+    /// Maximum number of simultaneous connections from a single IP address.
+    ///
+    /// Default: 3
+    pub max_connections_per_ip: u32,
+    /// Number of new connections a single IP address may open per minute.
+    ///
+    /// Default: 10
+    pub new_connection_rate: f64,
+    /// Address ranges that are not rate limited.
+    ///
+    /// Default: loopback addresses
+    pub whitelist: Vec<IpNet>,
+}
+
+impl Default for ConnectionRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_connections_per_ip: 3,
+            new_connection_rate: 10.0,
+            whitelist: vec![
+                "127.0.0.0/8".parse().expect("valid network"),
+                "::1/128".parse().expect("valid network"),
+            ],
+        }
+    }
 }
 
 /// Limits for sessions.
@@ -1,7 +1,7 @@
 //! Session handles
 use crate::{
     message::PeerMessage,
-    session::{Direction, SessionId},
+    session::{Direction, PeerInfo, SessionId},
 };
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
@@ -65,6 +65,10 @@ pub(crate) struct ActiveSessionHandle {
     pub(crate) client_version: String,
     /// The address we're connected to
     pub(crate) remote_addr: SocketAddr,
+    /// The local address of the connection
+    pub(crate) local_addr: Option<SocketAddr>,
+    /// The `Status` message the peer sent during the handshake
+    pub(crate) status: Status,
 }
 
 // === impl ActiveSessionHandle ===
@@ -75,21 +79,20 @@ impl ActiveSessionHandle {
         // Note: we clone the sender which ensures the channel has capacity to send the message
         let _ = self.commands_to_session.clone().try_send(SessionCommand::Disconnect { reason });
     }
-}
 
-/// Info about an active peer session.
-#[derive(Debug, Clone)]
-pub struct PeerInfo {
-    /// Announced capabilities of the peer
-    pub capabilities: Arc<Capabilities>,
-    /// The identifier of the remote peer
-    pub remote_id: PeerId,
-    /// The client's name and version
-    pub client_version: String,
-    /// The address we're connected to
-    pub remote_addr: SocketAddr,
-    /// The direction of the session
-    pub direction: Direction,
+    /// Returns the [`PeerInfo`] of the session.
+    pub(crate) fn peer_info(&self) -> PeerInfo {
+        PeerInfo {
+            remote_id: self.remote_id,
+            direction: self.direction,
+            remote_addr: self.remote_addr,
+            local_addr: self.local_addr,
+            capabilities: self.capabilities.clone(),
+            client_version: self.client_version.clone(),
+            eth_version: self.version,
+            status: self.status,
+        }
+    }
 }
 
 /// Events a pending session can produce.
@@ -103,6 +106,7 @@ pub(crate) enum PendingSessionEvent {
     Established {
         session_id: SessionId,
         remote_addr: SocketAddr,
+        local_addr: Option<SocketAddr>,
         /// The remote node's public key
         peer_id: PeerId,
         capabilities: Arc<Capabilities>,
@@ -1,4 +1,5 @@
 //! Support for handling peer sessions.
+pub use crate::message::PeerRequestSender;
 use crate::{
     message::PeerMessage,
     session::{
@@ -8,14 +9,14 @@ use crate::{
             ActiveSessionHandle, ActiveSessionMessage, PendingSessionEvent, PendingSessionHandle,
             SessionCommand,
         },
+        rate_limit::{ConnectionRateLimitError, ConnectionRateLimiter},
     },
 };
-pub use crate::{message::PeerRequestSender, session::handle::PeerInfo};
 use fnv::FnvHashMap;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
-    capability::{Capabilities, CapabilityMessage},
+    capability::{Capabilities, CapabilityMessage, SharedCapability},
     errors::EthStreamError,
     DisconnectReason, EthVersion, HelloMessage, Status, UnauthedEthStream, UnauthedP2PStream,
 };
@@ -24,6 +25,7 @@ use reth_net_common::{
     bandwidth_meter::{BandwidthMeter, MeteredStream},
     stream::HasRemoteAddr,
 };
+pub use reth_network_api::{Direction, PeerInfo};
 use reth_primitives::{ForkFilter, ForkId, ForkTransition, Head, PeerId};
 use reth_tasks::TaskSpawner;
 use secp256k1::SecretKey;
@@ -46,7 +48,8 @@ use tracing::{instrument, trace};
 mod active;
 mod config;
 mod handle;
-pub use config::SessionsConfig;
+mod rate_limit;
+pub use config::{ConnectionRateLimitConfig, SessionsConfig};
 
 /// Internal identifier for active sessions.
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
@@ -60,6 +63,8 @@ pub(crate) struct SessionManager {
     next_id: usize,
     /// Keeps track of all sessions
     counter: SessionCounter,
+    /// Limits the incoming connections per IP address.
+    rate_limiter: ConnectionRateLimiter,
     ///  The maximum initial time an [ActiveSession] waits for a response from the peer before it
     /// responds to an _internal_ request with a `TimeoutError`
     initial_internal_request_timeout: Duration,
@@ -122,6 +127,7 @@ impl SessionManager {
         Self {
             next_id: 0,
             counter: SessionCounter::new(config.limits),
+            rate_limiter: ConnectionRateLimiter::new(config.connection_rate_limit),
             initial_internal_request_timeout: config.initial_internal_request_timeout,
             protocol_breach_request_timeout: config.protocol_breach_request_timeout,
             secret_key,
@@ -185,13 +191,16 @@ impl SessionManager {
     /// An incoming TCP connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
-    /// Returns an error if the configured limit has been reached.
+    /// Returns an error if the configured limit has been reached, or if the remote IP address
+    /// exceeds its connection rate limit, in which case the stream is dropped before the
+    /// handshake.
     pub(crate) fn on_incoming(
         &mut self,
         stream: TcpStream,
         remote_addr: SocketAddr,
-    ) -> Result<SessionId, ExceedsSessionLimit> {
+    ) -> Result<SessionId, IncomingSessionError> {
         self.counter.ensure_pending_inbound()?;
+        self.rate_limiter.try_acquire(remote_addr.ip())?;
 
         let session_id = self.next_id();
 
@@ -321,6 +330,9 @@ impl SessionManager {
     fn remove_active_session(&mut self, id: &PeerId) -> Option<ActiveSessionHandle> {
         let session = self.active_sessions.remove(id)?;
         self.counter.dec_active(&session.direction);
+        if session.direction.is_incoming() {
+            self.rate_limiter.on_connection_closed(session.remote_addr.ip());
+        }
         Some(session)
     }
 
@@ -384,6 +396,7 @@ impl SessionManager {
             PendingSessionEvent::Established {
                 session_id,
                 remote_addr,
+                local_addr,
                 peer_id,
                 capabilities,
                 conn,
@@ -405,6 +418,10 @@ impl SessionManager {
                         "already connected"
                     );
 
+                    if direction.is_incoming() {
+                        self.rate_limiter.on_connection_closed(remote_addr.ip());
+                    }
+
                     self.spawn(async move {
                         // send a disconnect message
                         let _ =
@@ -464,6 +481,8 @@ impl SessionManager {
                     commands_to_session,
                     client_version: client_id,
                     remote_addr,
+                    local_addr,
+                    status,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
@@ -491,6 +510,7 @@ impl SessionManager {
                 self.remove_pending_session(&session_id);
                 match direction {
                     Direction::Incoming => {
+                        self.rate_limiter.on_connection_closed(remote_addr.ip());
                         Poll::Ready(SessionEvent::IncomingPendingSessionClosed {
                             remote_addr,
                             error: error.map(PendingSessionHandshakeError::Eth),
@@ -534,6 +554,7 @@ impl SessionManager {
                 self.remove_pending_session(&session_id);
                 match direction {
                     Direction::Incoming => {
+                        self.rate_limiter.on_connection_closed(remote_addr.ip());
                         Poll::Ready(SessionEvent::IncomingPendingSessionClosed {
                             remote_addr,
                             error: Some(PendingSessionHandshakeError::Ecies(error)),
@@ -553,29 +574,14 @@ impl SessionManager {
 
     /// Returns [`PeerInfo`] for all connected peers
     pub(crate) fn get_peer_info(&self) -> Vec<PeerInfo> {
-        self.active_sessions
-            .values()
-            .map(|session| PeerInfo {
-                remote_id: session.remote_id,
-                direction: session.direction,
-                remote_addr: session.remote_addr,
-                capabilities: session.capabilities.clone(),
-                client_version: session.client_version.clone(),
-            })
-            .collect()
+        self.active_sessions.values().map(ActiveSessionHandle::peer_info).collect()
     }
 
     /// Returns [`PeerInfo`] for a given peer.
     ///
     /// Returns `None` if there's no active session to the peer.
     pub(crate) fn get_peer_info_by_id(&self, peer_id: PeerId) -> Option<PeerInfo> {
-        self.active_sessions.get(&peer_id).map(|session| PeerInfo {
-            remote_id: session.remote_id,
-            direction: session.direction,
-            remote_addr: session.remote_addr,
-            capabilities: session.capabilities.clone(),
-            client_version: session.client_version.clone(),
-        })
+        self.active_sessions.get(&peer_id).map(ActiveSessionHandle::peer_info)
     }
 }
 
@@ -665,28 +671,23 @@ pub(crate) enum PendingSessionHandshakeError {
     Ecies(ECIESError),
 }
 
-/// The direction of the connection.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub enum Direction {
-    /// Incoming connection.
-    Incoming,
-    /// Outgoing connection to a specific node.
-    Outgoing(PeerId),
-}
-
-impl Direction {
-    /// Returns `true` if this an incoming connection.
-    pub(crate) fn is_incoming(&self) -> bool {
-        matches!(self, Direction::Incoming)
-    }
-}
-
 /// The error thrown when the max configured limit has been reached and no more connections are
 /// accepted.
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("Session limit reached {0}")]
 pub struct ExceedsSessionLimit(pub(crate) u32);
 
+/// The error returned when an incoming connection is rejected before the handshake.
+#[derive(Debug, Clone, thiserror::Error)]
+pub(crate) enum IncomingSessionError {
+    /// The limit of pending incoming sessions has been reached.
+    #[error(transparent)]
+    ExceedsSessionLimit(#[from] ExceedsSessionLimit),
+    /// The remote IP address exceeds its connection limits.
+    #[error(transparent)]
+    RateLimited(#[from] ConnectionRateLimitError),
+}
+
 /// Starts the authentication process for a connection initiated by a remote peer.
 ///
 /// This will wait for the _incoming_ handshake request and answer it.
@@ -775,6 +776,7 @@ async fn authenticate(
     status: Status,
     fork_filter: ForkFilter,
 ) {
+    let local_addr = stream.inner().local_addr().ok();
     let stream = match get_eciess_stream(stream, secret_key, direction).await {
         Ok(stream) => stream,
         Err(error) => {
@@ -796,6 +798,7 @@ async fn authenticate(
         unauthed,
         session_id,
         remote_addr,
+        local_addr,
         direction,
         hello,
         status,
@@ -843,6 +846,7 @@ async fn authenticate_stream(
     stream: UnauthedP2PStream<ECIESStream<MeteredStream<TcpStream>>>,
     session_id: SessionId,
     remote_addr: SocketAddr,
+    local_addr: Option<SocketAddr>,
     direction: Direction,
     hello: HelloMessage,
     status: Status,
@@ -865,9 +869,18 @@ async fn authenticate_stream(
     //
     // Before trying status handshake, set up the version to shared_capability
     let status = Status { version: p2p_stream.shared_capability().version(), ..status };
+    // the message ids of snap are relative to the offset of eth
+    let snap_offset = p2p_stream
+        .shared_capabilities()
+        .iter()
+        .find(|cap| matches!(cap, SharedCapability::Snap { .. }))
+        .map(|snap| snap.offset() - p2p_stream.shared_capability().offset());
     let eth_unauthed = UnauthedEthStream::new(p2p_stream);
     let (eth_stream, their_status) = match eth_unauthed.handshake(status, fork_filter).await {
-        Ok(stream_res) => stream_res,
+        Ok((eth_stream, their_status)) => match snap_offset {
+            Some(snap_offset) => (eth_stream.with_snap_offset(snap_offset), their_status),
+            None => (eth_stream, their_status),
+        },
         Err(err) => {
             return PendingSessionEvent::Disconnected {
                 remote_addr,
@@ -880,6 +893,7 @@ async fn authenticate_stream(
     PendingSessionEvent::Established {
         session_id,
         remote_addr,
+        local_addr,
         peer_id: their_hello.id,
         capabilities: Arc::new(Capabilities::from(their_hello.capabilities)),
         status: their_status,
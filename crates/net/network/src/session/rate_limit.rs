@@ -0,0 +1,220 @@
+//! Rate limiting of incoming connections per IP address.
+
+use crate::session::config::ConnectionRateLimitConfig;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+/// How often entries of IP addresses without connections and with a full bucket are removed.
+const STALE_ENTRIES_CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Limits the incoming connections per IP address.
+///
+/// Every IP address may have up to
+/// [`max_connections_per_ip`](ConnectionRateLimitConfig::max_connections_per_ip) simultaneous
+/// connections, and new connections are limited by a token bucket that refills at
+/// [`new_connection_rate`](ConnectionRateLimitConfig::new_connection_rate) tokens per minute.
+///
+/// Addresses in one of the [whitelisted](ConnectionRateLimitConfig::whitelist) ranges are not
+/// limited.
+#[derive(Debug)]
+pub(crate) struct ConnectionRateLimiter {
+    /// The limits to enforce.
+    config: ConnectionRateLimitConfig,
+    /// Tracked connections by IP address.
+    addresses: HashMap<IpAddr, IpConnections>,
+    /// When stale entries were removed the last time.
+    last_cleanup: Instant,
+}
+
+// === impl ConnectionRateLimiter ===
+
+impl ConnectionRateLimiter {
+    /// Creates a new limiter that enforces the given limits.
+    pub(crate) fn new(config: ConnectionRateLimitConfig) -> Self {
+        Self { config, addresses: Default::default(), last_cleanup: Instant::now() }
+    }
+
+    /// Tries to register a new incoming connection from the given address.
+    ///
+    /// Every successfully registered connection must be released via
+    /// [`ConnectionRateLimiter::on_connection_closed`] once it's closed.
+    pub(crate) fn try_acquire(&mut self, ip: IpAddr) -> Result<(), ConnectionRateLimitError> {
+        self.try_acquire_at(ip, Instant::now())
+    }
+
+    fn try_acquire_at(&mut self, ip: IpAddr, now: Instant) -> Result<(), ConnectionRateLimitError> {
+        if self.is_whitelisted(ip) {
+            return Ok(())
+        }
+
+        if now.saturating_duration_since(self.last_cleanup) >= STALE_ENTRIES_CLEANUP_INTERVAL {
+            self.remove_stale(now);
+        }
+
+        let capacity = self.bucket_capacity();
+        let refill_per_sec = self.config.new_connection_rate / 60.0;
+        let entry = self.addresses.entry(ip).or_insert_with(|| IpConnections::new(capacity, now));
+        entry.refill(now, capacity, refill_per_sec);
+
+        if entry.connections >= self.config.max_connections_per_ip {
+            return Err(ConnectionRateLimitError::TooManyConnections(
+                self.config.max_connections_per_ip,
+            ))
+        }
+        if entry.tokens < 1.0 {
+            return Err(ConnectionRateLimitError::RateExceeded)
+        }
+
+        entry.tokens -= 1.0;
+        entry.connections += 1;
+        Ok(())
+    }
+
+    /// Releases a connection from the given address that was registered via
+    /// [`ConnectionRateLimiter::try_acquire`].
+    pub(crate) fn on_connection_closed(&mut self, ip: IpAddr) {
+        if let Some(entry) = self.addresses.get_mut(&ip) {
+            entry.connections = entry.connections.saturating_sub(1);
+        }
+    }
+
+    /// Returns `true` if the address is in one of the whitelisted ranges.
+    fn is_whitelisted(&self, ip: IpAddr) -> bool {
+        self.config.whitelist.iter().any(|net| net.contains(&ip))
+    }
+
+    /// The maximum number of tokens of a bucket, which is the allowed burst of new connections.
+    fn bucket_capacity(&self) -> f64 {
+        self.config.new_connection_rate.max(1.0)
+    }
+
+    /// Removes all entries without connections whose bucket is full again, since these are
+    /// equivalent to untracked addresses.
+    fn remove_stale(&mut self, now: Instant) {
+        let capacity = self.bucket_capacity();
+        let refill_per_sec = self.config.new_connection_rate / 60.0;
+        self.addresses.retain(|_, entry| {
+            entry.refill(now, capacity, refill_per_sec);
+            entry.connections > 0 || entry.tokens < capacity
+        });
+        self.last_cleanup = now;
+    }
+}
+
+/// The connections of a single IP address.
+#[derive(Debug)]
+struct IpConnections {
+    /// Number of currently open connections.
+    connections: u32,
+    /// Remaining tokens for new connections.
+    tokens: f64,
+    /// When the tokens were last refilled.
+    last_refill: Instant,
+}
+
+impl IpConnections {
+    fn new(capacity: f64, now: Instant) -> Self {
+        Self { connections: 0, tokens: capacity, last_refill: now }
+    }
+
+    /// Adds the tokens accumulated since the last refill.
+    fn refill(&mut self, now: Instant, capacity: f64, refill_per_sec: f64) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * refill_per_sec).min(capacity);
+        self.last_refill = now;
+    }
+}
+
+/// The error returned when a connection exceeds the [`ConnectionRateLimiter`] limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub(crate) enum ConnectionRateLimitError {
+    /// The IP address already has the maximum number of connections.
+    #[error("Too many connections from the same ip, limit {0}")]
+    TooManyConnections(u32),
+    /// The IP address opened too many new connections recently.
+    #[error("Connection rate limit exceeded")]
+    RateExceeded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn config() -> ConnectionRateLimitConfig {
+        ConnectionRateLimitConfig {
+            max_connections_per_ip: 2,
+            new_connection_rate: 3.0,
+            whitelist: vec!["10.0.0.0/8".parse().unwrap()],
+        }
+    }
+
+    #[test]
+    fn test_max_connections_per_ip() {
+        let mut limiter = ConnectionRateLimiter::new(config());
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+        let now = Instant::now();
+
+        assert!(limiter.try_acquire_at(ip, now).is_ok());
+        assert!(limiter.try_acquire_at(ip, now).is_ok());
+        assert_eq!(
+            limiter.try_acquire_at(ip, now),
+            Err(ConnectionRateLimitError::TooManyConnections(2))
+        );
+
+        // other addresses are tracked separately
+        assert!(limiter.try_acquire_at(IpAddr::V4(Ipv4Addr::new(1, 2, 3, 5)), now).is_ok());
+
+        limiter.on_connection_closed(ip);
+        assert!(limiter.try_acquire_at(ip, now).is_ok());
+    }
+
+    #[test]
+    fn test_new_connection_rate() {
+        let mut limiter = ConnectionRateLimiter::new(config());
+        let ip = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.try_acquire_at(ip, now).is_ok());
+            limiter.on_connection_closed(ip);
+        }
+        assert_eq!(limiter.try_acquire_at(ip, now), Err(ConnectionRateLimitError::RateExceeded));
+
+        // refills one token every 20s
+        let later = now + Duration::from_secs(20);
+        assert!(limiter.try_acquire_at(ip, later).is_ok());
+        assert_eq!(limiter.try_acquire_at(ip, later), Err(ConnectionRateLimitError::RateExceeded));
+    }
+
+    #[test]
+    fn test_whitelisted_ip() {
+        let mut limiter = ConnectionRateLimiter::new(config());
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3));
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            assert!(limiter.try_acquire_at(ip, now).is_ok());
+        }
+        assert!(limiter.addresses.is_empty());
+    }
+
+    #[test]
+    fn test_remove_stale_entries() {
+        let mut limiter = ConnectionRateLimiter::new(config());
+        let idle = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4));
+        let connected = IpAddr::V4(Ipv4Addr::new(1, 2, 3, 5));
+        let now = Instant::now();
+
+        limiter.try_acquire_at(idle, now).unwrap();
+        limiter.on_connection_closed(idle);
+        limiter.try_acquire_at(connected, now).unwrap();
+
+        limiter.remove_stale(now + STALE_ENTRIES_CLEANUP_INTERVAL);
+        assert!(!limiter.addresses.contains_key(&idle));
+        assert!(limiter.addresses.contains_key(&connected));
+    }
+}
@@ -0,0 +1,6 @@
+//! Support for downloading the state with the `snap` protocol.
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+mod syncer;
+pub use syncer::{AccountRangeError, SnapSyncer, DEFAULT_ACCOUNT_RANGE_RESPONSE_BYTES};
@@ -0,0 +1,428 @@
+use futures::{Future, FutureExt, Stream};
+use reth_eth_wire::snap::{AccountData, AccountRange, GetAccountRange};
+use reth_interfaces::p2p::snap::client::SnapClient;
+use reth_primitives::{H256, U256};
+use std::{
+    fmt,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use thiserror::Error;
+use tokio::time::Sleep;
+use tracing::{debug, trace};
+
+/// The soft limit of the size of an [AccountRange] response that is requested by default.
+pub const DEFAULT_ACCOUNT_RANGE_RESPONSE_BYTES: u64 = 512 * 1024;
+
+/// The delay before a range is requested again after a failed or unusable response by default.
+pub const DEFAULT_ACCOUNT_RANGE_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Downloads all accounts of the state with the given root with `GetAccountRange` requests.
+///
+/// The account hashes are requested in consecutive ranges, starting at the zero hash. The accounts
+/// of a response are yielded in ascending order of their hashes, and the next request starts right
+/// after the last account of the previous response. The stream ends once a peer proves that there
+/// are no accounts left.
+///
+/// Responses with accounts outside of the requested range or out of order are reported and
+/// requested again. A range is only requested again after a delay, so the request is likely sent
+/// to another peer, see [FetchClient](crate::FetchClient).
+///
+/// Note: the merkle proofs of the responses are not verified against the state root yet.
+#[must_use = "Stream does nothing unless polled"]
+pub struct SnapSyncer<C: SnapClient> {
+    /// The client used to request account ranges.
+    client: Arc<C>,
+    /// The root of the state to download.
+    root_hash: H256,
+    /// The soft limit of the size of a response.
+    response_bytes: u64,
+    /// The starting hash of the next request, `None` once all accounts are downloaded.
+    next_hash: Option<H256>,
+    /// The id of the next request.
+    next_request_id: u64,
+    /// The request that is currently in progress.
+    inflight: Option<(GetAccountRange, C::Output)>,
+    /// The delay before a range is requested again.
+    retry_delay: Duration,
+    /// Delays the next request after a failed or unusable response.
+    retry_backoff: Option<Pin<Box<Sleep>>>,
+}
+
+// === impl SnapSyncer ===
+
+impl<C: SnapClient> SnapSyncer<C> {
+    /// Creates a new syncer that downloads the accounts of the state with the given root.
+    pub fn new(client: Arc<C>, root_hash: H256) -> Self {
+        Self {
+            client,
+            root_hash,
+            response_bytes: DEFAULT_ACCOUNT_RANGE_RESPONSE_BYTES,
+            next_hash: Some(H256::zero()),
+            next_request_id: 0,
+            inflight: None,
+            retry_delay: DEFAULT_ACCOUNT_RANGE_RETRY_DELAY,
+            retry_backoff: None,
+        }
+    }
+
+    /// Sets the soft limit of the size of a response.
+    pub fn with_response_bytes(mut self, response_bytes: u64) -> Self {
+        self.response_bytes = response_bytes;
+        self
+    }
+
+    /// Sets the delay before a range is requested again after a failed or unusable response.
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Returns the starting hash of the next request, or `None` if all accounts are downloaded.
+    pub fn next_hash(&self) -> Option<H256> {
+        self.next_hash
+    }
+
+    /// Sends the request for the accounts starting at the given hash.
+    fn request_account_range(&mut self, starting_hash: H256) {
+        let request = GetAccountRange {
+            request_id: self.next_request_id,
+            root_hash: self.root_hash,
+            starting_hash,
+            limit_hash: H256::repeat_byte(0xff),
+            response_bytes: self.response_bytes,
+        };
+        self.next_request_id += 1;
+        trace!(target: "net::snap", ?starting_hash, "Requesting account range");
+        let fut = self.client.get_account_range(request.clone());
+        self.inflight = Some((request, fut));
+    }
+
+    /// Delays the next request of the same range.
+    fn on_retry(&mut self) {
+        self.retry_backoff = Some(Box::pin(tokio::time::sleep(self.retry_delay)));
+    }
+
+    /// Handles a valid response and returns the downloaded accounts, if any.
+    fn on_account_range(&mut self, response: AccountRange) -> Option<Vec<AccountData>> {
+        let Some(last) = response.accounts.last() else {
+            if response.proof.is_empty() {
+                // the peer doesn't serve the state of this root
+                trace!(target: "net::snap", root_hash = ?self.root_hash, "Received empty account range");
+                self.on_retry();
+            } else {
+                // the proof shows that there are no accounts after the starting hash
+                self.next_hash = None;
+            }
+            return None
+        };
+
+        self.next_hash = if last.hash == H256::repeat_byte(0xff) {
+            None
+        } else {
+            let next = U256::from_be_bytes(last.hash.0) + U256::from(1);
+            Some(H256(next.to_be_bytes()))
+        };
+        Some(response.accounts)
+    }
+}
+
+impl<C: SnapClient> Stream for SnapSyncer<C> {
+    type Item = Vec<AccountData>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some((_, fut)) = &mut this.inflight {
+                let result = ready!(fut.poll_unpin(cx));
+                let (request, _) = this.inflight.take().expect("is set");
+
+                match result {
+                    Ok(response) => {
+                        let (peer_id, response) = response.split();
+                        match validate_account_range(&request, &response) {
+                            Ok(()) => {
+                                if let Some(accounts) = this.on_account_range(response) {
+                                    return Poll::Ready(Some(accounts))
+                                }
+                            }
+                            Err(err) => {
+                                debug!(target: "net::snap", ?peer_id, ?err, "Received invalid account range");
+                                this.client.report_bad_message(peer_id);
+                                this.on_retry();
+                            }
+                        }
+                    }
+                    Err(err) => {
+                        debug!(target: "net::snap", ?err, "Failed to request account range");
+                        this.on_retry();
+                    }
+                }
+            }
+
+            if let Some(backoff) = this.retry_backoff.as_mut() {
+                ready!(backoff.as_mut().poll(cx));
+                this.retry_backoff = None;
+            }
+
+            let Some(starting_hash) = this.next_hash else { return Poll::Ready(None) };
+            // request the next range, or the same range again if the last request failed
+            this.request_account_range(starting_hash);
+        }
+    }
+}
+
+impl<C: SnapClient> fmt::Debug for SnapSyncer<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapSyncer")
+            .field("client", &self.client)
+            .field("root_hash", &self.root_hash)
+            .field("response_bytes", &self.response_bytes)
+            .field("next_hash", &self.next_hash)
+            .field("next_request_id", &self.next_request_id)
+            .field("retry_delay", &self.retry_delay)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Errors of an invalid [AccountRange] response.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AccountRangeError {
+    /// The response belongs to a different request.
+    #[error("Mismatched request id: expected {expected}, got {got}")]
+    RequestIdMismatch {
+        /// The id of the request.
+        expected: u64,
+        /// The id of the response.
+        got: u64,
+    },
+    /// An account is outside of the requested range.
+    #[error("Account {0:?} is outside of the requested range")]
+    AccountOutOfRange(H256),
+    /// The accounts are not in ascending order of their hashes.
+    #[error("Account {0:?} is out of order")]
+    UnorderedAccount(H256),
+}
+
+/// Checks that the accounts of the response are in the requested range and ordered by their hashes.
+fn validate_account_range(
+    request: &GetAccountRange,
+    response: &AccountRange,
+) -> Result<(), AccountRangeError> {
+    if request.request_id != response.request_id {
+        return Err(AccountRangeError::RequestIdMismatch {
+            expected: request.request_id,
+            got: response.request_id,
+        })
+    }
+
+    let mut prev: Option<H256> = None;
+    for account in &response.accounts {
+        if account.hash < request.starting_hash {
+            return Err(AccountRangeError::AccountOutOfRange(account.hash))
+        }
+        if prev.map_or(false, |prev| account.hash <= prev) {
+            return Err(AccountRangeError::UnorderedAccount(account.hash))
+        }
+        prev = Some(account.hash);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use parking_lot::Mutex;
+    use reth_eth_wire::snap::SlimAccount;
+    use reth_interfaces::p2p::{
+        download::DownloadClient, error::PeerRequestResult, priority::Priority,
+        snap::client::AccountRangeFut,
+    };
+    use reth_primitives::{proofs::EMPTY_ROOT, Bytes, PeerId, WithPeerId, KECCAK_EMPTY};
+    use std::{collections::BTreeMap, time::Instant};
+
+    /// A [SnapClient] that serves account ranges of an in-memory state.
+    #[derive(Debug)]
+    struct TestSnapClient {
+        peer_id: PeerId,
+        root_hash: H256,
+        accounts: BTreeMap<H256, SlimAccount>,
+        /// The maximum number of accounts per response.
+        max_accounts: usize,
+        /// Whether the next response is served in reverse order.
+        reverse_next: Mutex<bool>,
+        requests: Mutex<Vec<GetAccountRange>>,
+        reported: Mutex<Vec<PeerId>>,
+    }
+
+    impl TestSnapClient {
+        fn new(root_hash: H256, accounts: BTreeMap<H256, SlimAccount>) -> Self {
+            Self {
+                peer_id: PeerId::random(),
+                root_hash,
+                accounts,
+                max_accounts: 3,
+                reverse_next: Mutex::new(false),
+                requests: Default::default(),
+                reported: Default::default(),
+            }
+        }
+
+        fn account_range(&self, request: &GetAccountRange) -> AccountRange {
+            if request.root_hash != self.root_hash {
+                return AccountRange { request_id: request.request_id, ..Default::default() }
+            }
+
+            let mut accounts = self
+                .accounts
+                .range(request.starting_hash..=request.limit_hash)
+                .take(self.max_accounts)
+                .map(|(hash, body)| AccountData { hash: *hash, body: *body })
+                .collect::<Vec<_>>();
+            if std::mem::take(&mut *self.reverse_next.lock()) {
+                accounts.reverse();
+            }
+            // the proofs are not verified
+            AccountRange {
+                request_id: request.request_id,
+                accounts,
+                proof: vec![Bytes::from_static(b"proof")],
+            }
+        }
+    }
+
+    impl DownloadClient for TestSnapClient {
+        fn report_bad_message(&self, peer_id: PeerId) {
+            self.reported.lock().push(peer_id);
+        }
+
+        fn num_connected_peers(&self) -> usize {
+            1
+        }
+    }
+
+    impl SnapClient for TestSnapClient {
+        type Output = AccountRangeFut;
+
+        fn get_account_range_with_priority(
+            &self,
+            request: GetAccountRange,
+            _priority: Priority,
+        ) -> Self::Output {
+            let response: PeerRequestResult<AccountRange> =
+                Ok(WithPeerId::from((self.peer_id, self.account_range(&request))));
+            self.requests.lock().push(request);
+            Box::pin(async move { response })
+        }
+    }
+
+    fn random_state(num_accounts: u64) -> BTreeMap<H256, SlimAccount> {
+        (0..num_accounts)
+            .map(|nonce| {
+                let account = SlimAccount {
+                    nonce,
+                    balance: U256::from(nonce),
+                    storage_root: EMPTY_ROOT,
+                    code_hash: KECCAK_EMPTY,
+                };
+                (H256::random(), account)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn downloads_all_accounts() {
+        let root_hash = H256::random();
+        let state = random_state(10);
+        let client = Arc::new(TestSnapClient::new(root_hash, state.clone()));
+
+        let mut syncer = SnapSyncer::new(Arc::clone(&client), root_hash);
+        let mut downloaded = Vec::new();
+        while let Some(accounts) = syncer.next().await {
+            downloaded.extend(accounts);
+        }
+
+        let expected =
+            state.into_iter().map(|(hash, body)| AccountData { hash, body }).collect::<Vec<_>>();
+        assert_eq!(downloaded, expected);
+        assert_eq!(syncer.next_hash(), None);
+
+        // 4 ranges of accounts and the proof that there are no accounts left
+        let requests = client.requests.lock();
+        assert_eq!(requests.len(), 5);
+        assert_eq!(requests[0].starting_hash, H256::zero());
+        for (request, accounts) in requests[1..].iter().zip(downloaded.chunks(3)) {
+            let last = U256::from_be_bytes(accounts.last().unwrap().hash.0);
+            assert_eq!(request.starting_hash, H256((last + U256::from(1)).to_be_bytes()));
+        }
+        assert!(client.reported.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn retries_unordered_account_range() {
+        let root_hash = H256::random();
+        let state = random_state(2);
+        let client = Arc::new(TestSnapClient::new(root_hash, state.clone()));
+        *client.reverse_next.lock() = true;
+
+        let mut syncer = SnapSyncer::new(Arc::clone(&client), root_hash)
+            .with_retry_delay(Duration::from_millis(10));
+        let accounts = syncer.next().await.unwrap();
+        let hashes = accounts.iter().map(|account| account.hash).collect::<Vec<_>>();
+        assert_eq!(hashes, state.keys().copied().collect::<Vec<_>>());
+
+        // the unordered response is reported and requested again
+        assert_eq!(*client.reported.lock(), vec![client.peer_id]);
+        let requests = client.requests.lock();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].starting_hash, requests[1].starting_hash);
+        assert_ne!(requests[0].request_id, requests[1].request_id);
+    }
+
+    #[tokio::test]
+    async fn delays_retry_of_invalid_account_range() {
+        let root_hash = H256::random();
+        let client = Arc::new(TestSnapClient::new(root_hash, random_state(2)));
+        *client.reverse_next.lock() = true;
+
+        let retry_delay = Duration::from_millis(100);
+        let mut syncer =
+            SnapSyncer::new(Arc::clone(&client), root_hash).with_retry_delay(retry_delay);
+        let start = Instant::now();
+
+        // the range is not requested again before the delay elapsed
+        assert!(tokio::time::timeout(retry_delay / 2, syncer.next()).await.is_err());
+        assert_eq!(client.requests.lock().len(), 1);
+
+        assert!(syncer.next().await.is_some());
+        assert!(start.elapsed() >= retry_delay);
+        assert_eq!(client.requests.lock().len(), 2);
+    }
+
+    #[test]
+    fn rejects_accounts_before_starting_hash() {
+        let request = GetAccountRange {
+            request_id: 1,
+            starting_hash: H256::repeat_byte(0x10),
+            limit_hash: H256::repeat_byte(0xff),
+            ..Default::default()
+        };
+        let account = AccountData { hash: H256::repeat_byte(0x01), ..Default::default() };
+        let response = AccountRange { request_id: 1, accounts: vec![account], proof: vec![] };
+        assert_eq!(
+            validate_account_range(&request, &response),
+            Err(AccountRangeError::AccountOutOfRange(H256::repeat_byte(0x01)))
+        );
+
+        let response = AccountRange { request_id: 2, ..Default::default() };
+        assert_eq!(
+            validate_account_range(&request, &response),
+            Err(AccountRangeError::RequestIdMismatch { expected: 1, got: 2 })
+        );
+    }
+}
@@ -3,18 +3,19 @@
 use crate::{
     cache::LruCache,
     discovery::{Discovery, DiscoveryEvent},
-    fetch::{BlockResponseOutcome, FetchAction, StateFetcher},
+    fetch::{BlockResponseOutcome, FetchAction, StateFetcher, SyncBandwidthConfig},
     message::{
         BlockRequest, NewBlockMessage, PeerRequest, PeerRequestSender, PeerResponse,
         PeerResponseResult,
     },
+    peer_activity::PeerActivityMonitor,
     peers::{PeerAction, PeersManager},
     FetchClient,
 };
 use reth_eth_wire::{
     capability::Capabilities, BlockHashNumber, DisconnectReason, NewBlockHashes, Status,
 };
-use reth_network_api::PeerKind;
+use reth_network_api::{PeerKind, ReputationChangeKind};
 use reth_primitives::{ForkId, PeerId, H256};
 use reth_provider::BlockProvider;
 use std::{
@@ -26,6 +27,7 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::sync::oneshot;
 use tracing::{debug, error};
@@ -61,6 +63,8 @@ pub struct NetworkState<C> {
     /// The fetcher streams RLPx related requests on a per-peer basis to this type. This type will
     /// then queue in the request and notify the fetcher once the result has been received.
     state_fetcher: StateFetcher,
+    /// Detects peers that stopped responding to their active requests.
+    activity_monitor: PeerActivityMonitor,
 }
 
 impl<C> NetworkState<C>
@@ -74,8 +78,11 @@ where
         peers_manager: PeersManager,
         genesis_hash: H256,
         num_active_peers: Arc<AtomicUsize>,
+        sync_bandwidth_config: Option<SyncBandwidthConfig>,
+        peer_inactivity_timeout: Duration,
     ) -> Self {
-        let state_fetcher = StateFetcher::new(peers_manager.handle(), num_active_peers);
+        let state_fetcher = StateFetcher::new(peers_manager.handle(), num_active_peers)
+            .with_bandwidth_limit(sync_bandwidth_config);
         Self {
             active_peers: Default::default(),
             peers_manager,
@@ -84,6 +91,7 @@ where
             discovery,
             genesis_hash,
             state_fetcher,
+            activity_monitor: PeerActivityMonitor::new(peer_inactivity_timeout),
         }
     }
 
@@ -130,6 +138,10 @@ where
         let block_number =
             self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
         self.state_fetcher.new_active_peer(peer, status.blockhash, block_number, timeout);
+        if capabilities.supports_snap() {
+            self.state_fetcher.set_snap_supported(&peer);
+        }
+        self.activity_monitor.on_session_activated(peer, Instant::now());
 
         self.active_peers.insert(
             peer,
@@ -149,6 +161,29 @@ where
     pub(crate) fn on_session_closed(&mut self, peer: PeerId) {
         self.active_peers.remove(&peer);
         self.state_fetcher.on_session_closed(&peer);
+        self.activity_monitor.on_session_closed(&peer);
+    }
+
+    /// Invoked when a message was received from the peer.
+    pub(crate) fn on_peer_message(&mut self, peer_id: &PeerId) {
+        self.activity_monitor.on_message(peer_id, Instant::now());
+    }
+
+    /// Invoked when the peer did not send any message for too long while it had an active
+    /// request.
+    ///
+    /// The request is sent to another peer, and the stale peer is penalized and disconnected.
+    fn on_stale_peer(&mut self, peer_id: PeerId) {
+        debug!(target: "net", ?peer_id, "Disconnecting stale peer");
+        if let Some(peer) = self.active_peers.get_mut(&peer_id) {
+            peer.pending_response = None;
+        }
+        self.state_fetcher.on_stale_peer(&peer_id);
+        self.peers_manager.apply_reputation_change(&peer_id, ReputationChangeKind::Timeout);
+        self.queued_messages.push_back(StateAction::Disconnect {
+            peer_id,
+            reason: Some(DisconnectReason::UselessPeer),
+        });
     }
 
     /// Starts propagating the new block to peers that haven't reported the block yet.
@@ -321,8 +356,8 @@ where
     ///
     /// Caution: this will replace an already pending response. It's the responsibility of the
     /// caller to select the peer.
-    fn handle_block_request(&mut self, peer: PeerId, request: BlockRequest) {
-        if let Some(ref mut peer) = self.active_peers.get_mut(&peer) {
+    fn handle_block_request(&mut self, peer_id: PeerId, request: BlockRequest) {
+        if let Some(ref mut peer) = self.active_peers.get_mut(&peer_id) {
             let (request, response) = match request {
                 BlockRequest::GetBlockHeaders(request) => {
                     let (response, rx) = oneshot::channel();
@@ -336,9 +371,16 @@ where
                     let response = PeerResponse::BlockBodies { response: rx };
                     (request, response)
                 }
+                BlockRequest::GetAccountRange(request) => {
+                    let (response, rx) = oneshot::channel();
+                    let request = PeerRequest::GetAccountRange { request, response };
+                    let response = PeerResponse::AccountRange { response: rx };
+                    (request, response)
+                }
             };
             let _ = peer.request_tx.to_session_tx.try_send(request);
             peer.pending_response = Some(response);
+            self.activity_monitor.on_request_sent(&peer_id, Instant::now());
         }
     }
 
@@ -361,6 +403,7 @@ where
     /// instruction that needs to be handled in [Self::on_block_response_outcome]. This could be
     /// a follow-up request or an instruction to slash the peer's reputation.
     fn on_eth_response(&mut self, peer: PeerId, resp: PeerResponseResult) -> Option<StateAction> {
+        self.activity_monitor.on_response(&peer, Instant::now());
         match resp {
             PeerResponseResult::BlockHeaders(res) => {
                 let outcome = self.state_fetcher.on_block_headers_response(peer, res)?;
@@ -370,6 +413,10 @@ where
                 let outcome = self.state_fetcher.on_block_bodies_response(peer, res)?;
                 self.on_block_response_outcome(outcome)
             }
+            PeerResponseResult::AccountRange(res) => {
+                let outcome = self.state_fetcher.on_account_range_response(peer, res)?;
+                self.on_block_response_outcome(outcome)
+            }
             _ => None,
         }
     }
@@ -386,6 +433,13 @@ where
                 self.on_discovery_event(discovery);
             }
 
+            // replace stale peers before dispatching their requests again
+            while let Poll::Ready(stale_peers) = self.activity_monitor.poll(cx) {
+                for peer_id in stale_peers {
+                    self.on_stale_peer(peer_id);
+                }
+            }
+
             while let Poll::Ready(action) = self.state_fetcher.poll(cx) {
                 match action {
                     FetchAction::BlockRequest { peer_id, request } => {
@@ -506,8 +560,13 @@ pub(crate) enum StateAction {
 #[cfg(test)]
 mod tests {
     use crate::{
-        discovery::Discovery, fetch::StateFetcher, message::PeerRequestSender, peers::PeersManager,
-        state::NetworkState, PeerRequest,
+        discovery::Discovery,
+        fetch::StateFetcher,
+        message::PeerRequestSender,
+        peer_activity::{PeerActivityMonitor, DEFAULT_PEER_INACTIVITY_TIMEOUT},
+        peers::PeersManager,
+        state::NetworkState,
+        PeerRequest,
     };
     use reth_eth_wire::{
         capability::{Capabilities, Capability},
@@ -519,6 +578,7 @@ mod tests {
     use std::{
         future::poll_fn,
         sync::{atomic::AtomicU64, Arc},
+        time::Duration,
     };
     use tokio::sync::mpsc;
     use tokio_stream::{wrappers::ReceiverStream, StreamExt};
@@ -535,6 +595,7 @@ mod tests {
             discovery: Discovery::noop(),
             genesis_hash: Default::default(),
             state_fetcher: StateFetcher::new(handle, Default::default()),
+            activity_monitor: PeerActivityMonitor::new(DEFAULT_PEER_INACTIVITY_TIMEOUT),
         }
     }
 
@@ -598,4 +659,54 @@ mod tests {
         assert!(resp.is_err());
         assert_eq!(resp.unwrap_err(), RequestError::ConnectionDropped);
     }
+
+    // tests that the request of a peer that stopped responding is sent to another peer
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_replace_stale_peer() {
+        let mut state = state();
+        state.activity_monitor = PeerActivityMonitor::new(Duration::from_millis(100));
+        let client = state.fetch_client();
+
+        // the stale peer is preferred because of its lower timeout
+        let (stale_peer, healthy_peer) = (PeerId::random(), PeerId::random());
+        let (stale_tx, mut stale_rx) = mpsc::channel(1);
+        let (healthy_tx, healthy_rx) = mpsc::channel(1);
+        for (peer_id, tx, timeout) in [(stale_peer, stale_tx, 1), (healthy_peer, healthy_tx, 2)] {
+            state.on_session_activated(
+                peer_id,
+                capabilities(),
+                Status::default(),
+                PeerRequestSender::new(peer_id, tx),
+                Arc::new(AtomicU64::new(timeout)),
+            );
+        }
+
+        let body = BlockBody { ommers: vec![Header::default()], ..Default::default() };
+        let body_response = body.clone();
+
+        // the stale peer receives the request but never responds
+        tokio::task::spawn(async move {
+            let _resp = stale_rx.recv().await.unwrap();
+            std::future::pending::<()>().await;
+        });
+        tokio::task::spawn(async move {
+            let mut stream = ReceiverStream::new(healthy_rx);
+            match stream.next().await.unwrap() {
+                PeerRequest::GetBlockBodies { response, .. } => {
+                    response.send(Ok(BlockBodies(vec![body_response]))).unwrap();
+                }
+                _ => unreachable!(),
+            }
+        });
+
+        tokio::task::spawn(async move {
+            loop {
+                poll_fn(|cx| state.poll(cx)).await;
+            }
+        });
+
+        let (peer, bodies) = client.get_block_bodies(vec![H256::random()]).await.unwrap().split();
+        assert_eq!(peer, healthy_peer);
+        assert_eq!(bodies, vec![body]);
+    }
 }
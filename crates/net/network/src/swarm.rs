@@ -2,7 +2,10 @@ use crate::{
     listener::{ConnectionListener, ListenerEvent},
     message::{PeerMessage, PeerRequestSender},
     peers::InboundConnectionError,
-    session::{Direction, PendingSessionHandshakeError, SessionEvent, SessionId, SessionManager},
+    session::{
+        Direction, IncomingSessionError, PendingSessionHandshakeError, SessionEvent, SessionId,
+        SessionManager,
+    },
     state::{NetworkState, StateAction},
 };
 use futures::Stream;
@@ -155,6 +158,7 @@ where
                 None
             }
             SessionEvent::ValidMessage { peer_id, message } => {
+                self.state.on_peer_message(&peer_id);
                 Some(SwarmEvent::ValidMessage { peer_id, message })
             }
             SessionEvent::InvalidMessage { peer_id, capabilities, message } => {
@@ -222,7 +226,14 @@ where
                         return Some(SwarmEvent::IncomingTcpConnection { session_id, remote_addr })
                     }
                     Err(err) => {
-                        warn!(target: "net", ?err, "Incoming connection rejected");
+                        match err {
+                            IncomingSessionError::RateLimited(err) => {
+                                trace!(target: "net", ?err, ?remote_addr, "Incoming connection rate limited");
+                            }
+                            err => {
+                                warn!(target: "net", ?err, "Incoming connection rejected");
+                            }
+                        }
                         self.state_mut()
                             .peers_mut()
                             .on_incoming_pending_session_rejected_internally();
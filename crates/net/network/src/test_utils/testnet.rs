@@ -8,7 +8,7 @@ use futures::{FutureExt, StreamExt};
 use pin_project::pin_project;
 use reth_eth_wire::{capability::Capability, DisconnectReason, HelloBuilder};
 use reth_primitives::PeerId;
-use reth_provider::{test_utils::NoopProvider, BlockProvider, HeaderProvider};
+use reth_provider::{test_utils::NoopProvider, BlockProvider, HeaderProvider, NodeDataProvider};
 use secp256k1::SecretKey;
 use std::{
     fmt,
@@ -122,7 +122,7 @@ where
 
 impl<C> Testnet<C>
 where
-    C: BlockProvider + HeaderProvider + Unpin + 'static,
+    C: BlockProvider + HeaderProvider + NodeDataProvider + Unpin + 'static,
 {
     /// Spawns the testnet to a separate task
     pub fn spawn(self) -> TestnetHandle<C> {
@@ -176,7 +176,7 @@ impl<C> fmt::Debug for Testnet<C> {
 
 impl<C> Future for Testnet<C>
 where
-    C: BlockProvider + HeaderProvider + Unpin,
+    C: BlockProvider + HeaderProvider + NodeDataProvider + Unpin,
 {
     type Output = ();
 
@@ -249,7 +249,7 @@ where
 
 impl<C> Future for Peer<C>
 where
-    C: BlockProvider + HeaderProvider + Unpin,
+    C: BlockProvider + HeaderProvider + NodeDataProvider + Unpin,
 {
     type Output = ();
 
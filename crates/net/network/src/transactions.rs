@@ -5,6 +5,10 @@ use crate::{
     manager::NetworkEvent,
     message::{PeerRequest, PeerRequestSender},
     metrics::TransactionsManagerMetrics,
+    seen_transactions::{
+        SeenTransactions, DEFAULT_SEEN_TRANSACTIONS_CAPACITY,
+        DEFAULT_SEEN_TRANSACTIONS_FALSE_POSITIVE_RATE,
+    },
     NetworkHandle,
 };
 use futures::{stream::FuturesUnordered, FutureExt, StreamExt};
@@ -12,10 +16,14 @@ use reth_eth_wire::{
     EthVersion, GetPooledTransactions, NewPooledTransactionHashes, NewPooledTransactionHashes66,
     NewPooledTransactionHashes68, PooledTransactions, Transactions,
 };
-use reth_interfaces::{p2p::error::RequestResult, sync::SyncStateProvider};
+use reth_interfaces::{
+    p2p::error::{RequestError, RequestResult},
+    sync::SyncStateProvider,
+};
 use reth_network_api::{Peers, ReputationChangeKind};
 use reth_primitives::{
-    FromRecoveredTransaction, IntoRecoveredTransaction, PeerId, TransactionSigned, TxHash, H256,
+    FromRecoveredTransaction, IntoRecoveredTransaction, PeerId, TransactionSigned, TxHash,
+    EIP4844_TX_TYPE_ID, H256,
 };
 use reth_rlp::Encodable;
 use reth_transaction_pool::{
@@ -23,12 +31,13 @@ use reth_transaction_pool::{
     ValidPoolTransaction,
 };
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, HashSet},
     future::Future,
     num::NonZeroUsize,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc, oneshot};
 use tokio_stream::wrappers::{ReceiverStream, UnboundedReceiverStream};
@@ -40,6 +49,49 @@ const PEER_TRANSACTION_CACHE_LIMIT: usize = 1024 * 10;
 /// Soft limit for NewPooledTransactions
 const NEW_POOLED_TRANSACTION_HASHES_SOFT_LIMIT: usize = 4096;
 
+/// The maximum size of a blob transaction announced via eth/68 that is still fetched from the peer.
+///
+/// Announcements of larger blob transactions are dropped without requesting the transaction.
+pub const MAX_BLOB_TX_NETWORK_SIZE: u32 = 1024 * 1024;
+
+/// The score penalty for every invalid transaction a peer sent.
+const INVALID_TX_SCORE_PENALTY: i64 = 4;
+
+/// The score penalty for every `GetPooledTransactions` request the peer failed to respond to.
+const TIMEOUT_SCORE_PENALTY: i64 = 2;
+
+/// The score penalty for every transaction the peer sent more than once.
+const DUPLICATE_TX_SCORE_PENALTY: i64 = 1;
+
+/// Configuration for the [`TransactionsManager`].
+#[derive(Debug, Clone)]
+pub struct TransactionsManagerConfig {
+    /// Peers with a transaction score below this are deprioritized for outbound
+    /// `GetPooledTransactions` requests.
+    pub peer_score_deprioritize_threshold: i64,
+    /// Peers with a transaction score below this are disconnected.
+    pub peer_score_disconnect_threshold: i64,
+    /// The interval at which the transaction score of a peer decays.
+    pub peer_score_decay_interval: Duration,
+    /// The estimated number of transactions tracked as recently seen, per generation of the
+    /// rolling bloom filter.
+    pub seen_transactions_capacity: usize,
+    /// The false positive rate of the rolling bloom filter of recently seen transactions.
+    pub seen_transactions_false_positive_rate: f64,
+}
+
+impl Default for TransactionsManagerConfig {
+    fn default() -> Self {
+        Self {
+            peer_score_deprioritize_threshold: -10,
+            peer_score_disconnect_threshold: -50,
+            peer_score_decay_interval: Duration::from_secs(60),
+            seen_transactions_capacity: DEFAULT_SEEN_TRANSACTIONS_CAPACITY,
+            seen_transactions_false_positive_rate: DEFAULT_SEEN_TRANSACTIONS_FALSE_POSITIVE_RATE,
+        }
+    }
+}
+
 /// The future for inserting a function into the pool
 pub type PoolImportFuture = Pin<Box<dyn Future<Output = PoolResult<TxHash>> + Send + 'static>>;
 
@@ -95,6 +147,10 @@ pub struct TransactionsManager<Pool> {
     /// This way we can track incoming transactions and prevent multiple pool imports for the same
     /// transaction
     transactions_by_peers: HashMap<TxHash, Vec<PeerId>>,
+    /// Approximate set of transactions recently received from peers.
+    ///
+    /// Announcements of these transactions are not fetched again.
+    seen_transactions: SeenTransactions,
     /// Transactions that are currently imported into the `Pool`
     pool_imports: FuturesUnordered<PoolImportFuture>,
     /// All the connected peers.
@@ -109,6 +165,8 @@ pub struct TransactionsManager<Pool> {
     transaction_events: UnboundedReceiverStream<NetworkTransactionEvent>,
     /// TransactionsManager metrics
     metrics: TransactionsManagerMetrics,
+    /// Configuration of peer scoring.
+    config: TransactionsManagerConfig,
 }
 
 impl<Pool: TransactionPool> TransactionsManager<Pool> {
@@ -119,6 +177,18 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
         network: NetworkHandle,
         pool: Pool,
         from_network: mpsc::UnboundedReceiver<NetworkTransactionEvent>,
+    ) -> Self {
+        Self::with_config(network, pool, from_network, Default::default())
+    }
+
+    /// Sets up a new instance with the given [`TransactionsManagerConfig`].
+    ///
+    /// Note: This expects an existing [`NetworkManager`](crate::NetworkManager) instance.
+    pub fn with_config(
+        network: NetworkHandle,
+        pool: Pool,
+        from_network: mpsc::UnboundedReceiver<NetworkTransactionEvent>,
+        config: TransactionsManagerConfig,
     ) -> Self {
         let network_events = network.event_listener();
         let (command_tx, command_rx) = mpsc::unbounded_channel();
@@ -132,6 +202,10 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
             network_events,
             inflight_requests: Default::default(),
             transactions_by_peers: Default::default(),
+            seen_transactions: SeenTransactions::new(
+                config.seen_transactions_capacity,
+                config.seen_transactions_false_positive_rate,
+            ),
             pool_imports: Default::default(),
             peers: Default::default(),
             command_tx,
@@ -139,6 +213,7 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
             pending_transactions: ReceiverStream::new(pending),
             transaction_events: UnboundedReceiverStream::new(from_network),
             metrics: Default::default(),
+            config,
         }
     }
 }
@@ -284,6 +359,19 @@ where
         let mut num_already_seen = 0;
 
         if let Some(peer) = self.peers.get_mut(&peer_id) {
+            // eth/68 announcements include the type and size of the transaction, which allows
+            // skipping blob transactions that are too large to fetch
+            let oversized = match &msg {
+                NewPooledTransactionHashes::Eth68(msg) => msg
+                    .iter_announced()
+                    .filter(|tx| {
+                        tx.tx_type == EIP4844_TX_TYPE_ID && tx.size > MAX_BLOB_TX_NETWORK_SIZE
+                    })
+                    .map(|tx| tx.hash)
+                    .collect::<HashSet<_>>(),
+                NewPooledTransactionHashes::Eth66(_) => HashSet::new(),
+            };
+
             let mut hashes = msg.into_hashes();
             // keep track of the transactions the peer knows
             for tx in hashes.iter().copied() {
@@ -292,26 +380,50 @@ where
                 }
             }
 
+            // skip transactions we recently received
+            let num_announced = hashes.len();
+            hashes.retain(|hash| !self.seen_transactions.contains(hash));
+            let num_duplicates = num_announced - hashes.len();
+            if num_duplicates > 0 {
+                self.metrics.duplicate_announcements.increment(num_duplicates as u64);
+            }
+
+            if !oversized.is_empty() {
+                trace!(target: "net::tx", ?peer_id, num_oversized = oversized.len(), "Dropping oversized blob transaction announcements");
+                hashes.retain(|hash| !oversized.contains(hash));
+            }
+
             self.pool.retain_unknown(&mut hashes);
 
-            if hashes.is_empty() {
-                // nothing to request
-                return
+            // peers with a bad score are only asked for transactions that aren't already requested
+            // from another peer
+            peer.score.decay(self.config.peer_score_decay_interval);
+            if peer.score.score() < self.config.peer_score_deprioritize_threshold {
+                hashes.retain(|hash| {
+                    !self.inflight_requests.iter().any(|req| req.hashes.contains(hash))
+                });
             }
 
-            // request the missing transactions
-            let (response, rx) = oneshot::channel();
-            let req = PeerRequest::GetPooledTransactions {
-                request: GetPooledTransactions(hashes),
-                response,
-            };
+            if !hashes.is_empty() {
+                // request the missing transactions
+                let (response, rx) = oneshot::channel();
+                let req = PeerRequest::GetPooledTransactions {
+                    request: GetPooledTransactions(hashes.clone()),
+                    response,
+                };
 
-            if peer.request_tx.try_send(req).is_ok() {
-                self.inflight_requests.push(GetPooledTxRequest { peer_id, response: rx })
+                if peer.request_tx.try_send(req).is_ok() {
+                    self.inflight_requests.push(GetPooledTxRequest {
+                        peer_id,
+                        hashes,
+                        response: rx,
+                    })
+                }
             }
         }
 
         if num_already_seen > 0 {
+            self.update_peer_score(peer_id, |score| score.duplicate_count += num_already_seen);
             self.report_bad_message(peer_id);
         }
     }
@@ -357,6 +469,7 @@ where
                         ),
                         request_tx: messages,
                         version,
+                        score: PeerTransactionScore::new(),
                     },
                 );
 
@@ -400,7 +513,7 @@ where
         }
 
         // tracks the quality of the given transactions
-        let mut has_bad_transactions = false;
+        let mut num_bad_transactions = 0;
         let mut num_already_seen = 0;
 
         if let Some(peer) = self.peers.get_mut(&peer_id) {
@@ -409,7 +522,7 @@ where
                 let tx = if let Some(tx) = tx.into_ecrecovered() {
                     tx
                 } else {
-                    has_bad_transactions = true;
+                    num_bad_transactions += 1;
                     continue
                 };
 
@@ -421,6 +534,8 @@ where
                     num_already_seen += 1;
                 }
 
+                self.seen_transactions.insert(tx.hash);
+
                 match self.transactions_by_peers.entry(tx.hash) {
                     Entry::Occupied(mut entry) => {
                         // transaction was already inserted
@@ -444,7 +559,11 @@ where
             }
         }
 
-        if has_bad_transactions || num_already_seen > 0 {
+        if num_bad_transactions > 0 || num_already_seen > 0 {
+            self.update_peer_score(peer_id, |score| {
+                score.invalid_tx_received += num_bad_transactions;
+                score.duplicate_count += num_already_seen;
+            });
             self.report_bad_message(peer_id);
         }
     }
@@ -453,13 +572,36 @@ where
         self.network.reputation_change(peer_id, ReputationChangeKind::BadTransactions);
     }
 
+    /// Applies the given update to the [`PeerTransactionScore`] of the peer and disconnects the
+    /// peer if its score dropped below the configured threshold.
+    fn update_peer_score(
+        &mut self,
+        peer_id: PeerId,
+        update: impl FnOnce(&mut PeerTransactionScore),
+    ) {
+        let Some(peer) = self.peers.get_mut(&peer_id) else { return };
+        peer.score.decay(self.config.peer_score_decay_interval);
+        update(&mut peer.score);
+
+        let score = peer.score.score();
+        if score < self.config.peer_score_disconnect_threshold {
+            trace!(target: "net::tx", ?peer_id, score, "Disconnecting peer with bad transaction score");
+            self.network.disconnect_peer(peer_id);
+        }
+    }
+
     fn on_good_import(&mut self, hash: TxHash) {
-        self.transactions_by_peers.remove(&hash);
+        if let Some(peers) = self.transactions_by_peers.remove(&hash) {
+            for peer_id in peers {
+                self.update_peer_score(peer_id, |score| score.valid_tx_received += 1);
+            }
+        }
     }
 
     fn on_bad_import(&mut self, hash: TxHash) {
         if let Some(peers) = self.transactions_by_peers.remove(&hash) {
             for peer_id in peers {
+                self.update_peer_score(peer_id, |score| score.invalid_tx_received += 1);
                 self.report_bad_message(peer_id);
             }
         }
@@ -505,6 +647,10 @@ where
                 Poll::Ready(Ok(Ok(txs))) => {
                     this.import_transactions(req.peer_id, txs.0, TransactionSource::Response);
                 }
+                Poll::Ready(Ok(Err(RequestError::Timeout))) => {
+                    this.update_peer_score(req.peer_id, |score| score.timeout_count += 1);
+                    this.report_bad_message(req.peer_id);
+                }
                 Poll::Ready(Ok(Err(_))) => {
                     this.report_bad_message(req.peer_id);
                 }
@@ -632,6 +778,7 @@ impl TransactionSource {
 #[allow(missing_docs)]
 struct GetPooledTxRequest {
     peer_id: PeerId,
+    hashes: Vec<TxHash>,
     response: oneshot::Receiver<RequestResult<PooledTransactions>>,
 }
 
@@ -643,6 +790,67 @@ struct Peer {
     request_tx: PeerRequestSender,
     /// negotiated version of the session.
     version: EthVersion,
+    /// The quality of the transactions the peer sent us.
+    score: PeerTransactionScore,
+}
+
+/// Tracks the quality of the transactions a peer sent us.
+///
+/// All counters are halved every [`TransactionsManagerConfig::peer_score_decay_interval`], so that
+/// peers can recover from transient bad behaviour.
+#[derive(Debug, Clone)]
+struct PeerTransactionScore {
+    /// Number of transactions that were successfully imported into the pool.
+    valid_tx_received: u64,
+    /// Number of transactions that couldn't be recovered or were rejected by the pool.
+    invalid_tx_received: u64,
+    /// Number of `GetPooledTransactions` requests that timed out.
+    timeout_count: u64,
+    /// Number of transactions the peer sent or announced more than once.
+    duplicate_count: u64,
+    /// When the counters were last decayed.
+    last_decay: Instant,
+}
+
+// === impl PeerTransactionScore ===
+
+impl PeerTransactionScore {
+    fn new() -> Self {
+        Self {
+            valid_tx_received: 0,
+            invalid_tx_received: 0,
+            timeout_count: 0,
+            duplicate_count: 0,
+            last_decay: Instant::now(),
+        }
+    }
+
+    /// Returns the current score, negative if the peer sent more bad than good transactions.
+    fn score(&self) -> i64 {
+        let penalty = self.invalid_tx_received as i64 * INVALID_TX_SCORE_PENALTY +
+            self.timeout_count as i64 * TIMEOUT_SCORE_PENALTY +
+            self.duplicate_count as i64 * DUPLICATE_TX_SCORE_PENALTY;
+        self.valid_tx_received as i64 - penalty
+    }
+
+    /// Halves all counters for every full `interval` that elapsed since the last decay.
+    fn decay(&mut self, interval: Duration) {
+        if interval.is_zero() {
+            return
+        }
+        let elapsed = self.last_decay.elapsed();
+        let periods = elapsed.as_nanos() / interval.as_nanos();
+        if periods == 0 {
+            return
+        }
+
+        let shift = periods.min(63) as u32;
+        self.valid_tx_received >>= shift;
+        self.invalid_tx_received >>= shift;
+        self.timeout_count >>= shift;
+        self.duplicate_count >>= shift;
+        self.last_decay = Instant::now();
+    }
 }
 
 /// Commands to send to the [`TransactionsManager`](crate::transactions::TransactionsManager)
@@ -672,7 +880,7 @@ mod tests {
     use crate::{NetworkConfigBuilder, NetworkManager};
     use reth_interfaces::sync::{SyncState, SyncStateUpdater};
     use reth_provider::test_utils::NoopProvider;
-    use reth_transaction_pool::test_utils::testing_pool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
     use secp256k1::SecretKey;
 
     #[tokio::test(flavor = "multi_thread")]
@@ -705,4 +913,171 @@ mod tests {
 
         assert!(pool.is_empty());
     }
+
+    /// Returns a [TransactionsManager] of a spawned network with a testing pool.
+    async fn new_transactions_manager() -> (NetworkHandle, TransactionsManager<TestPool>, TestPool)
+    {
+        reth_tracing::init_test_tracing();
+
+        let secret_key = SecretKey::new(&mut rand::thread_rng());
+
+        let client = NoopProvider::default();
+        let pool = testing_pool();
+        let config = NetworkConfigBuilder::new(secret_key).build(client);
+        let (handle, network, transactions, _) = NetworkManager::new(config)
+            .await
+            .unwrap()
+            .into_builder()
+            .transactions(pool.clone())
+            .split_with_handle();
+
+        tokio::task::spawn(network);
+
+        (handle, transactions, pool)
+    }
+
+    /// Adds a new peer to the manager and returns the receiving end of its session channel.
+    fn insert_peer(
+        transactions: &mut TransactionsManager<TestPool>,
+        version: EthVersion,
+        score: PeerTransactionScore,
+    ) -> (PeerId, mpsc::Receiver<PeerRequest>) {
+        let peer_id = PeerId::random();
+        let (to_session_tx, to_session_rx) = mpsc::channel(1);
+        transactions.peers.insert(
+            peer_id,
+            Peer {
+                transactions: LruCache::new(
+                    NonZeroUsize::new(PEER_TRANSACTION_CACHE_LIMIT).unwrap(),
+                ),
+                request_tx: PeerRequestSender::new(peer_id, to_session_tx),
+                version,
+                score,
+            },
+        );
+        (peer_id, to_session_rx)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_drop_oversized_blob_tx_announcements() {
+        let (_handle, mut transactions, _pool) = new_transactions_manager().await;
+
+        let (peer_id, mut to_session_rx) =
+            insert_peer(&mut transactions, EthVersion::Eth68, PeerTransactionScore::new());
+
+        let oversized = H256::random();
+        transactions.on_new_pooled_transaction_hashes(
+            peer_id,
+            NewPooledTransactionHashes68 {
+                types: vec![EIP4844_TX_TYPE_ID],
+                sizes: vec![MAX_BLOB_TX_NETWORK_SIZE as usize + 1],
+                hashes: vec![oversized],
+            }
+            .into(),
+        );
+
+        // the announcement is tracked but not fetched
+        assert!(transactions.peers[&peer_id].transactions.contains(&oversized));
+        assert!(to_session_rx.try_recv().is_err());
+
+        let blob = H256::random();
+        transactions.on_new_pooled_transaction_hashes(
+            peer_id,
+            NewPooledTransactionHashes68 {
+                types: vec![EIP4844_TX_TYPE_ID, EIP4844_TX_TYPE_ID],
+                sizes: vec![
+                    MAX_BLOB_TX_NETWORK_SIZE as usize,
+                    MAX_BLOB_TX_NETWORK_SIZE as usize + 1,
+                ],
+                hashes: vec![blob, H256::random()],
+            }
+            .into(),
+        );
+
+        match to_session_rx.try_recv().unwrap() {
+            PeerRequest::GetPooledTransactions { request, .. } => {
+                assert_eq!(request.0, vec![blob]);
+            }
+            req => panic!("unexpected request: {req:?}"),
+        }
+    }
+
+    #[test]
+    fn test_peer_transaction_score_decay() {
+        let mut score = PeerTransactionScore::new();
+        score.invalid_tx_received = 8;
+        score.duplicate_count = 4;
+        assert_eq!(score.score(), -36);
+
+        // not a full interval yet
+        score.decay(Duration::from_secs(60));
+        assert_eq!(score.score(), -36);
+
+        score.last_decay -= Duration::from_secs(120);
+        score.decay(Duration::from_secs(60));
+        assert_eq!(score.invalid_tx_received, 2);
+        assert_eq!(score.duplicate_count, 1);
+        assert_eq!(score.score(), -9);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_deprioritized_peer_skips_inflight_hashes() {
+        let (_handle, mut transactions, _pool) = new_transactions_manager().await;
+
+        let mut add_peer = |invalid_tx_received| {
+            let mut score = PeerTransactionScore::new();
+            score.invalid_tx_received = invalid_tx_received;
+            insert_peer(&mut transactions, EthVersion::Eth66, score)
+        };
+        let (good_peer, mut good_rx) = add_peer(0);
+        let (bad_peer, mut bad_rx) = add_peer(5);
+
+        let inflight = H256::random();
+        transactions.on_new_pooled_transaction_hashes(
+            good_peer,
+            NewPooledTransactionHashes66(vec![inflight]).into(),
+        );
+        assert!(good_rx.try_recv().is_ok());
+
+        // the deprioritized peer is only asked for the hash that isn't requested yet
+        let unknown = H256::random();
+        transactions.on_new_pooled_transaction_hashes(
+            bad_peer,
+            NewPooledTransactionHashes66(vec![inflight, unknown]).into(),
+        );
+        match bad_rx.try_recv().unwrap() {
+            PeerRequest::GetPooledTransactions { request, .. } => {
+                assert_eq!(request.0, vec![unknown]);
+            }
+            req => panic!("unexpected request: {req:?}"),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_skip_recently_seen_announcements() {
+        let (_handle, mut transactions, _pool) = new_transactions_manager().await;
+
+        let (peer_id, mut to_session_rx) =
+            insert_peer(&mut transactions, EthVersion::Eth66, PeerTransactionScore::new());
+
+        let seen = H256::random();
+        transactions.seen_transactions.insert(seen);
+        transactions.on_new_pooled_transaction_hashes(
+            peer_id,
+            NewPooledTransactionHashes66(vec![seen]).into(),
+        );
+        assert!(to_session_rx.try_recv().is_err());
+
+        let unseen = H256::random();
+        transactions.on_new_pooled_transaction_hashes(
+            peer_id,
+            NewPooledTransactionHashes66(vec![seen, unseen]).into(),
+        );
+        match to_session_rx.try_recv().unwrap() {
+            PeerRequest::GetPooledTransactions { request, .. } => {
+                assert_eq!(request.0, vec![unseen]);
+            }
+            req => panic!("unexpected request: {req:?}"),
+        }
+    }
 }
@@ -189,8 +189,10 @@ async fn test_get_peer_by_id() {
     handle0.add_peer(*handle1.peer_id(), handle1.local_addr());
     let _ = listener0.next_session_established().await.unwrap();
 
-    let peer = handle0.get_peer_by_id(*handle1.peer_id()).await.unwrap();
-    assert!(peer.is_some());
+    let peer = handle0.get_peer_by_id(*handle1.peer_id()).await.unwrap().unwrap();
+    assert_eq!(peer.remote_id, *handle1.peer_id());
+    assert_eq!(peer.remote_addr.ip(), handle1.local_addr().ip());
+    assert!(peer.local_addr.is_some());
 
     let peer = handle0.get_peer_by_id(*handle2.peer_id()).await.unwrap();
     assert!(peer.is_none());
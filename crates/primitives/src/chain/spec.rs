@@ -1,5 +1,5 @@
 use crate::{
-    constants::{EIP1559_INITIAL_BASE_FEE, EMPTY_WITHDRAWALS},
+    constants::{EIP1559_INITIAL_BASE_FEE, EMPTY_WITHDRAWALS, MAINNET_TERMINAL_TOTAL_DIFFICULTY},
     forkid::ForkFilterKey,
     header::Head,
     proofs::genesis_state_root,
@@ -39,7 +39,7 @@ pub static MAINNET: Lazy<ChainSpec> = Lazy::new(|| ChainSpec {
             Hardfork::Paris,
             ForkCondition::TTD {
                 fork_block: None,
-                total_difficulty: U256::from(58_750_000_000_000_000_000_000_u128),
+                total_difficulty: U256::from(MAINNET_TERMINAL_TOTAL_DIFFICULTY),
             },
         ),
     ]),
@@ -292,11 +292,15 @@ impl From<EthersGenesis> for ChainSpec {
         }
 
         // Time-based hardforks
-        let time_hardforks = genesis
-            .config
-            .shanghai_time
-            .map(|time| (Hardfork::Shanghai, ForkCondition::Timestamp(time)))
-            .into_iter()
+        let time_hardfork_opts = vec![
+            (Hardfork::Shanghai, genesis.config.shanghai_time),
+            (Hardfork::Cancun, genesis.config.cancun_time),
+        ];
+        let time_hardforks = time_hardfork_opts
+            .iter()
+            .filter_map(|(hardfork, opt)| {
+                opt.map(|time| (*hardfork, ForkCondition::Timestamp(time)))
+            })
             .collect::<BTreeMap<_, _>>();
 
         hardforks.extend(time_hardforks);
@@ -377,6 +381,31 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Activate Berlin at the given block.
+    pub fn berlin_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(Hardfork::Berlin, ForkCondition::Block(block))
+    }
+
+    /// Activate London at the given block.
+    pub fn london_at_block(self, block: BlockNumber) -> Self {
+        self.with_fork(Hardfork::London, ForkCondition::Block(block))
+    }
+
+    /// Activate Paris once the given terminal total difficulty is reached.
+    pub fn merge_at_ttd(self, total_difficulty: U256) -> Self {
+        self.with_fork(Hardfork::Paris, ForkCondition::TTD { fork_block: None, total_difficulty })
+    }
+
+    /// Activate Shanghai at the given timestamp.
+    pub fn shanghai_at_timestamp(self, timestamp: u64) -> Self {
+        self.with_fork(Hardfork::Shanghai, ForkCondition::Timestamp(timestamp))
+    }
+
+    /// Activate Cancun at the given timestamp.
+    pub fn cancun_at_timestamp(self, timestamp: u64) -> Self {
+        self.with_fork(Hardfork::Cancun, ForkCondition::Timestamp(timestamp))
+    }
+
     /// Enable Frontier at genesis.
     pub fn frontier_activated(mut self) -> Self {
         self.hardforks.insert(Hardfork::Frontier, ForkCondition::Block(0));
@@ -456,6 +485,13 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Enable Cancun at genesis.
+    pub fn cancun_activated(mut self) -> Self {
+        self = self.shanghai_activated();
+        self.hardforks.insert(Hardfork::Cancun, ForkCondition::Timestamp(0));
+        self
+    }
+
     /// Build the resulting [`ChainSpec`].
     ///
     /// # Panics
@@ -905,6 +941,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn custom_fork_schedule() {
+        let spec = ChainSpecBuilder::default()
+            .chain(Chain::Id(1337))
+            .genesis(Genesis::default())
+            .istanbul_activated()
+            .berlin_at_block(10)
+            .london_at_block(20)
+            .merge_at_ttd(U256::from(1000))
+            .shanghai_at_timestamp(1_000)
+            .cancun_at_timestamp(2_000)
+            .build();
+
+        assert_eq!(spec.fork(Hardfork::Berlin), ForkCondition::Block(10));
+        assert!(!spec.fork(Hardfork::London).active_at_block(19));
+        assert!(spec.fork(Hardfork::London).active_at_block(20));
+        assert!(spec.fork(Hardfork::Paris).active_at_ttd(U256::from(1000), U256::ZERO));
+        assert!(!spec.fork(Hardfork::Shanghai).active_at_timestamp(999));
+        assert!(spec.fork(Hardfork::Shanghai).active_at_timestamp(1_000));
+        assert_eq!(spec.fork(Hardfork::Cancun), ForkCondition::Timestamp(2_000));
+    }
+
     /// Checks that the fork is not active at a terminal ttd block.
     #[test]
     fn check_terminal_ttd() {
@@ -946,6 +1004,7 @@ mod tests {
             "arrowGlacierBlock": 0,
             "grayGlacierBlock": 0,
             "shanghaiTime": 0,
+            "cancunTime": 1,
             "terminalTotalDifficulty": 0,
             "terminalTotalDifficultyPassed": true,
             "ethash": {}
@@ -1040,6 +1099,10 @@ mod tests {
             chainspec.hardforks.get(&Hardfork::Shanghai).unwrap(),
             &ForkCondition::Timestamp(0)
         );
+        assert_eq!(
+            chainspec.hardforks.get(&Hardfork::Cancun).unwrap(),
+            &ForkCondition::Timestamp(1)
+        );
 
         // alloc key -> expected rlp mapping
         let key_rlp = vec![
@@ -1,11 +1,15 @@
 //! Ethereum protocol-related constants
 
-use crate::H256;
+use crate::{Address, H160, H256};
 use hex_literal::hex;
 
 /// The first four bytes of the call data for a function call specifies the function to be called.
 pub const SELECTOR_LEN: usize = 4;
 
+/// The terminal total difficulty of mainnet, after which blocks are produced by proof-of-stake, as
+/// defined in [EIP-3675](https://eips.ethereum.org/EIPS/eip-3675)
+pub const MAINNET_TERMINAL_TOTAL_DIFFICULTY: u128 = 58_750_000_000_000_000_000_000;
+
 /// Initial base fee as defined in [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)
 pub const EIP1559_INITIAL_BASE_FEE: u64 = 1_000_000_000;
 
@@ -15,6 +19,28 @@ pub const EIP1559_BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
 /// Elasticity multiplier as defined in [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)
 pub const EIP1559_ELASTICITY_MULTIPLIER: u64 = 2;
 
+/// The blob gas used by each blob as defined in [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+pub const EIP4844_DATA_GAS_PER_BLOB: u64 = 131_072;
+
+/// The target blob gas used by a block as defined in
+/// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+pub const EIP4844_TARGET_DATA_GAS_PER_BLOCK: u64 = 3 * EIP4844_DATA_GAS_PER_BLOB;
+
+/// Minimum price of blob gas as defined in [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+pub const EIP4844_MIN_BLOB_GASPRICE: u64 = 1;
+
+/// Controls the maximum rate of change of the blob gas price as defined in
+/// [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)
+pub const EIP4844_BLOB_GASPRICE_UPDATE_FRACTION: u64 = 3_338_477;
+
+/// The address of the beacon roots contract, which stores the roots of the parent beacon blocks
+/// as defined in [EIP-4788](https://eips.ethereum.org/EIPS/eip-4788)
+pub const BEACON_ROOTS_ADDRESS: Address = H160(hex!("000f3df6d732807ef1319fb7b8bb8522d0beac02"));
+
+/// Number of beacon block roots the beacon roots contract keeps as defined in
+/// [EIP-4788](https://eips.ethereum.org/EIPS/eip-4788)
+pub const BEACON_ROOTS_HISTORY_BUFFER_LENGTH: u64 = 8191;
+
 /// Multiplier for converting gwei to wei.
 pub const GWEI_TO_WEI: u64 = 1_000_000_000;
 
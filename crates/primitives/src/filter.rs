@@ -129,6 +129,10 @@ pub struct Filter {
     pub address: Option<ValueOrArray<Address>>,
     /// Topics (maxmimum of 4)
     pub topics: [Option<Topic>; 4],
+    /// Token of the page of logs to return, this is a reth specific extension of `eth_getLogs`.
+    ///
+    /// An empty token requests the first page.
+    pub page_token: Option<String>,
 }
 
 impl Filter {
@@ -378,7 +382,7 @@ impl Serialize for Filter {
     where
         S: Serializer,
     {
-        let mut s = serializer.serialize_struct("Filter", 5)?;
+        let mut s = serializer.serialize_struct("Filter", 6)?;
         match self.block_option {
             FilterBlockOption::Range { from_block, to_block } => {
                 if let Some(ref from_block) = from_block {
@@ -410,6 +414,10 @@ impl Serialize for Filter {
         }
         s.serialize_field("topics", &filtered_topics)?;
 
+        if let Some(ref page_token) = self.page_token {
+            s.serialize_field("pageToken", page_token)?;
+        }
+
         s.end()
     }
 }
@@ -437,6 +445,7 @@ impl<'de> Deserialize<'de> for Filter {
                 let mut block_hash: Option<Option<H256>> = None;
                 let mut address: Option<Option<ValueOrArray<Address>>> = None;
                 let mut topics: Option<Option<Vec<Option<Topic>>>> = None;
+                let mut page_token: Option<Option<String>> = None;
 
                 while let Some(key) = map.next_key::<String>()? {
                     match key.as_str() {
@@ -485,11 +494,24 @@ impl<'de> Deserialize<'de> for Filter {
                             }
                             topics = Some(map.next_value()?)
                         }
+                        "pageToken" => {
+                            if page_token.is_some() {
+                                return Err(serde::de::Error::duplicate_field("pageToken"))
+                            }
+                            page_token = Some(map.next_value()?)
+                        }
 
                         key => {
                             return Err(serde::de::Error::unknown_field(
                                 key,
-                                &["fromBlock", "toBlock", "address", "topics", "blockHash"],
+                                &[
+                                    "fromBlock",
+                                    "toBlock",
+                                    "address",
+                                    "topics",
+                                    "blockHash",
+                                    "pageToken",
+                                ],
                             ))
                         }
                     }
@@ -500,6 +522,7 @@ impl<'de> Deserialize<'de> for Filter {
                 let block_hash = block_hash.unwrap_or_default();
                 let address = address.unwrap_or_default();
                 let topics_vec = topics.flatten().unwrap_or_default();
+                let page_token = page_token.flatten();
 
                 // maximum allowed filter len
                 if topics_vec.len() > 4 {
@@ -516,7 +539,7 @@ impl<'de> Deserialize<'de> for Filter {
                     FilterBlockOption::Range { from_block, to_block }
                 };
 
-                Ok(Filter { block_option, address, topics })
+                Ok(Filter { block_option, address, topics, page_token })
             }
         }
 
@@ -983,6 +1006,7 @@ mod tests {
                 None,
                 None,
             ],
+            page_token: None,
         };
         let filtered_params = FilteredParams::new(Some(filter.clone()));
 
@@ -1021,8 +1045,12 @@ mod tests {
 
     #[test]
     fn can_match_empty_topics() {
-        let filter =
-            Filter { block_option: Default::default(), address: None, topics: Default::default() };
+        let filter = Filter {
+            block_option: Default::default(),
+            address: None,
+            topics: Default::default(),
+            page_token: None,
+        };
 
         let filtered_params = FilteredParams::new(Some(filter));
         let topics = Some(filtered_params.flat_topics);
@@ -1050,6 +1078,7 @@ mod tests {
                 None,
                 None,
             ],
+            page_token: None,
         };
         let filtered_params = FilteredParams::new(Some(filter.clone()));
         let topics = Some(filtered_params.flat_topics);
@@ -1076,6 +1105,7 @@ mod tests {
             block_option: Default::default(),
             address: None,
             topics: [None, Some(ValueOrArray::Array(vec![Some(topic2), Some(topic3)])), None, None],
+            page_token: None,
         };
         let filtered_params = FilteredParams::new(Some(filter));
         let topics = Some(filtered_params.flat_topics);
@@ -1097,6 +1127,7 @@ mod tests {
                 None,
                 None,
             ],
+            page_token: None,
         };
         let filtered_params = FilteredParams::new(Some(filter));
         let topics_input = Some(filtered_params.flat_topics);
@@ -1114,6 +1145,7 @@ mod tests {
             block_option: Default::default(),
             address: Some(ValueOrArray::Value(rng_address)),
             topics: Default::default(),
+            page_token: None,
         };
         let address_bloom = FilteredParams::address_filter(&filter.address);
         assert!(FilteredParams::matches_address(
@@ -1130,6 +1162,7 @@ mod tests {
             block_option: Default::default(),
             address: Some(ValueOrArray::Value(rng_address)),
             topics: Default::default(),
+            page_token: None,
         };
         let address_bloom = FilteredParams::address_filter(&filter.address);
         assert!(!FilteredParams::matches_address(
@@ -1182,6 +1215,7 @@ mod tests {
                     ))),
                     None,
                 ],
+                page_token: None,
             }
         );
     }
@@ -1207,6 +1241,7 @@ mod tests {
                 },
                 address: None,
                 topics: [None, None, None, None,],
+                page_token: None,
             }
         );
     }
@@ -39,6 +39,8 @@ pub enum Hardfork {
     Paris,
     /// Shanghai.
     Shanghai,
+    /// Cancun.
+    Cancun,
 }
 
 impl Hardfork {
@@ -82,6 +84,7 @@ impl FromStr for Hardfork {
             "grayglacier" => Hardfork::GrayGlacier,
             "paris" => Hardfork::Paris,
             "shanghai" => Hardfork::Shanghai,
+            "cancun" => Hardfork::Cancun,
             _ => return Err(format!("Unknown hardfork: {s}")),
         };
         Ok(hardfork)
@@ -120,6 +123,7 @@ mod tests {
             "grayglacier",
             "PARIS",
             "ShAnGhAI",
+            "CaNcUn",
         ];
         let expected_hardforks = [
             Hardfork::Frontier,
@@ -138,6 +142,7 @@ mod tests {
             Hardfork::GrayGlacier,
             Hardfork::Paris,
             Hardfork::Shanghai,
+            Hardfork::Cancun,
         ];
 
         let hardforks: Vec<Hardfork> =
@@ -6,10 +6,15 @@ use crate::{
 use bytes::{Buf, BufMut, BytesMut};
 use ethers_core::types::{Block, H256 as EthersH256, H64};
 use reth_codecs::{add_arbitrary_tests, derive_arbitrary, main_codec, Compact};
-use reth_rlp::{length_of_length, Decodable, Encodable, EMPTY_STRING_CODE};
+use reth_rlp::{length_of_length, Decodable, Encodable, EMPTY_LIST_CODE, EMPTY_STRING_CODE};
 use serde::{Deserialize, Serialize};
 use std::ops::{Deref, DerefMut};
 
+mod validation;
+pub use validation::{
+    calculate_next_block_base_fee, HeaderValidationError, HeaderValidator, MAXIMUM_EXTRA_DATA_SIZE,
+};
+
 /// Describes the current head block.
 ///
 /// The head block is the highest fully synced block.
@@ -87,6 +92,16 @@ pub struct Header {
     /// above the gas target, and decreasing when blocks are below the gas target. The base fee per
     /// gas is burned.
     pub base_fee_per_gas: Option<u64>,
+    /// The total amount of blob gas consumed by the transactions within the block.
+    /// <https://eips.ethereum.org/EIPS/eip-4844>
+    pub blob_gas_used: Option<u64>,
+    /// A running total of blob gas consumed in excess of the target, prior to the block. Blocks
+    /// with above-target blob gas consumption increase this value.
+    /// <https://eips.ethereum.org/EIPS/eip-4844>
+    pub excess_blob_gas: Option<u64>,
+    /// The hash of the parent beacon block's root.
+    /// <https://eips.ethereum.org/EIPS/eip-4788>
+    pub parent_beacon_block_root: Option<H256>,
     /// An arbitrary byte array containing data relevant to this block. This must be 32 bytes or
     /// fewer; formally Hx.
     pub extra_data: Bytes,
@@ -112,6 +127,9 @@ impl Default for Header {
             nonce: 0,
             base_fee_per_gas: None,
             withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
         }
     }
 }
@@ -145,6 +163,15 @@ impl Header {
         self.transactions_root == EMPTY_ROOT
     }
 
+    /// Returns `true` if the block has a block transition for the storage changes of the EIP-4788
+    /// beacon root contract call.
+    ///
+    /// The changes are part of the transition of the first transaction of the block, so only blocks
+    /// without transactions need a block transition for them.
+    pub fn has_beacon_root_block_transition(&self) -> bool {
+        self.parent_beacon_block_root.is_some() && self.transaction_root_is_empty()
+    }
+
     /// Seal the header with a known hash.
     ///
     /// WARNING: This method does not perform validation whether the hash is correct.
@@ -178,15 +205,37 @@ impl Header {
 
         if let Some(base_fee) = self.base_fee_per_gas {
             length += U256::from(base_fee).length();
-        } else if self.withdrawals_root.is_some() {
+        } else if self.withdrawals_root.is_some() || self.has_cancun_fields() {
             length += 1; // EMTY STRING CODE
         }
         if let Some(root) = self.withdrawals_root {
             length += root.length();
+        } else if self.has_cancun_fields() {
+            length += 1; // EMTY STRING CODE
+        }
+        if let Some(blob_gas_used) = self.blob_gas_used {
+            length += U256::from(blob_gas_used).length();
+        } else if self.excess_blob_gas.is_some() || self.parent_beacon_block_root.is_some() {
+            length += 1; // EMPTY LIST CODE
+        }
+        if let Some(excess_blob_gas) = self.excess_blob_gas {
+            length += U256::from(excess_blob_gas).length();
+        } else if self.parent_beacon_block_root.is_some() {
+            length += 1; // EMPTY LIST CODE
+        }
+        if let Some(root) = self.parent_beacon_block_root {
+            length += root.length();
         }
 
         length
     }
+
+    /// Returns `true` if any of the fields introduced with the Cancun hardfork is set.
+    fn has_cancun_fields(&self) -> bool {
+        self.blob_gas_used.is_some() ||
+            self.excess_blob_gas.is_some() ||
+            self.parent_beacon_block_root.is_some()
+    }
 }
 
 impl Encodable for Header {
@@ -214,12 +263,34 @@ impl Encodable for Header {
         // but withdrawals root is present.
         if let Some(ref base_fee) = self.base_fee_per_gas {
             U256::from(*base_fee).encode(out);
-        } else if self.withdrawals_root.is_some() {
+        } else if self.withdrawals_root.is_some() || self.has_cancun_fields() {
             out.put_u8(EMPTY_STRING_CODE);
         }
 
+        // Encode withdrawals root. Put empty string if withdrawals root is missing,
+        // but any of the Cancun fields is present.
         if let Some(ref root) = self.withdrawals_root {
             root.encode(out);
+        } else if self.has_cancun_fields() {
+            out.put_u8(EMPTY_STRING_CODE);
+        }
+
+        // Encode the blob gas fields. Put an empty list if a value is missing, but any of the
+        // following fields is present, since an empty string is a valid encoding of zero.
+        if let Some(ref blob_gas_used) = self.blob_gas_used {
+            U256::from(*blob_gas_used).encode(out);
+        } else if self.excess_blob_gas.is_some() || self.parent_beacon_block_root.is_some() {
+            out.put_u8(EMPTY_LIST_CODE);
+        }
+
+        if let Some(ref excess_blob_gas) = self.excess_blob_gas {
+            U256::from(*excess_blob_gas).encode(out);
+        } else if self.parent_beacon_block_root.is_some() {
+            out.put_u8(EMPTY_LIST_CODE);
+        }
+
+        if let Some(ref root) = self.parent_beacon_block_root {
+            root.encode(out);
         }
     }
 
@@ -256,6 +327,9 @@ impl Decodable for Header {
             nonce: H64::decode(buf)?.to_low_u64_be(),
             base_fee_per_gas: None,
             withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
         };
         if started_len - buf.len() < rlp_head.payload_length {
             if buf.first().map(|b| *b == EMPTY_STRING_CODE).unwrap_or_default() {
@@ -265,7 +339,28 @@ impl Decodable for Header {
             }
         }
         if started_len - buf.len() < rlp_head.payload_length {
-            this.withdrawals_root = Some(Decodable::decode(buf)?);
+            if buf.first().map(|b| *b == EMPTY_STRING_CODE).unwrap_or_default() {
+                buf.advance(1)
+            } else {
+                this.withdrawals_root = Some(Decodable::decode(buf)?);
+            }
+        }
+        if started_len - buf.len() < rlp_head.payload_length {
+            if buf.first().map(|b| *b == EMPTY_LIST_CODE).unwrap_or_default() {
+                buf.advance(1)
+            } else {
+                this.blob_gas_used = Some(U256::decode(buf)?.to::<u64>());
+            }
+        }
+        if started_len - buf.len() < rlp_head.payload_length {
+            if buf.first().map(|b| *b == EMPTY_LIST_CODE).unwrap_or_default() {
+                buf.advance(1)
+            } else {
+                this.excess_blob_gas = Some(U256::decode(buf)?.to::<u64>());
+            }
+        }
+        if started_len - buf.len() < rlp_head.payload_length {
+            this.parent_beacon_block_root = Some(Decodable::decode(buf)?);
         }
         let consumed = started_len - buf.len();
         if consumed != rlp_head.payload_length {
@@ -344,6 +439,9 @@ impl From<&Block<EthersH256>> for Header {
             ommers_hash: block.uncles_hash.0.into(),
             gas_used: block.gas_used.as_u64(),
             withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
             logs_bloom: block.logs_bloom.unwrap_or_default().0.into(),
         }
     }
@@ -491,7 +589,7 @@ impl From<HeadersDirection> for bool {
 #[cfg(test)]
 mod tests {
     use super::{Bytes, Decodable, Encodable, Header, H256};
-    use crate::{Address, HeadersDirection, U256};
+    use crate::{proofs::EMPTY_ROOT, Address, HeadersDirection, U256};
     use ethers_core::utils::hex::{self, FromHex};
     use std::str::FromStr;
 
@@ -542,6 +640,9 @@ mod tests {
             nonce: 0,
             base_fee_per_gas: Some(0x036b_u64),
             withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
         };
         assert_eq!(header.hash_slow(), expected_hash);
     }
@@ -620,6 +721,33 @@ mod tests {
         assert_eq!(header.hash_slow(), expected_hash);
     }
 
+    #[test]
+    fn test_encode_decode_block_header_with_cancun_fields() {
+        let header = Header {
+            number: 0x01,
+            gas_limit: 0x1c9c380,
+            timestamp: 0x6553f100,
+            base_fee_per_gas: Some(0x07),
+            withdrawals_root: Some(EMPTY_ROOT),
+            blob_gas_used: Some(0x020000),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(H256::repeat_byte(0x55)),
+            ..Default::default()
+        };
+        let mut data = vec![];
+        header.encode(&mut data);
+        assert_eq!(header.length(), data.len());
+        assert_eq!(<Header as Decodable>::decode(&mut data.as_slice()).unwrap(), header);
+
+        // Missing fields are encoded as placeholders if any of the following fields is set
+        let header =
+            Header { withdrawals_root: None, blob_gas_used: None, excess_blob_gas: None, ..header };
+        let mut data = vec![];
+        header.encode(&mut data);
+        assert_eq!(header.length(), data.len());
+        assert_eq!(<Header as Decodable>::decode(&mut data.as_slice()).unwrap(), header);
+    }
+
     #[test]
     fn sanity_direction() {
         let reverse = true;
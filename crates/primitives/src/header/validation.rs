@@ -0,0 +1,353 @@
+//! Validation of a header against its parent.
+
+use crate::{constants, ChainSpec, Hardfork, Header, U256};
+use std::{cmp::Ordering, ops::Deref, sync::Arc};
+use thiserror::Error;
+
+/// The maximum size of the extra data of a header in bytes.
+pub const MAXIMUM_EXTRA_DATA_SIZE: usize = 32;
+
+/// An error of the [HeaderValidator].
+#[allow(missing_docs)]
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderValidationError {
+    #[error("Child gas_limit {child_gas_limit:?} max increase is {parent_gas_limit:?}/1024.")]
+    GasLimitInvalidIncrease { parent_gas_limit: u64, child_gas_limit: u64 },
+    #[error("Child gas_limit {child_gas_limit:?} max decrease is {parent_gas_limit:?}/1024.")]
+    GasLimitInvalidDecrease { parent_gas_limit: u64, child_gas_limit: u64 },
+    #[error(
+        "Block timestamp {timestamp:?} is not after the parent timestamp {parent_timestamp:?}."
+    )]
+    TimestampNotAfterParent { parent_timestamp: u64, timestamp: u64 },
+    #[error("Extra data {len} exceeds max length.")]
+    ExtraDataExceedsMax { len: usize },
+    #[error("Difficulty after merge is not zero, got {difficulty:?}.")]
+    TheMergeDifficultyIsNotZero { difficulty: U256 },
+    #[error("Base fee missing.")]
+    BaseFeeMissing,
+    #[error("Block base fee ({got:?}) is different then expected: ({expected:?}).")]
+    BaseFeeDiff { expected: u64, got: u64 },
+}
+
+/// Checks the rules of a header that depend on its parent and on the active forks.
+///
+/// The checks that need state or the block body, like the gas used or the state root, are not
+/// covered and are done after execution.
+///
+/// The chain spec can be held by reference, e.g. `HeaderValidator::new(&chain_spec)`.
+#[derive(Debug, Clone)]
+pub struct HeaderValidator<C = Arc<ChainSpec>> {
+    /// The chain spec which defines when the forks are activated.
+    chain_spec: C,
+}
+
+impl<C: Deref<Target = ChainSpec>> HeaderValidator<C> {
+    /// Creates a new validator for the given chain.
+    pub fn new(chain_spec: C) -> Self {
+        Self { chain_spec }
+    }
+
+    /// Runs all checks of the header against its parent.
+    ///
+    /// The `total_difficulty` is the total difficulty at the header, including its own difficulty.
+    pub fn validate(
+        &self,
+        header: &Header,
+        parent: &Header,
+        total_difficulty: U256,
+    ) -> Result<(), HeaderValidationError> {
+        self.validate_gas_limit(header, parent)?;
+        self.validate_timestamp(header, parent)?;
+        self.validate_extra_data(header)?;
+        self.validate_difficulty(header, total_difficulty)?;
+        self.validate_base_fee(header, parent)
+    }
+
+    /// Checks that the gas limit changed by less than 1/1024 of the parent's gas limit.
+    ///
+    /// The parent's gas limit is multiplied by the elasticity at the London fork block.
+    pub fn validate_gas_limit(
+        &self,
+        header: &Header,
+        parent: &Header,
+    ) -> Result<(), HeaderValidationError> {
+        let mut parent_gas_limit = parent.gas_limit;
+        if self.chain_spec.fork(Hardfork::London).transitions_at_block(header.number) {
+            parent_gas_limit = parent.gas_limit * constants::EIP1559_ELASTICITY_MULTIPLIER;
+        }
+
+        if header.gas_limit > parent_gas_limit {
+            if header.gas_limit - parent_gas_limit >= parent_gas_limit / 1024 {
+                return Err(HeaderValidationError::GasLimitInvalidIncrease {
+                    parent_gas_limit,
+                    child_gas_limit: header.gas_limit,
+                })
+            }
+        } else if parent_gas_limit - header.gas_limit >= parent_gas_limit / 1024 {
+            return Err(HeaderValidationError::GasLimitInvalidDecrease {
+                parent_gas_limit,
+                child_gas_limit: header.gas_limit,
+            })
+        }
+        Ok(())
+    }
+
+    /// Checks that the timestamp is strictly greater than the parent's timestamp.
+    pub fn validate_timestamp(
+        &self,
+        header: &Header,
+        parent: &Header,
+    ) -> Result<(), HeaderValidationError> {
+        if header.timestamp <= parent.timestamp {
+            return Err(HeaderValidationError::TimestampNotAfterParent {
+                parent_timestamp: parent.timestamp,
+                timestamp: header.timestamp,
+            })
+        }
+        Ok(())
+    }
+
+    /// Checks that the extra data is at most [MAXIMUM_EXTRA_DATA_SIZE] bytes.
+    ///
+    /// The limit of the yellow paper still applies after the merge, see EIP-3675.
+    pub fn validate_extra_data(&self, header: &Header) -> Result<(), HeaderValidationError> {
+        if header.extra_data.len() > MAXIMUM_EXTRA_DATA_SIZE {
+            return Err(HeaderValidationError::ExtraDataExceedsMax { len: header.extra_data.len() })
+        }
+        Ok(())
+    }
+
+    /// Checks that the difficulty is zero after the merge.
+    ///
+    /// See [EIP-3675](https://eips.ethereum.org/EIPS/eip-3675#replacing-difficulty-with-0).
+    pub fn validate_difficulty(
+        &self,
+        header: &Header,
+        total_difficulty: U256,
+    ) -> Result<(), HeaderValidationError> {
+        if self.chain_spec.fork(Hardfork::Paris).active_at_ttd(total_difficulty, header.difficulty) &&
+            header.difficulty != U256::ZERO
+        {
+            return Err(HeaderValidationError::TheMergeDifficultyIsNotZero {
+                difficulty: header.difficulty,
+            })
+        }
+        Ok(())
+    }
+
+    /// Checks that the base fee follows from the parent as defined in
+    /// [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559).
+    pub fn validate_base_fee(
+        &self,
+        header: &Header,
+        parent: &Header,
+    ) -> Result<(), HeaderValidationError> {
+        let london = self.chain_spec.fork(Hardfork::London);
+        if !london.active_at_block(header.number) {
+            return Ok(())
+        }
+
+        let base_fee = header.base_fee_per_gas.ok_or(HeaderValidationError::BaseFeeMissing)?;
+        let expected = if london.transitions_at_block(header.number) {
+            constants::EIP1559_INITIAL_BASE_FEE
+        } else {
+            calculate_next_block_base_fee(
+                parent.gas_used,
+                parent.gas_limit,
+                parent.base_fee_per_gas.ok_or(HeaderValidationError::BaseFeeMissing)?,
+            )
+        };
+        if expected != base_fee {
+            return Err(HeaderValidationError::BaseFeeDiff { expected, got: base_fee })
+        }
+        Ok(())
+    }
+}
+
+/// Calculate base fee for next block. EIP-1559 spec
+///
+/// The arithmetic can't overflow: a change of the base fee is computed in `u128`, and the base fee
+/// saturates at the bounds of `u64`.
+pub fn calculate_next_block_base_fee(gas_used: u64, gas_limit: u64, base_fee: u64) -> u64 {
+    let gas_target = gas_limit / constants::EIP1559_ELASTICITY_MULTIPLIER;
+
+    // `base_fee * gas_used_delta / gas_target / BASE_FEE_MAX_CHANGE_DENOMINATOR`, headers with a
+    // zero gas target are invalid and don't change the base fee beyond the minimal increase
+    let base_fee_delta = |gas_used_delta: u64| {
+        let delta = (base_fee as u128 * gas_used_delta as u128)
+            .checked_div(
+                gas_target as u128 * constants::EIP1559_BASE_FEE_MAX_CHANGE_DENOMINATOR as u128,
+            )
+            .unwrap_or_default();
+        u64::try_from(delta).unwrap_or(u64::MAX)
+    };
+
+    match gas_used.cmp(&gas_target) {
+        Ordering::Equal => base_fee,
+        Ordering::Greater => base_fee.saturating_add(base_fee_delta(gas_used - gas_target).max(1)),
+        Ordering::Less => base_fee.saturating_sub(base_fee_delta(gas_target - gas_used)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bytes, ChainSpecBuilder, ForkCondition, MAINNET};
+
+    fn parent() -> Header {
+        Header {
+            number: 20_000_000,
+            gas_limit: 30_000_000,
+            gas_used: 15_000_000,
+            timestamp: 1_700_000_000,
+            base_fee_per_gas: Some(10),
+            ..Default::default()
+        }
+    }
+
+    fn child(parent: &Header) -> Header {
+        Header {
+            number: parent.number + 1,
+            gas_limit: parent.gas_limit,
+            timestamp: parent.timestamp + 12,
+            base_fee_per_gas: parent.base_fee_per_gas,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn valid_child() {
+        let validator = HeaderValidator::new(Arc::new(ChainSpecBuilder::mainnet().build()));
+        let parent = parent();
+        let child = child(&parent);
+        let total_difficulty = MAINNET.fork(Hardfork::Paris).ttd().unwrap();
+        assert_eq!(validator.validate(&child, &parent, total_difficulty), Ok(()));
+    }
+
+    #[test]
+    fn invalid_child() {
+        let validator = HeaderValidator::new(Arc::new(ChainSpecBuilder::mainnet().build()));
+        let parent = parent();
+
+        let header =
+            Header { gas_limit: parent.gas_limit + parent.gas_limit / 1024, ..child(&parent) };
+        assert_eq!(
+            validator.validate_gas_limit(&header, &parent),
+            Err(HeaderValidationError::GasLimitInvalidIncrease {
+                parent_gas_limit: parent.gas_limit,
+                child_gas_limit: header.gas_limit
+            })
+        );
+
+        let header = Header { timestamp: parent.timestamp, ..child(&parent) };
+        assert_eq!(
+            validator.validate_timestamp(&header, &parent),
+            Err(HeaderValidationError::TimestampNotAfterParent {
+                parent_timestamp: parent.timestamp,
+                timestamp: parent.timestamp
+            })
+        );
+
+        let header = Header { extra_data: Bytes::from(vec![0; 33]), ..child(&parent) };
+        assert_eq!(
+            validator.validate_extra_data(&header),
+            Err(HeaderValidationError::ExtraDataExceedsMax { len: 33 })
+        );
+
+        let header = Header { difficulty: U256::from(1), ..child(&parent) };
+        let total_difficulty = MAINNET.fork(Hardfork::Paris).ttd().unwrap();
+        assert_eq!(
+            validator.validate_difficulty(&header, total_difficulty + header.difficulty),
+            Err(HeaderValidationError::TheMergeDifficultyIsNotZero {
+                difficulty: header.difficulty
+            })
+        );
+
+        let header = Header { base_fee_per_gas: Some(11), ..child(&parent) };
+        assert_eq!(
+            validator.validate_base_fee(&header, &parent),
+            Err(HeaderValidationError::BaseFeeDiff { expected: 10, got: 11 })
+        );
+    }
+
+    #[test]
+    fn next_block_base_fee_saturates() {
+        assert_eq!(calculate_next_block_base_fee(u64::MAX, u64::MAX, u64::MAX), u64::MAX);
+        assert_eq!(calculate_next_block_base_fee(u64::MAX, 0, u64::MAX), u64::MAX);
+        assert_eq!(calculate_next_block_base_fee(1, 1, 10), 11);
+        assert_eq!(calculate_next_block_base_fee(0, 2, 10), 9);
+        assert_eq!(calculate_next_block_base_fee(0, u64::MAX, u64::MAX), u64::MAX - u64::MAX / 8);
+    }
+
+    #[test]
+    fn london_transition() {
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .with_fork(Hardfork::London, ForkCondition::Block(1))
+            .build();
+        let validator = HeaderValidator::new(Arc::new(chain_spec));
+        let parent =
+            Header { number: 0, gas_limit: 15_000_000, base_fee_per_gas: None, ..parent() };
+        let child = Header {
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(constants::EIP1559_INITIAL_BASE_FEE),
+            ..child(&parent)
+        };
+        assert_eq!(validator.validate_gas_limit(&child, &parent), Ok(()));
+        assert_eq!(validator.validate_base_fee(&child, &parent), Ok(()));
+    }
+
+    #[test]
+    fn base_fee_regarding_parent() {
+        let validator = HeaderValidator::new(Arc::new(ChainSpecBuilder::mainnet().build()));
+        let london = 12965000;
+        let parent = Header {
+            number: london + 1,
+            gas_limit: 30_000_000,
+            gas_used: 15_000_000,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        };
+        let child = |base_fee_per_gas| Header {
+            number: parent.number + 1,
+            base_fee_per_gas,
+            ..Default::default()
+        };
+
+        // gas used at the target keeps the base fee
+        assert_eq!(validator.validate_base_fee(&child(Some(1_000_000_000)), &parent), Ok(()));
+
+        // full parent increases the base fee by 12.5%
+        let full = Header { gas_used: 30_000_000, ..parent.clone() };
+        assert_eq!(validator.validate_base_fee(&child(Some(1_125_000_000)), &full), Ok(()));
+
+        // empty parent decreases the base fee by 12.5%
+        let empty = Header { gas_used: 0, ..parent.clone() };
+        assert_eq!(validator.validate_base_fee(&child(Some(875_000_000)), &empty), Ok(()));
+        assert_eq!(
+            validator.validate_base_fee(&child(Some(1_000_000_000)), &empty),
+            Err(HeaderValidationError::BaseFeeDiff { expected: 875_000_000, got: 1_000_000_000 })
+        );
+
+        assert_eq!(
+            validator.validate_base_fee(&child(None), &parent),
+            Err(HeaderValidationError::BaseFeeMissing)
+        );
+
+        // the first London block has the initial base fee, earlier blocks have none
+        let pre_london = Header { number: london - 1, base_fee_per_gas: None, ..parent.clone() };
+        let transition = |base_fee_per_gas| Header { number: london, ..child(base_fee_per_gas) };
+        assert_eq!(
+            validator.validate_base_fee(
+                &transition(Some(constants::EIP1559_INITIAL_BASE_FEE)),
+                &pre_london
+            ),
+            Ok(())
+        );
+        let pre_pre_london = Header { number: london - 2, ..pre_london.clone() };
+        assert_eq!(
+            validator
+                .validate_base_fee(&Header { number: london - 1, ..child(None) }, &pre_pre_london),
+            Ok(())
+        );
+    }
+}
@@ -53,7 +53,10 @@ pub use constants::{
 pub use forkid::{ForkFilter, ForkHash, ForkId, ForkTransition, ValidationError};
 pub use genesis::{Genesis, GenesisAccount};
 pub use hardfork::Hardfork;
-pub use header::{Head, Header, HeadersDirection, SealedHeader};
+pub use header::{
+    calculate_next_block_base_fee, Head, Header, HeaderValidationError, HeaderValidator,
+    HeadersDirection, SealedHeader, MAXIMUM_EXTRA_DATA_SIZE,
+};
 pub use hex_bytes::Bytes;
 pub use integer_list::IntegerList;
 pub use log::Log;
@@ -64,10 +67,12 @@ pub use revm_primitives::JumpMap;
 pub use serde_helper::JsonU256;
 pub use storage::{StorageEntry, StorageTrieEntry};
 pub use transaction::{
-    util::secp256k1::sign_message, AccessList, AccessListItem, AccessListWithGasUsed,
+    util::secp256k1::sign_message, verify_signatures_batch, AccessList, AccessListItem,
+    AccessListWithGasUsed, Blob, BlobTransaction, BlobTransactionSidecar, Bytes48,
     FromRecoveredTransaction, IntoRecoveredTransaction, InvalidTransactionError, Signature,
     Transaction, TransactionKind, TransactionSigned, TransactionSignedEcRecovered, TxEip1559,
-    TxEip2930, TxLegacy, TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
+    TxEip2930, TxLegacy, TxType, BLOB_SIZE, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID,
+    EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID, SIGNATURE_BATCH_SIZE,
 };
 pub use withdrawal::Withdrawal;
 
@@ -0,0 +1,61 @@
+use crate::{Address, TransactionSigned};
+use rayon::prelude::*;
+
+/// The number of transactions that are recovered together on the same thread.
+pub const SIGNATURE_BATCH_SIZE: usize = 64;
+
+/// Recovers the signers of all transactions, verifying their signatures.
+///
+/// The transactions are split into batches of [SIGNATURE_BATCH_SIZE] which are recovered in
+/// parallel on the [rayon] thread pool. Fewer transactions than a single batch are recovered on
+/// the calling thread.
+///
+/// Returns `None` if the signature of any transaction is invalid.
+pub fn verify_signatures_batch(txs: &[TransactionSigned]) -> Option<Vec<Address>> {
+    if txs.len() <= SIGNATURE_BATCH_SIZE {
+        return txs.iter().map(TransactionSigned::recover_signer).collect()
+    }
+
+    let batches = txs
+        .par_chunks(SIGNATURE_BATCH_SIZE)
+        .map(|batch| batch.iter().map(TransactionSigned::recover_signer).collect())
+        .collect::<Option<Vec<Vec<_>>>>()?;
+    Some(batches.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_message, Signature, Transaction, TxLegacy, H256};
+
+    fn signed_transactions(count: u64) -> Vec<TransactionSigned> {
+        (0..count)
+            .map(|nonce| {
+                let transaction = Transaction::Legacy(TxLegacy { nonce, ..Default::default() });
+                let secret = H256::from_low_u64_be(nonce % 7 + 1);
+                let signature = sign_message(secret, transaction.signature_hash()).unwrap();
+                TransactionSigned::from_transaction_and_signature(transaction, signature)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recovers_signers_in_order() {
+        for count in [0, 1, SIGNATURE_BATCH_SIZE as u64, 3 * SIGNATURE_BATCH_SIZE as u64 + 5] {
+            let txs = signed_transactions(count);
+            let expected = txs.iter().map(|tx| tx.recover_signer()).collect::<Option<Vec<_>>>();
+            assert_eq!(verify_signatures_batch(&txs), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_signature() {
+        let mut txs = signed_transactions(2 * SIGNATURE_BATCH_SIZE as u64);
+        let last = txs.pop().unwrap();
+        txs.push(TransactionSigned::from_transaction_and_signature(
+            last.transaction,
+            Signature::default(),
+        ));
+        assert_eq!(verify_signatures_batch(&txs), None);
+    }
+}
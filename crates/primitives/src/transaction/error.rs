@@ -21,6 +21,10 @@ pub enum InvalidTransactionError {
     Eip2930Disabled,
     #[error("Eip2930 transaction is enabled after london hardfork.")]
     Eip1559Disabled,
+    #[error("Eip4844 transaction is enabled after cancun hardfork.")]
+    Eip4844Disabled,
+    #[error("Eip4844 transaction has no blob versioned hashes.")]
+    BlobVersionedHashesEmpty,
     /// Thrown when calculating gas usage
     #[error("gas uint64 overflow")]
     GasUintOverflow,
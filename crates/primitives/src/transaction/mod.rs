@@ -1,5 +1,6 @@
 use crate::{keccak256, Address, Bytes, ChainId, TxHash, H256};
 pub use access_list::{AccessList, AccessListItem, AccessListWithGasUsed};
+pub use batch::{verify_signatures_batch, SIGNATURE_BATCH_SIZE};
 use bytes::{Buf, BytesMut};
 use derive_more::{AsRef, Deref};
 pub use error::InvalidTransactionError;
@@ -7,11 +8,16 @@ use reth_codecs::{add_arbitrary_tests, main_codec, Compact};
 use reth_rlp::{
     length_of_length, Decodable, DecodeError, Encodable, Header, EMPTY_LIST_CODE, EMPTY_STRING_CODE,
 };
+pub use sidecar::{Blob, BlobTransactionSidecar, Bytes48, BLOB_SIZE};
 pub use signature::Signature;
-pub use tx_type::{TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, LEGACY_TX_TYPE_ID};
+pub use tx_type::{
+    TxType, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID,
+};
 
 mod access_list;
+mod batch;
 mod error;
+mod sidecar;
 mod signature;
 mod tx_type;
 pub(crate) mod util;
@@ -162,6 +168,57 @@ pub struct TxEip1559 {
     pub input: Bytes,
 }
 
+/// A transaction that carries blobs ([EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)).
+///
+/// The blobs are only part of the network encoding of the transaction, see
+/// [`BlobTransactionSidecar`].
+#[main_codec]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BlobTransaction {
+    /// The chain id of the transaction, see [EIP-155](https://eips.ethereum.org/EIPS/eip-155).
+    pub chain_id: u64,
+    /// The number of transactions sent by the sender before this one; formally Tn.
+    pub nonce: u64,
+    /// The maximum amount of gas that can be used to execute the transaction, paid up-front;
+    /// formally Tg.
+    pub gas_limit: u64,
+    /// The maximum total fee per unit of gas the sender is willing to pay, including the base
+    /// fee and the priority fee.
+    ///
+    /// As ethereum circulation is around 120mil eth as of 2022 that is around
+    /// 120000000000000000000000000 wei we are safe to use u128 as its max number is:
+    /// 340282366920938463463374607431768211455
+    pub max_fee_per_gas: u128,
+    /// The maximum fee per unit of gas paid to the block beneficiary on top of the base fee.
+    ///
+    /// As ethereum circulation is around 120mil eth as of 2022 that is around
+    /// 120000000000000000000000000 wei we are safe to use u128 as its max number is:
+    /// 340282366920938463463374607431768211455
+    pub max_priority_fee_per_gas: u128,
+    /// The 160-bit address of the message call’s recipient. Blob transactions can't create
+    /// contracts.
+    pub to: Address,
+    /// The amount of Wei transferred to the recipient; formally Tv.
+    ///
+    /// As ethereum circulation is around 120mil eth as of 2022 that is around
+    /// 120000000000000000000000000 wei we are safe to use u128 as its max number is:
+    /// 340282366920938463463374607431768211455
+    pub value: u128,
+    /// The addresses and storage keys that are warmed up before execution, see
+    /// [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930).
+    pub access_list: AccessList,
+    /// The maximum fee per unit of blob gas the sender is willing to pay.
+    ///
+    /// As ethereum circulation is around 120mil eth as of 2022 that is around
+    /// 120000000000000000000000000 wei we are safe to use u128 as its max number is:
+    /// 340282366920938463463374607431768211455
+    pub max_fee_per_blob_gas: u128,
+    /// The versioned hashes of the KZG commitments to the blobs of the transaction.
+    pub blob_versioned_hashes: Vec<H256>,
+    /// The input data of the message call; formally Td.
+    pub input: Bytes,
+}
+
 /// A raw transaction.
 ///
 /// Transaction types were introduced in [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718).
@@ -174,6 +231,8 @@ pub enum Transaction {
     Eip2930(TxEip2930),
     /// A transaction with a priority fee ([EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)).
     Eip1559(TxEip1559),
+    /// A transaction that carries blobs ([EIP-4844](https://eips.ethereum.org/EIPS/eip-4844)).
+    Blob(BlobTransaction),
 }
 
 // === impl Transaction ===
@@ -193,6 +252,7 @@ impl Transaction {
             Transaction::Legacy(TxLegacy { chain_id, .. }) => *chain_id,
             Transaction::Eip2930(TxEip2930 { chain_id, .. }) => Some(*chain_id),
             Transaction::Eip1559(TxEip1559 { chain_id, .. }) => Some(*chain_id),
+            Transaction::Blob(BlobTransaction { chain_id, .. }) => Some(*chain_id),
         }
     }
 
@@ -202,16 +262,18 @@ impl Transaction {
             Transaction::Legacy(TxLegacy { chain_id: ref mut c, .. }) => *c = Some(chain_id),
             Transaction::Eip2930(TxEip2930 { chain_id: ref mut c, .. }) => *c = chain_id,
             Transaction::Eip1559(TxEip1559 { chain_id: ref mut c, .. }) => *c = chain_id,
+            Transaction::Blob(BlobTransaction { chain_id: ref mut c, .. }) => *c = chain_id,
         }
     }
 
     /// Gets the transaction's [`TransactionKind`], which is the address of the recipient or
     /// [`TransactionKind::Create`] if the transaction is a contract creation.
-    pub fn kind(&self) -> &TransactionKind {
+    pub fn kind(&self) -> TransactionKind {
         match self {
             Transaction::Legacy(TxLegacy { to, .. }) |
             Transaction::Eip2930(TxEip2930 { to, .. }) |
-            Transaction::Eip1559(TxEip1559 { to, .. }) => to,
+            Transaction::Eip1559(TxEip1559 { to, .. }) => *to,
+            Transaction::Blob(BlobTransaction { to, .. }) => TransactionKind::Call(*to),
         }
     }
 
@@ -221,6 +283,7 @@ impl Transaction {
             Transaction::Legacy { .. } => TxType::Legacy,
             Transaction::Eip2930 { .. } => TxType::EIP2930,
             Transaction::Eip1559 { .. } => TxType::EIP1559,
+            Transaction::Blob { .. } => TxType::EIP4844,
         }
     }
 
@@ -230,6 +293,7 @@ impl Transaction {
             Transaction::Legacy(TxLegacy { value, .. }) => value,
             Transaction::Eip2930(TxEip2930 { value, .. }) => value,
             Transaction::Eip1559(TxEip1559 { value, .. }) => value,
+            Transaction::Blob(BlobTransaction { value, .. }) => value,
         }
     }
 
@@ -239,6 +303,7 @@ impl Transaction {
             Transaction::Legacy(TxLegacy { nonce, .. }) => *nonce,
             Transaction::Eip2930(TxEip2930 { nonce, .. }) => *nonce,
             Transaction::Eip1559(TxEip1559 { nonce, .. }) => *nonce,
+            Transaction::Blob(BlobTransaction { nonce, .. }) => *nonce,
         }
     }
 
@@ -247,7 +312,8 @@ impl Transaction {
         match self {
             Transaction::Legacy(TxLegacy { gas_limit, .. }) |
             Transaction::Eip2930(TxEip2930 { gas_limit, .. }) |
-            Transaction::Eip1559(TxEip1559 { gas_limit, .. }) => *gas_limit,
+            Transaction::Eip1559(TxEip1559 { gas_limit, .. }) |
+            Transaction::Blob(BlobTransaction { gas_limit, .. }) => *gas_limit,
         }
     }
 
@@ -256,7 +322,8 @@ impl Transaction {
         match self {
             Transaction::Legacy(TxLegacy { gas_price, .. }) |
             Transaction::Eip2930(TxEip2930 { gas_price, .. }) => *gas_price,
-            Transaction::Eip1559(TxEip1559 { max_fee_per_gas, .. }) => *max_fee_per_gas,
+            Transaction::Eip1559(TxEip1559 { max_fee_per_gas, .. }) |
+            Transaction::Blob(BlobTransaction { max_fee_per_gas, .. }) => *max_fee_per_gas,
         }
     }
 
@@ -266,18 +333,45 @@ impl Transaction {
         match self {
             Transaction::Legacy(_) => None,
             Transaction::Eip2930(_) => None,
-            Transaction::Eip1559(TxEip1559 { max_priority_fee_per_gas, .. }) => {
+            Transaction::Eip1559(TxEip1559 { max_priority_fee_per_gas, .. }) |
+            Transaction::Blob(BlobTransaction { max_priority_fee_per_gas, .. }) => {
                 Some(*max_priority_fee_per_gas)
             }
         }
     }
 
+    /// Returns the price per gas the sender pays for the transaction in a block with the given base
+    /// fee.
+    ///
+    /// For EIP-1559 transactions this is the base fee plus the priority fee, capped at the max fee
+    /// per gas. Legacy and EIP-2930 transactions pay their gas price.
+    pub fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+        match (self.max_priority_fee_per_gas(), base_fee) {
+            (Some(max_priority_fee_per_gas), Some(base_fee)) => self
+                .max_fee_per_gas()
+                .min((base_fee as u128).saturating_add(max_priority_fee_per_gas)),
+            _ => self.max_fee_per_gas(),
+        }
+    }
+
     /// Get the transaction's input field.
     pub fn input(&self) -> &Bytes {
         match self {
             Transaction::Legacy(TxLegacy { input, .. }) => input,
             Transaction::Eip2930(TxEip2930 { input, .. }) => input,
             Transaction::Eip1559(TxEip1559 { input, .. }) => input,
+            Transaction::Blob(BlobTransaction { input, .. }) => input,
+        }
+    }
+
+    /// Get the versioned hashes of the blobs of the transaction, which are only present for blob
+    /// transactions.
+    pub fn blob_versioned_hashes(&self) -> Option<&[H256]> {
+        match self {
+            Transaction::Blob(BlobTransaction { blob_versioned_hashes, .. }) => {
+                Some(blob_versioned_hashes)
+            }
+            _ => None,
         }
     }
 
@@ -374,6 +468,33 @@ impl Transaction {
                 len += access_list.length();
                 len
             }
+            Transaction::Blob(BlobTransaction {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value,
+                access_list,
+                max_fee_per_blob_gas,
+                blob_versioned_hashes,
+                input,
+            }) => {
+                let mut len = 0;
+                len += chain_id.length();
+                len += nonce.length();
+                len += max_priority_fee_per_gas.length();
+                len += max_fee_per_gas.length();
+                len += gas_limit.length();
+                len += to.length();
+                len += value.length();
+                len += input.0.length();
+                len += access_list.length();
+                len += max_fee_per_blob_gas.length();
+                len += blob_versioned_hashes.length();
+                len
+            }
         }
     }
 
@@ -436,6 +557,31 @@ impl Transaction {
                 input.0.encode(out);
                 access_list.encode(out);
             }
+            Transaction::Blob(BlobTransaction {
+                chain_id,
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value,
+                access_list,
+                max_fee_per_blob_gas,
+                blob_versioned_hashes,
+                input,
+            }) => {
+                chain_id.encode(out);
+                nonce.encode(out);
+                max_priority_fee_per_gas.encode(out);
+                max_fee_per_gas.encode(out);
+                gas_limit.encode(out);
+                to.encode(out);
+                value.encode(out);
+                input.0.encode(out);
+                access_list.encode(out);
+                max_fee_per_blob_gas.encode(out);
+                blob_versioned_hashes.encode(out);
+            }
         }
     }
 }
@@ -707,7 +853,20 @@ impl TransactionSigned {
         // length of tx encoding = tx type byte (size = 1) + length of header + payload length
         let tx_length = 1 + header.length() + header.payload_length;
 
-        // decode common fields
+        let transaction = Self::decode_typed_transaction_fields(tx_type, data)?;
+        let signature = Signature::decode(data)?;
+
+        let hash = keccak256(&original_encoding[..tx_length]);
+        let signed = TransactionSigned { transaction, hash, signature };
+        Ok(signed)
+    }
+
+    /// Decodes the fields of an EIP-2718 typed transaction of the given type, without the
+    /// signature.
+    fn decode_typed_transaction_fields(
+        tx_type: u8,
+        data: &mut &[u8],
+    ) -> Result<Transaction, DecodeError> {
         let transaction = match tx_type {
             1 => Transaction::Eip2930(TxEip2930 {
                 chain_id: Decodable::decode(data)?,
@@ -730,14 +889,22 @@ impl TransactionSigned {
                 input: Bytes(Decodable::decode(data)?),
                 access_list: Decodable::decode(data)?,
             }),
+            3 => Transaction::Blob(BlobTransaction {
+                chain_id: Decodable::decode(data)?,
+                nonce: Decodable::decode(data)?,
+                max_priority_fee_per_gas: Decodable::decode(data)?,
+                max_fee_per_gas: Decodable::decode(data)?,
+                gas_limit: Decodable::decode(data)?,
+                to: Decodable::decode(data)?,
+                value: Decodable::decode(data)?,
+                input: Bytes(Decodable::decode(data)?),
+                access_list: Decodable::decode(data)?,
+                max_fee_per_blob_gas: Decodable::decode(data)?,
+                blob_versioned_hashes: Decodable::decode(data)?,
+            }),
             _ => return Err(DecodeError::Custom("unsupported typed transaction type")),
         };
-
-        let signature = Signature::decode(data)?;
-
-        let hash = keccak256(&original_encoding[..tx_length]);
-        let signed = TransactionSigned { transaction, hash, signature };
-        Ok(signed)
+        Ok(transaction)
     }
 
     /// Decodes the "raw" format of transaction (e.g. `eth_sendRawTransaction`).
@@ -761,6 +928,67 @@ impl TransactionSigned {
             TransactionSigned::decode_enveloped_typed_transaction(&mut data)
         }
     }
+
+    /// Encodes a blob transaction and its sidecar into the network format:
+    /// `type` + `rlp([tx_payload_body, blobs, commitments, proofs])`
+    ///
+    /// The hash of the transaction is computed without the sidecar, see
+    /// [TransactionSigned::encode_enveloped].
+    pub fn encode_enveloped_with_sidecar(
+        &self,
+        sidecar: &BlobTransactionSidecar,
+        out: &mut dyn bytes::BufMut,
+    ) {
+        let body_length = self.transaction.fields_len() + self.signature.payload_len();
+        let payload_length = length_of_length(body_length) + body_length + sidecar.fields_len();
+
+        out.put_u8(EIP4844_TX_TYPE_ID);
+        Header { list: true, payload_length }.encode(out);
+        Header { list: true, payload_length: body_length }.encode(out);
+        self.transaction.encode_fields(out);
+        self.signature.encode(out);
+        sidecar.encode_fields(out);
+    }
+
+    /// Decodes a blob transaction and its sidecar from the network format, see
+    /// [TransactionSigned::encode_enveloped_with_sidecar].
+    pub fn decode_enveloped_with_sidecar(
+        tx: Bytes,
+    ) -> Result<(Self, BlobTransactionSidecar), DecodeError> {
+        let mut data = tx.as_ref();
+
+        let tx_type = *data.first().ok_or(DecodeError::InputTooShort)?;
+        if tx_type != EIP4844_TX_TYPE_ID {
+            return Err(DecodeError::Custom("expected a blob transaction"))
+        }
+        data.advance(1);
+        let header = Header::decode(&mut data)?;
+        if !header.list {
+            return Err(DecodeError::Custom("blob tx network encoding must be a list"))
+        }
+        let remaining = data.len();
+
+        // keep the body around, so we can use it to calculate the hash
+        let body = data;
+        let body_header = Header::decode(&mut data)?;
+        if !body_header.list {
+            return Err(DecodeError::Custom("typed tx fields must be encoded as a list"))
+        }
+        let transaction = Self::decode_typed_transaction_fields(tx_type, &mut data)?;
+        let signature = Signature::decode(&mut data)?;
+
+        let mut hash_encoding = vec![tx_type];
+        hash_encoding.extend_from_slice(&body[..body_header.length() + body_header.payload_length]);
+        let hash = keccak256(&hash_encoding);
+
+        let sidecar = BlobTransactionSidecar::decode_fields(&mut data)?;
+
+        // the outer list must cover exactly the transaction and its sidecar
+        if remaining - data.len() != header.payload_length {
+            return Err(DecodeError::UnexpectedLength)
+        }
+        Ok((TransactionSigned { transaction, hash, signature }, sidecar))
+    }
 }
 
 impl From<TransactionSignedEcRecovered> for TransactionSigned {
@@ -791,7 +1019,14 @@ impl Decodable for TransactionSigned {
 
         // if the transaction is encoded as a string then it is a typed transaction
         if !header.list {
-            TransactionSigned::decode_enveloped_typed_transaction(buf)
+            let remaining = buf.len();
+            let tx = TransactionSigned::decode_enveloped_typed_transaction(buf)?;
+
+            // the string header must cover exactly the typed transaction
+            if remaining - buf.len() != header.payload_length {
+                return Err(DecodeError::UnexpectedLength)
+            }
+            Ok(tx)
         } else {
             let tx = TransactionSigned::decode_rlp_legacy_transaction(&mut original_encoding)?;
 
@@ -939,13 +1174,16 @@ impl IntoRecoveredTransaction for TransactionSignedEcRecovered {
 #[cfg(test)]
 mod tests {
     use crate::{
+        keccak256,
         transaction::{signature::Signature, TransactionKind, TxEip1559, TxEip2930, TxLegacy},
-        AccessList, Address, Bytes, Transaction, TransactionSigned, TransactionSignedEcRecovered,
+        AccessList, Address, BlobTransaction, BlobTransactionSidecar, Bytes, Transaction,
+        TransactionSigned, TransactionSignedEcRecovered, TxType, BLOB_SIZE, EIP4844_TX_TYPE_ID,
         H256, U256,
     };
-    use bytes::BytesMut;
+    use bytes::{BufMut, BytesMut};
     use ethers_core::utils::hex;
-    use reth_rlp::{Decodable, DecodeError, Encodable};
+    use proptest::prelude::*;
+    use reth_rlp::{Decodable, DecodeError, Encodable, Header, EMPTY_LIST_CODE};
     use std::str::FromStr;
 
     #[test]
@@ -1262,11 +1500,141 @@ mod tests {
         // random tx: <https://etherscan.io/getRawTx?tx=0x9448608d36e721ef403c53b00546068a6474d6cbab6816c3926de449898e7bce>
         let input = &hex::decode("02f871018302a90f808504890aef60826b6c94ddf4c5025d1a5742cf12f74eec246d4432c295e487e09c3bbcc12b2b80c080a0f21a4eacd0bf8fea9c5105c543be5a1d8c796516875710fafafdf16d16d8ee23a001280915021bb446d1973501a67f93d2b38894a514b976e7b46dc2fe54598d76").unwrap()[..];
         let decoded = TransactionSigned::decode_enveloped(input.into()).unwrap();
+        assert_eq!(
+            decoded.hash,
+            H256::from_str("0x9448608d36e721ef403c53b00546068a6474d6cbab6816c3926de449898e7bce")
+                .unwrap()
+        );
 
         let encoded = decoded.envelope_encoded();
         assert_eq!(encoded, input);
     }
 
+    #[test]
+    fn test_decode_typed_tx_length_mismatch() {
+        let input = &hex::decode("02f871018302a90f808504890aef60826b6c94ddf4c5025d1a5742cf12f74eec246d4432c295e487e09c3bbcc12b2b80c080a0f21a4eacd0bf8fea9c5105c543be5a1d8c796516875710fafafdf16d16d8ee23a001280915021bb446d1973501a67f93d2b38894a514b976e7b46dc2fe54598d76").unwrap()[..];
+
+        // string header claiming one more byte than the typed transaction
+        let mut encoded = BytesMut::new();
+        Header { list: false, payload_length: input.len() + 1 }.encode(&mut encoded);
+        encoded.extend_from_slice(input);
+        encoded.extend_from_slice(&[0x80]);
+
+        assert_eq!(
+            TransactionSigned::decode(&mut &encoded[..]),
+            Err(DecodeError::UnexpectedLength)
+        );
+    }
+
+    #[test]
+    fn test_effective_gas_price() {
+        let legacy = Transaction::Legacy(TxLegacy { gas_price: 10, ..Default::default() });
+        assert_eq!(legacy.effective_gas_price(Some(7)), 10);
+        assert_eq!(legacy.effective_gas_price(None), 10);
+
+        let eip1559 = Transaction::Eip1559(TxEip1559 {
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 2,
+            ..Default::default()
+        });
+        assert_eq!(eip1559.effective_gas_price(Some(7)), 9);
+        // the price is capped at the max fee
+        assert_eq!(eip1559.effective_gas_price(Some(9)), 10);
+        assert_eq!(eip1559.effective_gas_price(None), 10);
+    }
+
+    proptest! {
+        #[test]
+        fn test_encoding_roundtrip(tx in any::<TransactionSigned>()) {
+            // the hash is computed over the enveloped encoding
+            let enveloped = tx.envelope_encoded();
+            prop_assert_eq!(tx.hash, keccak256(&enveloped));
+            let decoded = TransactionSigned::decode_enveloped(enveloped.clone().into()).unwrap();
+            prop_assert_eq!(&decoded, &tx);
+
+            // legacy transactions are bare RLP, typed transactions are wrapped in a string
+            let mut encoded = BytesMut::new();
+            tx.encode(&mut encoded);
+            prop_assert_eq!(encoded.len(), tx.length());
+            if tx.tx_type() == TxType::Legacy {
+                prop_assert_eq!(&encoded[..], &enveloped[..]);
+            } else {
+                prop_assert!(encoded[0] < EMPTY_LIST_CODE);
+            }
+            let decoded = TransactionSigned::decode(&mut &encoded[..]).unwrap();
+            prop_assert_eq!(decoded.hash, tx.hash);
+            prop_assert_eq!(decoded, tx);
+        }
+    }
+
+    #[test]
+    fn test_blob_transaction_network_encoding() {
+        let transaction = Transaction::Blob(BlobTransaction {
+            chain_id: 1,
+            nonce: 2,
+            gas_limit: 21000,
+            max_fee_per_gas: 20_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            to: Address::random(),
+            value: 0,
+            access_list: Default::default(),
+            max_fee_per_blob_gas: 100,
+            blob_versioned_hashes: vec![H256::random()],
+            input: Default::default(),
+        });
+        let signature = Signature { r: U256::from(1), s: U256::from(2), odd_y_parity: true };
+        let tx = TransactionSigned::from_transaction_and_signature(transaction, signature);
+
+        // the enveloped encoding doesn't include the sidecar
+        let encoded = tx.envelope_encoded();
+        assert_eq!(encoded[0], 3);
+        assert_eq!(TransactionSigned::decode_enveloped(encoded.into()).unwrap(), tx);
+
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![[7; BLOB_SIZE]],
+            commitments: vec![[8; 48]],
+            proofs: vec![[9; 48]],
+        };
+        let mut encoded = BytesMut::new();
+        tx.encode_enveloped_with_sidecar(&sidecar, &mut encoded);
+        let (decoded, decoded_sidecar) =
+            TransactionSigned::decode_enveloped_with_sidecar(encoded.freeze().into()).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(decoded.hash, tx.hash);
+        assert_eq!(decoded_sidecar, sidecar);
+    }
+
+    #[test]
+    fn test_blob_transaction_network_encoding_length_mismatch() {
+        let transaction = Transaction::Blob(BlobTransaction {
+            chain_id: 1,
+            blob_versioned_hashes: vec![H256::random()],
+            ..Default::default()
+        });
+        let tx =
+            TransactionSigned::from_transaction_and_signature(transaction, Signature::default());
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![[7; BLOB_SIZE]],
+            commitments: vec![[8; 48]],
+            proofs: vec![[9; 48]],
+        };
+
+        // claim a longer outer list and append the extra byte
+        let mut encoded = BytesMut::new();
+        tx.encode_enveloped_with_sidecar(&sidecar, &mut encoded);
+        let payload_length = Header::decode(&mut &encoded[1..]).unwrap().payload_length;
+        let mut invalid = BytesMut::new();
+        invalid.put_u8(EIP4844_TX_TYPE_ID);
+        Header { list: true, payload_length: payload_length + 1 }.encode(&mut invalid);
+        invalid.extend_from_slice(&encoded[encoded.len() - payload_length..]);
+        invalid.put_u8(0);
+
+        assert_eq!(
+            TransactionSigned::decode_enveloped_with_sidecar(invalid.freeze().into()),
+            Err(DecodeError::UnexpectedLength)
+        );
+    }
+
     #[test]
     fn test_decode_signed_ec_recovered_transaction() {
         // random tx: <https://etherscan.io/getRawTx?tx=0x9448608d36e721ef403c53b00546068a6474d6cbab6816c3926de449898e7bce>
@@ -0,0 +1,54 @@
+use reth_rlp::{Decodable, DecodeError, Encodable};
+
+/// The size of a blob in bytes.
+pub const BLOB_SIZE: usize = 4096 * 32;
+
+/// A blob of data, which is committed to by a blob transaction.
+pub type Blob = [u8; BLOB_SIZE];
+
+/// A KZG commitment or proof.
+pub type Bytes48 = [u8; 48];
+
+/// The blobs of a [BlobTransaction](crate::BlobTransaction) together with their KZG commitments
+/// and proofs.
+///
+/// The sidecar is only sent along with the transaction on the network, it's not part of the
+/// transaction that is included in blocks.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BlobTransactionSidecar {
+    /// The blobs of the transaction.
+    pub blobs: Vec<Blob>,
+    /// The commitments to the blobs.
+    pub commitments: Vec<Bytes48>,
+    /// The proofs of the blobs against the commitments.
+    pub proofs: Vec<Bytes48>,
+}
+
+impl BlobTransactionSidecar {
+    /// Outputs the length of the sidecar's fields, without a RLP header.
+    pub(crate) fn fields_len(&self) -> usize {
+        self.blobs.length() + self.commitments.length() + self.proofs.length()
+    }
+
+    /// Encodes the sidecar's fields into the desired buffer, without a RLP header.
+    pub(crate) fn encode_fields(&self, out: &mut dyn bytes::BufMut) {
+        self.blobs.encode(out);
+        self.commitments.encode(out);
+        self.proofs.encode(out);
+    }
+
+    /// Decodes the sidecar's fields from the buffer, without a RLP header.
+    pub(crate) fn decode_fields(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let sidecar = Self {
+            blobs: Decodable::decode(buf)?,
+            commitments: Decodable::decode(buf)?,
+            proofs: Decodable::decode(buf)?,
+        };
+        if sidecar.commitments.len() != sidecar.blobs.len() ||
+            sidecar.proofs.len() != sidecar.blobs.len()
+        {
+            return Err(DecodeError::Custom("blob sidecar lengths do not match"))
+        }
+        Ok(sidecar)
+    }
+}
@@ -11,6 +11,9 @@ pub const EIP2930_TX_TYPE_ID: u8 = 1;
 /// Identifier for [TxEip1559](crate::TxEip1559) transaction.
 pub const EIP1559_TX_TYPE_ID: u8 = 2;
 
+/// Identifier for [BlobTransaction](crate::BlobTransaction) transaction.
+pub const EIP4844_TX_TYPE_ID: u8 = 3;
+
 /// Transaction Type
 #[derive_arbitrary(compact)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
@@ -22,6 +25,8 @@ pub enum TxType {
     EIP2930 = 1_isize,
     /// Transaction with Priority fee
     EIP1559 = 2_isize,
+    /// Transaction with blobs
+    EIP4844 = 3_isize,
 }
 
 impl From<TxType> for u8 {
@@ -30,6 +35,7 @@ impl From<TxType> for u8 {
             TxType::Legacy => LEGACY_TX_TYPE_ID,
             TxType::EIP2930 => EIP2930_TX_TYPE_ID,
             TxType::EIP1559 => EIP1559_TX_TYPE_ID,
+            TxType::EIP4844 => EIP4844_TX_TYPE_ID,
         }
     }
 }
@@ -40,6 +46,7 @@ impl Compact for TxType {
             TxType::Legacy => 0,
             TxType::EIP2930 => 1,
             TxType::EIP1559 => 2,
+            TxType::EIP4844 => 3,
         }
     }
 
@@ -48,7 +55,8 @@ impl Compact for TxType {
             match identifier {
                 0 => TxType::Legacy,
                 1 => TxType::EIP2930,
-                _ => TxType::EIP1559,
+                2 => TxType::EIP1559,
+                _ => TxType::EIP4844,
             },
             buf,
         )
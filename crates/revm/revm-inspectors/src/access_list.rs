@@ -19,15 +19,18 @@ pub struct AccessListInspector {
 impl AccessListInspector {
     /// Creates a new inspector instance
     ///
-    /// The `access_list` is the provided access list from the call request
+    /// The `access_list` is the provided access list from the call request.
+    ///
+    /// Accesses of `from`, `to` and the `warm_addresses` (like precompiles) are not recorded,
+    /// since these addresses are already warm when the transaction starts.
     pub fn new(
         access_list: AccessList,
         from: Address,
         to: Address,
-        precompiles: Vec<Address>,
+        warm_addresses: Vec<Address>,
     ) -> Self {
         AccessListInspector {
-            excluded: vec![from, to].iter().chain(precompiles.iter()).copied().collect(),
+            excluded: vec![from, to].iter().chain(warm_addresses.iter()).copied().collect(),
             access_list: access_list
                 .0
                 .iter()
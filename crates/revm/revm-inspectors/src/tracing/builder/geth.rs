@@ -1,9 +1,12 @@
 //! Geth trace builder
 
-use crate::tracing::{types::CallTraceNode, TraceInspectorConfig};
+use crate::tracing::{
+    types::{CallKind, CallTraceNode},
+    TraceInspectorConfig,
+};
 use reth_primitives::{Address, JsonU256, H256, U256};
 use reth_rpc_types::trace::geth::*;
-use revm::interpreter::opcode;
+use revm::interpreter::{opcode, InstructionResult};
 use std::collections::{BTreeMap, HashMap};
 
 /// A type for creating geth style traces
@@ -75,6 +78,57 @@ impl GethTraceBuilder {
         }
     }
 
+    /// Generate a geth-style call trace of the [CALL_TRACER]
+    pub fn geth_call_traces(&self, opts: CallConfig) -> CallFrame {
+        if self.nodes.is_empty() {
+            return Default::default()
+        }
+        let only_top_call = opts.only_top_call.unwrap_or_default();
+        self.call_frame(&self.nodes[0], only_top_call)
+    }
+
+    /// Converts the trace node and its children into a [CallFrame].
+    fn call_frame(&self, node: &CallTraceNode, only_top_call: bool) -> CallFrame {
+        let trace = &node.trace;
+        let typ = match trace.kind {
+            CallKind::Call => "CALL",
+            CallKind::StaticCall => "STATICCALL",
+            CallKind::CallCode => "CALLCODE",
+            CallKind::DelegateCall => "DELEGATECALL",
+            CallKind::Create => "CREATE",
+            CallKind::Create2 => "CREATE2",
+        };
+        let is_create = matches!(trace.kind, CallKind::Create | CallKind::Create2);
+        let output = (!trace.output.is_empty()).then(|| trace.output.clone().into());
+        let error = (!trace.success).then(|| match trace.status {
+            InstructionResult::Revert => "execution reverted".to_string(),
+            status => format!("{status:?}"),
+        });
+        let calls = if only_top_call {
+            Vec::new()
+        } else {
+            node.children
+                .iter()
+                .map(|child| self.call_frame(&self.nodes[*child], only_top_call))
+                .collect()
+        };
+
+        CallFrame {
+            typ: typ.to_string(),
+            from: trace.caller,
+            // failed creations have no address
+            to: (trace.success || !is_create).then_some(trace.address),
+            value: (trace.kind != CallKind::StaticCall && trace.kind != CallKind::DelegateCall)
+                .then_some(trace.value),
+            gas: U256::from(trace.gas_limit),
+            gas_used: U256::from(trace.gas_used),
+            input: trace.data.clone().into(),
+            output,
+            error,
+            calls,
+        }
+    }
+
     /// Generate a geth-style trace e.g. for `debug_traceTransaction`
     pub fn geth_traces(
         &self,
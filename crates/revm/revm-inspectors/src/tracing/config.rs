@@ -1,3 +1,5 @@
+use reth_rpc_types::trace::geth::GethDebugTracingOptions;
+
 /// Gives guidance to the [TracingInspector](crate::tracing::TracingInspector).
 ///
 /// Use [TraceInspectorConfig::default_parity] or [TraceInspectorConfig::default_geth] to get the
@@ -49,6 +51,16 @@ impl TraceInspectorConfig {
         }
     }
 
+    /// Returns a config for geth style traces based on the given [GethDebugTracingOptions].
+    pub fn from_geth_config(config: &GethDebugTracingOptions) -> Self {
+        Self {
+            record_memory_snapshots: config.enable_memory.unwrap_or_default(),
+            record_stack_snapshots: !config.disable_stack.unwrap_or_default(),
+            record_state_diff: !config.disable_storage.unwrap_or_default(),
+            ..Self::default_geth()
+        }
+    }
+
     /// Configure whether individual opcode level steps should be recorded
     pub fn set_steps(mut self, record_steps: bool) -> Self {
         self.record_steps = record_steps;
@@ -111,6 +111,7 @@ impl TracingInspector {
     /// Starts tracking a new trace.
     ///
     /// Invoked on [Inspector::call].
+    #[allow(clippy::too_many_arguments)]
     fn start_trace_on_call(
         &mut self,
         depth: usize,
@@ -119,6 +120,7 @@ impl TracingInspector {
         value: U256,
         kind: CallKind,
         caller: Address,
+        gas_limit: u64,
     ) {
         self.trace_stack.push(self.traces.push_trace(
             0,
@@ -130,6 +132,7 @@ impl TracingInspector {
                 value,
                 status: InstructionResult::Continue,
                 caller,
+                gas_limit,
                 ..Default::default()
             },
         ));
@@ -331,6 +334,7 @@ where
             inputs.transfer.value,
             inputs.context.scheme.into(),
             from,
+            inputs.gas_limit,
         );
 
         (InstructionResult::Continue, Gas::new(0), Bytes::new())
@@ -373,6 +377,7 @@ where
             inputs.value,
             inputs.scheme.into(),
             inputs.caller,
+            inputs.gas_limit,
         );
 
         (InstructionResult::Continue, None, Gas::new(inputs.gas_limit), Bytes::default())
@@ -99,6 +99,8 @@ pub(crate) struct CallTrace {
     /// The return data of the call if this was not a contract creation, otherwise it is the
     /// runtime bytecode of the created contract
     pub(crate) output: Bytes,
+    /// The gas limit of the call
+    pub(crate) gas_limit: u64,
     /// The gas cost of the call
     pub(crate) gas_used: u64,
     /// The status of the trace's call
@@ -121,6 +123,7 @@ impl Default for CallTrace {
             value: Default::default(),
             data: Default::default(),
             output: Default::default(),
+            gas_limit: Default::default(),
             gas_used: Default::default(),
             status: InstructionResult::Continue,
             call_context: Default::default(),
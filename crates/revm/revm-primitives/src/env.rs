@@ -1,7 +1,7 @@
 use crate::config::revm_spec;
 use reth_primitives::{
-    Address, ChainSpec, Head, Header, Transaction, TransactionKind, TransactionSignedEcRecovered,
-    TxEip1559, TxEip2930, TxLegacy, U256,
+    AccessList, Address, BlobTransaction, ChainSpec, Head, Header, Transaction, TransactionKind,
+    TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxLegacy, U256,
 };
 use revm::primitives::{AnalysisKind, BlockEnv, CfgEnv, SpecId, TransactTo, TxEnv};
 
@@ -69,6 +69,23 @@ pub fn fill_tx_env_with_recovered(tx_env: &mut TxEnv, transaction: &TransactionS
     fill_tx_env(tx_env, transaction.as_ref(), transaction.signer())
 }
 
+/// Converts the [AccessList] of a transaction into the format of [TxEnv].
+///
+/// revm warms all addresses and storage keys of the list before the transaction is executed, as
+/// specified by EIP-2929.
+pub fn to_revm_access_list(access_list: &AccessList) -> Vec<(Address, Vec<U256>)> {
+    access_list
+        .0
+        .iter()
+        .map(|l| {
+            (
+                l.address,
+                l.storage_keys.iter().map(|k| U256::from_be_bytes(k.to_fixed_bytes())).collect(),
+            )
+        })
+        .collect()
+}
+
 /// Fill transaction environment from a [Transaction] and the given sender address.
 pub fn fill_tx_env<T>(tx_env: &mut TxEnv, transaction: T, sender: Address)
 where
@@ -119,19 +136,7 @@ where
             tx_env.data = input.0.clone();
             tx_env.chain_id = Some(*chain_id);
             tx_env.nonce = Some(*nonce);
-            tx_env.access_list = access_list
-                .0
-                .iter()
-                .map(|l| {
-                    (
-                        l.address,
-                        l.storage_keys
-                            .iter()
-                            .map(|k| U256::from_be_bytes(k.to_fixed_bytes()))
-                            .collect(),
-                    )
-                })
-                .collect();
+            tx_env.access_list = to_revm_access_list(access_list);
         }
         Transaction::Eip1559(TxEip1559 {
             nonce,
@@ -155,19 +160,30 @@ where
             tx_env.data = input.0.clone();
             tx_env.chain_id = Some(*chain_id);
             tx_env.nonce = Some(*nonce);
-            tx_env.access_list = access_list
-                .0
-                .iter()
-                .map(|l| {
-                    (
-                        l.address,
-                        l.storage_keys
-                            .iter()
-                            .map(|k| U256::from_be_bytes(k.to_fixed_bytes()))
-                            .collect(),
-                    )
-                })
-                .collect();
+            tx_env.access_list = to_revm_access_list(access_list);
+        }
+        // blob gas is not supported by the evm yet
+        Transaction::Blob(BlobTransaction {
+            nonce,
+            chain_id,
+            gas_limit,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            to,
+            value,
+            input,
+            access_list,
+            ..
+        }) => {
+            tx_env.gas_limit = *gas_limit;
+            tx_env.gas_price = U256::from(*max_fee_per_gas);
+            tx_env.gas_priority_fee = Some(U256::from(*max_priority_fee_per_gas));
+            tx_env.transact_to = TransactTo::Call(*to);
+            tx_env.value = U256::from(*value);
+            tx_env.data = input.0.clone();
+            tx_env.chain_id = Some(*chain_id);
+            tx_env.nonce = Some(*nonce);
+            tx_env.access_list = to_revm_access_list(access_list);
         }
     }
 }
@@ -1,6 +1,6 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::NodeRecord;
-use reth_rpc_types::NodeInfo;
+use reth_rpc_types::{NodeInfo, PeerInfo};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server))]
@@ -28,6 +28,10 @@ pub trait AdminApi {
     #[method(name = "admin_removeTrustedPeer")]
     fn remove_trusted_peer(&self, record: NodeRecord) -> RpcResult<bool>;
 
+    /// Returns the info of all connected peers.
+    #[method(name = "admin_peers")]
+    async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
+
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
         name = "admin_peerEvents",
@@ -40,3 +44,14 @@ pub trait AdminApi {
     #[method(name = "admin_nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
 }
+
+/// Admin methods that are only served on the authenticated Engine API server.
+#[cfg_attr(not(feature = "client"), rpc(server))]
+#[cfg_attr(feature = "client", rpc(server, client))]
+pub trait AuthAdminApi {
+    /// Replaces the hex encoded JWT secret used to authenticate requests to the Engine API.
+    ///
+    /// Tokens signed with the previous secret are still accepted for a short grace period.
+    #[method(name = "admin_setJwtSecret")]
+    fn set_jwt_secret(&self, secret: String) -> RpcResult<bool>;
+}
@@ -2,7 +2,7 @@ use jsonrpsee::{core::RpcResult as Result, proc_macros::rpc};
 use reth_primitives::{BlockId, BlockNumberOrTag, Bytes, H256};
 use reth_rpc_types::{
     trace::geth::{BlockTraceResult, GethDebugTracingOptions, GethTraceFrame, TraceResult},
-    CallRequest, RichBlock,
+    AccountRange, CallRequest, RichBlock,
 };
 
 /// Debug rpc interface.
@@ -25,6 +25,18 @@ pub trait DebugApi {
     #[method(name = "debug_getRawReceipts")]
     async fn raw_receipts(&self, block_id: BlockId) -> Result<Vec<Bytes>>;
 
+    /// Returns up to `max_results` accounts of the state at the given block, in ascending order
+    /// of their address and starting at the address with the `start` prefix.
+    ///
+    /// If there are more accounts, the response contains the address to start the next range at.
+    #[method(name = "debug_accountRange")]
+    async fn account_range(
+        &self,
+        block_id: BlockId,
+        start: Bytes,
+        max_results: usize,
+    ) -> Result<AccountRange>;
+
     /// Returns an array of recent bad blocks that the client has seen on the network.
     #[method(name = "debug_getBadBlocks")]
     async fn bad_blocks(&self) -> Result<Vec<RichBlock>>;
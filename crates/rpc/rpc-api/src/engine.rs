@@ -1,8 +1,8 @@
 use jsonrpsee::{core::RpcResult as Result, proc_macros::rpc};
-use reth_primitives::{BlockHash, BlockNumber, H64};
+use reth_primitives::{BlockHash, BlockNumber, H256, H64};
 use reth_rpc_types::engine::{
-    ExecutionPayload, ExecutionPayloadBodies, ForkchoiceState, ForkchoiceUpdated,
-    PayloadAttributes, PayloadStatus, TransitionConfiguration,
+    BlobsBundleV1, ExecutionPayload, ExecutionPayloadBodies, ForkchoiceState, ForkchoiceUpdated,
+    PayloadAttributes, PayloadAttributesV3, PayloadStatus, TransitionConfiguration,
 };
 
 #[cfg_attr(not(feature = "client"), rpc(server))]
@@ -17,6 +17,15 @@ pub trait EngineApi {
     #[method(name = "engine_newPayloadV2")]
     async fn new_payload_v2(&self, payload: ExecutionPayload) -> Result<PayloadStatus>;
 
+    /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_newpayloadv3>
+    #[method(name = "engine_newPayloadV3")]
+    async fn new_payload_v3(
+        &self,
+        payload: ExecutionPayload,
+        versioned_hashes: Vec<H256>,
+        parent_beacon_block_root: H256,
+    ) -> Result<PayloadStatus>;
+
     /// See also <https://github.com/ethereum/execution-apis/blob/6709c2a795b707202e93c4f2867fa0bf2640a84f/src/engine/paris.md#engine_forkchoiceupdatedv1>
     ///
     /// Caution: This should not accept the `withdrawals` field
@@ -35,6 +44,14 @@ pub trait EngineApi {
         payload_attributes: Option<PayloadAttributes>,
     ) -> Result<ForkchoiceUpdated>;
 
+    /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_forkchoiceupdatedv3>
+    #[method(name = "engine_forkchoiceUpdatedV3")]
+    async fn fork_choice_updated_v3(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<PayloadAttributesV3>,
+    ) -> Result<ForkchoiceUpdated>;
+
     /// See also <https://github.com/ethereum/execution-apis/blob/6709c2a795b707202e93c4f2867fa0bf2640a84f/src/engine/paris.md#engine_getpayloadv1>
     ///
     /// Caution: This should not return the `withdrawals` field
@@ -45,6 +62,14 @@ pub trait EngineApi {
     #[method(name = "engine_getPayloadV2")]
     async fn get_payload_v2(&self, payload_id: H64) -> Result<ExecutionPayload>;
 
+    /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_getpayloadv3>
+    #[method(name = "engine_getPayloadV3")]
+    async fn get_payload_v3(&self, payload_id: H64) -> Result<ExecutionPayload>;
+
+    /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/experimental/blob-extension.md#engine_getblobsbundlev1>
+    #[method(name = "engine_getBlobsBundleV1")]
+    async fn get_blobs_bundle_v1(&self, payload_id: H64) -> Result<BlobsBundleV1>;
+
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/shanghai.md#engine_getpayloadbodiesbyhashv1>
     #[method(name = "engine_getPayloadBodiesByHashV1")]
     async fn get_payload_bodies_by_hash_v1(
@@ -3,7 +3,9 @@ use reth_primitives::{
     AccessListWithGasUsed, Address, BlockId, BlockNumberOrTag, Bytes, H256, H64, U256, U64,
 };
 use reth_rpc_types::{
-    state::StateOverride, CallRequest, EIP1186AccountProofResponse, FeeHistory, Index, RichBlock,
+    simulate::{SimulatedBlock, SimulationResult},
+    state::{BlockOverrides, StateOverride},
+    CallRequest, DryRunResult, EIP1186AccountProofResponse, FeeHistory, Index, RichBlock,
     SyncStatus, Transaction, TransactionReceipt, TransactionRequest, Work,
 };
 
@@ -107,6 +109,10 @@ pub trait EthApi {
     #[method(name = "eth_getTransactionReceipt")]
     async fn transaction_receipt(&self, hash: H256) -> Result<Option<TransactionReceipt>>;
 
+    /// Returns the receipts of all transactions in a block.
+    #[method(name = "eth_getBlockReceipts")]
+    async fn block_receipts(&self, block_id: BlockId) -> Result<Option<Vec<TransactionReceipt>>>;
+
     /// Returns the balance of the account of given address.
     #[method(name = "eth_getBalance")]
     async fn balance(&self, address: Address, block_number: Option<BlockId>) -> Result<U256>;
@@ -139,6 +145,7 @@ pub trait EthApi {
         request: CallRequest,
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<Bytes>;
 
     /// Generates an access list for a transaction.
@@ -171,6 +178,17 @@ pub trait EthApi {
         block_number: Option<BlockId>,
     ) -> Result<U256>;
 
+    /// Simulates the calls of consecutive blocks on top of the latest block.
+    ///
+    /// Every block sees the state changes of the previous ones, nothing is written to the
+    /// database.
+    #[method(name = "eth_simulateV1")]
+    async fn simulate_v1(
+        &self,
+        blocks: Vec<SimulatedBlock>,
+        block_overrides: Option<BlockOverrides>,
+    ) -> Result<Vec<SimulationResult>>;
+
     /// Returns the current price per gas in wei.
     #[method(name = "eth_gasPrice")]
     async fn gas_price(&self) -> Result<U256>;
@@ -248,3 +266,19 @@ pub trait EthApi {
         block_number: Option<BlockId>,
     ) -> Result<EIP1186AccountProofResponse>;
 }
+
+/// Non-standard extensions of the eth rpc interface.
+///
+/// These are only served if experimental methods are enabled.
+#[cfg_attr(not(feature = "client"), rpc(server))]
+#[cfg_attr(feature = "client", rpc(server, client))]
+#[async_trait::async_trait]
+pub trait EthExperimentalApi {
+    /// Validates a signed transaction like `eth_sendRawTransaction`, but neither adds it to the
+    /// pool nor broadcasts it to peers.
+    ///
+    /// The result reports whether the transaction would be accepted, its sender, its estimated
+    /// gas and its maximum cost.
+    #[method(name = "reth_dryRunTransaction")]
+    async fn dry_run_transaction(&self, bytes: Bytes) -> Result<DryRunResult>;
+}
@@ -1,6 +1,6 @@
 use jsonrpsee::{core::RpcResult as Result, proc_macros::rpc};
 use reth_primitives::filter::Filter;
-use reth_rpc_types::{FilterChanges, FilterId, Log};
+use reth_rpc_types::{FilterChanges, FilterId, Log, LogsResponse};
 
 /// Rpc Interface for poll-based ethereum filter API.
 #[cfg_attr(not(feature = "client"), rpc(server))]
@@ -31,6 +31,9 @@ pub trait EthFilterApi {
     async fn uninstall_filter(&self, id: FilterId) -> Result<bool>;
 
     /// Returns logs matching given filter object.
+    ///
+    /// If the filter has a `pageToken`, a page of the logs is returned together with the token of
+    /// the next page.
     #[method(name = "eth_getLogs")]
-    async fn logs(&self, filter: Filter) -> Result<Vec<Log>>;
+    async fn logs(&self, filter: Filter) -> Result<LogsResponse>;
 }
@@ -17,6 +17,7 @@ mod eth_filter;
 mod eth_pubsub;
 mod net;
 mod trace;
+mod txpool;
 mod web3;
 
 /// re-export of all server traits
@@ -25,9 +26,16 @@ pub use servers::*;
 /// Aggregates all server traits.
 pub mod servers {
     pub use crate::{
-        admin::AdminApiServer, debug::DebugApiServer, engine::EngineApiServer, eth::EthApiServer,
-        eth_filter::EthFilterApiServer, eth_pubsub::EthPubSubApiServer, net::NetApiServer,
-        trace::TraceApiServer, web3::Web3ApiServer,
+        admin::{AdminApiServer, AuthAdminApiServer},
+        debug::DebugApiServer,
+        engine::EngineApiServer,
+        eth::{EthApiServer, EthExperimentalApiServer},
+        eth_filter::EthFilterApiServer,
+        eth_pubsub::EthPubSubApiServer,
+        net::{NetApiServer, NetExperimentalApiServer},
+        trace::TraceApiServer,
+        txpool::TxPoolApiServer,
+        web3::Web3ApiServer,
     };
 }
 
@@ -39,7 +47,13 @@ pub use clients::*;
 #[cfg(feature = "client")]
 pub mod clients {
     pub use crate::{
-        admin::AdminApiClient, debug::DebugApiClient, engine::EngineApiClient, eth::EthApiClient,
-        net::NetApiClient, trace::TraceApiClient, web3::Web3ApiClient,
+        admin::{AdminApiClient, AuthAdminApiClient},
+        debug::DebugApiClient,
+        engine::EngineApiClient,
+        eth::{EthApiClient, EthExperimentalApiClient},
+        net::{NetApiClient, NetExperimentalApiClient},
+        trace::TraceApiClient,
+        txpool::TxPoolApiClient,
+        web3::Web3ApiClient,
     };
 }
@@ -1,5 +1,5 @@
 use jsonrpsee::{core::RpcResult as Result, proc_macros::rpc};
-use reth_rpc_types::PeerCount;
+use reth_rpc_types::{PeerCount, PeerCountByProtocol};
 
 /// Net rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server))]
@@ -18,3 +18,15 @@ pub trait NetApi {
     #[method(name = "net_listening")]
     fn is_listening(&self) -> Result<bool>;
 }
+
+/// Non-standard extensions of the net rpc interface.
+///
+/// These are only served if experimental methods are enabled.
+#[cfg_attr(not(feature = "client"), rpc(server))]
+#[cfg_attr(feature = "client", rpc(server, client))]
+#[async_trait::async_trait]
+pub trait NetExperimentalApi {
+    /// Returns the number of connected peers by the protocols they support.
+    #[method(name = "reth_net_peerCountByProtocol")]
+    async fn peer_count_by_protocol(&self) -> Result<PeerCountByProtocol>;
+}
@@ -14,8 +14,8 @@ pub use reth_ipc::server::{Builder as IpcServerBuilder, Endpoint};
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{BlockProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory};
 use reth_rpc::{
-    eth::cache::EthStateCache, AdminApi, AuthLayer, DebugApi, EngineApi, EthApi, JwtAuthValidator,
-    JwtSecret, NetApi, TraceApi, Web3Api,
+    eth::cache::EthStateCache, AdminApi, AtomicJwtSecretProvider, AuthAdminApi, AuthLayer,
+    DebugApi, EngineApi, EthApi, JwtAuthValidator, NetApi, TraceApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_engine_api::EngineApiHandle;
@@ -33,6 +33,9 @@ use tower::layer::util::{Identity, Stack};
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 
 /// Configure and launch an auth server with `engine` and a _new_ `eth` namespace.
+///
+/// Requests are authenticated with the given JWT secret, which can be replaced with
+/// `admin_setJwtSecret` on this server.
 pub async fn launch<Client, Pool, Network, Tasks>(
     client: Client,
     pool: Pool,
@@ -40,7 +43,7 @@ pub async fn launch<Client, Pool, Network, Tasks>(
     executor: Tasks,
     handle: EngineApiHandle,
     socket_addr: SocketAddr,
-    secret: JwtSecret,
+    secret: AtomicJwtSecretProvider,
 ) -> Result<ServerHandle, RpcError>
 where
     Client: BlockProvider
@@ -65,7 +68,7 @@ pub async fn launch_with_eth_api<Client, Pool, Network>(
     eth_api: EthApi<Client, Pool, Network>,
     handle: EngineApiHandle,
     socket_addr: SocketAddr,
-    secret: JwtSecret,
+    secret: AtomicJwtSecretProvider,
 ) -> Result<ServerHandle, RpcError>
 where
     Client: BlockProvider
@@ -81,11 +84,12 @@ where
     // Configure the module and start the server.
     let mut module = RpcModule::new(());
     module.merge(EngineApi::new(handle).into_rpc());
-    module.merge(eth_api.into_rpc());
+    module.merge(EthApiServer::into_rpc(eth_api));
+    module.merge(AuthAdminApi::new(secret.clone()).into_rpc());
 
     // Create auth middleware.
     let middleware =
-        tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
+        tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::with_provider(secret)));
 
     // By default, both http and ws are enabled.
     let server = ServerBuilder::new().set_middleware(middleware).build(socket_addr).await?;
@@ -1,5 +1,8 @@
 use reth_rpc::{
-    eth::cache::{EthStateCache, EthStateCacheConfig},
+    eth::{
+        cache::{EthStateCache, EthStateCacheConfig},
+        EthFilterConfig, FeeHistoryCacheConfig, PriorityFeeConfig,
+    },
     EthApi, EthFilter, EthPubSub,
 };
 use serde::{Deserialize, Serialize};
@@ -22,4 +25,10 @@ pub struct EthHandlers<Client, Pool, Network, Events> {
 pub struct EthConfig {
     /// Settings for the caching layer
     pub cache: EthStateCacheConfig,
+    /// Settings for the `eth_feeHistory` response cache
+    pub fee_history_cache: FeeHistoryCacheConfig,
+    /// Settings for the `eth_maxPriorityFeePerGas` estimation
+    pub priority_fee: PriorityFeeConfig,
+    /// Settings for the filter handlers, like the limits of `eth_getLogs`
+    pub filter: EthFilterConfig,
 }
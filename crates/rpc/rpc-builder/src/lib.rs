@@ -65,7 +65,8 @@ use reth_ipc::server::IpcServer;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{BlockProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory};
 use reth_rpc::{
-    AdminApi, DebugApi, EthApi, EthFilter, EthSubscriptionIdProvider, NetApi, TraceApi, Web3Api,
+    AdminApi, DebugApi, EthApi, EthApiSpec, EthFilter, EthSubscriptionIdProvider, NetApi, TraceApi,
+    TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_transaction_pool::TransactionPool;
@@ -241,6 +242,8 @@ impl Default for RpcModuleBuilder<(), (), (), ()> {
 pub struct RpcModuleConfig {
     /// `eth` namespace settings
     eth: EthConfig,
+    /// Whether non-standard methods that are still experimental are served
+    experimental_methods: bool,
 }
 
 // === impl RpcModuleConfig ===
@@ -256,6 +259,7 @@ impl RpcModuleConfig {
 #[derive(Default)]
 pub struct RpcModuleConfigBuilder {
     eth: Option<EthConfig>,
+    experimental_methods: bool,
 }
 
 // === impl RpcModuleConfigBuilder ===
@@ -267,10 +271,17 @@ impl RpcModuleConfigBuilder {
         self
     }
 
+    /// Enables non-standard methods that are still experimental, like
+    /// `reth_net_peerCountByProtocol` and `reth_dryRunTransaction`
+    pub fn experimental_methods(mut self, experimental_methods: bool) -> Self {
+        self.experimental_methods = experimental_methods;
+        self
+    }
+
     /// Consumes the type and creates the [RpcModuleConfig]
     pub fn build(self) -> RpcModuleConfig {
-        let RpcModuleConfigBuilder { eth } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default() }
+        let RpcModuleConfigBuilder { eth, experimental_methods } = self;
+        RpcModuleConfig { eth: eth.unwrap_or_default(), experimental_methods }
     }
 }
 
@@ -416,6 +427,8 @@ pub enum RethRpcModule {
     Net,
     /// `trace_` module
     Trace,
+    /// `txpool_` module
+    Txpool,
     /// `web3_` module
     Web3,
 }
@@ -435,6 +448,35 @@ impl Serialize for RethRpcModule {
     }
 }
 
+/// Creates the [Methods] of the `eth` namespace, including the non-standard methods if
+/// `experimental_methods` is set.
+fn eth_methods<Eth>(eth_api: Eth, experimental_methods: bool) -> Methods
+where
+    Eth: EthApiServer + EthExperimentalApiServer + Clone,
+{
+    let mut module = EthApiServer::into_rpc(eth_api.clone());
+    if experimental_methods {
+        module.merge(EthExperimentalApiServer::into_rpc(eth_api)).expect("No conflicts");
+    }
+    module.into()
+}
+
+/// Creates the [Methods] of the `net` namespace, including the non-standard methods if
+/// `experimental_methods` is set.
+fn net_methods<Network, Eth>(network: Network, eth_api: Eth, experimental_methods: bool) -> Methods
+where
+    Network: Peers + Clone + 'static,
+    Eth: EthApiSpec + Clone + 'static,
+{
+    let mut module = NetApiServer::into_rpc(NetApi::new(network.clone(), eth_api.clone()));
+    if experimental_methods {
+        module
+            .merge(NetExperimentalApiServer::into_rpc(NetApi::new(network, eth_api)))
+            .expect("No conflicts");
+    }
+    module.into()
+}
+
 /// A Helper type the holds instances of the configured modules.
 pub struct RethModuleRegistry<Client, Pool, Network, Tasks> {
     client: Client,
@@ -513,14 +555,19 @@ where
     /// Register Eth Namespace
     pub fn register_eth(&mut self) -> &mut Self {
         let eth_api = self.eth_api();
-        self.modules.insert(RethRpcModule::Eth, eth_api.into_rpc().into());
+        self.modules
+            .insert(RethRpcModule::Eth, eth_methods(eth_api, self.config.experimental_methods));
         self
     }
 
     /// Register Debug Namespace
     pub fn register_debug(&mut self) -> &mut Self {
         let eth_api = self.eth_api();
-        self.modules.insert(RethRpcModule::Debug, DebugApi::new(eth_api).into_rpc().into());
+        let eth_cache = self.eth_cache();
+        self.modules.insert(
+            RethRpcModule::Debug,
+            DebugApi::new(self.client.clone(), eth_api, eth_cache).into_rpc().into(),
+        );
         self
     }
 
@@ -529,11 +576,18 @@ where
         let eth_api = self.eth_api();
         self.modules.insert(
             RethRpcModule::Net,
-            NetApi::new(self.network.clone(), eth_api).into_rpc().into(),
+            net_methods(self.network.clone(), eth_api, self.config.experimental_methods),
         );
         self
     }
 
+    /// Register Txpool Namespace
+    pub fn register_txpool(&mut self) -> &mut Self {
+        self.modules
+            .insert(RethRpcModule::Txpool, TxPoolApi::new(self.pool.clone()).into_rpc().into());
+        self
+    }
+
     /// Helper function to create a [RpcModule] if it's not `None`
     fn maybe_module(&mut self, config: Option<&RpcModuleSelection>) -> Option<RpcModule<()>> {
         let config = config?;
@@ -570,16 +624,27 @@ where
                         RethRpcModule::Admin => {
                             AdminApi::new(self.network.clone()).into_rpc().into()
                         }
-                        RethRpcModule::Debug => DebugApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Eth => eth_api.clone().into_rpc().into(),
-                        RethRpcModule::Net => {
-                            NetApi::new(self.network.clone(), eth_api.clone()).into_rpc().into()
+                        RethRpcModule::Debug => {
+                            DebugApi::new(self.client.clone(), eth_api.clone(), eth_cache.clone())
+                                .into_rpc()
+                                .into()
+                        }
+                        RethRpcModule::Eth => {
+                            eth_methods(eth_api.clone(), self.config.experimental_methods)
                         }
+                        RethRpcModule::Net => net_methods(
+                            self.network.clone(),
+                            eth_api.clone(),
+                            self.config.experimental_methods,
+                        ),
                         RethRpcModule::Trace => {
                             TraceApi::new(self.client.clone(), eth_api.clone(), eth_cache.clone())
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::Txpool => {
+                            TxPoolApi::new(self.pool.clone()).into_rpc().into()
+                        }
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
                     })
                     .clone()
@@ -606,13 +671,20 @@ where
                 self.config.eth.cache.clone(),
                 self.executor.clone(),
             );
-            let api = EthApi::new(
+            let api = EthApi::with_fee_history_cache(
                 self.client.clone(),
                 self.pool.clone(),
                 self.network.clone(),
                 eth_cache.clone(),
+                self.config.eth.fee_history_cache,
+            )
+            .with_priority_fee_config(self.config.eth.priority_fee);
+            let filter = EthFilter::with_config(
+                self.client.clone(),
+                self.pool.clone(),
+                Box::new(self.executor.clone()),
+                self.config.eth.filter,
             );
-            let filter = EthFilter::new(self.client.clone(), self.pool.clone());
 
             // TODO: install pubsub
 
@@ -1134,6 +1206,7 @@ mod tests {
                 "eth" =>  RethRpcModule::Eth,
                 "net" =>  RethRpcModule::Net,
                 "trace" =>  RethRpcModule::Trace,
+                "txpool" =>  RethRpcModule::Txpool,
                 "web3" =>  RethRpcModule::Web3,
             );
     }
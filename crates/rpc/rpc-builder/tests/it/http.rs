@@ -1,6 +1,6 @@
 //! Standalone http tests
 
-use crate::utils::{launch_http, launch_http_ws, launch_ws};
+use crate::utils::{launch_http, launch_http_ws, launch_ws, test_address, test_rpc_builder};
 use jsonrpsee::{
     core::{
         client::{ClientT, SubscriptionClientT},
@@ -14,10 +14,18 @@ use reth_primitives::{
 };
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
-    DebugApiClient, NetApiClient, TraceApiClient, Web3ApiClient,
+    DebugApiClient, EthExperimentalApiClient, NetApiClient, NetExperimentalApiClient,
+    TraceApiClient, TxPoolApiClient, Web3ApiClient,
+};
+use reth_rpc::eth::error::EthApiError;
+use reth_rpc_builder::{RethRpcModule, RpcModuleConfig, RpcServerConfig, TransportRpcModuleConfig};
+use reth_rpc_types::{
+    trace::{
+        filter::TraceFilter,
+        geth::{GethDebugTracingOptions, CALL_TRACER, PRESTATE_TRACER},
+    },
+    CallRequest, Index, PeerCountByProtocol, TransactionRequest, TxpoolStatus,
 };
-use reth_rpc_builder::RethRpcModule;
-use reth_rpc_types::{trace::filter::TraceFilter, CallRequest, Index, TransactionRequest};
 use std::collections::HashSet;
 
 fn is_unimplemented(err: Error) -> bool {
@@ -30,6 +38,16 @@ fn is_unimplemented(err: Error) -> bool {
     }
 }
 
+fn is_invalid_params(err: Error, expected: EthApiError) -> bool {
+    match err {
+        Error::Call(CallError::Custom(error_obj)) => {
+            error_obj.code() == ErrorCode::InvalidParams.code() &&
+                error_obj.message() == expected.to_string()
+        }
+        _ => false,
+    }
+}
+
 async fn test_basic_admin_calls<C>(client: &C)
 where
     C: ClientT + SubscriptionClientT + Sync,
@@ -42,6 +60,7 @@ where
     AdminApiClient::add_trusted_peer(client, node).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    AdminApiClient::peers(client).await.unwrap();
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -66,6 +85,7 @@ where
     EthApiClient::get_code(client, address, None).await.unwrap();
     EthApiClient::send_raw_transaction(client, tx).await.unwrap();
     EthApiClient::fee_history(client, 0.into(), block_number.into(), None).await.unwrap();
+    EthApiClient::max_priority_fee_per_gas(client).await.unwrap();
     EthApiClient::balance(client, address, None).await.unwrap();
     EthApiClient::transaction_count(client, address, None).await.unwrap();
     EthApiClient::storage_at(client, address, U256::default(), None).await.unwrap();
@@ -85,22 +105,23 @@ where
     EthApiClient::transaction_by_hash(client, tx_hash).await.unwrap();
     EthApiClient::transaction_by_block_hash_and_index(client, hash, index).await.unwrap();
     EthApiClient::transaction_by_block_number_and_index(client, block_number, index).await.unwrap();
+    EthApiClient::block_receipts(client, block_number.into()).await.unwrap();
     EthApiClient::create_access_list(client, call_request.clone(), Some(block_number.into()))
         .await
         .unwrap();
     EthApiClient::estimate_gas(client, call_request.clone(), Some(block_number.into()))
         .await
         .unwrap();
-    EthApiClient::call(client, call_request.clone(), Some(block_number.into()), None)
+    EthApiClient::call(client, call_request.clone(), Some(block_number.into()), None, None)
         .await
         .unwrap();
+    EthApiClient::simulate_v1(client, vec![], None).await.unwrap();
 
     // Unimplemented
     assert!(is_unimplemented(EthApiClient::syncing(client).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::author(client).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::transaction_receipt(client, hash).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::gas_price(client).await.err().unwrap()));
-    assert!(is_unimplemented(EthApiClient::max_priority_fee_per_gas(client).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::is_mining(client).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::hashrate(client).await.err().unwrap()));
     assert!(is_unimplemented(EthApiClient::get_work(client).await.err().unwrap()));
@@ -137,6 +158,55 @@ where
     ));
     assert!(is_unimplemented(DebugApiClient::raw_receipts(client, block_id).await.err().unwrap()));
     assert!(is_unimplemented(DebugApiClient::bad_blocks(client).await.err().unwrap()));
+    assert!(is_unimplemented(
+        DebugApiClient::debug_trace_block(
+            client,
+            Bytes::default(),
+            GethDebugTracingOptions::default(),
+        )
+        .await
+        .err()
+        .unwrap()
+    ));
+
+    // the options are valid, but there is no block to trace
+    for tracer in [None, Some(CALL_TRACER), Some(PRESTATE_TRACER)] {
+        let opts = GethDebugTracingOptions {
+            tracer: tracer.map(String::from),
+            timeout: Some("10s".to_string()),
+            ..Default::default()
+        };
+        let err = DebugApiClient::debug_trace_block_by_number(
+            client,
+            BlockNumberOrTag::default(),
+            opts.clone(),
+        )
+        .await
+        .unwrap_err();
+        assert!(is_invalid_params(err, EthApiError::UnknownBlockNumber));
+        let err = DebugApiClient::debug_trace_block_by_hash(client, H256::default(), opts)
+            .await
+            .unwrap_err();
+        assert!(is_invalid_params(err, EthApiError::UnknownBlockNumber));
+    }
+
+    let opts =
+        GethDebugTracingOptions { tracer: Some("4byteTracer".to_string()), ..Default::default() };
+    let err = DebugApiClient::debug_trace_block_by_number(client, BlockNumberOrTag::default(), opts)
+        .await
+        .unwrap_err();
+    assert!(is_invalid_params(
+        err,
+        EthApiError::Unsupported(
+            "tracers other than the struct logger, callTracer and prestateTracer",
+        ),
+    ));
+
+    let opts = GethDebugTracingOptions { timeout: Some("soon".to_string()), ..Default::default() };
+    let err = DebugApiClient::debug_trace_block_by_number(client, BlockNumberOrTag::default(), opts)
+        .await
+        .unwrap_err();
+    assert!(is_invalid_params(err, EthApiError::InvalidTracerConfig));
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -195,6 +265,23 @@ where
     ));
 }
 
+async fn test_basic_txpool_calls<C>(client: &C)
+where
+    C: ClientT + SubscriptionClientT + Sync,
+{
+    let content = TxPoolApiClient::txpool_content_from(client, Address::default()).await.unwrap();
+    assert!(content.pending.is_empty());
+    assert!(content.queued.is_empty());
+    let content = TxPoolApiClient::txpool_content(client).await.unwrap();
+    assert!(content.pending.is_empty());
+    assert!(content.queued.is_empty());
+    let inspect = TxPoolApiClient::txpool_inspect(client).await.unwrap();
+    assert!(inspect.pending.is_empty());
+    assert!(inspect.queued.is_empty());
+    let status = TxPoolApiClient::txpool_status(client).await.unwrap();
+    assert_eq!(status, TxpoolStatus::default());
+}
+
 async fn test_basic_web3_calls<C>(client: &C)
 where
     C: ClientT + SubscriptionClientT + Sync,
@@ -311,6 +398,50 @@ async fn test_call_net_functions_http_and_ws() {
     test_basic_net_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_experimental_net_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let modules = TransportRpcModuleConfig::set_http(vec![RethRpcModule::Net])
+        .with_config(RpcModuleConfig::builder().experimental_methods(true).build());
+    let handle = test_rpc_builder()
+        .build(modules)
+        .start_server(RpcServerConfig::http(Default::default()).with_http_address(test_address()))
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+    let count = NetExperimentalApiClient::peer_count_by_protocol(&client).await.unwrap();
+    assert_eq!(count, PeerCountByProtocol::default());
+
+    // not served unless enabled
+    let handle = launch_http(vec![RethRpcModule::Net]).await;
+    let client = handle.http_client().unwrap();
+    NetExperimentalApiClient::peer_count_by_protocol(&client).await.unwrap_err();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_experimental_eth_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let modules = TransportRpcModuleConfig::set_http(vec![RethRpcModule::Eth])
+        .with_config(RpcModuleConfig::builder().experimental_methods(true).build());
+    let handle = test_rpc_builder()
+        .build(modules)
+        .start_server(RpcServerConfig::http(Default::default()).with_http_address(test_address()))
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+    let result =
+        EthExperimentalApiClient::dry_run_transaction(&client, Bytes::default()).await.unwrap();
+    assert!(!result.is_valid);
+    assert!(result.error.is_some());
+
+    // not served unless enabled
+    let handle = launch_http(vec![RethRpcModule::Eth]).await;
+    let client = handle.http_client().unwrap();
+    EthExperimentalApiClient::dry_run_transaction(&client, Bytes::default()).await.unwrap_err();
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_call_trace_functions_http() {
     reth_tracing::init_test_tracing();
@@ -338,6 +469,33 @@ async fn test_call_trace_functions_http_and_ws() {
     test_basic_trace_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_txpool_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http(vec![RethRpcModule::Txpool]).await;
+    let client = handle.http_client().unwrap();
+    test_basic_txpool_calls(&client).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_txpool_functions_ws() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_ws(vec![RethRpcModule::Txpool]).await;
+    let client = handle.ws_client().await.unwrap();
+    test_basic_txpool_calls(&client).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_txpool_functions_http_and_ws() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_http_ws(vec![RethRpcModule::Txpool]).await;
+    let client = handle.http_client().unwrap();
+    test_basic_txpool_calls(&client).await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_call_web3_functions_http() {
     reth_tracing::init_test_tracing();
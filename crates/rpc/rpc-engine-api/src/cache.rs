@@ -0,0 +1,126 @@
+use crate::metrics::PayloadValidationCacheMetrics;
+use lru::LruCache;
+use reth_primitives::H256;
+use reth_rpc_types::engine::{PayloadStatus, PayloadStatusEnum};
+use std::num::NonZeroUsize;
+
+/// An LRU cache of the outcome of payload executions, keyed by the block hash of the payload.
+///
+/// This prevents executing the same payload again if the Consensus layer sends it multiple times,
+/// e.g. after a restart. Only the final [PayloadStatusEnum::Valid] and
+/// [PayloadStatusEnum::Invalid] outcomes are cached.
+pub(crate) struct PayloadValidationCache {
+    /// The cached payload statuses.
+    entries: LruCache<H256, PayloadStatus>,
+    /// Number of lookups that were answered from the cache.
+    hits: u64,
+    /// Number of lookups that were not found in the cache.
+    misses: u64,
+    /// Cache metrics.
+    metrics: PayloadValidationCacheMetrics,
+}
+
+impl PayloadValidationCache {
+    /// Creates a new cache that holds at most `max_entries` payload statuses.
+    pub(crate) fn new(max_entries: usize) -> Self {
+        let capacity = NonZeroUsize::new(max_entries.max(1)).expect("is not zero");
+        Self { entries: LruCache::new(capacity), hits: 0, misses: 0, metrics: Default::default() }
+    }
+
+    /// Returns the cached status of the payload with the given block hash.
+    pub(crate) fn get(&mut self, block_hash: &H256) -> Option<PayloadStatus> {
+        let status = self.entries.get(block_hash).cloned();
+        if status.is_some() {
+            self.hits += 1;
+            self.metrics.hits.increment(1);
+        } else {
+            self.misses += 1;
+            self.metrics.misses.increment(1);
+        }
+        self.metrics.hit_rate.set(self.hit_rate());
+        status
+    }
+
+    /// Caches the status of the payload with the given block hash if it is final.
+    pub(crate) fn insert(&mut self, block_hash: H256, status: PayloadStatus) {
+        if matches!(status.status, PayloadStatusEnum::Valid | PayloadStatusEnum::Invalid { .. }) {
+            self.entries.put(block_hash, status);
+            self.metrics.entries.set(self.entries.len() as f64);
+        }
+    }
+
+    /// Removes all entries, e.g. because a reorg changed the state the payloads were validated
+    /// against.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.metrics.entries.set(0.0);
+    }
+
+    /// Returns the ratio of cache hits to all lookups.
+    pub(crate) fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0
+        }
+        self.hits as f64 / lookups as f64
+    }
+}
+
+impl std::fmt::Debug for PayloadValidationCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadValidationCache")
+            .field("entries", &self.entries.len())
+            .field("hits", &self.hits)
+            .field("misses", &self.misses)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caches_final_statuses() {
+        let mut cache = PayloadValidationCache::new(10);
+
+        let valid = H256::random();
+        let invalid = H256::random();
+        let syncing = H256::random();
+        cache.insert(valid, PayloadStatus::new(PayloadStatusEnum::Valid, valid));
+        cache.insert(
+            invalid,
+            PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                validation_error: "invalid".to_string(),
+            }),
+        );
+        cache.insert(syncing, PayloadStatus::from_status(PayloadStatusEnum::Syncing));
+        assert_eq!(cache.entries.len(), 2);
+
+        assert_eq!(cache.get(&valid), Some(PayloadStatus::new(PayloadStatusEnum::Valid, valid)));
+        assert!(matches!(
+            cache.get(&invalid),
+            Some(PayloadStatus { status: PayloadStatusEnum::Invalid { .. }, .. })
+        ));
+        assert_eq!(cache.get(&syncing), None);
+        assert_eq!(cache.hit_rate(), 2.0 / 3.0);
+
+        cache.clear();
+        assert_eq!(cache.entries.len(), 0);
+        assert_eq!(cache.get(&valid), None);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = PayloadValidationCache::new(1);
+
+        let first = H256::random();
+        let second = H256::random();
+        cache.insert(first, PayloadStatus::new(PayloadStatusEnum::Valid, first));
+        cache.insert(second, PayloadStatus::new(PayloadStatusEnum::Valid, second));
+
+        assert_eq!(cache.entries.len(), 1);
+        assert_eq!(cache.get(&first), None);
+        assert!(cache.get(&second).is_some());
+    }
+}
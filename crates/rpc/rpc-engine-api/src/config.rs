@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+/// The default time the Engine API waits for a response to a request before it gives up.
+pub const DEFAULT_ENGINE_API_REQUEST_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// The default number of payload validation outcomes the Engine API keeps in memory.
+pub const DEFAULT_PAYLOAD_VALIDATION_CACHE_MAX_ENTRIES: usize = 256;
+
+/// The default number of built payloads the Engine API keeps in memory until they are retrieved by
+/// the Consensus layer.
+pub const DEFAULT_BUILT_PAYLOADS_MAX_ENTRIES: usize = 16;
+
+/// The default number of canonical blocks a forkchoice update may reorg before the node
+/// resyncs instead, which is the reorg depth the blockchain tree supports.
+pub use reth_executor::blockchain_tree::config::DEFAULT_MAX_REORG_DEPTH;
+
+/// Configuration options for the Engine API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EngineApiConfig {
+    /// Max time to wait for the response to a request sent to the
+    /// [`EngineApi`][crate::EngineApi].
+    ///
+    /// Requests that take longer, e.g. because of a slow block execution, are answered with
+    /// [`EngineApiError::Timeout`][crate::EngineApiError::Timeout].
+    pub request_timeout: Duration,
+    /// Max number of payload validation outcomes that are cached, so that payloads sent
+    /// repeatedly by the Consensus layer are not executed again.
+    pub payload_validation_cache_max_entries: usize,
+    /// Max number of built payloads that are kept, along with the blobs of their blob
+    /// transactions, until the Consensus layer retrieves them.
+    pub built_payloads_max_entries: usize,
+    /// Max number of canonical blocks a forkchoice update may reorg.
+    ///
+    /// This should match the reorg depth of the blockchain tree, which defaults to the same
+    /// [`DEFAULT_MAX_REORG_DEPTH`].
+    ///
+    /// A forkchoice update with a head on a fork that is deeper and not finalized is answered with
+    /// `SYNCING`, and the node syncs to the new head from scratch.
+    pub max_reorg_depth: u64,
+}
+
+impl Default for EngineApiConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: DEFAULT_ENGINE_API_REQUEST_TIMEOUT,
+            payload_validation_cache_max_entries: DEFAULT_PAYLOAD_VALIDATION_CACHE_MAX_ENTRIES,
+            built_payloads_max_entries: DEFAULT_BUILT_PAYLOADS_MAX_ENTRIES,
+            max_reorg_depth: DEFAULT_MAX_REORG_DEPTH,
+        }
+    }
+}
@@ -1,10 +1,19 @@
-use crate::{message::EngineApiMessageVersion, EngineApiError, EngineApiMessage, EngineApiResult};
+use crate::{
+    cache::PayloadValidationCache,
+    forkchoice::{ForkchoiceQueue, ForkchoiceValidator, QueuedForkchoiceUpdate},
+    message::EngineApiMessageVersion,
+    metrics::EngineApiMetrics,
+    payload_builder::{payload_id, PayloadBuilder},
+    EngineApiConfig, EngineApiError, EngineApiMessage, EngineApiResult,
+};
 use futures::StreamExt;
+use lru::LruCache;
+use reth_executor::blockchain_tree::{shareable::BlockchainTreeEngine, BlockStatus};
 use reth_interfaces::consensus::ForkchoiceState;
 use reth_primitives::{
     proofs::{self, EMPTY_LIST_HASH},
-    BlockHash, BlockId, BlockNumber, ChainSpec, Hardfork, Header, SealedBlock, TransactionSigned,
-    H256, H64, U256,
+    BlockHash, BlockId, BlockNumber, ChainSpec, Hardfork, Header, HeaderValidator, SealedBlock,
+    TransactionSigned, H256, H64, U256,
 };
 use reth_provider::{
     BlockExecutor, BlockProvider, EvmEnvProvider, ExecutorFactory, HeaderProvider,
@@ -12,16 +21,22 @@ use reth_provider::{
 };
 use reth_rlp::Decodable;
 use reth_rpc_types::engine::{
-    ExecutionPayload, ExecutionPayloadBodies, ForkchoiceUpdated, PayloadAttributes, PayloadStatus,
-    PayloadStatusEnum, TransitionConfiguration,
+    BlobsBundleV1, CancunPayloadFields, ExecutionPayload, ExecutionPayloadBodies,
+    ForkchoiceUpdated, PayloadAttributesVersion, PayloadStatus, PayloadStatusEnum,
+    TransitionConfiguration, SUPPORTED_ENGINE_METHODS,
 };
 use std::{
     future::Future,
+    num::NonZeroUsize,
     pin::Pin,
     sync::Arc,
-    task::{ready, Context, Poll},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{mpsc, oneshot, watch},
+    time::Interval,
 };
-use tokio::sync::{mpsc, oneshot, watch};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 /// The Engine API handle.
@@ -33,6 +48,9 @@ pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
 /// The upper limit for payload bodies request.
 const MAX_PAYLOAD_BODIES_LIMIT: u64 = 1024;
 
+/// How often the time elapsed since the last forkchoice update is recorded.
+const FORKCHOICE_LAG_METRIC_INTERVAL: Duration = Duration::from_secs(1);
+
 /// The Engine API implementation that grants the Consensus layer access to data and
 /// functions in the Execution layer that are crucial for the consensus process.
 #[must_use = "EngineApi does nothing unless polled."]
@@ -42,9 +60,38 @@ pub struct EngineApi<Client> {
     chain_spec: Arc<ChainSpec>,
     message_rx: UnboundedReceiverStream<EngineApiMessage>,
     forkchoice_state_tx: watch::Sender<ForkchoiceState>,
-    // TODO: Placeholder for storing future blocks. Make cache bounded. Use lru
-    // local_store: HashMap<H64, ExecutionPayload>,
-    // remote_store: HashMap<H64, ExecutionPayload>,
+    /// The built payloads along with the blobs of their blob transactions, keyed by payload id.
+    built_payloads: LruCache<H64, BuiltPayload>,
+    /// The outcomes of previously executed payloads.
+    payload_validation_cache: PayloadValidationCache,
+    /// The forkchoice update waiting to be processed.
+    forkchoice_queue: ForkchoiceQueue,
+    /// Max number of canonical blocks a forkchoice update may reorg.
+    max_reorg_depth: u64,
+    /// Engine API metrics.
+    metrics: EngineApiMetrics,
+    /// When the last forkchoice update was received.
+    last_forkchoice_updated: Instant,
+    /// Interval for recording the time elapsed since the last forkchoice update, created on the
+    /// first poll.
+    forkchoice_lag_interval: Option<Interval>,
+    /// The blockchain tree that executes the payloads and makes the forkchoice heads canonical.
+    ///
+    /// Without a tree, payloads are executed on top of the latest state of the client.
+    blockchain_tree: Option<Arc<dyn BlockchainTreeEngine>>,
+    /// The builder of the payloads requested by forkchoice updates with payload attributes.
+    ///
+    /// Without a builder, no payloads are built.
+    payload_builder: Option<Arc<dyn PayloadBuilder>>,
+}
+
+/// A payload built by the Execution layer, waiting to be retrieved by the Consensus layer.
+#[derive(Debug, Clone)]
+struct BuiltPayload {
+    /// The built execution payload.
+    payload: ExecutionPayload,
+    /// The blobs of the blob transactions included in the payload.
+    blobs_bundle: BlobsBundleV1,
 }
 
 impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvider>
@@ -56,19 +103,89 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
         chain_spec: Arc<ChainSpec>,
         message_rx: mpsc::UnboundedReceiver<EngineApiMessage>,
         forkchoice_state_tx: watch::Sender<ForkchoiceState>,
+    ) -> Self {
+        Self::with_config(
+            client,
+            chain_spec,
+            message_rx,
+            forkchoice_state_tx,
+            EngineApiConfig::default(),
+        )
+    }
+
+    /// Create new instance of [EngineApi] with the given [EngineApiConfig].
+    pub fn with_config(
+        client: Client,
+        chain_spec: Arc<ChainSpec>,
+        message_rx: mpsc::UnboundedReceiver<EngineApiMessage>,
+        forkchoice_state_tx: watch::Sender<ForkchoiceState>,
+        config: EngineApiConfig,
     ) -> Self {
         Self {
             client,
             chain_spec,
             message_rx: UnboundedReceiverStream::new(message_rx),
             forkchoice_state_tx,
+            built_payloads: LruCache::new(
+                NonZeroUsize::new(config.built_payloads_max_entries.max(1)).expect("is not zero"),
+            ),
+            payload_validation_cache: PayloadValidationCache::new(
+                config.payload_validation_cache_max_entries,
+            ),
+            forkchoice_queue: ForkchoiceQueue::default(),
+            max_reorg_depth: config.max_reorg_depth,
+            metrics: EngineApiMetrics::default(),
+            last_forkchoice_updated: Instant::now(),
+            forkchoice_lag_interval: None,
+            blockchain_tree: None,
+            payload_builder: None,
+        }
+    }
+
+    /// Sets the blockchain tree that executes the payloads and makes the forkchoice heads
+    /// canonical.
+    pub fn with_blockchain_tree(mut self, blockchain_tree: Arc<dyn BlockchainTreeEngine>) -> Self {
+        self.blockchain_tree = Some(blockchain_tree);
+        self
+    }
+
+    /// Sets the builder of the payloads requested by forkchoice updates with payload attributes.
+    pub fn with_payload_builder(mut self, payload_builder: Arc<dyn PayloadBuilder>) -> Self {
+        self.payload_builder = Some(payload_builder);
+        self
+    }
+
+    /// Stores a built payload and the blobs bundle of its blob transactions, so that they can be
+    /// retrieved by the Consensus layer via `engine_getPayload` and `engine_getBlobsBundleV1`.
+    ///
+    /// If the store is full, the least recently built payload is evicted together with its blobs.
+    pub fn insert_built_payload(
+        &mut self,
+        payload_id: H64,
+        payload: ExecutionPayload,
+        blobs_bundle: BlobsBundleV1,
+    ) {
+        self.built_payloads.put(payload_id, BuiltPayload { payload, blobs_bundle });
+    }
+
+    /// Processes the queued forkchoice update, if any.
+    fn on_queued_forkchoice_update(&mut self) {
+        if let Some(request) = self.forkchoice_queue.pop() {
+            self.on_message(request.into_message());
         }
     }
 
     fn on_message(&mut self, msg: EngineApiMessage) {
         match msg {
             EngineApiMessage::GetPayload(payload_id, tx) => {
-                let _ = tx.send(self.get_payload(payload_id).ok_or(EngineApiError::PayloadUnknown));
+                let start = Instant::now();
+                let res = self.get_payload(payload_id).ok_or(EngineApiError::PayloadUnknown);
+                self.metrics.get_payload_build_duration_seconds.record(start.elapsed());
+                let _ = tx.send(res);
+            }
+            EngineApiMessage::GetBlobsBundle(payload_id, tx) => {
+                let _ = tx
+                    .send(self.get_blobs_bundle(payload_id).ok_or(EngineApiError::PayloadUnknown));
             }
             EngineApiMessage::GetPayloadBodiesByHash(hashes, tx) => {
                 let _ = tx.send(self.get_payload_bodies_by_hash(hashes));
@@ -76,34 +193,72 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             EngineApiMessage::GetPayloadBodiesByRange(start, count, tx) => {
                 let _ = tx.send(self.get_payload_bodies_by_range(start, count));
             }
-            EngineApiMessage::NewPayload(version, payload, tx) => {
-                if let Err(err) = self.validate_withdrawals_presence(
-                    version,
-                    payload.timestamp.as_u64(),
-                    payload.withdrawals.is_some(),
-                ) {
+            EngineApiMessage::NewPayload(version, payload, cancun_fields, tx) => {
+                let timestamp = payload.timestamp.as_u64();
+                if let Err(err) = self
+                    .validate_withdrawals_presence(
+                        version,
+                        timestamp,
+                        payload.withdrawals.is_some(),
+                    )
+                    .and_then(|_| {
+                        self.validate_cancun_fields_presence(
+                            version,
+                            timestamp,
+                            payload.has_blob_fields(),
+                        )
+                    })
+                {
                     let _ = tx.send(Err(err));
                     return
                 }
-                let _ = tx.send(self.new_payload(payload));
+
+                let start = Instant::now();
+                let res = match version {
+                    EngineApiMessageVersion::V1 | EngineApiMessageVersion::V2 => {
+                        self.new_payload(payload)
+                    }
+                    EngineApiMessageVersion::V3 => self.new_payload_v3(payload, cancun_fields),
+                };
+                self.metrics.record_new_payload(version, &res, start.elapsed());
+                let _ = tx.send(res);
             }
             EngineApiMessage::ForkchoiceUpdated(version, state, attrs, tx) => {
+                self.last_forkchoice_updated = Instant::now();
+                self.metrics.forkchoice_lag_seconds.set(0.0);
+
                 if let Some(attributes) = &attrs {
-                    if let Err(err) = self.validate_withdrawals_presence(
-                        version,
-                        attributes.timestamp.as_u64(),
-                        attributes.withdrawals.is_some(),
-                    ) {
+                    let timestamp = attributes.payload_attributes().timestamp.as_u64();
+                    if let Err(err) = self
+                        .validate_withdrawals_presence(
+                            version,
+                            timestamp,
+                            attributes.payload_attributes().withdrawals.is_some(),
+                        )
+                        .and_then(|_| {
+                            self.validate_cancun_fields_presence(
+                                version,
+                                timestamp,
+                                attributes.parent_beacon_block_root().is_some(),
+                            )
+                        })
+                    {
                         let _ = tx.send(Err(err));
                         return
                     }
                 }
 
-                let _ = tx.send(self.fork_choice_updated(state, attrs));
+                let start = Instant::now();
+                let res = self.fork_choice_updated(state, attrs);
+                self.metrics.forkchoice_updated_duration_seconds.record(start.elapsed());
+                let _ = tx.send(res);
             }
             EngineApiMessage::ExchangeTransitionConfiguration(config, tx) => {
                 let _ = tx.send(self.exchange_transition_configuration(config));
             }
+            EngineApiMessage::ExchangeCapabilities(capabilities, tx) => {
+                let _ = tx.send(Ok(self.exchange_capabilities(capabilities)));
+            }
         }
     }
 
@@ -124,7 +279,7 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
                     return Err(EngineApiError::InvalidParams)
                 }
             }
-            EngineApiMessageVersion::V2 => {
+            EngineApiMessageVersion::V2 | EngineApiMessageVersion::V3 => {
                 let shanghai_with_no_withdrawals = is_shanghai && !has_withdrawals;
                 let not_shanghai_with_withdrawals = !is_shanghai && has_withdrawals;
                 if shanghai_with_no_withdrawals || not_shanghai_with_withdrawals {
@@ -136,6 +291,40 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
         Ok(())
     }
 
+    /// Validates the presence of the fields introduced with Cancun according to the payload
+    /// timestamp.
+    ///
+    /// These are the `blobGasUsed` and `excessBlobGas` fields of the payload, or the
+    /// `parentBeaconBlockRoot` field of the payload attributes.
+    /// V3 messages are only supported after Cancun and must contain the fields.
+    /// V1 and V2 messages must not contain the fields.
+    fn validate_cancun_fields_presence(
+        &self,
+        version: EngineApiMessageVersion,
+        timestamp: u64,
+        has_cancun_fields: bool,
+    ) -> EngineApiResult<()> {
+        let is_cancun = self.chain_spec.fork(Hardfork::Cancun).active_at_timestamp(timestamp);
+
+        match version {
+            EngineApiMessageVersion::V1 | EngineApiMessageVersion::V2 => {
+                if has_cancun_fields {
+                    return Err(EngineApiError::InvalidParams)
+                }
+            }
+            EngineApiMessageVersion::V3 => {
+                if !is_cancun {
+                    return Err(EngineApiError::UnsupportedFork)
+                }
+                if !has_cancun_fields {
+                    return Err(EngineApiError::InvalidParams)
+                }
+            }
+        };
+
+        Ok(())
+    }
+
     /// Try to construct a block from given payload. Perform addition validation of `extra_data` and
     /// `base_fee_per_gas` fields.
     ///
@@ -144,7 +333,14 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
     /// comparing the value with `payload.block_hash`.
     ///
     /// See <https://github.com/ethereum/go-ethereum/blob/79a478bb6176425c2400e949890e668a3d9a3d05/core/beacon/types.go#L145>
-    fn try_construct_block(&self, payload: ExecutionPayload) -> EngineApiResult<SealedBlock> {
+    ///
+    /// The `parent_beacon_block_root` is only passed with `engine_newPayloadV3`, it is not part of
+    /// the [ExecutionPayload].
+    fn try_construct_block(
+        &self,
+        payload: ExecutionPayload,
+        parent_beacon_block_root: Option<H256>,
+    ) -> EngineApiResult<SealedBlock> {
         if payload.extra_data.len() > 32 {
             return Err(EngineApiError::PayloadExtraData(payload.extra_data))
         }
@@ -177,6 +373,9 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             timestamp: payload.timestamp.as_u64(),
             mix_hash: payload.prev_randao,
             base_fee_per_gas: Some(payload.base_fee_per_gas.to::<u64>()),
+            blob_gas_used: payload.blob_gas_used.map(|gas| gas.as_u64()),
+            excess_blob_gas: payload.excess_blob_gas.map(|gas| gas.as_u64()),
+            parent_beacon_block_root,
             extra_data: payload.extra_data,
             // Defaults
             ommers_hash: EMPTY_LIST_HASH,
@@ -200,13 +399,51 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
         })
     }
 
+    /// Checks the fields of the payload block that only depend on its parent.
+    ///
+    /// These checks are cheap, so obviously invalid payloads are rejected before they are executed.
+    fn validate_payload_against_parent(
+        &self,
+        block: &SealedBlock,
+        parent: &Header,
+    ) -> EngineApiResult<()> {
+        if block.number != parent.number + 1 {
+            return Err(EngineApiError::PayloadBlockNumber {
+                invalid: block.number,
+                parent: parent.number,
+            })
+        }
+
+        if block.timestamp <= parent.timestamp {
+            return Err(EngineApiError::PayloadTimestamp {
+                invalid: block.timestamp,
+                latest: parent.timestamp,
+            })
+        }
+
+        if block.gas_used > block.gas_limit {
+            return Err(EngineApiError::PayloadGasUsed {
+                gas_used: block.gas_used,
+                gas_limit: block.gas_limit,
+            })
+        }
+
+        HeaderValidator::new(self.chain_spec.clone()).validate_base_fee(&block.header, parent)?;
+        Ok(())
+    }
+
     /// Called to retrieve the latest state of the network, validate new blocks, and maintain
     /// consistency between the Consensus and Execution layers.
     ///
-    /// NOTE: Will always result in `PayloadUnknown` since we don't support block
-    /// building for now.
-    pub fn get_payload(&self, _payload_id: H64) -> Option<ExecutionPayload> {
-        None
+    /// Results in `PayloadUnknown` if no payload with the given id was built, or it was evicted.
+    pub fn get_payload(&self, payload_id: H64) -> Option<ExecutionPayload> {
+        self.built_payloads.peek(&payload_id).map(|built| built.payload.clone())
+    }
+
+    /// Called to retrieve the KZG commitments, proofs and blobs of the blob transactions included
+    /// in a built payload.
+    pub fn get_blobs_bundle(&self, payload_id: H64) -> Option<BlobsBundleV1> {
+        self.built_payloads.peek(&payload_id).map(|built| built.blobs_bundle.clone())
     }
 
     /// Called to retrieve execution payload bodies by range.
@@ -261,7 +498,16 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
     /// These responses should adhere to the [Engine API Spec for
     /// `engine_newPayload`](https://github.com/ethereum/execution-apis/blob/main/src/engine/paris.md#specification).
     pub fn new_payload(&mut self, payload: ExecutionPayload) -> EngineApiResult<PayloadStatus> {
-        let block = match self.try_construct_block(payload) {
+        self.on_new_payload(payload, None)
+    }
+
+    /// Constructs the block from the payload and validates and executes it.
+    fn on_new_payload(
+        &mut self,
+        payload: ExecutionPayload,
+        parent_beacon_block_root: Option<H256>,
+    ) -> EngineApiResult<PayloadStatus> {
+        let block = match self.try_construct_block(payload, parent_beacon_block_root) {
             Ok(b) => b,
             Err(err) => {
                 return Ok(PayloadStatus::from_status(PayloadStatusEnum::InvalidBlockHash {
@@ -272,6 +518,11 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
         let block_hash = block.header.hash();
         let parent_hash = block.parent_hash;
 
+        // The payload was already executed
+        if let Some(status) = self.payload_validation_cache.get(&block_hash) {
+            return Ok(status)
+        }
+
         // The block already exists in our database
         if self.client.is_known(&block_hash)? {
             return Ok(PayloadStatus::new(PayloadStatusEnum::Valid, block_hash))
@@ -285,9 +536,11 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
         let parent_td = if let Some(parent_td) = self.client.header_td(&block.parent_hash)? {
             parent_td
         } else {
-            return Ok(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+            let status = PayloadStatus::from_status(PayloadStatusEnum::Invalid {
                 validation_error: EngineApiError::PayloadPreMerge.to_string(),
-            }))
+            });
+            self.payload_validation_cache.insert(block_hash, status.clone());
+            return Ok(status)
         };
 
         // Short circuit the check by passing parent total difficulty.
@@ -304,20 +557,41 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             // }
             //
             // if terminal block conditions are not satisfied
-            return Ok(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+            let status = PayloadStatus::from_status(PayloadStatusEnum::Invalid {
                 validation_error: EngineApiError::PayloadPreMerge.to_string(),
             })
-            .with_latest_valid_hash(H256::zero()))
+            .with_latest_valid_hash(H256::zero());
+            self.payload_validation_cache.insert(block_hash, status.clone());
+            return Ok(status)
         }
 
-        if block.timestamp <= parent.timestamp {
-            return Ok(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
-                validation_error: EngineApiError::PayloadTimestamp {
-                    invalid: block.timestamp,
-                    latest: parent.timestamp,
+        if let Err(error) = self.validate_payload_against_parent(&block, &parent.header) {
+            let status = PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                validation_error: error.to_string(),
+            });
+            self.payload_validation_cache.insert(block_hash, status.clone());
+            return Ok(status)
+        }
+
+        if let Some(tree) = &self.blockchain_tree {
+            // The tree executes the block on top of its parent, which is not necessarily the
+            // canonical tip.
+            let status = match tree.insert_block(block) {
+                Ok(BlockStatus::Valid) => PayloadStatus::new(PayloadStatusEnum::Valid, block_hash),
+                Ok(BlockStatus::Accepted) => {
+                    PayloadStatus::from_status(PayloadStatusEnum::Accepted)
                 }
-                .to_string(),
-            }))
+                // The block is buffered until its parent is inserted
+                Ok(BlockStatus::Disconnected) => {
+                    return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
+                }
+                Err(err) => PayloadStatus::new(
+                    PayloadStatusEnum::Invalid { validation_error: err.to_string() },
+                    parent_hash,
+                ),
+            };
+            self.payload_validation_cache.insert(block_hash, status.clone());
+            return Ok(status)
         }
 
         let state_provider = self.client.latest()?;
@@ -325,13 +599,56 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
 
         let factory = reth_executor::Factory::new(self.chain_spec.clone());
         let mut executor = factory.with_sp(&state_provider);
-        match executor.execute_and_verify_receipt(&block.unseal(), total_difficulty, None) {
-            Ok(_) => Ok(PayloadStatus::new(PayloadStatusEnum::Valid, block_hash)),
-            Err(err) => Ok(PayloadStatus::new(
-                PayloadStatusEnum::Invalid { validation_error: err.to_string() },
-                parent_hash, // The parent hash is already in our database hence it is valid
-            )),
+        let status =
+            match executor.execute_and_verify_receipt(&block.unseal(), total_difficulty, None) {
+                Ok(_) => PayloadStatus::new(PayloadStatusEnum::Valid, block_hash),
+                Err(err) => PayloadStatus::new(
+                    PayloadStatusEnum::Invalid { validation_error: err.to_string() },
+                    parent_hash, // The parent hash is already in our database hence it is valid
+                ),
+            };
+        self.payload_validation_cache.insert(block_hash, status.clone());
+        Ok(status)
+    }
+
+    /// Validates the additional parameters of `engine_newPayloadV3` before processing the payload
+    /// like [EngineApi::new_payload].
+    ///
+    /// These responses should adhere to the [Engine API Spec for
+    /// `engine_newPayloadV3`](https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#specification).
+    pub fn new_payload_v3(
+        &mut self,
+        payload: ExecutionPayload,
+        cancun_fields: Option<CancunPayloadFields>,
+    ) -> EngineApiResult<PayloadStatus> {
+        let Some(CancunPayloadFields { versioned_hashes, parent_beacon_block_root }) =
+            cancun_fields
+        else {
+            return Err(EngineApiError::InvalidParams)
+        };
+
+        // The versioned hashes must match the blob versioned hashes of the payload transactions
+        // in order. Transactions that fail to decode are rejected by [EngineApi::new_payload].
+        if let Ok(transactions) = payload
+            .transactions
+            .iter()
+            .map(|tx| TransactionSigned::decode(&mut tx.as_ref()))
+            .collect::<std::result::Result<Vec<_>, _>>()
+        {
+            let blob_versioned_hashes = transactions
+                .iter()
+                .filter_map(|tx| tx.blob_versioned_hashes())
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>();
+            if versioned_hashes != blob_versioned_hashes {
+                return Ok(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                    validation_error: EngineApiError::PayloadVersionedHashes.to_string(),
+                }))
+            }
         }
+
+        self.on_new_payload(payload, Some(parent_beacon_block_root))
     }
 
     /// Called to resolve chain forks and ensure that the Execution layer is working with the latest
@@ -340,11 +657,12 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
     /// These responses should adhere to the [Engine API Spec for
     /// `engine_forkchoiceUpdated`](https://github.com/ethereum/execution-apis/blob/main/src/engine/paris.md#specification-1).
     pub fn fork_choice_updated(
-        &self,
+        &mut self,
         fork_choice_state: ForkchoiceState,
-        payload_attributes: Option<PayloadAttributes>,
+        payload_attributes: Option<PayloadAttributesVersion>,
     ) -> EngineApiResult<ForkchoiceUpdated> {
-        let ForkchoiceState { head_block_hash, finalized_block_hash, .. } = fork_choice_state;
+        let ForkchoiceState { head_block_hash, safe_block_hash, finalized_block_hash } =
+            fork_choice_state;
 
         if head_block_hash.is_zero() {
             return Ok(ForkchoiceUpdated::from_status(PayloadStatusEnum::Invalid {
@@ -352,6 +670,14 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             }))
         }
 
+        // Heads that were inserted as payloads are only written to the database once they're
+        // canonical. Other heads are synced by the pipeline.
+        if let Some(tree) = &self.blockchain_tree {
+            if let Err(error) = tree.make_canonical(&head_block_hash) {
+                tracing::debug!(target: "rpc::engine_api", ?error, head = ?head_block_hash, "Failed to make the head canonical in the blockchain tree");
+            }
+        }
+
         let head = if let Some(head) = self.client.header(&head_block_hash)? {
             head
         } else {
@@ -414,17 +740,85 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             .with_latest_valid_hash(H256::zero()))
         }
 
+        // From the Engine API spec:
+        //
+        // Client software MUST return -38002: Invalid forkchoice state error if the payload
+        // referenced by forkchoiceState.headBlockHash is VALID and a payload referenced by either
+        // forkchoiceState.finalizedBlockHash or forkchoiceState.safeBlockHash does not belong to
+        // the chain defined by forkchoiceState.headBlockHash.
+        //
+        // A head that is not a descendant of the previously finalized block would reorg finalized
+        // blocks, so it's invalid.
+        let last_finalized_hash = self.forkchoice_state_tx.borrow().finalized_block_hash;
+        match ForkchoiceValidator::new(&self.client).validate(
+            &fork_choice_state,
+            &head,
+            last_finalized_hash,
+        ) {
+            Ok(true) => {}
+            Ok(false) => return Ok(ForkchoiceUpdated::from_status(PayloadStatusEnum::Syncing)),
+            Err(error @ EngineApiError::ForkchoiceReorgsFinalized { .. }) => {
+                return Ok(ForkchoiceUpdated::from_status(PayloadStatusEnum::Invalid {
+                    validation_error: error.to_string(),
+                }))
+            }
+            Err(error) => return Err(error),
+        }
+
+        // The payloads were executed against the state of the previous head, which is no longer
+        // canonical if the new head is not a descendant of it.
+        let previous_head = self.forkchoice_state_tx.borrow().head_block_hash;
+        if !previous_head.is_zero() &&
+            previous_head != head_block_hash &&
+            previous_head != head.parent_hash
+        {
+            self.payload_validation_cache.clear();
+        }
+
+        // A deep reorg to a head that is not finalized could be a long-range attack, so the node
+        // syncs to the new head from scratch instead of unwinding its canonical chain.
+        let exceeds_reorg_depth = head_block_hash != finalized_block_hash &&
+            ForkchoiceValidator::new(&self.client).exceeds_reorg_depth(
+                previous_head,
+                head_block_hash,
+                &head,
+                self.max_reorg_depth,
+            )?;
+
         if let Err(error) = self.forkchoice_state_tx.send(fork_choice_state) {
             tracing::error!(target: "rpc::engine_api", ?error, "Failed to update forkchoice state");
         }
+        if let Some(tree) = &self.blockchain_tree {
+            tree.set_safe_and_finalized(safe_block_hash, finalized_block_hash);
+        }
 
-        if let Some(_attr) = payload_attributes {
-            // TODO: optionally build the block
+        if exceeds_reorg_depth {
+            tracing::warn!(target: "rpc::engine_api", ?previous_head, head = ?head_block_hash, max_reorg_depth = self.max_reorg_depth, "Forkchoice update exceeds the reorg depth limit, syncing to the new head");
+            return Ok(ForkchoiceUpdated::from_status(PayloadStatusEnum::Syncing))
+        }
+
+        let mut built_payload_id = None;
+        if let (Some(attributes), Some(builder)) = (payload_attributes, &self.payload_builder) {
+            let parent = head.seal(head_block_hash);
+            match builder.build_payload(&parent, &attributes) {
+                Ok((block, sidecars)) => {
+                    let id = payload_id(&head_block_hash, &attributes);
+                    self.insert_built_payload(id, block.into(), sidecars.into());
+                    built_payload_id = Some(id);
+                }
+                Err(error) => {
+                    tracing::warn!(target: "rpc::engine_api", ?error, parent = ?head_block_hash, "Failed to build the payload");
+                }
+            }
         }
 
         let chain_info = self.client.chain_info()?;
-        Ok(ForkchoiceUpdated::from_status(PayloadStatusEnum::Valid)
-            .with_latest_valid_hash(chain_info.best_hash))
+        let mut res = ForkchoiceUpdated::from_status(PayloadStatusEnum::Valid)
+            .with_latest_valid_hash(chain_info.best_hash);
+        if let Some(id) = built_payload_id {
+            res = res.with_payload_id(id);
+        }
+        Ok(res)
     }
 
     /// Called to verify network configuration parameters and ensure that Consensus and Execution
@@ -477,6 +871,18 @@ impl<Client: HeaderProvider + BlockProvider + StateProviderFactory + EvmEnvProvi
             }),
         }
     }
+
+    /// Called by the Consensus layer to advertise the Engine API methods it supports.
+    ///
+    /// Responds with the list of methods supported by the Execution layer, see
+    /// [SUPPORTED_ENGINE_METHODS]. The capabilities of the Consensus layer are only logged.
+    ///
+    /// These responses should adhere to the [Engine API Spec for
+    /// `engine_exchangeCapabilities`](https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/common.md#capabilities).
+    pub fn exchange_capabilities(&self, capabilities: Vec<String>) -> Vec<String> {
+        tracing::debug!(target: "rpc::engine_api", ?capabilities, "Received consensus layer capabilities");
+        SUPPORTED_ENGINE_METHODS.into_iter().map(str::to_owned).collect()
+    }
 }
 
 impl<Client> Future for EngineApi<Client>
@@ -487,11 +893,38 @@ where
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
+
+        let interval = this
+            .forkchoice_lag_interval
+            .get_or_insert_with(|| tokio::time::interval(FORKCHOICE_LAG_METRIC_INTERVAL));
+        while interval.poll_tick(cx).is_ready() {
+            this.metrics
+                .forkchoice_lag_seconds
+                .set(this.last_forkchoice_updated.elapsed().as_secs_f64());
+        }
+
         loop {
-            match ready!(this.message_rx.poll_next_unpin(cx)) {
-                Some(msg) => this.on_message(msg),
+            let Poll::Ready(msg) = this.message_rx.poll_next_unpin(cx) else {
+                // all received messages are handled, process the latest forkchoice update
+                this.on_queued_forkchoice_update();
+                return Poll::Pending
+            };
+
+            match msg {
+                Some(EngineApiMessage::ForkchoiceUpdated(version, state, attrs, tx)) => {
+                    // forkchoice updates are deferred, so that they can be superseded by
+                    // subsequent ones
+                    this.forkchoice_queue
+                        .push(QueuedForkchoiceUpdate::new(version, state, attrs, tx));
+                }
+                Some(msg) => {
+                    // preserve the order of the messages
+                    this.on_queued_forkchoice_update();
+                    this.on_message(msg)
+                }
                 None => {
                     // channel closed
+                    this.on_queued_forkchoice_update();
                     return Poll::Ready(())
                 }
             }
@@ -504,7 +937,10 @@ mod tests {
     use super::*;
     use assert_matches::assert_matches;
     use reth_interfaces::test_utils::generators::random_block;
-    use reth_primitives::{H256, MAINNET};
+    use reth_primitives::{
+        BlobTransaction, BlobTransactionSidecar, Block, Bytes, ChainSpecBuilder, SealedHeader,
+        Signature, Transaction, BLOB_SIZE, H256, MAINNET, U64,
+    };
     use reth_provider::test_utils::MockEthProvider;
     use std::sync::Arc;
     use tokio::sync::{
@@ -513,7 +949,12 @@ mod tests {
     };
 
     fn setup_engine_api() -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>>) {
-        let chain_spec = Arc::new(MAINNET.clone());
+        setup_engine_api_with_chain_spec(Arc::new(MAINNET.clone()))
+    }
+
+    fn setup_engine_api_with_chain_spec(
+        chain_spec: Arc<ChainSpec>,
+    ) -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>>) {
         let client = Arc::new(MockEthProvider::default());
         let (msg_tx, msg_rx) = unbounded_channel();
         let (forkchoice_state_tx, forkchoice_state_rx) = watch::channel(ForkchoiceState::default());
@@ -522,6 +963,19 @@ mod tests {
             chain_spec: chain_spec.clone(),
             message_rx: UnboundedReceiverStream::new(msg_rx),
             forkchoice_state_tx,
+            built_payloads: LruCache::new(
+                NonZeroUsize::new(EngineApiConfig::default().built_payloads_max_entries).unwrap(),
+            ),
+            payload_validation_cache: PayloadValidationCache::new(
+                EngineApiConfig::default().payload_validation_cache_max_entries,
+            ),
+            forkchoice_queue: ForkchoiceQueue::default(),
+            max_reorg_depth: EngineApiConfig::default().max_reorg_depth,
+            metrics: EngineApiMetrics::default(),
+            last_forkchoice_updated: Instant::now(),
+            forkchoice_lag_interval: None,
+            blockchain_tree: None,
+            payload_builder: None,
         };
         let handle = EngineApiTestHandle { chain_spec, client, msg_tx, forkchoice_state_rx };
         (handle, api)
@@ -548,12 +1002,79 @@ mod tests {
         }
     }
 
+    /// A blockchain tree that records the blocks the Engine API inserts and makes canonical.
+    #[derive(Debug, Default)]
+    struct TestBlockchainTree {
+        inserted: std::sync::Mutex<Vec<BlockHash>>,
+        canonical: std::sync::Mutex<Vec<BlockHash>>,
+        safe_and_finalized: std::sync::Mutex<Option<(BlockHash, BlockHash)>>,
+    }
+
+    impl BlockchainTreeEngine for TestBlockchainTree {
+        fn insert_block(&self, block: SealedBlock) -> Result<BlockStatus, reth_interfaces::Error> {
+            self.inserted.lock().unwrap().push(block.hash());
+            Ok(BlockStatus::Accepted)
+        }
+
+        fn make_canonical(&self, block_hash: &BlockHash) -> Result<(), reth_interfaces::Error> {
+            self.canonical.lock().unwrap().push(*block_hash);
+            Ok(())
+        }
+
+        fn set_safe_and_finalized(
+            &self,
+            safe_block_hash: BlockHash,
+            finalized_block_hash: BlockHash,
+        ) {
+            *self.safe_and_finalized.lock().unwrap() =
+                Some((safe_block_hash, finalized_block_hash));
+        }
+    }
+
+    /// A payload builder that builds a block with a blob transaction on top of the parent.
+    #[derive(Debug)]
+    struct TestPayloadBuilder {
+        sidecar: BlobTransactionSidecar,
+    }
+
+    impl PayloadBuilder for TestPayloadBuilder {
+        fn build_payload(
+            &self,
+            parent: &SealedHeader,
+            attributes: &PayloadAttributesVersion,
+        ) -> Result<(SealedBlock, Vec<BlobTransactionSidecar>), reth_interfaces::Error> {
+            let transaction = TransactionSigned::from_transaction_and_signature(
+                Transaction::Blob(BlobTransaction {
+                    chain_id: 1,
+                    blob_versioned_hashes: vec![H256::random()],
+                    ..Default::default()
+                }),
+                Signature::default(),
+            );
+            let block = Block {
+                header: Header {
+                    parent_hash: parent.hash(),
+                    number: parent.number + 1,
+                    timestamp: attributes.payload_attributes().timestamp.as_u64(),
+                    transactions_root: proofs::calculate_transaction_root([&transaction]),
+                    ..Default::default()
+                },
+                body: vec![transaction],
+                ommers: vec![],
+                withdrawals: None,
+            };
+            Ok((block.seal_slow(), vec![self.sidecar.clone()]))
+        }
+    }
+
     mod new_payload {
         use super::*;
         use reth_interfaces::test_utils::generators::random_header;
         use reth_primitives::{
             bytes::{Bytes, BytesMut},
-            Block,
+            hex_literal::hex,
+            proofs::EMPTY_ROOT,
+            Address, BlobTransaction, Block, HeaderValidationError, Signature, Transaction,
         };
         use reth_rlp::DecodeError;
 
@@ -584,7 +1105,10 @@ mod tests {
                 b.header.extra_data = BytesMut::zeroed(32).freeze().into();
                 b
             });
-            assert_matches!(api.try_construct_block(block_with_valid_extra_data.into()), Ok(_));
+            assert_matches!(
+                api.try_construct_block(block_with_valid_extra_data.into(), None),
+                Ok(_)
+            );
 
             // Invalid extra data
             let block_with_invalid_extra_data: Bytes = BytesMut::zeroed(33).freeze();
@@ -593,7 +1117,7 @@ mod tests {
                 b
             });
             assert_matches!(
-                api.try_construct_block(invalid_extra_data_block.into()),
+                api.try_construct_block(invalid_extra_data_block.into(), None),
                 Err(EngineApiError::PayloadExtraData(data)) if data == block_with_invalid_extra_data
             );
 
@@ -603,7 +1127,7 @@ mod tests {
                 b
             });
             assert_matches!(
-                api.try_construct_block(block_with_zero_base_fee.into()),
+                api.try_construct_block(block_with_zero_base_fee.into(), None),
                 Err(EngineApiError::PayloadBaseFee(val)) if val == U256::ZERO
             );
 
@@ -613,7 +1137,7 @@ mod tests {
                 *tx = Bytes::new().into();
             });
             assert_matches!(
-                api.try_construct_block(payload_with_invalid_txs),
+                api.try_construct_block(payload_with_invalid_txs, None),
                 Err(EngineApiError::Decode(DecodeError::InputTooShort))
             );
 
@@ -623,7 +1147,7 @@ mod tests {
                 b
             });
             assert_matches!(
-                api.try_construct_block(block_with_ommers.clone().into()),
+                api.try_construct_block(block_with_ommers.clone().into(), None),
                 Err(EngineApiError::PayloadBlockHash { consensus, .. })
                     if consensus == block_with_ommers.hash()
             );
@@ -634,7 +1158,7 @@ mod tests {
                 b
             });
             assert_matches!(
-                api.try_construct_block(block_with_difficulty.clone().into()),
+                api.try_construct_block(block_with_difficulty.clone().into(), None),
                 Err(EngineApiError::PayloadBlockHash { consensus, .. })
                     if consensus == block_with_difficulty.hash()
             );
@@ -645,14 +1169,55 @@ mod tests {
                 b
             });
             assert_matches!(
-                api.try_construct_block(block_with_nonce.clone().into()),
+                api.try_construct_block(block_with_nonce.clone().into(), None),
                 Err(EngineApiError::PayloadBlockHash { consensus, .. })
                     if consensus == block_with_nonce.hash()
             );
 
             // Valid block
             let valid_block = block;
-            assert_matches!(api.try_construct_block(valid_block.into()), Ok(_));
+            assert_matches!(api.try_construct_block(valid_block.into(), None), Ok(_));
+        }
+
+        #[tokio::test]
+        async fn payload_v3_round_trip() {
+            let (_, api) = setup_engine_api();
+
+            let parent_beacon_block_root = H256::repeat_byte(0x55);
+            let payload = ExecutionPayload {
+                parent_hash: H256::repeat_byte(0x11),
+                fee_recipient: Address::repeat_byte(0x22),
+                state_root: H256::repeat_byte(0x33),
+                receipts_root: EMPTY_ROOT,
+                logs_bloom: Default::default(),
+                prev_randao: H256::repeat_byte(0x44),
+                block_number: U64::from(1),
+                gas_limit: U64::from(30_000_000),
+                gas_used: U64::zero(),
+                timestamp: U64::from(1_700_000_000),
+                extra_data: Default::default(),
+                base_fee_per_gas: U256::from(7),
+                block_hash: H256(hex!(
+                    "68f080724c9822c21ebb41c37142152e775c9bddcea835e84c45826c197924f3"
+                )),
+                transactions: vec![],
+                withdrawals: Some(vec![]),
+                blob_gas_used: Some(U64::from(0x20000)),
+                excess_blob_gas: Some(U64::zero()),
+            };
+
+            let block =
+                api.try_construct_block(payload.clone(), Some(parent_beacon_block_root)).unwrap();
+            assert_eq!(block.blob_gas_used, Some(0x20000));
+            assert_eq!(block.excess_blob_gas, Some(0));
+            assert_eq!(block.parent_beacon_block_root, Some(parent_beacon_block_root));
+            assert_eq!(ExecutionPayload::from(block), payload);
+
+            // The beacon block root is part of the block hash
+            assert_matches!(
+                api.try_construct_block(payload, None),
+                Err(EngineApiError::PayloadBlockHash { .. })
+            );
         }
 
         #[tokio::test]
@@ -670,6 +1235,7 @@ mod tests {
             handle.send_message(EngineApiMessage::NewPayload(
                 EngineApiMessageVersion::V1,
                 execution_payload,
+                None,
                 result_tx,
             ));
 
@@ -687,6 +1253,7 @@ mod tests {
             handle.send_message(EngineApiMessage::NewPayload(
                 EngineApiMessageVersion::V1,
                 block.into(),
+                None,
                 result_tx,
             ));
 
@@ -712,6 +1279,7 @@ mod tests {
             handle.send_message(EngineApiMessage::NewPayload(
                 EngineApiMessageVersion::V1,
                 block.clone().into(),
+                None,
                 result_tx,
             ));
 
@@ -747,6 +1315,7 @@ mod tests {
             handle.send_message(EngineApiMessage::NewPayload(
                 EngineApiMessageVersion::V1,
                 block.clone().into(),
+                None,
                 result_tx,
             ));
 
@@ -760,69 +1329,417 @@ mod tests {
             assert_matches!( result_rx.await, Ok(Ok(result)) => assert_eq!(result, expected_result));
         }
 
-        // TODO: add execution tests
-    }
+        /// Adds a post-merge block to the client, with a gas target and base fee for its children.
+        fn add_post_merge_parent(handle: &EngineApiTestHandle) -> SealedBlock {
+            let parent = transform_block(random_block(20_000_000, None, None, Some(0)), |mut b| {
+                b.header.timestamp = 100;
+                b.header.gas_limit = 30_000_000;
+                b.header.gas_used = 15_000_000;
+                b.header.base_fee_per_gas = Some(1_000_000_000);
+                b.header.difficulty =
+                    handle.chain_spec.fork(Hardfork::Paris).ttd().unwrap() + U256::from(1);
+                b
+            });
+            handle.client.add_block(parent.hash(), parent.clone().unseal());
+            parent
+        }
 
-    // non exhaustive tests for engine_getPayload
-    // TODO: amend when block building is implemented
-    mod get_payload {
-        use super::*;
+        /// Returns an empty child of [add_post_merge_parent] that is valid against its parent,
+        /// after applying the given changes to its header.
+        fn child_block(parent: &SealedBlock, f: fn(&mut Header)) -> SealedBlock {
+            let block = random_block(20_000_001, Some(parent.hash()), Some(0), Some(0));
+            transform_block(block, |mut b| {
+                b.header.timestamp = 112;
+                b.header.gas_limit = 30_000_000;
+                b.header.base_fee_per_gas = Some(1_000_000_000);
+                f(&mut b.header);
+                b
+            })
+        }
 
         #[tokio::test]
-        async fn payload_unknown() {
-            let (handle, api) = setup_engine_api();
-            tokio::spawn(api);
+        async fn payload_validated_against_parent() {
+            let (handle, mut api) = setup_engine_api();
 
-            let payload_id = H64::random();
-            let (result_tx, result_rx) = oneshot::channel();
-            handle.send_message(EngineApiMessage::GetPayload(payload_id, result_tx));
+            let parent = add_post_merge_parent(&handle);
+            let child = |f: fn(&mut Header)| child_block(&parent, f);
 
-            assert_matches!(result_rx.await, Ok(Err(EngineApiError::PayloadUnknown)));
-        }
-    }
+            assert_matches!(
+                api.validate_payload_against_parent(&child(|_| {}), &parent.header),
+                Ok(())
+            );
+            assert_matches!(
+                api.validate_payload_against_parent(&child(|h| h.number += 1), &parent.header),
+                Err(EngineApiError::PayloadBlockNumber { invalid: 20_000_002, parent: 20_000_000 })
+            );
+            assert_matches!(
+                api.validate_payload_against_parent(
+                    &child(|h| h.gas_used = h.gas_limit + 1),
+                    &parent.header
+                ),
+                Err(EngineApiError::PayloadGasUsed { gas_used: 30_000_001, gas_limit: 30_000_000 })
+            );
+            assert_matches!(
+                api.validate_payload_against_parent(
+                    &child(|h| h.base_fee_per_gas = Some(1)),
+                    &parent.header
+                ),
+                Err(EngineApiError::PayloadHeader(HeaderValidationError::BaseFeeDiff {
+                    expected: 1_000_000_000,
+                    got: 1
+                }))
+            );
 
-    // tests covering `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`
-    mod get_payload_bodies {
-        use super::*;
-        use reth_interfaces::test_utils::generators::random_block_range;
+            // invalid payloads are rejected before they are executed
+            let expected_result = PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                validation_error: EngineApiError::PayloadBlockNumber {
+                    invalid: 20_000_002,
+                    parent: 20_000_000,
+                }
+                .to_string(),
+            });
+            assert_matches!(
+                api.new_payload(child(|h| h.number += 1).into()),
+                Ok(result) => assert_eq!(result, expected_result)
+            );
+        }
 
         #[tokio::test]
-        async fn invalid_params() {
+        async fn payload_inserted_into_blockchain_tree() {
             let (handle, api) = setup_engine_api();
-            tokio::spawn(api);
+            let tree = Arc::new(TestBlockchainTree::default());
+            let mut api = api.with_blockchain_tree(tree.clone());
 
-            let by_range_tests = [
-                // (start, count)
-                (0, 0),
-                (0, 1),
-                (1, 0),
-            ];
+            let parent = add_post_merge_parent(&handle);
+            let block = child_block(&parent, |_| {});
 
-            // test [EngineApiMessage::GetPayloadBodiesByRange]
-            for (start, count) in by_range_tests {
-                let (result_tx, result_rx) = oneshot::channel();
-                handle.send_message(EngineApiMessage::GetPayloadBodiesByRange(
-                    start, count, result_tx,
-                ));
-                assert_matches!(result_rx.await, Ok(Err(EngineApiError::InvalidParams)));
-            }
+            let expected_result = PayloadStatus::from_status(PayloadStatusEnum::Accepted);
+            assert_matches!(
+                api.new_payload(block.clone().into()),
+                Ok(result) => assert_eq!(result, expected_result)
+            );
+            assert_eq!(*tree.inserted.lock().unwrap(), vec![block.hash()]);
         }
 
         #[tokio::test]
-        async fn request_too_large() {
-            let (handle, api) = setup_engine_api();
-            tokio::spawn(api);
+        async fn payload_validation_cached() {
+            let (_handle, mut api) = setup_engine_api();
 
-            let request_count = MAX_PAYLOAD_BODIES_LIMIT + 1;
+            // the parent is unknown, so the payload would not be executed
+            let block = random_block(100, Some(H256::random()), None, Some(0));
+            let block_hash = block.hash();
+            let cached = PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                validation_error: "invalid".to_string(),
+            });
+            api.payload_validation_cache.insert(block_hash, cached.clone());
 
-            let (result_tx, result_rx) = oneshot::channel();
-            handle.send_message(EngineApiMessage::GetPayloadBodiesByRange(
-                0,
-                request_count,
-                result_tx,
-            ));
-            assert_matches!(
-                result_rx.await,
+            assert_matches!(api.new_payload(block.into()), Ok(result) => assert_eq!(result, cached));
+        }
+
+        #[tokio::test]
+        async fn invalid_payload_cached() {
+            let (handle, mut api) = setup_engine_api();
+
+            let parent = transform_block(random_block(100, None, None, Some(0)), |mut b| {
+                b.header.timestamp = 110;
+                b.header.difficulty =
+                    handle.chain_spec.fork(Hardfork::Paris).ttd().unwrap() + U256::from(1);
+                b
+            });
+            let block =
+                transform_block(random_block(101, Some(parent.hash()), None, Some(0)), |mut b| {
+                    b.header.timestamp = 100;
+                    b
+                });
+            handle.client.add_block(parent.hash(), parent.unseal());
+
+            let result = api.new_payload(block.clone().into()).unwrap();
+            assert_matches!(result.status, PayloadStatusEnum::Invalid { .. });
+            assert_eq!(api.payload_validation_cache.get(&block.hash()), Some(result));
+        }
+
+        #[tokio::test]
+        async fn v3_payload_rejected_by_v1_and_v2() {
+            let (handle, api) = setup_engine_api();
+            tokio::spawn(api);
+
+            let block = random_block(100, Some(H256::random()), None, Some(0));
+            let mut payload: ExecutionPayload = block.into();
+            payload.blob_gas_used = Some(U64::zero());
+            payload.excess_blob_gas = Some(U64::zero());
+
+            for version in [EngineApiMessageVersion::V1, EngineApiMessageVersion::V2] {
+                let (result_tx, result_rx) = oneshot::channel();
+                handle.send_message(EngineApiMessage::NewPayload(
+                    version,
+                    payload.clone(),
+                    None,
+                    result_tx,
+                ));
+                assert_matches!(result_rx.await, Ok(Err(EngineApiError::InvalidParams)));
+            }
+        }
+
+        #[tokio::test]
+        async fn v3_payload_pre_cancun() {
+            let (handle, api) = setup_engine_api();
+            tokio::spawn(api);
+
+            let block = random_block(100, Some(H256::random()), None, Some(0));
+            let mut payload: ExecutionPayload = block.into();
+            payload.withdrawals = Some(vec![]);
+            payload.blob_gas_used = Some(U64::zero());
+            payload.excess_blob_gas = Some(U64::zero());
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::NewPayload(
+                EngineApiMessageVersion::V3,
+                payload,
+                Some(CancunPayloadFields::default()),
+                result_tx,
+            ));
+            assert_matches!(result_rx.await, Ok(Err(EngineApiError::UnsupportedFork)));
+        }
+
+        #[tokio::test]
+        async fn v3_payload_invalid_versioned_hashes() {
+            let chain_spec = Arc::new(ChainSpecBuilder::mainnet().cancun_activated().build());
+            let (handle, api) = setup_engine_api_with_chain_spec(chain_spec);
+            tokio::spawn(api);
+
+            let block = random_block(100, Some(H256::random()), None, Some(0));
+            let mut payload: ExecutionPayload = block.into();
+            payload.withdrawals = Some(vec![]);
+
+            // missing blob fields
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::NewPayload(
+                EngineApiMessageVersion::V3,
+                payload.clone(),
+                Some(CancunPayloadFields::default()),
+                result_tx,
+            ));
+            assert_matches!(result_rx.await, Ok(Err(EngineApiError::InvalidParams)));
+
+            payload.blob_gas_used = Some(U64::zero());
+            payload.excess_blob_gas = Some(U64::zero());
+            let cancun_fields = CancunPayloadFields {
+                versioned_hashes: vec![H256::random()],
+                parent_beacon_block_root: H256::random(),
+            };
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::NewPayload(
+                EngineApiMessageVersion::V3,
+                payload,
+                Some(cancun_fields),
+                result_tx,
+            ));
+
+            let expected_result = PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                validation_error: EngineApiError::PayloadVersionedHashes.to_string(),
+            });
+            assert_matches!(result_rx.await, Ok(Ok(result)) => assert_eq!(result, expected_result));
+        }
+
+        #[tokio::test]
+        async fn v3_payload_with_blob_transaction() {
+            let chain_spec = Arc::new(ChainSpecBuilder::mainnet().cancun_activated().build());
+            let (handle, api) = setup_engine_api_with_chain_spec(chain_spec);
+            tokio::spawn(api);
+
+            let blob_versioned_hashes = vec![H256::random(), H256::random()];
+            let transaction = TransactionSigned::from_transaction_and_signature(
+                Transaction::Blob(BlobTransaction {
+                    chain_id: 1,
+                    blob_versioned_hashes: blob_versioned_hashes.clone(),
+                    ..Default::default()
+                }),
+                Signature::default(),
+            );
+            let block = random_block(100, Some(H256::random()), None, Some(0));
+            let block = transform_block(block, |mut b| {
+                b.body = vec![transaction];
+                b.withdrawals = Some(vec![]);
+                b.header.withdrawals_root = Some(EMPTY_ROOT);
+                b.header.blob_gas_used = Some(0);
+                b.header.excess_blob_gas = Some(0);
+                b.header.parent_beacon_block_root = Some(H256::zero());
+                b
+            });
+            let payload: ExecutionPayload = block.into();
+
+            // versioned hashes in the wrong order
+            let cancun_fields = CancunPayloadFields {
+                versioned_hashes: blob_versioned_hashes.iter().rev().copied().collect(),
+                parent_beacon_block_root: H256::zero(),
+            };
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::NewPayload(
+                EngineApiMessageVersion::V3,
+                payload.clone(),
+                Some(cancun_fields),
+                result_tx,
+            ));
+            let expected_result = PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                validation_error: EngineApiError::PayloadVersionedHashes.to_string(),
+            });
+            assert_matches!(result_rx.await, Ok(Ok(result)) => assert_eq!(result, expected_result));
+
+            // matching versioned hashes, the payload is processed and the parent is unknown
+            let cancun_fields = CancunPayloadFields {
+                versioned_hashes: blob_versioned_hashes,
+                parent_beacon_block_root: H256::zero(),
+            };
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::NewPayload(
+                EngineApiMessageVersion::V3,
+                payload,
+                Some(cancun_fields),
+                result_tx,
+            ));
+            let expected_result = PayloadStatus::from_status(PayloadStatusEnum::Syncing);
+            assert_matches!(result_rx.await, Ok(Ok(result)) => assert_eq!(result, expected_result));
+        }
+
+        // TODO: add execution tests
+    }
+
+    // non exhaustive tests for engine_getPayload
+    mod get_payload {
+        use super::*;
+
+        #[tokio::test]
+        async fn payload_unknown() {
+            let (handle, api) = setup_engine_api();
+            tokio::spawn(api);
+
+            let payload_id = H64::random();
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::GetPayload(payload_id, result_tx));
+
+            assert_matches!(result_rx.await, Ok(Err(EngineApiError::PayloadUnknown)));
+        }
+
+        #[tokio::test]
+        async fn returns_built_payload() {
+            let (handle, mut api) = setup_engine_api();
+
+            let payload_id = H64::random();
+            let payload: ExecutionPayload =
+                random_block(100, Some(H256::random()), None, Some(0)).into();
+            api.insert_built_payload(payload_id, payload.clone(), BlobsBundleV1::default());
+            tokio::spawn(api);
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::GetPayload(payload_id, result_tx));
+
+            assert_matches!(result_rx.await, Ok(Ok(result)) => assert_eq!(result, payload));
+        }
+    }
+
+    // tests covering `engine_getBlobsBundleV1`
+    mod get_blobs_bundle {
+        use super::*;
+
+        #[tokio::test]
+        async fn payload_unknown() {
+            let (handle, api) = setup_engine_api();
+            tokio::spawn(api);
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::GetBlobsBundle(H64::random(), result_tx));
+
+            assert_matches!(result_rx.await, Ok(Err(EngineApiError::PayloadUnknown)));
+        }
+
+        #[tokio::test]
+        async fn returns_stored_bundle() {
+            let (handle, mut api) = setup_engine_api();
+
+            let payload_id = H64::random();
+            let block = random_block(100, Some(H256::random()), None, Some(0));
+            let bundle = BlobsBundleV1 {
+                commitments: vec![Bytes::from(vec![1u8; 48]), Bytes::from(vec![2u8; 48])],
+                proofs: vec![Bytes::from(vec![3u8; 48]), Bytes::from(vec![4u8; 48])],
+                blobs: vec![Bytes::from(vec![5u8; 32]), Bytes::from(vec![6u8; 32])],
+            };
+            api.insert_built_payload(payload_id, block.into(), bundle.clone());
+            tokio::spawn(api);
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::GetBlobsBundle(payload_id, result_tx));
+
+            assert_matches!(result_rx.await, Ok(Ok(result)) => {
+                assert_eq!(result.commitments, bundle.commitments);
+                assert_eq!(result, bundle);
+            });
+        }
+
+        #[tokio::test]
+        async fn evicted_with_payload() {
+            let (_, mut api) = setup_engine_api();
+
+            let first = H64::random();
+            api.insert_built_payload(
+                first,
+                random_block(100, Some(H256::random()), None, Some(0)).into(),
+                BlobsBundleV1::default(),
+            );
+            for _ in 0..EngineApiConfig::default().built_payloads_max_entries {
+                api.insert_built_payload(
+                    H64::random(),
+                    random_block(100, Some(H256::random()), None, Some(0)).into(),
+                    BlobsBundleV1::default(),
+                );
+            }
+
+            assert_eq!(api.get_payload(first), None);
+            assert_eq!(api.get_blobs_bundle(first), None);
+        }
+    }
+
+    // tests covering `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`
+    mod get_payload_bodies {
+        use super::*;
+        use reth_interfaces::test_utils::generators::random_block_range;
+
+        #[tokio::test]
+        async fn invalid_params() {
+            let (handle, api) = setup_engine_api();
+            tokio::spawn(api);
+
+            let by_range_tests = [
+                // (start, count)
+                (0, 0),
+                (0, 1),
+                (1, 0),
+            ];
+
+            // test [EngineApiMessage::GetPayloadBodiesByRange]
+            for (start, count) in by_range_tests {
+                let (result_tx, result_rx) = oneshot::channel();
+                handle.send_message(EngineApiMessage::GetPayloadBodiesByRange(
+                    start, count, result_tx,
+                ));
+                assert_matches!(result_rx.await, Ok(Err(EngineApiError::InvalidParams)));
+            }
+        }
+
+        #[tokio::test]
+        async fn request_too_large() {
+            let (handle, api) = setup_engine_api();
+            tokio::spawn(api);
+
+            let request_count = MAX_PAYLOAD_BODIES_LIMIT + 1;
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::GetPayloadBodiesByRange(
+                0,
+                request_count,
+                result_tx,
+            ));
+            assert_matches!(
+                result_rx.await,
                 Ok(Err(EngineApiError::PayloadRequestTooLarge { .. }))
             );
 
@@ -903,6 +1820,7 @@ mod tests {
     mod fork_choice_updated {
         use super::*;
         use reth_interfaces::test_utils::generators::random_header;
+        use reth_rpc_types::engine::{PayloadAttributes, PayloadAttributesV3};
 
         #[tokio::test]
         async fn empty_head() {
@@ -924,6 +1842,58 @@ mod tests {
             assert!(!handle.forkchoice_state_has_changed());
         }
 
+        #[tokio::test]
+        async fn v3_attributes_pre_cancun() {
+            let chain_spec = Arc::new(ChainSpecBuilder::mainnet().shanghai_activated().build());
+            let (handle, api) = setup_engine_api_with_chain_spec(chain_spec);
+            tokio::spawn(api);
+
+            let attributes = PayloadAttributesV3 {
+                payload_attributes: PayloadAttributes {
+                    timestamp: U64::from(1),
+                    prev_randao: H256::random(),
+                    suggested_fee_recipient: Default::default(),
+                    withdrawals: Some(vec![]),
+                },
+                parent_beacon_block_root: H256::random(),
+            };
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::ForkchoiceUpdated(
+                EngineApiMessageVersion::V3,
+                ForkchoiceState::default(),
+                Some(attributes.into()),
+                result_tx,
+            ));
+
+            assert_matches!(result_rx.await, Ok(Err(EngineApiError::UnsupportedFork)));
+            assert!(!handle.forkchoice_state_has_changed());
+        }
+
+        #[tokio::test]
+        async fn v3_attributes_without_parent_beacon_block_root() {
+            let chain_spec = Arc::new(ChainSpecBuilder::mainnet().cancun_activated().build());
+            let (handle, api) = setup_engine_api_with_chain_spec(chain_spec);
+            tokio::spawn(api);
+
+            let attributes = PayloadAttributes {
+                timestamp: U64::from(1),
+                prev_randao: H256::random(),
+                suggested_fee_recipient: Default::default(),
+                withdrawals: Some(vec![]),
+            };
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::ForkchoiceUpdated(
+                EngineApiMessageVersion::V3,
+                ForkchoiceState::default(),
+                Some(attributes.into()),
+                result_tx,
+            ));
+
+            assert_matches!(result_rx.await, Ok(Err(EngineApiError::InvalidParams)));
+        }
+
         #[tokio::test]
         async fn unknown_head_hash() {
             let (handle, api) = setup_engine_api();
@@ -971,27 +1941,38 @@ mod tests {
             assert!(!handle.forkchoice_state_has_changed());
         }
 
+        /// Adds a chain of post-merge headers on top of the given parent to the client.
+        fn post_merge_chain(
+            handle: &EngineApiTestHandle,
+            range: std::ops::Range<u64>,
+            parent: H256,
+        ) -> Vec<SealedHeader> {
+            let ttd = handle.chain_spec.fork(Hardfork::Paris).ttd().unwrap();
+            let mut headers: Vec<SealedHeader> = Vec::new();
+            for number in range {
+                let parent = headers.last().map(|h| h.hash()).unwrap_or(parent);
+                let mut header = random_header(number, Some(parent)).unseal();
+                header.difficulty = ttd;
+                headers.push(header.seal_slow());
+            }
+            handle.client.extend_headers(
+                headers.iter().map(|header| (header.hash(), header.clone().unseal())),
+            );
+            headers
+        }
+
         #[tokio::test]
         async fn forkchoice_state_is_updated() {
             let (handle, api) = setup_engine_api();
             tokio::spawn(api);
 
-            let ttd = handle.chain_spec.fork(Hardfork::Paris).ttd().unwrap();
-            let finalized = random_header(90, None);
-            let mut head = random_header(100, None).unseal();
-
-            // set the difficulty so we know it is post-merge
-            head.difficulty = ttd;
-            let head = head.seal_slow();
-            handle.client.extend_headers([
-                (head.hash(), head.clone().unseal()),
-                (finalized.hash(), finalized.clone().unseal()),
-            ]);
+            let chain = post_merge_chain(&handle, 90..101, H256::zero());
+            let head = chain.last().unwrap();
 
             let state = ForkchoiceState {
                 head_block_hash: head.hash(),
-                finalized_block_hash: finalized.hash(),
-                ..Default::default()
+                safe_block_hash: chain[5].hash(),
+                finalized_block_hash: chain[0].hash(),
             };
 
             let (result_tx, result_rx) = oneshot::channel();
@@ -1015,6 +1996,318 @@ mod tests {
             assert_eq!(handle.forkchoice_state(), state);
         }
 
+        #[tokio::test]
+        async fn head_made_canonical_in_blockchain_tree() {
+            let (handle, api) = setup_engine_api();
+            let tree = Arc::new(TestBlockchainTree::default());
+            tokio::spawn(api.with_blockchain_tree(tree.clone()));
+
+            let chain = post_merge_chain(&handle, 90..101, H256::zero());
+            let head = chain.last().unwrap();
+
+            let state = ForkchoiceState { head_block_hash: head.hash(), ..Default::default() };
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::ForkchoiceUpdated(
+                EngineApiMessageVersion::V1,
+                state,
+                None,
+                result_tx,
+            ));
+
+            assert_matches!(result_rx.await, Ok(Ok(result)) => {
+                assert_eq!(result.payload_status.status, PayloadStatusEnum::Valid)
+            });
+            assert_eq!(*tree.canonical.lock().unwrap(), vec![head.hash()]);
+        }
+
+        #[tokio::test]
+        async fn safe_and_finalized_tracked_in_blockchain_tree() {
+            let (handle, api) = setup_engine_api();
+            let tree = Arc::new(TestBlockchainTree::default());
+            tokio::spawn(api.with_blockchain_tree(tree.clone()));
+
+            let chain = post_merge_chain(&handle, 90..101, H256::zero());
+            let state = ForkchoiceState {
+                head_block_hash: chain.last().unwrap().hash(),
+                safe_block_hash: chain[5].hash(),
+                finalized_block_hash: chain[0].hash(),
+            };
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::ForkchoiceUpdated(
+                EngineApiMessageVersion::V1,
+                state,
+                None,
+                result_tx,
+            ));
+
+            assert_matches!(result_rx.await, Ok(Ok(result)) => {
+                assert_eq!(result.payload_status.status, PayloadStatusEnum::Valid)
+            });
+            assert_eq!(
+                *tree.safe_and_finalized.lock().unwrap(),
+                Some((chain[5].hash(), chain[0].hash()))
+            );
+        }
+
+        #[tokio::test]
+        async fn payload_built_with_blobs_bundle() {
+            let (handle, api) = setup_engine_api();
+            let sidecar = BlobTransactionSidecar {
+                blobs: vec![[5; BLOB_SIZE]],
+                commitments: vec![[1; 48]],
+                proofs: vec![[3; 48]],
+            };
+            let builder = Arc::new(TestPayloadBuilder { sidecar: sidecar.clone() });
+            tokio::spawn(api.with_payload_builder(builder));
+
+            let chain = post_merge_chain(&handle, 90..101, H256::zero());
+            let head = chain.last().unwrap();
+
+            let attributes = PayloadAttributes {
+                timestamp: U64::from(head.timestamp + 12),
+                prev_randao: H256::random(),
+                suggested_fee_recipient: Default::default(),
+                withdrawals: None,
+            };
+            let state = ForkchoiceState { head_block_hash: head.hash(), ..Default::default() };
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::ForkchoiceUpdated(
+                EngineApiMessageVersion::V1,
+                state,
+                Some(attributes.into()),
+                result_tx,
+            ));
+            let payload_id = assert_matches!(result_rx.await, Ok(Ok(result)) => {
+                assert_eq!(result.payload_status.status, PayloadStatusEnum::Valid);
+                result.payload_id.expect("payload is built")
+            });
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::GetPayload(payload_id, result_tx));
+            let payload = assert_matches!(result_rx.await, Ok(Ok(payload)) => payload);
+            assert_eq!(payload.parent_hash, head.hash());
+            assert_eq!(payload.transactions.len(), 1);
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::GetBlobsBundle(payload_id, result_tx));
+            assert_matches!(result_rx.await, Ok(Ok(bundle)) => {
+                assert_eq!(bundle.commitments, vec![Bytes::from(sidecar.commitments[0].to_vec())]);
+                assert_eq!(bundle.proofs, vec![Bytes::from(sidecar.proofs[0].to_vec())]);
+                assert_eq!(bundle.blobs, vec![Bytes::from(sidecar.blobs[0].to_vec())]);
+            });
+        }
+
+        #[tokio::test]
+        async fn finalized_block_not_in_head_chain() {
+            let (handle, mut api) = setup_engine_api();
+
+            let chain = post_merge_chain(&handle, 90..101, H256::zero());
+            let fork = post_merge_chain(&handle, 95..96, chain[4].hash());
+
+            let state = ForkchoiceState {
+                head_block_hash: chain.last().unwrap().hash(),
+                finalized_block_hash: fork[0].hash(),
+                ..Default::default()
+            };
+            assert_matches!(
+                api.fork_choice_updated(state, None),
+                Err(EngineApiError::InvalidForkchoiceState)
+            );
+            assert!(!handle.forkchoice_state_has_changed());
+        }
+
+        #[tokio::test]
+        async fn safe_block_not_in_head_chain() {
+            let (handle, mut api) = setup_engine_api();
+
+            let chain = post_merge_chain(&handle, 90..101, H256::zero());
+            let fork = post_merge_chain(&handle, 95..97, chain[4].hash());
+
+            let state = ForkchoiceState {
+                head_block_hash: chain.last().unwrap().hash(),
+                safe_block_hash: fork[1].hash(),
+                finalized_block_hash: chain[2].hash(),
+            };
+            assert_matches!(
+                api.fork_choice_updated(state, None),
+                Err(EngineApiError::InvalidForkchoiceState)
+            );
+            assert!(!handle.forkchoice_state_has_changed());
+        }
+
+        #[tokio::test]
+        async fn safe_block_before_finalized_block() {
+            let (handle, mut api) = setup_engine_api();
+
+            let chain = post_merge_chain(&handle, 90..101, H256::zero());
+
+            let state = ForkchoiceState {
+                head_block_hash: chain.last().unwrap().hash(),
+                safe_block_hash: chain[2].hash(),
+                finalized_block_hash: chain[5].hash(),
+            };
+            assert_matches!(
+                api.fork_choice_updated(state, None),
+                Err(EngineApiError::InvalidForkchoiceState)
+            );
+            assert!(!handle.forkchoice_state_has_changed());
+        }
+
+        #[tokio::test]
+        async fn reorg_below_finalized_block() {
+            let (handle, mut api) = setup_engine_api();
+
+            let chain = post_merge_chain(&handle, 90..101, H256::zero());
+            let fork = post_merge_chain(&handle, 93..102, chain[2].hash());
+
+            let state = ForkchoiceState {
+                head_block_hash: chain.last().unwrap().hash(),
+                finalized_block_hash: chain[5].hash(),
+                ..Default::default()
+            };
+            assert_matches!(api.fork_choice_updated(state.clone(), None), Ok(_));
+
+            // the fork doesn't contain the finalized block
+            let fork_state = ForkchoiceState {
+                head_block_hash: fork.last().unwrap().hash(),
+                ..Default::default()
+            };
+            let expected_result = ForkchoiceUpdated::from_status(PayloadStatusEnum::Invalid {
+                validation_error: EngineApiError::ForkchoiceReorgsFinalized {
+                    finalized: chain[5].hash(),
+                }
+                .to_string(),
+            });
+            assert_matches!(
+                api.fork_choice_updated(fork_state, None),
+                Ok(result) => assert_eq!(result, expected_result)
+            );
+            assert_eq!(handle.forkchoice_state(), state);
+        }
+
+        #[tokio::test]
+        async fn concurrent_forkchoice_updates() {
+            let (handle, api) = setup_engine_api();
+
+            let ttd = handle.chain_spec.fork(Hardfork::Paris).ttd().unwrap();
+            // the latest head is the best block of the client
+            let heads = (0..10)
+                .map(|i| {
+                    let mut head = random_header(100 + i, None).unseal();
+                    head.difficulty = ttd;
+                    head.seal_slow()
+                })
+                .collect::<Vec<_>>();
+            handle
+                .client
+                .extend_headers(heads.iter().map(|head| (head.hash(), head.clone().unseal())));
+
+            // queue all requests before the engine starts processing them
+            let receivers = heads
+                .iter()
+                .map(|head| {
+                    let state =
+                        ForkchoiceState { head_block_hash: head.hash(), ..Default::default() };
+                    let (result_tx, result_rx) = oneshot::channel();
+                    handle.send_message(EngineApiMessage::ForkchoiceUpdated(
+                        EngineApiMessageVersion::V1,
+                        state,
+                        None,
+                        result_tx,
+                    ));
+                    result_rx
+                })
+                .collect::<Vec<_>>();
+            tokio::spawn(api);
+
+            let mut results = Vec::with_capacity(receivers.len());
+            for result_rx in receivers {
+                results.push(result_rx.await.unwrap());
+            }
+
+            // only the latest request is executed
+            let (latest, superseded) = results.split_last().unwrap();
+            for result in superseded {
+                assert_matches!(result, Err(EngineApiError::ForkchoiceUpdateSuperseded));
+            }
+            let latest_head = heads.last().unwrap().hash();
+            assert_matches!(latest, Ok(result) => {
+                assert_eq!(result.payload_status.latest_valid_hash, Some(latest_head))
+            });
+            assert_eq!(handle.forkchoice_state().head_block_hash, latest_head);
+        }
+
+        #[tokio::test]
+        async fn deep_reorg_syncs_to_new_head() {
+            let (handle, mut api) = setup_engine_api();
+
+            let chain = post_merge_chain(&handle, 0..201, H256::zero());
+            let state = |head: &SealedHeader| ForkchoiceState {
+                head_block_hash: head.hash(),
+                ..Default::default()
+            };
+            let status = |result: ForkchoiceUpdated| result.payload_status.status;
+            assert_matches!(
+                api.fork_choice_updated(state(&chain[200]), None),
+                Ok(result) => assert_eq!(status(result), PayloadStatusEnum::Valid)
+            );
+
+            // reorgs 128 blocks of the canonical chain
+            let fork = post_merge_chain(&handle, 73..202, chain[72].hash());
+            assert_matches!(
+                api.fork_choice_updated(state(fork.last().unwrap()), None),
+                Ok(result) => assert_eq!(status(result), PayloadStatusEnum::Syncing)
+            );
+            // the node syncs to the new head
+            assert_eq!(handle.forkchoice_state(), state(fork.last().unwrap()));
+
+            // a reorg within the limit is applied
+            assert_matches!(api.fork_choice_updated(state(&chain[200]), None), Ok(_));
+            let shallow_fork = post_merge_chain(&handle, 150..202, chain[149].hash());
+            assert_matches!(
+                api.fork_choice_updated(state(shallow_fork.last().unwrap()), None),
+                Ok(result) => assert_eq!(status(result), PayloadStatusEnum::Valid)
+            );
+        }
+
+        #[tokio::test]
+        async fn reorg_clears_payload_validation_cache() {
+            let (handle, mut api) = setup_engine_api();
+
+            let ttd = handle.chain_spec.fork(Hardfork::Paris).ttd().unwrap();
+            let post_merge_header = |number, parent| {
+                let mut header = random_header(number, parent).unseal();
+                header.difficulty = ttd;
+                header.seal_slow()
+            };
+            let head = post_merge_header(100, None);
+            let child = post_merge_header(101, Some(head.hash()));
+            let fork = post_merge_header(101, None);
+            handle.client.extend_headers([
+                (head.hash(), head.clone().unseal()),
+                (child.hash(), child.clone().unseal()),
+                (fork.hash(), fork.clone().unseal()),
+            ]);
+            let state = |head_block_hash| ForkchoiceState { head_block_hash, ..Default::default() };
+            let cache_payload = |api: &mut EngineApi<_>| {
+                let hash = H256::random();
+                api.payload_validation_cache
+                    .insert(hash, PayloadStatus::new(PayloadStatusEnum::Valid, hash));
+                hash
+            };
+
+            assert_matches!(api.fork_choice_updated(state(head.hash()), None), Ok(_));
+            let cached = cache_payload(&mut api);
+
+            // extending the canonical chain keeps the cache
+            assert_matches!(api.fork_choice_updated(state(child.hash()), None), Ok(_));
+            assert!(api.payload_validation_cache.get(&cached).is_some());
+
+            // switching to a fork invalidates the cache
+            assert_matches!(api.fork_choice_updated(state(fork.hash()), None), Ok(_));
+            assert!(api.payload_validation_cache.get(&cached).is_none());
+        }
+
         #[tokio::test]
         async fn forkchoice_updated_invalid_pow() {
             let (handle, api) = setup_engine_api();
@@ -1068,6 +2361,29 @@ mod tests {
         }
     }
 
+    // https://github.com/ethereum/execution-apis/blob/main/src/engine/common.md#capabilities
+    mod exchange_capabilities {
+        use super::*;
+
+        #[tokio::test]
+        async fn returns_supported_methods() {
+            let (handle, api) = setup_engine_api();
+            tokio::spawn(api);
+
+            let (result_tx, result_rx) = oneshot::channel();
+            handle.send_message(EngineApiMessage::ExchangeCapabilities(
+                vec!["engine_newPayloadV1".to_string()],
+                result_tx,
+            ));
+
+            assert_matches!(result_rx.await, Ok(Ok(methods)) => {
+                assert_eq!(methods.len(), SUPPORTED_ENGINE_METHODS.len());
+                assert!(methods.iter().any(|m| m == "engine_newPayloadV3"));
+                assert!(!methods.iter().any(|m| m == "engine_exchangeCapabilities"));
+            });
+        }
+    }
+
     // https://github.com/ethereum/execution-apis/blob/main/src/engine/paris.md#specification-3
     mod exchange_transition_configuration {
         use super::*;
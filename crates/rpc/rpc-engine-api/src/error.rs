@@ -1,4 +1,5 @@
-use reth_primitives::{Bytes, H256, U256};
+use reth_primitives::{Bytes, HeaderValidationError, H256, U256};
+use std::time::Duration;
 use thiserror::Error;
 
 /// The Engine API result type
@@ -6,8 +7,12 @@ pub type EngineApiResult<Ok> = Result<Ok, EngineApiError>;
 
 /// Payload unknown error code.
 pub const UNKNOWN_PAYLOAD_CODE: i32 = -38001;
+/// Invalid forkchoice state error code.
+pub const INVALID_FORKCHOICE_STATE_CODE: i32 = -38002;
 /// Request too large error code.
 pub const REQUEST_TOO_LARGE_CODE: i32 = -38004;
+/// Unsupported fork error code.
+pub const UNSUPPORTED_FORK_CODE: i32 = -38005;
 
 /// Error returned by [`EngineApi`][crate::EngineApi]
 #[derive(Error, PartialEq, Debug)]
@@ -34,12 +39,34 @@ pub enum EngineApiError {
         /// Latest available timestamp.
         latest: u64,
     },
+    /// The payload block number does not follow the parent block number.
+    #[error("Invalid payload block number: {invalid}. Parent: {parent}")]
+    PayloadBlockNumber {
+        /// The payload block number.
+        invalid: u64,
+        /// The parent block number.
+        parent: u64,
+    },
+    /// The payload uses more gas than its gas limit.
+    #[error("Invalid payload gas used: {gas_used}. Gas limit: {gas_limit}")]
+    PayloadGasUsed {
+        /// The payload gas used.
+        gas_used: u64,
+        /// The payload gas limit.
+        gas_limit: u64,
+    },
+    /// The payload header is invalid in regards to its parent.
+    #[error(transparent)]
+    PayloadHeader(#[from] HeaderValidationError),
     /// Failed to recover transaction signer.
     #[error("Failed to recover signer for payload transaction: {hash:?}")]
     PayloadSignerRecovery {
         /// The hash of the failed transaction
         hash: H256,
     },
+    /// Invalid payload versioned hashes.
+    #[error("Invalid payload versioned hashes")]
+    PayloadVersionedHashes,
     /// Received pre-merge payload.
     #[error("Received pre-merge payload.")]
     PayloadPreMerge,
@@ -52,6 +79,10 @@ pub enum EngineApiError {
         /// The length that was requested.
         len: u64,
     },
+    /// The message version is not supported at the timestamp of the payload or payload
+    /// attributes.
+    #[error("Unsupported fork")]
+    UnsupportedFork,
     /// The params are invalid.
     #[error("Invalid params")]
     InvalidParams,
@@ -75,12 +106,27 @@ pub enum EngineApiError {
         /// Consensus terminal block hash.
         consensus: H256,
     },
+    /// The forkchoice update was superseded by a newer one before it was processed.
+    #[error("Forkchoice update superseded by a newer request")]
+    ForkchoiceUpdateSuperseded,
+    /// The finalized or safe block of the forkchoice state is not in the chain of the head block.
+    #[error("Invalid forkchoice state")]
+    InvalidForkchoiceState,
+    /// The forkchoice head is not a descendant of the previously finalized block.
+    #[error("Forkchoice head reorgs the finalized block {finalized:?}")]
+    ForkchoiceReorgsFinalized {
+        /// The previously finalized block hash.
+        finalized: H256,
+    },
     /// Forkchoice zero hash head received.
     #[error("Received zero hash as forkchoice head")]
     ForkchoiceEmptyHead,
     /// Chain spec merge terminal total difficulty is not set
     #[error("The merge terminal total difficulty is not known")]
     UnknownMergeTerminalTotalDifficulty,
+    /// The request was not answered within the configured timeout.
+    #[error("Engine API request timed out after {0:?}")]
+    Timeout(Duration),
     /// Encountered decoding error.
     #[error(transparent)]
     Decode(#[from] reth_rlp::DecodeError),
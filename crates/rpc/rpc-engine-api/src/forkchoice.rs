@@ -0,0 +1,240 @@
+use crate::{
+    EngineApiError, EngineApiMessage, EngineApiMessageVersion, EngineApiResult, EngineApiSender,
+};
+use reth_interfaces::consensus::ForkchoiceState;
+use reth_primitives::{BlockNumber, Header, H256};
+use reth_provider::HeaderProvider;
+use reth_rpc_types::engine::{ForkchoiceUpdated, PayloadAttributesVersion};
+use tracing::warn;
+
+/// A forkchoice update request that has not been processed yet.
+#[derive(Debug)]
+pub(crate) struct QueuedForkchoiceUpdate {
+    version: EngineApiMessageVersion,
+    state: ForkchoiceState,
+    attributes: Option<PayloadAttributesVersion>,
+    tx: EngineApiSender<ForkchoiceUpdated>,
+}
+
+impl QueuedForkchoiceUpdate {
+    /// Creates a new queued forkchoice update request.
+    pub(crate) fn new(
+        version: EngineApiMessageVersion,
+        state: ForkchoiceState,
+        attributes: Option<PayloadAttributesVersion>,
+        tx: EngineApiSender<ForkchoiceUpdated>,
+    ) -> Self {
+        Self { version, state, attributes, tx }
+    }
+
+    /// Converts the request back into the [EngineApiMessage] it was received as.
+    pub(crate) fn into_message(self) -> EngineApiMessage {
+        EngineApiMessage::ForkchoiceUpdated(self.version, self.state, self.attributes, self.tx)
+    }
+}
+
+/// A queue of forkchoice update requests with a capacity of one.
+///
+/// Forkchoice updates are processed one at a time. If a new forkchoice update arrives before the
+/// queued one was processed, the queued request is superseded by the new one: only the latest
+/// forkchoice state is applied, and the superseded request is answered with
+/// [EngineApiError::ForkchoiceUpdateSuperseded].
+#[derive(Debug, Default)]
+pub(crate) struct ForkchoiceQueue {
+    /// The pending forkchoice update request.
+    pending: Option<QueuedForkchoiceUpdate>,
+}
+
+impl ForkchoiceQueue {
+    /// Queues the given request, replacing a pending one.
+    pub(crate) fn push(&mut self, request: QueuedForkchoiceUpdate) {
+        if let Some(superseded) = self.pending.replace(request) {
+            warn!(target: "rpc::engine_api", state = ?superseded.state, "Discarding superseded forkchoice update");
+            let _ = superseded.tx.send(Err(EngineApiError::ForkchoiceUpdateSuperseded));
+        }
+    }
+
+    /// Takes the pending request out of the queue.
+    pub(crate) fn pop(&mut self) -> Option<QueuedForkchoiceUpdate> {
+        self.pending.take()
+    }
+}
+
+/// Where a block referenced by a forkchoice state is in regards to the chain of the head block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockPosition {
+    /// The block hash is zero.
+    NotSet,
+    /// The position is not known because a header is missing.
+    Unknown,
+    /// The block is not the head block or one of its ancestors.
+    Outside,
+    /// The block is the head block or one of its ancestors.
+    InChain(BlockNumber),
+}
+
+/// Checks that a forkchoice state respects finality.
+#[derive(Debug)]
+pub(crate) struct ForkchoiceValidator<'a, Client> {
+    client: &'a Client,
+}
+
+impl<'a, Client: HeaderProvider> ForkchoiceValidator<'a, Client> {
+    /// Creates a new validator that reads the headers from the given client.
+    pub(crate) fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    /// Validates the forkchoice state with the given `head` header against the finalized block of
+    /// the previous forkchoice state.
+    ///
+    /// The finalized block must be an ancestor of the head, and the safe block must be between the
+    /// finalized block and the head. The head must not reorg the previously finalized block, and
+    /// the finalized block must not move backwards.
+    ///
+    /// Returns `Ok(false)` if the state can't be validated yet because headers of the chain of the
+    /// head are missing.
+    pub(crate) fn validate(
+        &self,
+        state: &ForkchoiceState,
+        head: &Header,
+        last_finalized_hash: H256,
+    ) -> EngineApiResult<bool> {
+        let position = |hash| self.position(state.head_block_hash, head, hash);
+        let last_finalized = position(last_finalized_hash)?;
+        let finalized = position(state.finalized_block_hash)?;
+        let safe = position(state.safe_block_hash)?;
+        if [last_finalized, finalized, safe].contains(&BlockPosition::Unknown) {
+            return Ok(false)
+        }
+
+        if last_finalized == BlockPosition::Outside {
+            return Err(EngineApiError::ForkchoiceReorgsFinalized { finalized: last_finalized_hash })
+        }
+
+        let finalized_number = match finalized {
+            BlockPosition::Outside => return Err(EngineApiError::InvalidForkchoiceState),
+            BlockPosition::InChain(number) => {
+                if let BlockPosition::InChain(last_finalized_number) = last_finalized {
+                    if number < last_finalized_number {
+                        return Err(EngineApiError::InvalidForkchoiceState)
+                    }
+                }
+                Some(number)
+            }
+            _ => None,
+        };
+
+        match safe {
+            BlockPosition::Outside => return Err(EngineApiError::InvalidForkchoiceState),
+            BlockPosition::InChain(number) if finalized_number.map_or(false, |f| number < f) => {
+                return Err(EngineApiError::InvalidForkchoiceState)
+            }
+            _ => {}
+        }
+
+        Ok(true)
+    }
+
+    /// Returns `true` if making `head` the new head reorgs more than `max_depth` blocks of the
+    /// chain of the previous head.
+    ///
+    /// Returns `false` if the common ancestor can't be found because headers are missing.
+    pub(crate) fn exceeds_reorg_depth(
+        &self,
+        previous_head_hash: H256,
+        head_hash: H256,
+        head: &Header,
+        max_depth: u64,
+    ) -> EngineApiResult<bool> {
+        if previous_head_hash.is_zero() {
+            return Ok(false)
+        }
+        let Some(previous_head) = self.client.header(&previous_head_hash)? else {
+            return Ok(false)
+        };
+
+        let (mut new_hash, mut new) = (head_hash, head.clone());
+        let (mut old_hash, mut old) = (previous_head_hash, previous_head);
+        let mut depth = 0;
+        while new_hash != old_hash {
+            // step back on the higher chain, or on both once they are at the same height
+            let (step_new, step_old) = (new.number >= old.number, old.number >= new.number);
+            if step_new {
+                let Some(parent) = self.client.header(&new.parent_hash)? else { return Ok(false) };
+                new_hash = new.parent_hash;
+                new = parent;
+            }
+            if step_old {
+                let Some(parent) = self.client.header(&old.parent_hash)? else { return Ok(false) };
+                old_hash = old.parent_hash;
+                old = parent;
+                depth += 1;
+                if depth > max_depth {
+                    return Ok(true)
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Walks the chain of the head block back to the number of the block with the given hash.
+    fn position(
+        &self,
+        head_hash: H256,
+        head: &Header,
+        hash: H256,
+    ) -> EngineApiResult<BlockPosition> {
+        if hash.is_zero() {
+            return Ok(BlockPosition::NotSet)
+        }
+        let Some(block) = self.client.header(&hash)? else { return Ok(BlockPosition::Unknown) };
+
+        let (mut current_hash, mut current_number, mut parent_hash) =
+            (head_hash, head.number, head.parent_hash);
+        while current_number > block.number {
+            let Some(parent) = self.client.header(&parent_hash)? else {
+                return Ok(BlockPosition::Unknown)
+            };
+            current_hash = parent_hash;
+            current_number = parent.number;
+            parent_hash = parent.parent_hash;
+        }
+
+        if current_hash == hash {
+            Ok(BlockPosition::InChain(block.number))
+        } else {
+            Ok(BlockPosition::Outside)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use reth_primitives::H256;
+    use tokio::sync::oneshot;
+
+    #[tokio::test]
+    async fn replaces_pending_request() {
+        let mut queue = ForkchoiceQueue::default();
+
+        let first = ForkchoiceState { head_block_hash: H256::random(), ..Default::default() };
+        let (first_tx, first_rx) = oneshot::channel();
+        queue.push(QueuedForkchoiceUpdate::new(EngineApiMessageVersion::V1, first, None, first_tx));
+
+        let second = ForkchoiceState { head_block_hash: H256::random(), ..Default::default() };
+        let (second_tx, _second_rx) = oneshot::channel();
+        queue.push(QueuedForkchoiceUpdate::new(
+            EngineApiMessageVersion::V1,
+            second.clone(),
+            None,
+            second_tx,
+        ));
+
+        assert_matches!(first_rx.await, Ok(Err(EngineApiError::ForkchoiceUpdateSuperseded)));
+        assert_matches!(queue.pop(), Some(request) => assert_eq!(request.state, second));
+        assert!(queue.pop().is_none());
+    }
+}
@@ -8,6 +8,12 @@
 //! The implementation of Engine API.
 //! [Read more](https://github.com/ethereum/execution-apis/tree/main/src/engine).
 
+/// The cache of payload validation outcomes.
+mod cache;
+
+/// The Engine API configuration.
+mod config;
+
 /// The Engine API implementation.
 mod engine_api;
 
@@ -17,6 +23,20 @@ mod message;
 /// Engine API error.
 mod error;
 
+/// The queue of forkchoice update requests.
+mod forkchoice;
+
+/// Engine API metrics.
+mod metrics;
+
+/// The builder of the payloads requested by the Consensus layer.
+mod payload_builder;
+
+pub use config::{
+    EngineApiConfig, DEFAULT_BUILT_PAYLOADS_MAX_ENTRIES, DEFAULT_ENGINE_API_REQUEST_TIMEOUT,
+    DEFAULT_MAX_REORG_DEPTH, DEFAULT_PAYLOAD_VALIDATION_CACHE_MAX_ENTRIES,
+};
 pub use engine_api::{EngineApi, EngineApiHandle, EngineApiSender};
 pub use error::*;
 pub use message::{EngineApiMessage, EngineApiMessageVersion};
+pub use payload_builder::PayloadBuilder;
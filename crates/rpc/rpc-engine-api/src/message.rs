@@ -2,26 +2,38 @@ use crate::EngineApiSender;
 use reth_interfaces::consensus::ForkchoiceState;
 use reth_primitives::{BlockHash, BlockNumber, H64};
 use reth_rpc_types::engine::{
-    ExecutionPayload, ExecutionPayloadBodies, ForkchoiceUpdated, PayloadAttributes, PayloadStatus,
-    TransitionConfiguration,
+    BlobsBundleV1, CancunPayloadFields, ExecutionPayload, ExecutionPayloadBodies,
+    ForkchoiceUpdated, PayloadAttributesVersion, PayloadStatus, TransitionConfiguration,
 };
 
 /// Message type for communicating with [`EngineApi`][crate::EngineApi].
 #[derive(Debug)]
 pub enum EngineApiMessage {
     /// New payload message
-    NewPayload(EngineApiMessageVersion, ExecutionPayload, EngineApiSender<PayloadStatus>),
+    ///
+    /// The [CancunPayloadFields] are only set for [EngineApiMessageVersion::V3].
+    NewPayload(
+        EngineApiMessageVersion,
+        ExecutionPayload,
+        Option<CancunPayloadFields>,
+        EngineApiSender<PayloadStatus>,
+    ),
     /// Get payload message
     GetPayload(H64, EngineApiSender<ExecutionPayload>),
+    /// Get blobs bundle message
+    GetBlobsBundle(H64, EngineApiSender<BlobsBundleV1>),
     /// Get payload bodies by range message
     GetPayloadBodiesByRange(BlockNumber, u64, EngineApiSender<ExecutionPayloadBodies>),
     /// Get payload bodies by hash message
     GetPayloadBodiesByHash(Vec<BlockHash>, EngineApiSender<ExecutionPayloadBodies>),
     /// Forkchoice updated message
+    ///
+    /// The [PayloadAttributesVersion::V3] attributes are only sent with
+    /// [EngineApiMessageVersion::V3].
     ForkchoiceUpdated(
         EngineApiMessageVersion,
         ForkchoiceState,
-        Option<PayloadAttributes>,
+        Option<PayloadAttributesVersion>,
         EngineApiSender<ForkchoiceUpdated>,
     ),
     /// Exchange transition configuration message
@@ -29,6 +41,8 @@ pub enum EngineApiMessage {
         TransitionConfiguration,
         EngineApiSender<TransitionConfiguration>,
     ),
+    /// Exchange capabilities message
+    ExchangeCapabilities(Vec<String>, EngineApiSender<Vec<String>>),
 }
 
 /// The version of Engine API message.
@@ -38,4 +52,6 @@ pub enum EngineApiMessageVersion {
     V1,
     /// Version 2
     V2,
+    /// Version 3
+    V3,
 }
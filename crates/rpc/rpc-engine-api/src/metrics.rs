@@ -0,0 +1,62 @@
+use crate::{EngineApiMessageVersion, EngineApiResult};
+use metrics::{Counter, Gauge, Histogram};
+use reth_metrics_derive::Metrics;
+use reth_rpc_types::engine::{PayloadStatus, PayloadStatusEnum};
+use std::time::Duration;
+
+/// The name of the histogram of the payload validation latency, labeled by `version` and
+/// `result`.
+const NEW_PAYLOAD_VALIDATION_DURATION: &str = "rpc_engine_new_payload_validation_duration_seconds";
+
+/// Metrics of the [`EngineApi`][crate::EngineApi].
+#[derive(Metrics)]
+#[metrics(scope = "rpc_engine", separator = "_")]
+pub(crate) struct EngineApiMetrics {
+    /// Latency of building the payload requested via `engine_getPayload`
+    pub(crate) get_payload_build_duration_seconds: Histogram,
+    /// Latency of processing `engine_forkchoiceUpdated`
+    pub(crate) forkchoice_updated_duration_seconds: Histogram,
+    /// Time elapsed since the last `engine_forkchoiceUpdated` call
+    pub(crate) forkchoice_lag_seconds: Gauge,
+}
+
+impl EngineApiMetrics {
+    /// Records the latency of validating a payload received via `engine_newPayload`.
+    pub(crate) fn record_new_payload(
+        &self,
+        version: EngineApiMessageVersion,
+        result: &EngineApiResult<PayloadStatus>,
+        elapsed: Duration,
+    ) {
+        let version = match version {
+            EngineApiMessageVersion::V1 => "v1",
+            EngineApiMessageVersion::V2 => "v2",
+            EngineApiMessageVersion::V3 => "v3",
+        };
+        let result = match result {
+            Ok(status) => match status.status {
+                PayloadStatusEnum::Valid => "valid",
+                PayloadStatusEnum::Invalid { .. } | PayloadStatusEnum::InvalidBlockHash { .. } => {
+                    "invalid"
+                }
+                PayloadStatusEnum::Syncing | PayloadStatusEnum::Accepted => "syncing",
+            },
+            Err(_) => "error",
+        };
+        metrics::histogram!(NEW_PAYLOAD_VALIDATION_DURATION, elapsed, "version" => version, "result" => result);
+    }
+}
+
+/// Metrics of the payload validation cache of the [`EngineApi`][crate::EngineApi].
+#[derive(Metrics)]
+#[metrics(scope = "rpc_engine.payload_validation_cache")]
+pub(crate) struct PayloadValidationCacheMetrics {
+    /// Number of payloads that were answered from the cache
+    pub(crate) hits: Counter,
+    /// Number of payloads that were not found in the cache
+    pub(crate) misses: Counter,
+    /// Ratio of cache hits to all lookups
+    pub(crate) hit_rate: Gauge,
+    /// Number of entries in the cache
+    pub(crate) entries: Gauge,
+}
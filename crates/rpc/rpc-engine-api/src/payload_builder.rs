@@ -0,0 +1,39 @@
+use reth_interfaces::Error;
+use reth_primitives::{keccak256, BlobTransactionSidecar, SealedBlock, SealedHeader, H256, H64};
+use reth_rlp::Encodable;
+use reth_rpc_types::engine::PayloadAttributesVersion;
+
+/// Builds the payloads that the Consensus layer requests with the payload attributes of a
+/// forkchoice update.
+pub trait PayloadBuilder: Send + Sync {
+    /// Builds a block on top of the given parent with the given attributes.
+    ///
+    /// The parent beacon block root of V3 attributes must be set in the header of the block and
+    /// stored in the beacon roots contract.
+    ///
+    /// Returns the block and the sidecars of its blob transactions, in the order of the
+    /// transactions.
+    fn build_payload(
+        &self,
+        parent: &SealedHeader,
+        attributes: &PayloadAttributesVersion,
+    ) -> Result<(SealedBlock, Vec<BlobTransactionSidecar>), Error>;
+}
+
+/// Returns the id of the payload built on top of the given parent with the given attributes.
+///
+/// The same attributes on top of the same parent result in the same id.
+pub(crate) fn payload_id(parent: &H256, attributes: &PayloadAttributesVersion) -> H64 {
+    let payload_attributes = attributes.payload_attributes();
+    let mut buf = parent.as_bytes().to_vec();
+    buf.extend_from_slice(&payload_attributes.timestamp.as_u64().to_be_bytes());
+    buf.extend_from_slice(payload_attributes.prev_randao.as_bytes());
+    buf.extend_from_slice(payload_attributes.suggested_fee_recipient.as_bytes());
+    if let Some(withdrawals) = &payload_attributes.withdrawals {
+        withdrawals.encode(&mut buf);
+    }
+    if let Some(parent_beacon_block_root) = attributes.parent_beacon_block_root() {
+        buf.extend_from_slice(parent_beacon_block_root.as_bytes());
+    }
+    H64::from_slice(&keccak256(buf)[..8])
+}
@@ -34,6 +34,25 @@ pub struct EIP1186AccountProofResponse {
     pub storage_proof: Vec<StorageProof>,
 }
 
+/// An account returned by `debug_accountRange`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountRangeEntry {
+    /// The keccak256 hash of the address.
+    pub hash: H256,
+    /// The address of the account.
+    pub address: Address,
+}
+
+/// Response for `debug_accountRange`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountRange {
+    /// The accounts of the range, ordered by address.
+    pub accounts: Vec<AccountRangeEntry>,
+    /// The address to start the next range at, if there are more accounts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<Address>,
+}
+
 /// Extended account information (used by `parity_allAccountInfo`).
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ExtAccountInfo {
@@ -3,20 +3,28 @@
 #![allow(missing_docs)]
 
 use reth_primitives::{
-    Address, Block, Bloom, Bytes, SealedBlock, Withdrawal, H256, H64, U256, U64,
+    Address, BlobTransactionSidecar, Block, Bloom, Bytes, SealedBlock, Withdrawal, H256, H64, U256,
+    U64,
 };
 use reth_rlp::Encodable;
 use serde::{Deserialize, Serialize};
 
-/// The list of supported Engine capabilities
-pub const CAPABILITIES: [&str; 9] = [
+/// The list of Engine API methods supported by reth.
+///
+/// This is returned in response to `engine_exchangeCapabilities`, which must not be part of the
+/// list itself.
+pub const SUPPORTED_ENGINE_METHODS: [&str; 13] = [
     "engine_forkchoiceUpdatedV1",
     "engine_forkchoiceUpdatedV2",
+    "engine_forkchoiceUpdatedV3",
     "engine_exchangeTransitionConfigurationV1",
     "engine_getPayloadV1",
     "engine_getPayloadV2",
+    "engine_getPayloadV3",
+    "engine_getBlobsBundleV1",
     "engine_newPayloadV1",
     "engine_newPayloadV2",
+    "engine_newPayloadV3",
     "engine_getPayloadBodiesByHashV1",
     "engine_getPayloadBodiesByRangeV1",
 ];
@@ -45,6 +53,21 @@ pub struct ExecutionPayload {
     /// See <https://github.com/ethereum/execution-apis/blob/6709c2a795b707202e93c4f2867fa0bf2640a84f/src/engine/shanghai.md#executionpayloadv2>
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub withdrawals: Option<Vec<Withdrawal>>,
+    /// Total amount of blob gas consumed by the transactions of the payload, enabled with V3
+    /// See <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#executionpayloadv3>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_gas_used: Option<U64>,
+    /// Running total of blob gas consumed in excess of the target, enabled with V3
+    /// See <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#executionpayloadv3>
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excess_blob_gas: Option<U64>,
+}
+
+impl ExecutionPayload {
+    /// Returns `true` if any of the fields enabled with V3 is set.
+    pub fn has_blob_fields(&self) -> bool {
+        self.blob_gas_used.is_some() || self.excess_blob_gas.is_some()
+    }
 }
 
 impl From<SealedBlock> for ExecutionPayload {
@@ -74,10 +97,51 @@ impl From<SealedBlock> for ExecutionPayload {
             block_hash: value.hash(),
             transactions,
             withdrawals: value.withdrawals,
+            blob_gas_used: value.blob_gas_used.map(U64::from),
+            excess_blob_gas: value.excess_blob_gas.map(U64::from),
         }
     }
 }
 
+/// The additional fields of `engine_newPayloadV3` that are not part of the [ExecutionPayload].
+///
+/// See also: <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_newpayloadv3>
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CancunPayloadFields {
+    /// The expected blob versioned hashes of the blob transactions in the payload.
+    pub versioned_hashes: Vec<H256>,
+    /// The root of the parent beacon block.
+    pub parent_beacon_block_root: H256,
+}
+
+/// The blobs of the blob transactions included in a built payload, along with their KZG
+/// commitments and proofs.
+///
+/// The entries at the same index in all three lists belong to the same blob.
+///
+/// See also: <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/experimental/blob-extension.md#blobsbundlev1>
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobsBundleV1 {
+    /// The KZG commitments of the blobs, 48 bytes each.
+    pub commitments: Vec<Bytes>,
+    /// The KZG proofs of the blobs against their commitments, 48 bytes each.
+    pub proofs: Vec<Bytes>,
+    /// The blobs, `FIELD_ELEMENTS_PER_BLOB * 32` bytes each.
+    pub blobs: Vec<Bytes>,
+}
+
+impl From<Vec<BlobTransactionSidecar>> for BlobsBundleV1 {
+    fn from(sidecars: Vec<BlobTransactionSidecar>) -> Self {
+        let mut bundle = BlobsBundleV1::default();
+        for sidecar in sidecars {
+            bundle.commitments.extend(sidecar.commitments.iter().map(|c| Bytes::from(c.to_vec())));
+            bundle.proofs.extend(sidecar.proofs.iter().map(|p| Bytes::from(p.to_vec())));
+            bundle.blobs.extend(sidecar.blobs.iter().map(|b| Bytes::from(b.to_vec())));
+        }
+        bundle
+    }
+}
+
 /// This structure contains a body of an execution payload.
 ///
 /// See also: <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/shanghai.md#executionpayloadbodyv1>
@@ -127,6 +191,58 @@ pub struct PayloadAttributes {
     pub withdrawals: Option<Vec<Withdrawal>>,
 }
 
+/// This structure extends the [PayloadAttributes] of `engine_forkchoiceUpdatedV2` by the root of
+/// the parent beacon block.
+///
+/// See also: <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#payloadattributesv3>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadAttributesV3 {
+    #[serde(flatten)]
+    pub payload_attributes: PayloadAttributes,
+    /// Root of the parent beacon block, stored in the beacon roots contract as per EIP-4788
+    pub parent_beacon_block_root: H256,
+}
+
+/// The payload attributes of an `engine_forkchoiceUpdated` call, by version of the call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PayloadAttributesVersion {
+    /// The attributes of `engine_forkchoiceUpdatedV1` and `engine_forkchoiceUpdatedV2`.
+    V2(PayloadAttributes),
+    /// The attributes of `engine_forkchoiceUpdatedV3`.
+    V3(PayloadAttributesV3),
+}
+
+impl PayloadAttributesVersion {
+    /// Returns the attributes shared by all versions.
+    pub fn payload_attributes(&self) -> &PayloadAttributes {
+        match self {
+            PayloadAttributesVersion::V2(attributes) => attributes,
+            PayloadAttributesVersion::V3(attributes) => &attributes.payload_attributes,
+        }
+    }
+
+    /// Returns the root of the parent beacon block, which is only set for V3 attributes.
+    pub fn parent_beacon_block_root(&self) -> Option<H256> {
+        match self {
+            PayloadAttributesVersion::V2(_) => None,
+            PayloadAttributesVersion::V3(attributes) => Some(attributes.parent_beacon_block_root),
+        }
+    }
+}
+
+impl From<PayloadAttributes> for PayloadAttributesVersion {
+    fn from(attributes: PayloadAttributes) -> Self {
+        PayloadAttributesVersion::V2(attributes)
+    }
+}
+
+impl From<PayloadAttributesV3> for PayloadAttributesVersion {
+    fn from(attributes: PayloadAttributesV3) -> Self {
+        PayloadAttributesVersion::V3(attributes)
+    }
+}
+
 /// This structure contains the result of processing a payload
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -232,4 +348,18 @@ mod tests {
             assert_eq!(block.withdrawals.unwrap_or_default(), payload_body.withdrawals);
         }
     }
+
+    #[test]
+    fn payload_attributes_v3_serde() {
+        let s = r#"{"timestamp":"0x1","prevRandao":"0x0000000000000000000000000000000000000000000000000000000000000002","suggestedFeeRecipient":"0x0000000000000000000000000000000000000003","withdrawals":[],"parentBeaconBlockRoot":"0x0000000000000000000000000000000000000000000000000000000000000004"}"#;
+        let attributes: PayloadAttributesV3 = serde_json::from_str(s).unwrap();
+        assert_eq!(attributes.payload_attributes.timestamp, U64::from(1));
+        assert_eq!(attributes.payload_attributes.withdrawals, Some(vec![]));
+        assert_eq!(attributes.parent_beacon_block_root, H256::from_low_u64_be(4));
+        assert_eq!(serde_json::to_string(&attributes).unwrap(), s);
+
+        // The parent beacon block root is required
+        let s = r#"{"timestamp":"0x1","prevRandao":"0x0000000000000000000000000000000000000000000000000000000000000002","suggestedFeeRecipient":"0x0000000000000000000000000000000000000003","withdrawals":[]}"#;
+        assert!(serde_json::from_str::<PayloadAttributesV3>(s).is_err());
+    }
 }
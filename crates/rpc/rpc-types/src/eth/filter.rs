@@ -1,6 +1,7 @@
 use crate::Log;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use jsonrpsee_types::SubscriptionId;
-use reth_primitives::H256;
+use reth_primitives::{BlockNumber, H256};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Response of the `eth_getFilterChanges` RPC.
@@ -60,6 +61,53 @@ impl<'de> Deserialize<'de> for FilterChanges {
     }
 }
 
+/// Response of the `eth_getLogs` RPC.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LogsResponse {
+    /// A page of logs, returned if the filter has a `pageToken`.
+    #[serde(rename_all = "camelCase")]
+    Page {
+        /// The logs of the page.
+        logs: Vec<Log>,
+        /// The token of the next page, if there are more logs.
+        next_page_token: Option<String>,
+    },
+    /// All logs, returned if the filter has no `pageToken`.
+    Logs(Vec<Log>),
+}
+
+/// The position of the first log of a page of `eth_getLogs` results.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct LogsPageToken {
+    /// The number of the block of the log.
+    pub block_number: BlockNumber,
+    /// The index of the log's transaction in the block.
+    pub tx_index: u64,
+    /// The index of the log in the block.
+    pub log_index: u64,
+}
+
+impl LogsPageToken {
+    /// Encodes the token as base64.
+    pub fn encode(&self) -> String {
+        let mut bytes = [0u8; 24];
+        bytes[..8].copy_from_slice(&self.block_number.to_be_bytes());
+        bytes[8..16].copy_from_slice(&self.tx_index.to_be_bytes());
+        bytes[16..].copy_from_slice(&self.log_index.to_be_bytes());
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Decodes a token returned by [LogsPageToken::encode].
+    pub fn decode(token: &str) -> Option<Self> {
+        let bytes: [u8; 24] = URL_SAFE_NO_PAD.decode(token).ok()?.try_into().ok()?;
+        let read = |offset: usize| {
+            u64::from_be_bytes(bytes[offset..offset + 8].try_into().expect("8 bytes"))
+        };
+        Some(Self { block_number: read(0), tx_index: read(8), log_index: read(16) })
+    }
+}
+
 /// Owned equivalent of [SubscriptionId]
 #[derive(Debug, PartialEq, Clone, Hash, Eq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -88,3 +136,27 @@ impl From<SubscriptionId<'_>> for FilterId {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logs_page_token_roundtrip() {
+        let token = LogsPageToken { block_number: 17_000_000, tx_index: 12, log_index: 345 };
+        assert_eq!(LogsPageToken::decode(&token.encode()), Some(token));
+        assert_eq!(LogsPageToken::decode("not a token"), None);
+        assert_eq!(LogsPageToken::decode(&URL_SAFE_NO_PAD.encode([0u8; 23])), None);
+    }
+
+    #[test]
+    fn serialize_logs_response() {
+        let logs = LogsResponse::Logs(vec![]);
+        assert_eq!(serde_json::to_string(&logs).unwrap(), "[]");
+
+        let page = LogsResponse::Page { logs: vec![], next_page_token: Some("token".to_string()) };
+        let json = serde_json::to_string(&page).unwrap();
+        assert_eq!(json, r#"{"logs":[],"nextPageToken":"token"}"#);
+        assert_eq!(serde_json::from_str::<LogsResponse>(&json).unwrap(), page);
+    }
+}
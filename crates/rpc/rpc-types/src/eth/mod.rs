@@ -10,6 +10,7 @@ mod filter;
 mod index;
 mod log;
 pub mod pubsub;
+pub mod simulate;
 pub mod state;
 mod syncing;
 pub mod trace;
@@ -1,7 +1,7 @@
 //! Ethereum types for pub-sub
 
 use crate::{Log, RichHeader};
-use reth_primitives::{filter::Filter, H256};
+use reth_primitives::{filter::Filter, Address, H256};
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Subscription result.
@@ -89,6 +89,40 @@ pub enum SubscriptionKind {
     Syncing,
 }
 
+/// Filter for the pending transactions of a
+/// [NewPendingTransactions](SubscriptionKind::NewPendingTransactions) subscription.
+///
+/// A transaction matches if it matches all set fields.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PendingTransactionFilter {
+    /// Matches transactions sent by any of these addresses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<Vec<Address>>,
+    /// Matches transactions sent to any of these addresses.
+    ///
+    /// Contract creations never match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Vec<Address>>,
+}
+
+impl PendingTransactionFilter {
+    /// Returns `true` if a transaction from the sender to the receiver matches the filter.
+    pub fn matches(&self, from: Address, to: Option<Address>) -> bool {
+        if let Some(senders) = &self.from {
+            if !senders.contains(&from) {
+                return false
+            }
+        }
+        if let Some(receivers) = &self.to {
+            if !to.map_or(false, |to| receivers.contains(&to)) {
+                return false
+            }
+        }
+        true
+    }
+}
+
 /// Subscription kind.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub enum Params {
@@ -97,6 +131,8 @@ pub enum Params {
     None,
     /// Log parameters.
     Logs(Box<Filter>),
+    /// Pending transaction parameters.
+    PendingTransactions(PendingTransactionFilter),
 }
 
 impl Serialize for Params {
@@ -107,6 +143,7 @@ impl Serialize for Params {
         match self {
             Params::None => (&[] as &[serde_json::Value]).serialize(serializer),
             Params::Logs(logs) => logs.serialize(serializer),
+            Params::PendingTransactions(filter) => filter.serialize(serializer),
         }
     }
 }
@@ -122,8 +159,42 @@ impl<'a> Deserialize<'a> for Params {
             return Ok(Params::None)
         }
 
+        // the fields of a pending transaction filter are not fields of a log filter
+        if v.as_object().map_or(false, |fields| !fields.is_empty()) {
+            if let Ok(filter) = serde_json::from_value(v.clone()) {
+                return Ok(Params::PendingTransactions(filter))
+            }
+        }
+
         serde_json::from_value(v)
             .map(|f| Params::Logs(Box::new(f)))
             .map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {e}")))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_params() {
+        let params: Params = serde_json::from_str("null").unwrap();
+        assert_eq!(params, Params::None);
+
+        let params: Params =
+            serde_json::from_str(r#"{"address":"0x0000000000000000000000000000000000000001"}"#)
+                .unwrap();
+        assert!(matches!(params, Params::Logs(_)));
+
+        let params: Params =
+            serde_json::from_str(r#"{"to":["0x0000000000000000000000000000000000000001"]}"#)
+                .unwrap();
+        let filter =
+            PendingTransactionFilter { from: None, to: Some(vec![Address::from_low_u64_be(1)]) };
+        assert_eq!(params, Params::PendingTransactions(filter.clone()));
+
+        assert!(filter.matches(Address::random(), Some(Address::from_low_u64_be(1))));
+        assert!(!filter.matches(Address::random(), Some(Address::from_low_u64_be(2))));
+        assert!(!filter.matches(Address::random(), None));
+    }
+}
@@ -0,0 +1,47 @@
+//! bindings for simulating multiple blocks of calls
+
+use crate::{
+    state::{BlockOverrides, StateOverride},
+    TransactionRequest,
+};
+use reth_primitives::{Bytes, U64};
+use serde::{Deserialize, Serialize};
+
+/// A block of calls that is simulated on top of the state of the previous simulated block
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct SimulatedBlock {
+    /// The calls of the block, executed in order
+    pub calls: Vec<TransactionRequest>,
+    /// Account overrides applied before the calls of the block are executed
+    pub state_overrides: Option<StateOverride>,
+    /// Values of the block environment that differ from the simulated defaults
+    pub block_overrides: Option<BlockOverrides>,
+}
+
+/// The outcome of a [SimulatedBlock]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationResult {
+    /// The number of the simulated block
+    pub number: U64,
+    /// The gas used by all calls of the block
+    pub gas_used: U64,
+    /// The outcome of each call of the block
+    pub calls: Vec<SimulatedCallResult>,
+}
+
+/// The outcome of a single simulated call
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCallResult {
+    /// `true` if the call succeeded
+    pub status: bool,
+    /// The gas used by the call
+    pub gas_used: U64,
+    /// The output of the call, or the revert data if it reverted
+    pub return_data: Bytes,
+    /// The reason the call failed, if it did
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
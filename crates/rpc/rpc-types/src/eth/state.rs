@@ -1,6 +1,6 @@
-//! bindings for state overrides in eth_call
+//! bindings for state and block overrides in eth_call
 
-use reth_primitives::{Address, Bytes, H256, U256};
+use reth_primitives::{Address, Bytes, H256, U256, U64};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -12,9 +12,87 @@ pub type StateOverride = HashMap<Address, AccountOverride>;
 #[serde(default, rename_all = "camelCase", deny_unknown_fields)]
 #[allow(missing_docs)]
 pub struct AccountOverride {
-    pub nonce: Option<u64>,
+    pub nonce: Option<U64>,
     pub code: Option<Bytes>,
     pub balance: Option<U256>,
     pub state: Option<HashMap<H256, H256>>,
     pub state_diff: Option<HashMap<H256, H256>>,
 }
+
+/// Custom values of the block environment used in call
+///
+/// The fields that are set replace the values of the block the call is executed at.
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+#[allow(missing_docs)]
+pub struct BlockOverrides {
+    pub number: Option<U256>,
+    pub difficulty: Option<U256>,
+    #[serde(alias = "time")]
+    pub timestamp: Option<U64>,
+    pub gas_limit: Option<U64>,
+    pub coinbase: Option<Address>,
+    /// The `prevrandao` value of the block.
+    pub random: Option<H256>,
+    #[serde(alias = "baseFee")]
+    pub base_fee_per_gas: Option<U256>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_state_override() {
+        let s = r#"{
+            "0x0000000000000000000000000000000000000124": {
+                "balance": "0x1",
+                "nonce": "0x2",
+                "code": "0x6000",
+                "stateDiff": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002"
+                }
+            }
+        }"#;
+        let state_override: StateOverride = serde_json::from_str(s).unwrap();
+        let account_override =
+            state_override.get(&Address::from_low_u64_be(0x124)).expect("account override");
+        assert_eq!(account_override.balance, Some(U256::from(1)));
+        assert_eq!(account_override.nonce, Some(U64::from(2)));
+        assert_eq!(account_override.code, Some(Bytes::from(vec![0x60, 0x00])));
+        assert_eq!(account_override.state, None);
+        assert_eq!(
+            account_override.state_diff,
+            Some(HashMap::from([(H256::from_low_u64_be(1), H256::from_low_u64_be(2))]))
+        );
+
+        // unknown fields are rejected
+        assert!(serde_json::from_str::<AccountOverride>(r#"{"storage": {}}"#).is_err());
+    }
+
+    #[test]
+    fn deserialize_block_overrides() {
+        let s = r#"{
+            "number": "0x10",
+            "time": "0x64",
+            "gasLimit": "0x1c9c380",
+            "baseFee": "0x7"
+        }"#;
+        let block_overrides: BlockOverrides = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            block_overrides,
+            BlockOverrides {
+                number: Some(U256::from(0x10)),
+                timestamp: Some(U64::from(0x64)),
+                gas_limit: Some(U64::from(30_000_000)),
+                base_fee_per_gas: Some(U256::from(7)),
+                ..Default::default()
+            }
+        );
+
+        let s = r#"{"timestamp": "0x64", "baseFeePerGas": "0x7"}"#;
+        let block_overrides: BlockOverrides = serde_json::from_str(s).unwrap();
+        assert_eq!(block_overrides.timestamp, Some(U64::from(0x64)));
+        assert_eq!(block_overrides.base_fee_per_gas, Some(U256::from(7)));
+    }
+}
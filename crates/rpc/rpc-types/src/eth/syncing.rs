@@ -1,4 +1,5 @@
-use reth_primitives::{H512, U256, U64};
+use reth_eth_wire::EthVersion;
+use reth_primitives::{NodeRecord, H512, U256, U64};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeMap;
 
@@ -12,6 +13,10 @@ pub struct SyncInfo {
     pub current_block: U256,
     /// Highest block seen so far
     pub highest_block: U256,
+    /// The latest safe block, if known
+    pub safe_block: Option<U256>,
+    /// The latest finalized block, if known
+    pub finalized_block: Option<U256>,
     /// Warp sync snapshot chunks total.
     pub warp_chunks_amount: Option<U256>,
     /// Warp sync snapshot chunks processed.
@@ -41,11 +46,58 @@ pub enum PeerCount {
     Hex(U64),
 }
 
+/// The number of connected peers by the protocols they support.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerCountByProtocol {
+    /// Number of connected peers
+    pub total: U64,
+    /// Number of peers with a negotiated `eth/66` session
+    pub eth66: U64,
+    /// Number of peers with a negotiated `eth/67` session
+    pub eth67: U64,
+    /// Number of peers with a negotiated `eth/68` session
+    pub eth68: U64,
+    /// Number of peers announcing `snap/1`
+    pub snap1: U64,
+}
+
+impl PeerCountByProtocol {
+    /// Counts the given connected peers by protocol.
+    pub fn from_peers<'a>(peers: impl IntoIterator<Item = &'a reth_network_api::PeerInfo>) -> Self {
+        let (mut total, mut eth66, mut eth67, mut eth68, mut snap1) = (0u64, 0, 0, 0, 0);
+        for peer in peers {
+            total += 1;
+            match peer.eth_version {
+                EthVersion::Eth66 => eth66 += 1,
+                EthVersion::Eth67 => eth67 += 1,
+                EthVersion::Eth68 => eth68 += 1,
+            }
+            if peer
+                .capabilities
+                .capabilities()
+                .iter()
+                .any(|cap| cap.name == "snap" && cap.version == 1)
+            {
+                snap1 += 1;
+            }
+        }
+        Self {
+            total: total.into(),
+            eth66: eth66.into(),
+            eth67: eth67.into(),
+            eth68: eth68.into(),
+            snap1: snap1.into(),
+        }
+    }
+}
+
 /// Peer connection information
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct PeerInfo {
     /// Public node id
     pub id: Option<String>,
+    /// Enode of the peer in URL format
+    pub enode: String,
     /// Node client ID
     pub name: String,
     /// Capabilities
@@ -56,6 +108,35 @@ pub struct PeerInfo {
     pub protocols: PeerProtocolsInfo,
 }
 
+impl From<reth_network_api::PeerInfo> for PeerInfo {
+    fn from(info: reth_network_api::PeerInfo) -> Self {
+        PeerInfo {
+            id: Some(format!("{:x}", info.remote_id)),
+            enode: NodeRecord::new(info.remote_addr, info.remote_id).to_string(),
+            name: info.client_version,
+            caps: info
+                .capabilities
+                .capabilities()
+                .iter()
+                .map(|cap| format!("{}/{}", cap.name, cap.version))
+                .collect(),
+            network: PeerNetworkInfo {
+                remote_address: info.remote_addr.to_string(),
+                local_address: info.local_addr.map(|addr| addr.to_string()).unwrap_or_default(),
+            },
+            protocols: PeerProtocolsInfo {
+                eth: Some(EthProtocolInfo {
+                    version: info.eth_version as u32,
+                    difficulty: Some(info.status.total_difficulty),
+                    head: format!("{:#x}", info.status.blockhash),
+                    genesis: format!("{:#x}", info.status.genesis),
+                }),
+                pip: None,
+            },
+        }
+    }
+}
+
 /// Peer network information
 #[derive(Debug, Clone, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -84,6 +165,8 @@ pub struct EthProtocolInfo {
     pub difficulty: Option<U256>,
     /// SHA3 of peer best block hash
     pub head: String,
+    /// SHA3 of peer genesis block hash
+    pub genesis: String,
 }
 
 /// Peer PIP protocol information
@@ -158,3 +241,110 @@ pub struct ChainStatus {
     /// Describes the gap in the blockchain, if there is one: (first, last)
     pub block_gap: Option<(U256, U256)>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_eth_wire::{capability::Capability, Status};
+    use reth_network_api::Direction;
+    use reth_primitives::{PeerId, H256};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn peer_info(
+        remote_id: PeerId,
+        eth_version: EthVersion,
+        caps: &[(&str, usize)],
+    ) -> reth_network_api::PeerInfo {
+        reth_network_api::PeerInfo {
+            capabilities: Arc::new(
+                caps.iter()
+                    .map(|(name, version)| Capability::new((*name).into(), *version))
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+            remote_id,
+            client_version: "reth/v0.1.0".to_string(),
+            remote_addr: "10.0.0.1:30303".parse().unwrap(),
+            local_addr: Some("10.0.0.2:40404".parse().unwrap()),
+            direction: Direction::Outgoing(remote_id),
+            eth_version,
+            status: Status {
+                total_difficulty: U256::from(17),
+                blockhash: H256::from_low_u64_be(2),
+                genesis: H256::from_low_u64_be(3),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn serialize_peer_info() {
+        let remote_id = PeerId::from_low_u64_be(1);
+        let info = peer_info(remote_id, EthVersion::Eth66, &[("eth", 66)]);
+
+        let value = serde_json::to_value(PeerInfo::from(info)).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "id": format!("{remote_id:x}"),
+                "enode": format!("enode://{remote_id:x}@10.0.0.1:30303"),
+                "name": "reth/v0.1.0",
+                "caps": ["eth/66"],
+                "network": {
+                    "remoteAddress": "10.0.0.1:30303",
+                    "localAddress": "10.0.0.2:40404",
+                },
+                "protocols": {
+                    "eth": {
+                        "version": 66,
+                        "difficulty": "0x11",
+                        "head": format!("{:#x}", H256::from_low_u64_be(2)),
+                        "genesis": format!("{:#x}", H256::from_low_u64_be(3)),
+                    },
+                    "pip": null,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn count_peers_by_protocol() {
+        let peers = [
+            peer_info(PeerId::random(), EthVersion::Eth66, &[("eth", 66), ("snap", 1)]),
+            peer_info(PeerId::random(), EthVersion::Eth67, &[("eth", 66), ("eth", 67)]),
+            peer_info(PeerId::random(), EthVersion::Eth68, &[("eth", 68), ("snap", 1)]),
+            peer_info(PeerId::random(), EthVersion::Eth68, &[("eth", 68)]),
+        ];
+
+        let value = serde_json::to_value(PeerCountByProtocol::from_peers(&peers)).unwrap();
+        assert_eq!(
+            value,
+            json!({ "total": "0x4", "eth66": "0x1", "eth67": "0x1", "eth68": "0x2", "snap1": "0x2" })
+        );
+    }
+
+    #[test]
+    fn serialize_sync_info() {
+        let info = SyncInfo {
+            current_block: U256::from(5),
+            highest_block: U256::from(10),
+            safe_block: Some(U256::from(4)),
+            ..Default::default()
+        };
+
+        let value = serde_json::to_value(SyncStatus::Info(info)).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "startingBlock": "0x0",
+                "currentBlock": "0x5",
+                "highestBlock": "0xa",
+                "safeBlock": "0x4",
+                "finalizedBlock": null,
+                "warpChunksAmount": null,
+                "warpChunksProcessed": null,
+            })
+        );
+    }
+}
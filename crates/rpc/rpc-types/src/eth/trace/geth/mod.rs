@@ -1,11 +1,92 @@
 #![allow(missing_docs)]
 /// Geth tracing types
-use reth_primitives::{Bytes, JsonU256, H256, U256};
+use reth_primitives::{Address, Bytes, JsonU256, H256, U256};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 // re-exported for geth tracing types
-pub use ethers_core::types::{GethDebugTracingOptions, GethTraceFrame};
+pub use ethers_core::types::GethTraceFrame;
+
+/// The name of geth's built-in call tracer.
+pub const CALL_TRACER: &str = "callTracer";
+
+/// The name of geth's built-in prestate tracer.
+pub const PRESTATE_TRACER: &str = "prestateTracer";
+
+/// Options for the `debug_trace*` methods.
+///
+/// If no tracer is set, the default struct logger is used.
+///
+/// <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-debug#traceconfig>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GethDebugTracingOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_storage: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_stack: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_memory: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_return_data: Option<bool>,
+    /// The name of the tracer, e.g. [CALL_TRACER].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracer: Option<String>,
+    /// The config of the tracer, e.g. [CallConfig] for the [CALL_TRACER].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracer_config: Option<serde_json::Value>,
+    /// The time budget of the trace as a Go duration string, e.g. `10s`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+}
+
+/// The config of the [CALL_TRACER].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallConfig {
+    /// Only trace the top level call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub only_top_call: Option<bool>,
+}
+
+/// A call frame of the [CALL_TRACER].
+///
+/// <https://github.com/ethereum/go-ethereum/blob/v1.11.5/eth/tracers/native/call.go#L44-L58>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub from: Address,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    pub gas: U256,
+    pub gas_used: U256,
+    pub input: Bytes,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+}
+
+/// The accounts touched by a transaction with their state before the transaction, as returned by
+/// the [PRESTATE_TRACER].
+pub type PreStateFrame = BTreeMap<Address, AccountState>;
+
+/// The state of an account in a [PreStateFrame].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountState {
+    pub balance: U256,
+    pub nonce: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
 
 /// Result type for geth style transaction trace
 pub type TraceResult = crate::trace::common::TraceResult<serde_json::Value, String>;
@@ -11,9 +11,9 @@ pub use signature::Signature;
 pub use typed::*;
 
 use reth_primitives::{
-    rpc::transaction::eip2930::AccessListItem, Address, BlockNumber, Bytes,
-    Transaction as PrimitiveTransaction, TransactionKind, TransactionSignedEcRecovered, TxType,
-    H256, U128, U256, U64,
+    rpc::transaction::eip2930::AccessListItem, Address, BlobTransaction, BlockNumber, Bytes,
+    Transaction as PrimitiveTransaction, TransactionKind, TransactionSignedEcRecovered, TxEip1559,
+    TxType, H256, U128, U256, U64,
 };
 use serde::{Deserialize, Serialize};
 
@@ -97,13 +97,15 @@ impl Transaction {
 
         let to = match signed_tx.kind() {
             TransactionKind::Create => None,
-            TransactionKind::Call(to) => Some(*to),
+            TransactionKind::Call(to) => Some(to),
         };
 
         let (gas_price, max_fee_per_gas) = match signed_tx.tx_type() {
             TxType::Legacy => (Some(U128::from(signed_tx.max_fee_per_gas())), None),
             TxType::EIP2930 => (None, Some(U128::from(signed_tx.max_fee_per_gas()))),
-            TxType::EIP1559 => (None, Some(U128::from(signed_tx.max_fee_per_gas()))),
+            TxType::EIP1559 | TxType::EIP4844 => {
+                (None, Some(U128::from(signed_tx.max_fee_per_gas())))
+            }
         };
 
         let chain_id = signed_tx.chain_id().map(U64::from);
@@ -119,8 +121,9 @@ impl Transaction {
                     })
                     .collect(),
             ),
-            PrimitiveTransaction::Eip1559(tx) => Some(
-                tx.access_list
+            PrimitiveTransaction::Eip1559(TxEip1559 { access_list, .. }) |
+            PrimitiveTransaction::Blob(BlobTransaction { access_list, .. }) => Some(
+                access_list
                     .0
                     .iter()
                     .map(|item| AccessListItem {
@@ -11,6 +11,10 @@
 
 mod admin;
 mod eth;
+mod reth;
+mod txpool;
 
 pub use admin::*;
 pub use eth::*;
+pub use reth::*;
+pub use txpool::*;
@@ -0,0 +1,59 @@
+use reth_primitives::{Address, U256, U64};
+use serde::{Deserialize, Serialize};
+
+/// Represents the `reth_dryRunTransaction` response: the outcome of validating a raw transaction
+/// against the current state of the chain and the pool, without submitting it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunResult {
+    /// Whether the transaction would be accepted by the pool and its execution succeeds.
+    pub is_valid: bool,
+    /// The reason the transaction was rejected, if it was.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The estimated gas used by the transaction.
+    ///
+    /// This is zero if the transaction could not be decoded or its execution fails.
+    pub estimated_gas: U64,
+    /// The recovered sender of the transaction.
+    ///
+    /// This is the zero address if the transaction could not be decoded.
+    pub from: Address,
+    /// The maximum cost of the transaction in wei: `gasLimit * maxFeePerGas + value`.
+    pub cost_wei: U256,
+}
+
+impl DryRunResult {
+    /// Creates the result for a transaction that could not be decoded or recovered.
+    pub fn invalid(error: impl ToString) -> Self {
+        Self { is_valid: false, error: Some(error.to_string()), ..Default::default() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_dry_run_result() {
+        let result = DryRunResult {
+            is_valid: false,
+            error: Some("nonce too low".to_string()),
+            estimated_gas: U64::from(21_000),
+            from: Address::from_low_u64_be(1),
+            cost_wei: U256::from(1_000),
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "isValid": false,
+                "error": "nonce too low",
+                "estimatedGas": "0x5208",
+                "from": "0x0000000000000000000000000000000000000001",
+                "costWei": "0x3e8"
+            })
+        );
+        assert_eq!(serde_json::from_value::<DryRunResult>(json).unwrap(), result);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::Transaction;
+use reth_primitives::{Address, U64};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Represents the `txpool_contentFrom` response, which lists all transactions of a single sender
+/// that are currently in the pool, keyed by their nonce.
+///
+/// Note: this format is not standardized. Reth follows Geth's format,
+/// see: <https://geth.ethereum.org/docs/interacting-with-geth/rpc/ns-txpool>
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxpoolContentFrom {
+    /// Transactions that are ready to be included in the next block.
+    pub pending: BTreeMap<String, Transaction>,
+    /// Transactions that are not executable yet.
+    pub queued: BTreeMap<String, Transaction>,
+}
+
+/// Represents the `txpool_content` response, which lists all transactions that are currently in
+/// the pool, keyed by their sender and nonce.
+///
+/// See [TxpoolContentFrom] for the format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxpoolContent {
+    /// Transactions that are ready to be included in the next block.
+    pub pending: BTreeMap<Address, BTreeMap<String, Transaction>>,
+    /// Transactions that are not executable yet.
+    pub queued: BTreeMap<Address, BTreeMap<String, Transaction>>,
+}
+
+/// Represents the `txpool_inspect` response, which lists a textual summary of all transactions
+/// that are currently in the pool, keyed by their sender and nonce.
+///
+/// A summary has the form `to: value wei + gasLimit gas × gasPrice wei`, where `to` is
+/// `contract creation` for transactions that create a contract.
+///
+/// See [TxpoolContentFrom] for the format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxpoolInspect {
+    /// Transactions that are ready to be included in the next block.
+    pub pending: BTreeMap<Address, BTreeMap<String, String>>,
+    /// Transactions that are not executable yet.
+    pub queued: BTreeMap<Address, BTreeMap<String, String>>,
+}
+
+/// Represents the `txpool_status` response, the number of transactions that are currently in the
+/// pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxpoolStatus {
+    /// Number of transactions that are ready to be included in the next block.
+    pub pending: U64,
+    /// Number of transactions that are not executable yet.
+    pub queued: U64,
+}
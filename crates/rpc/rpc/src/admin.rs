@@ -1,10 +1,13 @@
-use crate::result::ToRpcResult;
+use crate::{
+    result::{rpc_error_with_code, ToRpcResult},
+    AtomicJwtSecretProvider, JwtSecret,
+};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::{core::RpcResult, types::error::INVALID_PARAMS_CODE};
 use reth_network_api::{NetworkInfo, PeerKind, Peers};
 use reth_primitives::NodeRecord;
-use reth_rpc_api::AdminApiServer;
-use reth_rpc_types::NodeInfo;
+use reth_rpc_api::{AdminApiServer, AuthAdminApiServer};
+use reth_rpc_types::{NodeInfo, PeerInfo};
 
 /// `admin` API implementation.
 ///
@@ -50,6 +53,12 @@ where
         Ok(true)
     }
 
+    /// Handler for `admin_peers`
+    async fn peers(&self) -> RpcResult<Vec<PeerInfo>> {
+        let peers = self.network.get_peers().await.to_rpc_result()?;
+        Ok(peers.into_iter().map(Into::into).collect())
+    }
+
     /// Handler for `admin_peerEvents`
     fn subscribe_peer_events(
         &self,
@@ -72,3 +81,28 @@ impl<N> std::fmt::Debug for AdminApi<N> {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// `admin` API implementation of the methods that are only served on the authenticated Engine
+/// API server.
+#[derive(Debug, Clone)]
+pub struct AuthAdminApi {
+    /// The JWT secret used to authenticate requests to the Engine API
+    jwt_secret: AtomicJwtSecretProvider,
+}
+
+impl AuthAdminApi {
+    /// Creates a new instance of `AuthAdminApi`.
+    pub fn new(jwt_secret: AtomicJwtSecretProvider) -> Self {
+        AuthAdminApi { jwt_secret }
+    }
+}
+
+impl AuthAdminApiServer for AuthAdminApi {
+    /// Handler for `admin_setJwtSecret`
+    fn set_jwt_secret(&self, secret: String) -> RpcResult<bool> {
+        let secret = JwtSecret::from_hex(secret)
+            .map_err(|err| rpc_error_with_code(INVALID_PARAMS_CODE, err.to_string()))?;
+        self.jwt_secret.rotate(secret);
+        Ok(true)
+    }
+}
@@ -1,35 +1,199 @@
-use crate::{result::internal_rpc_err, EthApiSpec};
+use crate::{
+    eth::{
+        cache::EthStateCache,
+        error::{EthApiError, EthResult},
+        revm_utils::{inspect, transact},
+        EthTransactions, TransactionSource,
+    },
+    result::internal_rpc_err,
+};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_primitives::{BlockId, BlockNumberOrTag, Bytes, H256};
+use reth_primitives::{
+    keccak256, Address, BlockId, BlockNumberOrTag, Bytes, TransactionSignedEcRecovered, H256,
+    KECCAK_EMPTY, U256,
+};
+use reth_provider::{AccountProvider, BlockProvider, StateProvider};
+use reth_revm::{
+    database::{State, SubState},
+    env::tx_env_with_recovered,
+    tracing::{TraceInspectorConfig, TracingInspector},
+};
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_types::{
-    trace::geth::{BlockTraceResult, GethDebugTracingOptions, GethTraceFrame, TraceResult},
-    CallRequest, RichBlock,
+    trace::geth::{
+        AccountState, BlockTraceResult, CallConfig, DefaultFrame, GethDebugTracingOptions,
+        GethTraceFrame, PreStateFrame, TraceResult, CALL_TRACER, PRESTATE_TRACER,
+    },
+    AccountRange, AccountRangeEntry, CallRequest, RichBlock,
 };
+use revm::{
+    primitives::{BlockEnv, CfgEnv, Env, State as EvmState},
+    Database, DatabaseCommit,
+};
+use std::time::{Duration, Instant};
+
+/// The max number of accounts returned by `debug_accountRange`, same as geth.
+const ACCOUNT_RANGE_MAX_RESULTS: usize = 256;
 
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
 #[non_exhaustive]
-pub struct DebugApi<Eth> {
+pub struct DebugApi<Client, Eth> {
+    /// The client that can interact with the chain.
+    client: Client,
     /// The implementation of `eth` API
     eth: Eth,
+    /// The async cache frontend for eth related data
+    eth_cache: EthStateCache,
 }
 
 // === impl DebugApi ===
 
-impl<Eth> DebugApi<Eth> {
+impl<Client, Eth> DebugApi<Client, Eth> {
     /// Create a new instance of the [DebugApi]
-    pub fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub fn new(client: Client, eth: Eth, eth_cache: EthStateCache) -> Self {
+        Self { client, eth, eth_cache }
+    }
+}
+
+// === impl DebugApi ===
+
+impl<Client, Eth> DebugApi<Client, Eth>
+where
+    Client: BlockProvider + 'static,
+    Eth: EthTransactions + 'static,
+{
+    /// Replays all transactions of the block on top of the state of its parent block and traces
+    /// them with the tracer of the given options.
+    ///
+    /// Once the timeout of the options is exceeded, the remaining transactions are not traced
+    /// and the traces of the transactions before are returned.
+    pub async fn debug_trace_block(
+        &self,
+        block_id: BlockId,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let tracer = GethTracer::from_options(&opts)?;
+        let deadline = match opts.timeout.as_deref() {
+            Some(timeout) => Some(
+                Instant::now() + parse_duration(timeout).ok_or(EthApiError::InvalidTracerConfig)?,
+            ),
+            None => None,
+        };
+
+        let block_hash =
+            self.client.block_hash_for_id(block_id)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let block =
+            self.eth_cache.get_block(block_hash).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let (cfg, block_env, _) = self.eth.evm_env_at(block_hash.into()).await?;
+
+        let transactions = block
+            .body
+            .into_iter()
+            .map(|tx| tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature))
+            .collect::<EthResult<Vec<_>>>()?;
+
+        self.eth.with_state_at(block.header.parent_hash.into(), |state| {
+            let mut db = SubState::new(State::new(state));
+            let mut results = Vec::with_capacity(transactions.len());
+
+            for tx in &transactions {
+                if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+                    break
+                }
+
+                let env = Env {
+                    cfg: cfg.clone(),
+                    block: block_env.clone(),
+                    tx: tx_env_with_recovered(tx),
+                };
+                match tracer.trace(&mut db, env, &opts) {
+                    Ok((result, state)) => {
+                        // subsequent transactions are executed on top of the changes
+                        db.commit(state);
+                        results.push(TraceResult::Success { result });
+                    }
+                    Err(err) => results.push(TraceResult::Error { error: err.to_string() }),
+                }
+            }
+
+            Ok(results)
+        })
+    }
+
+    /// Returns up to `max_results` accounts of the state at the given block, starting at the
+    /// address with the `start` prefix.
+    ///
+    /// Only the accounts of the latest state can be iterated.
+    pub fn debug_account_range(
+        &self,
+        block_id: BlockId,
+        start: Bytes,
+        max_results: usize,
+    ) -> EthResult<AccountRange> {
+        // the plain accounts are only stored for the latest state
+        if !block_id.is_latest() {
+            return Err(EthApiError::Unsupported("account ranges of historical states"))
+        }
+        self.eth.with_state_at(block_id, |state| account_range(state, &start, max_results))
+    }
+
+    /// Trace the transaction according to the provided options.
+    ///
+    /// A mined transaction is traced on top of the state of its parent block, after the preceding
+    /// transactions of its block are replayed. A pending transaction is traced on top of the
+    /// pending state.
+    ///
+    /// Only the default struct logger is supported, see
+    /// <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers#struct-opcode-logger>
+    pub async fn debug_trace_transaction(
+        &self,
+        tx_hash: H256,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<DefaultFrame> {
+        if opts.tracer.is_some() {
+            return Err(EthApiError::Unsupported("tracers other than the struct logger"))
+        }
+
+        let (transaction, at) = match self.eth.transaction_by_hash_at(tx_hash).await? {
+            None => return Err(EthApiError::TransactionNotFound),
+            Some(res) => res,
+        };
+
+        let (cfg, block_env, at) = self.eth.evm_env_at(at).await?;
+
+        let (tx, state_at, preceding) = match transaction {
+            TransactionSource::Pool(tx) => (tx, at, Vec::new()),
+            TransactionSource::Database { transaction, index, block_hash, .. } => {
+                let block = self
+                    .eth_cache
+                    .get_block(block_hash)
+                    .await?
+                    .ok_or(EthApiError::UnknownBlockNumber)?;
+                let preceding = block
+                    .body
+                    .into_iter()
+                    .take(index)
+                    .map(|tx| tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature))
+                    .collect::<EthResult<Vec<_>>>()?;
+                (transaction, block.header.parent_hash.into(), preceding)
+            }
+        };
+
+        self.eth.with_state_at(state_at, |state| {
+            trace_transaction(state, cfg, block_env, &preceding, &tx, opts)
+        })
     }
 }
 
 #[async_trait]
-impl<Eth> DebugApiServer for DebugApi<Eth>
+impl<Client, Eth> DebugApiServer for DebugApi<Client, Eth>
 where
-    Eth: EthApiSpec + 'static,
+    Client: BlockProvider + 'static,
+    Eth: EthTransactions + 'static,
 {
     /// Handler for `debug_getRawHeader`
     async fn raw_header(&self, _block_id: BlockId) -> RpcResult<Bytes> {
@@ -52,6 +216,16 @@ where
         Err(internal_rpc_err("unimplemented"))
     }
 
+    /// Handler for `debug_accountRange`
+    async fn account_range(
+        &self,
+        block_id: BlockId,
+        start: Bytes,
+        max_results: usize,
+    ) -> RpcResult<AccountRange> {
+        Ok(DebugApi::debug_account_range(self, block_id, start, max_results)?)
+    }
+
     /// Handler for `debug_getBadBlocks`
     async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>> {
         Err(internal_rpc_err("unimplemented"))
@@ -78,28 +252,32 @@ where
     /// Handler for `debug_traceBlockByHash`
     async fn debug_trace_block_by_hash(
         &self,
-        _block: H256,
-        _opts: GethDebugTracingOptions,
+        block: H256,
+        opts: GethDebugTracingOptions,
     ) -> RpcResult<Vec<TraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(DebugApi::debug_trace_block(self, block.into(), opts).await?)
     }
 
     /// Handler for `debug_traceBlockByNumber`
     async fn debug_trace_block_by_number(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: GethDebugTracingOptions,
+        block: BlockNumberOrTag,
+        opts: GethDebugTracingOptions,
     ) -> RpcResult<Vec<TraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(DebugApi::debug_trace_block(self, block.into(), opts).await?)
     }
 
     /// Handler for `debug_traceTransaction`
     async fn debug_trace_transaction(
         &self,
-        _tx_hash: H256,
-        _opts: GethDebugTracingOptions,
+        tx_hash: H256,
+        opts: GethDebugTracingOptions,
     ) -> RpcResult<GethTraceFrame> {
-        Err(internal_rpc_err("unimplemented"))
+        let frame = DebugApi::debug_trace_transaction(self, tx_hash, opts).await?;
+        // both frames use the geth json format
+        serde_json::to_value(frame)
+            .and_then(serde_json::from_value)
+            .map_err(|err| internal_rpc_err(err.to_string()))
     }
 
     /// Handler for `debug_traceCall`
@@ -113,8 +291,314 @@ where
     }
 }
 
-impl<Eth> std::fmt::Debug for DebugApi<Eth> {
+impl<Client, Eth> std::fmt::Debug for DebugApi<Client, Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
     }
 }
+
+/// The geth tracers supported by the `debug_trace*` methods.
+#[derive(Debug, Clone)]
+enum GethTracer {
+    /// The default struct logger.
+    StructLogger,
+    /// The [CALL_TRACER].
+    Call(CallConfig),
+    /// The [PRESTATE_TRACER].
+    PreState,
+}
+
+impl GethTracer {
+    /// Returns the tracer that is selected by the options.
+    fn from_options(opts: &GethDebugTracingOptions) -> EthResult<Self> {
+        match opts.tracer.as_deref() {
+            None => Ok(GethTracer::StructLogger),
+            Some(CALL_TRACER) => {
+                let config = match &opts.tracer_config {
+                    Some(config) => serde_json::from_value(config.clone())
+                        .map_err(|_| EthApiError::InvalidTracerConfig)?,
+                    None => CallConfig::default(),
+                };
+                Ok(GethTracer::Call(config))
+            }
+            Some(PRESTATE_TRACER) => Ok(GethTracer::PreState),
+            Some(_) => Err(EthApiError::Unsupported(
+                "tracers other than the struct logger, callTracer and prestateTracer",
+            )),
+        }
+    }
+
+    /// Executes the transaction of the env with the tracer and returns the trace together with
+    /// the state changes, which are not committed to the database.
+    fn trace<DB: StateProvider>(
+        &self,
+        db: &mut SubState<DB>,
+        env: Env,
+        opts: &GethDebugTracingOptions,
+    ) -> EthResult<(serde_json::Value, EvmState)> {
+        let (trace, state) = match self {
+            GethTracer::StructLogger => {
+                let mut inspector =
+                    TracingInspector::new(TraceInspectorConfig::from_geth_config(opts));
+                let (res, _) = inspect(&mut *db, env, &mut inspector)?;
+                let gas_used = U256::from(res.result.gas_used());
+                let frame = inspector.into_geth_builder().geth_traces(gas_used, opts.clone());
+                (serde_json::to_value(frame), res.state)
+            }
+            GethTracer::Call(config) => {
+                let mut inspector = TracingInspector::new(TraceInspectorConfig::default_parity());
+                let (res, _) = inspect(&mut *db, env, &mut inspector)?;
+                let frame = inspector.into_geth_builder().geth_call_traces(config.clone());
+                (serde_json::to_value(frame), res.state)
+            }
+            GethTracer::PreState => {
+                let (res, _) = transact(&mut *db, env)?;
+                let frame = prestate_frame(db, &res.state)?;
+                (serde_json::to_value(frame), res.state)
+            }
+        };
+        let trace = trace.map_err(|err| EthApiError::TraceSerialization(err.to_string()))?;
+        Ok((trace, state))
+    }
+}
+
+/// Replays the preceding transactions on top of the state and traces the transaction with the
+/// struct logger.
+fn trace_transaction<S: StateProvider>(
+    state: S,
+    cfg: CfgEnv,
+    block_env: BlockEnv,
+    preceding: &[TransactionSignedEcRecovered],
+    tx: &TransactionSignedEcRecovered,
+    opts: GethDebugTracingOptions,
+) -> EthResult<DefaultFrame> {
+    let mut db = SubState::new(State::new(state));
+    for preceding in preceding {
+        let env = Env {
+            cfg: cfg.clone(),
+            block: block_env.clone(),
+            tx: tx_env_with_recovered(preceding),
+        };
+        let (res, _) = transact(&mut db, env)?;
+        // the traced transaction is executed on top of the changes
+        db.commit(res.state);
+    }
+
+    let env = Env { cfg, block: block_env, tx: tx_env_with_recovered(tx) };
+    let mut inspector = TracingInspector::new(TraceInspectorConfig::from_geth_config(&opts));
+    let (res, _) = inspect(&mut db, env, &mut inspector)?;
+
+    let gas_used = U256::from(res.result.gas_used());
+    Ok(inspector.into_geth_builder().geth_traces(gas_used, opts))
+}
+
+/// Returns the state of all accounts touched by a transaction before the transaction.
+///
+/// Expects that the changes of the transaction were not committed to the database yet.
+fn prestate_frame<DB: StateProvider>(
+    db: &mut SubState<DB>,
+    state: &EvmState,
+) -> EthResult<PreStateFrame> {
+    let mut frame = PreStateFrame::new();
+    for (address, account) in state {
+        let info = db.basic(*address)?.unwrap_or_default();
+        let code = if info.code_hash == KECCAK_EMPTY {
+            None
+        } else {
+            let code = match info.code {
+                Some(code) => code,
+                None => db.code_by_hash(info.code_hash)?,
+            };
+            Some(Bytes::from(code.bytes()[..code.len()].to_vec()))
+        };
+        let storage = account
+            .storage
+            .iter()
+            .map(|(key, slot)| (H256::from(*key), H256::from(slot.original_value)))
+            .collect();
+
+        frame.insert(
+            *address,
+            AccountState { balance: info.balance, nonce: info.nonce, code, storage },
+        );
+    }
+    Ok(frame)
+}
+
+/// Returns up to `max_results` accounts of the state, starting at the address with the `start`
+/// prefix.
+///
+/// Like geth, `max_results` is capped at [ACCOUNT_RANGE_MAX_RESULTS], which is also used if it's
+/// zero.
+fn account_range<S: AccountProvider>(
+    state: S,
+    start: &[u8],
+    max_results: usize,
+) -> EthResult<AccountRange> {
+    if start.len() > Address::len_bytes() {
+        return Err(EthApiError::Unsupported("start keys longer than an address"))
+    }
+    let mut start_address = Address::zero();
+    start_address[..start.len()].copy_from_slice(start);
+
+    let max_results = if max_results == 0 || max_results > ACCOUNT_RANGE_MAX_RESULTS {
+        ACCOUNT_RANGE_MAX_RESULTS
+    } else {
+        max_results
+    };
+
+    // fetch one more account to know where the next range starts
+    let mut addresses = state.account_range(start_address, max_results + 1)?;
+    let next = (addresses.len() > max_results).then(|| addresses[max_results]);
+    addresses.truncate(max_results);
+
+    let accounts = addresses
+        .into_iter()
+        .map(|address| AccountRangeEntry { hash: keccak256(address), address })
+        .collect();
+    Ok(AccountRange { accounts, next })
+}
+
+/// Parses a Go duration string like `300ms` or `1m30s`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    const UNITS: [(&str, u64); 7] = [
+        ("ns", 1),
+        ("us", 1_000),
+        ("µs", 1_000),
+        ("ms", 1_000_000),
+        ("s", 1_000_000_000),
+        ("m", 60_000_000_000),
+        ("h", 3_600_000_000_000),
+    ];
+
+    if s.is_empty() {
+        return None
+    }
+    let mut rest = s;
+    let mut nanos = 0.0;
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+
+        let unit_len = rest.find(|c: char| c.is_ascii_digit() || c == '.').unwrap_or(rest.len());
+        let (_, scale) = UNITS.iter().find(|(unit, _)| *unit == &rest[..unit_len])?;
+        rest = &rest[unit_len..];
+
+        nanos += number * *scale as f64;
+    }
+    Some(Duration::from_nanos(nanos.round() as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Signature, Transaction, TransactionKind, TransactionSigned, TxLegacy};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn paginate_account_range() {
+        let provider = MockEthProvider::default();
+        let mut addresses = (0..5).map(|_| Address::random()).collect::<Vec<_>>();
+        for address in &addresses {
+            provider.add_account(*address, ExtendedAccount::new(0, U256::ZERO));
+        }
+        addresses.sort_unstable();
+
+        let mut accounts = Vec::new();
+        let mut start = Bytes::default();
+        let mut pages = 0;
+        loop {
+            let range = account_range(&provider, &start, 2).unwrap();
+            assert!(range.accounts.len() <= 2);
+            accounts.extend(range.accounts);
+            pages += 1;
+            match range.next {
+                Some(next) => start = Bytes::from(next.as_bytes().to_vec()),
+                None => break,
+            }
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(
+            accounts,
+            addresses
+                .iter()
+                .map(|address| AccountRangeEntry { hash: keccak256(address), address: *address })
+                .collect::<Vec<_>>()
+        );
+
+        // the start is a prefix of the address
+        let range = account_range(&provider, &addresses[4][..4], 0).unwrap();
+        assert_eq!(range.accounts.len(), 1);
+        assert_eq!(range.next, None);
+        assert!(account_range(&provider, &[0; 21], 1).is_err());
+    }
+
+    #[test]
+    fn trace_transaction_after_preceding_transactions() {
+        // increments slot 0 and returns the new value
+        let code = Bytes::from(vec![
+            0x60, 0x00, 0x54, 0x60, 0x01, 0x01, 0x80, 0x60, 0x00, 0x55, 0x60, 0x00, 0x52, 0x60,
+            0x20, 0x60, 0x00, 0xf3,
+        ]);
+        let counter = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(counter, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code));
+
+        let sender = Address::random();
+        let increment = |nonce| {
+            let transaction = Transaction::Legacy(TxLegacy {
+                nonce,
+                gas_limit: 100_000,
+                to: TransactionKind::Call(counter),
+                ..Default::default()
+            });
+            TransactionSignedEcRecovered::from_signed_transaction(
+                TransactionSigned::from_transaction_and_signature(
+                    transaction,
+                    Signature::default(),
+                ),
+                sender,
+            )
+        };
+        let returned = |frame: DefaultFrame| {
+            assert!(!frame.failed);
+            U256::from_be_bytes::<32>(frame.return_value.as_ref().try_into().unwrap())
+        };
+
+        let opts = GethDebugTracingOptions::default();
+        let frame = trace_transaction(
+            provider.clone(),
+            CfgEnv::default(),
+            BlockEnv::default(),
+            &[],
+            &increment(0),
+            opts.clone(),
+        )
+        .unwrap();
+        assert_eq!(returned(frame), U256::from(1));
+
+        // the second transaction sees the counter of the first one
+        let frame = trace_transaction(
+            provider,
+            CfgEnv::default(),
+            BlockEnv::default(),
+            &[increment(0)],
+            &increment(1),
+            opts,
+        )
+        .unwrap();
+        assert_eq!(returned(frame), U256::from(2));
+    }
+
+    #[test]
+    fn parse_go_durations() {
+        assert_eq!(parse_duration("10s"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_duration("300ms"), Some(Duration::from_millis(300)));
+        assert_eq!(parse_duration("1m30s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1.5h"), Some(Duration::from_secs(5400)));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("10"), None);
+        assert_eq!(parse_duration("10d"), None);
+    }
+}
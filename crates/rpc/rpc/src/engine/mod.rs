@@ -5,15 +5,17 @@ use jsonrpsee::{
     types::error::INVALID_PARAMS_CODE,
 };
 use reth_interfaces::consensus::ForkchoiceState;
-use reth_primitives::{BlockHash, BlockNumber, H64};
+use reth_primitives::{BlockHash, BlockNumber, H256, H64};
 use reth_rpc_api::EngineApiServer;
 use reth_rpc_engine_api::{
-    EngineApiError, EngineApiHandle, EngineApiMessage, EngineApiMessageVersion, EngineApiResult,
-    REQUEST_TOO_LARGE_CODE, UNKNOWN_PAYLOAD_CODE,
+    EngineApiConfig, EngineApiError, EngineApiHandle, EngineApiMessage, EngineApiMessageVersion,
+    EngineApiResult, INVALID_FORKCHOICE_STATE_CODE, REQUEST_TOO_LARGE_CODE, UNKNOWN_PAYLOAD_CODE,
+    UNSUPPORTED_FORK_CODE,
 };
 use reth_rpc_types::engine::{
-    ExecutionPayload, ExecutionPayloadBodies, ForkchoiceUpdated, PayloadAttributes, PayloadStatus,
-    TransitionConfiguration, CAPABILITIES,
+    BlobsBundleV1, CancunPayloadFields, ExecutionPayload, ExecutionPayloadBodies,
+    ForkchoiceUpdated, PayloadAttributes, PayloadAttributesV3, PayloadStatus,
+    TransitionConfiguration,
 };
 use tokio::sync::oneshot::{self, Receiver};
 
@@ -21,12 +23,19 @@ use tokio::sync::oneshot::{self, Receiver};
 pub struct EngineApi {
     /// Handle to the consensus engine
     engine_tx: EngineApiHandle,
+    /// Configuration of the Engine API
+    config: EngineApiConfig,
 }
 
 impl EngineApi {
-    /// Creates a new instance of [EngineApi].
+    /// Creates a new instance of [EngineApi] with the default [EngineApiConfig].
     pub fn new(engine_tx: EngineApiHandle) -> Self {
-        Self { engine_tx }
+        Self::with_config(engine_tx, EngineApiConfig::default())
+    }
+
+    /// Creates a new instance of [EngineApi] with the given [EngineApiConfig].
+    pub fn with_config(engine_tx: EngineApiHandle, config: EngineApiConfig) -> Self {
+        Self { engine_tx, config }
     }
 }
 
@@ -43,11 +52,17 @@ impl EngineApi {
         rx: Receiver<EngineApiResult<T>>,
     ) -> Result<T> {
         let _ = self.engine_tx.send(msg);
-        rx.await.map_err(|err| Error::Custom(err.to_string()))?.map_err(|err| {
+        let timeout = self.config.request_timeout;
+        let response = tokio::time::timeout(timeout, rx)
+            .await
+            .unwrap_or(Ok(Err(EngineApiError::Timeout(timeout))));
+        response.map_err(|err| Error::Custom(err.to_string()))?.map_err(|err| {
             let code = match err {
                 EngineApiError::InvalidParams => INVALID_PARAMS_CODE,
                 EngineApiError::PayloadUnknown => UNKNOWN_PAYLOAD_CODE,
+                EngineApiError::InvalidForkchoiceState => INVALID_FORKCHOICE_STATE_CODE,
                 EngineApiError::PayloadRequestTooLarge { .. } => REQUEST_TOO_LARGE_CODE,
+                EngineApiError::UnsupportedFork => UNSUPPORTED_FORK_CODE,
                 // Any other server error
                 _ => jsonrpsee::types::error::INTERNAL_ERROR_CODE,
             };
@@ -64,7 +79,7 @@ impl EngineApiServer for EngineApi {
     async fn new_payload_v1(&self, payload: ExecutionPayload) -> Result<PayloadStatus> {
         let (tx, rx) = oneshot::channel();
         self.delegate_request(
-            EngineApiMessage::NewPayload(EngineApiMessageVersion::V1, payload, tx),
+            EngineApiMessage::NewPayload(EngineApiMessageVersion::V1, payload, None, tx),
             rx,
         )
         .await
@@ -75,7 +90,29 @@ impl EngineApiServer for EngineApi {
     async fn new_payload_v2(&self, payload: ExecutionPayload) -> Result<PayloadStatus> {
         let (tx, rx) = oneshot::channel();
         self.delegate_request(
-            EngineApiMessage::NewPayload(EngineApiMessageVersion::V2, payload, tx),
+            EngineApiMessage::NewPayload(EngineApiMessageVersion::V2, payload, None, tx),
+            rx,
+        )
+        .await
+    }
+
+    /// Handler for `engine_newPayloadV3`
+    /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_newpayloadv3>
+    async fn new_payload_v3(
+        &self,
+        payload: ExecutionPayload,
+        versioned_hashes: Vec<H256>,
+        parent_beacon_block_root: H256,
+    ) -> Result<PayloadStatus> {
+        let (tx, rx) = oneshot::channel();
+        let cancun_fields = CancunPayloadFields { versioned_hashes, parent_beacon_block_root };
+        self.delegate_request(
+            EngineApiMessage::NewPayload(
+                EngineApiMessageVersion::V3,
+                payload,
+                Some(cancun_fields),
+                tx,
+            ),
             rx,
         )
         .await
@@ -95,7 +132,7 @@ impl EngineApiServer for EngineApi {
             EngineApiMessage::ForkchoiceUpdated(
                 EngineApiMessageVersion::V1,
                 fork_choice_state,
-                payload_attributes,
+                payload_attributes.map(Into::into),
                 tx,
             ),
             rx,
@@ -115,7 +152,27 @@ impl EngineApiServer for EngineApi {
             EngineApiMessage::ForkchoiceUpdated(
                 EngineApiMessageVersion::V2,
                 fork_choice_state,
-                payload_attributes,
+                payload_attributes.map(Into::into),
+                tx,
+            ),
+            rx,
+        )
+        .await
+    }
+
+    /// Handler for `engine_forkchoiceUpdatedV3`
+    /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_forkchoiceupdatedv3>
+    async fn fork_choice_updated_v3(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<PayloadAttributesV3>,
+    ) -> Result<ForkchoiceUpdated> {
+        let (tx, rx) = oneshot::channel();
+        self.delegate_request(
+            EngineApiMessage::ForkchoiceUpdated(
+                EngineApiMessageVersion::V3,
+                fork_choice_state,
+                payload_attributes.map(Into::into),
                 tx,
             ),
             rx,
@@ -139,6 +196,20 @@ impl EngineApiServer for EngineApi {
         self.delegate_request(EngineApiMessage::GetPayload(payload_id, tx), rx).await
     }
 
+    /// Handler for `engine_getPayloadV3`
+    /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/cancun.md#engine_getpayloadv3>
+    async fn get_payload_v3(&self, payload_id: H64) -> Result<ExecutionPayload> {
+        let (tx, rx) = oneshot::channel();
+        self.delegate_request(EngineApiMessage::GetPayload(payload_id, tx), rx).await
+    }
+
+    /// Handler for `engine_getBlobsBundleV1`
+    /// See also <https://github.com/ethereum/execution-apis/blob/fe8e13c288c592ec154ce25c534e26cb7ce0530d/src/engine/experimental/blob-extension.md#engine_getblobsbundlev1>
+    async fn get_blobs_bundle_v1(&self, payload_id: H64) -> Result<BlobsBundleV1> {
+        let (tx, rx) = oneshot::channel();
+        self.delegate_request(EngineApiMessage::GetBlobsBundle(payload_id, tx), rx).await
+    }
+
     /// Handler for `engine_getPayloadBodiesByHashV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/shanghai.md#engine_getpayloadbodiesbyhashv1>
     async fn get_payload_bodies_by_hash_v1(
@@ -173,7 +244,30 @@ impl EngineApiServer for EngineApi {
 
     /// Handler for `engine_exchangeCapabilitiesV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/common.md#capabilities>
-    async fn exchange_capabilities(&self, _capabilities: Vec<String>) -> Result<Vec<String>> {
-        Ok(CAPABILITIES.into_iter().map(str::to_owned).collect())
+    async fn exchange_capabilities(&self, capabilities: Vec<String>) -> Result<Vec<String>> {
+        let (tx, rx) = oneshot::channel();
+        self.delegate_request(EngineApiMessage::ExchangeCapabilities(capabilities, tx), rx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::error::{CallError, INTERNAL_ERROR_CODE};
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn request_timeout() {
+        // keep the receiver alive but never answer the requests
+        let (engine_tx, _engine_rx) = mpsc::unbounded_channel();
+        let config =
+            EngineApiConfig { request_timeout: Duration::from_millis(10), ..Default::default() };
+        let api = EngineApi::with_config(engine_tx, config);
+
+        let err = api.get_payload_v1(H64::random()).await.unwrap_err();
+        let Error::Call(CallError::Custom(err)) = err else { panic!("unexpected error: {err:?}") };
+        assert_eq!(err.code(), INTERNAL_ERROR_CODE);
+        assert_eq!(err.message(), EngineApiError::Timeout(config.request_timeout).to_string());
     }
 }
@@ -4,9 +4,11 @@ use crate::{
     eth::error::{EthApiError, EthResult},
     EthApi,
 };
-use reth_primitives::BlockId;
+use reth_primitives::{
+    contract::create_address, verify_signatures_batch, BlockId, TransactionKind, U128, U256, U64,
+};
 use reth_provider::{BlockProvider, EvmEnvProvider, StateProviderFactory};
-use reth_rpc_types::{Block, Index, RichBlock};
+use reth_rpc_types::{Block, Index, Log, RichBlock, TransactionReceipt};
 
 impl<Client, Pool, Network> EthApi<Client, Pool, Network>
 where
@@ -51,6 +53,84 @@ where
         }
     }
 
+    /// Returns the receipts of all transactions in the given block.
+    ///
+    /// The receipts are read in a single range walk over the receipts table.
+    pub(crate) async fn block_receipts(
+        &self,
+        block_id: impl Into<BlockId>,
+    ) -> EthResult<Option<Vec<TransactionReceipt>>> {
+        let block_id = block_id.into();
+        // TODO support pending block
+
+        let Some(block) = self.client().block(block_id)? else { return Ok(None) };
+        let Some(receipts) = self.client().receipts_by_block(block_id)? else { return Ok(None) };
+        let block_hash =
+            self.client().block_hash_for_id(block_id)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let senders =
+            verify_signatures_batch(&block.body).ok_or(EthApiError::InvalidTransactionSignature)?;
+
+        let block_number = U256::from(block.header.number);
+        let base_fee = block.header.base_fee_per_gas;
+        let mut cumulative_gas_used = 0;
+        // tracks the index of a log in the entire block
+        let mut log_index = 0;
+        let mut all_receipts = Vec::with_capacity(receipts.len());
+        for (tx_index, ((transaction, receipt), from)) in
+            block.body.into_iter().zip(receipts).zip(senders).enumerate()
+        {
+            let gas_used = receipt.cumulative_gas_used - cumulative_gas_used;
+            cumulative_gas_used = receipt.cumulative_gas_used;
+
+            let (to, contract_address) = match transaction.kind() {
+                TransactionKind::Create => (None, Some(create_address(from, transaction.nonce()))),
+                TransactionKind::Call(to) => (Some(to), None),
+            };
+
+            let logs = receipt
+                .logs
+                .into_iter()
+                .enumerate()
+                .map(|(tx_log_index, log)| {
+                    let log = Log {
+                        address: log.address,
+                        topics: log.topics,
+                        data: log.data,
+                        block_hash: Some(block_hash),
+                        block_number: Some(block_number),
+                        transaction_hash: Some(transaction.hash),
+                        transaction_index: Some(U256::from(tx_index)),
+                        log_index: Some(U256::from(log_index)),
+                        transaction_log_index: Some(U256::from(tx_log_index)),
+                        removed: false,
+                    };
+                    log_index += 1;
+                    log
+                })
+                .collect();
+
+            all_receipts.push(TransactionReceipt {
+                transaction_hash: Some(transaction.hash),
+                transaction_index: Some(U256::from(tx_index)),
+                block_hash: Some(block_hash),
+                block_number: Some(block_number),
+                from,
+                to,
+                cumulative_gas_used: U256::from(receipt.cumulative_gas_used),
+                gas_used: Some(U256::from(gas_used)),
+                contract_address,
+                logs,
+                state_root: None,
+                logs_bloom: receipt.bloom,
+                status_code: Some(U64::from(receipt.success as u64)),
+                effective_gas_price: U128::from(transaction.effective_gas_price(base_fee)),
+                transaction_type: U256::from(u8::from(receipt.tx_type)),
+            });
+        }
+
+        Ok(Some(all_receipts))
+    }
+
     pub(crate) async fn block(
         &self,
         block_id: impl Into<BlockId>,
@@ -16,14 +16,14 @@ use reth_revm::{
     database::{State, SubState},
 };
 use reth_rpc_types::{
-    state::{AccountOverride, StateOverride},
+    state::{AccountOverride, BlockOverrides, StateOverride},
     CallRequest,
 };
 use reth_transaction_pool::TransactionPool;
 use revm::{
     db::{CacheDB, DatabaseRef},
     primitives::{
-        BlockEnv, Bytecode, CfgEnv, Env, ExecutionResult, Halt, ResultAndState, TransactTo,
+        BlockEnv, Bytecode, CfgEnv, Env, ExecutionResult, Halt, ResultAndState, SpecId, TransactTo,
     },
     Database,
 };
@@ -44,9 +44,16 @@ where
         request: CallRequest,
         at: BlockId,
         state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> EthResult<(ResultAndState, Env)> {
-        let (cfg, block_env, at) = self.evm_env_at(at).await?;
+        let (cfg, mut block_env, at) = self.evm_env_at(at).await?;
         let state = self.state_at(at)?;
+
+        // apply block overrides
+        if let Some(block_overrides) = block_overrides {
+            apply_block_overrides(*block_overrides, &mut block_env);
+        }
+
         self.call_with(cfg, block_env, request, state, state_overrides)
     }
 
@@ -285,8 +292,12 @@ where
 
         let initial = request.access_list.clone().unwrap_or_default();
 
-        let precompiles = get_precompiles(&env.cfg.spec_id);
-        let mut inspector = AccessListInspector::new(initial, from, to, precompiles);
+        let mut warm_addresses = get_precompiles(&env.cfg.spec_id);
+        // the coinbase is warm at the start of the transaction since EIP-3651
+        if SpecId::enabled(env.cfg.spec_id, SpecId::SHANGHAI) {
+            warm_addresses.push(env.block.coinbase);
+        }
+        let mut inspector = AccessListInspector::new(initial, from, to, warm_addresses);
         let (result, _env) = inspect(&mut db, env, &mut inspector)?;
 
         match result.result {
@@ -303,8 +314,46 @@ where
     }
 }
 
+/// Applies the given [BlockOverrides] to the [BlockEnv].
+pub(crate) fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
+    let BlockOverrides {
+        number,
+        difficulty,
+        timestamp,
+        gas_limit,
+        coinbase,
+        random,
+        base_fee_per_gas,
+    } = overrides;
+
+    if let Some(number) = number {
+        env.number = number;
+    }
+    if let Some(difficulty) = difficulty {
+        env.difficulty = difficulty;
+    }
+    if let Some(timestamp) = timestamp {
+        env.timestamp = U256::from(timestamp.as_u64());
+    }
+    if let Some(gas_limit) = gas_limit {
+        env.gas_limit = U256::from(gas_limit.as_u64());
+    }
+    if let Some(coinbase) = coinbase {
+        env.coinbase = coinbase;
+    }
+    if let Some(random) = random {
+        env.prevrandao = Some(random);
+    }
+    if let Some(base_fee) = base_fee_per_gas {
+        env.basefee = base_fee;
+    }
+}
+
 /// Applies the given state overrides (a set of [AccountOverride]) to the [CacheDB].
-fn apply_state_overrides<DB>(overrides: StateOverride, db: &mut CacheDB<DB>) -> EthResult<()>
+pub(crate) fn apply_state_overrides<DB>(
+    overrides: StateOverride,
+    db: &mut CacheDB<DB>,
+) -> EthResult<()>
 where
     DB: DatabaseRef,
     EthApiError: From<<DB as DatabaseRef>::Error>,
@@ -328,7 +377,7 @@ where
     let mut account_info = db.basic(account)?.unwrap_or_default();
 
     if let Some(nonce) = account_override.nonce {
-        account_info.nonce = nonce;
+        account_info.nonce = nonce.as_u64();
     }
     if let Some(code) = account_override.code {
         account_info.code = Some(Bytecode::new_raw(code.0));
@@ -371,3 +420,97 @@ where
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{keccak256, Bytes, H256};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use std::collections::HashMap;
+
+    fn slot(index: u64) -> H256 {
+        H256::from_low_u64_be(index)
+    }
+
+    #[test]
+    fn block_overrides() {
+        let mut env = BlockEnv {
+            number: U256::from(1),
+            timestamp: U256::from(10),
+            basefee: U256::from(7),
+            ..Default::default()
+        };
+        let coinbase = Address::random();
+        apply_block_overrides(
+            BlockOverrides {
+                timestamp: Some(100u64.into()),
+                coinbase: Some(coinbase),
+                base_fee_per_gas: Some(U256::ZERO),
+                ..Default::default()
+            },
+            &mut env,
+        );
+
+        assert_eq!(env.number, U256::from(1));
+        assert_eq!(env.timestamp, U256::from(100));
+        assert_eq!(env.coinbase, coinbase);
+        assert_eq!(env.basefee, U256::ZERO);
+    }
+
+    #[test]
+    fn state_overrides() {
+        let address = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(1, U256::from(10))
+                .extend_storage([(slot(1), U256::from(1)), (slot(2), U256::from(2))]),
+        );
+
+        // `stateDiff` is merged into the existing storage
+        let mut db = SubState::new(State::new(provider.clone()));
+        let account_override = AccountOverride {
+            balance: Some(U256::from(100)),
+            code: Some(Bytes::from(vec![0x60, 0x00])),
+            state_diff: Some(HashMap::from([(slot(2), slot(5)), (slot(3), slot(3))])),
+            ..Default::default()
+        };
+        apply_state_overrides(HashMap::from([(address, account_override)]), &mut db).unwrap();
+
+        let account = db.basic(address).unwrap().unwrap();
+        assert_eq!(account.nonce, 1);
+        assert_eq!(account.balance, U256::from(100));
+        assert_eq!(account.code_hash, keccak256([0x60, 0x00]));
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::from(1));
+        assert_eq!(db.storage(address, U256::from(2)).unwrap(), U256::from(5));
+        assert_eq!(db.storage(address, U256::from(3)).unwrap(), U256::from(3));
+
+        // `state` replaces the existing storage
+        let mut db = SubState::new(State::new(provider.clone()));
+        let account_override = AccountOverride {
+            nonce: Some(5u64.into()),
+            state: Some(HashMap::from([(slot(3), slot(3))])),
+            ..Default::default()
+        };
+        apply_state_overrides(HashMap::from([(address, account_override)]), &mut db).unwrap();
+
+        let account = db.basic(address).unwrap().unwrap();
+        assert_eq!(account.nonce, 5);
+        assert_eq!(account.balance, U256::from(10));
+        assert_eq!(db.storage(address, U256::from(1)).unwrap(), U256::ZERO);
+        assert_eq!(db.storage(address, U256::from(3)).unwrap(), U256::from(3));
+
+        // `state` and `stateDiff` are mutually exclusive
+        let mut db = SubState::new(State::new(provider));
+        let account_override = AccountOverride {
+            state: Some(Default::default()),
+            state_diff: Some(Default::default()),
+            ..Default::default()
+        };
+        let err = apply_state_overrides(HashMap::from([(address, account_override)]), &mut db)
+            .unwrap_err();
+        assert!(
+            matches!(err, EthApiError::BothStateAndStateDiffInOverride(addr) if addr == address)
+        );
+    }
+}
@@ -0,0 +1,423 @@
+//! Contains the `eth_feeHistory` implementation and its response cache, and the
+//! `eth_maxPriorityFeePerGas` estimation.
+
+use crate::{
+    eth::error::{EthApiError, EthResult},
+    EthApi,
+};
+use metrics::Counter;
+use reth_metrics_derive::Metrics;
+use reth_primitives::{constants::GWEI_TO_WEI, BlockId, BlockNumber, Header, U256};
+use reth_provider::{BlockProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory};
+use reth_rpc_types::{FeeHistory, FeeHistoryCacheItem};
+use schnellru::{ByLength, LruMap};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Settings for the [FeeHistoryResponseCache]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistoryCacheConfig {
+    /// Max number of cached `eth_feeHistory` responses.
+    ///
+    /// Default is 1024
+    pub max_responses: u32,
+    /// How long a response that includes non-finalized blocks is served from the cache.
+    ///
+    /// Default is 12 seconds (one slot). Responses that only cover finalized blocks never expire.
+    pub ttl: Duration,
+}
+
+impl Default for FeeHistoryCacheConfig {
+    fn default() -> Self {
+        Self { max_responses: 1024, ttl: Duration::from_secs(12) }
+    }
+}
+
+/// Settings for the `eth_maxPriorityFeePerGas` estimation
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityFeeConfig {
+    /// Number of the most recent non-empty blocks the tips are sampled from.
+    ///
+    /// Default is 20
+    pub blocks_to_sample: u64,
+    /// The percentile of the sampled tips that's suggested, from 0 to 100.
+    ///
+    /// Default is 60
+    pub percentile: u8,
+    /// The upper bound of the suggested tip in gwei. The lower bound is always 1 gwei.
+    ///
+    /// Default is 10 gwei
+    pub clamp_max_gwei: u64,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self { blocks_to_sample: 20, percentile: 60, clamp_max_gwei: 10 }
+    }
+}
+
+/// The number of blocks that are searched for non-empty blocks per sampled block, so the
+/// estimation doesn't walk the entire chain if it has been idle.
+const PRIORITY_FEE_MAX_BLOCKS_PER_SAMPLE: u64 = 5;
+
+/// Metrics for the [FeeHistoryResponseCache]
+#[derive(Metrics)]
+#[metrics(scope = "rpc_fee_history_cache", separator = "_")]
+struct FeeHistoryCacheMetrics {
+    /// The number of `eth_feeHistory` requests served from the cache
+    hits_total: Counter,
+    /// The number of `eth_feeHistory` requests that had to read the database
+    misses_total: Counter,
+}
+
+/// The key of a cached response: the newest block, the block count and the bit patterns of the
+/// reward percentiles, since floats are not hashable.
+type FeeHistoryKey = (BlockNumber, u64, Option<Vec<u64>>);
+
+/// A cached response and the time after which it's no longer served.
+struct FeeHistoryEntry {
+    response: FeeHistory,
+    /// `None` if the response only covers finalized blocks.
+    expires_at: Option<Instant>,
+}
+
+/// An LRU cache of complete `eth_feeHistory` responses.
+///
+/// Gas estimation libraries poll `eth_feeHistory` with the same parameters every few seconds,
+/// which are served from this cache until the next block is expected.
+#[derive(Clone)]
+pub(crate) struct FeeHistoryResponseCache {
+    entries: Arc<Mutex<LruMap<FeeHistoryKey, FeeHistoryEntry>>>,
+    ttl: Duration,
+    metrics: Arc<FeeHistoryCacheMetrics>,
+}
+
+impl FeeHistoryResponseCache {
+    /// Creates a new cache with the given settings.
+    pub(crate) fn new(config: FeeHistoryCacheConfig) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(LruMap::new(ByLength::new(config.max_responses)))),
+            ttl: config.ttl,
+            metrics: Default::default(),
+        }
+    }
+
+    /// Returns the cached response for the key, if it has not expired yet.
+    fn get(&self, key: &FeeHistoryKey) -> Option<FeeHistory> {
+        let mut entries = self.entries.lock().expect("not poisoned");
+        let response = match entries.get(key) {
+            Some(entry) if entry.expires_at.map_or(true, |at| Instant::now() < at) => {
+                Some(entry.response.clone())
+            }
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        };
+
+        if response.is_some() {
+            self.metrics.hits_total.increment(1);
+        } else {
+            self.metrics.misses_total.increment(1);
+        }
+        response
+    }
+
+    /// Caches the response for the key. Responses of finalized blocks never expire.
+    fn insert(&self, key: FeeHistoryKey, response: FeeHistory, finalized: bool) {
+        let expires_at = (!finalized).then(|| Instant::now() + self.ttl);
+        self.entries
+            .lock()
+            .expect("not poisoned")
+            .insert(key, FeeHistoryEntry { response, expires_at });
+    }
+}
+
+impl std::fmt::Debug for FeeHistoryResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FeeHistoryResponseCache").field("ttl", &self.ttl).finish_non_exhaustive()
+    }
+}
+
+impl<Client, Pool, Network> EthApi<Client, Pool, Network>
+where
+    Client: BlockProvider + HeaderProvider + StateProviderFactory + EvmEnvProvider + 'static,
+{
+    /// Returns the fee history of the `block_count` blocks up to `newest_block`.
+    ///
+    /// Complete responses are cached by the [FeeHistoryResponseCache]. On a miss, the fee history
+    /// of the individual blocks is looked up in the block-level LRU cache, and any cache misses
+    /// (in both the response and the cache itself) are filled with the data queried from the
+    /// database. To minimize the number of database seeks, only the range from the first to the
+    /// last non-cached block is queried.
+    pub(crate) async fn fee_history(
+        &self,
+        block_count: u64,
+        newest_block: BlockId,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> EthResult<FeeHistory> {
+        if block_count == 0 {
+            return Ok(FeeHistory::default())
+        }
+
+        let Some(end_block) = self.client().block_number_for_id(newest_block)? else { return Err(EthApiError::UnknownBlockNumber)};
+
+        if end_block < block_count {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+
+        let key = (
+            end_block,
+            block_count,
+            reward_percentiles.map(|p| p.into_iter().map(f64::to_bits).collect()),
+        );
+        if let Some(response) = self.fee_history_response_cache.get(&key) {
+            return Ok(response)
+        }
+
+        let start_block = end_block - block_count;
+
+        let mut fee_history_cache = self.fee_history_cache.0.lock().await;
+
+        // Sorted map that's populated in two rounds:
+        // 1. Cache entries until first non-cached block
+        // 2. Database query from the first non-cached block
+        let mut fee_history_cache_items = BTreeMap::new();
+
+        let mut first_non_cached_block = None;
+        let mut last_non_cached_block = None;
+        for block in start_block..=end_block {
+            // Check if block exists in cache, and move it to the head of the list if so
+            if let Some(fee_history_cache_item) = fee_history_cache.get(&block) {
+                fee_history_cache_items.insert(block, fee_history_cache_item.clone());
+            } else {
+                // If block doesn't exist in cache, set it as a first non-cached block to query it
+                // from the database
+                first_non_cached_block.get_or_insert(block);
+                // And last non-cached block, so we could query the database until we reach it
+                last_non_cached_block = Some(block);
+            }
+        }
+
+        // If we had any cache misses, query the database starting with the first non-cached block
+        // and ending with the last
+        if let (Some(start_block), Some(end_block)) =
+            (first_non_cached_block, last_non_cached_block)
+        {
+            let headers: Vec<Header> = self.client().headers_range(start_block..=end_block)?;
+
+            // We should receive exactly the amount of blocks missing from the cache
+            if headers.len() != (end_block - start_block + 1) as usize {
+                return Err(EthApiError::InvalidBlockRange)
+            }
+
+            for header in headers {
+                let base_fee_per_gas = header.base_fee_per_gas.
+                        unwrap_or_default(). // Zero for pre-EIP-1559 blocks
+                        try_into().unwrap(); // u64 -> U256 won't fail
+                let gas_used_ratio = header.gas_used as f64 / header.gas_limit as f64;
+
+                let fee_history_cache_item = FeeHistoryCacheItem {
+                    hash: None,
+                    base_fee_per_gas,
+                    gas_used_ratio,
+                    reward: None, // TODO: calculate rewards per transaction
+                };
+
+                // Insert missing cache entries in the map for further response composition from it
+                fee_history_cache_items.insert(header.number, fee_history_cache_item.clone());
+                // And populate the cache with new entries
+                fee_history_cache.push(header.number, fee_history_cache_item);
+            }
+        }
+
+        let oldest_block_hash =
+            self.client().block_hash(start_block)?.ok_or(EthApiError::UnknownBlockNumber)?;
+
+        fee_history_cache_items.get_mut(&start_block).unwrap().hash = Some(oldest_block_hash);
+        fee_history_cache.get_mut(&start_block).unwrap().hash = Some(oldest_block_hash);
+        drop(fee_history_cache);
+
+        // `fee_history_cache_items` now contains full requested block range (populated from both
+        // cache and database), so we can iterate over it in order and populate the response fields
+        let response = FeeHistory {
+            base_fee_per_gas: fee_history_cache_items
+                .values()
+                .map(|item| item.base_fee_per_gas)
+                .collect(),
+            gas_used_ratio: fee_history_cache_items
+                .values()
+                .map(|item| item.gas_used_ratio)
+                .collect(),
+            oldest_block: U256::from_be_bytes(oldest_block_hash.0),
+            reward: None,
+        };
+
+        let finalized = self
+            .client()
+            .chain_info()?
+            .last_finalized
+            .map_or(false, |finalized| end_block <= finalized);
+        self.fee_history_response_cache.insert(key, response.clone(), finalized);
+
+        Ok(response)
+    }
+}
+
+impl<Client, Pool, Network> EthApi<Client, Pool, Network>
+where
+    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + 'static,
+{
+    /// Suggests a priority fee for a transaction to be included in one of the next blocks.
+    ///
+    /// This is the configured percentile of the effective tips paid in the most recent non-empty
+    /// blocks, clamped to the configured bounds. Transactions that don't pay a tip are ignored,
+    /// since they are usually internal or sponsored.
+    pub(crate) async fn suggested_priority_fee(&self) -> EthResult<U256> {
+        let PriorityFeeConfig { blocks_to_sample, percentile, clamp_max_gwei } =
+            self.priority_fee_config;
+        let min_tip = GWEI_TO_WEI as u128;
+        let max_tip = (clamp_max_gwei as u128 * GWEI_TO_WEI as u128).max(min_tip);
+
+        let mut number = self.client().chain_info()?.best_number;
+        let max_blocks = blocks_to_sample.saturating_mul(PRIORITY_FEE_MAX_BLOCKS_PER_SAMPLE);
+
+        let mut tips = Vec::new();
+        let mut sampled_blocks = 0;
+        for _ in 0..max_blocks {
+            if sampled_blocks == blocks_to_sample {
+                break
+            }
+            let Some(block) = self.client().block_by_number(number)? else { break };
+
+            if !block.body.is_empty() {
+                sampled_blocks += 1;
+                let base_fee = block.header.base_fee_per_gas;
+                tips.extend(
+                    block
+                        .body
+                        .iter()
+                        .map(|tx| {
+                            tx.effective_gas_price(base_fee)
+                                .saturating_sub(base_fee.unwrap_or_default() as u128)
+                        })
+                        .filter(|tip| *tip > 0),
+                );
+            }
+
+            let Some(parent) = number.checked_sub(1) else { break };
+            number = parent;
+        }
+
+        if tips.is_empty() {
+            return Ok(U256::from(min_tip))
+        }
+
+        tips.sort_unstable();
+        let index = (tips.len() - 1) * percentile.min(100) as usize / 100;
+        Ok(U256::from(tips[index].clamp(min_tip, max_tip)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::cache::EthStateCache;
+    use reth_network_api::test_utils::NoopNetwork;
+    use reth_primitives::{
+        Block, Signature, Transaction, TransactionSigned, TxEip1559, TxLegacy, H256,
+    };
+    use reth_provider::test_utils::{MockEthProvider, NoopProvider};
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    #[test]
+    fn expires_non_finalized_responses() {
+        let cache = FeeHistoryResponseCache::new(FeeHistoryCacheConfig {
+            max_responses: 2,
+            ttl: Duration::ZERO,
+        });
+        let key = |newest_block| (newest_block, 1, Some(vec![50f64.to_bits()]));
+
+        cache.insert(key(1), FeeHistory::default(), true);
+        cache.insert(key(2), FeeHistory::default(), false);
+        assert!(cache.get(&key(1)).is_some());
+        assert!(cache.get(&key(2)).is_none());
+
+        // the least recently used response is evicted
+        cache.insert(key(2), FeeHistory::default(), true);
+        cache.insert(key(3), FeeHistory::default(), true);
+        assert!(cache.get(&key(1)).is_none());
+        assert!(cache.get(&key(2)).is_some());
+        assert!(cache.get(&key(3)).is_some());
+    }
+
+    #[tokio::test]
+    async fn suggests_priority_fee() {
+        const GWEI: u128 = GWEI_TO_WEI as u128;
+        let base_fee = 10 * GWEI;
+        let eip1559 = |tip: u128| {
+            Transaction::Eip1559(TxEip1559 {
+                max_fee_per_gas: base_fee + tip,
+                max_priority_fee_per_gas: tip,
+                ..Default::default()
+            })
+        };
+        let blocks = vec![
+            vec![Transaction::Legacy(TxLegacy {
+                gas_price: base_fee + 3 * GWEI,
+                ..Default::default()
+            })],
+            vec![eip1559(20 * GWEI), eip1559(4 * GWEI)],
+            vec![],
+            // zero tips are ignored
+            vec![eip1559(2 * GWEI), eip1559(0), eip1559(5 * GWEI)],
+        ];
+
+        let provider = MockEthProvider::default();
+        for (number, transactions) in blocks.into_iter().enumerate() {
+            let header = Header {
+                number: number as u64,
+                base_fee_per_gas: Some(base_fee as u64),
+                ..Default::default()
+            };
+            let body = transactions
+                .into_iter()
+                .map(|tx| {
+                    TransactionSigned::from_transaction_and_signature(tx, Signature::default())
+                })
+                .collect();
+            let hash = H256::from_low_u64_be(number as u64);
+            provider.add_block(hash, Block { header: header.clone(), body, ..Default::default() });
+            provider.add_header(hash, header);
+        }
+
+        let eth_api = EthApi::new(
+            provider,
+            testing_pool(),
+            NoopNetwork::default(),
+            EthStateCache::spawn(NoopProvider::default(), Default::default()),
+        );
+        // the 60th percentile of [2, 3, 4, 5, 20] gwei
+        assert_eq!(eth_api.suggested_priority_fee().await.unwrap(), U256::from(4 * GWEI));
+
+        let eth_api = eth_api.with_priority_fee_config(PriorityFeeConfig {
+            blocks_to_sample: 1,
+            ..Default::default()
+        });
+        assert_eq!(eth_api.suggested_priority_fee().await.unwrap(), U256::from(2 * GWEI));
+
+        let eth_api = eth_api.with_priority_fee_config(PriorityFeeConfig {
+            percentile: 100,
+            clamp_max_gwei: 3,
+            ..Default::default()
+        });
+        assert_eq!(eth_api.suggested_priority_fee().await.unwrap(), U256::from(3 * GWEI));
+    }
+}
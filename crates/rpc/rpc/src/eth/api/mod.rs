@@ -5,21 +5,30 @@
 
 use crate::eth::{cache::EthStateCache, signer::EthSigner};
 use async_trait::async_trait;
+use fee_history::FeeHistoryResponseCache;
 use reth_interfaces::Result;
 use reth_network_api::NetworkInfo;
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, ChainInfo, H256, U64};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, ChainInfo, H256, U256, U64};
 use reth_provider::{providers::ChainState, BlockProvider, EvmEnvProvider, StateProviderFactory};
-use reth_rpc_types::FeeHistoryCache;
+use reth_rpc_types::{FeeHistoryCache, SyncStatus};
 use reth_transaction_pool::TransactionPool;
-use std::{num::NonZeroUsize, sync::Arc};
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+use sync_status::SyncStatusTracker;
 
 mod block;
 mod call;
+mod fee_history;
 mod server;
 mod sign;
+mod simulate;
 mod state;
+mod sync_status;
 mod transactions;
 use crate::eth::error::{EthApiError, EthResult};
+pub use fee_history::{FeeHistoryCacheConfig, PriorityFeeConfig};
 pub use transactions::{EthTransactions, TransactionSource};
 
 /// Cache limit of block-level fee history for `eth_feeHistory` RPC method.
@@ -41,6 +50,9 @@ pub trait EthApiSpec: EthTransactions + Send + Sync {
 
     /// Returns a list of addresses owned by client.
     fn accounts(&self) -> Vec<Address>;
+
+    /// Returns the current sync status of the node.
+    fn sync_status(&self) -> SyncStatus;
 }
 
 /// `Eth` API implementation.
@@ -56,20 +68,57 @@ pub struct EthApi<Client, Pool, Network> {
     /// All nested fields bundled together.
     inner: Arc<EthApiInner<Client, Pool, Network>>,
     fee_history_cache: FeeHistoryCache,
+    /// Complete `eth_feeHistory` responses.
+    fee_history_response_cache: FeeHistoryResponseCache,
+    /// Settings for the `eth_maxPriorityFeePerGas` estimation.
+    priority_fee_config: PriorityFeeConfig,
 }
 
-impl<Client, Pool, Network> EthApi<Client, Pool, Network> {
+impl<Client, Pool, Network> EthApi<Client, Pool, Network>
+where
+    Network: NetworkInfo,
+{
     /// Creates a new, shareable instance.
     pub fn new(client: Client, pool: Pool, network: Network, eth_cache: EthStateCache) -> Self {
-        let inner = EthApiInner { client, pool, network, signers: Default::default(), eth_cache };
+        Self::with_fee_history_cache(client, pool, network, eth_cache, Default::default())
+    }
+
+    /// Creates a new, shareable instance that caches `eth_feeHistory` responses with the given
+    /// settings.
+    pub fn with_fee_history_cache(
+        client: Client,
+        pool: Pool,
+        network: Network,
+        eth_cache: EthStateCache,
+        fee_history_cache_config: FeeHistoryCacheConfig,
+    ) -> Self {
+        let sync_status = Mutex::new(SyncStatusTracker::new(network.subscribe_sync_events()));
+        let inner = EthApiInner {
+            client,
+            pool,
+            network,
+            signers: Default::default(),
+            eth_cache,
+            sync_status,
+        };
         Self {
             inner: Arc::new(inner),
             fee_history_cache: FeeHistoryCache::new(
                 NonZeroUsize::new(FEE_HISTORY_CACHE_LIMIT).unwrap(),
             ),
+            fee_history_response_cache: FeeHistoryResponseCache::new(fee_history_cache_config),
+            priority_fee_config: Default::default(),
         }
     }
 
+    /// Configures how `eth_maxPriorityFeePerGas` estimates the priority fee.
+    pub fn with_priority_fee_config(mut self, priority_fee_config: PriorityFeeConfig) -> Self {
+        self.priority_fee_config = priority_fee_config;
+        self
+    }
+}
+
+impl<Client, Pool, Network> EthApi<Client, Pool, Network> {
     /// Returns the state cache frontend
     pub(crate) fn cache(&self) -> &EthStateCache {
         &self.inner.eth_cache
@@ -193,6 +242,17 @@ where
     fn accounts(&self) -> Vec<Address> {
         self.inner.signers.iter().flat_map(|s| s.accounts()).collect()
     }
+
+    fn sync_status(&self) -> SyncStatus {
+        let mut status = self.inner.sync_status.lock().expect("not poisoned").status();
+        if let SyncStatus::Info(ref mut info) = status {
+            if let Ok(chain_info) = self.chain_info() {
+                info.safe_block = chain_info.safe_finalized.map(U256::from);
+                info.finalized_block = chain_info.last_finalized.map(U256::from);
+            }
+        }
+        status
+    }
 }
 
 /// Container type `EthApi`
@@ -207,4 +267,6 @@ struct EthApiInner<Client, Pool, Network> {
     signers: Vec<Box<dyn EthSigner>>,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
+    /// Tracks the sync status from the sync events of the network
+    sync_status: Mutex<SyncStatusTracker>,
 }
@@ -1,4 +1,5 @@
-//! Implementation of the [`jsonrpsee`] generated [`reth_rpc_api::EthApiServer`] trait
+//! Implementation of the [`jsonrpsee`] generated [`reth_rpc_api::EthApiServer`] and
+//! [`reth_rpc_api::EthExperimentalApiServer`] traits
 //! Handles RPC requests for the `eth_` namespace.
 
 use super::EthApiSpec;
@@ -11,18 +12,18 @@ use crate::{
 };
 use jsonrpsee::core::RpcResult as Result;
 use reth_primitives::{
-    AccessListWithGasUsed, Address, BlockId, BlockNumberOrTag, Bytes, Header, H256, H64, U256, U64,
+    AccessListWithGasUsed, Address, BlockId, BlockNumberOrTag, Bytes, H256, H64, U256, U64,
 };
 use reth_provider::{BlockProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory};
-use reth_rpc_api::EthApiServer;
+use reth_rpc_api::{EthApiServer, EthExperimentalApiServer};
 use reth_rpc_types::{
-    state::StateOverride, CallRequest, EIP1186AccountProofResponse, FeeHistory,
-    FeeHistoryCacheItem, Index, RichBlock, SyncStatus, TransactionReceipt, TransactionRequest,
-    Work,
+    simulate::{SimulatedBlock, SimulationResult},
+    state::{BlockOverrides, StateOverride},
+    CallRequest, DryRunResult, EIP1186AccountProofResponse, FeeHistory, Index, RichBlock,
+    SyncStatus, TransactionReceipt, TransactionRequest, Work,
 };
 use reth_transaction_pool::TransactionPool;
 use serde_json::Value;
-use std::collections::BTreeMap;
 
 #[async_trait::async_trait]
 impl<Client, Pool, Network> EthApiServer for EthApi<Client, Pool, Network>
@@ -39,7 +40,7 @@ where
 
     /// Handler for: `eth_syncing`
     fn syncing(&self) -> Result<SyncStatus> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(EthApiSpec::sync_status(self))
     }
 
     /// Handler for: `eth_coinbase`
@@ -147,6 +148,11 @@ where
         Err(internal_rpc_err("unimplemented"))
     }
 
+    /// Handler for: `eth_getBlockReceipts`
+    async fn block_receipts(&self, block_id: BlockId) -> Result<Option<Vec<TransactionReceipt>>> {
+        Ok(EthApi::block_receipts(self, block_id).await?)
+    }
+
     /// Handler for: `eth_getBalance`
     async fn balance(&self, address: Address, block_number: Option<BlockId>) -> Result<U256> {
         Ok(EthApi::balance(self, address, block_number)?)
@@ -182,12 +188,14 @@ where
         request: CallRequest,
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<Bytes> {
         let (res, _env) = self
             .execute_call_at(
                 request,
                 block_number.unwrap_or(BlockId::Number(BlockNumberOrTag::Pending)),
                 state_overrides,
+                block_overrides,
             )
             .await?;
 
@@ -201,7 +209,7 @@ where
         block_number: Option<BlockId>,
     ) -> Result<AccessListWithGasUsed> {
         let block_id = block_number.unwrap_or(BlockId::Number(BlockNumberOrTag::Pending));
-        let access_list = self.create_access_list_at(request.clone(), block_number).await?;
+        let access_list = self.create_access_list_at(request.clone(), Some(block_id)).await?;
         request.access_list = Some(access_list.clone());
         let gas_used = self.estimate_gas_at(request, block_id).await?;
         Ok(AccessListWithGasUsed { access_list, gas_used })
@@ -221,119 +229,34 @@ where
         .await?)
     }
 
+    /// Handler for: `eth_simulateV1`
+    async fn simulate_v1(
+        &self,
+        blocks: Vec<SimulatedBlock>,
+        block_overrides: Option<BlockOverrides>,
+    ) -> Result<Vec<SimulationResult>> {
+        Ok(EthApi::simulate_blocks(self, blocks, block_overrides).await?)
+    }
+
     /// Handler for: `eth_gasPrice`
     async fn gas_price(&self) -> Result<U256> {
         Err(internal_rpc_err("unimplemented"))
     }
 
-    // FeeHistory is calculated based on lazy evaluation of fees for historical blocks, and further
-    // caching of it in the LRU cache.
-    // When new RPC call is executed, the cache gets locked, we check it for the historical fees
-    // according to the requested block range, and fill any cache misses (in both RPC response
-    // and cache itself) with the actual data queried from the database.
-    // To minimize the number of database seeks required to query the missing data, we calculate the
-    // first non-cached block number and last non-cached block number. After that, we query this
-    // range of consecutive blocks from the database.
     /// Handler for: `eth_feeHistory`
     async fn fee_history(
         &self,
         block_count: U64,
         newest_block: BlockId,
-        _reward_percentiles: Option<Vec<f64>>,
+        reward_percentiles: Option<Vec<f64>>,
     ) -> Result<FeeHistory> {
-        let block_count = block_count.as_u64();
-
-        if block_count == 0 {
-            return Ok(FeeHistory::default())
-        }
-
-        let Some(end_block) = self.inner.client.block_number_for_id(newest_block).to_rpc_result()? else { return Err(EthApiError::UnknownBlockNumber.into())};
-
-        if end_block < block_count {
-            return Err(EthApiError::InvalidBlockRange.into())
-        }
-
-        let start_block = end_block - block_count;
-
-        let mut fee_history_cache = self.fee_history_cache.0.lock().await;
-
-        // Sorted map that's populated in two rounds:
-        // 1. Cache entries until first non-cached block
-        // 2. Database query from the first non-cached block
-        let mut fee_history_cache_items = BTreeMap::new();
-
-        let mut first_non_cached_block = None;
-        let mut last_non_cached_block = None;
-        for block in start_block..=end_block {
-            // Check if block exists in cache, and move it to the head of the list if so
-            if let Some(fee_history_cache_item) = fee_history_cache.get(&block) {
-                fee_history_cache_items.insert(block, fee_history_cache_item.clone());
-            } else {
-                // If block doesn't exist in cache, set it as a first non-cached block to query it
-                // from the database
-                first_non_cached_block.get_or_insert(block);
-                // And last non-cached block, so we could query the database until we reach it
-                last_non_cached_block = Some(block);
-            }
-        }
-
-        // If we had any cache misses, query the database starting with the first non-cached block
-        // and ending with the last
-        if let (Some(start_block), Some(end_block)) =
-            (first_non_cached_block, last_non_cached_block)
-        {
-            let headers: Vec<Header> =
-                self.inner.client.headers_range(start_block..=end_block).to_rpc_result()?;
-
-            // We should receive exactly the amount of blocks missing from the cache
-            if headers.len() != (end_block - start_block + 1) as usize {
-                return Err(EthApiError::InvalidBlockRange.into())
-            }
-
-            for header in headers {
-                let base_fee_per_gas = header.base_fee_per_gas.
-                        unwrap_or_default(). // Zero for pre-EIP-1559 blocks
-                        try_into().unwrap(); // u64 -> U256 won't fail
-                let gas_used_ratio = header.gas_used as f64 / header.gas_limit as f64;
-
-                let fee_history_cache_item = FeeHistoryCacheItem {
-                    hash: None,
-                    base_fee_per_gas,
-                    gas_used_ratio,
-                    reward: None, // TODO: calculate rewards per transaction
-                };
-
-                // Insert missing cache entries in the map for further response composition from it
-                fee_history_cache_items.insert(header.number, fee_history_cache_item.clone());
-                // And populate the cache with new entries
-                fee_history_cache.push(header.number, fee_history_cache_item);
-            }
-        }
-
-        let oldest_block_hash = self.inner.client.block_hash(start_block).to_rpc_result()?.unwrap();
-
-        fee_history_cache_items.get_mut(&start_block).unwrap().hash = Some(oldest_block_hash);
-        fee_history_cache.get_mut(&start_block).unwrap().hash = Some(oldest_block_hash);
-
-        // `fee_history_cache_items` now contains full requested block range (populated from both
-        // cache and database), so we can iterate over it in order and populate the response fields
-        Ok(FeeHistory {
-            base_fee_per_gas: fee_history_cache_items
-                .values()
-                .map(|item| item.base_fee_per_gas)
-                .collect(),
-            gas_used_ratio: fee_history_cache_items
-                .values()
-                .map(|item| item.gas_used_ratio)
-                .collect(),
-            oldest_block: U256::from_be_bytes(oldest_block_hash.0),
-            reward: None,
-        })
+        Ok(EthApi::fee_history(self, block_count.as_u64(), newest_block, reward_percentiles)
+            .await?)
     }
 
     /// Handler for: `eth_maxPriorityFeePerGas`
     async fn max_priority_fee_per_gas(&self) -> Result<U256> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(EthApi::suggested_priority_fee(self).await?)
     }
 
     /// Handler for: `eth_mining`
@@ -404,6 +327,19 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Client, Pool, Network> EthExperimentalApiServer for EthApi<Client, Pool, Network>
+where
+    Pool: TransactionPool + 'static,
+    Client: BlockProvider + HeaderProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: Send + Sync + 'static,
+{
+    /// Handler for: `reth_dryRunTransaction`
+    async fn dry_run_transaction(&self, tx: Bytes) -> Result<DryRunResult> {
+        Ok(EthApi::dry_run_transaction(self, tx).await?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{eth::cache::EthStateCache, EthApi};
@@ -412,8 +348,12 @@ mod tests {
         types::error::{CallError, INVALID_PARAMS_CODE},
     };
     use rand::random;
+    use reth_interfaces::{consensus::ForkchoiceState, test_utils::generators::random_signed_tx};
     use reth_network_api::test_utils::NoopNetwork;
-    use reth_primitives::{Block, BlockNumberOrTag, Header, H256, U256};
+    use reth_primitives::{
+        Address, Block, BlockId, BlockNumberOrTag, Header, Log, Receipt, TransactionKind, TxType,
+        H256, U128, U256, U64,
+    };
     use reth_provider::test_utils::{MockEthProvider, NoopProvider};
     use reth_rpc_api::EthApiServer;
     use reth_transaction_pool::test_utils::testing_pool;
@@ -428,7 +368,9 @@ mod tests {
             EthStateCache::spawn(NoopProvider::default(), Default::default()),
         );
 
-        let response = eth_api.fee_history(1.into(), BlockNumberOrTag::Latest.into(), None).await;
+        let response =
+            EthApiServer::fee_history(&eth_api, 1.into(), BlockNumberOrTag::Latest.into(), None)
+                .await;
         assert!(matches!(response, RpcResult::Err(RpcError::Call(CallError::Custom(_)))));
         let Err(RpcError::Call(CallError::Custom(error_object))) = response else { unreachable!() };
         assert_eq!(error_object.code(), INVALID_PARAMS_CODE);
@@ -472,17 +414,125 @@ mod tests {
             EthStateCache::spawn(NoopProvider::default(), Default::default()),
         );
 
-        let response =
-            eth_api.fee_history((newest_block + 1).into(), newest_block.into(), None).await;
+        let response = EthApiServer::fee_history(
+            &eth_api,
+            (newest_block + 1).into(),
+            newest_block.into(),
+            None,
+        )
+        .await;
         assert!(matches!(response, RpcResult::Err(RpcError::Call(CallError::Custom(_)))));
         let Err(RpcError::Call(CallError::Custom(error_object))) = response else { unreachable!() };
         assert_eq!(error_object.code(), INVALID_PARAMS_CODE);
 
         let fee_history =
-            eth_api.fee_history(block_count.into(), newest_block.into(), None).await.unwrap();
+            EthApiServer::fee_history(&eth_api, block_count.into(), newest_block.into(), None)
+                .await
+                .unwrap();
 
         assert_eq!(fee_history.base_fee_per_gas, base_fees_per_gas);
         assert_eq!(fee_history.gas_used_ratio, gas_used_ratios);
         assert_eq!(fee_history.oldest_block, U256::from_be_bytes(oldest_block.unwrap().0));
     }
+
+    #[tokio::test]
+    async fn safe_and_finalized_block_by_number() {
+        let mock_provider = MockEthProvider::default();
+        let hashes = (0..4)
+            .map(|number| {
+                let hash = H256::random();
+                let header = Header { number, ..Default::default() };
+                mock_provider.add_block(hash, Block { header, ..Default::default() });
+                hash
+            })
+            .collect::<Vec<_>>();
+        mock_provider.set_forkchoice_state(ForkchoiceState {
+            head_block_hash: hashes[3],
+            safe_block_hash: hashes[2],
+            finalized_block_hash: hashes[1],
+        });
+
+        let eth_api = EthApi::new(
+            mock_provider,
+            testing_pool(),
+            NoopNetwork::default(),
+            EthStateCache::spawn(NoopProvider::default(), Default::default()),
+        );
+
+        for (tag, hash) in
+            [(BlockNumberOrTag::Safe, hashes[2]), (BlockNumberOrTag::Finalized, hashes[1])]
+        {
+            let block = EthApiServer::block_by_number(&eth_api, tag, false).await.unwrap().unwrap();
+            assert_eq!(block.header.hash, Some(hash));
+        }
+    }
+
+    #[tokio::test]
+    async fn block_receipts() {
+        let mock_provider = MockEthProvider::default();
+        let body = vec![random_signed_tx(), random_signed_tx()];
+        let header = Header { number: 1, ..Default::default() };
+        let block = Block { header, body, ..Default::default() };
+        let hash = H256::random();
+        let log = |address| Log { address, topics: vec![H256::random()], data: Default::default() };
+        let receipts = vec![
+            Receipt {
+                tx_type: TxType::Legacy,
+                success: true,
+                cumulative_gas_used: 21_000,
+                bloom: Default::default(),
+                logs: vec![log(Address::random())],
+            },
+            Receipt {
+                tx_type: TxType::Legacy,
+                success: false,
+                cumulative_gas_used: 50_000,
+                bloom: Default::default(),
+                logs: vec![log(Address::random()), log(Address::random())],
+            },
+        ];
+        mock_provider.add_block(hash, block.clone());
+        mock_provider.add_receipts(1, receipts.clone());
+
+        let eth_api = EthApi::new(
+            mock_provider,
+            testing_pool(),
+            NoopNetwork::default(),
+            EthStateCache::spawn(NoopProvider::default(), Default::default()),
+        );
+
+        let block_receipts = EthApiServer::block_receipts(&eth_api, BlockId::Hash(hash.into()))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block_receipts.len(), 2);
+
+        let mut log_index = 0;
+        for (tx_index, ((rpc_receipt, receipt), transaction)) in
+            block_receipts.iter().zip(&receipts).zip(&block.body).enumerate()
+        {
+            assert_eq!(rpc_receipt.transaction_hash, Some(transaction.hash));
+            assert_eq!(rpc_receipt.transaction_index, Some(U256::from(tx_index)));
+            assert_eq!(rpc_receipt.block_hash, Some(hash));
+            assert_eq!(rpc_receipt.block_number, Some(U256::from(1)));
+            assert_eq!(rpc_receipt.from, transaction.recover_signer().unwrap());
+            assert_eq!(rpc_receipt.to.map(TransactionKind::Call), Some(transaction.kind()));
+            assert_eq!(rpc_receipt.cumulative_gas_used, U256::from(receipt.cumulative_gas_used));
+            assert_eq!(rpc_receipt.status_code, Some(U64::from(receipt.success as u64)));
+            assert_eq!(rpc_receipt.effective_gas_price, U128::from(transaction.max_fee_per_gas()));
+
+            for (tx_log_index, (rpc_log, log)) in
+                rpc_receipt.logs.iter().zip(&receipt.logs).enumerate()
+            {
+                assert_eq!(rpc_log.address, log.address);
+                assert_eq!(rpc_log.topics, log.topics);
+                assert_eq!(rpc_log.transaction_hash, Some(transaction.hash));
+                assert_eq!(rpc_log.log_index, Some(U256::from(log_index)));
+                assert_eq!(rpc_log.transaction_log_index, Some(U256::from(tx_log_index)));
+                log_index += 1;
+            }
+        }
+        assert_eq!(block_receipts[0].gas_used, Some(U256::from(21_000)));
+        assert_eq!(block_receipts[1].gas_used, Some(U256::from(29_000)));
+    }
 }
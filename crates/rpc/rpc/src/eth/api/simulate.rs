@@ -0,0 +1,227 @@
+//! Contains the implementation of simulating multiple blocks of calls.
+
+use crate::{
+    eth::{
+        api::call::{apply_block_overrides, apply_state_overrides},
+        error::{EthResult, InvalidTransactionError, RevertError},
+        revm_utils::{build_call_evm_env, transact},
+        EthTransactions,
+    },
+    EthApi,
+};
+use reth_primitives::{BlockId, BlockNumberOrTag, U256, U64};
+use reth_provider::{BlockProvider, EvmEnvProvider, StateProvider, StateProviderFactory};
+use reth_revm::database::{State, SubState};
+use reth_rpc_types::{
+    simulate::{SimulatedBlock, SimulatedCallResult, SimulationResult},
+    state::BlockOverrides,
+    CallRequest, TransactionRequest,
+};
+use reth_transaction_pool::TransactionPool;
+use revm::{
+    primitives::{BlockEnv, CfgEnv, ExecutionResult},
+    DatabaseCommit,
+};
+
+/// The time between two simulated blocks, unless overridden.
+const SIMULATED_BLOCK_TIME: u64 = 12;
+
+impl<Client, Pool, Network> EthApi<Client, Pool, Network>
+where
+    Self: EthTransactions,
+    Pool: TransactionPool + 'static,
+    Client: BlockProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: Send + Sync + 'static,
+{
+    /// Simulates the blocks in order on top of the latest block.
+    ///
+    /// The environment of the latest block with the `block_override` applied is the parent of the
+    /// first simulated block, and every simulated block increments the number and the timestamp of
+    /// its parent. The state changes of the calls are only kept in memory, so
+    /// every block sees the changes of the previous ones but nothing is written to the database.
+    pub(crate) async fn simulate_blocks(
+        &self,
+        blocks: Vec<SimulatedBlock>,
+        block_override: Option<BlockOverrides>,
+    ) -> EthResult<Vec<SimulationResult>> {
+        let (cfg, mut block_env, at) =
+            self.evm_env_at(BlockId::Number(BlockNumberOrTag::Latest)).await?;
+        let state = self.state_at(at)?;
+        if let Some(block_override) = block_override {
+            apply_block_overrides(block_override, &mut block_env);
+        }
+        simulate_blocks_with(cfg, block_env, state, blocks)
+    }
+}
+
+/// Simulates the blocks in order against the state, on top of the given parent block environment.
+fn simulate_blocks_with<S>(
+    mut cfg: CfgEnv,
+    mut block_env: BlockEnv,
+    state: S,
+    blocks: Vec<SimulatedBlock>,
+) -> EthResult<Vec<SimulationResult>>
+where
+    S: StateProvider,
+{
+    // same as for `eth_call`, the calls are not limited by the gas limit of the block
+    cfg.disable_block_gas_limit = true;
+
+    // all changes are committed to this in-memory cache on top of the state
+    let mut db = SubState::new(State::new(state));
+
+    let mut results = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        block_env.number += U256::from(1);
+        block_env.timestamp += U256::from(SIMULATED_BLOCK_TIME);
+        if let Some(block_overrides) = block.block_overrides {
+            apply_block_overrides(block_overrides, &mut block_env);
+        }
+        if let Some(state_overrides) = block.state_overrides {
+            apply_state_overrides(state_overrides, &mut db)?;
+        }
+
+        let mut gas_used = 0;
+        let mut calls = Vec::with_capacity(block.calls.len());
+        for request in block.calls {
+            let env = build_call_evm_env(cfg.clone(), block_env.clone(), call_request(request))?;
+            let (res, _) = transact(&mut db, env)?;
+            db.commit(res.state);
+
+            gas_used += res.result.gas_used();
+            calls.push(call_result(res.result));
+        }
+
+        results.push(SimulationResult {
+            number: U64::from(block_env.number.saturating_to::<u64>()),
+            gas_used: U64::from(gas_used),
+            calls,
+        });
+    }
+    Ok(results)
+}
+
+/// Converts the transaction of a simulated block into the request of a call.
+fn call_request(request: TransactionRequest) -> CallRequest {
+    let TransactionRequest {
+        from,
+        to,
+        gas_price,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        gas,
+        value,
+        data,
+        nonce,
+        access_list,
+        ..
+    } = request;
+    CallRequest {
+        from,
+        to,
+        gas_price: gas_price.map(U256::from),
+        max_fee_per_gas: max_fee_per_gas.map(U256::from),
+        max_priority_fee_per_gas: max_priority_fee_per_gas.map(U256::from),
+        gas,
+        value,
+        data,
+        nonce,
+        chain_id: None,
+        access_list,
+    }
+}
+
+/// Converts the result of a simulated call.
+fn call_result(result: ExecutionResult) -> SimulatedCallResult {
+    let gas_used = U64::from(result.gas_used());
+    match result {
+        ExecutionResult::Success { output, .. } => SimulatedCallResult {
+            status: true,
+            gas_used,
+            return_data: output.into_data().into(),
+            error: None,
+        },
+        ExecutionResult::Revert { output, .. } => SimulatedCallResult {
+            status: false,
+            gas_used,
+            error: Some(RevertError::new(output.clone()).to_string()),
+            return_data: output.into(),
+        },
+        ExecutionResult::Halt { reason, gas_used: halt_gas_used } => SimulatedCallResult {
+            status: false,
+            gas_used,
+            return_data: Default::default(),
+            error: Some(InvalidTransactionError::halt(reason, halt_gas_used).to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, Bytes, H256};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_types::state::AccountOverride;
+    use std::collections::HashMap;
+
+    #[test]
+    fn simulated_blocks_see_previous_blocks() {
+        // increments slot 0 and returns the new value
+        let code = Bytes::from(vec![
+            0x60, 0x00, 0x54, 0x60, 0x01, 0x01, 0x80, 0x60, 0x00, 0x55, 0x60, 0x00, 0x52, 0x60,
+            0x20, 0x60, 0x00, 0xf3,
+        ]);
+        let counter = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(counter, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code));
+
+        let increment = TransactionRequest { to: Some(counter), ..Default::default() };
+        let blocks = vec![
+            SimulatedBlock {
+                calls: vec![increment.clone(), increment.clone()],
+                ..Default::default()
+            },
+            SimulatedBlock { calls: vec![increment.clone()], ..Default::default() },
+            SimulatedBlock {
+                calls: vec![increment],
+                state_overrides: Some(HashMap::from([(
+                    counter,
+                    AccountOverride {
+                        state_diff: Some(HashMap::from([(
+                            H256::zero(),
+                            H256::from_low_u64_be(10),
+                        )])),
+                        ..Default::default()
+                    },
+                )])),
+                ..Default::default()
+            },
+        ];
+        let block_env = BlockEnv { number: U256::from(100), ..Default::default() };
+        let results =
+            simulate_blocks_with(CfgEnv::default(), block_env, provider.clone(), blocks).unwrap();
+
+        let counts = results
+            .iter()
+            .map(|block| {
+                block
+                    .calls
+                    .iter()
+                    .map(|call| {
+                        assert!(call.status);
+                        U256::from_be_bytes::<32>(call.return_data.as_ref().try_into().unwrap())
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            counts,
+            vec![vec![U256::from(1), U256::from(2)], vec![U256::from(3)], vec![U256::from(11)]]
+        );
+        let numbers = results.iter().map(|block| block.number).collect::<Vec<_>>();
+        assert_eq!(numbers, vec![U64::from(101), U64::from(102), U64::from(103)]);
+
+        // the simulated state was not written to the provider
+        assert_eq!(provider.storage(counter, H256::zero()).unwrap(), None);
+    }
+}
@@ -0,0 +1,102 @@
+//! Tracks the sync status for `eth_syncing`.
+
+use reth_network_api::SyncEvent;
+use reth_primitives::U256;
+use reth_rpc_types::{SyncInfo, SyncStatus};
+use tokio::sync::broadcast::{self, error::TryRecvError};
+
+/// Keeps track of the node's sync status by consuming the [`SyncEvent`]s of the network.
+///
+/// Events are consumed lazily whenever the status is requested.
+#[derive(Debug)]
+pub(crate) struct SyncStatusTracker {
+    /// The subscription to the sync events.
+    events: broadcast::Receiver<SyncEvent>,
+    /// The ongoing sync, if any.
+    current: Option<SyncInfo>,
+}
+
+impl SyncStatusTracker {
+    /// Creates a new tracker that consumes the given events.
+    pub(crate) fn new(events: broadcast::Receiver<SyncEvent>) -> Self {
+        Self { events, current: None }
+    }
+
+    /// Applies all pending events and returns the current status.
+    pub(crate) fn status(&mut self) -> SyncStatus {
+        loop {
+            match self.events.try_recv() {
+                Ok(event) => self.on_event(event),
+                // skipped events are superseded by the following events
+                Err(TryRecvError::Lagged(_)) => continue,
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+        match self.current {
+            Some(ref info) => SyncStatus::Info(info.clone()),
+            None => SyncStatus::None,
+        }
+    }
+
+    fn on_event(&mut self, event: SyncEvent) {
+        match event {
+            SyncEvent::SyncStarted { start_block, target_block } => {
+                self.current = Some(SyncInfo {
+                    starting_block: U256::from(start_block),
+                    current_block: U256::from(start_block),
+                    highest_block: U256::from(target_block),
+                    ..Default::default()
+                });
+            }
+            SyncEvent::SyncProgress { current_block, highest_block } => {
+                let info = self.current.get_or_insert_with(|| SyncInfo {
+                    starting_block: U256::from(current_block),
+                    ..Default::default()
+                });
+                info.current_block = U256::from(current_block);
+                info.highest_block = U256::from(highest_block);
+            }
+            SyncEvent::SyncFinished => self.current = None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_sync_events() {
+        let (tx, rx) = broadcast::channel(10);
+        let mut tracker = SyncStatusTracker::new(rx);
+        assert_eq!(tracker.status(), SyncStatus::None);
+
+        tx.send(SyncEvent::SyncStarted { start_block: 5, target_block: 100 }).unwrap();
+        tx.send(SyncEvent::SyncProgress { current_block: 50, highest_block: 120 }).unwrap();
+        assert_eq!(
+            tracker.status(),
+            SyncStatus::Info(SyncInfo {
+                starting_block: U256::from(5),
+                current_block: U256::from(50),
+                highest_block: U256::from(120),
+                ..Default::default()
+            })
+        );
+
+        tx.send(SyncEvent::SyncFinished).unwrap();
+        assert_eq!(tracker.status(), SyncStatus::None);
+    }
+
+    #[test]
+    fn recovers_from_lagged_events() {
+        let (tx, rx) = broadcast::channel(2);
+        let mut tracker = SyncStatusTracker::new(rx);
+
+        tx.send(SyncEvent::SyncStarted { start_block: 0, target_block: 100 }).unwrap();
+        for current_block in 1..=10 {
+            tx.send(SyncEvent::SyncProgress { current_block, highest_block: 100 }).unwrap();
+        }
+        let SyncStatus::Info(info) = tracker.status() else { panic!("expected syncing") };
+        assert_eq!(info.current_block, U256::from(10));
+    }
+}
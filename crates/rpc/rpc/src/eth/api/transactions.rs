@@ -5,13 +5,16 @@ use crate::{
 };
 use async_trait::async_trait;
 use reth_primitives::{
-    BlockId, BlockNumberOrTag, Bytes, FromRecoveredTransaction, IntoRecoveredTransaction,
-    TransactionSigned, TransactionSignedEcRecovered, H256, U256,
+    BlobTransaction, BlockId, BlockNumberOrTag, Bytes, FromRecoveredTransaction,
+    IntoRecoveredTransaction, Transaction as PrimitiveTransaction, TransactionKind,
+    TransactionSigned, TransactionSignedEcRecovered, TxEip1559, TxEip2930, H256, U256, U64,
 };
 use reth_provider::{providers::ChainState, BlockProvider, EvmEnvProvider, StateProviderFactory};
 use reth_rlp::Decodable;
-use reth_rpc_types::{Index, Transaction, TransactionInfo, TransactionRequest};
-use reth_transaction_pool::{TransactionOrigin, TransactionPool};
+use reth_rpc_types::{
+    CallRequest, DryRunResult, Index, Transaction, TransactionInfo, TransactionRequest,
+};
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
 use revm::primitives::{BlockEnv, CfgEnv};
 
 /// Commonly used transaction related functions for the [EthApi] type in the `eth_` namespace
@@ -204,6 +207,81 @@ where
 
         Ok(hash)
     }
+
+    /// Decodes and recovers the transaction and validates it like [Self::send_raw_transaction],
+    /// but without submitting it to the pool.
+    ///
+    /// Validation failures are reported in the [DryRunResult] instead of as an error.
+    pub(crate) async fn dry_run_transaction(&self, tx: Bytes) -> EthResult<DryRunResult> {
+        let mut data = tx.as_ref();
+        if data.is_empty() {
+            return Ok(DryRunResult::invalid(EthApiError::EmptyRawTransactionData))
+        }
+        let Ok(transaction) = TransactionSigned::decode(&mut data) else {
+            return Ok(DryRunResult::invalid(EthApiError::FailedToDecodeSignedTransaction))
+        };
+        let Some(recovered) = transaction.into_ecrecovered() else {
+            return Ok(DryRunResult::invalid(EthApiError::InvalidTransactionSignature))
+        };
+
+        let request = call_request(&recovered);
+        let from = recovered.signer();
+        let pool_transaction = <Pool::Transaction>::from_recovered_transaction(recovered);
+        let cost_wei = pool_transaction.cost();
+
+        let mut result = DryRunResult { is_valid: true, from, cost_wei, ..Default::default() };
+        if let Err(err) =
+            self.pool().validate_transaction(TransactionOrigin::Local, pool_transaction).await
+        {
+            result.is_valid = false;
+            result.error = Some(EthApiError::from(err).to_string());
+        }
+
+        // the estimate is still useful if the pool rejects the transaction, e.g. for a nonce gap
+        match self.estimate_gas_at(request, BlockId::Number(BlockNumberOrTag::Latest)).await {
+            Ok(gas) => result.estimated_gas = U64::from(gas.saturating_to::<u64>()),
+            Err(err) if result.is_valid => {
+                result.is_valid = false;
+                result.error = Some(err.to_string());
+            }
+            Err(_) => {}
+        }
+
+        Ok(result)
+    }
+}
+
+/// Creates the [CallRequest] that executes the transaction as it would be included in a block.
+fn call_request(tx: &TransactionSignedEcRecovered) -> CallRequest {
+    let to = match tx.kind() {
+        TransactionKind::Call(to) => Some(to),
+        TransactionKind::Create => None,
+    };
+    let (gas_price, max_fee_per_gas) = match tx.max_priority_fee_per_gas() {
+        Some(_) => (None, Some(U256::from(tx.max_fee_per_gas()))),
+        None => (Some(U256::from(tx.max_fee_per_gas())), None),
+    };
+    let access_list = match &tx.transaction {
+        PrimitiveTransaction::Legacy(_) => None,
+        PrimitiveTransaction::Eip2930(TxEip2930 { access_list, .. }) |
+        PrimitiveTransaction::Eip1559(TxEip1559 { access_list, .. }) |
+        PrimitiveTransaction::Blob(BlobTransaction { access_list, .. }) => {
+            Some(access_list.clone())
+        }
+    };
+    CallRequest {
+        from: Some(tx.signer()),
+        to,
+        gas_price,
+        max_fee_per_gas,
+        max_priority_fee_per_gas: tx.max_priority_fee_per_gas().map(U256::from),
+        gas: Some(U256::from(tx.gas_limit())),
+        value: Some(U256::from(*tx.value())),
+        data: Some(tx.input().clone()),
+        nonce: Some(U256::from(tx.nonce())),
+        chain_id: tx.chain_id().map(U64::from),
+        access_list,
+    }
 }
 
 /// Represents from where a transaction was fetched.
@@ -291,7 +369,7 @@ impl From<TransactionSource> for Transaction {
 #[cfg(test)]
 mod tests {
     use crate::eth::cache::EthStateCache;
-    use reth_primitives::{hex_literal::hex, Bytes};
+    use reth_primitives::{hex_literal::hex, Bytes, U256};
     use reth_provider::test_utils::NoopProvider;
     use reth_transaction_pool::{test_utils::testing_pool, TransactionPool};
 
@@ -335,4 +413,26 @@ mod tests {
         assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
         assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
     }
+
+    #[tokio::test]
+    async fn dry_run_transaction() {
+        let pool = testing_pool();
+        let eth_api = EthApi::new(
+            NoopProvider::default(),
+            pool.clone(),
+            (),
+            EthStateCache::spawn(NoopProvider::default(), Default::default()),
+        );
+
+        let result = eth_api.dry_run_transaction(Bytes::default()).await.unwrap();
+        assert!(!result.is_valid);
+        assert!(result.error.is_some());
+
+        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+        let tx = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
+        let result = eth_api.dry_run_transaction(tx).await.unwrap();
+        assert!(!result.from.is_zero());
+        assert!(result.cost_wei > U256::ZERO);
+        assert!(pool.is_empty(), "dry run must not add the transaction to the pool");
+    }
 }
@@ -2,6 +2,7 @@
 
 use crate::result::{internal_rpc_err, rpc_err};
 use jsonrpsee::{core::Error as RpcError, types::error::INVALID_PARAMS_CODE};
+use reth_interfaces::provider::ProviderError;
 use reth_primitives::{constants::SELECTOR_LEN, Address, Bytes, U256};
 use reth_rpc_types::{error::EthRpcErrorCode, BlockError};
 use reth_transaction_pool::error::{InvalidPoolTransactionError, PoolError};
@@ -27,6 +28,12 @@ pub enum EthApiError {
     UnknownBlockNumber,
     #[error("Invalid block range")]
     InvalidBlockRange,
+    /// Thrown when a requested transaction could not be found
+    #[error("Transaction not found")]
+    TransactionNotFound,
+    /// Thrown when a requested feature is not supported
+    #[error("Unsupported: {0}")]
+    Unsupported(&'static str),
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("Prevrandao not in th EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -49,6 +56,12 @@ pub enum EthApiError {
     /// conflicting `state` and `stateDiff` fields
     #[error("account {0:?} has both 'state' and 'stateDiff'")]
     BothStateAndStateDiffInOverride(Address),
+    /// Thrown when the tracer config or timeout of a `debug_trace*` request is invalid
+    #[error("invalid tracer config")]
+    InvalidTracerConfig,
+    /// Thrown when a trace could not be serialized
+    #[error("failed to serialize trace: {0}")]
+    TraceSerialization(String),
     /// Other internal error
     #[error(transparent)]
     Internal(#[from] reth_interfaces::Error),
@@ -65,17 +78,24 @@ impl From<EthApiError> for RpcError {
             EthApiError::EmptyRawTransactionData |
             EthApiError::UnknownBlockNumber |
             EthApiError::InvalidBlockRange |
+            EthApiError::TransactionNotFound |
+            EthApiError::Unsupported(_) |
             EthApiError::ConflictingRequestGasPrice { .. } |
             EthApiError::ConflictingRequestGasPriceAndTipSet { .. } |
             EthApiError::RequestLegacyGasPriceAndTipSet { .. } |
             EthApiError::Signing(_) |
-            EthApiError::BothStateAndStateDiffInOverride(_) => {
+            EthApiError::BothStateAndStateDiffInOverride(_) |
+            EthApiError::InvalidTracerConfig => {
                 rpc_err(INVALID_PARAMS_CODE, error.to_string(), None)
             }
             EthApiError::InvalidTransaction(err) => err.into(),
+            EthApiError::Internal(reth_interfaces::Error::Provider(
+                ProviderError::StateHistoryPruned { .. } | ProviderError::HistoryAccountRange,
+            )) => rpc_err(EthRpcErrorCode::InvalidInput.code(), error.to_string(), None),
             EthApiError::PoolError(_) |
             EthApiError::PrevrandaoNotSet |
             EthApiError::InvalidBlockData(_) |
+            EthApiError::TraceSerialization(_) |
             EthApiError::Internal(_) => internal_rpc_err(error.to_string()),
         }
     }
@@ -336,7 +356,7 @@ impl From<PoolError> for RpcPoolError {
         match err {
             PoolError::ReplacementUnderpriced(_) => RpcPoolError::ReplaceUnderpriced,
             PoolError::ProtocolFeeCapTooLow(_, _) => RpcPoolError::Underpriced,
-            PoolError::SpammerExceededCapacity(_, _) => RpcPoolError::TxPoolOverflow,
+            PoolError::TooManyTransactions(_, _) => RpcPoolError::TxPoolOverflow,
             PoolError::DiscardedOnInsert(_) => RpcPoolError::TxPoolOverflow,
             PoolError::InvalidTransaction(_, err) => err.into(),
             PoolError::Other(_, err) => RpcPoolError::Other(err),
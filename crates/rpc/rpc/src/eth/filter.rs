@@ -1,21 +1,55 @@
 use crate::{
     eth::{error::EthApiError, logs_utils},
-    result::{internal_rpc_err, rpc_error_with_code, ToRpcResult},
+    result::{rpc_error_with_code, ToRpcResult},
     EthSubscriptionIdProvider,
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
-use reth_primitives::filter::{Filter, FilterBlockOption, FilteredParams};
+use reth_primitives::{
+    filter::{Filter, FilterBlockOption, FilteredParams},
+    TxHash, U256,
+};
 use reth_provider::{BlockProvider, EvmEnvProvider};
 use reth_rpc_api::EthFilterApiServer;
-use reth_rpc_types::{FilterChanges, FilterId, Log};
+use reth_rpc_types::{FilterChanges, FilterId, Log, LogsPageToken, LogsResponse};
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::TransactionPool;
-use std::{collections::HashMap, sync::Arc, time::Instant};
-use tokio::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{mpsc::Receiver, Mutex};
 use tracing::trace;
 
-/// The default maximum of logs in a single response.
-const DEFAULT_MAX_LOGS_IN_RESPONSE: usize = 2_000;
+/// The error code for requests that exceed a limit, see [EIP-1474](https://eips.ethereum.org/EIPS/eip-1474).
+const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The default time after which a filter that was not polled is uninstalled.
+const DEFAULT_STALE_FILTER_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Settings for the [EthFilter]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthFilterConfig {
+    /// Max number of blocks an `eth_getLogs` request can cover.
+    ///
+    /// Default is 2000
+    pub max_blocks_per_filter: u64,
+    /// Max number of logs in a response.
+    ///
+    /// Larger results are an error, unless the `eth_getLogs` request asked for a page of the logs.
+    ///
+    /// Default is 10000
+    pub max_logs_per_response: usize,
+}
+
+impl Default for EthFilterConfig {
+    fn default() -> Self {
+        Self { max_blocks_per_filter: 2_000, max_logs_per_response: 10_000 }
+    }
+}
 
 /// `Eth` filter RPC implementation.
 #[derive(Debug, Clone)]
@@ -26,14 +60,42 @@ pub struct EthFilter<Client, Pool> {
 
 impl<Client, Pool> EthFilter<Client, Pool> {
     /// Creates a new, shareable instance.
+    ///
+    /// The task that uninstalls stale filters is spawned via [tokio::task::spawn]
     pub fn new(client: Client, pool: Pool) -> Self {
+        Self::with_spawner(client, pool, Box::<TokioTaskExecutor>::default())
+    }
+
+    /// Creates a new, shareable instance.
+    ///
+    /// Spawns a task that uninstalls filters that were not polled for
+    /// [`DEFAULT_STALE_FILTER_TTL`].
+    pub fn with_spawner(client: Client, pool: Pool, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self::with_config(client, pool, task_spawner, Default::default())
+    }
+
+    /// Creates a new, shareable instance with the given [EthFilterConfig].
+    ///
+    /// Spawns a task that uninstalls filters that were not polled for
+    /// [`DEFAULT_STALE_FILTER_TTL`].
+    pub fn with_config(
+        client: Client,
+        pool: Pool,
+        task_spawner: Box<dyn TaskSpawner>,
+        config: EthFilterConfig,
+    ) -> Self {
         let inner = EthFilterInner {
             client,
             active_filters: Default::default(),
             pool,
             id_provider: Arc::new(EthSubscriptionIdProvider::default()),
-            max_logs_in_response: DEFAULT_MAX_LOGS_IN_RESPONSE,
+            max_blocks_per_filter: config.max_blocks_per_filter,
+            max_logs_in_response: config.max_logs_per_response,
         };
+        let active_filters = inner.active_filters.clone();
+        task_spawner.spawn(Box::pin(async move {
+            active_filters.clear_stale_filters_every(DEFAULT_STALE_FILTER_TTL).await;
+        }));
         Self { inner: Arc::new(inner) }
     }
 
@@ -61,7 +123,9 @@ where
 
     /// Handler for `eth_newPendingTransactionFilter`
     async fn new_pending_transaction_filter(&self) -> RpcResult<FilterId> {
-        self.inner.install_filter(FilterKind::PendingTransaction).await
+        let receiver = self.inner.pool.pending_transactions_listener();
+        let pending_txs = PendingTransactionsReceiver { txs: Arc::new(Mutex::new(receiver)) };
+        self.inner.install_filter(FilterKind::PendingTransaction(pending_txs)).await
     }
 
     /// Handler for `eth_getFilterChanges`
@@ -84,8 +148,8 @@ where
         };
 
         match kind {
-            FilterKind::PendingTransaction => {
-                return Err(internal_rpc_err("method not implemented"))
+            FilterKind::PendingTransaction(pending_txs) => {
+                Ok(FilterChanges::Hashes(pending_txs.drain().await))
             }
             FilterKind::Block => {
                 let mut block_hashes = Vec::new();
@@ -152,8 +216,8 @@ where
     }
 
     /// Handler for `eth_getLogs`
-    async fn logs(&self, _filter: Filter) -> RpcResult<Vec<Log>> {
-        todo!()
+    async fn logs(&self, filter: Filter) -> RpcResult<LogsResponse> {
+        self.inner.logs_for_filter(filter)
     }
 }
 
@@ -168,6 +232,8 @@ struct EthFilterInner<Client, Pool> {
     active_filters: ActiveFilters,
     /// Provides ids to identify filters
     id_provider: Arc<dyn IdProvider>,
+    /// Maximum number of blocks an `eth_getLogs` request can cover
+    max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a response
     max_logs_in_response: usize,
 }
@@ -193,6 +259,70 @@ where
         Ok(id)
     }
 
+    /// Handler for `eth_getLogs`
+    ///
+    /// Returns an error if the filter covers more than the configured number of blocks.
+    fn logs_for_filter(&self, filter: Filter) -> RpcResult<LogsResponse> {
+        let info = self.client.chain_info().to_rpc_result()?;
+        let (from_block, to_block) = match filter.block_option {
+            FilterBlockOption::Range { from_block, to_block } => {
+                let from_block = from_block
+                    .and_then(|num| info.convert_block_number(num.into()))
+                    .unwrap_or(info.best_number);
+                let to_block = to_block
+                    .and_then(|num| info.convert_block_number(num.into()))
+                    .unwrap_or(info.best_number)
+                    .min(info.best_number);
+                (from_block, to_block)
+            }
+            FilterBlockOption::AtBlockHash(hash) => {
+                let number = self
+                    .client
+                    .block_number(hash)
+                    .to_rpc_result()?
+                    .ok_or(EthApiError::UnknownBlockNumber)?;
+                (number, number)
+            }
+        };
+
+        if to_block.saturating_sub(from_block) >= self.max_blocks_per_filter {
+            return Err(FilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter).into())
+        }
+
+        let Some(page_token) = &filter.page_token else {
+            return self.filter_logs(&filter, from_block, to_block).map(LogsResponse::Logs)
+        };
+
+        // an empty token requests the first page
+        let start = if page_token.is_empty() {
+            LogsPageToken { block_number: from_block, ..Default::default() }
+        } else {
+            LogsPageToken::decode(page_token)
+                .filter(|token| (from_block..=to_block).contains(&token.block_number))
+                .ok_or(FilterError::InvalidPageToken)?
+        };
+
+        let mut logs = Vec::new();
+        self.for_each_block_logs(&filter, start.block_number, to_block, |block_logs| {
+            logs.extend(block_logs.into_iter().filter(|log| {
+                log.block_number != Some(U256::from(start.block_number)) ||
+                    log.log_index >= Some(U256::from(start.log_index))
+            }));
+            Ok(logs.len() <= self.max_logs_in_response)
+        })?;
+
+        let next_page_token = (logs.len() > self.max_logs_in_response).then(|| {
+            let next = logs.drain(self.max_logs_in_response..).next().expect("exists");
+            LogsPageToken {
+                block_number: next.block_number.unwrap_or_default().to(),
+                tx_index: next.transaction_index.unwrap_or_default().to(),
+                log_index: next.log_index.unwrap_or_default().to(),
+            }
+            .encode()
+        });
+        Ok(LogsResponse::Page { logs, next_page_token })
+    }
+
     /// Returns all logs in the given range that match the filter
     ///
     /// Returns an error if:
@@ -200,6 +330,31 @@ where
     ///  - amount of matches exceeds configured limit
     fn filter_logs(&self, filter: &Filter, from_block: u64, to_block: u64) -> RpcResult<Vec<Log>> {
         let mut all_logs = Vec::new();
+        self.for_each_block_logs(filter, from_block, to_block, |block_logs| {
+            all_logs.extend(block_logs);
+
+            // size check
+            if all_logs.len() > self.max_logs_in_response {
+                return Err(FilterError::QueryExceedsMaxResults(self.max_logs_in_response).into())
+            }
+            Ok(true)
+        })?;
+
+        Ok(all_logs)
+    }
+
+    /// Calls `f` with the matching logs of each block in the given range that has any, until `f`
+    /// returns `false`.
+    fn for_each_block_logs<F>(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+        mut f: F,
+    ) -> RpcResult<()>
+    where
+        F: FnMut(Vec<Log>) -> RpcResult<bool>,
+    {
         let filter_params = FilteredParams::new(Some(filter.clone()));
 
         let topics = filter.has_topics().then(|| filter_params.flat_topics.clone());
@@ -222,27 +377,22 @@ where
                     {
                         let block_hash = block.hash_slow();
 
-                        logs_utils::append_matching_block_logs(
-                            &mut all_logs,
+                        let block_logs = logs_utils::matching_block_logs(
                             &filter_params,
                             block_hash,
                             block_number,
                             block.body.into_iter().map(|tx| tx.hash).zip(receipts),
                         );
 
-                        // size check
-                        if all_logs.len() > self.max_logs_in_response {
-                            return Err(FilterError::QueryExceedsMaxResults(
-                                self.max_logs_in_response,
-                            )
-                            .into())
+                        if !f(block_logs)? {
+                            break
                         }
                     }
                 }
             }
         }
 
-        Ok(all_logs)
+        Ok(())
     }
 }
 
@@ -252,6 +402,35 @@ pub struct ActiveFilters {
     inner: Arc<Mutex<HashMap<FilterId, ActiveFilter>>>,
 }
 
+impl ActiveFilters {
+    /// Uninstalls all filters that were not polled within the given duration.
+    async fn clear_stale_filters(&self, ttl: Duration) {
+        let now = Instant::now();
+        self.inner.lock().await.retain(|id, filter| {
+            let is_stale = now.duration_since(filter.last_poll_timestamp) > ttl;
+            if is_stale {
+                trace!(target: "rpc::eth::filter", ?id, "uninstalled stale filter");
+            }
+            !is_stale
+        });
+    }
+
+    /// Uninstalls stale filters once per `ttl`, until all other handles to the filters are
+    /// dropped.
+    async fn clear_stale_filters_every(self, ttl: Duration) {
+        let mut interval = tokio::time::interval(ttl);
+        // the first tick completes immediately
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if Arc::strong_count(&self.inner) == 1 {
+                return
+            }
+            self.clear_stale_filters(ttl).await;
+        }
+    }
+}
+
 /// An installed filter
 #[derive(Debug)]
 struct ActiveFilter {
@@ -267,7 +446,25 @@ struct ActiveFilter {
 enum FilterKind {
     Log(Box<Filter>),
     Block,
-    PendingTransaction,
+    PendingTransaction(PendingTransactionsReceiver),
+}
+
+/// Receives the hashes of the transactions that became pending since the filter was polled last.
+#[derive(Clone, Debug)]
+struct PendingTransactionsReceiver {
+    txs: Arc<Mutex<Receiver<TxHash>>>,
+}
+
+impl PendingTransactionsReceiver {
+    /// Returns all hashes that were received since the last call.
+    async fn drain(&self) -> Vec<TxHash> {
+        let mut txs = self.txs.lock().await;
+        let mut hashes = Vec::new();
+        while let Ok(hash) = txs.try_recv() {
+            hashes.push(hash);
+        }
+        hashes
+    }
 }
 
 /// Errors that can occur in the handler implementation
@@ -277,6 +474,10 @@ pub enum FilterError {
     FilterNotFound(FilterId),
     #[error("Query exceeds max results {0}")]
     QueryExceedsMaxResults(usize),
+    #[error("query exceeds max block range {0}")]
+    QueryExceedsMaxBlocks(u64),
+    #[error("invalid page token")]
+    InvalidPageToken,
 }
 
 // convert the error
@@ -287,9 +488,100 @@ impl From<FilterError> for jsonrpsee::core::Error {
                 jsonrpsee::types::error::INVALID_PARAMS_CODE,
                 "filter not found",
             ),
-            err @ FilterError::QueryExceedsMaxResults(_) => {
+            err @ (FilterError::QueryExceedsMaxResults(_) | FilterError::InvalidPageToken) => {
                 rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
             }
+            err @ FilterError::QueryExceedsMaxBlocks(_) => {
+                rpc_error_with_code(LIMIT_EXCEEDED_CODE, err.to_string())
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Block, Header, Receipt, TransactionSigned, H256};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    #[tokio::test]
+    async fn clear_stale_filters() {
+        let filters = ActiveFilters::default();
+        let active_filter = |last_poll_timestamp| ActiveFilter {
+            block: 0,
+            last_poll_timestamp,
+            kind: FilterKind::Block,
+        };
+        let ttl = Duration::from_secs(60);
+        let (stale, fresh) = (FilterId::Num(0), FilterId::Num(1));
+        filters.inner.lock().await.extend([
+            (stale.clone(), active_filter(Instant::now() - 2 * ttl)),
+            (fresh.clone(), active_filter(Instant::now())),
+        ]);
+
+        filters.clear_stale_filters(ttl).await;
+
+        let filters = filters.inner.lock().await;
+        assert!(!filters.contains_key(&stale));
+        assert!(filters.contains_key(&fresh));
+    }
+
+    #[test]
+    fn paged_logs() {
+        // three blocks with one transaction that emitted two logs each
+        let client = MockEthProvider::default();
+        for number in 0..3 {
+            let hash = H256::from_low_u64_be(number);
+            let header = Header { number, ..Default::default() };
+            let body = vec![TransactionSigned::default()];
+            client.add_block(hash, Block { header, body, ..Default::default() });
+            let logs = vec![Default::default(); 2];
+            client.add_receipts(number, vec![Receipt { logs, ..Default::default() }]);
+        }
+        let filter_inner = |max_blocks_per_filter, max_logs_in_response| EthFilterInner {
+            pool: testing_pool(),
+            client: client.clone(),
+            active_filters: Default::default(),
+            id_provider: Arc::new(EthSubscriptionIdProvider::default()),
+            max_blocks_per_filter,
+            max_logs_in_response,
+        };
+        let filter = |page_token: Option<&str>| Filter {
+            block_option: FilterBlockOption::Range {
+                from_block: Some(0u64.into()),
+                to_block: Some(2u64.into()),
+            },
+            page_token: page_token.map(str::to_string),
+            ..Default::default()
+        };
+
+        let inner = filter_inner(2, 10);
+        assert!(inner.logs_for_filter(filter(None)).is_err());
+
+        let inner = filter_inner(10, 3);
+        assert!(inner.logs_for_filter(filter(None)).is_err());
+
+        let LogsResponse::Page { logs, next_page_token } =
+            inner.logs_for_filter(filter(Some(""))).unwrap()
+        else {
+            panic!("expected a page")
+        };
+        assert_eq!(logs.len(), 3);
+        let next_page_token = next_page_token.expect("more logs");
+        assert_eq!(
+            LogsPageToken::decode(&next_page_token),
+            Some(LogsPageToken { block_number: 1, tx_index: 0, log_index: 1 })
+        );
+
+        let LogsResponse::Page { logs, next_page_token } =
+            inner.logs_for_filter(filter(Some(&next_page_token))).unwrap()
+        else {
+            panic!("expected a page")
+        };
+        assert_eq!(logs.len(), 3);
+        assert_eq!(logs[0].block_number, Some(U256::from(1)));
+        assert_eq!(logs[0].log_index, Some(U256::from(1)));
+        assert_eq!(next_page_token, None);
+    }
+}
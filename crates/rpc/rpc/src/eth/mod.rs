@@ -10,7 +10,10 @@ mod pubsub;
 pub(crate) mod revm_utils;
 mod signer;
 
-pub use api::{EthApi, EthApiSpec, EthTransactions, TransactionSource};
-pub use filter::EthFilter;
+pub use api::{
+    EthApi, EthApiSpec, EthTransactions, FeeHistoryCacheConfig, PriorityFeeConfig,
+    TransactionSource,
+};
+pub use filter::{EthFilter, EthFilterConfig};
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pubsub::EthPubSub;
@@ -4,18 +4,18 @@ use crate::eth::logs_utils;
 use futures::StreamExt;
 use jsonrpsee::{types::SubscriptionResult, SubscriptionSink};
 use reth_interfaces::{events::ChainEventSubscriptions, sync::SyncStateProvider};
-use reth_primitives::{filter::FilteredParams, BlockId, TxHash};
+use reth_primitives::{filter::FilteredParams, BlockId, TransactionKind, TxHash};
 use reth_provider::{BlockProvider, EvmEnvProvider};
 use reth_rpc_api::EthPubSubApiServer;
 use reth_rpc_types::{
     pubsub::{
-        Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
-        SyncStatusMetadata,
+        Params, PendingTransactionFilter, PubSubSyncStatus, SubscriptionKind,
+        SubscriptionResult as EthSubscriptionResult, SyncStatusMetadata,
     },
     Header, Log,
 };
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
 use tokio_stream::{
     wrappers::{ReceiverStream, UnboundedReceiverStream},
     Stream,
@@ -111,8 +111,12 @@ async fn handle_accepted<Client, Pool, Events, Network>(
             accepted_sink.pipe_from_stream(stream).await;
         }
         SubscriptionKind::NewPendingTransactions => {
+            let filter = match params {
+                Some(Params::PendingTransactions(filter)) => Some(filter),
+                _ => None,
+            };
             let stream = pubsub
-                .into_pending_transaction_stream()
+                .into_pending_transaction_stream(filter)
                 .map(EthSubscriptionResult::TransactionHash);
             accepted_sink.pipe_from_stream(stream).await;
         }
@@ -191,9 +195,28 @@ impl<Client, Pool, Events, Network> EthPubSubInner<Client, Pool, Events, Network
 where
     Pool: TransactionPool + 'static,
 {
-    /// Returns a stream that yields all transactions emitted by the txpool.
-    fn into_pending_transaction_stream(self) -> impl Stream<Item = TxHash> {
-        ReceiverStream::new(self.pool.pending_transactions_listener())
+    /// Returns a stream that yields all transactions emitted by the txpool that match the given
+    /// filter.
+    ///
+    /// Transactions that already left the pool when they are filtered are skipped.
+    fn into_pending_transaction_stream(
+        self,
+        filter: Option<PendingTransactionFilter>,
+    ) -> impl Stream<Item = TxHash> {
+        let pool = self.pool;
+        ReceiverStream::new(pool.pending_transactions_listener()).filter(move |hash| {
+            let matches = match &filter {
+                Some(filter) => pool.get(hash).map_or(false, |tx| {
+                    let to = match tx.transaction.kind() {
+                        TransactionKind::Create => None,
+                        TransactionKind::Call(to) => Some(to),
+                    };
+                    filter.matches(tx.sender(), to)
+                }),
+                None => true,
+            };
+            futures::future::ready(matches)
+        })
     }
 }
 
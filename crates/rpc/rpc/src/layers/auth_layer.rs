@@ -173,11 +173,16 @@ mod tests {
     };
 
     use super::AuthLayer;
-    use crate::{layers::jwt_secret::Claims, JwtAuthValidator, JwtError, JwtSecret};
+    use crate::{
+        layers::jwt_secret::Claims, AtomicJwtSecretProvider, AuthAdminApi, JwtAuthValidator,
+        JwtError, JwtSecret,
+    };
+    use reth_rpc_api::AuthAdminApiServer;
 
     const AUTH_PORT: u32 = 8551;
     const AUTH_ADDR: &str = "0.0.0.0";
     const SECRET: &str = "f79ae8046bc11c9927afe911db7143c51a806c4a537cc08e0d37140b0192f430";
+    const ROTATED_SECRET: &str = "4bd4a9566c7d478cf2a71bd7d64b7ca407c2e2b994971dfd2961776ac1420dd1";
 
     #[tokio::test]
     async fn test_jwt_layer() {
@@ -238,34 +243,88 @@ mod tests {
         assert_eq!(body, "JWT decoding error Error(InvalidToken)".to_string());
     }
 
+    #[tokio::test]
+    async fn test_jwt_secret_rotation() {
+        let provider = AtomicJwtSecretProvider::new(JwtSecret::from_hex(SECRET).unwrap());
+        let layer = AuthLayer::new(JwtAuthValidator::with_provider(provider.clone()));
+        let middleware = tower::ServiceBuilder::default().layer(layer);
+        let server = ServerBuilder::default()
+            .set_middleware(middleware)
+            .build("127.0.0.1:0".parse::<SocketAddr>().unwrap())
+            .await
+            .unwrap();
+        let address = format!("http://{}", server.local_addr().unwrap());
+
+        let mut module = RpcModule::new(());
+        module.register_method("greet_melkor", |_, _| Ok("You are the dark lord")).unwrap();
+        module.merge(AuthAdminApi::new(provider).into_rpc()).unwrap();
+        let server = server.start(module).unwrap();
+
+        let old_jwt = jwt(JwtSecret::from_hex(SECRET).unwrap());
+        let new_jwt = jwt(JwtSecret::from_hex(ROTATED_SECRET).unwrap());
+        let greet = r#"{"jsonrpc": "2.0", "method": "greet_melkor", "params": [], "id": 1}"#;
+
+        let (status, _) = post(&address, &new_jwt, greet).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let rotate = format!(
+            r#"{{"jsonrpc": "2.0", "method": "admin_setJwtSecret", "params": ["{ROTATED_SECRET}"], "id": 1}}"#
+        );
+        let (status, body) = post(&address, &old_jwt, &rotate).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains(r#""result":true"#));
+
+        let (status, _) = post(&address, &new_jwt, greet).await;
+        assert_eq!(status, StatusCode::OK);
+        // in-flight requests signed with the old secret are still accepted
+        let (status, _) = post(&address, &old_jwt, greet).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, body) = post(&address, &jwt(JwtSecret::random()), greet).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body, JwtError::InvalidSignature.to_string());
+
+        server.stop().unwrap();
+        server.stopped().await;
+    }
+
     async fn send_request(jwt: Option<String>) -> (StatusCode, String) {
         let server = spawn_server().await;
-        let client = hyper::Client::new();
 
         let jwt = jwt.unwrap_or("".into());
         let address = format!("http://{AUTH_ADDR}:{AUTH_PORT}");
-        let bearer = format!("Bearer {jwt}");
         let body = r#"{"jsonrpc": "2.0", "method": "greet_melkor", "params": [], "id": 1}"#;
+        let (status, body) = post(&address, &jwt, body).await;
+
+        server.stop().unwrap();
+        server.stopped().await;
+
+        (status, body)
+    }
+
+    /// Sends the JSON-RPC request to the address with the JWT as bearer token.
+    async fn post(address: &str, jwt: &str, body: &str) -> (StatusCode, String) {
+        let client = hyper::Client::new();
+        let bearer = format!("Bearer {jwt}");
 
         let req = Request::builder()
             .method(Method::POST)
             .header(header::AUTHORIZATION, bearer)
             .header(header::CONTENT_TYPE, "application/json")
             .uri(address)
-            .body(Body::from(body))
+            .body(Body::from(body.to_string()))
             .unwrap();
 
         let res = client.request(req).await.unwrap();
         let status = res.status();
         let body_bytes = body::to_bytes(res.into_body()).await.unwrap();
         let body = String::from_utf8(body_bytes.to_vec()).expect("response was not valid utf-8");
-
-        server.stop().unwrap();
-        server.stopped().await;
-
         (status, body)
     }
 
+    fn jwt(secret: JwtSecret) -> String {
+        secret.encode(&Claims { iat: to_u64(SystemTime::now()), exp: Some(10000000000) }).unwrap()
+    }
+
     /// Spawn a new RPC server equipped with a JwtLayer auth middleware.
     async fn spawn_server() -> ServerHandle {
         let secret = JwtSecret::from_hex(SECRET).unwrap();
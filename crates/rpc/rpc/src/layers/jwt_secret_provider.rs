@@ -0,0 +1,153 @@
+use crate::{JwtError, JwtSecret};
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// Tokens signed with the previous secret remain valid for this long after a secret rotation, so
+/// that in-flight requests of the consensus layer client don't fail.
+pub const JWT_SECRET_ROTATION_GRACE_PERIOD: Duration = Duration::from_secs(60);
+
+/// Provides the [`JwtSecret`]s that are used to validate JWT tokens.
+///
+/// This allows the secret of a running [`JwtAuthValidator`][crate::JwtAuthValidator] to be
+/// changed.
+pub trait JwtSecretProvider: Send + Sync {
+    /// Returns the secret tokens are currently signed with.
+    fn current_secret(&self) -> JwtSecret;
+
+    /// Returns the secret that was replaced by the current one, if tokens signed with it are
+    /// still accepted.
+    fn previous_secret(&self) -> Option<JwtSecret> {
+        None
+    }
+
+    /// Validates the JWT token against the current secret, and the previous secret if the token
+    /// is not signed with the current one.
+    fn validate(&self, jwt: String) -> Result<(), JwtError> {
+        match self.current_secret().validate(jwt.clone()) {
+            Err(JwtError::InvalidSignature) => match self.previous_secret() {
+                Some(previous) => previous.validate(jwt),
+                None => Err(JwtError::InvalidSignature),
+            },
+            res => res,
+        }
+    }
+}
+
+impl JwtSecretProvider for JwtSecret {
+    fn current_secret(&self) -> JwtSecret {
+        self.clone()
+    }
+}
+
+/// A [`JwtSecretProvider`] whose secret can be rotated at runtime.
+///
+/// This is a cheaply cloneable handle, all clones share the same secret.
+#[derive(Clone)]
+pub struct AtomicJwtSecretProvider {
+    inner: Arc<RwLock<RotatingJwtSecret>>,
+    /// How long tokens signed with the previous secret are still accepted.
+    grace_period: Duration,
+}
+
+/// The secret of an [`AtomicJwtSecretProvider`].
+struct RotatingJwtSecret {
+    current: JwtSecret,
+    /// The replaced secret and when it was replaced.
+    previous: Option<(JwtSecret, Instant)>,
+}
+
+impl AtomicJwtSecretProvider {
+    /// Creates a new provider with the given initial secret and the default
+    /// [`JWT_SECRET_ROTATION_GRACE_PERIOD`].
+    pub fn new(secret: JwtSecret) -> Self {
+        Self::with_grace_period(secret, JWT_SECRET_ROTATION_GRACE_PERIOD)
+    }
+
+    /// Creates a new provider with the given initial secret that accepts tokens signed with a
+    /// replaced secret for the given duration.
+    pub fn with_grace_period(secret: JwtSecret, grace_period: Duration) -> Self {
+        let inner = RotatingJwtSecret { current: secret, previous: None };
+        Self { inner: Arc::new(RwLock::new(inner)), grace_period }
+    }
+
+    /// Replaces the current secret with the given one.
+    ///
+    /// Tokens signed with the replaced secret remain valid for the configured grace period.
+    pub fn rotate(&self, secret: JwtSecret) {
+        let mut inner = self.inner.write().expect("not poisoned");
+        let previous = std::mem::replace(&mut inner.current, secret);
+        inner.previous = Some((previous, Instant::now()));
+    }
+}
+
+impl JwtSecretProvider for AtomicJwtSecretProvider {
+    fn current_secret(&self) -> JwtSecret {
+        self.inner.read().expect("not poisoned").current.clone()
+    }
+
+    fn previous_secret(&self) -> Option<JwtSecret> {
+        let inner = self.inner.read().expect("not poisoned");
+        let (previous, rotated_at) = inner.previous.as_ref()?;
+        (rotated_at.elapsed() <= self.grace_period).then(|| previous.clone())
+    }
+}
+
+impl From<JwtSecret> for AtomicJwtSecretProvider {
+    fn from(secret: JwtSecret) -> Self {
+        Self::new(secret)
+    }
+}
+
+impl std::fmt::Debug for AtomicJwtSecretProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AtomicJwtSecretProvider")
+            .field("grace_period", &self.grace_period)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layers::jwt_secret::Claims;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn token(secret: &JwtSecret) -> String {
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        secret.encode(&Claims { iat, exp: None }).unwrap()
+    }
+
+    #[test]
+    fn rotated_secret_is_used() {
+        let old = JwtSecret::random();
+        let new = JwtSecret::random();
+        let provider = AtomicJwtSecretProvider::new(old.clone());
+        assert!(provider.validate(token(&old)).is_ok());
+        assert!(matches!(provider.validate(token(&new)), Err(JwtError::InvalidSignature)));
+
+        provider.clone().rotate(new.clone());
+        assert!(provider.validate(token(&new)).is_ok());
+        // still accepted during the grace period
+        assert!(provider.validate(token(&old)).is_ok());
+        assert!(matches!(
+            provider.validate(token(&JwtSecret::random())),
+            Err(JwtError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn previous_secret_expires() {
+        let old = JwtSecret::random();
+        let new = JwtSecret::random();
+        let provider = AtomicJwtSecretProvider::with_grace_period(old.clone(), Duration::ZERO);
+
+        provider.rotate(new.clone());
+        std::thread::sleep(Duration::from_millis(1));
+
+        assert!(provider.previous_secret().is_none());
+        assert!(provider.validate(token(&new)).is_ok());
+        assert!(matches!(provider.validate(token(&old)), Err(JwtError::InvalidSignature)));
+    }
+}
@@ -1,7 +1,8 @@
 use http::{header, HeaderMap, Response, StatusCode};
+use std::sync::Arc;
 use tracing::error;
 
-use crate::{AuthValidator, JwtError, JwtSecret};
+use crate::{AuthValidator, JwtError, JwtSecret, JwtSecretProvider};
 
 /// Implements JWT validation logics and integrates
 /// to an Http [`AuthLayer`][crate::layers::AuthLayer]
@@ -9,7 +10,7 @@ use crate::{AuthValidator, JwtError, JwtSecret};
 #[derive(Clone)]
 #[allow(missing_debug_implementations)]
 pub struct JwtAuthValidator {
-    secret: JwtSecret,
+    secret: Arc<dyn JwtSecretProvider>,
 }
 
 impl JwtAuthValidator {
@@ -17,7 +18,13 @@ impl JwtAuthValidator {
     /// Validation logics are implemnted by the `secret`
     /// argument (see [`JwtSecret`]).
     pub fn new(secret: JwtSecret) -> Self {
-        Self { secret }
+        Self::with_provider(secret)
+    }
+
+    /// Creates a new instance of [`JwtAuthValidator`] that validates tokens against the secrets
+    /// of the given [`JwtSecretProvider`].
+    pub fn with_provider(provider: impl JwtSecretProvider + 'static) -> Self {
+        Self { secret: Arc::new(provider) }
     }
 }
 
@@ -2,9 +2,13 @@ use http::{HeaderMap, Response};
 
 mod auth_layer;
 mod jwt_secret;
+mod jwt_secret_provider;
 mod jwt_validator;
 pub use auth_layer::AuthLayer;
 pub use jwt_secret::{JwtError, JwtSecret};
+pub use jwt_secret_provider::{
+    AtomicJwtSecretProvider, JwtSecretProvider, JWT_SECRET_ROTATION_GRACE_PERIOD,
+};
 pub use jwt_validator::JwtAuthValidator;
 
 /// General purpose trait to validate Http Authorization
@@ -18,15 +18,20 @@ pub mod eth;
 mod layers;
 mod net;
 mod trace;
+mod txpool;
 mod web3;
 
-pub use admin::AdminApi;
+pub use admin::{AdminApi, AuthAdminApi};
 pub use debug::DebugApi;
 pub use engine::EngineApi;
 pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider};
-pub use layers::{AuthLayer, AuthValidator, JwtAuthValidator, JwtError, JwtSecret};
+pub use layers::{
+    AtomicJwtSecretProvider, AuthLayer, AuthValidator, JwtAuthValidator, JwtError, JwtSecret,
+    JwtSecretProvider, JWT_SECRET_ROTATION_GRACE_PERIOD,
+};
 pub use net::NetApi;
 pub use trace::TraceApi;
+pub use txpool::TxPoolApi;
 pub use web3::Web3Api;
 
 pub(crate) mod result;
@@ -1,8 +1,9 @@
-use crate::eth::EthApiSpec;
+use crate::{eth::EthApiSpec, result::ToRpcResult};
+use async_trait::async_trait;
 use jsonrpsee::core::RpcResult as Result;
-use reth_network_api::PeersInfo;
-use reth_rpc_api::NetApiServer;
-use reth_rpc_types::PeerCount;
+use reth_network_api::{Peers, PeersInfo};
+use reth_rpc_api::{NetApiServer, NetExperimentalApiServer};
+use reth_rpc_types::{PeerCount, PeerCountByProtocol};
 
 /// `Net` API implementation.
 ///
@@ -45,6 +46,20 @@ where
     }
 }
 
+/// Non-standard net rpc implementation
+#[async_trait]
+impl<Net, Eth> NetExperimentalApiServer for NetApi<Net, Eth>
+where
+    Net: Peers + 'static,
+    Eth: Send + Sync + 'static,
+{
+    /// Handler for `reth_net_peerCountByProtocol`
+    async fn peer_count_by_protocol(&self) -> Result<PeerCountByProtocol> {
+        let peers = self.network.get_peers().await.to_rpc_result()?;
+        Ok(PeerCountByProtocol::from_peers(&peers))
+    }
+}
+
 impl<Net, Eth> std::fmt::Debug for NetApi<Net, Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("NetApi").finish_non_exhaustive()
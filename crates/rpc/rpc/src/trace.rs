@@ -1,12 +1,18 @@
 use crate::{
-    eth::{cache::EthStateCache, error::EthResult, revm_utils::inspect, EthTransactions},
+    eth::{
+        cache::EthStateCache,
+        error::{EthApiError, EthResult},
+        revm_utils::inspect,
+        EthTransactions,
+    },
     result::internal_rpc_err,
 };
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult as Result;
-use reth_primitives::{BlockId, Bytes, H256};
+use reth_primitives::{BlockId, Bytes, Header, H256, U256};
 use reth_provider::{BlockProvider, EvmEnvProvider, StateProviderFactory};
 use reth_revm::{
+    config::{WEI_2ETH, WEI_3ETH, WEI_5ETH},
     database::{State, SubState},
     env::tx_env_with_recovered,
     tracing::{TraceInspectorConfig, TracingInspector},
@@ -14,9 +20,12 @@ use reth_revm::{
 use reth_rpc_api::TraceApiServer;
 use reth_rpc_types::{
     trace::{filter::TraceFilter, parity::*},
-    CallRequest, Index,
+    CallRequest, Index, TransactionInfo,
+};
+use revm::{
+    primitives::{Env, SpecId},
+    DatabaseCommit,
 };
-use revm::primitives::Env;
 use std::collections::HashSet;
 
 /// `trace` API implementation.
@@ -91,6 +100,69 @@ where
             Ok(Some(traces))
         })
     }
+
+    /// Returns the traces of all transactions in the block, followed by the block and uncle
+    /// rewards.
+    ///
+    /// The transactions are replayed in order on top of the state of the parent block.
+    pub async fn trace_block(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let block_hash = match self.client.block_hash_for_id(block_id)? {
+            Some(block_hash) => block_hash,
+            None => return Ok(None),
+        };
+        let block = match self.eth_cache.get_block(block_hash).await? {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+
+        let (cfg, block_env, _) = self.eth_api.evm_env_at(block_hash.into()).await?;
+
+        let transactions = block
+            .body
+            .into_iter()
+            .map(|tx| tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature))
+            .collect::<EthResult<Vec<_>>>()?;
+
+        let header = block.header;
+        let parent = BlockId::from(header.parent_hash);
+
+        let mut traces = self.eth_api.with_state_at(parent, |state| {
+            let mut db = SubState::new(State::new(state));
+            let mut traces = Vec::new();
+
+            for (index, tx) in transactions.iter().enumerate() {
+                let tx_info = TransactionInfo {
+                    hash: Some(tx.hash()),
+                    index: Some(index),
+                    block_hash: Some(block_hash),
+                    block_number: Some(header.number),
+                };
+                let env = Env {
+                    cfg: cfg.clone(),
+                    block: block_env.clone(),
+                    tx: tx_env_with_recovered(tx),
+                };
+                let mut inspector = TracingInspector::new(TraceInspectorConfig::default_parity());
+
+                let (res, _) = inspect(&mut db, env, &mut inspector)?;
+                // subsequent transactions are executed on top of the changes
+                db.commit(res.state);
+
+                traces.extend(
+                    inspector.into_parity_builder().into_localized_transaction_traces_iter(tx_info),
+                );
+            }
+
+            Ok(traces)
+        })?;
+
+        traces.extend(reward_traces(&header, &block.ommers, block_hash, cfg.spec_id));
+
+        Ok(Some(traces))
+    }
 }
 
 #[async_trait]
@@ -149,9 +221,9 @@ where
     /// Handler for `trace_block`
     async fn trace_block(
         &self,
-        _block_id: BlockId,
+        block_id: BlockId,
     ) -> Result<Option<Vec<LocalizedTransactionTrace>>> {
-        Err(internal_rpc_err("unimplemented"))
+        Ok(TraceApi::trace_block(self, block_id).await?)
     }
 
     /// Handler for `trace_filter`
@@ -178,8 +250,100 @@ where
     }
 }
 
+/// Returns the base block reward for the given spec, or `None` after the merge.
+fn block_reward(spec_id: SpecId) -> Option<u128> {
+    if SpecId::enabled(spec_id, SpecId::MERGE) {
+        None
+    } else if SpecId::enabled(spec_id, SpecId::PETERSBURG) {
+        Some(WEI_2ETH)
+    } else if SpecId::enabled(spec_id, SpecId::BYZANTIUM) {
+        Some(WEI_3ETH)
+    } else {
+        Some(WEI_5ETH)
+    }
+}
+
+/// Returns the parity style traces of the block reward and the uncle rewards.
+///
+/// See also <https://github.com/openethereum/openethereum/blob/6c2d392d867b058ff867c4373e40850ca3f96969/crates/ethcore/src/ethereum/ethash.rs#L319-L333>
+fn reward_traces(
+    header: &Header,
+    ommers: &[Header],
+    block_hash: H256,
+    spec_id: SpecId,
+) -> Vec<LocalizedTransactionTrace> {
+    let Some(reward) = block_reward(spec_id) else { return vec![] };
+
+    let reward_trace = |author, value, reward_type| LocalizedTransactionTrace {
+        trace: TransactionTrace {
+            action: Action::Reward(RewardAction { author, value, reward_type }),
+            result: None,
+            trace_address: vec![],
+            subtraces: 0,
+        },
+        transaction_position: None,
+        transaction_hash: None,
+        block_number: Some(header.number),
+        block_hash: Some(block_hash),
+    };
+
+    // the beneficiary receives an additional 1/32 of the reward for every included uncle
+    let block_reward = U256::from(reward + (reward >> 5) * ommers.len() as u128);
+    let mut traces = vec![reward_trace(header.beneficiary, block_reward, RewardType::Block)];
+    traces.extend(ommers.iter().map(|ommer| {
+        let ommer_reward = U256::from(((8 + ommer.number - header.number) as u128 * reward) >> 3);
+        reward_trace(ommer.beneficiary, ommer_reward, RewardType::Uncle)
+    }));
+    traces
+}
+
 impl<Client, Eth> std::fmt::Debug for TraceApi<Client, Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TraceApi").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Address;
+
+    #[test]
+    fn test_reward_traces() {
+        let header = Header { number: 10, beneficiary: Address::random(), ..Default::default() };
+        let ommer = Header { number: 9, beneficiary: Address::random(), ..Default::default() };
+        let block_hash = H256::random();
+
+        let traces =
+            reward_traces(&header, std::slice::from_ref(&ommer), block_hash, SpecId::PETERSBURG);
+        assert_eq!(traces.len(), 2);
+
+        let rewards = traces
+            .iter()
+            .map(|trace| match &trace.trace.action {
+                Action::Reward(reward) => reward.clone(),
+                action => panic!("unexpected action {action:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rewards,
+            vec![
+                RewardAction {
+                    author: header.beneficiary,
+                    value: U256::from(WEI_2ETH + (WEI_2ETH >> 5)),
+                    reward_type: RewardType::Block,
+                },
+                RewardAction {
+                    author: ommer.beneficiary,
+                    value: U256::from(WEI_2ETH * 7 / 8),
+                    reward_type: RewardType::Uncle,
+                },
+            ]
+        );
+        assert!(traces.iter().all(|trace| trace.transaction_hash.is_none() &&
+            trace.block_hash == Some(block_hash) &&
+            trace.block_number == Some(10)));
+
+        assert!(reward_traces(&header, &[], block_hash, SpecId::MERGE).is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use ethers_core::utils::to_checksum;
+use jsonrpsee::core::RpcResult as Result;
+use reth_primitives::{
+    Address, IntoRecoveredTransaction, TransactionKind, TransactionSignedEcRecovered, TxHash, U64,
+};
+use reth_rpc_api::TxPoolApiServer;
+use reth_rpc_types::{Transaction, TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
+use reth_transaction_pool::TransactionPool;
+use std::collections::{BTreeMap, HashSet};
+
+/// Transactions of the pool, keyed by their sender and nonce.
+type TxsBySender<T> = BTreeMap<Address, BTreeMap<String, T>>;
+
+/// `txpool` API implementation.
+///
+/// This type provides the functionality for handling `txpool` related requests.
+pub struct TxPoolApi<Pool> {
+    /// An interface to interact with the pool
+    pool: Pool,
+}
+
+// === impl TxPoolApi ===
+
+impl<Pool> TxPoolApi<Pool> {
+    /// Creates a new instance of `TxPoolApi`.
+    pub fn new(pool: Pool) -> Self {
+        TxPoolApi { pool }
+    }
+}
+
+impl<Pool> TxPoolApi<Pool>
+where
+    Pool: TransactionPool + 'static,
+{
+    /// Returns all transactions in the pool converted with `f`, split into pending and queued
+    /// transactions.
+    fn content<T>(
+        &self,
+        mut f: impl FnMut(TransactionSignedEcRecovered) -> T,
+    ) -> (TxsBySender<T>, TxsBySender<T>) {
+        let mut pending = TxsBySender::new();
+        let mut queued = TxsBySender::new();
+        for sender in self.pool.all_senders() {
+            let pending_hashes = self.pending_hashes(sender);
+            for tx in self.pool.get_transactions_by_sender(sender) {
+                let entry =
+                    if pending_hashes.contains(tx.hash()) { &mut pending } else { &mut queued };
+                entry
+                    .entry(sender)
+                    .or_default()
+                    .insert(tx.nonce().to_string(), f(tx.transaction.to_recovered_transaction()));
+            }
+        }
+        (pending, queued)
+    }
+
+    /// Returns the hashes of the pending transactions of the sender.
+    fn pending_hashes(&self, sender: Address) -> HashSet<TxHash> {
+        self.pool.get_pending_transactions_by_sender(sender).iter().map(|tx| *tx.hash()).collect()
+    }
+}
+
+/// Returns the summary of the transaction in the format of geth's `txpool_inspect`:
+/// `to: value wei + gasLimit gas × gasPrice wei`
+///
+/// The gas price of EIP-1559 transactions is their max fee per gas.
+fn inspect_summary(tx: &TransactionSignedEcRecovered) -> String {
+    let to = match tx.kind() {
+        TransactionKind::Call(to) => to_checksum(&ethers_core::types::H160(to.0), None),
+        TransactionKind::Create => "contract creation".to_string(),
+    };
+    format!("{to}: {} wei + {} gas × {} wei", tx.value(), tx.gas_limit(), tx.max_fee_per_gas())
+}
+
+#[async_trait]
+impl<Pool> TxPoolApiServer for TxPoolApi<Pool>
+where
+    Pool: TransactionPool + 'static,
+{
+    /// Handler for `txpool_contentFrom`
+    async fn txpool_content_from(&self, from: Address) -> Result<TxpoolContentFrom> {
+        let pending = self.pending_hashes(from);
+
+        let mut content = TxpoolContentFrom::default();
+        for tx in self.pool.get_transactions_by_sender(from) {
+            let entry = if pending.contains(tx.hash()) {
+                &mut content.pending
+            } else {
+                &mut content.queued
+            };
+            entry.insert(
+                tx.nonce().to_string(),
+                Transaction::from_recovered(tx.transaction.to_recovered_transaction()),
+            );
+        }
+        Ok(content)
+    }
+
+    /// Handler for `txpool_content`
+    async fn txpool_content(&self) -> Result<TxpoolContent> {
+        let (pending, queued) = self.content(Transaction::from_recovered);
+        Ok(TxpoolContent { pending, queued })
+    }
+
+    /// Handler for `txpool_inspect`
+    async fn txpool_inspect(&self) -> Result<TxpoolInspect> {
+        let (pending, queued) = self.content(|tx| inspect_summary(&tx));
+        Ok(TxpoolInspect { pending, queued })
+    }
+
+    /// Handler for `txpool_status`
+    async fn txpool_status(&self) -> Result<TxpoolStatus> {
+        Ok(TxpoolStatus {
+            pending: U64::from(self.pool.pending_count()),
+            queued: U64::from(self.pool.queued_count()),
+        })
+    }
+}
+
+impl<Pool> std::fmt::Debug for TxPoolApi<Pool> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxPoolApi").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        Signature, Transaction as PrimitiveTransaction, TransactionSigned, TxLegacy,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn inspect_summary_matches_geth() {
+        let summary = |to| {
+            let tx = PrimitiveTransaction::Legacy(TxLegacy {
+                to,
+                value: 1_000_000_000_000_000_000,
+                gas_limit: 21_000,
+                gas_price: 2_000_000_000,
+                ..Default::default()
+            });
+            let tx = TransactionSignedEcRecovered::from_signed_transaction(
+                TransactionSigned::from_transaction_and_signature(tx, Signature::default()),
+                Address::zero(),
+            );
+            inspect_summary(&tx)
+        };
+
+        let to = Address::from_str("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+        assert_eq!(
+            summary(TransactionKind::Call(to)),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed: 1000000000000000000 wei + 21000 gas × 2000000000 wei"
+        );
+        assert_eq!(
+            summary(TransactionKind::Create),
+            "contract creation: 1000000000000000000 wei + 21000 gas × 2000000000 wei"
+        );
+    }
+}
@@ -25,12 +25,15 @@ impl Config {
         &self,
         nat_resolution_method: reth_net_nat::NatResolver,
         peers_file: Option<PathBuf>,
+        ban_list_file: Option<PathBuf>,
     ) -> NetworkConfigBuilder {
         let peer_config = self
             .peers
             .clone()
             .with_basic_nodes_from_file(peers_file)
             .unwrap_or_else(|_| self.peers.clone());
+        let peer_config =
+            peer_config.clone().with_ban_list_from_file(ban_list_file).unwrap_or(peer_config);
         let discv4 =
             Discv4Config::builder().external_ip_resolver(Some(nat_resolution_method)).clone();
         NetworkConfigBuilder::new(rng_secret_key()).peer_config(peer_config).discovery(discv4)
@@ -59,11 +62,20 @@ pub struct HeadersConfig {
     pub commit_threshold: u64,
     /// The maximum number of headers to request from a peer at a time.
     pub downloader_batch_size: u64,
+    /// The minimum number of requests to send concurrently.
+    pub downloader_min_concurrent_requests: usize,
+    /// The maximum number of requests to send concurrently.
+    pub downloader_max_concurrent_requests: usize,
 }
 
 impl Default for HeadersConfig {
     fn default() -> Self {
-        Self { commit_threshold: 10_000, downloader_batch_size: 1000 }
+        Self {
+            commit_threshold: 10_000,
+            downloader_batch_size: 1000,
+            downloader_min_concurrent_requests: 5,
+            downloader_max_concurrent_requests: 150,
+        }
     }
 }
 
@@ -72,6 +84,8 @@ impl From<HeadersConfig> for ReverseHeadersDownloaderBuilder {
         ReverseHeadersDownloaderBuilder::default()
             .request_limit(config.downloader_batch_size)
             .stream_batch_size(config.commit_threshold as usize)
+            .min_concurrent_requests(config.downloader_min_concurrent_requests)
+            .max_concurrent_requests(config.downloader_max_concurrent_requests)
     }
 }
 
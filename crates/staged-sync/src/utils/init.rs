@@ -1,19 +1,30 @@
 use reth_db::{
     cursor::DbCursorRO,
     database::Database,
-    mdbx::{Env, WriteMap},
+    mdbx::{DatabaseConfig, Env, WriteMap},
+    migrations::MigrationRunner,
     tables,
     transaction::{DbTx, DbTxMut},
 };
-use reth_primitives::{Account, ChainSpec, H256};
+use reth_primitives::{keccak256, Account, Bytecode, ChainSpec, StorageEntry, H256, U256};
 use std::{path::Path, sync::Arc};
 use tracing::debug;
 
 /// Opens up an existing database or creates a new one at the specified path.
 pub fn init_db<P: AsRef<Path>>(path: P) -> eyre::Result<Env<WriteMap>> {
+    init_db_with_config(path, DatabaseConfig::default())
+}
+
+/// Opens up an existing database or creates a new one at the specified path with the given
+/// configuration.
+pub fn init_db_with_config<P: AsRef<Path>>(
+    path: P,
+    config: DatabaseConfig,
+) -> eyre::Result<Env<WriteMap>> {
     std::fs::create_dir_all(path.as_ref())?;
-    let db = Env::<WriteMap>::open(path.as_ref(), reth_db::mdbx::EnvKind::RW)?;
+    let db = Env::<WriteMap>::open_with_config(path.as_ref(), reth_db::mdbx::EnvKind::RW, config)?;
     db.create_tables()?;
+    MigrationRunner::new(&db).run()?;
 
     Ok(db)
 }
@@ -22,7 +33,9 @@ pub fn init_db<P: AsRef<Path>>(path: P) -> eyre::Result<Env<WriteMap>> {
 #[derive(Debug, thiserror::Error, PartialEq, Eq, Clone)]
 pub enum InitDatabaseError {
     /// Attempted to reinitialize database with inconsistent genesis block
-    #[error("Genesis hash mismatch: expected {expected}, got {actual}")]
+    #[error(
+        "Database initialized with a different genesis block: expected {expected}, got {actual}"
+    )]
     GenesisHashMismatch {
         /// Expected genesis hash.
         expected: H256,
@@ -58,29 +71,47 @@ pub fn init_genesis<DB: Database>(
 
     drop(tx);
     debug!("Writing genesis block.");
-    let tx = db.tx_mut()?;
-
-    // Insert account state
-    for (address, account) in &genesis.alloc {
-        tx.put::<tables::PlainAccountState>(
-            *address,
-            Account {
-                nonce: account.nonce.unwrap_or_default(),
-                balance: account.balance,
-                bytecode_hash: None,
-            },
-        )?;
-    }
+    // a large genesis allocation can fill the memory map
+    db.update_with_retry(|tx| {
+        // Insert account state
+        for (address, account) in &genesis.alloc {
+            let mut bytecode_hash = None;
+            if let Some(code) = &account.code {
+                let hash = keccak256(code);
+                tx.put::<tables::Bytecodes>(hash, Bytecode::new_raw(code.to_vec().into()))?;
+                bytecode_hash = Some(hash);
+            }
+
+            tx.put::<tables::PlainAccountState>(
+                *address,
+                Account {
+                    nonce: account.nonce.unwrap_or_default(),
+                    balance: account.balance,
+                    bytecode_hash,
+                },
+            )?;
+
+            // Insert storage, skipping the zero slots which are not part of the state
+            if let Some(storage) = &account.storage {
+                for (&key, value) in storage.iter().filter(|(_, value)| !value.is_zero()) {
+                    tx.put::<tables::PlainStorageState>(
+                        *address,
+                        StorageEntry { key, value: U256::from_be_bytes(value.0) },
+                    )?;
+                }
+            }
+        }
 
-    // Insert header
-    tx.put::<tables::CanonicalHeaders>(0, hash)?;
-    tx.put::<tables::HeaderNumbers>(hash, 0)?;
-    tx.put::<tables::BlockBodies>(0, Default::default())?;
-    tx.put::<tables::BlockTransitionIndex>(0, 0)?;
-    tx.put::<tables::HeaderTD>(0, header.difficulty.into())?;
-    tx.put::<tables::Headers>(0, header)?;
+        // Insert header
+        tx.put::<tables::CanonicalHeaders>(0, hash)?;
+        tx.put::<tables::HeaderNumbers>(hash, 0)?;
+        tx.put::<tables::BlockBodies>(0, Default::default())?;
+        tx.put::<tables::BlockTransitionIndex>(0, 0)?;
+        tx.put::<tables::HeaderTD>(0, header.difficulty.into())?;
+        tx.put::<tables::Headers>(0, header.clone())?;
+        Ok(())
+    })?;
 
-    tx.commit()?;
     Ok(hash)
 }
 
@@ -89,11 +120,33 @@ mod tests {
 
     use std::sync::Arc;
 
-    use super::{init_genesis, InitDatabaseError};
-    use reth_db::mdbx::test_utils::create_test_rw_db;
+    use super::{init_db, init_genesis, InitDatabaseError};
+    use reth_db::{
+        cursor::DbDupCursorRO,
+        database::Database,
+        mdbx::test_utils::create_test_rw_db,
+        migrations::{MigrationRunner, CURRENT_VERSION},
+        tables,
+        transaction::DbTx,
+    };
     use reth_primitives::{
-        GOERLI, GOERLI_GENESIS, MAINNET, MAINNET_GENESIS, SEPOLIA, SEPOLIA_GENESIS,
+        keccak256, Address, Bytes, Chain, ChainSpecBuilder, Genesis, GenesisAccount, StorageEntry,
+        GOERLI, GOERLI_GENESIS, H256, MAINNET, MAINNET_GENESIS, SEPOLIA, SEPOLIA_GENESIS, U256,
     };
+    use std::collections::HashMap;
+
+    #[test]
+    fn init_db_creates_tables() {
+        let path = tempfile::TempDir::new().unwrap();
+        let db = init_db(path.path()).unwrap();
+
+        let tx = db.tx().unwrap();
+        for (_, table) in tables::TABLES {
+            assert!(tx.inner.open_db(Some(table)).is_ok(), "{table}");
+        }
+        tx.commit().unwrap();
+        assert_eq!(MigrationRunner::new(&db).version(), Ok(CURRENT_VERSION));
+    }
 
     #[test]
     fn success_init_genesis_mainnet() {
@@ -138,4 +191,43 @@ mod tests {
             }
         )
     }
+
+    #[test]
+    fn success_init_genesis_custom_alloc() {
+        let contract = Address::random();
+        let code = Bytes::from(vec![0x60, 0x00, 0x60, 0x00, 0xf3]);
+        let slot = H256::from_low_u64_be(1);
+        let account = GenesisAccount::default()
+            .with_balance(U256::from(1))
+            .with_code(Some(code.clone()))
+            .with_storage(Some(HashMap::from([
+                (slot, H256::from_low_u64_be(42)),
+                (H256::from_low_u64_be(2), H256::zero()),
+            ])));
+        let chain = ChainSpecBuilder::default()
+            .chain(Chain::Id(1337))
+            .genesis(Genesis::default().extend_accounts([(contract, account)]))
+            .london_activated()
+            .build();
+
+        let db = create_test_rw_db();
+        let genesis_hash = init_genesis(db.clone(), Arc::new(chain.clone())).unwrap();
+        assert_eq!(genesis_hash, chain.genesis_hash());
+
+        let tx = db.tx().unwrap();
+        let code_hash = keccak256(&code);
+        let account = tx.get::<tables::PlainAccountState>(contract).unwrap().unwrap();
+        assert_eq!(account.balance, U256::from(1));
+        assert_eq!(account.bytecode_hash, Some(code_hash));
+        assert!(tx.get::<tables::Bytecodes>(code_hash).unwrap().is_some());
+
+        // zero slots are not written
+        let mut storage = tx.cursor_dup_read::<tables::PlainStorageState>().unwrap();
+        let entries = storage.walk_dup(Some(contract), None).unwrap().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].as_ref().unwrap().1,
+            StorageEntry { key: slot, value: U256::from(42) }
+        );
+    }
 }
@@ -64,6 +64,15 @@ pub fn parse_socket_address(value: &str) -> Result<SocketAddr, eyre::Error> {
     .ok_or_else(|| eyre::eyre!("Could not parse socket address from {}", value))
 }
 
+/// Parse a false positive rate, which has to be between 0 and 1, exclusive.
+pub fn parse_false_positive_rate(value: &str) -> Result<f64, eyre::Error> {
+    let rate: f64 = value.parse()?;
+    if !(rate > 0.0 && rate < 1.0) {
+        eyre::bail!("False positive rate must be between 0 and 1, got {}", value);
+    }
+    Ok(rate)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +87,12 @@ mod tests {
             assert_eq!(socket_addr.port(), 9000);
         }
     }
+
+    #[test]
+    fn parse_false_positive_rates() {
+        assert_eq!(parse_false_positive_rate("0.0001").unwrap(), 0.0001);
+        for value in ["0", "1", "-0.1", "NaN", "rate"] {
+            assert!(parse_false_positive_rate(value).is_err(), "parsed {value}");
+        }
+    }
 }
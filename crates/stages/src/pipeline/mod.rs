@@ -1,7 +1,7 @@
 use crate::{error::*, util::opt, ExecInput, ExecOutput, Stage, StageError, StageId, UnwindInput};
 use metrics::Gauge;
 use reth_db::database::Database;
-use reth_interfaces::sync::{SyncState, SyncStateUpdater};
+use reth_interfaces::sync::{SyncEvent, SyncState, SyncStateUpdater};
 use reth_metrics_derive::Metrics;
 use reth_primitives::BlockNumber;
 use reth_provider::Transaction;
@@ -10,6 +10,7 @@ use std::{
     fmt::{Debug, Formatter},
     ops::Deref,
     sync::Arc,
+    time::Instant,
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing::*;
@@ -80,6 +81,8 @@ pub struct Pipeline<DB: Database, U: SyncStateUpdater> {
     listeners: PipelineEventListeners,
     sync_state_updater: Option<U>,
     progress: PipelineProgress,
+    /// Whether a [SyncEvent::SyncStarted] was emitted that hasn't finished yet.
+    syncing: bool,
     metrics: Metrics,
 }
 
@@ -91,6 +94,7 @@ impl<DB: Database, U: SyncStateUpdater> Default for Pipeline<DB, U> {
             listeners: PipelineEventListeners::default(),
             sync_state_updater: None,
             progress: PipelineProgress::default(),
+            syncing: false,
             metrics: Metrics::default(),
         }
     }
@@ -152,6 +156,7 @@ impl<DB: Database, U: SyncStateUpdater> Pipeline<DB, U> {
                     max_block = ?self.max_block,
                     "Terminating pipeline."
                 );
+                self.finish_sync();
                 return Ok(())
             }
         }
@@ -165,6 +170,9 @@ impl<DB: Database, U: SyncStateUpdater> Pipeline<DB, U> {
     /// up to the block that caused the error.
     async fn run_loop(&mut self, db: &DB) -> Result<ControlFlow, PipelineError> {
         let mut previous_stage = None;
+        // The checkpoint of the first stage that made progress, if this pass starts a new sync
+        let mut sync_start = None;
+        let mut made_progress = false;
         for stage_index in 0..self.stages.len() {
             let stage = &self.stages[stage_index];
             let stage_id = stage.id();
@@ -175,11 +183,14 @@ impl<DB: Database, U: SyncStateUpdater> Pipeline<DB, U> {
                 updater.update_sync_state(state);
             }
 
+            let start_block = if self.syncing || made_progress {
+                None
+            } else {
+                Some(db.view(|tx| stage_id.get_progress(tx))??.unwrap_or_default())
+            };
+
             trace!(target: "sync::pipeline", stage = %stage_id, "Executing stage");
-            let next = self
-                .execute_stage_to_completion(db, previous_stage, stage_index)
-                .instrument(info_span!("execute", stage = %stage_id))
-                .await?;
+            let next = self.execute_stage_to_completion(db, previous_stage, stage_index).await?;
 
             match next {
                 ControlFlow::NoProgress { stage_progress } => {
@@ -187,7 +198,11 @@ impl<DB: Database, U: SyncStateUpdater> Pipeline<DB, U> {
                         self.progress.update(progress);
                     }
                 }
-                ControlFlow::Continue { progress } => self.progress.update(progress),
+                ControlFlow::Continue { progress } => {
+                    made_progress = true;
+                    sync_start = sync_start.or(start_block);
+                    self.progress.update(progress);
+                }
                 ControlFlow::Unwind { target, bad_block } => {
                     // reset the sync state
                     if let Some(ref updater) = self.sync_state_updater {
@@ -202,9 +217,43 @@ impl<DB: Database, U: SyncStateUpdater> Pipeline<DB, U> {
                 Some((stage_id, db.view(|tx| stage_id.get_progress(tx))??.unwrap_or_default()));
         }
 
+        // A pass in which no stage made progress means the node caught up with the chain
+        if made_progress {
+            self.on_sync_progress(sync_start);
+        } else {
+            self.finish_sync();
+        }
+
         Ok(self.progress.next_ctrl())
     }
 
+    /// Emits the [SyncEvent]s for a pass of the pipeline that made progress.
+    ///
+    /// If no sync is ongoing, a new sync that starts at `start_block` is started first.
+    fn on_sync_progress(&mut self, start_block: Option<BlockNumber>) {
+        let Some(ref updater) = self.sync_state_updater else { return };
+        let current_block = self.progress.minimum_progress.unwrap_or_default();
+        let highest_block = self.progress.maximum_progress.unwrap_or(current_block);
+        if let Some(start_block) = start_block.filter(|_| !self.syncing) {
+            self.syncing = true;
+            updater.notify_sync_event(SyncEvent::SyncStarted {
+                start_block,
+                target_block: highest_block,
+            });
+        }
+        updater.notify_sync_event(SyncEvent::SyncProgress { current_block, highest_block });
+    }
+
+    /// Emits [SyncEvent::SyncFinished] if a sync is ongoing.
+    fn finish_sync(&mut self) {
+        if !std::mem::take(&mut self.syncing) {
+            return
+        }
+        if let Some(ref updater) = self.sync_state_updater {
+            updater.notify_sync_event(SyncEvent::SyncFinished);
+        }
+    }
+
     /// Unwind the stages to the target block.
     ///
     /// If the unwind is due to a bad block the number of that block should be specified.
@@ -290,10 +339,30 @@ impl<DB: Database, U: SyncStateUpdater> Pipeline<DB, U> {
             self.listeners
                 .notify(PipelineEvent::Running { stage_id, stage_progress: prev_progress });
 
-            match stage
-                .execute(&mut tx, ExecInput { previous_stage, stage_progress: prev_progress })
-                .await
-            {
+            // Every run of the stage is wrapped in a span that carries the executed block range,
+            // so that its logs can be told apart from the other stages
+            let span = info_span!(
+                target: "sync::pipeline",
+                "stage",
+                name = %stage_id,
+                start_block = %prev_progress.unwrap_or_default(),
+                end_block = field::Empty
+            );
+            let output = async {
+                let started = Instant::now();
+                let output = stage
+                    .execute(&mut tx, ExecInput { previous_stage, stage_progress: prev_progress })
+                    .await;
+                if let Ok(out) = &output {
+                    Span::current().record("end_block", out.stage_progress);
+                }
+                debug!(target: "sync::pipeline", elapsed = ?started.elapsed(), "Stage run finished");
+                output
+            }
+            .instrument(span)
+            .await;
+
+            match output {
                 Ok(out @ ExecOutput { stage_progress, done }) => {
                     made_progress |= stage_progress != prev_progress.unwrap_or_default();
                     info!(
@@ -426,7 +495,11 @@ mod tests {
     use crate::{StageId, UnwindOutput};
     use assert_matches::assert_matches;
     use reth_db::mdbx::{self, test_utils, EnvKind};
-    use reth_interfaces::{consensus, provider::ProviderError, sync::NoopSyncStateUpdate};
+    use reth_interfaces::{
+        consensus,
+        provider::ProviderError,
+        sync::{NoopSyncStateUpdate, SyncStateProvider},
+    };
     use tokio_stream::StreamExt;
     use utils::TestStage;
 
@@ -512,6 +585,61 @@ mod tests {
         );
     }
 
+    /// Emits sync events while the pipeline is syncing.
+    #[tokio::test]
+    async fn run_pipeline_sync_events() {
+        let db = test_utils::create_test_db::<mdbx::WriteMap>(EnvKind::RW);
+        let updater = TestSyncStateUpdater::default();
+
+        let mut pipeline = Pipeline::builder()
+            .add_stage(
+                TestStage::new(StageId("A"))
+                    .add_exec(Ok(ExecOutput { stage_progress: 20, done: true })),
+            )
+            .add_stage(
+                TestStage::new(StageId("B"))
+                    .add_exec(Ok(ExecOutput { stage_progress: 10, done: true })),
+            )
+            .with_max_block(10)
+            .with_sync_state_updater(updater.clone())
+            .build();
+
+        pipeline.run(db).await.unwrap();
+
+        assert_eq!(
+            updater.events(),
+            vec![
+                SyncEvent::SyncStarted { start_block: 0, target_block: 20 },
+                SyncEvent::SyncProgress { current_block: 10, highest_block: 20 },
+                SyncEvent::SyncFinished,
+            ]
+        );
+    }
+
+    /// A [SyncStateUpdater] that records all [SyncEvent]s.
+    #[derive(Debug, Clone, Default)]
+    struct TestSyncStateUpdater(Arc<std::sync::Mutex<Vec<SyncEvent>>>);
+
+    impl TestSyncStateUpdater {
+        fn events(&self) -> Vec<SyncEvent> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl SyncStateProvider for TestSyncStateUpdater {
+        fn is_syncing(&self) -> bool {
+            false
+        }
+    }
+
+    impl SyncStateUpdater for TestSyncStateUpdater {
+        fn update_sync_state(&self, _state: SyncState) {}
+
+        fn notify_sync_event(&self, event: SyncEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
     /// Unwinds a simple pipeline.
     #[tokio::test]
     async fn unwind_pipeline() {
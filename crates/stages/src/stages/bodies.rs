@@ -113,6 +113,8 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
             // Write block
             let block_number = response.block_number();
             let difficulty = response.difficulty();
+            let has_beacon_root_block_transition =
+                response.header().has_beacon_root_block_transition();
 
             let mut has_withdrawals = false;
             match response {
@@ -176,7 +178,8 @@ impl<DB: Database, D: BodyDownloader> Stage<DB> for BodyStage<D> {
                 .ok_or(ProviderError::TotalDifficulty { number: block_number })?
                 .1;
             let has_reward = self.consensus.has_block_reward(td.into(), difficulty);
-            let has_post_block_transition = has_reward || has_withdrawals;
+            let has_post_block_transition =
+                has_reward || has_withdrawals || has_beacon_root_block_transition;
             if has_post_block_transition {
                 transition_id += 1;
             }
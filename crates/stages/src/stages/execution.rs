@@ -12,7 +12,9 @@ use reth_db::{
 use reth_interfaces::provider::ProviderError;
 use reth_primitives::{Address, Block, U256};
 use reth_provider::{
-    post_state::PostState, BlockExecutor, ExecutorFactory, LatestStateProviderRef, Transaction,
+    post_state::PostState,
+    pruner::{get_prune_checkpoint, save_prune_checkpoint, PrunePart},
+    BlockExecutor, ExecutorFactory, LatestStateProviderRef, Transaction,
 };
 use tracing::*;
 
@@ -42,6 +44,9 @@ pub const EXECUTION: StageId = StageId("Execution");
 /// - [tables::AccountChangeSet]
 /// - [tables::StorageChangeSet]
 ///
+/// If the stage only keeps the state history of the most recent blocks, the change sets of older
+/// blocks are not written and the prune checkpoints of the state history are moved forward.
+///
 /// For unwinds we are accessing:
 /// - [tables::BlockBodies] get tx index to know what needs to be unwinded
 /// - [tables::AccountHistory] to remove change set and apply old values to
@@ -54,18 +59,27 @@ pub struct ExecutionStage<EF: ExecutorFactory> {
     pub executor_factory: EF,
     /// Commit threshold
     pub commit_threshold: u64,
+    /// The number of blocks before the tip whose change sets are written, or `None` if the change
+    /// sets of all blocks are written.
+    pub state_history_blocks: Option<u64>,
 }
 
 impl<EF: ExecutorFactory> ExecutionStage<EF> {
     /// Create new execution stage with specified config.
     pub fn new(executor_factory: EF, commit_threshold: u64) -> Self {
-        Self { executor_factory, commit_threshold }
+        Self { executor_factory, commit_threshold, state_history_blocks: None }
     }
 
     /// Create execution stage with executor factory and default commit threshold set to 10_000
     /// blocks
     pub fn new_default_threshold(executor_factory: EF) -> Self {
-        Self { executor_factory, commit_threshold: 10_000 }
+        Self::new(executor_factory, 10_000)
+    }
+
+    /// Only write the change sets of the given number of blocks before the tip.
+    pub fn with_state_history_blocks(mut self, state_history_blocks: Option<u64>) -> Self {
+        self.state_history_blocks = state_history_blocks;
+        self
     }
 
     /// Execute the stage.
@@ -113,6 +127,12 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
 
         let mut executor = self.executor_factory.with_sp(LatestStateProviderRef::new(&**tx));
 
+        // the change sets of the blocks up to this one are not written
+        let prune_to = self
+            .state_history_blocks
+            .and_then(|blocks| input.previous_stage_progress().checked_sub(blocks));
+        let mut pruned = None;
+
         // Fetch transactions, execute them and generate results
         let mut changesets = PostState::default();
         for (header, td, body, ommers, withdrawals) in block_batch.into_iter() {
@@ -157,6 +177,20 @@ impl<EF: ExecutorFactory> ExecutionStage<EF> {
                 )
                 .map_err(|error| StageError::ExecutionError { block: block_number, error })?;
             changesets.extend(changeset);
+
+            if prune_to.map_or(false, |prune_to| block_number <= prune_to) {
+                pruned = Some((block_number, changesets.transitions_count()));
+            }
+        }
+
+        if let Some((block_number, transitions)) = pruned {
+            trace!(target: "sync::stages::execution", to_block = block_number, "Skipping change sets");
+            changesets.discard_changes_before(transitions as u64);
+            for part in [PrunePart::AccountHistory, PrunePart::StorageHistory] {
+                if get_prune_checkpoint(&**tx, part)?.map_or(true, |pruned| pruned < block_number) {
+                    save_prune_checkpoint(&**tx, part, block_number)?;
+                }
+            }
         }
 
         // put execution results to database
@@ -207,6 +241,10 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
     ) -> Result<UnwindOutput, StageError> {
         info!(target: "sync::stages::execution", to_block = input.unwind_to, "Unwinding");
 
+        if tx.is_state_history_pruned(input.unwind_to)? {
+            return Err(ProviderError::StateHistoryPruned { block_number: input.unwind_to }.into())
+        }
+
         // Acquire changeset cursors
         let mut account_changeset = tx.cursor_dup_write::<tables::AccountChangeSet>()?;
         let mut storage_changeset = tx.cursor_dup_write::<tables::StorageChangeSet>()?;
@@ -290,8 +328,9 @@ impl<EF: ExecutorFactory, DB: Database> Stage<DB> for ExecutionStage<EF> {
 mod tests {
     use super::*;
     use crate::test_utils::{TestTransaction, PREV_STAGE_ID};
+    use assert_matches::assert_matches;
     use reth_db::{
-        mdbx::{test_utils::create_test_db, EnvKind, WriteMap},
+        mdbx::{test_utils::create_test_db, Env, EnvKind, WriteMap},
         models::AccountBeforeTx,
     };
     use reth_executor::Factory;
@@ -400,24 +439,18 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn sanity_execute_unwind() {
-        // TODO cleanup the setup after https://github.com/paradigmxyz/reth/issues/332
-        // is merged as it has similar framework
-
-        let state_db = create_test_db::<WriteMap>(EnvKind::RW);
-        let mut tx = Transaction::new(state_db.as_ref()).unwrap();
-        let input = ExecInput {
-            previous_stage: Some((PREV_STAGE_ID, 1)),
-            /// The progress of this stage the last time it was executed.
-            stage_progress: None,
-        };
+    /// Inserts the genesis block and block 1 of the execution tests, and the pre state of block 1.
+    ///
+    /// Returns the accounts of the pre state.
+    fn insert_block_with_pre_state(
+        tx: &mut Transaction<'_, Env<WriteMap>>,
+    ) -> [(Address, Account); 2] {
         let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
         let genesis = SealedBlock::decode(&mut genesis_rlp).unwrap();
         let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
         let block = SealedBlock::decode(&mut block_rlp).unwrap();
         insert_canonical_block(tx.deref_mut(), genesis, None, true).unwrap();
-        insert_canonical_block(tx.deref_mut(), block.clone(), None, true).unwrap();
+        insert_canonical_block(tx.deref_mut(), block, None, true).unwrap();
         tx.commit().unwrap();
 
         // variables
@@ -436,6 +469,23 @@ mod tests {
         db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
         tx.commit().unwrap();
 
+        [(acc1, acc1_info), (acc2, acc2_info)]
+    }
+
+    #[tokio::test]
+    async fn sanity_execute_unwind() {
+        // TODO cleanup the setup after https://github.com/paradigmxyz/reth/issues/332
+        // is merged as it has similar framework
+
+        let state_db = create_test_db::<WriteMap>(EnvKind::RW);
+        let mut tx = Transaction::new(state_db.as_ref()).unwrap();
+        let input = ExecInput {
+            previous_stage: Some((PREV_STAGE_ID, 1)),
+            /// The progress of this stage the last time it was executed.
+            stage_progress: None,
+        };
+        let [(acc1, acc1_info), (acc2, acc2_info)] = insert_block_with_pre_state(&mut tx);
+
         // execute
         let mut execution_stage = stage();
         let _ = execution_stage.execute(&mut tx, input).await.unwrap();
@@ -470,6 +520,42 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn skip_changesets_of_old_blocks() {
+        let state_db = create_test_db::<WriteMap>(EnvKind::RW);
+        let mut tx = Transaction::new(state_db.as_ref()).unwrap();
+        let input = ExecInput {
+            previous_stage: Some((PREV_STAGE_ID, 1)),
+            /// The progress of this stage the last time it was executed.
+            stage_progress: None,
+        };
+        insert_block_with_pre_state(&mut tx);
+
+        // only the state of the tip is kept, so the change sets of block 1 are not written
+        let mut execution_stage = stage().with_state_history_blocks(Some(0));
+        let _ = execution_stage.execute(&mut tx, input).await.unwrap();
+        tx.commit().unwrap();
+
+        let db_tx = tx.deref();
+        let miner_acc = H160(hex!("2adc25665018aa1fe0e6bc666dac8fc2697ff9ba"));
+        assert!(db_tx.get::<tables::PlainAccountState>(miner_acc).unwrap().is_some());
+        assert_eq!(db_tx.cursor_read::<tables::AccountChangeSet>().unwrap().first(), Ok(None));
+        assert_eq!(db_tx.cursor_read::<tables::StorageChangeSet>().unwrap().first(), Ok(None));
+        assert_eq!(get_prune_checkpoint(db_tx, PrunePart::AccountHistory), Ok(Some(1)));
+        assert_eq!(get_prune_checkpoint(db_tx, PrunePart::StorageHistory), Ok(Some(1)));
+
+        // the state before block 1 can't be restored
+        let err = execution_stage
+            .unwind(&mut tx, UnwindInput { stage_progress: 1, unwind_to: 0, bad_block: None })
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            StageError::DatabaseIntegrity(ProviderError::StateHistoryPruned { block_number: 0 })
+        );
+        assert!(tx.deref().get::<tables::PlainAccountState>(miner_acc).unwrap().is_some());
+    }
+
     #[tokio::test]
     async fn test_selfdestruct() {
         let test_tx = TestTransaction::default();
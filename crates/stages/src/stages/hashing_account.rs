@@ -7,7 +7,7 @@ use reth_db::{
     transaction::{DbTx, DbTxMut},
 };
 use reth_primitives::{keccak256, AccountHashingCheckpoint};
-use reth_provider::Transaction;
+use reth_provider::{ProviderError, Transaction};
 use std::{collections::BTreeMap, fmt::Debug, ops::Range};
 use tracing::*;
 
@@ -173,8 +173,12 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
         // if there are more blocks then threshold it is faster to go over Plain state and hash all
         // account otherwise take changesets aggregate the sets and apply hashing to
         // AccountHashing table. Also, if we start from genesis, we need to hash from scratch, as
-        // genesis accounts are not in changeset.
-        if to_transition - from_transition > self.clean_threshold || stage_progress == 0 {
+        // genesis accounts are not in changeset. The same applies if the changesets of the range
+        // have been pruned.
+        if to_transition - from_transition > self.clean_threshold ||
+            stage_progress == 0 ||
+            tx.is_state_history_pruned(stage_progress)?
+        {
             let mut checkpoint = self.get_checkpoint(tx)?;
 
             if checkpoint.address.is_none() ||
@@ -252,6 +256,9 @@ impl<DB: Database> Stage<DB> for AccountHashingStage {
     ) -> Result<UnwindOutput, StageError> {
         // There is no threshold on account unwind, we will always take changesets and
         // apply past values to HashedAccount table.
+        if tx.is_state_history_pruned(input.unwind_to)? {
+            return Err(ProviderError::StateHistoryPruned { block_number: input.unwind_to }.into())
+        }
 
         let from_transition_rev = tx.get_block_transition(input.unwind_to)?;
         let to_transition_rev = tx.get_block_transition(input.stage_progress)?;
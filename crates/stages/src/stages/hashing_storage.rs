@@ -9,7 +9,7 @@ use reth_db::{
     transaction::{DbTx, DbTxMut},
 };
 use reth_primitives::{keccak256, Address, StorageEntry, StorageHashingCheckpoint};
-use reth_provider::Transaction;
+use reth_provider::{ProviderError, Transaction};
 use std::{collections::BTreeMap, fmt::Debug};
 use tracing::*;
 
@@ -93,8 +93,12 @@ impl<DB: Database> Stage<DB> for StorageHashingStage {
         // if there are more blocks then threshold it is faster to go over Plain state and hash all
         // account otherwise take changesets aggregate the sets and apply hashing to
         // AccountHashing table. Also, if we start from genesis, we need to hash from scratch, as
-        // genesis accounts are not in changeset, along with their storages.
-        if to_transition - from_transition > self.clean_threshold || stage_progress == 0 {
+        // genesis accounts are not in changeset, along with their storages. The same applies if
+        // the changesets of the range have been pruned.
+        if to_transition - from_transition > self.clean_threshold ||
+            stage_progress == 0 ||
+            tx.is_state_history_pruned(stage_progress)?
+        {
             let mut checkpoint = self.get_checkpoint(tx)?;
 
             if checkpoint.address.is_none() ||
@@ -204,6 +208,10 @@ impl<DB: Database> Stage<DB> for StorageHashingStage {
         tx: &mut Transaction<'_, DB>,
         input: UnwindInput,
     ) -> Result<UnwindOutput, StageError> {
+        if tx.is_state_history_pruned(input.unwind_to)? {
+            return Err(ProviderError::StateHistoryPruned { block_number: input.unwind_to }.into())
+        }
+
         let from_transition_rev = tx.get_block_transition(input.unwind_to)?;
         let to_transition_rev = tx.get_block_transition(input.stage_progress)?;
 
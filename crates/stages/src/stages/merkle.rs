@@ -109,9 +109,13 @@ impl<DB: Database> Stage<DB> for MerkleStage {
         let trie_root = if from_transition == to_transition {
             block_root
         } else {
-            let res = if to_transition - from_transition > threshold || stage_progress == 0 {
+            let res = if to_transition - from_transition > threshold ||
+                stage_progress == 0 ||
+                tx.is_state_history_pruned(stage_progress)?
+            {
                 debug!(target: "sync::stages::merkle::exec", current = ?stage_progress, target = ?previous_stage_progress, "Rebuilding trie");
-                // if there are more blocks than threshold it is faster to rebuild the trie
+                // if there are more blocks than threshold it is faster to rebuild the trie, and
+                // it has to be rebuilt if the changesets of the blocks have been pruned
                 let mut loader = DBTrieLoader::new(tx.deref_mut());
                 loader.calculate_root().map_err(|e| StageError::Fatal(Box::new(e)))?
             } else {
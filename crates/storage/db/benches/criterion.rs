@@ -6,6 +6,7 @@ use criterion::{
 use pprof::criterion::{Output, PProfProfiler};
 use reth_db::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    mdbx::batch::BatchWriter,
     tables::*,
 };
 use std::time::Instant;
@@ -120,7 +121,8 @@ where
     });
 }
 
-/// Measures `SeqWrite`, `RandomWrite`, `SeqRead` and `RandomRead` using `cursor` and `tx.put`.
+/// Measures `SeqWrite`, `RandomWrite`, `RandomBatchWrite`, `SeqRead` and `RandomRead` using
+/// `cursor` and `tx.put`.
 fn measure_table_db<T>(group: &mut BenchmarkGroup<WallTime>)
 where
     T: Table + Default,
@@ -177,6 +179,29 @@ where
         )
     });
 
+    group.bench_function(format!("{}.RandomBatchWrite", T::NAME), |b| {
+        b.iter_with_setup(
+            || {
+                // Reset DB
+                let _ = std::fs::remove_dir_all(bench_db_path);
+                (input, create_test_db_with_path::<WriteMap>(EnvKind::RW, bench_db_path))
+            },
+            |(input, db)| {
+                // Create TX
+                let writer = BatchWriter::new(db.tx_mut().expect("tx"));
+
+                black_box({
+                    for index in RANDOM_INDEXES {
+                        let (k, _, v, _) = input.get(index).unwrap().clone();
+                        writer.put::<T>(k, v).expect("submit");
+                    }
+
+                    writer.commit().unwrap()
+                });
+            },
+        )
+    });
+
     group.bench_function(format!("{}.SeqRead", T::NAME), |b| {
         let db = set_up_db::<T>(bench_db_path, input);
 
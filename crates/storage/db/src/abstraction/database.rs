@@ -52,6 +52,23 @@ pub trait Database: for<'a> DatabaseGAT<'a> {
 
         Ok(res)
     }
+
+    /// Takes a function and passes a write-read transaction into it, making sure it's committed in
+    /// the end of the execution.
+    ///
+    /// Databases that can run out of space abort the transaction, make room and run the function
+    /// again if it fails because the database is full. By default, the function is only run once.
+    fn update_with_retry<T, F>(&self, mut f: F) -> Result<T, Error>
+    where
+        F: FnMut(&<Self as DatabaseGAT<'_>>::TXMut) -> Result<T, Error>,
+    {
+        let tx = self.tx_mut()?;
+
+        let res = f(&tx)?;
+        tx.commit()?;
+
+        Ok(res)
+    }
 }
 
 // Generic over Arc
@@ -68,6 +85,13 @@ impl<DB: Database> Database for Arc<DB> {
     fn tx_mut(&self) -> Result<<Self as DatabaseGAT<'_>>::TXMut, Error> {
         <DB as Database>::tx_mut(self)
     }
+
+    fn update_with_retry<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        F: FnMut(&<Self as DatabaseGAT<'_>>::TXMut) -> Result<T, Error>,
+    {
+        <DB as Database>::update_with_retry(self, f)
+    }
 }
 
 // Generic over reference
@@ -84,4 +108,11 @@ impl<DB: Database> Database for &DB {
     fn tx_mut(&self) -> Result<<Self as DatabaseGAT<'_>>::TXMut, Error> {
         <DB as Database>::tx_mut(self)
     }
+
+    fn update_with_retry<T, F>(&self, f: F) -> Result<T, Error>
+    where
+        F: FnMut(&<Self as DatabaseGAT<'_>>::TXMut) -> Result<T, Error>,
+    {
+        <DB as Database>::update_with_retry(self, f)
+    }
 }
@@ -0,0 +1,316 @@
+//! Write buffer for MDBX transactions.
+
+use super::{cursor::Cursor, tx::Tx};
+use crate::{
+    table::{Compress, Decompress, DupSort, Encode, Table},
+    tables::{PlainAccountState, TableType, TABLES},
+    transaction::{DbTx, DbTxGAT, DbTxMut, DbTxMutGAT},
+    Error,
+};
+use reth_libmdbx::{EnvironmentKind, WriteFlags, RW};
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, MutexGuard},
+};
+
+/// The default size of the buffered writes after which they are written to the transaction.
+pub const DEFAULT_MAX_BUFFER_BYTES: usize = 256 * 1024 * 1024;
+
+/// Writes that were not yet written to the transaction.
+#[derive(Debug, Default)]
+struct Buffer {
+    /// The latest value of each written entry by table and encoded key, `None` if it was deleted.
+    writes: BTreeMap<(&'static str, Vec<u8>), Option<Vec<u8>>>,
+    /// The size of the buffered keys and values in bytes.
+    size: usize,
+}
+
+/// A read-write transaction that coalesces writes in memory.
+///
+/// Puts and deletes are buffered, so repeated writes of the same entry only reach MDBX once, and
+/// are written sorted by table and key when the writer is committed, which turns random writes
+/// into sequential ones. Once the buffer exceeds [`BatchWriter::with_max_buffer_bytes`], the
+/// buffered writes are written to the transaction early, where MDBX can spill them to disk.
+///
+/// Reads consult the buffer first. Cursors operate on the transaction, so the buffer is written
+/// to the transaction before a cursor is created. Writes to [DupSort] tables are not buffered.
+#[derive(Debug)]
+pub struct BatchWriter<'tx, E: EnvironmentKind> {
+    /// The transaction the writes are flushed to.
+    tx: Tx<'tx, RW, E>,
+    /// The buffered writes.
+    buffer: Mutex<Buffer>,
+    /// The size of the buffer after which it's flushed.
+    max_buffer_bytes: usize,
+}
+
+impl<'tx, E: EnvironmentKind> BatchWriter<'tx, E> {
+    /// Creates a new writer that buffers the writes to the given transaction.
+    pub fn new(tx: Tx<'tx, RW, E>) -> Self {
+        Self { tx, buffer: Default::default(), max_buffer_bytes: DEFAULT_MAX_BUFFER_BYTES }
+    }
+
+    /// Sets the size of the buffered writes after which they are written to the transaction.
+    pub fn with_max_buffer_bytes(mut self, max_buffer_bytes: usize) -> Self {
+        self.max_buffer_bytes = max_buffer_bytes;
+        self
+    }
+
+    /// Returns the size of the buffered writes in bytes.
+    pub fn buffer_bytes(&self) -> usize {
+        self.buffer().size
+    }
+
+    /// Writes all buffered writes to the transaction, sorted by table and key.
+    ///
+    /// Written accounts are added to the account filter of the transaction, if any.
+    pub fn flush(&self) -> Result<(), Error> {
+        let Buffer { writes, .. } = std::mem::take(&mut *self.buffer());
+        for ((table, key), value) in writes {
+            match value {
+                Some(value) => {
+                    if let Some(filter) = &self.tx.account_filter {
+                        if table == PlainAccountState::NAME {
+                            filter.insert::<PlainAccountState>(&key);
+                        }
+                    }
+                    let db =
+                        self.tx.inner.open_db(Some(table)).map_err(|e| Error::Write(e.into()))?;
+                    self.tx
+                        .inner
+                        .put(&db, key, value, WriteFlags::UPSERT)
+                        .map_err(|e| Error::Write(e.into()))?;
+                }
+                None => {
+                    let db =
+                        self.tx.inner.open_db(Some(table)).map_err(|e| Error::Delete(e.into()))?;
+                    self.tx.inner.del(&db, key, None).map_err(|e| Error::Delete(e.into()))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Flushes the buffered writes and returns the transaction.
+    pub fn into_inner(self) -> Result<Tx<'tx, RW, E>, Error> {
+        self.flush()?;
+        Ok(self.tx)
+    }
+
+    fn buffer(&self) -> MutexGuard<'_, Buffer> {
+        self.buffer.lock().expect("not poisoned")
+    }
+
+    /// Buffers the write of an entry and flushes the buffer if it's full.
+    fn write<T: Table>(&self, key: Vec<u8>, value: Option<Vec<u8>>) -> Result<(), Error> {
+        let key_len = key.len();
+        let mut buffer = self.buffer();
+        buffer.size += key_len + value.as_ref().map_or(0, Vec::len);
+        if let Some(old) = buffer.writes.insert((T::NAME, key), value) {
+            buffer.size -= key_len + old.map_or(0, |old| old.len());
+        }
+
+        let full = buffer.size > self.max_buffer_bytes;
+        drop(buffer);
+        if full {
+            self.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns `true` if the table with the given name is a [DupSort] table.
+fn is_dup_sort(table: &str) -> bool {
+    TABLES
+        .iter()
+        .any(|(table_type, name)| *name == table && matches!(table_type, TableType::DupSort))
+}
+
+impl<'a, E: EnvironmentKind> DbTxGAT<'a> for BatchWriter<'_, E> {
+    type Cursor<T: Table> = Cursor<'a, RW, T>;
+    type DupCursor<T: DupSort> = Cursor<'a, RW, T>;
+}
+
+impl<'a, E: EnvironmentKind> DbTxMutGAT<'a> for BatchWriter<'_, E> {
+    type CursorMut<T: Table> = Cursor<'a, RW, T>;
+    type DupCursorMut<T: DupSort> = Cursor<'a, RW, T>;
+}
+
+impl<'tx, E: EnvironmentKind> DbTx<'tx> for BatchWriter<'tx, E> {
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<T::Value>, Error> {
+        let key = key.encode().as_ref().to_vec();
+        match self.buffer().writes.get(&(T::NAME, key.clone())) {
+            Some(Some(value)) => return T::Value::decompress(value.clone()).map(Some),
+            Some(None) => return Ok(None),
+            None => {}
+        }
+        self.tx.get_encoded::<T>(&key)
+    }
+
+    fn commit(self) -> Result<bool, Error> {
+        self.into_inner()?.commit()
+    }
+
+    fn drop(self) {
+        drop(self.tx)
+    }
+
+    fn cursor_read<T: Table>(&self) -> Result<<Self as DbTxGAT<'_>>::Cursor<T>, Error> {
+        self.flush()?;
+        self.tx.new_cursor()
+    }
+
+    fn cursor_dup_read<T: DupSort>(&self) -> Result<<Self as DbTxGAT<'_>>::DupCursor<T>, Error> {
+        self.flush()?;
+        self.tx.new_cursor()
+    }
+}
+
+impl<'tx, E: EnvironmentKind> DbTxMut<'tx> for BatchWriter<'tx, E> {
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), Error> {
+        if is_dup_sort(T::NAME) {
+            return self.tx.put::<T>(key, value)
+        }
+
+        let key = key.encode().as_ref().to_vec();
+        self.write::<T>(key, Some(value.compress().as_ref().to_vec()))
+    }
+
+    fn delete<T: Table>(&self, key: T::Key, value: Option<T::Value>) -> Result<bool, Error> {
+        // deleting a specific value needs the current value, so it's not buffered
+        if is_dup_sort(T::NAME) || value.is_some() {
+            self.flush()?;
+            return self.tx.delete::<T>(key, value)
+        }
+
+        let key = key.encode().as_ref().to_vec();
+        let existed = match self.buffer().writes.get(&(T::NAME, key.clone())) {
+            Some(value) => value.is_some(),
+            None => self.tx.get_encoded::<T>(&key)?.is_some(),
+        };
+        self.write::<T>(key, None)?;
+        Ok(existed)
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), Error> {
+        let mut buffer = self.buffer();
+        let mut size = buffer.size;
+        buffer.writes.retain(|(table, key), value| {
+            if *table != T::NAME {
+                return true
+            }
+            size -= key.len() + value.as_ref().map_or(0, Vec::len);
+            false
+        });
+        buffer.size = size;
+        drop(buffer);
+
+        self.tx.clear::<T>()
+    }
+
+    fn cursor_write<T: Table>(&self) -> Result<<Self as DbTxMutGAT<'_>>::CursorMut<T>, Error> {
+        self.flush()?;
+        self.tx.new_cursor()
+    }
+
+    fn cursor_dup_write<T: DupSort>(
+        &self,
+    ) -> Result<<Self as DbTxMutGAT<'_>>::DupCursorMut<T>, Error> {
+        self.flush()?;
+        self.tx.new_cursor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cursor::DbCursorRO,
+        database::Database,
+        mdbx::{
+            test_utils::{
+                create_test_rw_db, ERROR_DB_CREATION, ERROR_TABLE_CREATION, ERROR_TEMPDIR,
+            },
+            DatabaseConfig, Env, EnvKind,
+        },
+        tables::{CanonicalHeaders, PlainStorageState},
+    };
+    use reth_libmdbx::WriteMap;
+    use reth_primitives::{Account, Address, StorageEntry, H256, U256};
+    use tempfile::TempDir;
+
+    #[test]
+    fn buffers_writes_until_commit() {
+        let db = create_test_rw_db();
+        let writer = BatchWriter::new(db.tx_mut().unwrap());
+
+        for number in (0..10u64).rev() {
+            writer.put::<CanonicalHeaders>(number, H256::from_low_u64_be(number)).unwrap();
+        }
+        writer.put::<CanonicalHeaders>(3, H256::zero()).unwrap();
+        assert_eq!(writer.delete::<CanonicalHeaders>(5, None), Ok(true));
+        assert_eq!(writer.delete::<CanonicalHeaders>(5, None), Ok(false));
+        assert_eq!(writer.buffer_bytes(), 9 * (8 + 32) + 8);
+
+        // reads see the buffered writes, while the transaction doesn't
+        assert_eq!(writer.get::<CanonicalHeaders>(3), Ok(Some(H256::zero())));
+        assert_eq!(writer.get::<CanonicalHeaders>(5), Ok(None));
+        assert_eq!(writer.tx.get::<CanonicalHeaders>(3), Ok(None));
+
+        // dup sorted tables are written through
+        let entry = StorageEntry { key: H256::zero(), value: U256::from(1) };
+        writer.put::<PlainStorageState>(Address::zero(), entry).unwrap();
+        assert_eq!(writer.tx.get::<PlainStorageState>(Address::zero()), Ok(Some(entry)));
+
+        writer.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let numbers = tx
+            .cursor_read::<CanonicalHeaders>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![0, 1, 2, 3, 4, 6, 7, 8, 9]);
+        assert_eq!(tx.get::<CanonicalHeaders>(3), Ok(Some(H256::zero())));
+    }
+
+    #[test]
+    fn flushes_full_buffer() {
+        let db = create_test_rw_db();
+        let writer = BatchWriter::new(db.tx_mut().unwrap()).with_max_buffer_bytes(100);
+
+        writer.put::<CanonicalHeaders>(0, H256::zero()).unwrap();
+        writer.put::<CanonicalHeaders>(1, H256::zero()).unwrap();
+        assert_eq!(writer.buffer_bytes(), 80);
+
+        writer.put::<CanonicalHeaders>(2, H256::zero()).unwrap();
+        assert_eq!(writer.buffer_bytes(), 0);
+        assert_eq!(writer.tx.get::<CanonicalHeaders>(2), Ok(Some(H256::zero())));
+    }
+
+    #[test]
+    fn updates_account_filter() {
+        let path = TempDir::new().expect(ERROR_TEMPDIR).into_path();
+        let config = DatabaseConfig { account_filter_fp_rate: Some(0.0001), ..Default::default() };
+        let db =
+            Env::<WriteMap>::open_with_config(&path, EnvKind::RW, config).expect(ERROR_DB_CREATION);
+        db.create_tables().expect(ERROR_TABLE_CREATION);
+
+        let accounts = (0..100u64)
+            .map(|nonce| (Address::from_low_u64_be(nonce), Account { nonce, ..Default::default() }))
+            .collect::<Vec<_>>();
+        let writer = BatchWriter::new(db.tx_mut().unwrap()).with_max_buffer_bytes(1000);
+        for (address, account) in &accounts {
+            writer.put::<PlainAccountState>(*address, *account).unwrap();
+        }
+        writer.commit().unwrap();
+
+        // the filter doesn't rule out any of the written accounts
+        let tx = db.tx().unwrap();
+        for (address, account) in accounts {
+            assert_eq!(tx.get::<PlainAccountState>(address), Ok(Some(account)));
+        }
+    }
+}
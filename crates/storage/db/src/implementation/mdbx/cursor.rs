@@ -1,6 +1,6 @@
 //! Cursor wrapper for libmdbx-sys.
 
-use std::{borrow::Cow, collections::Bound, marker::PhantomData, ops::RangeBounds};
+use std::{borrow::Cow, collections::Bound, marker::PhantomData, ops::RangeBounds, sync::Arc};
 
 use crate::{
     common::{PairResult, ValueOnlyResult},
@@ -10,6 +10,7 @@ use crate::{
     },
     table::{Compress, DupSort, Encode, Table},
     tables::utils::*,
+    utils::BloomAccountFilter,
     Error,
 };
 use reth_libmdbx::{self, Error as MDBXError, TransactionKind, WriteFlags, RO, RW};
@@ -26,6 +27,8 @@ pub struct Cursor<'tx, K: TransactionKind, T: Table> {
     pub inner: reth_libmdbx::Cursor<'tx, K>,
     /// Table name as is inside the database.
     pub table: &'static str,
+    /// The filter of existing accounts that is updated on writes, if enabled.
+    pub(crate) account_filter: Option<Arc<BloomAccountFilter>>,
     /// Phantom data to enforce encoding/decoding.
     pub _dbi: std::marker::PhantomData<T>,
 }
@@ -206,27 +209,42 @@ impl<'tx, K: TransactionKind, T: DupSort> DbDupCursorRO<'tx, T> for Cursor<'tx,
     }
 }
 
+impl<'tx, K: TransactionKind, T: Table> Cursor<'tx, K, T> {
+    /// Records a written key in the filter of existing accounts.
+    fn record_write(&self, key: &[u8]) {
+        if let Some(filter) = &self.account_filter {
+            filter.insert::<T>(key);
+        }
+    }
+}
+
 impl<'tx, T: Table> DbCursorRW<'tx, T> for Cursor<'tx, RW, T> {
     /// Database operation that will update an existing row if a specified value already
     /// exists in a table, and insert a new row if the specified value doesn't already exist
     fn upsert(&mut self, key: T::Key, value: T::Value) -> Result<(), Error> {
         // Default `WriteFlags` is UPSERT
+        let key = key.encode();
+        self.record_write(key.as_ref());
         self.inner
-            .put(key.encode().as_ref(), value.compress().as_ref(), WriteFlags::UPSERT)
+            .put(key.as_ref(), value.compress().as_ref(), WriteFlags::UPSERT)
             .map_err(|e| Error::Write(e.into()))
     }
 
     fn insert(&mut self, key: T::Key, value: T::Value) -> Result<(), Error> {
+        let key = key.encode();
+        self.record_write(key.as_ref());
         self.inner
-            .put(key.encode().as_ref(), value.compress().as_ref(), WriteFlags::NO_OVERWRITE)
+            .put(key.as_ref(), value.compress().as_ref(), WriteFlags::NO_OVERWRITE)
             .map_err(|e| Error::Write(e.into()))
     }
 
     /// Appends the data to the end of the table. Consequently, the append operation
     /// will fail if the inserted key is less than the last table key
     fn append(&mut self, key: T::Key, value: T::Value) -> Result<(), Error> {
+        let key = key.encode();
+        self.record_write(key.as_ref());
         self.inner
-            .put(key.encode().as_ref(), value.compress().as_ref(), WriteFlags::APPEND)
+            .put(key.as_ref(), value.compress().as_ref(), WriteFlags::APPEND)
             .map_err(|e| Error::Write(e.into()))
     }
 
@@ -2,21 +2,35 @@
 
 use crate::{
     database::{Database, DatabaseGAT},
-    tables::{TableType, TABLES},
-    utils::default_page_size,
+    table::Table,
+    tables::{PlainAccountState, TableType, TABLES},
+    transaction::DbTx,
+    utils::{default_page_size, BloomAccountFilter},
     Error,
 };
+use metrics::gauge;
 use reth_libmdbx::{
     DatabaseFlags, Environment, EnvironmentFlags, EnvironmentKind, Geometry, Mode, PageSize,
-    SyncMode, RO, RW,
+    SyncMode, Transaction, RO, RW,
 };
-use std::{ops::Deref, path::Path};
+use std::{borrow::Cow, ops::Deref, path::Path, sync::Arc};
+
+pub mod batch;
 
 pub mod cursor;
 
 pub mod tx;
 use tx::Tx;
 
+/// 1 GiB in bytes.
+const GIGABYTE: usize = 1024 * 1024 * 1024;
+
+/// 1 TiB in bytes.
+const TERABYTE: usize = GIGABYTE * 1024;
+
+/// How often a write transaction is retried after the map ran full.
+const MAP_FULL_RETRIES: usize = 3;
+
 /// Environment used when opening a MDBX environment. RO/RW.
 #[derive(Debug)]
 pub enum EnvKind {
@@ -26,11 +40,46 @@ pub enum EnvKind {
     RW,
 }
 
+/// Configures the size of the memory map of a MDBX environment.
+///
+/// MDBX grows the map on demand in steps of [`DatabaseConfig::growth_step`], so the database only
+/// runs out of space once it reaches [`DatabaseConfig::max_map_size`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatabaseConfig {
+    /// The lower bound of the map size.
+    pub initial_map_size: usize,
+    /// The upper bound of the map size.
+    pub max_map_size: usize,
+    /// The step in which the map grows once it's full.
+    pub growth_step: usize,
+    /// The false positive rate of the Bloom filter over the existing accounts, which lets lookups
+    /// of accounts that don't exist skip the database.
+    ///
+    /// The filter is only used by read-write environments, since it's kept up to date by the
+    /// writes of this environment. Disabled if `None`.
+    pub account_filter_fp_rate: Option<f64>,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            initial_map_size: 0,
+            max_map_size: 4 * TERABYTE,
+            growth_step: 4 * GIGABYTE,
+            account_filter_fp_rate: None,
+        }
+    }
+}
+
 /// Wrapper for the libmdbx environment.
 #[derive(Debug)]
 pub struct Env<E: EnvironmentKind> {
     /// Libmdbx-sys environment.
     pub inner: Environment<E>,
+    /// The map size configuration the environment was opened with.
+    config: DatabaseConfig,
+    /// The filter of existing accounts, if enabled.
+    account_filter: Option<Arc<BloomAccountFilter>>,
 }
 
 impl<'a, E: EnvironmentKind> DatabaseGAT<'a> for Env<E> {
@@ -40,11 +89,45 @@ impl<'a, E: EnvironmentKind> DatabaseGAT<'a> for Env<E> {
 
 impl<E: EnvironmentKind> Database for Env<E> {
     fn tx(&self) -> Result<<Self as DatabaseGAT<'_>>::TX, Error> {
-        Ok(Tx::new(self.inner.begin_ro_txn().map_err(|e| Error::InitTransaction(e.into()))?))
+        Ok(Tx::new(self.inner.begin_ro_txn().map_err(|e| Error::InitTransaction(e.into()))?)
+            .with_account_filter(self.account_filter.clone()))
     }
 
     fn tx_mut(&self) -> Result<<Self as DatabaseGAT<'_>>::TXMut, Error> {
-        Ok(Tx::new(self.inner.begin_rw_txn().map_err(|e| Error::InitTransaction(e.into()))?))
+        if let Ok(info) = self.inner.info() {
+            gauge!("db.map_size_bytes", info.map_size() as f64);
+        }
+        Ok(Tx::new(self.inner.begin_rw_txn().map_err(|e| Error::InitTransaction(e.into()))?)
+            .with_account_filter(self.account_filter.clone()))
+    }
+
+    /// Takes a function and passes a write-read transaction into it, making sure it's committed in
+    /// the end of the execution.
+    ///
+    /// If the memory map runs full, the transaction is aborted, the map is grown by
+    /// [`DatabaseConfig::growth_step`] and the function is retried, up to 3 times. Returns
+    /// [`Error::MapFull`] if the map can't be grown any further.
+    fn update_with_retry<T, F>(&self, mut f: F) -> Result<T, Error>
+    where
+        F: FnMut(&<Self as DatabaseGAT<'_>>::TXMut) -> Result<T, Error>,
+    {
+        let mut retries = 0;
+        loop {
+            let tx = self.tx_mut()?;
+            // dropping the transaction on error aborts it
+            let res = f(&tx).and_then(|res| tx.commit().map(|_| res));
+
+            match res {
+                Err(err) if is_map_full(&err) => {
+                    if retries == MAP_FULL_RETRIES {
+                        return Err(Error::MapFull)
+                    }
+                    retries += 1;
+                    self.grow_map()?;
+                }
+                res => return res,
+            }
+        }
     }
 }
 
@@ -53,17 +136,31 @@ impl<E: EnvironmentKind> Env<E> {
     ///
     /// It does not create the tables, for that call [`Env::create_tables`].
     pub fn open(path: &Path, kind: EnvKind) -> Result<Env<E>, Error> {
+        Self::open_with_config(path, kind, DatabaseConfig::default())
+    }
+
+    /// Opens the database at the specified path with the given `EnvKind` and map size
+    /// configuration.
+    ///
+    /// It does not create the tables, for that call [`Env::create_tables`].
+    pub fn open_with_config(
+        path: &Path,
+        kind: EnvKind,
+        config: DatabaseConfig,
+    ) -> Result<Env<E>, Error> {
+        let DatabaseConfig { initial_map_size, max_map_size, growth_step, account_filter_fp_rate } =
+            config;
         let mode = match kind {
             EnvKind::RO => Mode::ReadOnly,
             EnvKind::RW => Mode::ReadWrite { sync_mode: SyncMode::Durable },
         };
 
-        let env = Env {
+        let mut env = Env {
             inner: Environment::new()
                 .set_max_dbs(TABLES.len())
                 .set_geometry(Geometry {
-                    size: Some(0..(1024 * 1024 * 1024 * 1024 * 4)), // TODO: reevaluate (4 tb)
-                    growth_step: Some(1024 * 1024 * 256),           // TODO: reevaluate (256 mb)
+                    size: Some(initial_map_size..max_map_size),
+                    growth_step: Some(growth_step as isize),
                     shrink_threshold: None,
                     page_size: Some(PageSize::Set(default_page_size())),
                 })
@@ -75,23 +172,51 @@ impl<E: EnvironmentKind> Env<E> {
                 })
                 .open(path)
                 .map_err(|e| Error::DatabaseLocation(e.into()))?,
+            config,
+            account_filter: None,
         };
 
+        if let (EnvKind::RW, Some(false_positive_rate)) = (kind, account_filter_fp_rate) {
+            env.account_filter = Some(Arc::new(env.load_account_filter(false_positive_rate)?));
+        }
+
         Ok(env)
     }
 
+    /// Creates the filter of existing accounts from the [PlainAccountState] table.
+    fn load_account_filter(&self, false_positive_rate: f64) -> Result<BloomAccountFilter, Error> {
+        let tx = self.inner.begin_ro_txn().map_err(|e| Error::InitTransaction(e.into()))?;
+        // the table doesn't exist before the tables were created
+        let Ok(db) = tx.open_db(Some(PlainAccountState::NAME)) else {
+            return Ok(BloomAccountFilter::new(0, false_positive_rate))
+        };
+
+        let accounts = tx.db_stat(&db).map_err(|e| Error::Read(e.into()))?.entries();
+        let filter = BloomAccountFilter::new(accounts, false_positive_rate);
+        let mut cursor = tx.cursor(&db).map_err(|e| Error::InitCursor(e.into()))?;
+        for entry in cursor.iter_start::<Cow<'_, [u8]>, ()>() {
+            let (key, _) = entry.map_err(|e| Error::Read(e.into()))?;
+            filter.insert::<PlainAccountState>(&key);
+        }
+        Ok(filter)
+    }
+
+    /// Grows the memory map by [`DatabaseConfig::growth_step`], up to
+    /// [`DatabaseConfig::max_map_size`].
+    fn grow_map(&self) -> Result<(), Error> {
+        let map_size = self.inner.info().map_err(|_| Error::MapFull)?.map_size();
+        if map_size >= self.config.max_map_size {
+            return Err(Error::MapFull)
+        }
+        let new_map_size = (map_size + self.config.growth_step).min(self.config.max_map_size);
+        self.inner.set_map_size(new_map_size).map_err(|_| Error::MapFull)
+    }
+
     /// Creates all the defined tables, if necessary.
     pub fn create_tables(&self) -> Result<(), Error> {
         let tx = self.inner.begin_rw_txn().map_err(|e| Error::InitTransaction(e.into()))?;
 
-        for (table_type, table) in TABLES {
-            let flags = match table_type {
-                TableType::Table => DatabaseFlags::default(),
-                TableType::DupSort => DatabaseFlags::DUP_SORT,
-            };
-
-            tx.create_db(Some(table), flags).map_err(|e| Error::TableCreation(e.into()))?;
-        }
+        create_tables(&tx)?;
 
         tx.commit().map_err(|e| Error::Commit(e.into()))?;
 
@@ -99,6 +224,25 @@ impl<E: EnvironmentKind> Env<E> {
     }
 }
 
+/// Creates all the defined tables in the transaction, if necessary.
+pub(crate) fn create_tables<E: EnvironmentKind>(tx: &Transaction<'_, RW, E>) -> Result<(), Error> {
+    for (table_type, table) in TABLES {
+        let flags = match table_type {
+            TableType::Table => DatabaseFlags::default(),
+            TableType::DupSort => DatabaseFlags::DUP_SORT,
+        };
+
+        tx.create_db(Some(table), flags).map_err(|e| Error::TableCreation(e.into()))?;
+    }
+    Ok(())
+}
+
+/// Returns `true` if the error was caused by a full memory map.
+fn is_map_full(err: &Error) -> bool {
+    let map_full = reth_libmdbx::Error::MapFull.to_err_code();
+    matches!(err, Error::Write(code) | Error::Commit(code) if *code == map_full)
+}
+
 impl<E: EnvironmentKind> Deref for Env<E> {
     type Target = reth_libmdbx::Environment<E>;
 
@@ -143,7 +287,7 @@ pub mod test_utils {
 
 #[cfg(test)]
 mod tests {
-    use super::{test_utils, Env, EnvKind};
+    use super::{test_utils, DatabaseConfig, Env, EnvKind};
     use crate::{
         cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, ReverseWalker, Walker},
         database::Database,
@@ -171,6 +315,88 @@ mod tests {
         test_utils::create_test_db::<NoWriteMap>(EnvKind::RW);
     }
 
+    #[test]
+    fn db_creation_with_config() {
+        let path = TempDir::new().expect(test_utils::ERROR_TEMPDIR).into_path();
+        let config = DatabaseConfig {
+            initial_map_size: 16 * 1024 * 1024,
+            max_map_size: 64 * 1024 * 1024,
+            growth_step: 8 * 1024 * 1024,
+            ..Default::default()
+        };
+        let env = Env::<NoWriteMap>::open_with_config(&path, EnvKind::RW, config)
+            .expect(ERROR_DB_CREATION);
+        env.create_tables().expect(test_utils::ERROR_TABLE_CREATION);
+
+        let info = env.info().unwrap();
+        assert_eq!(info.geometry().min(), config.initial_map_size as u64);
+        assert!(info.map_size() <= config.max_map_size);
+    }
+
+    #[test]
+    fn db_account_filter() {
+        let path = TempDir::new().expect(test_utils::ERROR_TEMPDIR).into_path();
+        let config = DatabaseConfig { account_filter_fp_rate: Some(0.0001), ..Default::default() };
+        let existing = Address::from_low_u64_be(1);
+        let inserted = Address::from_low_u64_be(2);
+        let account = Account { nonce: 1, ..Default::default() };
+
+        {
+            let env = Env::<NoWriteMap>::open_with_config(&path, EnvKind::RW, config)
+                .expect(ERROR_DB_CREATION);
+            env.create_tables().expect(test_utils::ERROR_TABLE_CREATION);
+            let tx = env.tx_mut().expect(ERROR_INIT_TX);
+            tx.put::<PlainAccountState>(existing, account).expect(ERROR_PUT);
+            tx.commit().expect(ERROR_COMMIT);
+        }
+
+        // the filter is loaded from the table on startup
+        let env = Env::<NoWriteMap>::open_with_config(&path, EnvKind::RW, config)
+            .expect(ERROR_DB_CREATION);
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.get::<PlainAccountState>(existing), Ok(Some(account)));
+        assert_eq!(tx.get::<PlainAccountState>(inserted), Ok(None));
+        tx.commit().expect(ERROR_COMMIT);
+
+        // and updated by cursor writes
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        tx.cursor_write::<PlainAccountState>().unwrap().upsert(inserted, account).expect(ERROR_PUT);
+        assert_eq!(tx.get::<PlainAccountState>(inserted), Ok(Some(account)));
+        tx.commit().expect(ERROR_COMMIT);
+    }
+
+    #[test]
+    fn db_update_with_retry_map_full() {
+        let path = TempDir::new().expect(test_utils::ERROR_TEMPDIR).into_path();
+        let config = DatabaseConfig {
+            initial_map_size: 0,
+            max_map_size: 1024 * 1024,
+            growth_step: 256 * 1024,
+            ..Default::default()
+        };
+        let env = Env::<NoWriteMap>::open_with_config(&path, EnvKind::RW, config)
+            .expect(ERROR_DB_CREATION);
+        env.create_tables().expect(test_utils::ERROR_TABLE_CREATION);
+
+        // a small batch fits
+        env.update_with_retry(|tx| {
+            (0..10).try_for_each(|key| tx.put::<Headers>(key, Header::default()))
+        })
+        .expect(ERROR_PUT);
+
+        // a batch that exceeds the maximum map size
+        let header = Header { extra_data: vec![0; 1024].into(), ..Default::default() };
+        let res = env.update_with_retry(|tx| {
+            (0..10_000).try_for_each(|key| tx.put::<Headers>(key, header.clone()))
+        });
+        assert_eq!(res, Err(Error::MapFull));
+
+        // the aborted transaction is not persisted
+        let tx = env.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.get::<Headers>(10).expect(ERROR_GET), None);
+        assert!(tx.get::<Headers>(9).expect(ERROR_GET).is_some());
+    }
+
     #[test]
     fn db_manual_put_get() {
         let env = test_utils::create_test_db::<NoWriteMap>(EnvKind::RW);
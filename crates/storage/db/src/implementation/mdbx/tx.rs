@@ -5,17 +5,20 @@ use crate::{
     table::{Compress, DupSort, Encode, Table, TableImporter},
     tables::utils::decode_one,
     transaction::{DbTx, DbTxGAT, DbTxMut, DbTxMutGAT},
+    utils::BloomAccountFilter,
     Error,
 };
 use metrics::histogram;
 use reth_libmdbx::{EnvironmentKind, Transaction, TransactionKind, WriteFlags, RW};
-use std::{marker::PhantomData, time::Instant};
+use std::{marker::PhantomData, sync::Arc, time::Instant};
 
 /// Wrapper for the libmdbx transaction.
 #[derive(Debug)]
 pub struct Tx<'a, K: TransactionKind, E: EnvironmentKind> {
     /// Libmdbx-sys transaction.
     pub inner: Transaction<'a, K, E>,
+    /// The filter of existing accounts of the environment, if enabled.
+    pub(crate) account_filter: Option<Arc<BloomAccountFilter>>,
 }
 
 impl<'env, K: TransactionKind, E: EnvironmentKind> Tx<'env, K, E> {
@@ -24,7 +27,16 @@ impl<'env, K: TransactionKind, E: EnvironmentKind> Tx<'env, K, E> {
     where
         'a: 'env,
     {
-        Self { inner }
+        Self { inner, account_filter: None }
+    }
+
+    /// Consults and maintains the given filter of existing accounts.
+    pub(crate) fn with_account_filter(
+        mut self,
+        account_filter: Option<Arc<BloomAccountFilter>>,
+    ) -> Self {
+        self.account_filter = account_filter;
+        self
     }
 
     /// Gets this transaction ID.
@@ -32,6 +44,21 @@ impl<'env, K: TransactionKind, E: EnvironmentKind> Tx<'env, K, E> {
         self.inner.id()
     }
 
+    /// Gets the value of an encoded key.
+    pub(crate) fn get_encoded<T: Table>(&self, key: &[u8]) -> Result<Option<T::Value>, Error> {
+        if let Some(filter) = &self.account_filter {
+            if !filter.may_contain::<T>(key) {
+                return Ok(None)
+            }
+        }
+
+        self.inner
+            .get(&self.inner.open_db(Some(T::NAME)).map_err(|e| Error::Read(e.into()))?, key)
+            .map_err(|e| Error::Read(e.into()))?
+            .map(decode_one::<T>)
+            .transpose()
+    }
+
     /// Create db Cursor
     pub fn new_cursor<T: Table>(&self) -> Result<Cursor<'env, K, T>, Error> {
         Ok(Cursor {
@@ -42,6 +69,7 @@ impl<'env, K: TransactionKind, E: EnvironmentKind> Tx<'env, K, E> {
                 )
                 .map_err(|e| Error::InitCursor(e.into()))?,
             table: T::NAME,
+            account_filter: self.account_filter.clone(),
             _dbi: PhantomData,
         })
     }
@@ -73,7 +101,7 @@ impl<'tx, K: TransactionKind, E: EnvironmentKind> DbTx<'tx> for Tx<'tx, K, E> {
     fn commit(self) -> Result<bool, Error> {
         let start = Instant::now();
         let result = self.inner.commit().map_err(|e| Error::Commit(e.into()));
-        histogram!("tx.commit", start.elapsed());
+        histogram!("db.tx_commit", start.elapsed());
         result
     }
 
@@ -82,23 +110,21 @@ impl<'tx, K: TransactionKind, E: EnvironmentKind> DbTx<'tx> for Tx<'tx, K, E> {
     }
 
     fn get<T: Table>(&self, key: T::Key) -> Result<Option<<T as Table>::Value>, Error> {
-        self.inner
-            .get(
-                &self.inner.open_db(Some(T::NAME)).map_err(|e| Error::Read(e.into()))?,
-                key.encode().as_ref(),
-            )
-            .map_err(|e| Error::Read(e.into()))?
-            .map(decode_one::<T>)
-            .transpose()
+        self.get_encoded::<T>(key.encode().as_ref())
     }
 }
 
 impl<E: EnvironmentKind> DbTxMut<'_> for Tx<'_, RW, E> {
     fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), Error> {
+        let key = key.encode();
+        if let Some(filter) = &self.account_filter {
+            filter.insert::<T>(key.as_ref());
+        }
+
         self.inner
             .put(
                 &self.inner.open_db(Some(T::NAME)).map_err(|e| Error::Write(e.into()))?,
-                &key.encode(),
+                &key,
                 &value.compress(),
                 WriteFlags::UPSERT,
             )
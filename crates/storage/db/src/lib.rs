@@ -11,6 +11,8 @@
 pub mod abstraction;
 
 mod implementation;
+#[cfg(feature = "mdbx")]
+pub mod migrations;
 pub mod tables;
 mod utils;
 
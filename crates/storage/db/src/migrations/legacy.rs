@@ -0,0 +1,119 @@
+//! Storage layouts of previous schema versions that are read by the migrations.
+
+use crate::{
+    table::{Compress, Decompress, Table},
+    tables::{self, models::StoredBlockOmmers},
+    Error,
+};
+use reth_codecs::{main_codec, Compact};
+use reth_primitives::{BlockNumber, Bloom, Bytes, Header, H160, H256, U256};
+
+/// The [Header] layout before the Cancun fields were added, schema version 1.
+#[main_codec]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct HeaderV1 {
+    pub(crate) parent_hash: H256,
+    pub(crate) ommers_hash: H256,
+    pub(crate) beneficiary: H160,
+    pub(crate) state_root: H256,
+    pub(crate) transactions_root: H256,
+    pub(crate) receipts_root: H256,
+    pub(crate) withdrawals_root: Option<H256>,
+    pub(crate) logs_bloom: Bloom,
+    pub(crate) difficulty: U256,
+    pub(crate) number: BlockNumber,
+    pub(crate) gas_limit: u64,
+    pub(crate) gas_used: u64,
+    pub(crate) timestamp: u64,
+    pub(crate) mix_hash: H256,
+    pub(crate) nonce: u64,
+    pub(crate) base_fee_per_gas: Option<u64>,
+    pub(crate) extra_data: Bytes,
+}
+
+impl From<HeaderV1> for Header {
+    fn from(header: HeaderV1) -> Self {
+        Header {
+            parent_hash: header.parent_hash,
+            ommers_hash: header.ommers_hash,
+            beneficiary: header.beneficiary,
+            state_root: header.state_root,
+            transactions_root: header.transactions_root,
+            receipts_root: header.receipts_root,
+            withdrawals_root: header.withdrawals_root,
+            logs_bloom: header.logs_bloom,
+            difficulty: header.difficulty,
+            number: header.number,
+            gas_limit: header.gas_limit,
+            gas_used: header.gas_used,
+            timestamp: header.timestamp,
+            mix_hash: header.mix_hash,
+            nonce: header.nonce,
+            base_fee_per_gas: header.base_fee_per_gas,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            extra_data: header.extra_data,
+        }
+    }
+}
+
+/// The [StoredBlockOmmers] layout of schema version 1.
+#[main_codec]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct StoredBlockOmmersV1 {
+    pub(crate) ommers: Vec<HeaderV1>,
+}
+
+impl From<StoredBlockOmmersV1> for StoredBlockOmmers {
+    fn from(ommers: StoredBlockOmmersV1) -> Self {
+        StoredBlockOmmers { ommers: ommers.ommers.into_iter().map(Into::into).collect() }
+    }
+}
+
+/// Implements compression for the legacy Compact types.
+macro_rules! impl_compression_for_compact {
+    ($($name:tt),+) => {
+        $(
+            impl Compress for $name {
+                type Compressed = Vec<u8>;
+
+                fn compress(self) -> Self::Compressed {
+                    let mut buf = vec![];
+                    let _ = Compact::to_compact(self, &mut buf);
+                    buf
+                }
+            }
+
+            impl Decompress for $name {
+                fn decompress<B: Into<bytes::Bytes>>(value: B) -> Result<$name, Error> {
+                    let value = value.into();
+                    let (obj, _) = Compact::from_compact(&value, value.len());
+                    Ok(obj)
+                }
+            }
+        )+
+    };
+}
+
+impl_compression_for_compact!(HeaderV1, StoredBlockOmmersV1);
+
+/// The [tables::Headers] table of schema version 1.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct HeadersV1;
+
+impl Table for HeadersV1 {
+    const NAME: &'static str = tables::Headers::NAME;
+    type Key = BlockNumber;
+    type Value = HeaderV1;
+}
+
+/// The [tables::BlockOmmers] table of schema version 1.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct BlockOmmersV1;
+
+impl Table for BlockOmmersV1 {
+    const NAME: &'static str = tables::BlockOmmers::NAME;
+    type Key = BlockNumber;
+    type Value = StoredBlockOmmersV1;
+}
@@ -0,0 +1,179 @@
+//! Versioned upgrades of the database schema.
+//!
+//! Every migration upgrades the schema by one version. A migration and the update of the stored
+//! [SchemaVersion](tables::SchemaVersion) are written in the same write transaction, so a crash
+//! during a migration leaves the database at the previous version and the migration is retried
+//! the next time the [MigrationRunner] runs.
+
+use crate::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    implementation::mdbx::{create_tables, tx::Tx, Env},
+    table::Table,
+    tables,
+    transaction::{DbTx, DbTxMut},
+    Error,
+};
+use reth_libmdbx::{EnvironmentKind, RW};
+
+mod legacy;
+
+/// The schema version of databases created by this version of reth.
+pub const CURRENT_VERSION: u64 = 2;
+
+/// The key of the version in the [tables::SchemaVersion] table.
+const SCHEMA_VERSION_KEY: &str = "SchemaVersion";
+
+/// Upgrades the schema from one version to the next one.
+type Migration<E> = fn(&Tx<'_, RW, E>) -> Result<(), Error>;
+
+/// Returns all migrations, the migration at index `n` upgrades the schema from version `n` to
+/// `n + 1`.
+fn migrations<E: EnvironmentKind>() -> [Migration<E>; CURRENT_VERSION as usize] {
+    [migrate_v0_to_v1, migrate_v1_to_v2]
+}
+
+/// Upgrades the schema of a database to the [CURRENT_VERSION].
+#[derive(Debug)]
+pub struct MigrationRunner<'a, E: EnvironmentKind> {
+    /// The database to upgrade.
+    env: &'a Env<E>,
+}
+
+impl<'a, E: EnvironmentKind> MigrationRunner<'a, E> {
+    /// Creates a new runner for the given database.
+    pub fn new(env: &'a Env<E>) -> Self {
+        Self { env }
+    }
+
+    /// Returns the schema version of the database.
+    ///
+    /// Databases without a [tables::SchemaVersion] table are at version 0.
+    pub fn version(&self) -> Result<u64, Error> {
+        let tx = self.env.tx()?;
+        if tx.inner.open_db(Some(tables::SchemaVersion::NAME)).is_err() {
+            return Ok(0)
+        }
+        let version = tx.get::<tables::SchemaVersion>(SCHEMA_VERSION_KEY.into())?;
+        tx.commit()?;
+        Ok(version.unwrap_or_default())
+    }
+
+    /// Runs all migrations from the version of the database up to the [CURRENT_VERSION].
+    ///
+    /// Returns the version of the database after the upgrade. Databases of a newer version are
+    /// left untouched.
+    pub fn run(&self) -> Result<u64, Error> {
+        let migrations = migrations::<E>();
+        loop {
+            let version = self.version()?;
+            let Some(migration) = migrations.get(version as usize) else { return Ok(version) };
+
+            let tx = self.env.tx_mut()?;
+            migration(&tx)?;
+            tx.put::<tables::SchemaVersion>(SCHEMA_VERSION_KEY.into(), version + 1)?;
+            tx.commit()?;
+        }
+    }
+}
+
+/// Creates all tables of the initial schema. Databases created before the schema was versioned
+/// already have most of these tables, which are kept as they are.
+fn migrate_v0_to_v1<E: EnvironmentKind>(tx: &Tx<'_, RW, E>) -> Result<(), Error> {
+    create_tables(&tx.inner)
+}
+
+/// Rewrites the stored headers in the layout with the Cancun fields, which are unset for all
+/// headers written before.
+fn migrate_v1_to_v2<E: EnvironmentKind>(tx: &Tx<'_, RW, E>) -> Result<(), Error> {
+    let mut legacy_headers = tx.cursor_read::<legacy::HeadersV1>()?;
+    let mut headers = tx.cursor_write::<tables::Headers>()?;
+    let mut entry = legacy_headers.first()?;
+    while let Some((number, header)) = entry {
+        headers.upsert(number, header.into())?;
+        entry = legacy_headers.next()?;
+    }
+
+    let mut legacy_ommers = tx.cursor_read::<legacy::BlockOmmersV1>()?;
+    let mut ommers = tx.cursor_write::<tables::BlockOmmers>()?;
+    let mut entry = legacy_ommers.first()?;
+    while let Some((number, block_ommers)) = entry {
+        ommers.upsert(number, block_ommers.into())?;
+        entry = legacy_ommers.next()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mdbx::{test_utils::ERROR_TEMPDIR, EnvKind},
+        tables::{models::StoredBlockOmmers, TableType, TABLES},
+    };
+    use reth_libmdbx::{DatabaseFlags, NoWriteMap};
+    use reth_primitives::Header;
+
+    #[test]
+    fn migrate_empty_database() {
+        let path = tempfile::TempDir::new().expect(ERROR_TEMPDIR).into_path();
+        let env = Env::<NoWriteMap>::open(&path, EnvKind::RW).unwrap();
+        let runner = MigrationRunner::new(&env);
+        assert_eq!(runner.version(), Ok(0));
+
+        assert_eq!(runner.run(), Ok(CURRENT_VERSION));
+        assert_eq!(runner.version(), Ok(CURRENT_VERSION));
+
+        let tx = env.tx().unwrap();
+        for (table_type, table) in TABLES {
+            let db = tx.inner.open_db(Some(table)).unwrap();
+            let flags = tx.inner.db_flags(&db).unwrap();
+            match table_type {
+                TableType::Table => assert!(!flags.contains(DatabaseFlags::DUP_SORT), "{table}"),
+                TableType::DupSort => assert!(flags.contains(DatabaseFlags::DUP_SORT), "{table}"),
+            }
+        }
+        tx.commit().unwrap();
+
+        // running again is a no-op
+        assert_eq!(runner.run(), Ok(CURRENT_VERSION));
+    }
+
+    #[test]
+    fn migrate_v1_headers() {
+        let path = tempfile::TempDir::new().expect(ERROR_TEMPDIR).into_path();
+        let env = Env::<NoWriteMap>::open(&path, EnvKind::RW).unwrap();
+        let runner = MigrationRunner::new(&env);
+
+        let header = legacy::HeaderV1 {
+            number: 1,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            extra_data: vec![1, 2, 3].into(),
+            ..Default::default()
+        };
+        let tx = env.tx_mut().unwrap();
+        migrate_v0_to_v1(&tx).unwrap();
+        tx.put::<tables::SchemaVersion>(SCHEMA_VERSION_KEY.into(), 1).unwrap();
+        tx.put::<legacy::HeadersV1>(1, header.clone()).unwrap();
+        tx.put::<legacy::BlockOmmersV1>(
+            1,
+            legacy::StoredBlockOmmersV1 { ommers: vec![header.clone()] },
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        assert_eq!(runner.run(), Ok(CURRENT_VERSION));
+
+        let expected = Header::from(header);
+        assert_eq!(expected.blob_gas_used, None);
+        let tx = env.tx().unwrap();
+        assert_eq!(tx.get::<tables::Headers>(1), Ok(Some(expected.clone())));
+        assert_eq!(
+            tx.get::<tables::BlockOmmers>(1),
+            Ok(Some(StoredBlockOmmers { ommers: vec![expected] }))
+        );
+        tx.commit().unwrap();
+    }
+}
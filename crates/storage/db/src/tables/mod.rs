@@ -32,7 +32,7 @@ pub enum TableType {
 }
 
 /// Default tables that should be present inside database.
-pub const TABLES: [(TableType, &str); 27] = [
+pub const TABLES: [(TableType, &str); 28] = [
     (TableType::Table, CanonicalHeaders::const_name()),
     (TableType::Table, HeaderTD::const_name()),
     (TableType::Table, HeaderNumbers::const_name()),
@@ -60,6 +60,7 @@ pub const TABLES: [(TableType, &str); 27] = [
     (TableType::Table, TxSenders::const_name()),
     (TableType::Table, SyncStage::const_name()),
     (TableType::Table, SyncStageProgress::const_name()),
+    (TableType::Table, SchemaVersion::const_name()),
 ];
 
 #[macro_export]
@@ -83,6 +84,16 @@ macro_rules! table {
             pub const fn const_name() -> &'static str {
                 stringify!($table_name)
             }
+
+            #[doc=concat!("Return the key type of ", stringify!($table_name), " as declared in the schema.")]
+            pub const fn key_type() -> &'static str {
+                stringify!($key)
+            }
+
+            #[doc=concat!("Return the value type of ", stringify!($table_name), " as declared in the schema.")]
+            pub const fn value_type() -> &'static str {
+                stringify!($value)
+            }
         }
 
         impl std::fmt::Display for $table_name {
@@ -299,6 +310,11 @@ table!(
     ( SyncStageProgress ) StageId | Vec<u8>
 );
 
+table!(
+    /// Stores the version of the database schema.
+    ( SchemaVersion ) String | u64
+);
+
 ///
 /// Alias Types
 
@@ -0,0 +1,74 @@
+//! Bloom filter over the keys of the [PlainAccountState] table.
+
+use crate::{table::Table, tables::PlainAccountState};
+use bloomfilter::Bloom;
+use metrics::gauge;
+use std::{fmt, sync::RwLock};
+
+/// The smallest number of accounts a filter is sized for.
+const MIN_CAPACITY: usize = 1_000_000;
+
+/// A Bloom filter over the encoded addresses in the [PlainAccountState] table.
+///
+/// If the filter doesn't contain an address, the account definitely doesn't exist and the
+/// database lookup can be skipped. Addresses are never removed, so deleted accounts remain false
+/// positives until the filter is rebuilt on the next startup.
+pub(crate) struct BloomAccountFilter {
+    /// The filter, shared by all transactions of the environment.
+    bloom: RwLock<Bloom<[u8]>>,
+}
+
+impl BloomAccountFilter {
+    /// Creates an empty filter for the given number of existing accounts.
+    ///
+    /// The filter is sized for twice as many accounts, so the false positive rate only degrades
+    /// once the number of accounts doubled.
+    pub(crate) fn new(accounts: usize, false_positive_rate: f64) -> Self {
+        let bloom = Bloom::new_for_fp_rate((accounts * 2).max(MIN_CAPACITY), false_positive_rate);
+        gauge!("db.account_filter_size_bytes", (bloom.number_of_bits() / 8) as f64);
+        Self { bloom: RwLock::new(bloom) }
+    }
+
+    /// Returns `false` if the encoded key is definitely not in the table `T`.
+    pub(crate) fn may_contain<T: Table>(&self, key: &[u8]) -> bool {
+        if T::NAME != PlainAccountState::NAME {
+            return true
+        }
+        self.bloom.read().expect("not poisoned").check(key)
+    }
+
+    /// Records that the encoded key was written to the table `T`.
+    pub(crate) fn insert<T: Table>(&self, key: &[u8]) {
+        if T::NAME == PlainAccountState::NAME {
+            self.bloom.write().expect("not poisoned").set(key);
+        }
+    }
+}
+
+impl fmt::Debug for BloomAccountFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BloomAccountFilter").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tables::PlainStorageState;
+
+    #[test]
+    fn filters_plain_account_state() {
+        let filter = BloomAccountFilter::new(0, 0.0001);
+        let key = [1u8; 20];
+
+        assert!(!filter.may_contain::<PlainAccountState>(&key));
+        // other tables are not filtered
+        assert!(filter.may_contain::<PlainStorageState>(&key));
+
+        filter.insert::<PlainStorageState>(&key);
+        assert!(!filter.may_contain::<PlainAccountState>(&key));
+
+        filter.insert::<PlainAccountState>(&key);
+        assert!(filter.may_contain::<PlainAccountState>(&key));
+    }
+}
@@ -1,5 +1,10 @@
 //! Utils crate for `db`.
 
+#[cfg(feature = "mdbx")]
+mod bloom;
+#[cfg(feature = "mdbx")]
+pub(crate) use bloom::BloomAccountFilter;
+
 /// Returns the default page size that can be used in this OS.
 pub(crate) fn default_page_size() -> usize {
     let os_page_size = page_size::get();
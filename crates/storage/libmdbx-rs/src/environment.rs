@@ -138,6 +138,17 @@ where
         mdbx_result(unsafe { ffi::mdbx_env_sync_ex(self.env(), force, false) })
     }
 
+    /// Changes the current size of the memory map.
+    ///
+    /// The size must be within the bounds of the configured geometry, and no write transaction of
+    /// this process must be open.
+    pub fn set_map_size(&self, size: usize) -> Result<()> {
+        mdbx_result(unsafe {
+            ffi::mdbx_env_set_geometry(self.env(), -1, size as isize, -1, -1, -1, -1)
+        })?;
+        Ok(())
+    }
+
     /// Retrieves statistics about this environment.
     pub fn stat(&self) -> Result<Stat> {
         unsafe {
@@ -12,8 +12,10 @@
 mod traits;
 pub use traits::{
     AccountProvider, BlockExecutor, BlockHashProvider, BlockIdProvider, BlockProvider,
-    EvmEnvProvider, ExecutorFactory, HeaderProvider, ReceiptProvider, StateProvider,
-    StateProviderFactory, TransactionsProvider, WithdrawalsProvider,
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
+    CanonStateSubscriptions, EvmEnvProvider, ExecutorFactory, HeaderProvider, NodeDataProvider,
+    ReceiptProvider, StateProvider, StateProviderFactory, TransactionsProvider,
+    WithdrawalsProvider,
 };
 
 /// Provider trait implementations.
@@ -29,6 +31,9 @@ pub mod trie;
 /// Execution result
 pub mod post_state;
 
+/// Pruning of historical data
+pub mod pruner;
+
 /// Helper types for interacting with the database
 mod transaction;
 pub use transaction::{Transaction, TransactionError};
@@ -329,6 +329,14 @@ impl PostState {
         non_reverted_state
     }
 
+    /// Discards the changes of all transitions before `transition_id`.
+    ///
+    /// Unlike [PostState::revert_to], the effects of the changes are kept, so only the change sets
+    /// written by [PostState::write_to_db] are affected.
+    pub fn discard_changes_before(&mut self, transition_id: TransitionId) {
+        self.changes.retain(|change| change.transition_id() >= transition_id);
+    }
+
     /// Add a newly created account to the post-state.
     pub fn create_account(&mut self, address: Address, account: Account) {
         self.add_and_apply(Change::AccountCreated {
@@ -825,4 +833,25 @@ mod tests {
         assert_eq!(state.transitions_count(), 1);
         assert_eq!(reverted_changes.len(), 1);
     }
+
+    #[test]
+    fn discard_changes_before() {
+        let mut state = PostState::new();
+        state.create_account(
+            Address::repeat_byte(0),
+            Account { nonce: 1, balance: U256::from(1), bytecode_hash: None },
+        );
+        state.finish_transition();
+        state.create_account(
+            Address::repeat_byte(0xff),
+            Account { nonce: 2, balance: U256::from(2), bytecode_hash: None },
+        );
+        state.finish_transition();
+
+        state.discard_changes_before(1);
+        assert_eq!(state.accounts().len(), 2);
+        assert_eq!(state.transitions_count(), 2);
+        assert_eq!(state.changes().len(), 1);
+        assert_eq!(state.changes()[0].address(), Address::repeat_byte(0xff));
+    }
 }
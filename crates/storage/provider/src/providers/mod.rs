@@ -1,6 +1,7 @@
 use crate::{
-    BlockHashProvider, BlockIdProvider, BlockProvider, EvmEnvProvider, HeaderProvider,
-    ProviderError, StateProviderFactory, TransactionsProvider, WithdrawalsProvider,
+    pruner::ensure_state_history_available, BlockHashProvider, BlockIdProvider, BlockProvider,
+    EvmEnvProvider, HeaderProvider, NodeDataProvider, ProviderError, StateProviderFactory,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{
     cursor::DbCursorRO,
@@ -8,10 +9,10 @@ use reth_db::{
     tables,
     transaction::DbTx,
 };
-use reth_interfaces::Result;
+use reth_interfaces::{consensus::ForkchoiceState, Result};
 use reth_primitives::{
-    Block, BlockHash, BlockId, BlockNumber, ChainInfo, ChainSpec, Hardfork, Head, Header, Receipt,
-    TransactionSigned, TxHash, TxNumber, Withdrawal, H256, U256,
+    Block, BlockHash, BlockId, BlockNumber, Bytes, ChainInfo, ChainSpec, Hardfork, Head, Header,
+    Receipt, TransactionSigned, TxHash, TxNumber, Withdrawal, H256, U256,
 };
 use reth_revm_primitives::{
     config::revm_spec,
@@ -19,6 +20,7 @@ use reth_revm_primitives::{
 };
 use revm_primitives::{BlockEnv, CfgEnv, SpecId};
 use std::{ops::RangeBounds, sync::Arc};
+use tokio::sync::watch;
 
 mod state;
 use crate::traits::ReceiptProvider;
@@ -36,18 +38,36 @@ pub struct ShareableDatabase<DB> {
     db: DB,
     /// Chain spec
     chain_spec: Arc<ChainSpec>,
+    /// The latest forkchoice state, used to resolve the safe and finalized blocks.
+    forkchoice_state: Option<watch::Receiver<ForkchoiceState>>,
 }
 
 impl<DB> ShareableDatabase<DB> {
     /// create new database provider
     pub fn new(db: DB, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { db, chain_spec }
+        Self { db, chain_spec, forkchoice_state: None }
+    }
+
+    /// Resolve the safe and finalized blocks of the [ChainInfo] from the given forkchoice state
+    /// updates.
+    ///
+    /// Without forkchoice state updates the safe and finalized blocks are unknown.
+    pub fn with_forkchoice_state(
+        mut self,
+        forkchoice_state: watch::Receiver<ForkchoiceState>,
+    ) -> Self {
+        self.forkchoice_state = Some(forkchoice_state);
+        self
     }
 }
 
 impl<DB: Clone> Clone for ShareableDatabase<DB> {
     fn clone(&self) -> Self {
-        Self { db: self.db.clone(), chain_spec: Arc::clone(&self.chain_spec) }
+        Self {
+            db: self.db.clone(),
+            chain_spec: Arc::clone(&self.chain_spec),
+            forkchoice_state: self.forkchoice_state.clone(),
+        }
     }
 }
 
@@ -120,7 +140,27 @@ impl<DB: Database> BlockIdProvider for ShareableDatabase<DB> {
             .map_err(Into::<reth_interfaces::db::Error>::into)?
             .unwrap_or_default();
         let best_hash = self.block_hash(best_number)?.unwrap_or_default();
-        Ok(ChainInfo { best_hash, best_number, last_finalized: None, safe_finalized: None })
+
+        let (safe_block_hash, finalized_block_hash) = match &self.forkchoice_state {
+            Some(state) => {
+                let state = state.borrow();
+                (state.safe_block_hash, state.finalized_block_hash)
+            }
+            None => Default::default(),
+        };
+        let number_of = |hash: H256| -> Result<Option<BlockNumber>> {
+            if hash.is_zero() {
+                return Ok(None)
+            }
+            self.block_number(hash)
+        };
+
+        Ok(ChainInfo {
+            best_hash,
+            best_number,
+            last_finalized: number_of(finalized_block_hash)?,
+            safe_finalized: number_of(safe_block_hash)?,
+        })
     }
 
     fn block_number(&self, hash: H256) -> Result<Option<BlockNumber>> {
@@ -265,6 +305,18 @@ impl<DB: Database> ReceiptProvider for ShareableDatabase<DB> {
     }
 }
 
+impl<DB: Database> NodeDataProvider for ShareableDatabase<DB> {
+    fn node_data(&self, hash: H256) -> Result<Option<Bytes>> {
+        self.db.view(|tx| {
+            if let Some(node) = tx.get::<tables::AccountsTrie>(hash)? {
+                return Ok(Some(node.into()))
+            }
+            // storage trie nodes are keyed by the account, so only contract code is left
+            Ok(tx.get::<tables::Bytecodes>(hash)?.map(|code| code.original_bytes().into()))
+        })?
+    }
+}
+
 impl<DB: Database> WithdrawalsProvider for ShareableDatabase<DB> {
     fn withdrawals_by_block(&self, id: BlockId, timestamp: u64) -> Result<Option<Vec<Withdrawal>>> {
         if self.chain_spec.fork(Hardfork::Shanghai).active_at_timestamp(timestamp) {
@@ -360,6 +412,7 @@ impl<DB: Database> StateProviderFactory for ShareableDatabase<DB> {
 
     fn history_by_block_number(&self, block_number: BlockNumber) -> Result<Self::HistorySP<'_>> {
         let tx = self.db.tx()?;
+        ensure_state_history_available(&tx, block_number)?;
 
         // get transition id
         let transition = tx
@@ -375,6 +428,7 @@ impl<DB: Database> StateProviderFactory for ShareableDatabase<DB> {
         let block_number = tx
             .get::<tables::HeaderNumbers>(block_hash)?
             .ok_or(ProviderError::BlockHash { block_hash })?;
+        ensure_state_history_available(&tx, block_number)?;
 
         // get transition id
         let transition = tx
@@ -391,8 +445,15 @@ mod tests {
 
     use super::ShareableDatabase;
     use crate::{BlockIdProvider, StateProviderFactory};
-    use reth_db::mdbx::{test_utils::create_test_db, EnvKind, WriteMap};
+    use reth_db::{
+        database::Database,
+        mdbx::{test_utils::create_test_db, EnvKind, WriteMap},
+        tables,
+        transaction::DbTxMut,
+    };
+    use reth_interfaces::consensus::ForkchoiceState;
     use reth_primitives::{ChainSpecBuilder, H256};
+    use tokio::sync::watch;
 
     #[test]
     fn common_history_provider() {
@@ -414,4 +475,34 @@ mod tests {
         assert_eq!(chain_info.last_finalized, None);
         assert_eq!(chain_info.safe_finalized, None);
     }
+
+    #[test]
+    fn forkchoice_chain_info() {
+        let chain_spec = ChainSpecBuilder::mainnet().build();
+        let db = create_test_db::<WriteMap>(EnvKind::RW);
+        let (safe, finalized) = (H256::random(), H256::random());
+        db.update(|tx| {
+            tx.put::<tables::HeaderNumbers>(safe, 2).unwrap();
+            tx.put::<tables::HeaderNumbers>(finalized, 1).unwrap();
+        })
+        .unwrap();
+
+        let (forkchoice_tx, forkchoice_rx) = watch::channel(ForkchoiceState::default());
+        let provider =
+            ShareableDatabase::new(db, Arc::new(chain_spec)).with_forkchoice_state(forkchoice_rx);
+        let chain_info = provider.chain_info().unwrap();
+        assert_eq!(chain_info.last_finalized, None);
+        assert_eq!(chain_info.safe_finalized, None);
+
+        forkchoice_tx
+            .send(ForkchoiceState {
+                head_block_hash: safe,
+                safe_block_hash: safe,
+                finalized_block_hash: finalized,
+            })
+            .unwrap();
+        let chain_info = provider.chain_info().unwrap();
+        assert_eq!(chain_info.last_finalized, Some(1));
+        assert_eq!(chain_info.safe_finalized, Some(2));
+    }
 }
@@ -43,12 +43,14 @@ impl<'a, 'b, TX: DbTx<'a>> AccountProvider for HistoricalStateProviderRef<'a, 'b
         // history key to search IntegerList of transition id changesets.
         let history_key = ShardedKey::new(address, self.transition);
 
-        let Some(changeset_transition_id) = self.tx.cursor_read::<tables::AccountHistory>()?
+        let changeset_transition_id = self
+            .tx
+            .cursor_read::<tables::AccountHistory>()?
             .seek(history_key)?
-            .filter(|(key,_)| key.key == address)
-            .map(|(_,list)| list.0.enable_rank().successor(self.transition as usize).map(|i| i as u64)) else {
-                return Ok(None)
-            };
+            .filter(|(key, _)| key.key == address)
+            .and_then(|(_, list)| {
+                list.0.enable_rank().successor(self.transition as usize).map(|i| i as u64)
+            });
 
         // if changeset transition id is present we are getting value from changeset
         if let Some(changeset_transition_id) = changeset_transition_id {
@@ -63,8 +65,9 @@ impl<'a, 'b, TX: DbTx<'a>> AccountProvider for HistoricalStateProviderRef<'a, 'b
                 })?;
             Ok(account.info)
         } else {
-            // if changeset is not present that means that there was history shard but we need to
-            // use newest value from plain state
+            // if changeset is not present the account did not change after the transition, either
+            // because there is no later history shard entry or no history at all (e.g. genesis
+            // accounts), so we need to use newest value from plain state
             Ok(self.tx.get::<tables::PlainAccountState>(address)?)
         }
     }
@@ -96,12 +99,14 @@ impl<'a, 'b, TX: DbTx<'a>> StateProvider for HistoricalStateProviderRef<'a, 'b,
         // history key to search IntegerList of transition id changesets.
         let history_key = StorageShardedKey::new(address, storage_key, self.transition);
 
-        let Some(changeset_transition_id) = self.tx.cursor_read::<tables::StorageHistory>()?
+        let changeset_transition_id = self
+            .tx
+            .cursor_read::<tables::StorageHistory>()?
             .seek(history_key)?
-            .filter(|(key,_)| key.address == address && key.sharded_key.key == storage_key)
-            .map(|(_,list)| list.0.enable_rank().successor(self.transition as usize).map(|i| i as u64)) else {
-                return Ok(None)
-            };
+            .filter(|(key, _)| key.address == address && key.sharded_key.key == storage_key)
+            .and_then(|(_, list)| {
+                list.0.enable_rank().successor(self.transition as usize).map(|i| i as u64)
+            });
 
         // if changeset transition id is present we are getting value from changeset
         if let Some(changeset_transition_id) = changeset_transition_id {
@@ -117,8 +122,8 @@ impl<'a, 'b, TX: DbTx<'a>> StateProvider for HistoricalStateProviderRef<'a, 'b,
                 })?;
             Ok(Some(storage_entry.value))
         } else {
-            // if changeset is not present that means that there was history shard but we need to
-            // use newest value from plain state
+            // if changeset is not present the slot did not change after the transition, so we need
+            // to use newest value from plain state
             Ok(self
                 .tx
                 .cursor_dup_read::<tables::PlainStorageState>()?
@@ -279,6 +284,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn history_provider_get_account_without_history() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+
+        let genesis_address = H160(hex!("0000000000000000000000000000000000000002"));
+        let genesis_acc = Account { nonce: 1, balance: U256::from(5), bytecode_hash: None };
+        let created_acc = Account { nonce: 0, balance: U256::from(1), bytecode_hash: None };
+
+        // account that was created at transition 5
+        tx.put::<tables::AccountHistory>(
+            ShardedKey { key: ADDRESS, highest_transition_id: u64::MAX },
+            TransitionList::new([5]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSet>(5, AccountBeforeTx { address: ADDRESS, info: None })
+            .unwrap();
+
+        // setup plain state
+        tx.put::<tables::PlainAccountState>(ADDRESS, created_acc).unwrap();
+        tx.put::<tables::PlainAccountState>(genesis_address, genesis_acc).unwrap();
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+
+        // run
+        assert_eq!(HistoricalStateProviderRef::new(&tx, 1).basic_account(ADDRESS), Ok(None));
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 6).basic_account(ADDRESS),
+            Ok(Some(created_acc))
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 1).basic_account(genesis_address),
+            Ok(Some(genesis_acc))
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&tx, 1)
+                .basic_account(H160(hex!("0000000000000000000000000000000000000003"))),
+            Ok(None)
+        );
+    }
+
     #[test]
     fn history_provider_get_storage() {
         let db = create_test_rw_db();
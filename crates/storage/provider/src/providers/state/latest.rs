@@ -34,6 +34,16 @@ impl<'a, 'b, TX: DbTx<'a>> AccountProvider for LatestStateProviderRef<'a, 'b, TX
     fn basic_account(&self, address: Address) -> Result<Option<Account>> {
         self.db.get::<tables::PlainAccountState>(address).map_err(Into::into)
     }
+
+    /// Get the addresses of up to `limit` accounts, starting at the given address.
+    fn account_range(&self, start: Address, limit: usize) -> Result<Vec<Address>> {
+        let mut cursor = self.db.cursor_read::<tables::PlainAccountState>()?;
+        cursor
+            .walk(Some(start))?
+            .take(limit)
+            .map(|result| result.map(|(address, _)| address).map_err(Into::into))
+            .collect()
+    }
 }
 
 impl<'a, 'b, TX: DbTx<'a>> BlockHashProvider for LatestStateProviderRef<'a, 'b, TX> {
@@ -137,10 +147,28 @@ delegate_provider_impls!(LatestStateProvider<'a, TX> where [TX: DbTx<'a>]);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_db::{database::Database, mdbx::test_utils::create_test_rw_db, transaction::DbTxMut};
 
     fn assert_state_provider<T: StateProvider>() {}
     #[allow(unused)]
     fn assert_latest_state_provider<'txn, T: DbTx<'txn> + 'txn>() {
         assert_state_provider::<LatestStateProvider<'txn, T>>();
     }
+
+    #[test]
+    fn latest_provider_account_range() {
+        let db = create_test_rw_db();
+        let tx = db.tx_mut().unwrap();
+        let addresses = (1..=5).map(Address::from_low_u64_be).collect::<Vec<_>>();
+        for address in &addresses {
+            tx.put::<tables::PlainAccountState>(*address, Account::default()).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let provider = LatestStateProviderRef::new(&tx);
+        assert_eq!(provider.account_range(Address::zero(), 2).unwrap(), addresses[..2]);
+        assert_eq!(provider.account_range(addresses[3], 10).unwrap(), addresses[3..]);
+        assert!(provider.account_range(Address::from_low_u64_be(6), 10).unwrap().is_empty());
+    }
 }
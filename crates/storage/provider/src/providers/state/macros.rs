@@ -32,6 +32,7 @@ macro_rules! delegate_provider_impls {
             for $target =>
             AccountProvider $(where [$($generics)*])? {
                 fn basic_account(&self, address: reth_primitives::Address) -> reth_interfaces::Result<Option<reth_primitives::Account>>;
+                fn account_range(&self, start: reth_primitives::Address, limit: usize) -> reth_interfaces::Result<Vec<reth_primitives::Address>>;
             }
             BlockHashProvider $(where [$($generics)*])? {
                 fn block_hash(&self, number: u64) -> reth_interfaces::Result<Option<reth_primitives::H256>>;
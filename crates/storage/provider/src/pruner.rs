@@ -0,0 +1,481 @@
+//! Pruning of historical state and transaction lookup data.
+
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    table::Table,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_interfaces::{db::Error as DbError, provider::ProviderError, Result};
+use reth_primitives::BlockNumber;
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+/// The default number of entries that are deleted in a single database transaction.
+const DEFAULT_BATCH_SIZE: usize = 10_000;
+
+/// The number of most recent blocks whose state is kept by a [NodeMode::Full] node.
+pub const FULL_NODE_STATE_HISTORY: u64 = 128;
+
+/// Determines how much of the historical state is kept by the node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NodeMode {
+    /// Keep the state of the last [FULL_NODE_STATE_HISTORY] blocks.
+    Full,
+    /// Keep the state of all blocks.
+    #[default]
+    Archive,
+    /// Keep the state of the given number of most recent blocks.
+    Pruned(u64),
+}
+
+impl NodeMode {
+    /// Returns the number of most recent blocks whose state is kept, or `None` if the state of all
+    /// blocks is kept.
+    pub fn state_history_blocks(&self) -> Option<u64> {
+        match self {
+            NodeMode::Full => Some(FULL_NODE_STATE_HISTORY),
+            NodeMode::Archive => None,
+            NodeMode::Pruned(blocks) => Some(*blocks),
+        }
+    }
+
+    /// Returns the configuration of the [Pruner] that removes the state history the node doesn't
+    /// keep.
+    pub fn pruner_config(&self) -> PrunerConfig {
+        let blocks = self.state_history_blocks();
+        PrunerConfig { account_history: blocks, storage_history: blocks, ..Default::default() }
+    }
+}
+
+impl fmt::Display for NodeMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeMode::Full => f.write_str("full"),
+            NodeMode::Archive => f.write_str("archive"),
+            NodeMode::Pruned(blocks) => write!(f, "pruned:{blocks}"),
+        }
+    }
+}
+
+/// Error when parsing a [NodeMode]
+#[derive(Debug, thiserror::Error)]
+pub enum ParseNodeModeError {
+    /// Failed to parse the number of blocks of [NodeMode::Pruned]
+    #[error(transparent)]
+    ParseIntError(#[from] ParseIntError),
+    /// Failed to parse due to unknown variant
+    #[error("Unknown node mode: {0}")]
+    UnknownVariant(String),
+}
+
+impl FromStr for NodeMode {
+    type Err = ParseNodeModeError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mode = match s {
+            "full" => NodeMode::Full,
+            "archive" => NodeMode::Archive,
+            s => {
+                if let Some(blocks) = s.strip_prefix("pruned:") {
+                    NodeMode::Pruned(blocks.parse()?)
+                } else {
+                    return Err(ParseNodeModeError::UnknownVariant(s.to_string()))
+                }
+            }
+        };
+        Ok(mode)
+    }
+}
+
+/// Configures which parts of the history are pruned, and how many of the most recent finalized
+/// blocks are kept for each of them.
+///
+/// Parts without a retention window are never pruned, which is what archive nodes need.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrunerConfig {
+    /// Number of blocks to keep the account change sets for.
+    pub account_history: Option<u64>,
+    /// Number of blocks to keep the storage change sets for.
+    pub storage_history: Option<u64>,
+    /// Number of blocks to keep the transaction hash to number lookup for.
+    pub transaction_lookup: Option<u64>,
+    /// Maximum number of entries deleted in a single database transaction.
+    pub batch_size: usize,
+}
+
+impl PrunerConfig {
+    /// Returns a config that keeps all parts for the given number of blocks.
+    pub fn recent_history(keep_history_blocks: u64) -> Self {
+        Self {
+            account_history: Some(keep_history_blocks),
+            storage_history: Some(keep_history_blocks),
+            transaction_lookup: Some(keep_history_blocks),
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for PrunerConfig {
+    fn default() -> Self {
+        Self {
+            account_history: None,
+            storage_history: None,
+            transaction_lookup: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+}
+
+/// A part of the history that can be pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrunePart {
+    /// The [tables::AccountChangeSet] table.
+    AccountHistory,
+    /// The [tables::StorageChangeSet] table.
+    StorageHistory,
+    /// The [tables::TxHashNumber] table.
+    TransactionLookup,
+}
+
+impl PrunePart {
+    /// The key of the checkpoint in [tables::SyncStageProgress].
+    fn checkpoint_key(&self) -> &'static str {
+        match self {
+            PrunePart::AccountHistory => "AccountHistoryPruner",
+            PrunePart::StorageHistory => "StorageHistoryPruner",
+            PrunePart::TransactionLookup => "TransactionLookupPruner",
+        }
+    }
+}
+
+/// Returns the block before which the data of the part has been pruned, if any.
+pub fn get_prune_checkpoint<'a, TX: DbTx<'a>>(
+    tx: &TX,
+    part: PrunePart,
+) -> std::result::Result<Option<BlockNumber>, DbError> {
+    let checkpoint = tx.get::<tables::SyncStageProgress>(part.checkpoint_key().into())?;
+    Ok(checkpoint.and_then(|buf| buf.try_into().ok()).map(BlockNumber::from_be_bytes))
+}
+
+/// Saves the block before which the data of the part has been pruned.
+///
+/// The change sets before the block must not be read afterwards, even if they are still present.
+pub fn save_prune_checkpoint<'a, TX: DbTxMut<'a>>(
+    tx: &TX,
+    part: PrunePart,
+    block: BlockNumber,
+) -> std::result::Result<(), DbError> {
+    tx.put::<tables::SyncStageProgress>(part.checkpoint_key().into(), block.to_be_bytes().to_vec())
+}
+
+/// Returns the block before which the state history, i.e. the account or the storage change sets,
+/// has been pruned, if any.
+pub fn get_state_history_checkpoint<'a, TX: DbTx<'a>>(
+    tx: &TX,
+) -> std::result::Result<Option<BlockNumber>, DbError> {
+    Ok(get_prune_checkpoint(tx, PrunePart::AccountHistory)?
+        .max(get_prune_checkpoint(tx, PrunePart::StorageHistory)?))
+}
+
+/// Returns an error if the state history of the block has been pruned.
+pub(crate) fn ensure_state_history_available<'a, TX: DbTx<'a>>(
+    tx: &TX,
+    block_number: BlockNumber,
+) -> Result<()> {
+    if get_state_history_checkpoint(tx)?.map_or(false, |pruned| block_number < pruned) {
+        return Err(ProviderError::StateHistoryPruned { block_number }.into())
+    }
+    Ok(())
+}
+
+/// Deletes history that is no longer needed from the database.
+///
+/// The pruner is intended to be run after a new block was finalized. Entries are deleted in
+/// batches of [`PrunerConfig::batch_size`], with every batch committed in its own transaction, to
+/// avoid long write stalls.
+#[derive(Debug)]
+pub struct Pruner<DB> {
+    /// The database to prune.
+    db: DB,
+    /// The pruning configuration.
+    config: PrunerConfig,
+}
+
+impl<DB: Database> Pruner<DB> {
+    /// Creates a new pruner for the given database.
+    pub fn new(db: DB, config: PrunerConfig) -> Self {
+        Self { db, config }
+    }
+
+    /// Prunes all configured parts relative to the given finalized block.
+    pub fn run(&self, finalized_block: BlockNumber) -> Result<()> {
+        let prune_to = |keep: Option<u64>| keep.and_then(|keep| finalized_block.checked_sub(keep));
+
+        if let Some(to_block) = prune_to(self.config.account_history) {
+            self.prune_changesets::<tables::AccountChangeSet, _>(
+                PrunePart::AccountHistory,
+                to_block,
+                |transition| *transition,
+            )?;
+        }
+        if let Some(to_block) = prune_to(self.config.storage_history) {
+            self.prune_changesets::<tables::StorageChangeSet, _>(
+                PrunePart::StorageHistory,
+                to_block,
+                |key| key.transition_id(),
+            )?;
+        }
+        if let Some(to_block) = prune_to(self.config.transaction_lookup) {
+            self.prune_transaction_lookup(to_block)?;
+        }
+        Ok(())
+    }
+
+    /// Deletes all change sets that are only needed for the state of blocks before `to_block`.
+    fn prune_changesets<T, F>(
+        &self,
+        part: PrunePart,
+        to_block: BlockNumber,
+        selector: F,
+    ) -> Result<()>
+    where
+        T: Table,
+        F: Fn(&T::Key) -> u64,
+    {
+        let tx = self.db.tx_mut()?;
+
+        // the state at `to_block` is computed with the change sets from its end transition onwards
+        let to_transition = tx
+            .get::<tables::BlockTransitionIndex>(to_block)?
+            .ok_or(ProviderError::BlockTransition { block_number: to_block })?;
+
+        // the checkpoint is saved first, so the partially pruned history is never read. It may be
+        // ahead already if the execution stage skipped writing the change sets, but older change
+        // sets can still be left over and are deleted regardless.
+        if get_prune_checkpoint(&tx, part)?.map_or(true, |pruned| pruned < to_block) {
+            save_prune_checkpoint(&tx, part, to_block)?;
+        }
+        tx.commit()?;
+
+        loop {
+            let tx = self.db.tx_mut()?;
+            let mut cursor = tx.cursor_write::<T>()?;
+
+            let mut deleted = 0;
+            while let Some((key, _)) = cursor.first()? {
+                if selector(&key) >= to_transition {
+                    break
+                }
+                cursor.delete_current()?;
+                deleted += 1;
+                if deleted == self.config.batch_size {
+                    break
+                }
+            }
+            drop(cursor);
+            tx.commit()?;
+
+            if deleted < self.config.batch_size {
+                return Ok(())
+            }
+        }
+    }
+
+    /// Deletes the transaction hash to number lookup of all transactions before `to_block`.
+    fn prune_transaction_lookup(&self, to_block: BlockNumber) -> Result<()> {
+        let part = PrunePart::TransactionLookup;
+        loop {
+            let tx = self.db.tx_mut()?;
+            let from_block = get_prune_checkpoint(&tx, part)?.unwrap_or_default();
+            if from_block >= to_block {
+                return Ok(())
+            }
+
+            let mut body_cursor = tx.cursor_read::<tables::BlockBodies>()?;
+            let mut transaction_cursor = tx.cursor_read::<tables::Transactions>()?;
+            let mut tx_hash_number_cursor = tx.cursor_write::<tables::TxHashNumber>()?;
+
+            let mut deleted = 0;
+            let mut pruned_to = from_block;
+            for entry in body_cursor.walk_range(from_block..to_block)? {
+                let (number, body) = entry?;
+                for tx_id in body.tx_id_range() {
+                    if let Some((_, transaction)) = transaction_cursor.seek_exact(tx_id)? {
+                        if tx_hash_number_cursor.seek_exact(transaction.hash)?.is_some() {
+                            tx_hash_number_cursor.delete_current()?;
+                            deleted += 1;
+                        }
+                    }
+                }
+                pruned_to = number + 1;
+                if deleted >= self.config.batch_size {
+                    break
+                }
+            }
+            drop((body_cursor, transaction_cursor, tx_hash_number_cursor));
+
+            // the next run continues with the first block that was not pruned
+            save_prune_checkpoint(
+                &tx,
+                part,
+                if pruned_to > from_block { pruned_to } else { to_block },
+            )?;
+            tx.commit()?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountProvider, ShareableDatabase, StateProviderFactory};
+    use reth_db::{
+        mdbx::test_utils::create_test_rw_db,
+        models::{AccountBeforeTx, StoredBlockBody},
+    };
+    use reth_interfaces::Error;
+    use reth_primitives::{
+        Address, ChainSpecBuilder, Signature, StorageEntry, Transaction, TransactionSigned,
+        TxLegacy, H256, U256,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn prune_state_history() {
+        let db = create_test_rw_db();
+        let address = Address::random();
+
+        let tx = db.tx_mut().unwrap();
+        // one transition per block
+        for block in 0..4u64 {
+            tx.put::<tables::BlockTransitionIndex>(block, block + 1).unwrap();
+            tx.put::<tables::AccountChangeSet>(
+                block,
+                AccountBeforeTx { address, info: Default::default() },
+            )
+            .unwrap();
+            tx.put::<tables::StorageChangeSet>(
+                (block, address).into(),
+                StorageEntry { key: H256::zero(), value: U256::from(block) },
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+
+        let config = PrunerConfig { batch_size: 1, ..PrunerConfig::recent_history(1) };
+        Pruner::new(db.clone(), config).run(3).unwrap();
+
+        let tx = db.tx().unwrap();
+        let account_transitions = tx
+            .cursor_read::<tables::AccountChangeSet>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(account_transitions, vec![3]);
+        assert_eq!(
+            tx.cursor_read::<tables::StorageChangeSet>().unwrap().walk(None).unwrap().count(),
+            1
+        );
+        assert_eq!(get_prune_checkpoint(&tx, PrunePart::AccountHistory), Ok(Some(2)));
+        assert_eq!(get_prune_checkpoint(&tx, PrunePart::StorageHistory), Ok(Some(2)));
+        drop(tx);
+
+        let provider = ShareableDatabase::new(db, Arc::new(ChainSpecBuilder::mainnet().build()));
+        assert_eq!(
+            provider.history_by_block_number(1).err(),
+            Some(Error::Provider(ProviderError::StateHistoryPruned { block_number: 1 }))
+        );
+        assert!(provider.history_by_block_number(2).unwrap().basic_account(address).is_ok());
+    }
+
+    #[test]
+    fn prune_changesets_behind_checkpoint() {
+        let db = create_test_rw_db();
+        let address = Address::random();
+
+        let tx = db.tx_mut().unwrap();
+        for block in 0..4u64 {
+            tx.put::<tables::BlockTransitionIndex>(block, block + 1).unwrap();
+        }
+        // the change sets of block 1 have been written before the execution stage started to
+        // skip them
+        tx.put::<tables::AccountChangeSet>(1, AccountBeforeTx { address, info: None }).unwrap();
+        tx.put::<tables::AccountChangeSet>(3, AccountBeforeTx { address, info: None }).unwrap();
+        save_prune_checkpoint(&tx, PrunePart::AccountHistory, 3).unwrap();
+        tx.commit().unwrap();
+
+        Pruner::new(db.clone(), PrunerConfig::recent_history(1)).run(3).unwrap();
+
+        let tx = db.tx().unwrap();
+        let account_transitions = tx
+            .cursor_read::<tables::AccountChangeSet>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(account_transitions, vec![3]);
+        // the checkpoint is not moved back
+        assert_eq!(get_prune_checkpoint(&tx, PrunePart::AccountHistory), Ok(Some(3)));
+        assert_eq!(get_state_history_checkpoint(&tx), Ok(Some(3)));
+    }
+
+    #[test]
+    fn parse_node_mode() {
+        for mode in [NodeMode::Full, NodeMode::Archive, NodeMode::Pruned(1000)] {
+            assert_eq!(mode.to_string().parse::<NodeMode>().unwrap(), mode);
+        }
+        assert!("pruned:".parse::<NodeMode>().is_err());
+        assert!("light".parse::<NodeMode>().is_err());
+
+        assert_eq!(
+            NodeMode::Full.pruner_config(),
+            PrunerConfig {
+                account_history: Some(FULL_NODE_STATE_HISTORY),
+                storage_history: Some(FULL_NODE_STATE_HISTORY),
+                ..Default::default()
+            }
+        );
+        assert_eq!(NodeMode::Archive.pruner_config(), PrunerConfig::default());
+    }
+
+    #[test]
+    fn prune_transaction_lookup() {
+        let db = create_test_rw_db();
+
+        let tx = db.tx_mut().unwrap();
+        let mut hashes = Vec::new();
+        for block in 0..4u64 {
+            tx.put::<tables::BlockBodies>(
+                block,
+                StoredBlockBody { start_tx_id: block, tx_count: 1 },
+            )
+            .unwrap();
+            let transaction = TransactionSigned::from_transaction_and_signature(
+                Transaction::Legacy(TxLegacy { nonce: block, ..Default::default() }),
+                Signature::default(),
+            );
+            tx.put::<tables::TxHashNumber>(transaction.hash, block).unwrap();
+            hashes.push(transaction.hash);
+            tx.put::<tables::Transactions>(block, transaction).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let config =
+            PrunerConfig { transaction_lookup: Some(1), batch_size: 1, ..Default::default() };
+        Pruner::new(db.clone(), config).run(3).unwrap();
+
+        let tx = db.tx().unwrap();
+        let lookups = hashes
+            .iter()
+            .map(|hash| tx.get::<tables::TxHashNumber>(*hash).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lookups, vec![None, None, Some(2), Some(3)]);
+        assert_eq!(get_prune_checkpoint(&tx, PrunePart::TransactionLookup), Ok(Some(2)));
+        // change sets are kept
+        assert_eq!(get_prune_checkpoint(&tx, PrunePart::AccountHistory), Ok(None));
+    }
+}
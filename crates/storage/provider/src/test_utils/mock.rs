@@ -1,9 +1,10 @@
 use crate::{
     traits::ReceiptProvider, AccountProvider, BlockHashProvider, BlockIdProvider, BlockProvider,
-    EvmEnvProvider, HeaderProvider, StateProvider, StateProviderFactory, TransactionsProvider,
+    EvmEnvProvider, HeaderProvider, NodeDataProvider, StateProvider, StateProviderFactory,
+    TransactionsProvider,
 };
 use parking_lot::Mutex;
-use reth_interfaces::Result;
+use reth_interfaces::{consensus::ForkchoiceState, Result};
 use reth_primitives::{
     keccak256, Account, Address, Block, BlockHash, BlockId, BlockNumber, BlockNumberOrTag,
     Bytecode, Bytes, ChainInfo, Header, Receipt, StorageKey, StorageValue, TransactionSigned,
@@ -21,6 +22,10 @@ pub struct MockEthProvider {
     pub headers: Arc<Mutex<HashMap<H256, Header>>>,
     /// Local account store
     pub accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
+    /// Local receipt store by block number
+    pub receipts: Arc<Mutex<HashMap<BlockNumber, Vec<Receipt>>>>,
+    /// The latest forkchoice state
+    pub forkchoice_state: Arc<Mutex<ForkchoiceState>>,
 }
 
 /// An extended account for local store
@@ -48,6 +53,15 @@ impl ExtendedAccount {
         self.bytecode = Some(Bytecode::new_raw(bytecode.into()));
         self
     }
+
+    /// Add storage to the extended account
+    pub fn extend_storage(
+        mut self,
+        storage: impl IntoIterator<Item = (StorageKey, StorageValue)>,
+    ) -> Self {
+        self.storage.extend(storage);
+        self
+    }
 }
 
 impl MockEthProvider {
@@ -57,6 +71,11 @@ impl MockEthProvider {
         self.blocks.lock().insert(hash, block);
     }
 
+    /// Add the receipts of a block to local receipt store
+    pub fn add_receipts(&self, block_number: BlockNumber, receipts: Vec<Receipt>) {
+        self.receipts.lock().insert(block_number, receipts);
+    }
+
     /// Add multiple blocks to local block store
     pub fn extend_blocks(&self, iter: impl IntoIterator<Item = (H256, Block)>) {
         for (hash, block) in iter.into_iter() {
@@ -65,6 +84,11 @@ impl MockEthProvider {
         }
     }
 
+    /// Set the forkchoice state that resolves the safe and finalized blocks
+    pub fn set_forkchoice_state(&self, state: ForkchoiceState) {
+        *self.forkchoice_state.lock() = state;
+    }
+
     /// Add header to local header store
     pub fn add_header(&self, hash: H256, header: Header) {
         self.headers.lock().insert(hash, header);
@@ -170,8 +194,19 @@ impl ReceiptProvider for MockEthProvider {
         Ok(None)
     }
 
-    fn receipts_by_block(&self, _block: BlockId) -> Result<Option<Vec<Receipt>>> {
-        Ok(None)
+    fn receipts_by_block(&self, block: BlockId) -> Result<Option<Vec<Receipt>>> {
+        let Some(number) = self.block_number_for_id(block)? else { return Ok(None) };
+        Ok(self.receipts.lock().get(&number).cloned())
+    }
+}
+
+impl NodeDataProvider for MockEthProvider {
+    fn node_data(&self, hash: H256) -> Result<Option<Bytes>> {
+        let lock = self.accounts.lock();
+        Ok(lock.values().find_map(|account| {
+            let code = account.bytecode.as_ref()?.original_bytes();
+            (keccak256(&code) == hash).then(|| code.into())
+        }))
     }
 }
 
@@ -197,15 +232,17 @@ impl BlockHashProvider for MockEthProvider {
 
 impl BlockIdProvider for MockEthProvider {
     fn chain_info(&self) -> Result<ChainInfo> {
+        let forkchoice_state = self.forkchoice_state.lock().clone();
         let lock = self.headers.lock();
+        let number_of = |hash: H256| lock.get(&hash).map(|header| header.number);
         Ok(lock
             .iter()
             .max_by_key(|h| h.1.number)
             .map(|(hash, header)| ChainInfo {
                 best_hash: *hash,
                 best_number: header.number,
-                last_finalized: None,
-                safe_finalized: None,
+                last_finalized: number_of(forkchoice_state.finalized_block_hash),
+                safe_finalized: number_of(forkchoice_state.safe_block_hash),
             })
             .expect("provider is empty"))
     }
@@ -219,15 +256,15 @@ impl BlockIdProvider for MockEthProvider {
 
 impl BlockProvider for MockEthProvider {
     fn block(&self, id: BlockId) -> Result<Option<Block>> {
-        let lock = self.blocks.lock();
         match id {
-            BlockId::Hash(hash) => Ok(lock.get(hash.as_ref()).cloned()),
+            BlockId::Hash(hash) => Ok(self.blocks.lock().get(hash.as_ref()).cloned()),
             BlockId::Number(BlockNumberOrTag::Number(num)) => {
-                Ok(lock.values().find(|b| b.number == num).cloned())
-            }
-            _ => {
-                unreachable!("unused in network tests")
+                Ok(self.blocks.lock().values().find(|b| b.number == num).cloned())
             }
+            BlockId::Number(tag) => match self.convert_block_number(tag)? {
+                Some(num) => self.block(num.into()),
+                None => Ok(None),
+            },
         }
     }
 
@@ -240,6 +277,19 @@ impl AccountProvider for MockEthProvider {
     fn basic_account(&self, address: Address) -> Result<Option<Account>> {
         Ok(self.accounts.lock().get(&address).cloned().map(|a| a.account))
     }
+
+    fn account_range(&self, start: Address, limit: usize) -> Result<Vec<Address>> {
+        let mut addresses = self
+            .accounts
+            .lock()
+            .keys()
+            .filter(|address| **address >= start)
+            .copied()
+            .collect::<Vec<_>>();
+        addresses.sort_unstable();
+        addresses.truncate(limit);
+        Ok(addresses)
+    }
 }
 
 impl StateProvider for MockEthProvider {
@@ -1,6 +1,7 @@
 use crate::{
     traits::ReceiptProvider, AccountProvider, BlockHashProvider, BlockIdProvider, BlockProvider,
-    EvmEnvProvider, HeaderProvider, StateProvider, StateProviderFactory, TransactionsProvider,
+    EvmEnvProvider, HeaderProvider, NodeDataProvider, StateProvider, StateProviderFactory,
+    TransactionsProvider,
 };
 use reth_interfaces::Result;
 use reth_primitives::{
@@ -82,6 +83,12 @@ impl ReceiptProvider for NoopProvider {
     }
 }
 
+impl NodeDataProvider for NoopProvider {
+    fn node_data(&self, _hash: H256) -> Result<Option<Bytes>> {
+        Ok(None)
+    }
+}
+
 impl HeaderProvider for NoopProvider {
     fn header(&self, _block_hash: &BlockHash) -> Result<Option<Header>> {
         Ok(None)
@@ -108,6 +115,10 @@ impl AccountProvider for NoopProvider {
     fn basic_account(&self, _address: Address) -> Result<Option<Account>> {
         Ok(None)
     }
+
+    fn account_range(&self, _start: Address, _limit: usize) -> Result<Vec<Address>> {
+        Ok(vec![])
+    }
 }
 
 impl StateProvider for NoopProvider {
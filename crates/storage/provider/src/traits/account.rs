@@ -1,5 +1,5 @@
 use auto_impl::auto_impl;
-use reth_interfaces::Result;
+use reth_interfaces::{provider::ProviderError, Result};
 use reth_primitives::{Account, Address};
 
 /// Account provider
@@ -7,4 +7,12 @@ use reth_primitives::{Account, Address};
 pub trait AccountProvider: Send + Sync {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> Result<Option<Account>>;
+
+    /// Get the addresses of up to `limit` accounts in ascending order, starting at the given
+    /// address.
+    ///
+    /// Only supported by providers of the latest state.
+    fn account_range(&self, _start: Address, _limit: usize) -> Result<Vec<Address>> {
+        Err(ProviderError::HistoryAccountRange.into())
+    }
 }
@@ -0,0 +1,58 @@
+use reth_primitives::SealedBlock;
+use tokio::sync::broadcast;
+
+/// A receiver of [CanonStateNotification]s.
+pub type CanonStateNotifications = broadcast::Receiver<CanonStateNotification>;
+
+/// A sender of [CanonStateNotification]s.
+pub type CanonStateNotificationSender = broadcast::Sender<CanonStateNotification>;
+
+/// A type that notifies about changes of the canonical chain.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait CanonStateSubscriptions: Send + Sync {
+    /// Returns a new receiver of the changes of the canonical chain.
+    ///
+    /// A receiver that falls behind by more than the capacity of the channel misses the oldest
+    /// notifications.
+    fn subscribe_canon_state(&self) -> CanonStateNotifications;
+}
+
+/// A change of the canonical chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanonStateNotification {
+    /// Blocks were appended to the canonical chain.
+    Committed {
+        /// The new canonical blocks, in ascending order.
+        new_blocks: Vec<SealedBlock>,
+    },
+    /// Blocks of the canonical chain were replaced by the blocks of a fork.
+    Reorganized {
+        /// The blocks that are no longer canonical, in ascending order.
+        old_blocks: Vec<SealedBlock>,
+        /// The new canonical blocks, in ascending order.
+        new_blocks: Vec<SealedBlock>,
+    },
+}
+
+impl CanonStateNotification {
+    /// Returns the blocks that are no longer canonical.
+    pub fn old_blocks(&self) -> &[SealedBlock] {
+        match self {
+            CanonStateNotification::Committed { .. } => &[],
+            CanonStateNotification::Reorganized { old_blocks, .. } => old_blocks,
+        }
+    }
+
+    /// Returns the new canonical blocks.
+    pub fn new_blocks(&self) -> &[SealedBlock] {
+        match self {
+            CanonStateNotification::Committed { new_blocks } |
+            CanonStateNotification::Reorganized { new_blocks, .. } => new_blocks,
+        }
+    }
+
+    /// Returns the new tip of the canonical chain.
+    pub fn tip(&self) -> Option<&SealedBlock> {
+        self.new_blocks().last()
+    }
+}
@@ -12,12 +12,21 @@ pub use block_hash::BlockHashProvider;
 mod block_id;
 pub use block_id::BlockIdProvider;
 
+mod chain;
+pub use chain::{
+    CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
+    CanonStateSubscriptions,
+};
+
 mod evm_env;
 pub use evm_env::EvmEnvProvider;
 
 mod header;
 pub use header::HeaderProvider;
 
+mod node_data;
+pub use node_data::NodeDataProvider;
+
 mod receipts;
 pub use receipts::ReceiptProvider;
 
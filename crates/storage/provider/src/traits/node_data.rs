@@ -0,0 +1,10 @@
+use auto_impl::auto_impl;
+use reth_interfaces::Result;
+use reth_primitives::{Bytes, H256};
+
+/// Client trait for fetching the state data that is served via `GetNodeData`.
+#[auto_impl(&, Arc)]
+pub trait NodeDataProvider: Send + Sync {
+    /// Get the raw bytes of the trie node or contract code with the given keccak256 hash.
+    fn node_data(&self, hash: H256) -> Result<Option<Bytes>>;
+}
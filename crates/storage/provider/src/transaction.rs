@@ -1,6 +1,7 @@
 use crate::{
     insert_canonical_block,
     post_state::{Change, PostState, StorageChangeset},
+    pruner::get_state_history_checkpoint,
     trie::{DBTrieLoader, TrieError},
 };
 use itertools::{izip, Itertools};
@@ -154,6 +155,13 @@ where
         Ok(last_transition_id)
     }
 
+    /// Returns `true` if the state history of the block, i.e. the change sets from the last
+    /// transition of the block onwards, is incomplete because it has been pruned.
+    pub fn is_state_history_pruned(&self, block: BlockNumber) -> Result<bool, TransactionError> {
+        let checkpoint = get_state_history_checkpoint(&**self)?;
+        Ok(checkpoint.map_or(false, |pruned| block < pruned))
+    }
+
     /// Get the next start transaction id and transition for the `block` by looking at the previous
     /// block. Returns Zero/Zero for Genesis.
     pub fn get_next_block_ids(
@@ -105,7 +105,7 @@ pub fn insert_block<'a, TX: DbTxMut<'a> + DbTx<'a>>(
         }
     }
 
-    if has_block_reward || has_withdrawals {
+    if has_block_reward || has_withdrawals || block.header.has_beacon_root_block_transition() {
         transition_id += 1;
     }
     tx.put::<tables::BlockTransitionIndex>(block_number, transition_id)?;
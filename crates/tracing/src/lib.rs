@@ -9,6 +9,7 @@
 //! Contains a standardized set of layers:
 //!
 //! - [`stdout()`]
+//! - [`stdout_json()`]
 //! - [`file()`]
 //! - [`journald()`]
 //!
@@ -37,6 +38,9 @@ pub fn init(layers: Vec<BoxedLayer<Registry>>) {
 ///
 /// Colors can be disabled with `RUST_LOG_STYLE=never`, and event targets can be displayed with
 /// `RUST_LOG_TARGET=1`.
+///
+/// Individual components can be made more verbose with the targets of their events, e.g.
+/// `RUST_LOG=info,sync::stages::execution=debug` logs the execution stage at the debug level.
 pub fn stdout<S>(default_directive: impl Into<Directive>) -> BoxedLayer<S>
 where
     S: Subscriber,
@@ -46,16 +50,38 @@ where
     let with_ansi = std::env::var("RUST_LOG_STYLE").map(|val| val != "never").unwrap_or(true);
     let with_target = std::env::var("RUST_LOG_TARGET").map(|val| val != "0").unwrap_or(false);
 
-    let filter =
-        EnvFilter::builder().with_default_directive(default_directive.into()).from_env_lossy();
-
     tracing_subscriber::fmt::layer()
         .with_ansi(with_ansi)
         .with_target(with_target)
-        .with_filter(filter)
+        .with_filter(env_filter(default_directive))
         .boxed()
 }
 
+/// Builds a new tracing layer that writes events to stdout as newline-delimited JSON.
+///
+/// Every event includes its target and the spans it was emitted in, e.g. the stage of the
+/// pipeline, which makes the output suitable for log aggregation systems like Loki or
+/// Elasticsearch.
+///
+/// The events are filtered by `default_directive`, unless overriden by `RUST_LOG`.
+pub fn stdout_json<S>(default_directive: impl Into<Directive>) -> BoxedLayer<S>
+where
+    S: Subscriber,
+    for<'a> S: LookupSpan<'a>,
+{
+    tracing_subscriber::fmt::layer()
+        .json()
+        .with_current_span(true)
+        .with_span_list(true)
+        .with_filter(env_filter(default_directive))
+        .boxed()
+}
+
+/// Returns a filter of the `RUST_LOG` directives, falling back to `default_directive`.
+fn env_filter(default_directive: impl Into<Directive>) -> EnvFilter {
+    EnvFilter::builder().with_default_directive(default_directive.into()).from_env_lossy()
+}
+
 /// Builds a new tracing layer that appends to a log file.
 ///
 /// The events are filtered by `directive`.
@@ -1,6 +1,13 @@
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub(crate) const MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
+/// Default time (in seconds) a transaction can stay parked before it is evicted, compatible with
+/// geth.
+pub(crate) const DEFAULT_TRANSACTION_MAX_IDLE_SECONDS: u64 = 3600;
+
+/// Default price bump (in %) for the transaction pool underpriced check, compatible with geth.
+pub(crate) const DEFAULT_PRICE_BUMP: u64 = 10;
+
 ///! Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -10,8 +17,18 @@ pub struct PoolConfig {
     pub basefee_limit: SubPoolLimit,
     /// Max number of transaction in the queued sub-pool
     pub queued_limit: SubPoolLimit,
+    /// Max number of transaction in the blob sub-pool
+    pub blob_limit: SubPoolLimit,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: usize,
+    /// Minimum price bump (in %) required to replace an existing transaction with the same
+    /// sender and nonce.
+    pub replacement_price_bump_percent: u64,
+    /// Max time (in seconds) a transaction can stay in the queued or basefee sub-pool before it is
+    /// evicted.
+    pub transaction_max_idle_seconds: u64,
+    /// Whether local transactions are also evicted after `transaction_max_idle_seconds`.
+    pub expire_locals: bool,
 }
 
 impl Default for PoolConfig {
@@ -20,7 +37,11 @@ impl Default for PoolConfig {
             pending_limit: Default::default(),
             basefee_limit: Default::default(),
             queued_limit: Default::default(),
+            blob_limit: Default::default(),
             max_account_slots: MAX_ACCOUNT_SLOTS_PER_SENDER,
+            replacement_price_bump_percent: DEFAULT_PRICE_BUMP,
+            transaction_max_idle_seconds: DEFAULT_TRANSACTION_MAX_IDLE_SECONDS,
+            expire_locals: false,
         }
     }
 }
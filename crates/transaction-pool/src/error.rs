@@ -14,9 +14,10 @@ pub enum PoolError {
     /// Encountered a transaction that was already added into the poll
     #[error("[{0:?}] Transaction feeCap {1} below chain minimum.")]
     ProtocolFeeCapTooLow(TxHash, u128),
-    /// Thrown when the number of unique transactions of a sender exceeded the slot capacity.
-    #[error("{0:?} identified as spammer. Transaction {1:?} rejected.")]
-    SpammerExceededCapacity(Address, TxHash),
+    /// Thrown when a sender already occupies all of its account slots and the new transaction
+    /// doesn't replace an existing one.
+    #[error("{0:?} has too many transactions in the pool. Transaction {1:?} rejected.")]
+    TooManyTransactions(Address, TxHash),
     /// Thrown when a new transaction is added to the pool, but then immediately discarded to
     /// respect the size limits of the pool.
     #[error("[{0:?}] Transaction discarded outright due to pool size constraints.")]
@@ -38,7 +39,7 @@ impl PoolError {
         match self {
             PoolError::ReplacementUnderpriced(hash) => hash,
             PoolError::ProtocolFeeCapTooLow(hash, _) => hash,
-            PoolError::SpammerExceededCapacity(_, hash) => hash,
+            PoolError::TooManyTransactions(_, hash) => hash,
             PoolError::DiscardedOnInsert(hash) => hash,
             PoolError::InvalidTransaction(hash, _) => hash,
             PoolError::Other(hash, _) => hash,
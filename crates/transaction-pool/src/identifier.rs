@@ -17,7 +17,6 @@ pub(crate) struct SenderIdentifiers {
 
 impl SenderIdentifiers {
     /// Returns the address for the given identifier.
-    #[allow(unused)]
     pub(crate) fn address(&self, id: &SenderId) -> Option<&Address> {
         self.sender_to_address.get(id)
     }
@@ -27,6 +26,12 @@ impl SenderIdentifiers {
         self.address_to_id.get(addr).copied()
     }
 
+    /// Returns the existing `SenderId` or the one that would be assigned next, without assigning
+    /// it.
+    pub(crate) fn peek_sender_id(&self, addr: &Address) -> SenderId {
+        self.sender_id(addr).unwrap_or(SenderId(self.id))
+    }
+
     /// Returns the existing `SendId` or assigns a new one if it's missing
     pub(crate) fn sender_id_or_create(&mut self, addr: Address) -> SenderId {
         if let Some(id) = self.sender_id(&addr) {
@@ -57,7 +62,6 @@ pub struct SenderId(u64);
 
 impl SenderId {
     /// Returns a `Bound` for `TransactionId` starting with nonce `0`
-    #[cfg(test)]
     pub(crate) fn start_bound(self) -> std::ops::Bound<TransactionId> {
         std::ops::Bound::Included(TransactionId::new(self, 0))
     }
@@ -152,4 +156,17 @@ mod tests {
         let set = BTreeSet::from([tx1, tx2]);
         assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![tx1, tx2]);
     }
+
+    #[test]
+    fn test_peek_sender_id() {
+        let mut identifiers = SenderIdentifiers::default();
+        let (known, unknown) = (Address::random(), Address::random());
+        let id = identifiers.sender_id_or_create(known);
+        assert_eq!(identifiers.peek_sender_id(&known), id);
+
+        let next = identifiers.peek_sender_id(&unknown);
+        assert_ne!(next, id);
+        assert_eq!(identifiers.sender_id(&unknown), None);
+        assert_eq!(identifiers.sender_id_or_create(unknown), next);
+    }
 }
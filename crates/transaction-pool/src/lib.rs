@@ -93,20 +93,23 @@ pub use crate::{
 };
 use crate::{
     error::PoolResult,
+    metrics::PoolMetrics,
     pool::PoolInner,
     traits::{NewTransactionEvent, PoolSize},
 };
 
 use crate::error::PoolError;
-use reth_primitives::{TxHash, U256};
+use reth_primitives::{Address, TxHash, U256};
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::mpsc::Receiver;
 
 mod config;
 pub mod error;
 mod identifier;
+pub mod maintain;
 pub mod metrics;
 mod ordering;
+mod persist;
 pub mod pool;
 mod traits;
 mod validate;
@@ -149,7 +152,18 @@ where
 {
     /// Create a new transaction pool instance.
     pub fn new(validator: V, ordering: T, config: PoolConfig) -> Self {
-        Self { pool: Arc::new(PoolInner::new(validator, ordering, config)) }
+        Self::with_metrics(validator, ordering, config, Default::default())
+    }
+
+    /// Create a new transaction pool instance that records its sub-pool metrics to the given
+    /// [`PoolMetrics`].
+    pub fn with_metrics(
+        validator: V,
+        ordering: T,
+        config: PoolConfig,
+        metrics: PoolMetrics,
+    ) -> Self {
+        Self { pool: Arc::new(PoolInner::new(validator, ordering, config, metrics)) }
     }
 
     /// Returns the wrapped pool.
@@ -218,6 +232,14 @@ where
         self.pool.size()
     }
 
+    fn pending_count(&self) -> usize {
+        self.pool.pending_count()
+    }
+
+    fn queued_count(&self) -> usize {
+        self.pool.queued_count()
+    }
+
     fn on_new_block(&self, event: OnNewBlockEvent) {
         self.pool.on_new_block(event);
     }
@@ -242,6 +264,15 @@ where
         }
     }
 
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> PoolResult<TxHash> {
+        let (_, tx) = self.validate(origin, transaction).await;
+        self.pool.check_transaction(origin, tx)
+    }
+
     async fn add_transactions(
         &self,
         origin: TransactionOrigin,
@@ -275,6 +306,10 @@ where
         Box::new(self.pool.best_transactions())
     }
 
+    fn drain_pending(&self, max_count: usize) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.drain_pending(max_count)
+    }
+
     fn remove_invalid(
         &self,
         hashes: impl IntoIterator<Item = TxHash>,
@@ -297,6 +332,24 @@ where
         self.inner().get_all(txs)
     }
 
+    fn get_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.inner().get_transactions_by_sender(sender)
+    }
+
+    fn get_pending_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.inner().get_pending_transactions_by_sender(sender)
+    }
+
+    fn all_senders(&self) -> Vec<Address> {
+        self.inner().all_senders()
+    }
+
     fn on_propagated(&self, txs: PropagatedTransactions) {
         self.inner().on_propagated(txs)
     }
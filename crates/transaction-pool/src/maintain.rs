@@ -0,0 +1,108 @@
+//! Support for keeping the pool in sync with the canonical chain.
+
+use crate::{traits::StateDiff, OnNewBlockEvent, TransactionOrigin, TransactionPool};
+use reth_consensus_common::validation::{blob_gasprice, calculate_excess_blob_gas};
+use reth_primitives::{calculate_next_block_base_fee, FromRecoveredTransaction};
+use reth_provider::{CanonStateNotification, CanonStateSubscriptions};
+use std::collections::HashSet;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, warn};
+
+/// Updates the pool on every change of the canonical chain, until the notifications end.
+pub async fn maintain_transaction_pool<Client, Pool>(client: Client, pool: Pool)
+where
+    Client: CanonStateSubscriptions,
+    Pool: TransactionPool,
+{
+    let mut notifications = client.subscribe_canon_state();
+    loop {
+        match notifications.recv().await {
+            Ok(notification) => on_canon_state_change(&pool, notification).await,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(target: "txpool", skipped, "Missed canonical state notifications");
+            }
+            Err(RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Applies a change of the canonical chain to the pool.
+///
+/// The transactions of blocks that are no longer canonical are added back to the pool, unless
+/// they are included in one of the new blocks, and the transactions of the new blocks are removed.
+pub async fn on_canon_state_change<Pool>(pool: &Pool, notification: CanonStateNotification)
+where
+    Pool: TransactionPool,
+{
+    let Some(tip) = notification.tip() else { return };
+
+    let mined_transactions = notification
+        .new_blocks()
+        .iter()
+        .flat_map(|block| block.body.iter().map(|tx| tx.hash()))
+        .collect::<Vec<_>>();
+    let mined = mined_transactions.iter().collect::<HashSet<_>>();
+
+    let reorged = notification
+        .old_blocks()
+        .iter()
+        .flat_map(|block| block.body.iter())
+        .filter(|tx| !mined.contains(&tx.hash()))
+        .filter_map(|tx| tx.try_ecrecovered())
+        .map(<Pool::Transaction as FromRecoveredTransaction>::from_recovered_transaction)
+        .collect::<Vec<_>>();
+    if !reorged.is_empty() {
+        if let Err(err) = pool.add_transactions(TransactionOrigin::External, reorged).await {
+            debug!(target: "txpool", ?err, "Failed to add reorged transactions back to the pool");
+        }
+    }
+
+    let pending_block_base_fee = tip
+        .base_fee_per_gas
+        .map(|base_fee| calculate_next_block_base_fee(tip.gas_used, tip.gas_limit, base_fee))
+        .unwrap_or_default();
+    // the excess blob gas of a block before Cancun is zero, which is the minimum blob gas price
+    let pending_block_excess_blob_gas = calculate_excess_blob_gas(
+        tip.excess_blob_gas.unwrap_or_default(),
+        tip.blob_gas_used.unwrap_or_default(),
+    );
+    pool.on_new_block(OnNewBlockEvent {
+        hash: tip.hash(),
+        pending_block_base_fee: pending_block_base_fee as u128,
+        pending_block_blob_fee: blob_gasprice(pending_block_excess_blob_gas).saturating_to(),
+        state_changes: StateDiff {},
+        mined_transactions,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::testing_pool;
+    use reth_primitives::{hex_literal::hex, Header, SealedBlock, TransactionSigned};
+    use reth_rlp::Decodable;
+
+    #[tokio::test]
+    async fn reorged_transactions_are_added_back() {
+        let raw = hex!("f88b8212b085028fa6ae00830f424094aad593da0c8116ef7d2d594dd6a63241bccfc26c80a48318b64b000000000000000000000000641c5d790f862a58ec7abcfd644c0442e9c201b32aa0a6ef9e170bca5ffb7ac05433b13b7043de667fbb0b4a5e45d3b54fb2d6efcc63a0037ec2c05c3d60c5f5f78244ce0a3859e3a18a36c61efb061b383507d3ce19d2");
+        let tx = TransactionSigned::decode(&mut raw.as_ref()).unwrap();
+        let block = |number, body| SealedBlock {
+            header: Header { number, ..Default::default() }.seal_slow(),
+            body,
+            ..Default::default()
+        };
+
+        let pool = testing_pool();
+        let reorg = CanonStateNotification::Reorganized {
+            old_blocks: vec![block(1, vec![tx.clone()])],
+            new_blocks: vec![block(1, vec![])],
+        };
+        on_canon_state_change(&pool, reorg).await;
+        assert!(pool.contains(&tx.hash()));
+
+        let commit =
+            CanonStateNotification::Committed { new_blocks: vec![block(2, vec![tx.clone()])] };
+        on_canon_state_change(&pool, commit).await;
+        assert!(!pool.contains(&tx.hash()));
+    }
+}
@@ -1,11 +1,11 @@
 //! Transaction pool metrics.
 
-use metrics::Counter;
+use metrics::{Counter, Gauge, Histogram};
 use reth_metrics_derive::Metrics;
 
 /// Transaction pool metrics
 #[derive(Metrics)]
-#[metrics(scope = "transaction_pool")]
+#[metrics(scope = "txpool")]
 pub struct TxPoolMetrics {
     /// Number of transactions inserted in the pool
     pub(crate) inserted_transactions: Counter,
@@ -14,3 +14,27 @@ pub struct TxPoolMetrics {
     /// Number of removed transactions from the pool
     pub(crate) removed_transactions: Counter,
 }
+
+/// Transaction pool metrics broken down by sub-pool.
+///
+/// This is injected into the pool on construction, see [`Pool::with_metrics`](crate::Pool).
+#[derive(Metrics)]
+#[metrics(scope = "txpool", separator = "_")]
+pub struct PoolMetrics {
+    /// Number of transactions in the pending sub-pool
+    pub(crate) pending_count: Gauge,
+    /// Number of transactions in the basefee sub-pool
+    pub(crate) basefee_count: Gauge,
+    /// Number of transactions in the queued sub-pool
+    pub(crate) queued_count: Gauge,
+    /// Number of transactions in the blob sub-pool
+    pub(crate) blob_count: Gauge,
+    /// Total number of transactions evicted from the pool to respect the size limits
+    pub(crate) evicted_total: Counter,
+    /// Total number of transactions replaced by a transaction with the same sender and nonce
+    pub(crate) replaced_total: Counter,
+    /// Total number of parked transactions evicted because they exceeded the max idle time
+    pub(crate) expired_total: Counter,
+    /// Size of the rlp encoded transactions inserted into the pool
+    pub(crate) transaction_size_bytes: Histogram,
+}
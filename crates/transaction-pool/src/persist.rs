@@ -0,0 +1,229 @@
+//! Persistence of the pool's content across restarts.
+//!
+//! The pool can be written to disk on shutdown via [`Pool::persist_pool_to_disk`] and restored on
+//! startup via [`Pool::load_pool_from_disk`].
+//!
+//! The file consists of a single version byte, followed by the RLP encoded list of all
+//! [`TransactionSigned`] in the pool.
+
+use crate::{
+    Pool, PoolConfig, TransactionOrdering, TransactionOrigin, TransactionPool, TransactionValidator,
+};
+use reth_primitives::{FromRecoveredTransaction, IntoRecoveredTransaction, TransactionSigned};
+use reth_rlp::{Decodable, DecodeError};
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::Path,
+};
+use tracing::{debug, info, warn};
+
+/// The version of the persisted pool format.
+///
+/// Files written with a different version are considered outdated and are ignored on load.
+const PERSISTED_POOL_VERSION: u8 = 1;
+
+/// Possible errors when decoding a persisted pool file.
+#[derive(Debug, thiserror::Error)]
+enum PersistedPoolError {
+    /// The file is empty.
+    #[error("empty pool file")]
+    Empty,
+    /// The file was written with a different format version.
+    #[error("outdated pool file version {0}")]
+    Outdated(u8),
+    /// The file content couldn't be decoded.
+    #[error(transparent)]
+    Rlp(#[from] DecodeError),
+}
+
+/// Encodes the given transactions into the persisted pool format.
+fn encode_persisted_pool(transactions: &[TransactionSigned]) -> Vec<u8> {
+    let mut buf = vec![PERSISTED_POOL_VERSION];
+    reth_rlp::encode_list::<TransactionSigned, _>(transactions, &mut buf);
+    buf
+}
+
+/// Decodes the transactions from the persisted pool format.
+fn decode_persisted_pool(data: &[u8]) -> Result<Vec<TransactionSigned>, PersistedPoolError> {
+    let (version, mut rlp) = data.split_first().ok_or(PersistedPoolError::Empty)?;
+    if *version != PERSISTED_POOL_VERSION {
+        return Err(PersistedPoolError::Outdated(*version))
+    }
+    Ok(Vec::<TransactionSigned>::decode(&mut rlp)?)
+}
+
+// === impl Pool ===
+
+impl<V, T> Pool<V, T>
+where
+    V: TransactionValidator,
+    T: TransactionOrdering<Transaction = <V as TransactionValidator>::Transaction>,
+{
+    /// Writes all transactions currently in the pool to the given file.
+    ///
+    /// The file is written atomically: the content is written to a temporary file first which is
+    /// then renamed to `path`.
+    pub fn persist_pool_to_disk(&self, path: &Path) -> io::Result<()> {
+        let transactions = self
+            .pooled_transactions()
+            .into_iter()
+            .map(|tx| tx.transaction.to_recovered_transaction().into_signed())
+            .collect::<Vec<_>>();
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, encode_persisted_pool(&transactions))?;
+        fs::rename(&tmp_path, path)?;
+
+        info!(target: "txpool", file = %path.display(), count = transactions.len(), "Persisted transaction pool");
+        Ok(())
+    }
+
+    /// Creates a new pool and re-inserts all transactions previously written to the given file via
+    /// [`Pool::persist_pool_to_disk`].
+    ///
+    /// Every transaction goes through the validation pipeline of the given validator against the
+    /// current state before it is inserted, invalid transactions are discarded.
+    ///
+    /// A missing, corrupted or outdated file results in an empty pool.
+    pub async fn load_pool_from_disk(
+        path: &Path,
+        validator: V,
+        ordering: T,
+        config: PoolConfig,
+    ) -> io::Result<Self> {
+        let pool = Self::new(validator, ordering, config);
+
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(pool),
+            Err(err) => return Err(err),
+        };
+
+        let transactions = match decode_persisted_pool(&data) {
+            Ok(transactions) => transactions,
+            Err(err) => {
+                warn!(target: "txpool", file = %path.display(), ?err, "Failed to load persisted transaction pool, starting with an empty pool");
+                return Ok(pool)
+            }
+        };
+
+        let transactions = transactions
+            .into_iter()
+            .filter_map(|tx| tx.into_ecrecovered())
+            .map(<V::Transaction as FromRecoveredTransaction>::from_recovered_transaction)
+            .collect::<Vec<_>>();
+        let count = transactions.len();
+
+        match pool.add_transactions(TransactionOrigin::External, transactions).await {
+            Ok(results) => {
+                let imported = results.iter().filter(|res| res.is_ok()).count();
+                info!(target: "txpool", file = %path.display(), imported, discarded = count - imported, "Loaded persisted transaction pool");
+            }
+            Err(err) => {
+                debug!(target: "txpool", ?err, "Failed to re-insert persisted transactions");
+            }
+        }
+
+        Ok(pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{sign_transaction, NoopTransactionValidator},
+        CostOrdering, PoolTransaction, PooledTransaction,
+    };
+    use reth_primitives::{Transaction, TransactionKind, TxEip1559, TxLegacy, H256};
+    use std::collections::HashSet;
+
+    #[test]
+    fn decode_empty_pool() {
+        let data = encode_persisted_pool(&[]);
+        assert!(decode_persisted_pool(&data).unwrap().is_empty());
+    }
+
+    #[test]
+    fn decode_outdated_pool() {
+        let mut data = encode_persisted_pool(&[]);
+        data[0] = PERSISTED_POOL_VERSION + 1;
+        assert!(matches!(decode_persisted_pool(&data), Err(PersistedPoolError::Outdated(_))));
+    }
+
+    #[test]
+    fn decode_corrupted_pool() {
+        assert!(matches!(decode_persisted_pool(&[]), Err(PersistedPoolError::Empty)));
+        assert!(matches!(
+            decode_persisted_pool(&[PERSISTED_POOL_VERSION, 0xff, 0x01]),
+            Err(PersistedPoolError::Rlp(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn persist_and_load_pool() {
+        let pool = Pool::new(
+            NoopTransactionValidator::<PooledTransaction>::default(),
+            CostOrdering::default(),
+            Default::default(),
+        );
+
+        // two transactions for each of three senders
+        let mut transactions = Vec::new();
+        for key in 1..=3 {
+            for nonce in 0..2 {
+                let transaction = if nonce == 0 {
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(1),
+                        nonce,
+                        gas_price: 1_000_000_000,
+                        gas_limit: 21_000,
+                        to: TransactionKind::Call(Default::default()),
+                        ..Default::default()
+                    })
+                } else {
+                    Transaction::Eip1559(TxEip1559 {
+                        chain_id: 1,
+                        nonce,
+                        max_fee_per_gas: 2_000_000_000,
+                        max_priority_fee_per_gas: 1_000_000_000,
+                        gas_limit: 21_000,
+                        to: TransactionKind::Call(Default::default()),
+                        ..Default::default()
+                    })
+                };
+                transactions.push(PooledTransaction::from_recovered_transaction(sign_transaction(
+                    H256::from_low_u64_be(key),
+                    transaction,
+                )));
+            }
+        }
+        let senders = transactions.iter().map(|tx| tx.sender()).collect::<HashSet<_>>();
+        assert_eq!(senders.len(), 3);
+
+        let results =
+            pool.add_transactions(TransactionOrigin::External, transactions).await.unwrap();
+        let hashes = results.into_iter().map(|res| res.unwrap()).collect::<HashSet<_>>();
+        assert_eq!(hashes.len(), 6);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("txpool.rlp");
+        pool.persist_pool_to_disk(&path).unwrap();
+
+        let loaded = Pool::load_pool_from_disk(
+            &path,
+            NoopTransactionValidator::<PooledTransaction>::default(),
+            CostOrdering::default(),
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(loaded.len(), 6);
+        assert_eq!(loaded.pooled_transaction_hashes().into_iter().collect::<HashSet<_>>(), hashes);
+        assert_eq!(loaded.all_senders().into_iter().collect::<HashSet<_>>(), senders);
+    }
+}
@@ -1,9 +1,8 @@
 use crate::{
-    identifier::TransactionId,
+    identifier::{SenderId, TransactionId},
     pool::pending::{PendingTransaction, PendingTransactionRef},
     TransactionOrdering, ValidPoolTransaction,
 };
-use reth_primitives::H256 as TxHash;
 use std::{
     collections::{BTreeMap, BTreeSet, HashSet},
     sync::Arc,
@@ -26,14 +25,18 @@ pub(crate) struct BestTransactions<T: TransactionOrdering> {
     /// Once an `independent` transaction with the nonce `N` is returned, it unlocks `N+1`, which
     /// then can be moved from the `all` set to the `independent` set.
     pub(crate) independent: BTreeSet<PendingTransactionRef<T>>,
-    /// There might be the case where a yielded transactions is invalid, this will track it.
-    pub(crate) invalid: HashSet<TxHash>,
+    /// There might be the case where a yielded transactions is invalid, this will track the
+    /// senders of these transactions, since all their remaining transactions depend on it.
+    pub(crate) invalid: HashSet<SenderId>,
 }
 
 impl<T: TransactionOrdering> BestTransactions<T> {
     /// Mark the transaction and it's descendants as invalid.
+    ///
+    /// The descendants are skipped even if the transaction was already yielded, for example
+    /// because the sender can't afford it after the previous transactions were executed.
     pub(crate) fn mark_invalid(&mut self, tx: &Arc<ValidPoolTransaction<T::Transaction>>) {
-        self.invalid.insert(*tx.hash());
+        self.invalid.insert(tx.sender_id());
     }
 }
 
@@ -53,8 +56,8 @@ impl<T: TransactionOrdering> Iterator for BestTransactions<T> {
             let best = self.independent.take(&best)?;
             let hash = best.transaction.hash();
 
-            // skip transactions that were marked as invalid
-            if self.invalid.contains(hash) {
+            // skip transactions that were marked as invalid, or whose ancestor was
+            if self.invalid.contains(&best.transaction.sender_id()) {
                 debug!(
                     target: "txpool",
                     "[{:?}] skipping invalid transaction",
@@ -130,4 +133,29 @@ mod tests {
         // iterator is empty
         assert!(best.next().is_none());
     }
+
+    #[test]
+    fn test_best_iter_invalid_yielded() {
+        let mut pool = PendingPool::new(MockOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        let a0 = MockTransaction::eip1559();
+        let a1 = a0.next();
+        let b0 = MockTransaction::eip1559();
+        for tx in [a0.clone(), a1, b0.clone()] {
+            pool.add_transaction(Arc::new(f.validated(tx)));
+        }
+
+        let mut best = pool.best();
+        let mut yielded = Vec::new();
+        while let Some(tx) = best.next() {
+            // the sender can't afford `a0`, so `a1` is skipped as well
+            if *tx.hash() == a0.get_hash() {
+                best.mark_invalid(&tx);
+                continue
+            }
+            yielded.push(*tx.hash());
+        }
+        assert_eq!(yielded, vec![b0.get_hash()]);
+    }
 }
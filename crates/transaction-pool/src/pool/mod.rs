@@ -32,7 +32,7 @@
 //! fee cap of the transaction needs to be no less than the base fee of block.
 //!
 //!
-//! In essence the transaction pool is made of three separate sub-pools:
+//! In essence the transaction pool is made of four separate sub-pools:
 //!
 //!  - Pending Pool: Contains all transactions that are valid on the current state and satisfy
 //! (3. a)(1): _No_ nonce gaps. A _pending_ transaction is considered _ready_ when it has the lowest
@@ -45,6 +45,9 @@
 //!  - Basefee Pool: To account for the dynamic base fee requirement (3. b) which could render
 //! an EIP-1559 and all subsequent transactions of the sender currently invalid.
 //!
+//!  - Blob Pool: Contains EIP-4844 blob transactions that currently can't satisfy both the dynamic
+//! base fee requirement (3. b) and the separate blob fee requirement of the pending block.
+//!
 //! The classification of transactions is always dependent on the current state that is changed as
 //! soon as a new block is mined. Once a new block is mined, the account changeset must be applied
 //! to the transaction pool.
@@ -70,6 +73,7 @@
 use crate::{
     error::{PoolError, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
+    metrics::PoolMetrics,
     pool::{listener::PoolEventBroadcast, state::SubPool, txpool::TxPool},
     traits::{
         NewTransactionEvent, PoolSize, PoolTransaction, PropagatedTransactions, TransactionOrigin,
@@ -83,7 +87,7 @@ use parking_lot::{Mutex, RwLock};
 use reth_primitives::{Address, TxHash, H256};
 use std::{collections::HashSet, fmt, sync::Arc, time::Instant};
 use tokio::sync::mpsc;
-use tracing::warn;
+use tracing::debug;
 
 mod best;
 mod events;
@@ -121,12 +125,12 @@ where
     T: TransactionOrdering<Transaction = <V as TransactionValidator>::Transaction>,
 {
     /// Create a new transaction pool instance.
-    pub(crate) fn new(validator: V, ordering: T, config: PoolConfig) -> Self {
+    pub(crate) fn new(validator: V, ordering: T, config: PoolConfig, metrics: PoolMetrics) -> Self {
         Self {
             identifiers: Default::default(),
             validator,
             event_listener: Default::default(),
-            pool: RwLock::new(TxPool::new(ordering, config.clone())),
+            pool: RwLock::new(TxPool::with_metrics(ordering, config.clone(), metrics)),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             config,
@@ -138,11 +142,27 @@ where
         self.pool.read().size()
     }
 
+    /// Returns the number of transactions in the _pending_ sub-pool.
+    pub(crate) fn pending_count(&self) -> usize {
+        self.pool.read().pending_count()
+    }
+
+    /// Returns the number of transactions that are not executable yet.
+    pub(crate) fn queued_count(&self) -> usize {
+        self.pool.read().queued_count()
+    }
+
     /// Returns the internal `SenderId` for this address
     pub(crate) fn get_sender_id(&self, addr: Address) -> SenderId {
         self.identifiers.write().sender_id_or_create(addr)
     }
 
+    /// Returns the internal `SenderId` for this address without assigning a new one, see
+    /// [SenderIdentifiers::peek_sender_id]
+    fn peek_sender_id(&self, addr: &Address) -> SenderId {
+        self.identifiers.read().peek_sender_id(addr)
+    }
+
     /// Get the config the pool was configured with.
     pub fn config(&self) -> &PoolConfig {
         &self.config
@@ -200,18 +220,7 @@ where
         match tx {
             TransactionValidationOutcome::Valid { balance, state_nonce, transaction } => {
                 let sender_id = self.get_sender_id(transaction.sender());
-                let transaction_id = TransactionId::new(sender_id, transaction.nonce());
-                let encoded_length = transaction.encoded_length();
-
-                let tx = ValidPoolTransaction {
-                    cost: transaction.cost(),
-                    transaction,
-                    transaction_id,
-                    propagate: false,
-                    timestamp: Instant::now(),
-                    origin,
-                    encoded_length,
-                };
+                let tx = valid_pool_transaction(transaction, sender_id, origin);
 
                 let added = self.pool.write().add_transaction(tx, balance, state_nonce)?;
                 let hash = *added.hash();
@@ -242,6 +251,30 @@ where
         }
     }
 
+    /// Checks whether the validated transaction would be added to the pool, without adding it.
+    pub(crate) fn check_transaction(
+        &self,
+        origin: TransactionOrigin,
+        tx: TransactionValidationOutcome<T::Transaction>,
+    ) -> PoolResult<TxHash> {
+        match tx {
+            TransactionValidationOutcome::Valid { transaction, .. } => {
+                // a sender that's unknown to the pool has no transactions in it, so the check
+                // doesn't need to assign an id to it
+                let sender_id = self.peek_sender_id(&transaction.sender());
+                let tx = valid_pool_transaction(transaction, sender_id, origin);
+
+                let hash = *tx.hash();
+                self.pool.read().check_transaction(tx)?;
+                Ok(hash)
+            }
+            TransactionValidationOutcome::Invalid(tx, err) => {
+                Err(PoolError::InvalidTransaction(*tx.hash(), err))
+            }
+            TransactionValidationOutcome::Error(tx, err) => Err(PoolError::Other(*tx.hash(), err)),
+        }
+    }
+
     /// Adds all transactions in the iterator to the pool, returning a list of results.
     pub fn add_transactions(
         &self,
@@ -278,10 +311,12 @@ where
         transaction_listeners.retain_mut(|listener| match listener.try_send(*ready) {
             Ok(()) => true,
             Err(err) => {
+                // listeners like unpolled pending transaction filters fill up on every new
+                // transaction, so this is not worth more than a debug log
                 if matches!(err, mpsc::error::TrySendError::Full(_)) {
-                    warn!(
+                    debug!(
                         target: "txpool",
-                        "[{:?}] dropping full ready transaction listener",
+                        "[{:?}] skipping transaction on full ready transaction listener",
                         ready,
                     );
                     true
@@ -300,7 +335,7 @@ where
             Ok(()) => true,
             Err(err) => {
                 if matches!(err, mpsc::error::TrySendError::Full(_)) {
-                    warn!(
+                    debug!(
                         target: "txpool",
                         "skipping transaction on full transaction listener",
                     );
@@ -346,6 +381,16 @@ where
         self.pool.read().best_transactions()
     }
 
+    /// Returns up to `max_count` pending transactions ordered by their effective miner tip.
+    ///
+    /// This does not remove the transactions from the pool.
+    pub(crate) fn drain_pending(
+        &self,
+        max_count: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.pool.read().drain_pending(max_count)
+    }
+
     /// Removes and returns all matching transactions from the pool.
     pub(crate) fn remove_invalid(
         &self,
@@ -384,6 +429,37 @@ where
         self.pool.read().get_all(txs).collect()
     }
 
+    /// Returns all transactions sent by the given address, sorted by ascending nonce.
+    pub(crate) fn get_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let sender_id = self.identifiers.read().sender_id(&sender);
+        match sender_id {
+            Some(sender_id) => self.pool.read().get_transactions_by_sender(sender_id),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns all pending transactions sent by the given address, sorted by ascending nonce.
+    pub(crate) fn get_pending_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let sender_id = self.identifiers.read().sender_id(&sender);
+        match sender_id {
+            Some(sender_id) => self.pool.read().get_pending_transactions_by_sender(sender_id),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the addresses of all senders that have transactions in the pool.
+    pub(crate) fn all_senders(&self) -> Vec<Address> {
+        let sender_ids = self.pool.read().all().senders_iter().collect::<Vec<_>>();
+        let identifiers = self.identifiers.read();
+        sender_ids.iter().filter_map(|id| identifiers.address(id).copied()).collect()
+    }
+
     /// Notify about propagated transactions.
     pub(crate) fn on_propagated(&self, txs: PropagatedTransactions) {
         let mut listener = self.event_listener.write();
@@ -407,6 +483,26 @@ where
     }
 }
 
+/// Creates the pool representation of a validated transaction of the given sender.
+fn valid_pool_transaction<T: PoolTransaction>(
+    transaction: T,
+    sender_id: SenderId,
+    origin: TransactionOrigin,
+) -> ValidPoolTransaction<T> {
+    let transaction_id = TransactionId::new(sender_id, transaction.nonce());
+    let encoded_length = transaction.encoded_length();
+
+    ValidPoolTransaction {
+        cost: transaction.cost(),
+        transaction,
+        transaction_id,
+        propagate: false,
+        timestamp: Instant::now(),
+        origin,
+        encoded_length,
+    }
+}
+
 impl<V: TransactionValidator, T: TransactionOrdering> fmt::Debug for PoolInner<V, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PoolInner").field("config", &self.config).finish_non_exhaustive()
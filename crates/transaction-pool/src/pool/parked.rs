@@ -251,3 +251,27 @@ impl<T: PoolTransaction> Ord for QueuedOrd<T> {
             other.timestamp.cmp(&self.timestamp))
     }
 }
+
+/// A new type wrapper for [`ValidPoolTransaction`]
+///
+/// This sorts transactions by their blob fee.
+///
+/// Caution: This assumes all transaction in the `Blob` sub-pool are blob transactions.
+#[derive(Debug)]
+pub(crate) struct BlobOrd<T: PoolTransaction>(Arc<ValidPoolTransaction<T>>);
+
+impl_ord_wrapper!(BlobOrd);
+
+impl<T: PoolTransaction> Ord for BlobOrd<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (
+            self.0.transaction.max_fee_per_blob_gas(),
+            other.0.transaction.max_fee_per_blob_gas(),
+        ) {
+            (Some(fee), Some(other)) => fee.cmp(&other),
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            _ => Ordering::Equal,
+        }
+    }
+}
@@ -5,8 +5,8 @@ use crate::{
 };
 use reth_primitives::TxHash;
 use std::{
-    cmp::Ordering,
-    collections::{BTreeMap, BTreeSet},
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
     sync::Arc,
 };
 
@@ -83,6 +83,45 @@ impl<T: TransactionOrdering> PendingPool<T> {
         }
     }
 
+    /// Returns up to `max_count` transactions that are _currently_ ready, ordered by their
+    /// effective miner tip for the given `base_fee`.
+    ///
+    /// Like [`Self::best`], this never returns a transaction before its ancestor transaction of the
+    /// same sender: the nonces of transactions with the same sender always increase by exactly 1.
+    ///
+    /// If two transactions pay the same tip, then the transaction which spent more time in the pool
+    /// is returned first.
+    ///
+    /// NOTE: This does not remove any transactions from the pool.
+    pub(crate) fn best_by_tip(
+        &self,
+        base_fee: u128,
+        max_count: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let key = |tx: &PendingTransactionRef<T>| {
+            let tip = tx.transaction.effective_tip_per_gas(base_fee).unwrap_or_default();
+            (tip, Reverse(tx.submission_id), *tx.transaction.id())
+        };
+
+        let mut independent =
+            self.independent_transactions.iter().map(key).collect::<BinaryHeap<_>>();
+        let mut best = Vec::with_capacity(max_count.min(self.len()));
+
+        while best.len() < max_count {
+            let Some((_, _, id)) = independent.pop() else { break };
+            let Some(tx) = self.by_id.get(&id) else { continue };
+
+            // Insert the transaction that just got unlocked.
+            if let Some(unlocked) = self.by_id.get(&tx.transaction.unlocks()) {
+                independent.push(key(&unlocked.transaction));
+            }
+
+            best.push(tx.transaction.transaction.clone());
+        }
+
+        best
+    }
+
     /// Returns the ancestor the given transaction, the transaction with `nonce - 1`.
     ///
     /// Note: for a transaction with nonce higher than the current on chain nonce this will always
@@ -4,6 +4,12 @@ bitflags::bitflags! {
     /// This mirrors [erigon's ephemeral state field](https://github.com/ledgerwatch/erigon/wiki/Transaction-Pool-Design#ordering-function).
     #[derive(Default)]
     pub(crate) struct TxState: u8 {
+        /// Set to `1` if the `feeCap` of the transaction meets the chain's minimum fee requirement.
+        ///
+        /// This is always set for transactions that are in the pool, since transactions below the protocol minimum are rejected outright.
+        const ENOUGH_FEE_CAP_PROTOCOL = 0b10000000;
+        /// Set to `1` if the transaction carries blobs (EIP-4844).
+        const IS_BLOB = 0b1000000;
         /// Set to `1` if all ancestor transactions are pending.
         const NO_PARKED_ANCESTORS = 0b100000;
         /// Set to `1` of the transaction is either the next transaction of the sender (on chain nonce == tx.nonce) or all prior transactions are also present in the pool.
@@ -19,6 +25,10 @@ bitflags::bitflags! {
         ///
         /// Set to 1 if `feeCap` of the transaction meets the requirement of the pending block.
         const ENOUGH_FEE_CAP_BLOCK = 0b000010;
+        /// Covers the blob fee requirement of EIP-4844.
+        ///
+        /// Set to 1 if `maxFeePerBlobGas` of the transaction meets the blob fee requirement of the pending block.
+        const ENOUGH_BLOB_FEE_CAP_BLOCK = 0b000001;
 
         const PENDING_POOL_BITS = Self::NO_PARKED_ANCESTORS.bits | Self::NO_NONCE_GAPS.bits | Self::ENOUGH_BALANCE.bits | Self::NOT_TOO_MUCH_GAS.bits |  Self::ENOUGH_FEE_CAP_BLOCK.bits;
 
@@ -26,6 +36,8 @@ bitflags::bitflags! {
 
         const QUEUED_POOL_BITS  = Self::NO_PARKED_ANCESTORS.bits;
 
+        const BLOB_POOL_BITS = Self::IS_BLOB.bits | Self::ENOUGH_FEE_CAP_PROTOCOL.bits | Self::NO_NONCE_GAPS.bits;
+
     }
 }
 
@@ -36,9 +48,19 @@ impl TxState {
     ///   - _No_ parked ancestors
     ///   - enough balance
     ///   - enough fee cap
+    ///   - enough blob fee cap, if this is a blob transaction
     #[inline]
     pub(crate) fn is_pending(&self) -> bool {
-        *self >= TxState::PENDING_POOL_BITS
+        if self.is_blob() {
+            return self.contains(TxState::PENDING_POOL_BITS | TxState::ENOUGH_BLOB_FEE_CAP_BLOCK)
+        }
+        self.contains(TxState::PENDING_POOL_BITS)
+    }
+
+    /// Returns `true` if the `IS_BLOB` bit is set.
+    #[inline]
+    pub(crate) fn is_blob(&self) -> bool {
+        self.intersects(TxState::IS_BLOB)
     }
 
     /// Returns `true` if the `ENOUGH_FEE_CAP_BLOCK` bit is set.
@@ -47,6 +69,12 @@ impl TxState {
         self.intersects(TxState::ENOUGH_FEE_CAP_BLOCK)
     }
 
+    /// Returns `true` if the `ENOUGH_BLOB_FEE_CAP_BLOCK` bit is set.
+    #[inline]
+    pub(crate) fn has_enough_blob_fee_cap(&self) -> bool {
+        self.intersects(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK)
+    }
+
     /// Returns `true` if the transaction has a nonce gap.
     #[inline]
     pub(crate) fn has_nonce_gap(&self) -> bool {
@@ -61,6 +89,7 @@ pub enum SubPool {
     Queued = 0,
     Pending,
     BaseFee,
+    BlobPool,
 }
 
 // === impl PoolDestination ===
@@ -71,6 +100,11 @@ impl SubPool {
         matches!(self, SubPool::Pending)
     }
 
+    /// Whether this transaction is to be moved to the blob sub-pool.
+    pub fn is_blob(&self) -> bool {
+        matches!(self, SubPool::BlobPool)
+    }
+
     /// Returns whether this is a promotion depending on the current sub-pool location.
     pub fn is_promoted(&self, other: SubPool) -> bool {
         self > &other
@@ -82,7 +116,14 @@ impl From<TxState> for SubPool {
         if value.is_pending() {
             return SubPool::Pending
         }
-        if value < TxState::BASE_FEE_POOL_BITS {
+        if value.is_blob() {
+            // blob transactions are parked separately until both fee caps are satisfied
+            if value.contains(TxState::BLOB_POOL_BITS) {
+                return SubPool::BlobPool
+            }
+            return SubPool::Queued
+        }
+        if !value.contains(TxState::BASE_FEE_POOL_BITS) {
             return SubPool::Queued
         }
         SubPool::BaseFee
@@ -128,4 +169,31 @@ mod tests {
         assert_eq!(SubPool::Pending, state.into());
         assert!(state.is_pending());
     }
+
+    #[test]
+    fn test_tx_blob() {
+        let state = TxState::BLOB_POOL_BITS;
+        assert_eq!(SubPool::BlobPool, state.into());
+        assert!(!state.is_pending());
+
+        // satisfies the EIP-1559 fee cap but not the blob fee cap
+        let state = TxState::PENDING_POOL_BITS | TxState::BLOB_POOL_BITS;
+        assert_eq!(SubPool::BlobPool, state.into());
+
+        // satisfies the blob fee cap but not the EIP-1559 fee cap
+        let state = (TxState::PENDING_POOL_BITS | TxState::BLOB_POOL_BITS) -
+            TxState::ENOUGH_FEE_CAP_BLOCK |
+            TxState::ENOUGH_BLOB_FEE_CAP_BLOCK;
+        assert_eq!(SubPool::BlobPool, state.into());
+
+        let state = TxState::PENDING_POOL_BITS |
+            TxState::BLOB_POOL_BITS |
+            TxState::ENOUGH_BLOB_FEE_CAP_BLOCK;
+        assert_eq!(SubPool::Pending, state.into());
+        assert!(state.is_pending());
+
+        // nonce gap
+        let state = TxState::IS_BLOB | TxState::ENOUGH_FEE_CAP_PROTOCOL;
+        assert_eq!(SubPool::Queued, state.into());
+    }
 }
@@ -1,12 +1,12 @@
 //! The internal transaction pool implementation.
 use crate::{
-    config::MAX_ACCOUNT_SLOTS_PER_SENDER,
+    config::{DEFAULT_PRICE_BUMP, MAX_ACCOUNT_SLOTS_PER_SENDER},
     error::{InvalidPoolTransactionError, PoolError},
     identifier::{SenderId, TransactionId},
-    metrics::TxPoolMetrics,
+    metrics::{PoolMetrics, TxPoolMetrics},
     pool::{
         best::BestTransactions,
-        parked::{BasefeeOrd, ParkedPool, QueuedOrd},
+        parked::{BasefeeOrd, BlobOrd, ParkedPool, QueuedOrd},
         pending::PendingPool,
         state::{SubPool, TxState},
         update::{Destination, PoolUpdate},
@@ -24,6 +24,7 @@ use std::{
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 /// The minimal value the basefee can decrease to.
@@ -48,6 +49,7 @@ pub(crate) const MIN_PROTOCOL_BASE_FEE: u128 = 7;
 ///         B3[(Queued)]
 ///         B1[(Pending)]
 ///         B2[(Basefee)]
+///         B4[(Blob)]
 ///     end
 ///   end
 ///   discard([discard])
@@ -57,6 +59,7 @@ pub(crate) const MIN_PROTOCOL_BASE_FEE: u128 = 7;
 ///   pool --> |if ready| B1
 ///   pool --> |if ready + basfee too low| B2
 ///   pool --> |nonce gap or lack of funds| B3
+///   pool --> |blob tx + fee caps too low| B4
 ///   pool --> |update| pool
 ///   B1 --> |best| production
 ///   B2 --> |worst| discard
@@ -65,6 +68,7 @@ pub(crate) const MIN_PROTOCOL_BASE_FEE: u128 = 7;
 ///   B2 --> |decreased fee| B1
 ///   B3 --> |promote| B1
 ///   B3 -->  |promote| B2
+///   B4 -->  |increased fee caps| B1
 ///   new -->  |apply state changes| pool
 /// ```
 pub struct TxPool<T: TransactionOrdering> {
@@ -88,10 +92,17 @@ pub struct TxPool<T: TransactionOrdering> {
     /// Holds all parked transactions that currently violate the dynamic fee requirement but could
     /// be moved to pending if the base fee changes in their favor (decreases) in future blocks.
     basefee_pool: ParkedPool<BasefeeOrd<T::Transaction>>,
+    /// blob subpool
+    ///
+    /// Holds all parked EIP-4844 blob transactions that currently violate either the dynamic fee
+    /// requirement or the blob fee requirement of the pending block.
+    blob_pool: ParkedPool<BlobOrd<T::Transaction>>,
     /// All transactions in the pool.
     all_transactions: AllTransactions<T::Transaction>,
     /// Transaction pool metrics
     metrics: TxPoolMetrics,
+    /// Sub-pool metrics
+    pool_metrics: PoolMetrics,
 }
 
 // === impl TxPool ===
@@ -99,14 +110,21 @@ pub struct TxPool<T: TransactionOrdering> {
 impl<T: TransactionOrdering> TxPool<T> {
     /// Create a new graph pool instance.
     pub(crate) fn new(ordering: T, config: PoolConfig) -> Self {
+        Self::with_metrics(ordering, config, Default::default())
+    }
+
+    /// Create a new graph pool instance that records to the given [`PoolMetrics`].
+    pub(crate) fn with_metrics(ordering: T, config: PoolConfig, pool_metrics: PoolMetrics) -> Self {
         Self {
             sender_info: Default::default(),
             pending_pool: PendingPool::new(ordering),
             queued_pool: Default::default(),
             basefee_pool: Default::default(),
-            all_transactions: AllTransactions::new(config.max_account_slots),
+            blob_pool: Default::default(),
+            all_transactions: AllTransactions::new(&config),
             config,
             metrics: Default::default(),
+            pool_metrics,
         }
     }
 
@@ -124,9 +142,31 @@ impl<T: TransactionOrdering> TxPool<T> {
             basefee_size: self.basefee_pool.size(),
             queued: self.queued_pool.len(),
             queued_size: self.queued_pool.size(),
+            blob: self.blob_pool.len(),
+            blob_size: self.blob_pool.size(),
         }
     }
 
+    /// Returns the number of transactions in the _pending_ sub-pool.
+    pub(crate) fn pending_count(&self) -> usize {
+        self.pending_pool.len()
+    }
+
+    /// Returns the number of transactions that are not executable yet.
+    ///
+    /// This includes all parked sub-pools: _queued_, _basefee_ and _blob_.
+    pub(crate) fn queued_count(&self) -> usize {
+        self.queued_pool.len() + self.basefee_pool.len() + self.blob_pool.len()
+    }
+
+    /// Updates the sub-pool size metrics.
+    fn update_size_metrics(&self) {
+        self.pool_metrics.pending_count.set(self.pending_pool.len() as f64);
+        self.pool_metrics.basefee_count.set(self.basefee_pool.len() as f64);
+        self.pool_metrics.queued_count.set(self.queued_pool.len() as f64);
+        self.pool_metrics.blob_count.set(self.blob_pool.len() as f64);
+    }
+
     /// Updates the pool based on the changed base fee.
     ///
     /// This enforces the dynamic fee requirement.
@@ -140,6 +180,17 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.pending_pool.best()
     }
 
+    /// Returns up to `max_count` pending transactions ordered by their effective miner tip for the
+    /// pending block's base fee.
+    ///
+    /// See also [`PendingPool::best_by_tip`].
+    pub(crate) fn drain_pending(
+        &self,
+        max_count: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.pending_pool.best_by_tip(self.all_transactions.pending_basefee, max_count)
+    }
+
     /// Returns `true` if the transaction with the given hash is already included in this pool.
     pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
         self.all_transactions.contains(tx_hash)
@@ -161,6 +212,29 @@ impl<T: TransactionOrdering> TxPool<T> {
         txs.into_iter().filter_map(|tx| self.get(&tx))
     }
 
+    /// Returns all transactions of the given sender, sorted by ascending nonce.
+    ///
+    /// This includes transactions of all sub-pools.
+    pub(crate) fn get_transactions_by_sender(
+        &self,
+        sender: SenderId,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.all_transactions.txs_iter(sender).map(|(_, tx)| Arc::clone(&tx.transaction)).collect()
+    }
+
+    /// Returns all transactions of the given sender that are in the pending sub-pool, sorted by
+    /// ascending nonce.
+    pub(crate) fn get_pending_transactions_by_sender(
+        &self,
+        sender: SenderId,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.all_transactions
+            .txs_iter(sender)
+            .filter(|(_, tx)| tx.subpool.is_pending())
+            .map(|(_, tx)| Arc::clone(&tx.transaction))
+            .collect()
+    }
+
     /// Updates the entire pool after a new block was mined.
     ///
     /// This removes all mined transactions, updates according to the new base fee and rechecks
@@ -174,11 +248,19 @@ impl<T: TransactionOrdering> TxPool<T> {
         }
 
         // Apply the state changes to the total set of transactions which triggers sub-pool updates.
-        let updates =
-            self.all_transactions.update(event.pending_block_base_fee, &event.state_changes);
+        let updates = self.all_transactions.update(
+            event.pending_block_base_fee,
+            event.pending_block_blob_fee,
+            &event.state_changes,
+        );
 
         // Process the sub-pool updates
-        let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+        let UpdateOutcome { promoted, mut discarded } = self.process_updates(updates);
+
+        // Evict transactions that are parked for too long
+        discarded.extend(self.remove_expired(Instant::now()).iter().map(|tx| *tx.hash()));
+
+        self.update_size_metrics();
 
         OnNewBlockOutcome {
             block_hash: event.hash,
@@ -188,9 +270,19 @@ impl<T: TransactionOrdering> TxPool<T> {
         }
     }
 
+    /// Checks whether the transaction would be added to the pool, without adding it.
+    ///
+    /// See [AllTransactions::check_tx].
+    pub(crate) fn check_transaction(
+        &self,
+        tx: ValidPoolTransaction<T::Transaction>,
+    ) -> PoolResult<()> {
+        self.all_transactions.check_tx(tx)
+    }
+
     /// Adds the transaction into the pool.
     ///
-    /// This pool consists of two three-pools: `Queued`, `Pending` and `BaseFee`.
+    /// This pool consists of four sub-pools: `Queued`, `Pending`, `BaseFee` and `BlobPool`.
     ///
     /// The `Queued` pool contains transactions with gaps in its dependency tree: It requires
     /// additional transactions that are note yet present in the pool. And transactions that the
@@ -208,6 +300,11 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// the sender's balance or nonce and instead their `feeCap` determines whether the
     /// transaction is _currently_ (on the current state) ready or needs to be parked until the
     /// `feeCap` satisfies the block's `baseFee`.
+    ///
+    /// The `BlobPool` contains gapless EIP-4844 transactions that can't satisfy the dynamic fee
+    /// requirement and the blob fee requirement at the same time. A blob transaction is only moved
+    /// to the `Pending` pool once both its `feeCap` and its `maxFeePerBlobGas` satisfy the
+    /// requirements of the pending block.
     pub(crate) fn add_transaction(
         &mut self,
         tx: ValidPoolTransaction<T::Transaction>,
@@ -222,11 +319,19 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         match self.all_transactions.insert_tx(tx, on_chain_balance, on_chain_nonce) {
             Ok(InsertOk { transaction, move_to, replaced_tx, updates, .. }) => {
+                // Update transaction size and replaced transactions metrics
+                self.pool_metrics.transaction_size_bytes.record(transaction.encoded_length as f64);
+                if replaced_tx.is_some() {
+                    self.pool_metrics.replaced_total.increment(1);
+                }
+
                 self.add_new_transaction(transaction.clone(), replaced_tx, move_to);
                 // Update inserted transactions metric
                 self.metrics.inserted_transactions.increment(1);
                 let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
 
+                self.update_size_metrics();
+
                 // This transaction was moved to the pending pool.
                 let res = if move_to.is_pending() {
                     AddedTransaction::Pending(AddedPendingTransaction {
@@ -243,28 +348,7 @@ impl<T: TransactionOrdering> TxPool<T> {
             Err(e) => {
                 // Update invalid transactions metric
                 self.metrics.invalid_transactions.increment(1);
-                match e {
-                    InsertErr::Underpriced { existing, .. } => {
-                        Err(PoolError::ReplacementUnderpriced(existing))
-                    }
-                    InsertErr::ProtocolFeeCapTooLow { transaction, fee_cap } => {
-                        Err(PoolError::ProtocolFeeCapTooLow(*transaction.hash(), fee_cap))
-                    }
-                    InsertErr::ExceededSenderTransactionsCapacity { transaction } => {
-                        Err(PoolError::SpammerExceededCapacity(
-                            transaction.sender(),
-                            *transaction.hash(),
-                        ))
-                    }
-                    InsertErr::TxGasLimitMoreThanAvailableBlockGas {
-                        transaction,
-                        block_gas_limit,
-                        tx_gas_limit,
-                    } => Err(PoolError::InvalidTransaction(
-                        *transaction.hash(),
-                        InvalidPoolTransactionError::ExceedsGasLimit(block_gas_limit, tx_gas_limit),
-                    )),
-                }
+                Err(e.into())
             }
         }
     }
@@ -307,7 +391,34 @@ impl<T: TransactionOrdering> TxPool<T> {
         &mut self,
         hashes: impl IntoIterator<Item = TxHash>,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        hashes.into_iter().filter_map(|hash| self.remove_transaction_by_hash(&hash)).collect()
+        let removed = hashes
+            .into_iter()
+            .filter_map(|hash| self.remove_transaction_by_hash(&hash))
+            .collect::<Vec<_>>();
+        self.update_size_metrics();
+        removed
+    }
+
+    /// Removes all transactions that have been parked in the `Queued` or `BaseFee` sub-pool for
+    /// longer than [`PoolConfig::transaction_max_idle_seconds`], including their descendants.
+    pub(crate) fn remove_expired(
+        &mut self,
+        now: Instant,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let max_idle = Duration::from_secs(self.config.transaction_max_idle_seconds);
+        let expired =
+            self.all_transactions.expired_transactions(now, max_idle, self.config.expire_locals);
+
+        let mut removed = Vec::new();
+        for id in expired {
+            // the transaction may already be removed as descendant of an expired transaction
+            if let Some(tx) = self.remove_transaction(&id) {
+                removed.push(tx);
+                self.remove_descendants(&id, &mut removed);
+            }
+        }
+        self.pool_metrics.expired_total.increment(removed.len() as u64);
+        removed
     }
 
     /// Remove the transaction from the entire pool.
@@ -344,6 +455,7 @@ impl<T: TransactionOrdering> TxPool<T> {
             SubPool::Queued => self.queued_pool.remove_transaction(tx),
             SubPool::Pending => self.pending_pool.remove_transaction(tx),
             SubPool::BaseFee => self.basefee_pool.remove_transaction(tx),
+            SubPool::BlobPool => self.blob_pool.remove_transaction(tx),
         }
     }
 
@@ -388,6 +500,9 @@ impl<T: TransactionOrdering> TxPool<T> {
             SubPool::BaseFee => {
                 self.basefee_pool.add_transaction(tx);
             }
+            SubPool::BlobPool => {
+                self.blob_pool.add_transaction(tx);
+            }
         }
     }
 
@@ -438,10 +553,16 @@ impl<T: TransactionOrdering> TxPool<T> {
             self, removed, [
                 pending_limit  => pending_pool,
                 basefee_limit  => basefee_pool,
-                queued_limit  => queued_pool
+                queued_limit  => queued_pool,
+                blob_limit  => blob_pool
             ]
         );
 
+        if !removed.is_empty() {
+            self.pool_metrics.evicted_total.increment(removed.len() as u64);
+            self.update_size_metrics();
+        }
+
         removed
     }
 
@@ -471,6 +592,10 @@ impl<T: TransactionOrdering> TxPool<T> {
     pub(crate) fn queued(&self) -> &ParkedPool<QueuedOrd<T::Transaction>> {
         &self.queued_pool
     }
+
+    pub(crate) fn blob(&self) -> &ParkedPool<BlobOrd<T::Transaction>> {
+        &self.blob_pool
+    }
 }
 
 impl<T: TransactionOrdering> fmt::Debug for TxPool<T> {
@@ -486,6 +611,8 @@ impl<T: TransactionOrdering> fmt::Debug for TxPool<T> {
 pub(crate) struct AllTransactions<T: PoolTransaction> {
     /// Expected base fee for the pending block.
     pending_basefee: u128,
+    /// Expected blob fee for the pending block.
+    pending_blob_fee: u128,
     /// Minimum base fee required by the protocol.
     ///
     /// Transactions with a lower base fee will never be included by the chain
@@ -494,6 +621,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     block_gas_limit: u64,
     /// Max number of executable transaction slots guaranteed per account
     max_account_slots: usize,
+    /// Minimum price bump (in %) required to replace an existing transaction.
+    price_bump: u64,
     /// _All_ transactions identified by their hash.
     by_hash: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
     /// _All_ transaction in the pool sorted by their sender and nonce pair.
@@ -504,8 +633,12 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
 
 impl<T: PoolTransaction> AllTransactions<T> {
     /// Create a new instance
-    fn new(max_account_slots: usize) -> Self {
-        Self { max_account_slots, ..Default::default() }
+    fn new(config: &PoolConfig) -> Self {
+        Self {
+            max_account_slots: config.max_account_slots,
+            price_bump: config.replacement_price_bump_percent,
+            ..Default::default()
+        }
     }
 
     /// Returns an iterator over all _unique_ hashes in the pool
@@ -556,6 +689,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
     /// For all transactions:
     ///   - decreased basefee: promotes from `basefee` to `pending` sub-pool.
     ///   - increased basefee: demotes from `pending` to `basefee` sub-pool.
+    ///   - decreased blob fee: promotes blob transactions from `blob` to `pending` sub-pool.
+    ///   - increased blob fee: demotes blob transactions from `pending` to `blob` sub-pool.
     /// Individually:
     ///   - decreased sender allowance: demote from (`basefee`|`pending`) to `queued`.
     ///   - increased sender allowance: promote from `queued` to
@@ -567,10 +702,12 @@ impl<T: PoolTransaction> AllTransactions<T> {
     pub(crate) fn update(
         &mut self,
         pending_block_base_fee: u128,
+        pending_block_blob_fee: u128,
         _state_diffs: &StateDiff,
     ) -> Vec<PoolUpdate> {
-        // update new basefee
+        // update new basefee and blob fee
         self.pending_basefee = pending_block_base_fee;
+        self.pending_blob_fee = pending_block_blob_fee;
 
         // TODO(mattsse): probably good idea to allocate some capacity here.
         let mut updates = Vec::new();
@@ -612,6 +749,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
             // Update the first transaction of this sender.
             Self::update_base_fee(&pending_block_base_fee, tx);
+            Self::update_blob_fee(&pending_block_blob_fee, tx);
             // Track if the transaction's sub-pool changed.
             Self::record_subpool_update(&mut updates, tx);
 
@@ -640,6 +778,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
                 // Update and record sub-pool changes.
                 Self::update_base_fee(&pending_block_base_fee, tx);
+                Self::update_blob_fee(&pending_block_blob_fee, tx);
                 Self::record_subpool_update(&mut updates, tx);
 
                 // Advance iterator
@@ -657,6 +796,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     fn record_subpool_update(updates: &mut Vec<PoolUpdate>, tx: &mut PoolInternalTransaction<T>) {
         let current_pool = tx.subpool;
         tx.subpool = tx.state.into();
+        tx.on_subpool_change(current_pool);
         if current_pool != tx.subpool {
             updates.push(PoolUpdate {
                 id: *tx.transaction.id(),
@@ -682,10 +822,26 @@ impl<T: PoolTransaction> AllTransactions<T> {
         }
     }
 
+    /// Rechecks the transaction's blob fee condition.
+    ///
+    /// This is independent of the dynamic fee condition and only affects blob transactions.
+    fn update_blob_fee(pending_block_blob_fee: &u128, tx: &mut PoolInternalTransaction<T>) {
+        if let Some(blob_fee_cap) = tx.transaction.max_fee_per_blob_gas() {
+            if blob_fee_cap >= *pending_block_blob_fee {
+                tx.state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+            } else {
+                tx.state.remove(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+            }
+        }
+    }
+
+    /// Returns an iterator over all senders that have transactions in the pool.
+    pub(crate) fn senders_iter(&self) -> impl Iterator<Item = SenderId> + '_ {
+        self.tx_counter.keys().copied()
+    }
+
     /// Returns an iterator over all transactions for the given sender, starting with the lowest
     /// nonce
-    #[cfg(test)]
-    #[allow(unused)]
     pub(crate) fn txs_iter(
         &self,
         sender: SenderId,
@@ -785,31 +941,51 @@ impl<T: PoolTransaction> AllTransactions<T> {
     ///
     /// This will enforce all additional rules in the context of this pool, such as:
     ///   - Spam protection: reject new non-local transaction from a sender that exhausted its slot
-    ///     capacity.
+    ///     capacity, unless it replaces an existing transaction.
     ///   - Gas limit: reject transactions if they exceed a block's maximum gas.
+    ///   - Protocol fee: reject transactions whose `feeCap` is below the minimal protocol base fee.
+    ///   - Replacement: reject transactions that replace an existing transaction without bumping
+    ///     its price enough.
     fn ensure_valid(
         &self,
-        transaction: ValidPoolTransaction<T>,
-    ) -> Result<ValidPoolTransaction<T>, InsertErr<T>> {
-        if !transaction.origin.is_local() {
+        transaction: Arc<ValidPoolTransaction<T>>,
+    ) -> Result<Arc<ValidPoolTransaction<T>>, InsertErr<T>> {
+        let existing = self.txs.get(transaction.id());
+        if !transaction.origin.is_local() && existing.is_none() {
             let current_txs =
                 self.tx_counter.get(&transaction.sender_id()).copied().unwrap_or_default();
             if current_txs >= self.max_account_slots {
-                return Err(InsertErr::ExceededSenderTransactionsCapacity {
-                    transaction: Arc::new(transaction),
-                })
+                return Err(InsertErr::ExceededSenderTransactionsCapacity { transaction })
             }
         }
         if transaction.gas_limit() > self.block_gas_limit {
             return Err(InsertErr::TxGasLimitMoreThanAvailableBlockGas {
                 block_gas_limit: self.block_gas_limit,
                 tx_gas_limit: transaction.gas_limit(),
-                transaction: Arc::new(transaction),
+                transaction,
             })
         }
+        if let Some(fee_cap) = transaction.max_fee_per_gas() {
+            if fee_cap < self.minimal_protocol_basefee {
+                return Err(InsertErr::ProtocolFeeCapTooLow { transaction, fee_cap })
+            }
+        }
+        if let Some(existing) = existing {
+            if transaction.is_underpriced(existing.transaction.as_ref(), self.price_bump) {
+                let existing = *existing.transaction.hash();
+                return Err(InsertErr::Underpriced { transaction, existing })
+            }
+        }
         Ok(transaction)
     }
 
+    /// Checks whether the transaction would be accepted by [Self::insert_tx], without inserting
+    /// it.
+    pub(crate) fn check_tx(&self, transaction: ValidPoolTransaction<T>) -> PoolResult<()> {
+        self.ensure_valid(Arc::new(transaction))?;
+        Ok(())
+    }
+
     /// Inserts a new transaction into the pool.
     ///
     /// If the transaction already exists, it will be replaced if not underpriced.
@@ -828,7 +1004,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     ) -> InsertResult<T> {
         assert!(on_chain_nonce <= transaction.nonce(), "Invalid transaction");
 
-        let transaction = Arc::new(self.ensure_valid(transaction)?);
+        let transaction = self.ensure_valid(Arc::new(transaction))?;
         let tx_id = *transaction.id();
         let mut state = TxState::default();
         let mut cumulative_cost = U256::ZERO;
@@ -843,11 +1019,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
             state.insert(TxState::NO_PARKED_ANCESTORS);
         }
 
-        // Check dynamic fee
+        // Check dynamic fee, the minimal protocol fee is enforced by `ensure_valid`
         if let Some(fee_cap) = transaction.max_fee_per_gas() {
-            if fee_cap < self.minimal_protocol_basefee {
-                return Err(InsertErr::ProtocolFeeCapTooLow { transaction, fee_cap })
-            }
             if fee_cap >= self.pending_basefee {
                 state.insert(TxState::ENOUGH_FEE_CAP_BLOCK);
             }
@@ -855,6 +1028,15 @@ impl<T: PoolTransaction> AllTransactions<T> {
             // legacy transactions always satisfy the condition
             state.insert(TxState::ENOUGH_FEE_CAP_BLOCK);
         }
+        state.insert(TxState::ENOUGH_FEE_CAP_PROTOCOL);
+
+        // Check blob fee, this is enforced separately from the dynamic fee
+        if let Some(blob_fee_cap) = transaction.max_fee_per_blob_gas() {
+            state.insert(TxState::IS_BLOB);
+            if blob_fee_cap >= self.pending_blob_fee {
+                state.insert(TxState::ENOUGH_BLOB_FEE_CAP_BLOCK);
+            }
+        }
 
         // Ensure tx does not exceed block gas limit
         if transaction.gas_limit() < self.block_gas_limit {
@@ -868,6 +1050,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
             subpool: SubPool::Queued,
             state,
             cumulative_cost,
+            queued_since: Instant::now(),
         };
 
         // try to insert the transaction
@@ -878,14 +1061,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 entry.insert(pool_tx);
             }
             Entry::Occupied(mut entry) => {
-                // Transaction already exists
-                // Ensure the new transaction is not underpriced
-                if transaction.is_underpriced(entry.get().transaction.as_ref()) {
-                    return Err(InsertErr::Underpriced {
-                        transaction: pool_tx.transaction,
-                        existing: *entry.get().transaction.hash(),
-                    })
-                }
+                // Transaction already exists, `ensure_valid` checked that it's not underpriced
                 let new_hash = *pool_tx.transaction.hash();
                 let new_transaction = pool_tx.transaction.clone();
                 let replaced = entry.insert(pool_tx);
@@ -958,6 +1134,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
                     state = tx.state;
                 } else {
                     tx.subpool = tx.state.into();
+                    tx.on_subpool_change(current_pool);
                     if current_pool != tx.subpool {
                         updates.push(PoolUpdate {
                             id: *id,
@@ -978,7 +1155,30 @@ impl<T: PoolTransaction> AllTransactions<T> {
             self.tx_inc(tx_id.sender);
         }
 
-        Ok(InsertOk { transaction, move_to: state.into(), state, replaced_tx, updates })
+        // Keep track of the sub-pool the new transaction is moved to
+        let move_to = state.into();
+        if let Some(tx) = self.txs.get_mut(&tx_id) {
+            tx.subpool = move_to;
+        }
+
+        Ok(InsertOk { transaction, move_to, state, replaced_tx, updates })
+    }
+
+    /// Returns the ids of all transactions that have been parked in the `Queued` or `BaseFee`
+    /// sub-pool for longer than `max_idle`.
+    ///
+    /// Local transactions are only included if `expire_locals` is set.
+    pub(crate) fn expired_transactions(
+        &self,
+        now: Instant,
+        max_idle: Duration,
+        expire_locals: bool,
+    ) -> Vec<TransactionId> {
+        self.txs
+            .iter()
+            .filter(|(_, tx)| tx.is_expired(now, max_idle, expire_locals))
+            .map(|(id, _)| *id)
+            .collect()
     }
 
     /// Number of transactions in the entire pool
@@ -1004,7 +1204,9 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
     fn default() -> Self {
         Self {
             max_account_slots: MAX_ACCOUNT_SLOTS_PER_SENDER,
+            price_bump: DEFAULT_PRICE_BUMP,
             pending_basefee: Default::default(),
+            pending_blob_fee: Default::default(),
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             block_gas_limit: 30_000_000,
             by_hash: Default::default(),
@@ -1038,6 +1240,28 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
     },
 }
 
+impl<T: PoolTransaction> From<InsertErr<T>> for PoolError {
+    fn from(err: InsertErr<T>) -> Self {
+        match err {
+            InsertErr::Underpriced { existing, .. } => PoolError::ReplacementUnderpriced(existing),
+            InsertErr::ProtocolFeeCapTooLow { transaction, fee_cap } => {
+                PoolError::ProtocolFeeCapTooLow(*transaction.hash(), fee_cap)
+            }
+            InsertErr::ExceededSenderTransactionsCapacity { transaction } => {
+                PoolError::TooManyTransactions(transaction.sender(), *transaction.hash())
+            }
+            InsertErr::TxGasLimitMoreThanAvailableBlockGas {
+                transaction,
+                block_gas_limit,
+                tx_gas_limit,
+            } => PoolError::InvalidTransaction(
+                *transaction.hash(),
+                InvalidPoolTransactionError::ExceedsGasLimit(block_gas_limit, tx_gas_limit),
+            ),
+        }
+    }
+}
+
 /// Transaction was successfully inserted into the pool
 #[derive(Debug)]
 pub(crate) struct InsertOk<T: PoolTransaction> {
@@ -1068,6 +1292,10 @@ pub(crate) struct PoolInternalTransaction<T: PoolTransaction> {
     /// This is the combined `cost` of all transactions from the same sender that currently
     /// come before this transaction.
     pub(crate) cumulative_cost: U256,
+    /// When the transaction entered a non-pending sub-pool.
+    ///
+    /// This is used to evict transactions that are parked for too long.
+    pub(crate) queued_since: Instant,
 }
 
 // === impl PoolInternalTransaction ===
@@ -1076,6 +1304,21 @@ impl<T: PoolTransaction> PoolInternalTransaction<T> {
     fn next_cumulative_cost(&self) -> U256 {
         self.cumulative_cost + self.transaction.cost
     }
+
+    /// Resets the `queued_since` timestamp if the transaction was moved out of the pending pool.
+    fn on_subpool_change(&mut self, previous: SubPool) {
+        if previous.is_pending() && !self.subpool.is_pending() {
+            self.queued_since = Instant::now();
+        }
+    }
+
+    /// Returns `true` if the transaction has been parked in the `Queued` or `BaseFee` sub-pool for
+    /// longer than `max_idle`.
+    fn is_expired(&self, now: Instant, max_idle: Duration, expire_locals: bool) -> bool {
+        matches!(self.subpool, SubPool::Queued | SubPool::BaseFee) &&
+            (expire_locals || !self.transaction.is_local()) &&
+            now.saturating_duration_since(self.queued_since) >= max_idle
+    }
 }
 
 /// Tracks the result after updating the pool
@@ -1143,9 +1386,10 @@ impl SenderInfo {
 mod tests {
     use super::*;
     use crate::{
-        test_utils::{MockTransaction, MockTransactionFactory},
+        test_utils::{mock_tx_pool, MockTransaction, MockTransactionFactory},
         traits::TransactionOrigin,
     };
+    use std::collections::HashSet;
 
     #[test]
     fn test_simple_insert() {
@@ -1308,6 +1552,268 @@ mod tests {
         assert!(first_in_pool.state.contains(TxState::NO_NONCE_GAPS));
     }
 
+    #[test]
+    fn insert_blob() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+        pool.pending_basefee = 100;
+        pool.pending_blob_fee = 100;
+
+        let tx = MockTransaction::eip4844().with_max_fee(50).with_blob_fee(50);
+        let valid_tx = f.validated(tx);
+        let InsertOk { state, move_to, .. } =
+            pool.insert_tx(valid_tx.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        assert!(state.is_blob());
+        assert!(!state.has_enough_fee_cap());
+        assert!(!state.has_enough_blob_fee_cap());
+        assert_eq!(move_to, SubPool::BlobPool);
+
+        // only the dynamic fee requirement is satisfied
+        let updates = pool.update(50, 100, &StateDiff {});
+        assert!(updates.is_empty());
+        assert_eq!(pool.get(valid_tx.id()).unwrap().subpool, SubPool::BlobPool);
+
+        // only the blob fee requirement is satisfied
+        let updates = pool.update(100, 50, &StateDiff {});
+        assert!(updates.is_empty());
+        assert_eq!(pool.get(valid_tx.id()).unwrap().subpool, SubPool::BlobPool);
+    }
+
+    #[test]
+    fn promote_blob_to_pending() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+        pool.pending_basefee = 100;
+        pool.pending_blob_fee = 100;
+
+        let tx = MockTransaction::eip4844().with_max_fee(50).with_blob_fee(50);
+        let valid_tx = f.validated(tx);
+        let InsertOk { move_to, .. } =
+            pool.insert_tx(valid_tx.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        assert_eq!(move_to, SubPool::BlobPool);
+
+        // both fee caps are satisfied at the same time
+        let updates = pool.update(50, 50, &StateDiff {});
+        assert_eq!(updates.len(), 1);
+        let update = &updates[0];
+        assert_eq!(update.current, SubPool::BlobPool);
+        assert!(matches!(update.destination, Destination::Pool(SubPool::Pending)));
+
+        let inserted = pool.get(valid_tx.id()).unwrap();
+        assert_eq!(inserted.subpool, SubPool::Pending);
+        assert!(inserted.state.is_pending());
+    }
+
+    #[test]
+    fn drain_pending_by_tip() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = mock_tx_pool();
+
+        let a0 = MockTransaction::eip1559().with_max_fee(200).with_priority_fee(10);
+        let a1 = a0.next().with_priority_fee(100);
+        let b0 = MockTransaction::eip1559().with_max_fee(200).with_priority_fee(50);
+
+        for tx in [a1.clone(), a0.clone(), b0.clone()] {
+            pool.add_transaction(f.validated(tx), on_chain_balance, on_chain_nonce).unwrap();
+        }
+        assert_eq!(pool.pending().len(), 3);
+
+        let best = pool.drain_pending(usize::MAX);
+        let hashes = best.iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        // `a1` pays the highest tip but depends on `a0`
+        assert_eq!(hashes, vec![b0.get_hash(), a0.get_hash(), a1.get_hash()]);
+
+        // transactions remain in the pool
+        assert_eq!(pool.pending().len(), 3);
+
+        let best = pool.drain_pending(2);
+        assert_eq!(best.len(), 2);
+
+        // a transaction added afterwards is visible to the next call
+        let c0 = MockTransaction::eip1559().with_max_fee(200).with_priority_fee(200);
+        pool.add_transaction(f.validated(c0.clone()), on_chain_balance, on_chain_nonce).unwrap();
+        let best = pool.drain_pending(1);
+        assert_eq!(*best[0].hash(), c0.get_hash());
+    }
+
+    #[test]
+    fn get_transactions_by_sender() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = mock_tx_pool();
+
+        let a0 = MockTransaction::eip1559();
+        let a2 = a0.skip(1);
+        let b0 = MockTransaction::eip1559();
+
+        let a0 = f.validated(a0);
+        let sender = a0.sender_id();
+        let a0_hash = *a0.hash();
+        for tx in [f.validated(a2.clone()), a0, f.validated(b0)] {
+            pool.add_transaction(tx, on_chain_balance, on_chain_nonce).unwrap();
+        }
+        // `a0` is pending while `a2` is queued due to the nonce gap
+        assert_eq!(pool.pending().len(), 2);
+        assert_eq!(pool.queued().len(), 1);
+
+        let txs = pool.get_transactions_by_sender(sender);
+        let hashes = txs.iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(hashes, vec![a0_hash, a2.get_hash()]);
+
+        let pending = pool.get_pending_transactions_by_sender(sender);
+        let hashes = pending.iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(hashes, vec![a0_hash]);
+    }
+
+    #[test]
+    fn senders_iter() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = mock_tx_pool();
+
+        let a0 = MockTransaction::eip1559();
+        let a1 = a0.next();
+        let b0 = MockTransaction::eip1559();
+
+        let a0 = f.validated(a0);
+        let b0 = f.validated(b0);
+        let senders = HashSet::from([a0.sender_id(), b0.sender_id()]);
+        for tx in [a0, f.validated(a1), b0] {
+            pool.add_transaction(tx, on_chain_balance, on_chain_nonce).unwrap();
+        }
+
+        // every sender is returned once, regardless of its number of transactions
+        let pool_senders = pool.all().senders_iter().collect::<Vec<_>>();
+        assert_eq!(pool_senders.len(), 2);
+        assert_eq!(pool_senders.into_iter().collect::<HashSet<_>>(), senders);
+    }
+
+    #[test]
+    fn pending_and_queued_count() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = mock_tx_pool();
+        pool.all_transactions.pending_basefee = 50;
+
+        let pending = MockTransaction::eip1559().with_max_fee(100);
+        // nonce gap
+        let queued = pending.skip(1);
+        // below the pending base fee
+        let basefee = MockTransaction::eip1559();
+        for tx in [pending, queued, basefee] {
+            pool.add_transaction(f.validated(tx), on_chain_balance, on_chain_nonce).unwrap();
+        }
+        assert_eq!(pool.base_fee().len(), 1);
+        assert_eq!(pool.pending_count(), 1);
+        assert_eq!(pool.queued_count(), 2);
+    }
+
+    #[test]
+    fn expire_parked_transactions() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = mock_tx_pool();
+
+        let pending = MockTransaction::eip1559();
+        // nonce gap
+        let queued = pending.skip(1);
+        let local = MockTransaction::eip1559().skip(1);
+
+        pool.add_transaction(f.validated(pending), on_chain_balance, on_chain_nonce).unwrap();
+        pool.add_transaction(f.validated(queued.clone()), on_chain_balance, on_chain_nonce)
+            .unwrap();
+        pool.add_transaction(
+            f.validated_with_origin(TransactionOrigin::Local, local.clone()),
+            on_chain_balance,
+            on_chain_nonce,
+        )
+        .unwrap();
+        assert_eq!(pool.pending().len(), 1);
+        assert_eq!(pool.queued().len(), 2);
+
+        let max_idle = Duration::from_secs(pool.config.transaction_max_idle_seconds);
+
+        // nothing expired yet
+        assert!(pool.remove_expired(Instant::now()).is_empty());
+
+        let removed = pool.remove_expired(Instant::now() + max_idle);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(*removed[0].hash(), queued.get_hash());
+        assert_eq!(pool.pending().len(), 1);
+        assert_eq!(pool.queued().len(), 1);
+
+        // locals are only expired if configured
+        pool.config.expire_locals = true;
+        let removed = pool.remove_expired(Instant::now() + max_idle);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(*removed[0].hash(), local.get_hash());
+        assert_eq!(pool.queued().len(), 0);
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn replacement_price_bump() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+        assert_eq!(pool.price_bump, 10);
+
+        let tx = MockTransaction::eip1559().with_gas_price(100);
+        let first = f.validated(tx.clone());
+        pool.insert_tx(first, on_chain_balance, on_chain_nonce).unwrap();
+
+        // 9% bump is rejected
+        let replacement = f.validated(tx.clone().rng_hash().with_gas_price(109));
+        let err = pool.insert_tx(replacement, on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::Underpriced { .. }));
+
+        // bumping only one of the fee values is rejected
+        let replacement =
+            f.validated(tx.clone().rng_hash().with_gas_price(111).with_priority_fee(100));
+        let err = pool.insert_tx(replacement, on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::Underpriced { .. }));
+
+        // 11% bump succeeds
+        let replacement = f.validated(tx.rng_hash().with_gas_price(111));
+        let InsertOk { replaced_tx, .. } =
+            pool.insert_tx(replacement.clone(), on_chain_balance, on_chain_nonce).unwrap();
+        assert!(replaced_tx.is_some());
+        assert!(pool.contains(replacement.hash()));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn replacement_price_bump_blob() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+
+        let tx = MockTransaction::eip4844().with_gas_price(100).with_blob_fee(100);
+        pool.insert_tx(f.validated(tx.clone()), on_chain_balance, on_chain_nonce).unwrap();
+
+        // 9% blob fee bump is rejected
+        let replacement = f.validated(tx.clone().rng_hash().with_gas_price(111).with_blob_fee(109));
+        let err = pool.insert_tx(replacement, on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::Underpriced { .. }));
+
+        // 11% blob fee bump succeeds
+        let replacement = f.validated(tx.rng_hash().with_gas_price(111).with_blob_fee(111));
+        pool.insert_tx(replacement, on_chain_balance, on_chain_nonce).unwrap();
+        assert_eq!(pool.len(), 1);
+    }
+
     #[test]
     fn rejects_spammer() {
         let on_chain_balance = U256::from(1_000);
@@ -1331,6 +1837,36 @@ mod tests {
         assert!(matches!(err, InsertErr::ExceededSenderTransactionsCapacity { .. }));
     }
 
+    #[test]
+    fn allow_replacement_at_slot_limit() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+
+        let first = MockTransaction::eip1559().with_gas_price(100);
+        let mut tx = first.clone();
+        pool.insert_tx(f.validated(tx.clone()), on_chain_balance, on_chain_nonce).unwrap();
+        for _ in 1..pool.max_account_slots {
+            tx = tx.next();
+            pool.insert_tx(f.validated(tx.clone()), on_chain_balance, on_chain_nonce).unwrap();
+        }
+        let sender = f.ids.sender_id(&tx.get_sender()).unwrap();
+        assert_eq!(pool.max_account_slots, pool.tx_count(sender));
+
+        // a new nonce is rejected
+        let err =
+            pool.insert_tx(f.validated(tx.next()), on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::ExceededSenderTransactionsCapacity { .. }));
+
+        // replacing an existing nonce is still possible
+        let replacement = first.clone().rng_hash().with_gas_price(200);
+        let InsertOk { replaced_tx, .. } =
+            pool.insert_tx(f.validated(replacement), on_chain_balance, on_chain_nonce).unwrap();
+        assert_eq!(*replaced_tx.unwrap().0.hash(), first.get_hash());
+        assert_eq!(pool.max_account_slots, pool.tx_count(sender));
+    }
+
     #[test]
     fn allow_local_spamming() {
         let on_chain_balance = U256::from(1_000);
@@ -1362,6 +1898,31 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn check_tx_without_insert() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+
+        let tx = MockTransaction::eip1559().with_gas_price(100);
+        let first = f.validated(tx.clone());
+        assert!(pool.check_tx(first.clone()).is_ok());
+        assert!(pool.is_empty());
+        pool.insert_tx(first.clone(), on_chain_balance, on_chain_nonce).unwrap();
+
+        let replacement = f.validated(tx.clone().rng_hash().with_gas_price(109));
+        assert!(matches!(
+            pool.check_tx(replacement),
+            Err(PoolError::ReplacementUnderpriced(hash)) if hash == *first.hash()
+        ));
+
+        let replacement = f.validated(tx.rng_hash().with_gas_price(111));
+        assert!(pool.check_tx(replacement).is_ok());
+        assert!(pool.contains(first.hash()));
+        assert_eq!(pool.len(), 1);
+    }
+
     #[test]
     fn reject_tx_over_gas_limit() {
         let on_chain_balance = U256::from(1_000);
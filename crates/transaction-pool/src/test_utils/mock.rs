@@ -12,8 +12,9 @@ use rand::{
     prelude::Distribution,
 };
 use reth_primitives::{
-    Address, FromRecoveredTransaction, IntoRecoveredTransaction, Transaction, TransactionKind,
-    TransactionSignedEcRecovered, TxEip1559, TxHash, TxLegacy, TxType, H256, U128, U256,
+    Address, BlobTransaction, FromRecoveredTransaction, IntoRecoveredTransaction, Transaction,
+    TransactionKind, TransactionSignedEcRecovered, TxEip1559, TxHash, TxLegacy, TxType,
+    EIP4844_TX_TYPE_ID, H256, U128, U256,
 };
 use std::{ops::Range, sync::Arc, time::Instant};
 
@@ -37,6 +38,9 @@ macro_rules! set_value {
             MockTransaction::Eip1559 { ref mut $field, .. } => {
                 *$field = new_value;
             }
+            MockTransaction::Eip4844 { ref mut $field, .. } => {
+                *$field = new_value;
+            }
         }
     };
 }
@@ -47,6 +51,7 @@ macro_rules! get_value {
         match $this {
             MockTransaction::Legacy { $field, .. } => $field,
             MockTransaction::Eip1559 { $field, .. } => $field,
+            MockTransaction::Eip4844 { $field, .. } => $field,
         }
     };
 }
@@ -98,6 +103,18 @@ pub enum MockTransaction {
         to: TransactionKind,
         value: U256,
     },
+    Eip4844 {
+        hash: H256,
+        sender: Address,
+        nonce: u64,
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+        max_fee_per_blob_gas: u128,
+        blob_versioned_hashes: Vec<H256>,
+        gas_limit: u64,
+        to: TransactionKind,
+        value: U256,
+    },
 }
 
 // === impl MockTransaction ===
@@ -138,22 +155,66 @@ impl MockTransaction {
         }
     }
 
+    /// Returns a new EIP4844 transaction with random address and hash and empty values
+    pub fn eip4844() -> Self {
+        MockTransaction::Eip4844 {
+            hash: H256::random(),
+            sender: Address::random(),
+            nonce: 0,
+            max_fee_per_gas: MIN_PROTOCOL_BASE_FEE,
+            max_priority_fee_per_gas: MIN_PROTOCOL_BASE_FEE,
+            max_fee_per_blob_gas: 1,
+            blob_versioned_hashes: vec![H256::random()],
+            gas_limit: 0,
+            to: TransactionKind::Call(Address::random()),
+            value: Default::default(),
+        }
+    }
+
+    pub fn set_blob_fee(&mut self, val: u128) -> &mut Self {
+        if let MockTransaction::Eip4844 { max_fee_per_blob_gas, .. } = self {
+            *max_fee_per_blob_gas = val;
+        }
+        self
+    }
+
+    pub fn with_blob_fee(mut self, val: u128) -> Self {
+        if let MockTransaction::Eip4844 { ref mut max_fee_per_blob_gas, .. } = self {
+            *max_fee_per_blob_gas = val;
+        }
+        self
+    }
+
+    pub fn get_blob_fee(&self) -> Option<u128> {
+        if let MockTransaction::Eip4844 { max_fee_per_blob_gas, .. } = self {
+            Some(*max_fee_per_blob_gas)
+        } else {
+            None
+        }
+    }
+
     pub fn set_priority_fee(&mut self, val: u128) -> &mut Self {
-        if let MockTransaction::Eip1559 { max_priority_fee_per_gas, .. } = self {
+        if let MockTransaction::Eip1559 { max_priority_fee_per_gas, .. } |
+        MockTransaction::Eip4844 { max_priority_fee_per_gas, .. } = self
+        {
             *max_priority_fee_per_gas = val;
         }
         self
     }
 
     pub fn with_priority_fee(mut self, val: u128) -> Self {
-        if let MockTransaction::Eip1559 { ref mut max_priority_fee_per_gas, .. } = self {
+        if let MockTransaction::Eip1559 { ref mut max_priority_fee_per_gas, .. } |
+        MockTransaction::Eip4844 { ref mut max_priority_fee_per_gas, .. } = self
+        {
             *max_priority_fee_per_gas = val;
         }
         self
     }
 
     pub fn get_priority_fee(&self) -> Option<u128> {
-        if let MockTransaction::Eip1559 { max_priority_fee_per_gas, .. } = self {
+        if let MockTransaction::Eip1559 { max_priority_fee_per_gas, .. } |
+        MockTransaction::Eip4844 { max_priority_fee_per_gas, .. } = self
+        {
             Some(*max_priority_fee_per_gas)
         } else {
             None
@@ -161,21 +222,27 @@ impl MockTransaction {
     }
 
     pub fn set_max_fee(&mut self, val: u128) -> &mut Self {
-        if let MockTransaction::Eip1559 { max_fee_per_gas, .. } = self {
+        if let MockTransaction::Eip1559 { max_fee_per_gas, .. } |
+        MockTransaction::Eip4844 { max_fee_per_gas, .. } = self
+        {
             *max_fee_per_gas = val;
         }
         self
     }
 
     pub fn with_max_fee(mut self, val: u128) -> Self {
-        if let MockTransaction::Eip1559 { ref mut max_fee_per_gas, .. } = self {
+        if let MockTransaction::Eip1559 { ref mut max_fee_per_gas, .. } |
+        MockTransaction::Eip4844 { ref mut max_fee_per_gas, .. } = self
+        {
             *max_fee_per_gas = val;
         }
         self
     }
 
     pub fn get_max_fee(&self) -> Option<u128> {
-        if let MockTransaction::Eip1559 { max_fee_per_gas, .. } = self {
+        if let MockTransaction::Eip1559 { max_fee_per_gas, .. } |
+        MockTransaction::Eip4844 { max_fee_per_gas, .. } = self
+        {
             Some(*max_fee_per_gas)
         } else {
             None
@@ -187,7 +254,8 @@ impl MockTransaction {
             MockTransaction::Legacy { gas_price, .. } => {
                 *gas_price = val;
             }
-            MockTransaction::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas, .. } => {
+            MockTransaction::Eip1559 { max_fee_per_gas, max_priority_fee_per_gas, .. } |
+            MockTransaction::Eip4844 { max_fee_per_gas, max_priority_fee_per_gas, .. } => {
                 *max_fee_per_gas = val;
                 *max_priority_fee_per_gas = val;
             }
@@ -204,6 +272,11 @@ impl MockTransaction {
                 ref mut max_fee_per_gas,
                 ref mut max_priority_fee_per_gas,
                 ..
+            } |
+            MockTransaction::Eip4844 {
+                ref mut max_fee_per_gas,
+                ref mut max_priority_fee_per_gas,
+                ..
             } => {
                 *max_fee_per_gas = val;
                 *max_priority_fee_per_gas = val;
@@ -216,6 +289,7 @@ impl MockTransaction {
         match self {
             MockTransaction::Legacy { gas_price, .. } => *gas_price,
             MockTransaction::Eip1559 { max_fee_per_gas, .. } => *max_fee_per_gas,
+            MockTransaction::Eip4844 { max_fee_per_gas, .. } => *max_fee_per_gas,
         }
     }
 
@@ -276,6 +350,10 @@ impl MockTransaction {
     pub fn is_eip1559(&self) -> bool {
         matches!(self, MockTransaction::Eip1559 { .. })
     }
+
+    pub fn is_eip4844(&self) -> bool {
+        matches!(self, MockTransaction::Eip4844 { .. })
+    }
 }
 
 impl PoolTransaction for MockTransaction {
@@ -283,6 +361,7 @@ impl PoolTransaction for MockTransaction {
         match self {
             MockTransaction::Legacy { hash, .. } => hash,
             MockTransaction::Eip1559 { hash, .. } => hash,
+            MockTransaction::Eip4844 { hash, .. } => hash,
         }
     }
 
@@ -290,6 +369,7 @@ impl PoolTransaction for MockTransaction {
         match self {
             MockTransaction::Legacy { sender, .. } => *sender,
             MockTransaction::Eip1559 { sender, .. } => *sender,
+            MockTransaction::Eip4844 { sender, .. } => *sender,
         }
     }
 
@@ -297,6 +377,7 @@ impl PoolTransaction for MockTransaction {
         match self {
             MockTransaction::Legacy { nonce, .. } => *nonce,
             MockTransaction::Eip1559 { nonce, .. } => *nonce,
+            MockTransaction::Eip4844 { nonce, .. } => *nonce,
         }
     }
 
@@ -305,7 +386,8 @@ impl PoolTransaction for MockTransaction {
             MockTransaction::Legacy { gas_price, value, gas_limit, .. } => {
                 U256::from(*gas_limit) * U256::from(*gas_price) + *value
             }
-            MockTransaction::Eip1559 { max_fee_per_gas, value, gas_limit, .. } => {
+            MockTransaction::Eip1559 { max_fee_per_gas, value, gas_limit, .. } |
+            MockTransaction::Eip4844 { max_fee_per_gas, value, gas_limit, .. } => {
                 U256::from(*gas_limit) * U256::from(*max_fee_per_gas) + *value
             }
         }
@@ -323,22 +405,36 @@ impl PoolTransaction for MockTransaction {
         match self {
             MockTransaction::Legacy { .. } => None,
             MockTransaction::Eip1559 { max_fee_per_gas, .. } => Some(*max_fee_per_gas),
+            MockTransaction::Eip4844 { max_fee_per_gas, .. } => Some(*max_fee_per_gas),
         }
     }
 
     fn max_priority_fee_per_gas(&self) -> Option<u128> {
         match self {
             MockTransaction::Legacy { .. } => None,
-            MockTransaction::Eip1559 { max_priority_fee_per_gas, .. } => {
+            MockTransaction::Eip1559 { max_priority_fee_per_gas, .. } |
+            MockTransaction::Eip4844 { max_priority_fee_per_gas, .. } => {
                 Some(*max_priority_fee_per_gas)
             }
         }
     }
 
-    fn kind(&self) -> &TransactionKind {
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        self.get_blob_fee()
+    }
+
+    fn blob_versioned_hashes(&self) -> Option<&[H256]> {
+        match self {
+            MockTransaction::Eip4844 { blob_versioned_hashes, .. } => Some(blob_versioned_hashes),
+            _ => None,
+        }
+    }
+
+    fn kind(&self) -> TransactionKind {
         match self {
-            MockTransaction::Legacy { to, .. } => to,
-            MockTransaction::Eip1559 { to, .. } => to,
+            MockTransaction::Legacy { to, .. } => *to,
+            MockTransaction::Eip1559 { to, .. } => *to,
+            MockTransaction::Eip4844 { to, .. } => *to,
         }
     }
 
@@ -350,6 +446,7 @@ impl PoolTransaction for MockTransaction {
         match self {
             MockTransaction::Legacy { .. } => TxType::Legacy.into(),
             MockTransaction::Eip1559 { .. } => TxType::EIP1559.into(),
+            MockTransaction::Eip4844 { .. } => EIP4844_TX_TYPE_ID,
         }
     }
 
@@ -405,6 +502,28 @@ impl FromRecoveredTransaction for MockTransaction {
                 to,
                 value: U256::from(value),
             },
+            Transaction::Blob(BlobTransaction {
+                nonce,
+                gas_limit,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to,
+                value,
+                max_fee_per_blob_gas,
+                blob_versioned_hashes,
+                ..
+            }) => MockTransaction::Eip4844 {
+                hash,
+                sender,
+                nonce,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                max_fee_per_blob_gas,
+                blob_versioned_hashes,
+                gas_limit,
+                to: TransactionKind::Call(to),
+                value: U256::from(value),
+            },
             Transaction::Eip2930 { .. } => {
                 unimplemented!()
             }
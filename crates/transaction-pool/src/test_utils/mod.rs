@@ -9,6 +9,9 @@ use crate::{
 };
 use async_trait::async_trait;
 pub use mock::*;
+use reth_primitives::{
+    sign_message, Transaction, TransactionSigned, TransactionSignedEcRecovered, H256,
+};
 use std::{marker::PhantomData, sync::Arc};
 
 /// A [Pool] used for testing
@@ -19,6 +22,14 @@ pub fn testing_pool() -> TestPool {
     Pool::new(NoopTransactionValidator::default(), MockOrdering::default(), Default::default())
 }
 
+/// Signs the transaction with the given secret key and returns it with the recovered signer.
+pub fn sign_transaction(secret: H256, transaction: Transaction) -> TransactionSignedEcRecovered {
+    let signature = sign_message(secret, transaction.signature_hash()).unwrap();
+    TransactionSigned::from_transaction_and_signature(transaction, signature)
+        .into_ecrecovered()
+        .unwrap()
+}
+
 // A [`TransactionValidator`] that does nothing.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
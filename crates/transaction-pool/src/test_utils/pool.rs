@@ -26,7 +26,10 @@ pub struct MockPool<T: TransactionOrdering = MockOrdering> {
 impl MockPool {
     /// The total size of all subpools
     fn total_subpool_size(&self) -> usize {
-        self.pool.pending().len() + self.pool.base_fee().len() + self.pool.queued().len()
+        self.pool.pending().len() +
+            self.pool.base_fee().len() +
+            self.pool.queued().len() +
+            self.pool.blob().len()
     }
 
     /// Checks that all pool invariants hold.
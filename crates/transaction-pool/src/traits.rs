@@ -1,7 +1,8 @@
 use crate::{error::PoolResult, pool::state::SubPool, validate::ValidPoolTransaction};
 use reth_primitives::{
-    Address, FromRecoveredTransaction, IntoRecoveredTransaction, PeerId, Transaction,
-    TransactionKind, TransactionSignedEcRecovered, TxHash, H256, U256,
+    constants::EIP4844_DATA_GAS_PER_BLOB, Address, FromRecoveredTransaction,
+    IntoRecoveredTransaction, PeerId, Transaction, TransactionKind, TransactionSignedEcRecovered,
+    TxHash, H256, U256,
 };
 use reth_rlp::Encodable;
 use std::{collections::HashMap, fmt, sync::Arc};
@@ -27,6 +28,17 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Returns stats about the pool.
     fn status(&self) -> PoolSize;
 
+    /// Returns the number of transactions that are ready for inclusion in the next block.
+    ///
+    /// Consumer: RPC
+    fn pending_count(&self) -> usize;
+
+    /// Returns the number of transactions that are not executable yet, because of a nonce gap,
+    /// insufficient balance or a fee cap below the current base fee.
+    ///
+    /// Consumer: RPC
+    fn queued_count(&self) -> usize;
+
     /// Event listener for when a new block was mined.
     ///
     /// Implementers need to update the pool accordingly.
@@ -53,6 +65,16 @@ pub trait TransactionPool: Send + Sync + Clone {
         transaction: Self::Transaction,
     ) -> PoolResult<TxHash>;
 
+    /// Validates the given _unvalidated_ transaction against the current state and the pool, like
+    /// [`TransactionPool::add_transaction`], but without adding it to the pool.
+    ///
+    /// Consumer: RPC
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> PoolResult<TxHash>;
+
     /// Adds the given _unvalidated_ transaction into the pool.
     ///
     /// Returns a list of results.
@@ -93,6 +115,19 @@ pub trait TransactionPool: Send + Sync + Clone {
         &self,
     ) -> Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<Self::Transaction>>>>;
 
+    /// Returns up to `max_count` transactions that are ready for block production, ordered by
+    /// their effective miner tip (`min(maxFeePerGas - baseFee, maxPriorityFeePerGas)`) for the
+    /// pending block.
+    ///
+    /// Transactions of the same sender are always returned in nonce order.
+    ///
+    /// Note: Despite its name this does _not_ remove the returned transactions from the pool, the
+    /// block builder may speculatively include them. Transactions that are added concurrently are
+    /// visible to subsequent calls.
+    ///
+    /// Consumer: Block production
+    fn drain_pending(&self, max_count: usize) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Removes all transactions corresponding to the given hashes.
     ///
     /// Also removes all dependent transactions.
@@ -128,6 +163,28 @@ pub trait TransactionPool: Send + Sync + Clone {
         txs: impl IntoIterator<Item = TxHash>,
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns all transactions sent by the given address, sorted by ascending nonce.
+    ///
+    /// This includes transactions of all sub-pools, pending as well as parked.
+    fn get_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
+    /// Returns all transactions sent by the given address that are in the pending sub-pool,
+    /// sorted by ascending nonce.
+    ///
+    /// Consumer: RPC
+    fn get_pending_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
+    /// Returns the addresses of all senders that currently have transactions in the pool.
+    ///
+    /// Consumer: RPC
+    fn all_senders(&self) -> Vec<Address>;
+
     /// Notify the pool about transactions that are propagated to peers.
     ///
     /// Consumer: P2P
@@ -219,6 +276,8 @@ pub struct OnNewBlockEvent {
     ///
     /// The base fee of a block depends on the utilization of the last block and its base fee.
     pub pending_block_base_fee: u128,
+    /// EIP-4844 blob base fee of the _next_ (pending) block
+    pub pending_block_blob_fee: u128,
     /// Provides a set of state changes that affected the accounts.
     pub state_changes: StateDiff,
     /// All mined transactions in the block
@@ -285,9 +344,24 @@ pub trait PoolTransaction:
     /// This will return `None` for non-EIP1559 transactions
     fn max_priority_fee_per_gas(&self) -> Option<u128>;
 
+    /// Returns the EIP-4844 max fee per blob gas the caller is willing to pay.
+    ///
+    /// This will return `None` for non-EIP4844 transactions
+    fn max_fee_per_blob_gas(&self) -> Option<u128>;
+
+    /// Returns the versioned hashes of the blobs of the transaction.
+    ///
+    /// This will return `None` for non-EIP4844 transactions
+    fn blob_versioned_hashes(&self) -> Option<&[H256]>;
+
+    /// Returns `true` if this is an EIP-4844 blob transaction.
+    fn is_blob(&self) -> bool {
+        self.max_fee_per_blob_gas().is_some()
+    }
+
     /// Returns the transaction's [`TransactionKind`], which is the address of the recipient or
     /// [`TransactionKind::Create`] if the transaction is a contract creation.
-    fn kind(&self) -> &TransactionKind;
+    fn kind(&self) -> TransactionKind;
 
     /// Returns a measurement of the heap usage of this type and all its internals.
     fn size(&self) -> usize;
@@ -368,6 +442,7 @@ impl PoolTransaction for PooledTransaction {
             Transaction::Legacy(_) => None,
             Transaction::Eip2930(_) => None,
             Transaction::Eip1559(tx) => Some(tx.max_fee_per_gas),
+            Transaction::Blob(tx) => Some(tx.max_fee_per_gas),
         }
     }
 
@@ -379,12 +454,33 @@ impl PoolTransaction for PooledTransaction {
             Transaction::Legacy(_) => None,
             Transaction::Eip2930(_) => None,
             Transaction::Eip1559(tx) => Some(tx.max_priority_fee_per_gas),
+            Transaction::Blob(tx) => Some(tx.max_priority_fee_per_gas),
+        }
+    }
+
+    /// Returns the EIP-4844 max fee per blob gas the caller is willing to pay.
+    ///
+    /// This will return `None` for non-EIP4844 transactions
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        match &self.transaction.transaction {
+            Transaction::Blob(tx) => Some(tx.max_fee_per_blob_gas),
+            _ => None,
+        }
+    }
+
+    /// Returns the versioned hashes of the blobs of the transaction.
+    ///
+    /// This will return `None` for non-EIP4844 transactions
+    fn blob_versioned_hashes(&self) -> Option<&[H256]> {
+        match &self.transaction.transaction {
+            Transaction::Blob(tx) => Some(&tx.blob_versioned_hashes),
+            _ => None,
         }
     }
 
     /// Returns the transaction's [`TransactionKind`], which is the address of the recipient or
     /// [`TransactionKind::Create`] if the transaction is a contract creation.
-    fn kind(&self) -> &TransactionKind {
+    fn kind(&self) -> TransactionKind {
         self.transaction.kind()
     }
 
@@ -428,6 +524,15 @@ impl FromRecoveredTransaction for PooledTransaction {
                 let effective_gas_price = t.max_priority_fee_per_gas;
                 (cost, effective_gas_price)
             }
+            Transaction::Blob(t) => {
+                let blob_gas =
+                    U256::from(t.blob_versioned_hashes.len() as u64 * EIP4844_DATA_GAS_PER_BLOB);
+                let cost = U256::from(t.max_fee_per_gas) * U256::from(t.gas_limit) +
+                    U256::from(t.max_fee_per_blob_gas) * blob_gas +
+                    U256::from(t.value);
+                let effective_gas_price = t.max_priority_fee_per_gas;
+                (cost, effective_gas_price)
+            }
         };
 
         PooledTransaction { transaction: tx, cost, effective_gas_price }
@@ -455,4 +560,8 @@ pub struct PoolSize {
     pub queued: usize,
     /// Reported size of transactions in the _queued_ sub-pool.
     pub queued_size: usize,
+    /// Number of transactions in the _blob_ sub-pool.
+    pub blob: usize,
+    /// Reported size of transactions in the _blob_ sub-pool.
+    pub blob_size: usize,
 }
@@ -7,12 +7,12 @@ use crate::{
     MAX_INIT_CODE_SIZE, TX_MAX_SIZE,
 };
 use reth_primitives::{
-    Address, ChainSpec, IntoRecoveredTransaction, InvalidTransactionError, TransactionKind,
-    TransactionSignedEcRecovered, TxHash, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID,
-    LEGACY_TX_TYPE_ID, U256,
+    Address, ChainSpec, Hardfork, IntoRecoveredTransaction, InvalidTransactionError,
+    TransactionKind, TransactionSignedEcRecovered, TxHash, EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID,
+    EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID, U256,
 };
-use reth_provider::AccountProvider;
-use std::{fmt, sync::Arc, time::Instant};
+use reth_provider::{AccountProvider, BlockIdProvider, HeaderProvider};
+use std::{fmt, marker::PhantomData, sync::Arc, time::Instant};
 
 /// A Result type returned after checking a transaction's validity.
 #[derive(Debug)]
@@ -70,7 +70,7 @@ pub trait TransactionValidator: Send + Sync {
         transaction: Self::Transaction,
         max_init_code_size: usize,
     ) -> Result<(), InvalidPoolTransactionError> {
-        if *transaction.kind() == TransactionKind::Create && transaction.size() > max_init_code_size
+        if transaction.kind() == TransactionKind::Create && transaction.size() > max_init_code_size
         {
             Err(InvalidPoolTransactionError::ExceedsMaxInitCodeSize(
                 transaction.size(),
@@ -84,7 +84,7 @@ pub trait TransactionValidator: Send + Sync {
 
 /// A [TransactionValidator] implementation that validates ethereum transaction.
 #[derive(Debug, Clone)]
-pub struct EthTransactionValidator<Client> {
+pub struct EthTransactionValidator<Client, T> {
     /// Spec of the chain
     chain_spec: Arc<ChainSpec>,
     /// This type fetches account info from the db
@@ -99,11 +99,13 @@ pub struct EthTransactionValidator<Client> {
     current_max_gas_limit: u64,
     /// Current base fee.
     base_fee: Option<u128>,
+    /// Marker for the transaction type
+    _marker: PhantomData<T>,
 }
 
 // === impl EthTransactionValidator ===
 
-impl<Client> EthTransactionValidator<Client> {
+impl<Client, T> EthTransactionValidator<Client, T> {
     /// Creates a new instance for the given [ChainSpec]
     pub fn new(client: Client, chain_spec: Arc<ChainSpec>) -> Self {
         // TODO(mattsse): improve these settings by checking against hardfork
@@ -116,6 +118,7 @@ impl<Client> EthTransactionValidator<Client> {
             eip1559: true,
             current_max_gas_limit: 30_000_000,
             base_fee: None,
+            _marker: Default::default(),
         }
     }
 
@@ -125,9 +128,23 @@ impl<Client> EthTransactionValidator<Client> {
     }
 }
 
+impl<Client, T> EthTransactionValidator<Client, T>
+where
+    Client: BlockIdProvider + HeaderProvider,
+{
+    /// Returns whether EIP-4844 is active at the timestamp of the current head of the chain.
+    fn is_eip4844_active_at_head(&self) -> reth_interfaces::Result<bool> {
+        let best_hash = self.client.chain_info()?.best_hash;
+        let timestamp = self.client.header(&best_hash)?.map_or(0, |header| header.timestamp);
+        Ok(self.chain_spec.fork(Hardfork::Cancun).active_at_timestamp(timestamp))
+    }
+}
+
 #[async_trait::async_trait]
-impl<T: PoolTransaction + AccountProvider + Clone> TransactionValidator
-    for EthTransactionValidator<T>
+impl<Client, T> TransactionValidator for EthTransactionValidator<Client, T>
+where
+    Client: AccountProvider + BlockIdProvider + HeaderProvider,
+    T: PoolTransaction + Clone,
 {
     type Transaction = T;
 
@@ -147,7 +164,7 @@ impl<T: PoolTransaction + AccountProvider + Clone> TransactionValidator
                 if !self.eip2718 {
                     return TransactionValidationOutcome::Invalid(
                         transaction,
-                        InvalidTransactionError::Eip1559Disabled.into(),
+                        InvalidTransactionError::Eip2930Disabled.into(),
                     )
                 }
             }
@@ -162,6 +179,30 @@ impl<T: PoolTransaction + AccountProvider + Clone> TransactionValidator
                 }
             }
 
+            EIP4844_TX_TYPE_ID => {
+                // Reject blob transactions until EIP-4844 activates at the head of the chain.
+                match self.is_eip4844_active_at_head() {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        return TransactionValidationOutcome::Invalid(
+                            transaction,
+                            InvalidTransactionError::Eip4844Disabled.into(),
+                        )
+                    }
+                    Err(err) => {
+                        return TransactionValidationOutcome::Error(transaction, Box::new(err))
+                    }
+                }
+
+                // A blob transaction has to carry at least one blob.
+                if transaction.blob_versioned_hashes().map_or(true, |hashes| hashes.is_empty()) {
+                    return TransactionValidationOutcome::Invalid(
+                        transaction,
+                        InvalidTransactionError::BlobVersionedHashesEmpty.into(),
+                    )
+                }
+            }
+
             _ => {
                 return TransactionValidationOutcome::Invalid(
                     transaction,
@@ -199,15 +240,27 @@ impl<T: PoolTransaction + AccountProvider + Clone> TransactionValidator
         }
 
         // Ensure max_fee_per_gas is greater than or equal to max_priority_fee_per_gas.
-        if transaction.max_fee_per_gas() <= transaction.max_priority_fee_per_gas() {
-            return TransactionValidationOutcome::Invalid(
-                transaction,
-                InvalidTransactionError::TipAboveFeeCap.into(),
-            )
+        //
+        // Legacy and EIP-2930 transactions only have a `gas_price`, which is both their fee cap
+        // and their tip.
+        if let (Some(max_fee), Some(max_priority_fee)) =
+            (transaction.max_fee_per_gas(), transaction.max_priority_fee_per_gas())
+        {
+            if max_fee < max_priority_fee {
+                return TransactionValidationOutcome::Invalid(
+                    transaction,
+                    InvalidTransactionError::TipAboveFeeCap.into(),
+                )
+            }
         }
 
+        // The fee cap of the transaction, this is the `gas_price` for legacy and EIP-2930
+        // transactions.
+        let fee_cap =
+            transaction.max_fee_per_gas().unwrap_or_else(|| transaction.effective_gas_price());
+
         // Drop non-local transactions under our own minimal accepted gas price or tip
-        if !origin.is_local() && transaction.max_fee_per_gas() < self.base_fee {
+        if !origin.is_local() && self.base_fee.map_or(false, |base_fee| fee_cap < base_fee) {
             return TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidTransactionError::MaxFeeLessThenBaseFee.into(),
@@ -258,11 +311,10 @@ impl<T: PoolTransaction + AccountProvider + Clone> TransactionValidator
 
         // Checks for max cost
         if transaction.cost() > account.balance {
-            let max_fee = transaction.max_fee_per_gas().unwrap_or_default();
             return TransactionValidationOutcome::Invalid(
                 transaction,
                 InvalidTransactionError::InsufficientFunds {
-                    max_fee,
+                    max_fee: fee_cap,
                     available_funds: account.balance,
                 }
                 .into(),
@@ -334,14 +386,65 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
         self.transaction.max_fee_per_gas()
     }
 
+    /// Returns the EIP-1559 Priority fee the caller is paying to the block author.
+    pub fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        self.transaction.max_priority_fee_per_gas()
+    }
+
+    /// Returns the effective tip per gas the block author receives for this transaction given
+    /// the `base_fee`: `min(maxFeePerGas - baseFee, maxPriorityFeePerGas)`.
+    ///
+    /// For legacy transactions this is `gasPrice - baseFee`.
+    ///
+    /// Returns `None` if the transaction's fee cap is below the `base_fee`.
+    pub fn effective_tip_per_gas(&self, base_fee: u128) -> Option<u128> {
+        match self.max_fee_per_gas() {
+            Some(max_fee) => {
+                let tip = max_fee.checked_sub(base_fee)?;
+                Some(self.max_priority_fee_per_gas().map_or(tip, |priority| tip.min(priority)))
+            }
+            None => self.transaction.effective_gas_price().checked_sub(base_fee),
+        }
+    }
+
+    /// Returns the EIP-4844 max fee per blob gas the caller is willing to pay.
+    pub fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        self.transaction.max_fee_per_blob_gas()
+    }
+
     /// Amount of gas that should be used in executing this transaction. This is paid up-front.
     pub fn gas_limit(&self) -> u64 {
         self.transaction.gas_limit()
     }
 
     /// Returns true if this transaction is underpriced compared to the other.
-    pub(crate) fn is_underpriced(&self, other: &Self) -> bool {
-        self.transaction.effective_gas_price() <= other.transaction.effective_gas_price()
+    ///
+    /// A replacement transaction must increase _all_ of its fee values by at least `price_bump`
+    /// percent: `maxFeePerGas`, `maxPriorityFeePerGas` and `maxFeePerBlobGas` if the replaced
+    /// transaction is a blob transaction. For legacy transactions the `gasPrice` is used for both
+    /// `maxFeePerGas` and `maxPriorityFeePerGas`.
+    pub(crate) fn is_underpriced(&self, other: &Self, price_bump: u64) -> bool {
+        let max_fee = |tx: &Self| {
+            tx.max_fee_per_gas().unwrap_or_else(|| tx.transaction.effective_gas_price())
+        };
+        let priority_fee = |tx: &Self| {
+            tx.max_priority_fee_per_gas().unwrap_or_else(|| tx.transaction.effective_gas_price())
+        };
+
+        if !is_price_bumped(max_fee(self), max_fee(other), price_bump) ||
+            !is_price_bumped(priority_fee(self), priority_fee(other), price_bump)
+        {
+            return true
+        }
+
+        if let Some(replaced_blob_fee) = other.max_fee_per_blob_gas() {
+            match self.max_fee_per_blob_gas() {
+                Some(blob_fee) if is_price_bumped(blob_fee, replaced_blob_fee, price_bump) => {}
+                _ => return true,
+            }
+        }
+
+        false
     }
 
     /// Whether the transaction originated locally.
@@ -355,6 +458,12 @@ impl<T: PoolTransaction> ValidPoolTransaction<T> {
     }
 }
 
+/// Returns `true` if the `new` fee is higher than the `old` fee by at least `price_bump` percent.
+#[inline]
+fn is_price_bumped(new: u128, old: u128, price_bump: u64) -> bool {
+    new > old && new.saturating_mul(100) >= old.saturating_mul(100 + price_bump as u128)
+}
+
 impl<T: PoolTransaction> IntoRecoveredTransaction for ValidPoolTransaction<T> {
     fn to_recovered_transaction(&self) -> TransactionSignedEcRecovered {
         self.transaction.to_recovered_transaction()
@@ -386,3 +495,202 @@ impl<T: PoolTransaction> fmt::Debug for ValidPoolTransaction<T> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ordering::CostOrdering, test_utils::sign_transaction, traits::PooledTransaction, Pool,
+        TransactionPool,
+    };
+    use reth_primitives::{
+        hex_literal::hex, AccessList, AccessListItem, BlobTransaction, ChainSpec, ChainSpecBuilder,
+        ForkCondition, FromRecoveredTransaction, Header, Transaction, TxEip1559, TxEip2930, H256,
+        MAINNET,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    /// The private key `0x01`.
+    fn secret() -> H256 {
+        H256::from_low_u64_be(1)
+    }
+
+    /// The address of the private key `0x01`.
+    fn sender() -> Address {
+        Address::from(hex!("7e5f4552091a69125d5dfcb7b8c2659029395bdf"))
+    }
+
+    /// Returns a validator for the given chain, with a funded account for [sender].
+    fn funded_validator(
+        chain_spec: ChainSpec,
+    ) -> EthTransactionValidator<MockEthProvider, PooledTransaction> {
+        let client = MockEthProvider::default();
+        client.add_account(sender(), ExtendedAccount::new(0, U256::from(10u64.pow(18))));
+        EthTransactionValidator::new(client, Arc::new(chain_spec))
+    }
+
+    /// Signs the transaction with [secret] and validates it as an external transaction.
+    async fn validate(
+        validator: &EthTransactionValidator<MockEthProvider, PooledTransaction>,
+        transaction: Transaction,
+    ) -> TransactionValidationOutcome<PooledTransaction> {
+        let recovered = sign_transaction(secret(), transaction);
+        validator
+            .validate_transaction(
+                TransactionOrigin::External,
+                PooledTransaction::from_recovered_transaction(recovered),
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn add_eip2930_transaction() {
+        let validator = funded_validator(MAINNET.clone());
+        let pool = Pool::new(validator, CostOrdering::default(), Default::default());
+
+        let transaction = Transaction::Eip2930(TxEip2930 {
+            chain_id: 1,
+            nonce: 0,
+            gas_price: 1_000_000_000,
+            gas_limit: 30_000,
+            to: TransactionKind::Call(Address::random()),
+            value: 1,
+            input: Default::default(),
+            access_list: AccessList(vec![AccessListItem {
+                address: Address::random(),
+                storage_keys: vec![H256::zero()],
+            }]),
+        });
+        let recovered = sign_transaction(secret(), transaction);
+        assert_eq!(recovered.signer(), sender());
+
+        let hash = pool
+            .add_transaction(
+                TransactionOrigin::External,
+                PooledTransaction::from_recovered_transaction(recovered),
+            )
+            .await
+            .unwrap();
+        assert_eq!(pool.pending_count(), 1);
+        assert!(pool.best_transactions().any(|tx| *tx.hash() == hash));
+    }
+
+    #[tokio::test]
+    async fn reject_eip2930_transaction_with_other_chain_id() {
+        let validator = funded_validator(MAINNET.clone());
+
+        let transaction = Transaction::Eip2930(TxEip2930 {
+            chain_id: 5,
+            gas_price: 1_000_000_000,
+            gas_limit: 21_000,
+            to: TransactionKind::Call(Address::random()),
+            ..Default::default()
+        });
+
+        let outcome = validate(&validator, transaction).await;
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Consensus(InvalidTransactionError::ChainIdMismatch)
+            )
+        ));
+    }
+
+    #[tokio::test]
+    async fn accept_eip1559_transaction_with_tip_equal_to_fee_cap() {
+        let validator = funded_validator(MAINNET.clone());
+
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            gas_limit: 21_000,
+            to: TransactionKind::Call(Address::random()),
+            ..Default::default()
+        });
+
+        let outcome = validate(&validator, transaction).await;
+        assert!(matches!(outcome, TransactionValidationOutcome::Valid { .. }));
+    }
+
+    #[tokio::test]
+    async fn reject_eip1559_transaction_with_tip_above_fee_cap() {
+        let validator = funded_validator(MAINNET.clone());
+
+        let transaction = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            max_fee_per_gas: 1_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_001,
+            gas_limit: 21_000,
+            to: TransactionKind::Call(Address::random()),
+            ..Default::default()
+        });
+
+        let outcome = validate(&validator, transaction).await;
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Consensus(InvalidTransactionError::TipAboveFeeCap)
+            )
+        ));
+    }
+
+    #[tokio::test]
+    async fn reject_blob_transaction_before_cancun_at_head() {
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .shanghai_activated()
+            .with_fork(Hardfork::Cancun, ForkCondition::Timestamp(1_000))
+            .build();
+        let validator = funded_validator(chain_spec);
+        let head = Header { timestamp: 999, ..Default::default() };
+        validator.client.add_header(H256::random(), head);
+
+        let transaction = Transaction::Blob(BlobTransaction {
+            chain_id: 1,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            gas_limit: 21_000,
+            to: Address::random(),
+            blob_versioned_hashes: vec![H256::random()],
+            ..Default::default()
+        });
+
+        let outcome = validate(&validator, transaction).await;
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Consensus(InvalidTransactionError::Eip4844Disabled)
+            )
+        ));
+    }
+
+    #[tokio::test]
+    async fn reject_blob_transaction_without_blobs() {
+        let validator = funded_validator(ChainSpecBuilder::mainnet().cancun_activated().build());
+        validator.client.add_header(H256::random(), Header::default());
+
+        let transaction = Transaction::Blob(BlobTransaction {
+            chain_id: 1,
+            max_fee_per_gas: 2_000_000_000,
+            max_priority_fee_per_gas: 1_000_000_000,
+            gas_limit: 21_000,
+            to: Address::random(),
+            blob_versioned_hashes: vec![],
+            ..Default::default()
+        });
+
+        let outcome = validate(&validator, transaction).await;
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Consensus(
+                    InvalidTransactionError::BlobVersionedHashesEmpty
+                )
+            )
+        ));
+    }
+}